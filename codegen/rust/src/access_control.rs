@@ -2,9 +2,15 @@
 //
 // Purely computational access control checks with combinators
 // for composing access decisions (or_if, and_if).
+//
+// `evaluate` applies attribute-based rules ("owner may edit", "editors in
+// the same group may comment") to the subject, action, resource and
+// environment attributes of a request. Forbid rules win over allow rules,
+// and the decision names the rule that produced it for auditing.
 
 use crate::storage::{ConceptStorage, StorageResult};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 // --- Check ---
 
@@ -52,6 +58,166 @@ pub enum AndIfOutput {
     Ok { result: String },
 }
 
+// --- Evaluate ---
+
+/// Whether a matching rule grants or forbids the action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Effect {
+    Allow,
+    Forbid,
+}
+
+/// A test over request attributes. Attributes are dotted paths rooted at
+/// `subject`, `action`, `resource` or `environment`, e.g. `resource.owner_id`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum Condition {
+    /// Always holds.
+    Always,
+    /// The attribute equals a literal value.
+    Equals {
+        attribute: String,
+        value: Value,
+    },
+    /// Two attributes are present and equal, e.g. subject id and owner id.
+    SameAs {
+        attribute: String,
+        other: String,
+    },
+    /// The array at `collection` contains the value of `attribute`, or
+    /// shares an element with it when both are arrays.
+    Includes {
+        collection: String,
+        attribute: String,
+    },
+    All {
+        conditions: Vec<Condition>,
+    },
+    Any {
+        conditions: Vec<Condition>,
+    },
+    Not {
+        condition: Box<Condition>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccessRule {
+    pub id: String,
+    pub effect: Effect,
+    /// Actions the rule applies to; `*` applies to every action.
+    pub actions: Vec<String>,
+    pub condition: Condition,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AccessRequest {
+    #[serde(default)]
+    pub subject: Value,
+    pub action: String,
+    #[serde(default)]
+    pub resource: Value,
+    #[serde(default)]
+    pub environment: Value,
+}
+
+impl AccessRequest {
+    /// Resolve a dotted attribute path against the subject, resource or
+    /// environment attributes.
+    pub fn attribute(&self, path: &str) -> Option<&Value> {
+        let mut segments = path.split('.');
+        let root = match segments.next()? {
+            "subject" => &self.subject,
+            "resource" => &self.resource,
+            "environment" => &self.environment,
+            _ => return None,
+        };
+        segments.try_fold(root, |value, key| match value {
+            Value::Object(map) => map.get(key),
+            Value::Array(items) => key.parse::<usize>().ok().and_then(|i| items.get(i)),
+            _ => None,
+        })
+    }
+}
+
+impl Condition {
+    pub fn holds(&self, request: &AccessRequest) -> bool {
+        let attr = |path: &str| {
+            if path == "action" {
+                Some(Value::String(request.action.clone()))
+            } else {
+                request.attribute(path).filter(|v| !v.is_null()).cloned()
+            }
+        };
+        match self {
+            Condition::Always => true,
+            Condition::Equals { attribute, value } => attr(attribute).as_ref() == Some(value),
+            Condition::SameAs { attribute, other } => match (attr(attribute), attr(other)) {
+                (Some(a), Some(b)) => a == b,
+                _ => false,
+            },
+            Condition::Includes {
+                collection,
+                attribute,
+            } => match (attr(collection), attr(attribute)) {
+                (Some(Value::Array(items)), Some(Value::Array(wanted))) => {
+                    wanted.iter().any(|w| items.contains(w))
+                }
+                (Some(Value::Array(items)), Some(wanted)) => items.contains(&wanted),
+                _ => false,
+            },
+            Condition::All { conditions } => conditions.iter().all(|c| c.holds(request)),
+            Condition::Any { conditions } => conditions.iter().any(|c| c.holds(request)),
+            Condition::Not { condition } => !condition.holds(request),
+        }
+    }
+}
+
+impl AccessRule {
+    pub fn applies_to(&self, request: &AccessRequest) -> bool {
+        self.actions
+            .iter()
+            .any(|action| action == "*" || *action == request.action)
+            && self.condition.holds(request)
+    }
+}
+
+/// Evaluate `rules` in order. The first matching forbid rule wins;
+/// otherwise the first matching allow rule; otherwise the result is
+/// "neutral" with no matched rule.
+pub fn evaluate_rules(rules: &[AccessRule], request: &AccessRequest) -> (String, Option<String>) {
+    let mut allowed_by = None;
+    for rule in rules.iter().filter(|rule| rule.applies_to(request)) {
+        match rule.effect {
+            Effect::Forbid => return ("forbidden".to_string(), Some(rule.id.clone())),
+            Effect::Allow => {
+                allowed_by.get_or_insert_with(|| rule.id.clone());
+            }
+        }
+    }
+    match allowed_by {
+        Some(id) => ("allowed".to_string(), Some(id)),
+        None => ("neutral".to_string(), None),
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvaluateInput {
+    pub rules: Vec<AccessRule>,
+    pub request: AccessRequest,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "variant")]
+pub enum EvaluateOutput {
+    #[serde(rename = "ok")]
+    Ok {
+        result: String,
+        matched_rule: Option<String>,
+    },
+}
+
 pub struct AccessControlHandler;
 
 impl AccessControlHandler {
@@ -117,6 +283,18 @@ impl AccessControlHandler {
             result: result.to_string(),
         })
     }
+
+    pub async fn evaluate(
+        &self,
+        input: EvaluateInput,
+        _storage: &dyn ConceptStorage,
+    ) -> StorageResult<EvaluateOutput> {
+        let (result, matched_rule) = evaluate_rules(&input.rules, &input.request);
+        Ok(EvaluateOutput::Ok {
+            result,
+            matched_rule,
+        })
+    }
 }
 
 // ── Tests ──────────────────────────────────────────────────
@@ -307,4 +485,113 @@ mod tests {
             AndIfOutput::Ok { result } => assert_eq!(result, "neutral"),
         }
     }
+
+    // --- evaluate ---
+
+    fn document_policy() -> Vec<AccessRule> {
+        vec![
+            AccessRule {
+                id: "owner-may-edit".into(),
+                effect: Effect::Allow,
+                actions: vec!["edit".into(), "delete".into()],
+                condition: Condition::SameAs {
+                    attribute: "subject.id".into(),
+                    other: "resource.owner_id".into(),
+                },
+            },
+            AccessRule {
+                id: "group-editors-may-comment".into(),
+                effect: Effect::Allow,
+                actions: vec!["comment".into()],
+                condition: Condition::All {
+                    conditions: vec![
+                        Condition::Includes {
+                            collection: "subject.roles".into(),
+                            attribute: "environment.editor_role".into(),
+                        },
+                        Condition::Includes {
+                            collection: "subject.groups".into(),
+                            attribute: "resource.group".into(),
+                        },
+                    ],
+                },
+            },
+            AccessRule {
+                id: "only-owner-may-delete".into(),
+                effect: Effect::Forbid,
+                actions: vec!["delete".into()],
+                condition: Condition::Not {
+                    condition: Box::new(Condition::SameAs {
+                        attribute: "subject.id".into(),
+                        other: "resource.owner_id".into(),
+                    }),
+                },
+            },
+        ]
+    }
+
+    async fn decide(subject: Value, action: &str) -> (String, Option<String>) {
+        let storage = InMemoryStorage::new();
+        let handler = AccessControlHandler;
+
+        let result = handler
+            .evaluate(
+                EvaluateInput {
+                    rules: document_policy(),
+                    request: AccessRequest {
+                        subject,
+                        action: action.into(),
+                        resource: serde_json::json!({ "owner_id": "alice", "group": "newsroom" }),
+                        environment: serde_json::json!({ "editor_role": "editor" }),
+                    },
+                },
+                &storage,
+            )
+            .await
+            .unwrap();
+
+        match result {
+            EvaluateOutput::Ok {
+                result,
+                matched_rule,
+            } => (result, matched_rule),
+        }
+    }
+
+    #[tokio::test]
+    async fn evaluate_allows_owner() {
+        let (result, rule) = decide(serde_json::json!({ "id": "alice" }), "edit").await;
+        assert_eq!(result, "allowed");
+        assert_eq!(rule.as_deref(), Some("owner-may-edit"));
+    }
+
+    #[tokio::test]
+    async fn evaluate_denies_non_owner() {
+        let subject = serde_json::json!({ "id": "bob", "roles": ["editor"], "groups": ["sports"] });
+
+        let (result, rule) = decide(subject.clone(), "edit").await;
+        assert_eq!(result, "neutral");
+        assert_eq!(rule, None);
+
+        let (result, rule) = decide(subject, "delete").await;
+        assert_eq!(result, "forbidden");
+        assert_eq!(rule.as_deref(), Some("only-owner-may-delete"));
+    }
+
+    #[tokio::test]
+    async fn evaluate_allows_editor_in_same_group() {
+        let editor =
+            serde_json::json!({ "id": "carol", "roles": ["editor"], "groups": ["newsroom"] });
+        let (result, rule) = decide(editor, "comment").await;
+        assert_eq!(result, "allowed");
+        assert_eq!(rule.as_deref(), Some("group-editors-may-comment"));
+
+        let other_group =
+            serde_json::json!({ "id": "dan", "roles": ["editor"], "groups": ["sports"] });
+        assert_eq!(decide(other_group, "comment").await.0, "neutral");
+
+        let viewer =
+            serde_json::json!({ "id": "erin", "roles": ["viewer"], "groups": ["newsroom"] });
+        assert_eq!(decide(viewer, "comment").await.0, "neutral");
+    }
 }
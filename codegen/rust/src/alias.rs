@@ -1,7 +1,8 @@
 // Alias Concept Implementation (Rust)
 //
 // Named aliases for entities — add, remove, and resolve aliases
-// to their underlying entity identifiers.
+// to their underlying entity identifiers. An alias may point at another
+// alias; resolution follows the chain to the canonical id.
 
 use crate::storage::{ConceptStorage, StorageResult};
 use serde::{Deserialize, Serialize};
@@ -61,6 +62,87 @@ pub enum ResolveOutput {
     Ok { entity_id: String },
     #[serde(rename = "notfound")]
     NotFound { message: String },
+    #[serde(rename = "cycle")]
+    Cycle { message: String },
+    #[serde(rename = "too_many_hops")]
+    TooManyHops { message: String },
+}
+
+// --- Canonicalize ---
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CanonicalizeInput;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "variant")]
+pub enum CanonicalizeOutput {
+    #[serde(rename = "ok")]
+    Ok {
+        /// Aliases rewritten to point straight at their canonical id.
+        flattened: u64,
+        /// JSON array of the alias chains that loop, each listed once.
+        cycles: String,
+    },
+}
+
+// --- Chain resolution ---
+
+/// Longest alias chain `resolve_alias` will follow.
+pub const MAX_ALIAS_HOPS: usize = 16;
+
+/// Why an alias chain has no canonical id.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AliasError {
+    /// The chain returns to an alias already visited; `chain` is the loop.
+    Cycle { chain: Vec<String> },
+    /// The chain is longer than `MAX_ALIAS_HOPS`.
+    TooManyHops { chain: Vec<String> },
+}
+
+impl std::fmt::Display for AliasError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AliasError::Cycle { chain } => {
+                write!(f, "alias cycle: {} -> {}", chain.join(" -> "), chain[0])
+            }
+            AliasError::TooManyHops { chain } => write!(
+                f,
+                "alias chain from '{}' exceeds {} hops",
+                chain[0], MAX_ALIAS_HOPS
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AliasError {}
+
+/// Follow `name` through alias → alias → … to the canonical entity id.
+/// Returns `None` when `name` is not an alias at all.
+pub async fn resolve_alias(
+    name: &str,
+    storage: &dyn ConceptStorage,
+) -> StorageResult<Result<Option<String>, AliasError>> {
+    let mut chain: Vec<String> = vec![];
+    let mut current = name.to_string();
+
+    while let Some(record) = storage.get("alias", &current).await? {
+        chain.push(current);
+        let target = record
+            .get("entity_id")
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string();
+
+        if let Some(start) = chain.iter().position(|alias| *alias == target) {
+            return Ok(Err(AliasError::Cycle { chain: chain.split_off(start) }));
+        }
+        if chain.len() > MAX_ALIAS_HOPS {
+            return Ok(Err(AliasError::TooManyHops { chain }));
+        }
+        current = target;
+    }
+
+    Ok(Ok((!chain.is_empty()).then_some(current)))
 }
 
 pub struct AliasHandler;
@@ -136,20 +218,60 @@ impl AliasHandler {
         input: ResolveInput,
         storage: &dyn ConceptStorage,
     ) -> StorageResult<ResolveOutput> {
-        let existing = storage.get("alias", &input.name).await?;
-        match existing {
-            None => Ok(ResolveOutput::NotFound {
+        match resolve_alias(&input.name, storage).await? {
+            Ok(Some(entity_id)) => Ok(ResolveOutput::Ok { entity_id }),
+            Ok(None) => Ok(ResolveOutput::NotFound {
                 message: format!("alias '{}' not found", input.name),
             }),
-            Some(record) => {
-                let entity_id = record
-                    .get("entity_id")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("")
-                    .to_string();
-                Ok(ResolveOutput::Ok { entity_id })
+            Err(err @ AliasError::Cycle { .. }) => Ok(ResolveOutput::Cycle {
+                message: err.to_string(),
+            }),
+            Err(err @ AliasError::TooManyHops { .. }) => Ok(ResolveOutput::TooManyHops {
+                message: err.to_string(),
+            }),
+        }
+    }
+
+    /// Maintenance pass: repoint every alias in a chain directly at its
+    /// canonical id so later lookups take one hop. Aliases caught in a loop
+    /// are left alone and reported.
+    pub async fn canonicalize(
+        &self,
+        _input: CanonicalizeInput,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<CanonicalizeOutput> {
+        let mut aliases = storage.find("alias", None).await?;
+        aliases.sort_by(|a, b| a["alias_name"].as_str().cmp(&b["alias_name"].as_str()));
+
+        let mut flattened: u64 = 0;
+        let mut cycles: Vec<Vec<String>> = vec![];
+        for mut record in aliases {
+            let alias_name = record["alias_name"].as_str().unwrap_or("").to_string();
+            match resolve_alias(&alias_name, storage).await? {
+                Ok(Some(canonical)) => {
+                    if record["entity_id"].as_str() != Some(canonical.as_str()) {
+                        record["entity_id"] = json!(canonical);
+                        storage.put("alias", &alias_name, record).await?;
+                        flattened += 1;
+                    }
+                }
+                Ok(None) => {}
+                Err(AliasError::Cycle { mut chain }) => {
+                    // Every member reports the same loop; keep one rotation
+                    let min = chain.iter().enumerate().min_by(|a, b| a.1.cmp(b.1)).map_or(0, |(i, _)| i);
+                    chain.rotate_left(min);
+                    if !cycles.contains(&chain) {
+                        cycles.push(chain);
+                    }
+                }
+                Err(AliasError::TooManyHops { .. }) => {}
             }
         }
+
+        Ok(CanonicalizeOutput::Ok {
+            flattened,
+            cycles: serde_json::to_string(&cycles)?,
+        })
     }
 }
 
@@ -346,4 +468,74 @@ mod tests {
 
         assert!(matches!(result, ResolveOutput::NotFound { .. }));
     }
+
+    // --- chains ---
+
+    async fn add(storage: &InMemoryStorage, alias_name: &str, entity_id: &str) {
+        AliasHandler
+            .add_alias(
+                AddAliasInput { entity_id: entity_id.into(), alias_name: alias_name.into() },
+                storage,
+            )
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn resolve_follows_two_hop_chain_and_canonicalize_flattens_it() {
+        let storage = InMemoryStorage::new();
+        let handler = AliasHandler;
+        add(&storage, "rustlang", "rust").await;
+        add(&storage, "rust", "topic-rust").await;
+
+        assert_eq!(resolve_alias("rustlang", &storage).await.unwrap(), Ok(Some("topic-rust".to_string())));
+        let result = handler.resolve(ResolveInput { name: "rustlang".into() }, &storage).await.unwrap();
+        assert!(matches!(result, ResolveOutput::Ok { entity_id } if entity_id == "topic-rust"));
+
+        let CanonicalizeOutput::Ok { flattened, cycles } = handler.canonicalize(CanonicalizeInput, &storage).await.unwrap();
+        assert_eq!((flattened, cycles.as_str()), (1, "[]"));
+        let record = storage.get("alias", "rustlang").await.unwrap().unwrap();
+        assert_eq!(record["entity_id"], "topic-rust");
+
+        // Already flat, so a second pass changes nothing
+        let CanonicalizeOutput::Ok { flattened, .. } = handler.canonicalize(CanonicalizeInput, &storage).await.unwrap();
+        assert_eq!(flattened, 0);
+    }
+
+    #[tokio::test]
+    async fn resolve_detects_alias_cycle() {
+        let storage = InMemoryStorage::new();
+        let handler = AliasHandler;
+        add(&storage, "start", "a").await;
+        add(&storage, "a", "b").await;
+        add(&storage, "b", "a").await;
+
+        assert_eq!(
+            resolve_alias("start", &storage).await.unwrap(),
+            Err(AliasError::Cycle { chain: vec!["a".into(), "b".into()] })
+        );
+        let result = handler.resolve(ResolveInput { name: "b".into() }, &storage).await.unwrap();
+        assert!(matches!(result, ResolveOutput::Cycle { message } if message == "alias cycle: b -> a -> b"));
+
+        let CanonicalizeOutput::Ok { flattened, cycles } = handler.canonicalize(CanonicalizeInput, &storage).await.unwrap();
+        assert_eq!(flattened, 0);
+        assert_eq!(cycles, r#"[["a","b"]]"#);
+    }
+
+    #[tokio::test]
+    async fn resolve_reports_overlong_chains_apart_from_cycles() {
+        let storage = InMemoryStorage::new();
+        let handler = AliasHandler;
+        for hop in 0..=MAX_ALIAS_HOPS {
+            add(&storage, &format!("a{}", hop), &format!("a{}", hop + 1)).await;
+        }
+
+        let err = resolve_alias("a0", &storage).await.unwrap().unwrap_err();
+        assert!(matches!(&err, AliasError::TooManyHops { chain } if chain.len() == MAX_ALIAS_HOPS + 1));
+        let result = handler.resolve(ResolveInput { name: "a0".into() }, &storage).await.unwrap();
+        assert!(matches!(result, ResolveOutput::TooManyHops { message } if message == err.to_string()));
+
+        // One hop shorter resolves
+        assert_eq!(resolve_alias("a1", &storage).await.unwrap(), Ok(Some(format!("a{}", MAX_ALIAS_HOPS + 1))));
+    }
 }
@@ -79,7 +79,7 @@ pub enum ArticleGetOutput {
 
 // ── Helpers ────────────────────────────────────────────────
 
-fn slugify(title: &str) -> String {
+pub(crate) fn slugify(title: &str) -> String {
     let raw: String = title
         .to_lowercase()
         .chars()
@@ -2,10 +2,15 @@
 //
 // Role-based permission management — grant/revoke permissions on roles,
 // assign roles to users, and check user permissions.
+//
+// Roles form a hierarchy: a role inherits every permission of its parent
+// roles, transitively. Permissions are dot-separated and a grant ending in
+// `*` covers everything beneath it, so `article.*` allows `article.edit`.
 
 use crate::storage::{ConceptStorage, StorageResult};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::collections::{BTreeSet, VecDeque};
 
 // --- GrantPermission ---
 
@@ -78,6 +83,52 @@ pub enum CheckPermissionOutput {
     Ok { allowed: bool },
 }
 
+// --- AddParentRole ---
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddParentRoleInput {
+    pub role_id: String,
+    pub parent_role_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "variant")]
+pub enum AddParentRoleOutput {
+    #[serde(rename = "ok")]
+    Ok {
+        role_id: String,
+        parent_role_id: String,
+    },
+    /// The parent already inherits from the role; `path` runs from the
+    /// parent down to the role.
+    #[serde(rename = "cycle")]
+    Cycle { path: Vec<String> },
+}
+
+/// Whether a granted permission covers the requested one. A grant of `*`
+/// covers everything and `article.*` covers `article` and anything below it.
+pub fn permission_matches(granted: &str, requested: &str) -> bool {
+    if granted == requested || granted == "*" {
+        return true;
+    }
+    match granted.strip_suffix(".*") {
+        Some(prefix) => requested
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('.')),
+        None => false,
+    }
+}
+
+async fn parent_roles(role_id: &str, storage: &dyn ConceptStorage) -> StorageResult<Vec<String>> {
+    let parents = storage
+        .find("role_parent", Some(&json!({ "role_id": role_id })))
+        .await?;
+    Ok(parents
+        .iter()
+        .filter_map(|p| p["parent_role_id"].as_str().map(str::to_string))
+        .collect())
+}
+
 pub struct AuthorizationHandler;
 
 impl AuthorizationHandler {
@@ -173,23 +224,129 @@ impl AuthorizationHandler {
         input: CheckPermissionInput,
         storage: &dyn ConceptStorage,
     ) -> StorageResult<CheckPermissionOutput> {
+        let allowed = self
+            .has_permission(&input.user_id, &input.permission_id, storage)
+            .await?;
+        Ok(CheckPermissionOutput::Ok { allowed })
+    }
+
+    /// Make `role_id` inherit the permissions of `parent_role_id`. Rejected
+    /// when the parent already inherits from the role.
+    pub async fn add_parent_role(
+        &self,
+        input: AddParentRoleInput,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<AddParentRoleOutput> {
+        if let Some(path) = self
+            .inheritance_path(&input.parent_role_id, &input.role_id, storage)
+            .await?
+        {
+            return Ok(AddParentRoleOutput::Cycle { path });
+        }
+
+        let compound_key = format!("{}:{}", input.role_id, input.parent_role_id);
+        storage
+            .put(
+                "role_parent",
+                &compound_key,
+                json!({
+                    "role_id": input.role_id,
+                    "parent_role_id": input.parent_role_id,
+                }),
+            )
+            .await?;
+
+        Ok(AddParentRoleOutput::Ok {
+            role_id: input.role_id,
+            parent_role_id: input.parent_role_id,
+        })
+    }
+
+    /// The chain of parent links from `from` up to `to`, if `from` inherits
+    /// from `to` (or is `to`).
+    async fn inheritance_path(
+        &self,
+        from: &str,
+        to: &str,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<Option<Vec<String>>> {
+        let mut queue = VecDeque::from([vec![from.to_string()]]);
+        let mut seen = BTreeSet::from([from.to_string()]);
+        while let Some(path) = queue.pop_front() {
+            let role = path.last().cloned().unwrap_or_default();
+            if role == to {
+                return Ok(Some(path));
+            }
+            for parent in parent_roles(&role, storage).await? {
+                if seen.insert(parent.clone()) {
+                    let mut next = path.clone();
+                    next.push(parent);
+                    queue.push_back(next);
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// `role_id` and every role it inherits from, nearest first.
+    pub async fn effective_roles(
+        &self,
+        role_id: &str,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<Vec<String>> {
+        let mut roles = vec![role_id.to_string()];
+        let mut seen = BTreeSet::from([role_id.to_string()]);
+        let mut next = 0;
+        // Stored hierarchies are acyclic, but `seen` keeps a bad record from
+        // looping forever
+        while next < roles.len() {
+            for parent in parent_roles(&roles[next], storage).await? {
+                if seen.insert(parent.clone()) {
+                    roles.push(parent);
+                }
+            }
+            next += 1;
+        }
+        Ok(roles)
+    }
+
+    /// Whether any role assigned to `subject`, or any role those roles
+    /// inherit from, grants `permission` directly or through a wildcard.
+    pub async fn has_permission(
+        &self,
+        subject: &str,
+        permission: &str,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<bool> {
         // Find all roles assigned to the user
         let user_roles = storage
-            .find("user_role", Some(&json!({ "user_id": input.user_id })))
+            .find("user_role", Some(&json!({ "user_id": subject })))
             .await?;
 
-        // Check if any of the user's roles have the requested permission
+        let mut checked = BTreeSet::new();
         for user_role in &user_roles {
-            if let Some(role_id) = user_role.get("role_id").and_then(|v| v.as_str()) {
-                let compound_key = format!("{}:{}", role_id, input.permission_id);
-                let perm = storage.get("permission", &compound_key).await?;
-                if perm.is_some() {
-                    return Ok(CheckPermissionOutput::Ok { allowed: true });
+            let Some(role_id) = user_role.get("role_id").and_then(|v| v.as_str()) else {
+                continue;
+            };
+            for role in self.effective_roles(role_id, storage).await? {
+                if !checked.insert(role.clone()) {
+                    continue;
+                }
+                let grants = storage
+                    .find("permission", Some(&json!({ "role_id": role })))
+                    .await?;
+                let granted = grants.iter().any(|grant| {
+                    grant["permission_id"]
+                        .as_str()
+                        .is_some_and(|granted| permission_matches(granted, permission))
+                });
+                if granted {
+                    return Ok(true);
                 }
             }
         }
 
-        Ok(CheckPermissionOutput::Ok { allowed: false })
+        Ok(false)
     }
 }
 
@@ -408,4 +565,153 @@ mod tests {
             CheckPermissionOutput::Ok { allowed } => assert!(!allowed),
         }
     }
+
+    // --- role hierarchy ---
+
+    async fn grant(
+        handler: &AuthorizationHandler,
+        storage: &InMemoryStorage,
+        role: &str,
+        perm: &str,
+    ) {
+        handler
+            .grant_permission(
+                GrantPermissionInput {
+                    role_id: role.into(),
+                    permission_id: perm.into(),
+                },
+                storage,
+            )
+            .await
+            .unwrap();
+    }
+
+    async fn inherit(
+        handler: &AuthorizationHandler,
+        storage: &InMemoryStorage,
+        role: &str,
+        parent: &str,
+    ) -> AddParentRoleOutput {
+        handler
+            .add_parent_role(
+                AddParentRoleInput {
+                    role_id: role.into(),
+                    parent_role_id: parent.into(),
+                },
+                storage,
+            )
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn permissions_inherit_through_two_levels() {
+        let storage = InMemoryStorage::new();
+        let handler = AuthorizationHandler;
+
+        grant(&handler, &storage, "viewer", "article.read").await;
+        grant(&handler, &storage, "editor", "article.edit").await;
+        grant(&handler, &storage, "admin", "user.delete").await;
+        inherit(&handler, &storage, "editor", "viewer").await;
+        inherit(&handler, &storage, "admin", "editor").await;
+        handler
+            .assign_role(
+                AssignRoleInput {
+                    user_id: "alice".into(),
+                    role_id: "admin".into(),
+                },
+                &storage,
+            )
+            .await
+            .unwrap();
+
+        assert!(handler
+            .has_permission("alice", "article.read", &storage)
+            .await
+            .unwrap());
+        assert!(handler
+            .has_permission("alice", "article.edit", &storage)
+            .await
+            .unwrap());
+        assert!(handler
+            .has_permission("alice", "user.delete", &storage)
+            .await
+            .unwrap());
+        assert!(!handler
+            .has_permission("alice", "billing.view", &storage)
+            .await
+            .unwrap());
+
+        let roles = handler.effective_roles("admin", &storage).await.unwrap();
+        assert_eq!(roles, vec!["admin", "editor", "viewer"]);
+    }
+
+    #[tokio::test]
+    async fn wildcard_grant_matches_nested_permission() {
+        let storage = InMemoryStorage::new();
+        let handler = AuthorizationHandler;
+
+        grant(&handler, &storage, "author", "article.*").await;
+        handler
+            .assign_role(
+                AssignRoleInput {
+                    user_id: "bob".into(),
+                    role_id: "author".into(),
+                },
+                &storage,
+            )
+            .await
+            .unwrap();
+
+        let result = handler
+            .check_permission(
+                CheckPermissionInput {
+                    user_id: "bob".into(),
+                    permission_id: "article.edit".into(),
+                },
+                &storage,
+            )
+            .await
+            .unwrap();
+        assert!(matches!(
+            result,
+            CheckPermissionOutput::Ok { allowed: true }
+        ));
+        assert!(!handler
+            .has_permission("bob", "articles.edit", &storage)
+            .await
+            .unwrap());
+        assert!(!handler
+            .has_permission("bob", "comment.edit", &storage)
+            .await
+            .unwrap());
+
+        assert!(permission_matches("*", "anything.at.all"));
+        assert!(permission_matches("article.*", "article.comment.delete"));
+    }
+
+    #[tokio::test]
+    async fn add_parent_role_rejects_cycles() {
+        let storage = InMemoryStorage::new();
+        let handler = AuthorizationHandler;
+
+        inherit(&handler, &storage, "editor", "viewer").await;
+        inherit(&handler, &storage, "admin", "editor").await;
+
+        match inherit(&handler, &storage, "viewer", "admin").await {
+            AddParentRoleOutput::Cycle { path } => {
+                assert_eq!(path, vec!["admin", "editor", "viewer"]);
+            }
+            other => panic!("expected cycle, got {:?}", other),
+        }
+        assert!(matches!(
+            inherit(&handler, &storage, "viewer", "viewer").await,
+            AddParentRoleOutput::Cycle { .. }
+        ));
+        assert!(storage
+            .get("role_parent", "viewer:admin")
+            .await
+            .unwrap()
+            .is_none());
+    }
 }
@@ -1,11 +1,18 @@
 // AutomationRule Concept Implementation (Rust)
 //
 // Automation suite — defines automation rules with triggers, conditions,
-// and actions; enables/disables rules; evaluates events against rules.
+// and actions; enables/disables rules; evaluates events against rules;
+// fires cron-scheduled rules when they come due.
 
+use crate::relation;
 use crate::storage::{ConceptStorage, StorageResult};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::collections::HashMap;
+use std::str::FromStr;
 
 // ── Define ────────────────────────────────────────────────
 
@@ -73,6 +80,202 @@ pub enum AutomationRuleEvaluateOutput {
     NotFound { message: String },
 }
 
+// ── DefineScheduled ───────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutomationRuleDefineScheduledInput {
+    /// Standard five-field cron (`0 9 * * *`), or six/seven fields with seconds.
+    pub cron: String,
+    /// IANA timezone the schedule is read in; UTC when empty.
+    pub timezone: String,
+    pub conditions: String,
+    pub actions: String,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "variant")]
+pub enum AutomationRuleDefineScheduledOutput {
+    #[serde(rename = "ok")]
+    Ok { rule_id: String, next_fire_at: String },
+    #[serde(rename = "invalid")]
+    Invalid { message: String },
+}
+
+// ── Schedules ─────────────────────────────────────────────
+
+/// Trigger prefix marking a rule as time-based rather than event-based.
+pub const CRON_TRIGGER_PREFIX: &str = "cron:";
+
+/// A cron schedule read in a fixed timezone, so `0 9 * * *` stays at 09:00
+/// local time on both sides of a DST change.
+#[derive(Debug, Clone)]
+pub struct CronTrigger {
+    schedule: cron::Schedule,
+    timezone: Tz,
+}
+
+impl CronTrigger {
+    pub fn parse(expression: &str, timezone: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        // The cron crate wants a leading seconds field and numbers weekdays
+        // 1 (Sunday) to 7, so POSIX weekdays are rewritten as names.
+        let normalized = if let [minute, hour, day, month, weekday] = fields[..] {
+            let weekday = posix_weekdays(weekday)
+                .map_err(|e| format!("invalid cron expression '{}': {}", expression, e))?;
+            format!("0 {} {} {} {} {}", minute, hour, day, month, weekday)
+        } else {
+            expression.trim().to_string()
+        };
+        let schedule = cron::Schedule::from_str(&normalized)
+            .map_err(|e| format!("invalid cron expression '{}': {}", expression, e))?;
+        let timezone = if timezone.is_empty() {
+            Tz::UTC
+        } else {
+            Tz::from_str(timezone).map_err(|_| format!("unknown timezone '{}'", timezone))?
+        };
+        Ok(Self { schedule, timezone })
+    }
+
+    /// The first fire time strictly after `after`.
+    pub fn next_fire(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.schedule
+            .after(&after.with_timezone(&self.timezone))
+            .next()
+            .map(|at| at.with_timezone(&Utc))
+    }
+}
+
+const WEEKDAY_NAMES: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+/// Rewrite a POSIX day-of-week field (0 or 7 = Sunday, with lists, ranges
+/// and steps) as the explicit list of day names it selects.
+fn posix_weekdays(field: &str) -> Result<String, String> {
+    if field == "*" || field == "?" {
+        return Ok(field.to_string());
+    }
+    let day = |token: &str| -> Result<usize, String> {
+        token
+            .parse::<usize>()
+            .ok()
+            .filter(|d| *d <= 7)
+            .or_else(|| {
+                WEEKDAY_NAMES
+                    .iter()
+                    .position(|name| name.eq_ignore_ascii_case(token))
+            })
+            .ok_or_else(|| format!("'{}' is not a day of the week", token))
+    };
+
+    let mut days = [false; 7];
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step = step
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|s| *s > 0)
+                    .ok_or_else(|| format!("'{}' is not a valid step", step))?;
+                (range, Some(step))
+            }
+            None => (part, None),
+        };
+        let (first, last) = match range.split_once('-') {
+            _ if range == "*" => (0, 6),
+            Some((from, to)) => (day(from)?, day(to)?),
+            // `n/step` runs from n to the end of the week
+            None if step.is_some() => (day(range)?, 6),
+            None => (day(range)?, day(range)?),
+        };
+        if first > last {
+            return Err(format!("'{}' is not an ascending range", range));
+        }
+        for d in (first..=last).step_by(step.unwrap_or(1)) {
+            days[d % 7] = true;
+        }
+    }
+
+    Ok(WEEKDAY_NAMES
+        .iter()
+        .zip(days)
+        .filter_map(|(name, on)| on.then_some(*name))
+        .collect::<Vec<_>>()
+        .join(","))
+}
+
+/// Runs the actions of a scheduled rule when it fires.
+#[async_trait]
+pub trait RuleExecutor: Send + Sync {
+    async fn execute(
+        &self,
+        rule: &serde_json::Value,
+        fired_at: DateTime<Utc>,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<()>;
+}
+
+fn cron_trigger(rule: &serde_json::Value) -> Option<CronTrigger> {
+    let expression = rule["trigger"].as_str()?.strip_prefix(CRON_TRIGGER_PREFIX)?;
+    CronTrigger::parse(expression, rule["timezone"].as_str().unwrap_or("")).ok()
+}
+
+/// Name of the relation whose links order scheduled rules: a link from one
+/// rule to another makes the source fire first when both are due.
+pub const RULE_ORDER_RELATION: &str = "runs_before";
+
+/// Each rule's position in the `RULE_ORDER_RELATION` ordering, if one is
+/// defined. A cycle in the links is an error.
+async fn rule_ranks(storage: &dyn ConceptStorage) -> StorageResult<HashMap<String, usize>> {
+    let defs = storage.find("relation_def", Some(&json!({ "name": RULE_ORDER_RELATION }))).await?;
+    let mut ranks = HashMap::new();
+    for def in defs {
+        let relation_id = def["relation_id"].as_str().unwrap_or("");
+        let order = relation::topological_order(relation_id, storage).await??;
+        for rule_id in order {
+            let next = ranks.len();
+            ranks.entry(rule_id).or_insert(next);
+        }
+    }
+    Ok(ranks)
+}
+
+/// Fire every enabled scheduled rule due at `now`, then advance each one's
+/// `next_fire_at`. A rule that missed several slots fires once. Rules linked
+/// by `RULE_ORDER_RELATION` fire in link order, ahead of the rest; the others
+/// fire by id. Returns the fired rule ids in firing order.
+pub async fn run_due(
+    now: DateTime<Utc>,
+    executor: &dyn RuleExecutor,
+    storage: &dyn ConceptStorage,
+) -> StorageResult<Vec<String>> {
+    let ranks = rule_ranks(storage).await?;
+    let mut rules = storage.find("automation_rule", Some(&json!({ "enabled": true }))).await?;
+    rules.sort_by_key(|rule| {
+        let rule_id = rule["rule_id"].as_str().unwrap_or("").to_string();
+        (ranks.get(&rule_id).copied().unwrap_or(usize::MAX), rule_id)
+    });
+
+    let mut fired = Vec::new();
+    for mut rule in rules {
+        let Some(trigger) = cron_trigger(&rule) else { continue };
+        let due = rule["next_fire_at"]
+            .as_str()
+            .and_then(|at| DateTime::parse_from_rfc3339(at).ok())
+            .is_some_and(|at| at <= now);
+        if !due {
+            continue;
+        }
+
+        executor.execute(&rule, now, storage).await?;
+        let rule_id = rule["rule_id"].as_str().unwrap_or("").to_string();
+        rule["last_fired_at"] = json!(now.to_rfc3339());
+        rule["next_fire_at"] = json!(trigger.next_fire(now).map(|at| at.to_rfc3339()));
+        storage.put("automation_rule", &rule_id, rule).await?;
+        fired.push(rule_id);
+    }
+    Ok(fired)
+}
+
 // ── Handler ───────────────────────────────────────────────
 
 pub struct AutomationRuleHandler;
@@ -102,6 +305,51 @@ impl AutomationRuleHandler {
         Ok(AutomationRuleDefineOutput::Ok { rule_id })
     }
 
+    pub async fn define_scheduled(
+        &self,
+        input: AutomationRuleDefineScheduledInput,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<AutomationRuleDefineScheduledOutput> {
+        self.define_scheduled_at(input, Utc::now(), storage).await
+    }
+
+    /// `define_scheduled` with the first fire time computed from `now`.
+    pub async fn define_scheduled_at(
+        &self,
+        input: AutomationRuleDefineScheduledInput,
+        now: DateTime<Utc>,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<AutomationRuleDefineScheduledOutput> {
+        let trigger = match CronTrigger::parse(&input.cron, &input.timezone) {
+            Ok(trigger) => trigger,
+            Err(message) => return Ok(AutomationRuleDefineScheduledOutput::Invalid { message }),
+        };
+        let Some(next_fire_at) = trigger.next_fire(now).map(|at| at.to_rfc3339()) else {
+            return Ok(AutomationRuleDefineScheduledOutput::Invalid {
+                message: format!("cron expression '{}' never fires", input.cron),
+            });
+        };
+
+        let rule_id = format!("rule_{}", rand::random::<u32>());
+        storage
+            .put(
+                "automation_rule",
+                &rule_id,
+                json!({
+                    "rule_id": rule_id,
+                    "trigger": format!("{}{}", CRON_TRIGGER_PREFIX, input.cron),
+                    "timezone": input.timezone,
+                    "conditions": input.conditions,
+                    "actions": input.actions,
+                    "enabled": input.enabled,
+                    "next_fire_at": next_fire_at,
+                    "created_at": now.to_rfc3339(),
+                }),
+            )
+            .await?;
+        Ok(AutomationRuleDefineScheduledOutput::Ok { rule_id, next_fire_at })
+    }
+
     pub async fn enable(
         &self,
         input: AutomationRuleEnableInput,
@@ -158,8 +406,12 @@ impl AutomationRuleHandler {
             }),
             Some(record) => {
                 let enabled = record["enabled"].as_bool().unwrap_or(false);
-                // A rule matches if it is enabled (simplified evaluation)
-                let matched = enabled;
+                let scheduled = record["trigger"]
+                    .as_str()
+                    .is_some_and(|t| t.starts_with(CRON_TRIGGER_PREFIX));
+                // A rule matches if it is enabled (simplified evaluation);
+                // scheduled rules fire from run_due, never from events
+                let matched = enabled && !scheduled;
                 Ok(AutomationRuleEvaluateOutput::Ok {
                     rule_id: input.rule_id,
                     matched,
@@ -367,4 +619,186 @@ mod tests {
 
         assert!(matches!(result, AutomationRuleEvaluateOutput::NotFound { .. }));
     }
+
+    // --- scheduled triggers ---
+
+    fn utc(at: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(at).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn next_fire_keeps_local_time_across_dst() {
+        let trigger = CronTrigger::parse("0 9 * * *", "America/New_York").unwrap();
+
+        // EST (UTC-5) before the March 10 2024 spring-forward, EDT (UTC-4) after
+        assert_eq!(trigger.next_fire(utc("2024-03-09T12:00:00Z")), Some(utc("2024-03-09T14:00:00Z")));
+        assert_eq!(trigger.next_fire(utc("2024-03-09T14:00:00Z")), Some(utc("2024-03-10T13:00:00Z")));
+        // And back to EST after the November 3 fall-back
+        assert_eq!(trigger.next_fire(utc("2024-11-02T13:00:00Z")), Some(utc("2024-11-03T14:00:00Z")));
+
+        let utc_trigger = CronTrigger::parse("0 9 * * *", "").unwrap();
+        assert_eq!(utc_trigger.next_fire(utc("2024-03-09T12:00:00Z")), Some(utc("2024-03-10T09:00:00Z")));
+
+        assert!(CronTrigger::parse("not cron", "UTC").is_err());
+        assert!(CronTrigger::parse("0 9 * * *", "Mars/Olympus").is_err());
+    }
+
+    #[test]
+    fn posix_weekdays_fire_on_the_named_day() {
+        use chrono::{Datelike, Weekday};
+
+        // 2024-05-01 was a Wednesday
+        let start = utc("2024-05-01T12:00:00Z");
+        let weekday = |expression: &str| {
+            CronTrigger::parse(expression, "")
+                .unwrap()
+                .next_fire(start)
+                .unwrap()
+                .weekday()
+        };
+        assert_eq!(weekday("0 9 * * 1"), Weekday::Mon);
+        assert_eq!(weekday("0 9 * * 0"), Weekday::Sun);
+        assert_eq!(weekday("0 9 * * 7"), Weekday::Sun);
+        assert_eq!(weekday("0 9 * * 5-6"), Weekday::Fri);
+        assert_eq!(weekday("0 9 * * 1,6"), Weekday::Sat);
+        assert_eq!(weekday("0 9 * * */6"), Weekday::Sat);
+
+        let weekdays = CronTrigger::parse("0 9 * * 1-5", "").unwrap();
+        let fires: Vec<Weekday> = weekdays
+            .schedule
+            .after(&start)
+            .take(5)
+            .map(|at| at.weekday())
+            .collect();
+        assert_eq!(
+            fires,
+            vec![
+                Weekday::Thu,
+                Weekday::Fri,
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed
+            ]
+        );
+
+        assert_eq!(posix_weekdays("1-7/2").unwrap(), "SUN,MON,WED,FRI");
+        assert!(CronTrigger::parse("0 9 * * 8", "").is_err());
+        assert!(CronTrigger::parse("0 9 * * 5-1", "").is_err());
+    }
+
+    struct RecordingExecutor(std::sync::Mutex<Vec<(String, DateTime<Utc>)>>);
+
+    #[async_trait]
+    impl RuleExecutor for RecordingExecutor {
+        async fn execute(
+            &self,
+            rule: &serde_json::Value,
+            fired_at: DateTime<Utc>,
+            _storage: &dyn ConceptStorage,
+        ) -> StorageResult<()> {
+            let rule_id = rule["rule_id"].as_str().unwrap_or("").to_string();
+            self.0.lock().unwrap().push((rule_id, fired_at));
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn run_due_fires_scheduled_rules_and_advances() {
+        let storage = InMemoryStorage::new();
+        let handler = AutomationRuleHandler;
+        let result = handler
+            .define_scheduled_at(
+                AutomationRuleDefineScheduledInput {
+                    cron: "0 9 * * *".into(),
+                    timezone: "America/New_York".into(),
+                    conditions: "".into(),
+                    actions: "send_digest".into(),
+                    enabled: true,
+                },
+                utc("2024-03-09T12:00:00Z"),
+                &storage,
+            )
+            .await
+            .unwrap();
+        let AutomationRuleDefineScheduledOutput::Ok { rule_id, next_fire_at } = result else {
+            panic!("expected Ok variant")
+        };
+        assert_eq!(utc(&next_fire_at), utc("2024-03-09T14:00:00Z"));
+
+        let executor = RecordingExecutor(std::sync::Mutex::new(Vec::new()));
+        assert!(run_due(utc("2024-03-09T13:59:00Z"), &executor, &storage).await.unwrap().is_empty());
+        assert_eq!(run_due(utc("2024-03-09T14:00:00Z"), &executor, &storage).await.unwrap(), vec![rule_id.clone()]);
+        assert_eq!(executor.0.lock().unwrap().len(), 1);
+
+        let record = storage.get("automation_rule", &rule_id).await.unwrap().unwrap();
+        assert_eq!(utc(record["next_fire_at"].as_str().unwrap()), utc("2024-03-10T13:00:00Z"));
+
+        // Scheduled rules never match events
+        let result = handler
+            .evaluate(AutomationRuleEvaluateInput { rule_id, event: "page_created".into() }, &storage)
+            .await
+            .unwrap();
+        assert!(matches!(result, AutomationRuleEvaluateOutput::Ok { matched: false, .. }));
+    }
+
+    #[tokio::test]
+    async fn run_due_fires_linked_rules_in_relation_order() {
+        use crate::relation::{DefineRelationInput, DefineRelationOutput, LinkInput, RelationHandler};
+
+        let storage = InMemoryStorage::new();
+        let mut rule_ids = Vec::new();
+        for actions in ["archive", "backup", "compact"] {
+            let result = AutomationRuleHandler
+                .define_scheduled_at(
+                    AutomationRuleDefineScheduledInput {
+                        cron: "0 2 * * *".into(),
+                        timezone: "UTC".into(),
+                        conditions: "".into(),
+                        actions: actions.into(),
+                        enabled: true,
+                    },
+                    utc("2024-03-09T00:00:00Z"),
+                    &storage,
+                )
+                .await
+                .unwrap();
+            let AutomationRuleDefineScheduledOutput::Ok { rule_id, .. } = result else {
+                panic!("expected Ok variant")
+            };
+            rule_ids.push(rule_id);
+        }
+        let relations = RelationHandler;
+        let DefineRelationOutput::Ok { relation_id } = relations
+            .define_relation(
+                DefineRelationInput {
+                    name: RULE_ORDER_RELATION.into(),
+                    source_type: "automation_rule".into(),
+                    target_type: "automation_rule".into(),
+                    cardinality: "many_to_many".into(),
+                    is_bidirectional: false,
+                },
+                &storage,
+            )
+            .await
+            .unwrap();
+        let link = |source: &String, target: &String| LinkInput {
+            relation_id: relation_id.clone(),
+            source_id: source.clone(),
+            target_id: target.clone(),
+        };
+        // Whatever the random ids, the linked pair fires first, in link order
+        let mut unlinked = rule_ids.clone();
+        unlinked.sort();
+        let (first, second) = (unlinked.remove(2), unlinked.remove(0));
+        relations.link(link(&first, &second), &storage).await.unwrap();
+
+        let executor = RecordingExecutor(std::sync::Mutex::new(Vec::new()));
+        let fired = run_due(utc("2024-03-09T02:00:00Z"), &executor, &storage).await.unwrap();
+        assert_eq!(fired, vec![first.clone(), second.clone(), unlinked[0].clone()]);
+
+        relations.link(link(&second, &first), &storage).await.unwrap();
+        let err = run_due(utc("2024-03-10T02:00:00Z"), &executor, &storage).await.unwrap_err();
+        assert!(err.downcast_ref::<relation::CycleError>().is_some());
+        assert_eq!(executor.0.lock().unwrap().len(), 3);
+    }
 }
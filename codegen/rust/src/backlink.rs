@@ -2,8 +2,13 @@
 //
 // Reverse-index of references — retrieve all entities that reference
 // a given entity, and reindex backlinks from the reference store.
+// BacklinkIndexer keeps the index current by listening for reference
+// events on the event bus.
 
+use crate::event_bus::{EventBusHandler, EventBusSubscribeInput, EventListener};
+use crate::reference::{Reference, REFERENCE_ADDED, REFERENCE_REMOVED};
 use crate::storage::{ConceptStorage, StorageResult};
+use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
@@ -62,54 +67,154 @@ impl BacklinkHandler {
         _input: ReindexInput,
         storage: &dyn ConceptStorage,
     ) -> StorageResult<ReindexOutput> {
-        // Fetch all references and rebuild backlink index
-        let all_refs = storage.find("reference", None).await?;
-        let mut count: u64 = 0;
-
-        // Clear existing backlinks by processing each one
-        let existing_backlinks = storage.find("backlink", None).await?;
-        for bl in &existing_backlinks {
-            if let Some(key) = bl.get("backlink_key").and_then(|v| v.as_str()) {
-                storage.del("backlink", key).await?;
-            }
+        let count = rebuild_backlinks(storage).await?;
+        Ok(ReindexOutput::Ok { count })
+    }
+}
+
+// --- Index maintenance ---
+
+/// Rebuild the backlink index from scratch by rescanning every reference.
+/// Use it to repair the index after references were written without the
+/// indexer listening. Returns the number of backlinks indexed.
+pub async fn rebuild_backlinks(storage: &dyn ConceptStorage) -> StorageResult<u64> {
+    let existing_backlinks = storage.find("backlink", None).await?;
+    for bl in &existing_backlinks {
+        if let Some(key) = bl.get("backlink_key").and_then(|v| v.as_str()) {
+            storage.del("backlink", key).await?;
         }
+    }
 
-        // Rebuild from references
-        for reference in &all_refs {
-            let source_id = reference
-                .get("source_id")
-                .and_then(|v| v.as_str())
-                .unwrap_or("");
-            let target_id = reference
-                .get("target_id")
-                .and_then(|v| v.as_str())
-                .unwrap_or("");
-            let ref_type = reference
-                .get("ref_type")
-                .and_then(|v| v.as_str())
-                .unwrap_or("link");
-
-            if !source_id.is_empty() && !target_id.is_empty() {
-                let backlink_key = format!("{}:{}", target_id, source_id);
-                storage
-                    .put(
-                        "backlink",
-                        &backlink_key,
-                        json!({
-                            "backlink_key": backlink_key,
-                            "target_id": target_id,
-                            "source_id": source_id,
-                            "ref_type": ref_type,
-                            "indexed_at": chrono::Utc::now().to_rfc3339(),
-                        }),
-                    )
-                    .await?;
-                count += 1;
-            }
+    let mut count: u64 = 0;
+    for reference in storage.find("reference", None).await? {
+        let source_id = reference.get("source_id").and_then(|v| v.as_str()).unwrap_or("");
+        let target_id = reference.get("target_id").and_then(|v| v.as_str()).unwrap_or("");
+        let ref_type = reference.get("ref_type").and_then(|v| v.as_str()).unwrap_or("link");
+
+        if !source_id.is_empty() && !target_id.is_empty() {
+            index_backlink(
+                &Reference {
+                    source_id: source_id.to_string(),
+                    target_id: target_id.to_string(),
+                    ref_type: ref_type.to_string(),
+                },
+                storage,
+            )
+            .await?;
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+/// References pointing at `node_id`, ordered by source.
+pub async fn backlinks_of(node_id: &str, storage: &dyn ConceptStorage) -> StorageResult<Vec<Reference>> {
+    let mut references: Vec<Reference> = storage
+        .find("backlink", Some(&json!({ "target_id": node_id })))
+        .await?
+        .into_iter()
+        .map(|bl| Reference {
+            source_id: bl["source_id"].as_str().unwrap_or("").to_string(),
+            target_id: bl["target_id"].as_str().unwrap_or("").to_string(),
+            ref_type: bl["ref_type"].as_str().unwrap_or("link").to_string(),
+        })
+        .collect();
+    references.sort_by(|a, b| a.source_id.cmp(&b.source_id));
+    Ok(references)
+}
+
+async fn index_backlink(reference: &Reference, storage: &dyn ConceptStorage) -> StorageResult<()> {
+    let backlink_key = format!("{}:{}", reference.target_id, reference.source_id);
+    storage
+        .put(
+            "backlink",
+            &backlink_key,
+            json!({
+                "backlink_key": backlink_key,
+                "target_id": reference.target_id,
+                "source_id": reference.source_id,
+                "ref_type": reference.ref_type,
+                "indexed_at": chrono::Utc::now().to_rfc3339(),
+            }),
+        )
+        .await
+}
+
+/// Event bus listener that applies `reference.added` and
+/// `reference.removed` to the backlink index. Pass it to
+/// `ReferenceHandler::add_ref_with` / `remove_ref_with` after `subscribe`.
+pub struct BacklinkIndexer;
+
+impl BacklinkIndexer {
+    pub const LISTENER_ID: &'static str = "backlink";
+
+    /// Subscribe the indexer to both reference events.
+    pub async fn subscribe(&self, storage: &dyn ConceptStorage) -> StorageResult<()> {
+        for event_type_id in [REFERENCE_ADDED, REFERENCE_REMOVED] {
+            EventBusHandler
+                .subscribe(
+                    EventBusSubscribeInput {
+                        event_type_id: event_type_id.to_string(),
+                        listener_id: Self::LISTENER_ID.to_string(),
+                        priority: 0,
+                    },
+                    storage,
+                )
+                .await?;
         }
+        Ok(())
+    }
+}
 
-        Ok(ReindexOutput::Ok { count })
+#[async_trait]
+impl EventListener for BacklinkIndexer {
+    fn listener_id(&self) -> &str {
+        Self::LISTENER_ID
     }
+
+    async fn handle(
+        &self,
+        event_type_id: &str,
+        payload: &serde_json::Value,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<()> {
+        let reference: Reference = serde_json::from_value(payload.clone())?;
+        match event_type_id {
+            REFERENCE_ADDED => index_backlink(&reference, storage).await,
+            REFERENCE_REMOVED => remove_backlink(&reference, storage).await,
+            _ => Ok(()),
+        }
+    }
+
+    /// Reverse `handle`. Rolling back an add that replaced an existing
+    /// reference restores the backlink with the previous reference type.
+    async fn compensate(
+        &self,
+        event_type_id: &str,
+        payload: &serde_json::Value,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<()> {
+        let reference: Reference = serde_json::from_value(payload.clone())?;
+        match event_type_id {
+            REFERENCE_ADDED => match payload["previous_ref_type"].as_str() {
+                Some(previous_type) => {
+                    let previous = Reference {
+                        ref_type: previous_type.to_string(),
+                        ..reference
+                    };
+                    index_backlink(&previous, storage).await
+                }
+                None => remove_backlink(&reference, storage).await,
+            },
+            REFERENCE_REMOVED => index_backlink(&reference, storage).await,
+            _ => Ok(()),
+        }
+    }
+}
+
+async fn remove_backlink(reference: &Reference, storage: &dyn ConceptStorage) -> StorageResult<()> {
+    let backlink_key = format!("{}:{}", reference.target_id, reference.source_id);
+    storage.del("backlink", &backlink_key).await
 }
 
 // ── Tests ──────────────────────────────────────────────────
@@ -117,6 +222,7 @@ impl BacklinkHandler {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::reference::{AddRefInput, ReferenceHandler, RemoveRefInput};
     use crate::storage::InMemoryStorage;
 
     // --- get_backlinks ---
@@ -236,4 +342,176 @@ mod tests {
         let bl = storage.get("backlink", "pageB:pageA").await.unwrap();
         assert!(bl.is_some());
     }
+
+    // --- event-driven indexing ---
+
+    #[tokio::test]
+    async fn reference_events_keep_backlinks_in_sync() {
+        let storage = InMemoryStorage::new();
+        let references = ReferenceHandler;
+        let indexer = BacklinkIndexer;
+        indexer.subscribe(&storage).await.unwrap();
+
+        for (source_id, ref_type) in [("pageA", "link"), ("pageC", "embed")] {
+            references
+                .add_ref_with(
+                    AddRefInput {
+                        source_id: source_id.into(),
+                        target_id: "pageB".into(),
+                        ref_type: ref_type.into(),
+                    },
+                    &[&indexer],
+                    &storage,
+                )
+                .await
+                .unwrap();
+        }
+
+        let backlinks = backlinks_of("pageB", &storage).await.unwrap();
+        assert_eq!(
+            backlinks.iter().map(|r| (r.source_id.as_str(), r.ref_type.as_str())).collect::<Vec<_>>(),
+            vec![("pageA", "link"), ("pageC", "embed")]
+        );
+
+        references
+            .remove_ref_with(
+                RemoveRefInput { source_id: "pageA".into(), target_id: "pageB".into() },
+                &[&indexer],
+                &storage,
+            )
+            .await
+            .unwrap();
+
+        let backlinks = backlinks_of("pageB", &storage).await.unwrap();
+        assert_eq!(backlinks.len(), 1);
+        assert_eq!(backlinks[0].source_id, "pageC");
+    }
+
+    struct RejectingListener;
+
+    #[async_trait]
+    impl EventListener for RejectingListener {
+        fn listener_id(&self) -> &str {
+            "rejecting"
+        }
+
+        async fn handle(
+            &self,
+            _event_type_id: &str,
+            _payload: &serde_json::Value,
+            _storage: &dyn ConceptStorage,
+        ) -> StorageResult<()> {
+            Err("rejected".into())
+        }
+    }
+
+    #[tokio::test]
+    async fn failed_publish_compensates_the_indexer() {
+        let storage = InMemoryStorage::new();
+        let references = ReferenceHandler;
+        let indexer = BacklinkIndexer;
+        indexer.subscribe(&storage).await.unwrap();
+        references
+            .add_ref_with(
+                AddRefInput {
+                    source_id: "a".into(),
+                    target_id: "b".into(),
+                    ref_type: "link".into(),
+                },
+                &[&indexer],
+                &storage,
+            )
+            .await
+            .unwrap();
+
+        // The indexer (priority 0) runs before the rejecting listener (-1)
+        EventBusHandler
+            .subscribe(
+                EventBusSubscribeInput {
+                    event_type_id: REFERENCE_REMOVED.into(),
+                    listener_id: "rejecting".into(),
+                    priority: -1,
+                },
+                &storage,
+            )
+            .await
+            .unwrap();
+        let removed = references
+            .remove_ref_with(
+                RemoveRefInput {
+                    source_id: "a".into(),
+                    target_id: "b".into(),
+                },
+                &[&indexer, &RejectingListener],
+                &storage,
+            )
+            .await;
+        assert!(removed.is_err());
+        assert!(storage.get("reference", "a:b").await.unwrap().is_some());
+        assert_eq!(backlinks_of("b", &storage).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn failed_retype_restores_the_previous_backlink() {
+        let storage = InMemoryStorage::new();
+        let references = ReferenceHandler;
+        let indexer = BacklinkIndexer;
+        indexer.subscribe(&storage).await.unwrap();
+        let add = |ref_type: &str| AddRefInput {
+            source_id: "a".into(),
+            target_id: "b".into(),
+            ref_type: ref_type.into(),
+        };
+        references
+            .add_ref_with(add("link"), &[&indexer], &storage)
+            .await
+            .unwrap();
+
+        EventBusHandler
+            .subscribe(
+                EventBusSubscribeInput {
+                    event_type_id: REFERENCE_ADDED.into(),
+                    listener_id: "rejecting".into(),
+                    priority: -1,
+                },
+                &storage,
+            )
+            .await
+            .unwrap();
+        let retyped = references
+            .add_ref_with(add("embed"), &[&indexer, &RejectingListener], &storage)
+            .await;
+        assert!(retyped.is_err());
+        assert_eq!(
+            backlinks_of("b", &storage).await.unwrap(),
+            vec![Reference {
+                source_id: "a".into(),
+                target_id: "b".into(),
+                ref_type: "link".into()
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn rebuild_backlinks_repairs_missed_events() {
+        let storage = InMemoryStorage::new();
+        let references = ReferenceHandler;
+
+        // Written without the indexer, so no backlink is recorded
+        references
+            .add_ref(
+                AddRefInput { source_id: "x".into(), target_id: "y".into(), ref_type: "link".into() },
+                &storage,
+            )
+            .await
+            .unwrap();
+        assert!(backlinks_of("y", &storage).await.unwrap().is_empty());
+
+        assert_eq!(rebuild_backlinks(&storage).await.unwrap(), 1);
+        let backlinks = backlinks_of("y", &storage).await.unwrap();
+        assert_eq!(
+            backlinks,
+            vec![Reference { source_id: "x".into(), target_id: "y".into(), ref_type: "link".into() }]
+        );
+    }
 }
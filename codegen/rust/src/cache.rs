@@ -1,11 +1,14 @@
 // Cache Concept Implementation (Rust)
 //
 // Infrastructure suite — set/get cached values with TTL and tags,
-// invalidate by key or by tags.
+// invalidate by key or by tags. LruCache is the bounded in-process variant
+// for hot paths that should not round-trip through storage.
 
 use crate::storage::{ConceptStorage, StorageResult};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::time::{Duration, Instant};
 
 // ── Set ───────────────────────────────────────────────────
 
@@ -68,6 +71,177 @@ pub enum CacheInvalidateByTagsOutput {
     Ok { count: u64 },
 }
 
+// ── LRU ───────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheMetrics {
+    pub hits: u64,
+    pub misses: u64,
+    /// Entries dropped to make room at capacity.
+    pub evictions: u64,
+    /// Entries dropped because their TTL ran out.
+    pub expirations: u64,
+}
+
+#[derive(Debug, Clone)]
+struct LruEntry<V> {
+    value: V,
+    expires_at: Option<Instant>,
+    /// Position in the recency order; larger is more recent.
+    tick: u64,
+}
+
+/// A bounded cache evicting the least recently used entry once full.
+/// Entries may carry a TTL; expired entries are dropped when touched or by
+/// `sweep`. Wrap in a `Mutex` to share between tasks.
+#[derive(Debug, Clone)]
+pub struct LruCache<V> {
+    capacity: usize,
+    entries: HashMap<String, LruEntry<V>>,
+    recency: BTreeMap<u64, String>,
+    /// Entries with a TTL, soonest expiry first.
+    expiries: BTreeSet<(Instant, String)>,
+    tick: u64,
+    metrics: CacheMetrics,
+}
+
+impl<V: Clone> LruCache<V> {
+    /// A cache holding at most `capacity` entries (at least one).
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+            expiries: BTreeSet::new(),
+            tick: 0,
+            metrics: CacheMetrics::default(),
+        }
+    }
+
+    pub fn get(&mut self, key: &str) -> Option<V> {
+        self.get_at(key, Instant::now())
+    }
+
+    /// `get` as of `now`.
+    pub fn get_at(&mut self, key: &str, now: Instant) -> Option<V> {
+        let expired = match self.entries.get(key) {
+            None => {
+                self.metrics.misses += 1;
+                return None;
+            }
+            Some(entry) => entry.expires_at.is_some_and(|at| at <= now),
+        };
+        if expired {
+            self.remove(key);
+            self.metrics.expirations += 1;
+            self.metrics.misses += 1;
+            return None;
+        }
+
+        self.tick += 1;
+        let entry = self.entries.get_mut(key)?;
+        self.recency.remove(&entry.tick);
+        entry.tick = self.tick;
+        self.recency.insert(self.tick, key.to_string());
+        self.metrics.hits += 1;
+        Some(entry.value.clone())
+    }
+
+    /// Insert or replace `key`, living for `ttl` when given.
+    pub fn put(&mut self, key: &str, value: V, ttl: Option<Duration>) {
+        self.put_at(key, value, ttl, Instant::now());
+    }
+
+    /// `put` as of `now`. A TTL too large to represent never expires.
+    pub fn put_at(&mut self, key: &str, value: V, ttl: Option<Duration>, now: Instant) {
+        self.remove(key);
+        while self.entries.len() >= self.capacity {
+            // Prefer dropping something already expired over a live entry
+            if self.expire_next(now) {
+                continue;
+            }
+            let Some((_, oldest)) = self.recency.first_key_value() else { break };
+            let oldest = oldest.clone();
+            self.remove(&oldest);
+            self.metrics.evictions += 1;
+        }
+
+        self.tick += 1;
+        let expires_at = ttl.and_then(|ttl| now.checked_add(ttl));
+        if let Some(at) = expires_at {
+            self.expiries.insert((at, key.to_string()));
+        }
+        self.recency.insert(self.tick, key.to_string());
+        self.entries.insert(
+            key.to_string(),
+            LruEntry {
+                value,
+                expires_at,
+                tick: self.tick,
+            },
+        );
+    }
+
+    pub fn invalidate(&mut self, key: &str) -> bool {
+        self.remove(key).is_some()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+        self.expiries.clear();
+    }
+
+    /// Drop every expired entry, returning how many were dropped.
+    pub fn sweep(&mut self) -> usize {
+        self.sweep_at(Instant::now())
+    }
+
+    /// `sweep` as of `now`.
+    pub fn sweep_at(&mut self, now: Instant) -> usize {
+        let mut dropped = 0;
+        while self.expire_next(now) {
+            dropped += 1;
+        }
+        dropped
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn metrics(&self) -> CacheMetrics {
+        self.metrics
+    }
+
+    /// Drop the entry expiring soonest if it has expired by `now`.
+    fn expire_next(&mut self, now: Instant) -> bool {
+        let Some((at, key)) = self.expiries.first() else {
+            return false;
+        };
+        if *at > now {
+            return false;
+        }
+        let key = key.clone();
+        self.remove(&key);
+        self.metrics.expirations += 1;
+        true
+    }
+
+    fn remove(&mut self, key: &str) -> Option<LruEntry<V>> {
+        let entry = self.entries.remove(key)?;
+        self.recency.remove(&entry.tick);
+        if let Some(at) = entry.expires_at {
+            self.expiries.remove(&(at, key.to_string()));
+        }
+        Some(entry)
+    }
+}
+
 // ── Handler ───────────────────────────────────────────────
 
 pub struct CacheHandler;
@@ -400,4 +574,61 @@ mod tests {
             CacheInvalidateByTagsOutput::Ok { count } => assert_eq!(count, 0),
         }
     }
+
+    // --- lru ---
+
+    #[test]
+    fn lru_evicts_least_recently_used_at_capacity() {
+        let mut cache = LruCache::new(2);
+        cache.put("a", 1, None);
+        cache.put("b", 2, None);
+        assert_eq!(cache.get("a"), Some(1));
+
+        // "b" is now least recently used
+        cache.put("c", 3, None);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get("b"), None);
+        assert_eq!(cache.get("a"), Some(1));
+        assert_eq!(cache.get("c"), Some(3));
+
+        // Replacing a key does not evict anything
+        cache.put("c", 30, None);
+        assert_eq!(cache.get("c"), Some(30));
+
+        assert!(cache.invalidate("a"));
+        assert!(!cache.invalidate("a"));
+        assert_eq!(
+            cache.metrics(),
+            CacheMetrics { hits: 4, misses: 1, evictions: 1, expirations: 0 }
+        );
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn lru_expires_entries_by_ttl() {
+        let start = Instant::now();
+        let mut cache = LruCache::new(2);
+        cache.put_at("short", "s", Some(Duration::from_secs(5)), start);
+        cache.put_at("long", "l", Some(Duration::from_secs(60)), start);
+
+        assert_eq!(cache.get_at("short", start + Duration::from_secs(4)), Some("s"));
+        assert_eq!(cache.get_at("short", start + Duration::from_secs(5)), None);
+        assert_eq!(cache.len(), 1);
+
+        // At capacity an expired entry is dropped before any live one
+        cache.put_at("x", "x", Some(Duration::from_secs(1)), start);
+        cache.put_at("y", "y", None, start + Duration::from_secs(2));
+        assert_eq!(cache.get_at("long", start + Duration::from_secs(2)), Some("l"));
+        assert_eq!(cache.metrics().evictions, 0);
+
+        assert_eq!(cache.sweep_at(start + Duration::from_secs(60)), 1);
+        assert_eq!(cache.get_at("y", start + Duration::from_secs(3600)), Some("y"));
+        assert_eq!(cache.metrics().expirations, 3);
+
+        // A TTL past the end of time never expires
+        cache.put_at("forever", "f", Some(Duration::MAX), start);
+        assert_eq!(cache.get_at("forever", start + Duration::from_secs(1 << 40)), Some("f"));
+        assert_eq!(cache.sweep_at(start + Duration::from_secs(1 << 40)), 0);
+    }
 }
@@ -2,7 +2,12 @@
 //
 // Manages a canvas with positioned nodes, edges, and grouping.
 // See Architecture doc Sections on canvas and spatial layout.
+//
+// `export` linearizes the canvas into a Markdown outline, reading nodes
+// top-to-bottom then left-to-right with edges as reference links, or draws
+// the node graph as SVG.
 
+use crate::escape::escape_html;
 use crate::storage::{ConceptStorage, StorageResult};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
@@ -72,6 +77,221 @@ pub enum GroupNodesOutput {
     Ok { group_id: String },
 }
 
+// ── Export ────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    Markdown,
+    Svg,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportInput {
+    pub format: ExportFormat,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "variant")]
+pub enum ExportOutput {
+    #[serde(rename = "ok")]
+    Ok {
+        format: ExportFormat,
+        content: String,
+    },
+}
+
+/// Size of a node box in SVG exports.
+const NODE_WIDTH: f64 = 160.0;
+const NODE_HEIGHT: f64 = 60.0;
+const SVG_MARGIN: f64 = 20.0;
+
+#[derive(Debug, Clone)]
+struct ExportNode {
+    id: String,
+    x: f64,
+    y: f64,
+    content: String,
+}
+
+#[derive(Debug, Clone)]
+struct ExportEdge {
+    from: String,
+    to: String,
+    label: String,
+}
+
+/// Canvas nodes in reading order: top-to-bottom, then left-to-right.
+/// Group records are containers, not content, and are left out.
+async fn nodes_in_reading_order(storage: &dyn ConceptStorage) -> StorageResult<Vec<ExportNode>> {
+    let mut nodes: Vec<ExportNode> = storage
+        .find("canvas_node", None)
+        .await?
+        .iter()
+        .filter(|n| n["node_type"].as_str() != Some("group"))
+        .map(|n| ExportNode {
+            id: n["node_id"].as_str().unwrap_or("").to_string(),
+            x: n["position_x"].as_f64().unwrap_or(0.0),
+            y: n["position_y"].as_f64().unwrap_or(0.0),
+            content: n["content"].as_str().unwrap_or("").to_string(),
+        })
+        .collect();
+    nodes.sort_by(|a, b| {
+        a.y.total_cmp(&b.y)
+            .then(a.x.total_cmp(&b.x))
+            .then_with(|| a.id.cmp(&b.id))
+    });
+    Ok(nodes)
+}
+
+async fn edges(storage: &dyn ConceptStorage) -> StorageResult<Vec<ExportEdge>> {
+    let mut edges: Vec<(String, ExportEdge)> = storage
+        .find("canvas_edge", None)
+        .await?
+        .iter()
+        .map(|e| {
+            let text = |key: &str| e[key].as_str().unwrap_or("").to_string();
+            (
+                text("edge_id"),
+                ExportEdge {
+                    from: text("from_id"),
+                    to: text("to_id"),
+                    label: text("label"),
+                },
+            )
+        })
+        .collect();
+    edges.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(edges.into_iter().map(|(_, edge)| edge).collect())
+}
+
+/// Node content as one line of Markdown text, safe inside link text and
+/// free of inline HTML.
+fn markdown_text(content: &str) -> String {
+    let line = content.split_whitespace().collect::<Vec<_>>().join(" ");
+    escape_html(&line).replace('[', "\\[").replace(']', "\\]")
+}
+
+/// A node id as a Markdown reference label, with brackets and backslashes
+/// escaped so the label cannot end early.
+fn reference_label(id: &str) -> String {
+    id.replace('\\', "\\\\")
+        .replace('[', "\\[")
+        .replace(']', "\\]")
+}
+
+/// A node id as a URL fragment: bytes outside the unreserved set are
+/// percent-encoded.
+fn fragment(id: &str) -> String {
+    id.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+fn export_markdown(nodes: &[ExportNode], edges: &[ExportEdge]) -> String {
+    let mut out = String::from("# Canvas\n\n");
+    let mut referenced = Vec::new();
+    for node in nodes {
+        out.push_str(&format!(
+            "- <a id=\"{}\"></a>{}\n",
+            escape_html(&node.id),
+            markdown_text(&node.content)
+        ));
+        for edge in edges.iter().filter(|e| e.from == node.id) {
+            let Some(target) = nodes.iter().find(|n| n.id == edge.to) else {
+                continue;
+            };
+            let relation = if edge.label.is_empty() {
+                "links to".to_string()
+            } else {
+                markdown_text(&edge.label)
+            };
+            out.push_str(&format!(
+                "  - {}: [{}][{}]\n",
+                relation,
+                markdown_text(&target.content),
+                reference_label(&target.id)
+            ));
+            if !referenced.contains(&target.id) {
+                referenced.push(target.id.clone());
+            }
+        }
+    }
+    if !referenced.is_empty() {
+        out.push('\n');
+        for id in &referenced {
+            out.push_str(&format!("[{}]: #{}\n", reference_label(id), fragment(id)));
+        }
+    }
+    out
+}
+
+fn export_svg(nodes: &[ExportNode], edges: &[ExportEdge]) -> String {
+    let min_x = nodes.iter().map(|n| n.x).fold(f64::INFINITY, f64::min);
+    let min_y = nodes.iter().map(|n| n.y).fold(f64::INFINITY, f64::min);
+    let (min_x, min_y) = if nodes.is_empty() {
+        (0.0, 0.0)
+    } else {
+        (min_x, min_y)
+    };
+    let max_x = nodes.iter().map(|n| n.x + NODE_WIDTH).fold(min_x, f64::max);
+    let max_y = nodes
+        .iter()
+        .map(|n| n.y + NODE_HEIGHT)
+        .fold(min_y, f64::max);
+
+    let mut out = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">\n",
+        min_x - SVG_MARGIN,
+        min_y - SVG_MARGIN,
+        max_x - min_x + 2.0 * SVG_MARGIN,
+        max_y - min_y + 2.0 * SVG_MARGIN
+    );
+    let center = |n: &ExportNode| (n.x + NODE_WIDTH / 2.0, n.y + NODE_HEIGHT / 2.0);
+    for edge in edges {
+        let from = nodes.iter().find(|n| n.id == edge.from);
+        let to = nodes.iter().find(|n| n.id == edge.to);
+        let (Some(from), Some(to)) = (from, to) else {
+            continue;
+        };
+        let ((x1, y1), (x2, y2)) = (center(from), center(to));
+        out.push_str(&format!(
+            "  <line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"#888\"/>\n",
+            x1, y1, x2, y2
+        ));
+        if !edge.label.is_empty() {
+            out.push_str(&format!(
+                "  <text x=\"{}\" y=\"{}\" text-anchor=\"middle\">{}</text>\n",
+                (x1 + x2) / 2.0,
+                (y1 + y2) / 2.0,
+                escape_html(&edge.label)
+            ));
+        }
+    }
+    for node in nodes {
+        let (cx, cy) = center(node);
+        out.push_str(&format!(
+            "  <g id=\"{}\"><rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"6\" fill=\"#fff\" stroke=\"#333\"/>\
+             <text x=\"{}\" y=\"{}\" text-anchor=\"middle\">{}</text></g>\n",
+            escape_html(&node.id),
+            node.x,
+            node.y,
+            NODE_WIDTH,
+            NODE_HEIGHT,
+            cx,
+            cy,
+            escape_html(&node.content)
+        ));
+    }
+    out.push_str("</svg>\n");
+    out
+}
+
 // ── Handler ───────────────────────────────────────────────
 
 pub struct CanvasHandler;
@@ -197,6 +417,25 @@ impl CanvasHandler {
 
         Ok(GroupNodesOutput::Ok { group_id })
     }
+
+    pub async fn export(
+        &self,
+        input: ExportInput,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<ExportOutput> {
+        let nodes = nodes_in_reading_order(storage).await?;
+        let edges = edges(storage).await?;
+
+        let content = match input.format {
+            ExportFormat::Markdown => export_markdown(&nodes, &edges),
+            ExportFormat::Svg => export_svg(&nodes, &edges),
+        };
+
+        Ok(ExportOutput::Ok {
+            format: input.format,
+            content,
+        })
+    }
 }
 
 // ── Tests ──────────────────────────────────────────────────
@@ -446,4 +685,124 @@ mod tests {
 
         assert!(matches!(result, GroupNodesOutput::Ok { .. }));
     }
+
+    // --- export ---
+
+    async fn put_node(storage: &InMemoryStorage, id: &str, x: f64, y: f64, content: &str) {
+        storage
+            .put(
+                "canvas_node",
+                id,
+                json!({
+                    "node_id": id,
+                    "node_type": "text",
+                    "position_x": x,
+                    "position_y": y,
+                    "content": content,
+                    "group_id": null,
+                }),
+            )
+            .await
+            .unwrap();
+    }
+
+    async fn sample_canvas(storage: &InMemoryStorage) {
+        put_node(storage, "n_bottom", 0.0, 300.0, "Conclusion").await;
+        put_node(storage, "n_right", 400.0, 0.0, "Evidence").await;
+        put_node(storage, "n_left", 0.0, 0.0, "Claim").await;
+        put_node(storage, "n_middle", 200.0, 150.0, "Counterpoint <draft>").await;
+
+        let handler = CanvasHandler;
+        handler
+            .connect_nodes(
+                ConnectNodesInput {
+                    from_id: "n_left".into(),
+                    to_id: "n_right".into(),
+                    label: "supported by".into(),
+                },
+                storage,
+            )
+            .await
+            .unwrap();
+    }
+
+    async fn export(storage: &InMemoryStorage, format: ExportFormat) -> String {
+        let result = CanvasHandler
+            .export(ExportInput { format }, storage)
+            .await
+            .unwrap();
+        match result {
+            ExportOutput::Ok { content, .. } => content,
+        }
+    }
+
+    #[tokio::test]
+    async fn export_markdown_follows_node_coordinates() {
+        let storage = InMemoryStorage::new();
+        sample_canvas(&storage).await;
+
+        let markdown = export(&storage, ExportFormat::Markdown).await;
+        let items: Vec<&str> = markdown
+            .lines()
+            .filter_map(|line| line.strip_prefix("- "))
+            .collect();
+        assert_eq!(
+            items,
+            vec![
+                "<a id=\"n_left\"></a>Claim",
+                "<a id=\"n_right\"></a>Evidence",
+                "<a id=\"n_middle\"></a>Counterpoint &lt;draft&gt;",
+                "<a id=\"n_bottom\"></a>Conclusion",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn export_markdown_renders_edges_as_reference_links() {
+        let storage = InMemoryStorage::new();
+        sample_canvas(&storage).await;
+
+        let markdown = export(&storage, ExportFormat::Markdown).await;
+        assert!(markdown
+            .contains("- <a id=\"n_left\"></a>Claim\n  - supported by: [Evidence][n_right]\n"));
+        assert!(markdown.ends_with("\n[n_right]: #n_right\n"));
+
+        let svg = export(&storage, ExportFormat::Svg).await;
+        assert!(svg.starts_with("<svg "));
+        assert_eq!(svg.matches("<rect ").count(), 4);
+        assert!(svg.contains(">supported by</text>"));
+        assert!(svg.contains("Counterpoint &lt;draft&gt;"));
+    }
+
+    #[tokio::test]
+    async fn export_markdown_escapes_node_ids_in_anchors() {
+        let storage = InMemoryStorage::new();
+        put_node(&storage, "a\"><script>", 0.0, 0.0, "Claim").await;
+
+        let markdown = export(&storage, ExportFormat::Markdown).await;
+        assert!(markdown.contains("- <a id=\"a&quot;&gt;&lt;script&gt;\"></a>Claim\n"));
+        assert!(!markdown.contains("<script>"));
+    }
+
+    #[tokio::test]
+    async fn export_markdown_escapes_node_ids_in_references() {
+        let storage = InMemoryStorage::new();
+        put_node(&storage, "a", 0.0, 0.0, "Claim").await;
+        put_node(&storage, "b] [x y", 0.0, 100.0, "Evidence").await;
+        CanvasHandler
+            .connect_nodes(
+                ConnectNodesInput {
+                    from_id: "a".into(),
+                    to_id: "b] [x y".into(),
+                    label: String::new(),
+                },
+                &storage,
+            )
+            .await
+            .unwrap();
+
+        let markdown = export(&storage, ExportFormat::Markdown).await;
+        assert!(markdown.contains("  - links to: [Evidence][b\\] \\[x y]\n"));
+        assert!(markdown.ends_with("\n[b\\] \\[x y]: #b%5D%20%5Bx%20y\n"));
+    }
 }
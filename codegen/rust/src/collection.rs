@@ -2,10 +2,17 @@
 //
 // Manages concrete and virtual collections of content nodes.
 // See Architecture doc Sections on collection management.
+//
+// Members keep the order they were added in. `CollectionView` resolves the
+// member nodes from storage and backs list views with stable sorting,
+// grouping and pagination.
 
+use crate::query::compare_values;
 use crate::storage::{ConceptStorage, StorageResult};
 use serde::{Deserialize, Serialize};
-use serde_json::json;
+use serde_json::{json, Value};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet};
 
 // ── Create ────────────────────────────────────────────────
 
@@ -76,6 +83,159 @@ pub enum GetMembersOutput {
     NotFound { message: String },
 }
 
+// ── Views ─────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+/// One page of a view. Pages are numbered from 1.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Page {
+    pub items: Vec<Value>,
+    pub page: usize,
+    pub size: usize,
+    pub total: usize,
+    pub total_pages: usize,
+}
+
+/// Look up a dotted field path, e.g. `metadata.tags`, treating null as
+/// missing.
+fn field_value<'a>(record: &'a Value, field: &str) -> Option<&'a Value> {
+    field
+        .split('.')
+        .try_fold(record, |value, key| value.get(key))
+        .filter(|value| !value.is_null())
+}
+
+/// The member records of a collection, in member order until sorted.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CollectionView {
+    items: Vec<Value>,
+}
+
+impl CollectionView {
+    pub fn from_records(items: Vec<Value>) -> Self {
+        Self { items }
+    }
+
+    /// Resolve a collection's members to their `content_node` records.
+    /// Members whose node no longer exists are skipped; `None` when the
+    /// collection does not exist.
+    pub async fn load(
+        collection_id: &str,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<Option<Self>> {
+        if storage.get("collection", collection_id).await?.is_none() {
+            return Ok(None);
+        }
+        let mut items = Vec::new();
+        for node_id in ordered_member_ids(collection_id, storage).await? {
+            if let Some(node) = storage.get("content_node", &node_id).await? {
+                items.push(node);
+            }
+        }
+        Ok(Some(Self { items }))
+    }
+
+    pub fn items(&self) -> &[Value] {
+        &self.items
+    }
+
+    pub fn total(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Stable sort on a field. Records without the field sort last in
+    /// either direction.
+    pub fn sort_by(mut self, field: &str, dir: SortDirection) -> Self {
+        self.items.sort_by(
+            |a, b| match (field_value(a, field), field_value(b, field)) {
+                (Some(a), Some(b)) => match dir {
+                    SortDirection::Asc => compare_values(a, b),
+                    SortDirection::Desc => compare_values(b, a),
+                },
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            },
+        );
+        self
+    }
+
+    /// Group records by a field's value. A record whose field holds an
+    /// array joins the group of each element; records without the field
+    /// are grouped under "". Groups keep the view's order.
+    pub fn group_by(&self, field: &str) -> BTreeMap<String, Vec<Value>> {
+        let key = |value: &Value| match value {
+            Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+        let mut groups: BTreeMap<String, Vec<Value>> = BTreeMap::new();
+        for item in &self.items {
+            let keys: BTreeSet<String> = match field_value(item, field) {
+                None => BTreeSet::from([String::new()]),
+                Some(Value::Array(values)) => values.iter().map(key).collect(),
+                Some(value) => BTreeSet::from([key(value)]),
+            };
+            for k in keys {
+                groups.entry(k).or_default().push(item.clone());
+            }
+        }
+        groups
+    }
+
+    /// The `page`th page of `size` records. Page 0 is treated as page 1.
+    pub fn paginate(&self, page: usize, size: usize) -> Page {
+        let page = page.max(1);
+        let total = self.total();
+        let items = if size == 0 {
+            Vec::new()
+        } else {
+            self.items
+                .iter()
+                .skip((page - 1).saturating_mul(size))
+                .take(size)
+                .cloned()
+                .collect()
+        };
+        Page {
+            items,
+            page,
+            size,
+            total,
+            total_pages: if size == 0 { 0 } else { total.div_ceil(size) },
+        }
+    }
+}
+
+/// Member node ids in the order they were added. Members stored before
+/// positions were recorded come last, by id.
+async fn ordered_member_ids(
+    collection_id: &str,
+    storage: &dyn ConceptStorage,
+) -> StorageResult<Vec<String>> {
+    let mut members = storage
+        .find(
+            "collection_member",
+            Some(&json!({ "collection_id": collection_id })),
+        )
+        .await?;
+    members.sort_by(|a, b| {
+        let position = |m: &Value| m["position"].as_u64().unwrap_or(u64::MAX);
+        position(a)
+            .cmp(&position(b))
+            .then_with(|| a["node_id"].as_str().cmp(&b["node_id"].as_str()))
+    });
+    Ok(members
+        .iter()
+        .filter_map(|m| m["node_id"].as_str().map(String::from))
+        .collect())
+}
+
 // ── Handler ───────────────────────────────────────────────
 
 pub struct CollectionHandler;
@@ -121,6 +281,24 @@ impl CollectionHandler {
         }
 
         let member_key = format!("{}:{}", input.collection_id, input.node_id);
+        // Re-adding a member keeps its place
+        let position = match storage.get("collection_member", &member_key).await? {
+            Some(member) => member["position"].clone(),
+            None => {
+                let members = storage
+                    .find(
+                        "collection_member",
+                        Some(&json!({ "collection_id": input.collection_id })),
+                    )
+                    .await?;
+                let next = members
+                    .iter()
+                    .filter_map(|m| m["position"].as_u64())
+                    .max()
+                    .map_or(0, |max| max + 1);
+                json!(next)
+            }
+        };
         storage
             .put(
                 "collection_member",
@@ -128,6 +306,7 @@ impl CollectionHandler {
                 json!({
                     "collection_id": input.collection_id,
                     "node_id": input.node_id,
+                    "position": position,
                 }),
             )
             .await?;
@@ -174,17 +353,7 @@ impl CollectionHandler {
             });
         }
 
-        let all_members = storage
-            .find(
-                "collection_member",
-                Some(&json!({ "collection_id": input.collection_id })),
-            )
-            .await?;
-
-        let member_ids: Vec<String> = all_members
-            .iter()
-            .filter_map(|m| m["node_id"].as_str().map(String::from))
-            .collect();
+        let member_ids = ordered_member_ids(&input.collection_id, storage).await?;
 
         Ok(GetMembersOutput::Ok {
             collection_id: input.collection_id,
@@ -426,4 +595,117 @@ mod tests {
 
         assert!(matches!(result, GetMembersOutput::NotFound { .. }));
     }
+
+    // --- views ---
+
+    async fn articles_collection(storage: &InMemoryStorage) {
+        let handler = CollectionHandler;
+        handler
+            .create(
+                CreateInput {
+                    name: "articles".into(),
+                    collection_type: "concrete".into(),
+                    schema_id: Some("article".into()),
+                },
+                storage,
+            )
+            .await
+            .unwrap();
+
+        let articles = [
+            ("a1", "2024-03-01T09:00:00Z", json!(["rust", "release"])),
+            ("a2", "2024-01-15T09:00:00Z", json!(["design"])),
+            ("a3", "2024-02-10T09:00:00Z", json!(["rust", "design", "rust"])),
+            ("a4", "2024-01-15T09:00:00Z", json!(null)),
+        ];
+        for (id, created_at, tags) in articles {
+            storage
+                .put(
+                    "content_node",
+                    id,
+                    json!({
+                        "id": id,
+                        "node_type": "article",
+                        "created_at": created_at,
+                        "metadata": { "tags": tags },
+                    }),
+                )
+                .await
+                .unwrap();
+            handler
+                .add_member(
+                    AddMemberInput {
+                        collection_id: "col_articles".into(),
+                        node_id: id.into(),
+                    },
+                    storage,
+                )
+                .await
+                .unwrap();
+        }
+    }
+
+    fn ids(items: &[Value]) -> Vec<&str> {
+        items
+            .iter()
+            .map(|item| item["id"].as_str().unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn view_groups_articles_by_tag() {
+        let storage = InMemoryStorage::new();
+        articles_collection(&storage).await;
+
+        let view = CollectionView::load("col_articles", &storage)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(ids(view.items()), vec!["a1", "a2", "a3", "a4"]);
+
+        let groups = view.group_by("metadata.tags");
+        let keys: Vec<&str> = groups.keys().map(String::as_str).collect();
+        assert_eq!(keys, vec!["", "design", "release", "rust"]);
+        assert_eq!(ids(&groups["rust"]), vec!["a1", "a3"]);
+        assert_eq!(ids(&groups["design"]), vec!["a2", "a3"]);
+        assert_eq!(ids(&groups[""]), vec!["a4"]);
+
+        assert!(CollectionView::load("col_missing", &storage)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn view_sorts_by_created_date_stably_and_paginates() {
+        let storage = InMemoryStorage::new();
+        articles_collection(&storage).await;
+        let view = CollectionView::load("col_articles", &storage)
+            .await
+            .unwrap()
+            .unwrap();
+
+        // a2 and a4 share a date and keep their member order
+        let oldest_first = view.clone().sort_by("created_at", SortDirection::Asc);
+        assert_eq!(ids(oldest_first.items()), vec!["a2", "a4", "a3", "a1"]);
+        let newest_first = view.sort_by("created_at", SortDirection::Desc);
+        assert_eq!(ids(newest_first.items()), vec!["a1", "a3", "a2", "a4"]);
+
+        let page = newest_first.paginate(2, 3);
+        assert_eq!(ids(&page.items), vec!["a4"]);
+        assert_eq!((page.total, page.total_pages), (4, 2));
+        assert!(newest_first.paginate(3, 3).items.is_empty());
+        assert_eq!(newest_first.total(), 4);
+    }
+
+    #[test]
+    fn view_sorts_numeric_text_like_query_filters_compare_it() {
+        let view = CollectionView::from_records(vec![
+            json!({ "id": "a", "rank": "10" }),
+            json!({ "id": "b", "rank": 9 }),
+            json!({ "id": "c", "rank": " 2 " }),
+        ]);
+        let sorted = view.sort_by("rank", SortDirection::Asc);
+        assert_eq!(ids(sorted.items()), vec!["c", "b", "a"]);
+    }
 }
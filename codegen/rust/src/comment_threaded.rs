@@ -6,6 +6,7 @@
 use crate::storage::{ConceptStorage, StorageResult};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::collections::HashMap;
 
 // ── AddComment ────────────────────────────────────────────
 
@@ -89,6 +90,135 @@ pub enum DeleteOutput {
     NotFound { message: String },
 }
 
+// ── Thread loading ────────────────────────────────────────
+
+/// Which slice of replies to load under each comment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ThreadPage {
+    /// Replies of the root to skip, for "load more" at the top level.
+    pub offset: usize,
+    /// Most replies loaded under any one comment.
+    pub limit: usize,
+}
+
+/// A comment with the slice of its reply tree that was loaded.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThreadNode {
+    pub comment: serde_json::Value,
+    pub replies: Vec<ThreadNode>,
+    /// Direct replies not included here, by depth limit or page size.
+    pub has_more: bool,
+    /// All replies below this comment, loaded or not.
+    pub descendant_count: usize,
+}
+
+/// Replies keyed by parent id, each list ordered by `created_at` then id so
+/// pages are stable.
+async fn replies_by_parent(
+    host_node_id: &str,
+    storage: &dyn ConceptStorage,
+) -> StorageResult<HashMap<String, Vec<serde_json::Value>>> {
+    let mut by_parent: HashMap<String, Vec<serde_json::Value>> = HashMap::new();
+    for comment in storage
+        .find("threaded_comment", Some(&json!({ "host_node_id": host_node_id })))
+        .await?
+    {
+        if let Some(parent) = comment["parent_comment_id"].as_str() {
+            by_parent.entry(parent.to_string()).or_default().push(comment);
+        }
+    }
+    for replies in by_parent.values_mut() {
+        replies.sort_by(|a, b| {
+            (a["created_at"].as_str(), a["comment_id"].as_str())
+                .cmp(&(b["created_at"].as_str(), b["comment_id"].as_str()))
+        });
+    }
+    Ok(by_parent)
+}
+
+/// Replies at any depth below each comment in `root`'s subtree, summed
+/// child-first in a single post-order pass.
+fn descendant_counts<'a>(
+    root: &'a str,
+    by_parent: &'a HashMap<String, Vec<serde_json::Value>>,
+) -> HashMap<&'a str, usize> {
+    let reply_id = |reply: &'a serde_json::Value| reply["comment_id"].as_str().unwrap_or("");
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    let mut stack = vec![(root, false)];
+    while let Some((id, children_done)) = stack.pop() {
+        let replies = by_parent.get(id).map(Vec::as_slice).unwrap_or(&[]);
+        if children_done {
+            let count = replies
+                .iter()
+                .map(|reply| 1 + counts.get(reply_id(reply)).copied().unwrap_or(0))
+                .sum();
+            counts.insert(id, count);
+        } else {
+            stack.push((id, true));
+            stack.extend(replies.iter().map(|reply| (reply_id(reply), false)));
+        }
+    }
+    counts
+}
+
+fn build_node(
+    comment: serde_json::Value,
+    depth: usize,
+    max_depth: usize,
+    skip: usize,
+    page: ThreadPage,
+    by_parent: &HashMap<String, Vec<serde_json::Value>>,
+    counts: &HashMap<&str, usize>,
+) -> ThreadNode {
+    let comment_id = comment["comment_id"].as_str().unwrap_or("").to_string();
+    let children = by_parent.get(&comment_id).map(Vec::as_slice).unwrap_or(&[]);
+
+    let replies: Vec<ThreadNode> = if depth < max_depth {
+        children
+            .iter()
+            .skip(skip)
+            .take(page.limit)
+            .map(|child| build_node(child.clone(), depth + 1, max_depth, 0, page, by_parent, counts))
+            .collect()
+    } else {
+        vec![]
+    };
+
+    ThreadNode {
+        has_more: skip + replies.len() < children.len(),
+        descendant_count: counts.get(comment_id.as_str()).copied().unwrap_or(0),
+        comment,
+        replies,
+    }
+}
+
+/// Load `root` and its replies down to `max_depth` levels (0 loads the root
+/// alone), taking at most `page.limit` replies per comment. Returns `None`
+/// when the root comment does not exist.
+pub async fn load_thread(
+    root: &str,
+    max_depth: usize,
+    page: ThreadPage,
+    storage: &dyn ConceptStorage,
+) -> StorageResult<Option<ThreadNode>> {
+    let Some(root_comment) = storage.get("threaded_comment", root).await? else {
+        return Ok(None);
+    };
+    let host_node_id = root_comment["host_node_id"].as_str().unwrap_or("").to_string();
+    let by_parent = replies_by_parent(&host_node_id, storage).await?;
+    let counts = descendant_counts(root, &by_parent);
+    Ok(Some(build_node(root_comment, 0, max_depth, page.offset, page, &by_parent, &counts)))
+}
+
+/// Number of replies at any depth below `comment_id`, for "N more replies".
+pub async fn count_descendants(comment_id: &str, storage: &dyn ConceptStorage) -> StorageResult<usize> {
+    let Some(comment) = storage.get("threaded_comment", comment_id).await? else {
+        return Ok(0);
+    };
+    let by_parent = replies_by_parent(comment["host_node_id"].as_str().unwrap_or(""), storage).await?;
+    Ok(descendant_counts(comment_id, &by_parent)[comment_id])
+}
+
 // ── Handler ───────────────────────────────────────────────
 
 pub struct ThreadedCommentHandler;
@@ -457,4 +587,88 @@ mod tests {
 
         assert!(matches!(result, DeleteOutput::NotFound { .. }));
     }
+
+    // ── thread loading ─────────────────────────────────────
+
+    /// root
+    /// ├── r1 (00:01)
+    /// │   ├── r1a (00:03)
+    /// │   │   └── r1a1 (00:05)
+    /// │   └── r1b (00:04)
+    /// ├── r2 (00:02)
+    /// └── r3 (00:02, same time as r2, ordered by id)
+    async fn three_level_thread(storage: &InMemoryStorage) {
+        let comments = [
+            ("root", None, "00:00"),
+            ("r1", Some("root"), "00:01"),
+            ("r3", Some("root"), "00:02"),
+            ("r2", Some("root"), "00:02"),
+            ("r1a", Some("r1"), "00:03"),
+            ("r1b", Some("r1"), "00:04"),
+            ("r1a1", Some("r1a"), "00:05"),
+        ];
+        for (id, parent, time) in comments {
+            storage
+                .put(
+                    "threaded_comment",
+                    id,
+                    json!({
+                        "comment_id": id,
+                        "host_node_id": "post1",
+                        "parent_comment_id": parent,
+                        "content": id,
+                        "author": "u",
+                        "published": true,
+                        "created_at": format!("2024-01-01T00:{}Z", time),
+                    }),
+                )
+                .await
+                .unwrap();
+        }
+    }
+
+    fn ids(node: &ThreadNode) -> Vec<&str> {
+        node.replies.iter().map(|r| r.comment["comment_id"].as_str().unwrap()).collect()
+    }
+
+    #[tokio::test]
+    async fn load_thread_limits_depth_and_counts_descendants() {
+        let storage = InMemoryStorage::new();
+        three_level_thread(&storage).await;
+
+        let page = ThreadPage { offset: 0, limit: 10 };
+        let thread = load_thread("root", 2, page, &storage).await.unwrap().unwrap();
+        assert_eq!(ids(&thread), vec!["r1", "r2", "r3"]);
+        assert_eq!(thread.descendant_count, 6);
+        assert!(!thread.has_more);
+
+        let r1 = &thread.replies[0];
+        assert_eq!(ids(r1), vec!["r1a", "r1b"]);
+        assert_eq!(r1.descendant_count, 3);
+
+        // r1a sits at the depth limit: its reply is counted but not loaded
+        let r1a = &r1.replies[0];
+        assert!(r1a.replies.is_empty());
+        assert!(r1a.has_more);
+        assert_eq!(r1a.descendant_count, 1);
+
+        assert_eq!(count_descendants("r1", &storage).await.unwrap(), 3);
+        assert_eq!(count_descendants("r2", &storage).await.unwrap(), 0);
+        assert!(load_thread("missing", 2, page, &storage).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn load_thread_pages_top_level_replies() {
+        let storage = InMemoryStorage::new();
+        three_level_thread(&storage).await;
+
+        let first = load_thread("root", 1, ThreadPage { offset: 0, limit: 2 }, &storage).await.unwrap().unwrap();
+        assert_eq!(ids(&first), vec!["r1", "r2"]);
+        assert!(first.has_more);
+        assert!(first.replies[0].has_more);
+
+        let second = load_thread("root", 1, ThreadPage { offset: 2, limit: 2 }, &storage).await.unwrap().unwrap();
+        assert_eq!(ids(&second), vec!["r3"]);
+        assert!(!second.has_more);
+    }
 }
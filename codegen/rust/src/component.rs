@@ -3,10 +3,18 @@
 // Layout suite — registers components with configuration, places them
 // in regions with weight ordering, manages visibility conditions,
 // and renders component output.
+//
+// Components also compose: a `Component` template declares props and
+// named slots (`{{title}}`, `{{slot:body}}`), slots are filled with child
+// components, and `render` validates each component's props against its
+// declared schema before filling the template.
 
+use crate::escape::escape_html;
 use crate::storage::{ConceptStorage, StorageResult};
 use serde::{Deserialize, Serialize};
-use serde_json::json;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::fmt;
 
 // ── Register ──────────────────────────────────────────────
 
@@ -93,6 +101,223 @@ pub enum ComponentRenderOutput {
     NotFound { message: String },
 }
 
+// ── Composition ───────────────────────────────────────────
+
+/// A declared prop. `prop_type` is one of string, number, boolean, array,
+/// object or any.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PropSpec {
+    pub name: String,
+    pub prop_type: String,
+    pub required: bool,
+    pub default: Option<Value>,
+}
+
+impl PropSpec {
+    pub fn required(name: &str, prop_type: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            prop_type: prop_type.to_string(),
+            required: true,
+            default: None,
+        }
+    }
+
+    pub fn optional(name: &str, prop_type: &str, default: Option<Value>) -> Self {
+        Self {
+            name: name.to_string(),
+            prop_type: prop_type.to_string(),
+            required: false,
+            default,
+        }
+    }
+
+    fn accepts(&self, value: &Value) -> bool {
+        match self.prop_type.as_str() {
+            "string" => value.is_string(),
+            "number" => value.is_number(),
+            "boolean" => value.is_boolean(),
+            "array" => value.is_array(),
+            "object" => value.is_object(),
+            _ => true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ComponentError {
+    MissingProp {
+        component: String,
+        prop: String,
+    },
+    InvalidProp {
+        component: String,
+        prop: String,
+        expected: String,
+    },
+    UnknownSlot {
+        component: String,
+        slot: String,
+    },
+}
+
+impl fmt::Display for ComponentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ComponentError::MissingProp { component, prop } => {
+                write!(f, "component '{}' requires prop '{}'", component, prop)
+            }
+            ComponentError::InvalidProp {
+                component,
+                prop,
+                expected,
+            } => write!(
+                f,
+                "prop '{}' of component '{}' must be {}",
+                prop, component, expected
+            ),
+            ComponentError::UnknownSlot { component, slot } => {
+                write!(f, "component '{}' has no slot '{}'", component, slot)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ComponentError {}
+
+/// A child placed in a slot, with the props it renders with.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlotChild {
+    pub component: Component,
+    pub props: Value,
+}
+
+/// A component template plus the children filling its slots. Templates
+/// use `{{prop}}` for escaped prop values and `{{slot:name}}` for slots.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Component {
+    pub name: String,
+    pub template: String,
+    pub props: Vec<PropSpec>,
+    pub slots: BTreeMap<String, Vec<SlotChild>>,
+}
+
+impl Component {
+    pub fn new(name: &str, template: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            template: template.to_string(),
+            props: Vec::new(),
+            slots: BTreeMap::new(),
+        }
+    }
+
+    pub fn prop(mut self, spec: PropSpec) -> Self {
+        self.props.push(spec);
+        self
+    }
+
+    /// Append `child` to `slot`; children render in the order added.
+    pub fn fill(mut self, slot: &str, child: Component, props: Value) -> Self {
+        self.slots
+            .entry(slot.to_string())
+            .or_default()
+            .push(SlotChild {
+                component: child,
+                props,
+            });
+        self
+    }
+
+    /// Slot names the template declares, in template order.
+    pub fn declared_slots(&self) -> Vec<&str> {
+        placeholders(&self.template)
+            .filter_map(|name| name.strip_prefix("slot:"))
+            .collect()
+    }
+
+    /// Check `props` against the prop schema, returning them with defaults
+    /// applied.
+    pub fn validate_props(&self, props: &Value) -> Result<BTreeMap<String, Value>, ComponentError> {
+        let mut resolved = BTreeMap::new();
+        for spec in &self.props {
+            let value = props
+                .get(&spec.name)
+                .filter(|v| !v.is_null())
+                .or(spec.default.as_ref());
+            match value {
+                None if spec.required => {
+                    return Err(ComponentError::MissingProp {
+                        component: self.name.clone(),
+                        prop: spec.name.clone(),
+                    })
+                }
+                None => {}
+                Some(value) if !spec.accepts(value) => {
+                    return Err(ComponentError::InvalidProp {
+                        component: self.name.clone(),
+                        prop: spec.name.clone(),
+                        expected: spec.prop_type.clone(),
+                    })
+                }
+                Some(value) => {
+                    resolved.insert(spec.name.clone(), value.clone());
+                }
+            }
+        }
+        Ok(resolved)
+    }
+}
+
+/// The names inside `{{...}}` placeholders, trimmed.
+fn placeholders(template: &str) -> impl Iterator<Item = &str> {
+    template
+        .split("{{")
+        .skip(1)
+        .filter_map(|part| part.split_once("}}").map(|(name, _)| name.trim()))
+}
+
+/// Render `component` with `props`, rendering slot children depth-first.
+/// Unfilled slots and undeclared props render empty.
+pub fn render(component: &Component, props: &Value) -> Result<String, ComponentError> {
+    let resolved = component.validate_props(props)?;
+    let declared = component.declared_slots();
+    if let Some(slot) = component
+        .slots
+        .keys()
+        .find(|slot| !declared.contains(&slot.as_str()))
+    {
+        return Err(ComponentError::UnknownSlot {
+            component: component.name.clone(),
+            slot: slot.clone(),
+        });
+    }
+
+    let mut out = String::new();
+    let mut rest = component.template.as_str();
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start..].find("}}") else {
+            break;
+        };
+        out.push_str(&rest[..start]);
+        let name = rest[start + 2..start + len].trim();
+        if let Some(slot) = name.strip_prefix("slot:") {
+            for child in component.slots.get(slot).into_iter().flatten() {
+                out.push_str(&render(&child.component, &child.props)?);
+            }
+        } else if let Some(value) = resolved.get(name) {
+            match value {
+                Value::String(s) => out.push_str(&escape_html(s)),
+                other => out.push_str(&escape_html(&other.to_string())),
+            }
+        }
+        rest = &rest[start + len + 2..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
 // ── Handler ───────────────────────────────────────────────
 
 pub struct ComponentHandler;
@@ -493,4 +718,65 @@ mod tests {
 
         assert!(matches!(result, ComponentRenderOutput::NotFound { .. }));
     }
+
+    // --- composition ---
+
+    fn card() -> Component {
+        Component::new(
+            "card",
+            "<article class=\"card {{variant}}\"><header>{{slot:header}}</header><div>{{slot:body}}</div></article>",
+        )
+        .prop(PropSpec::optional("variant", "string", Some(json!("plain"))))
+    }
+
+    fn heading() -> Component {
+        Component::new("heading", "<h2>{{text}}</h2>").prop(PropSpec::required("text", "string"))
+    }
+
+    fn paragraph() -> Component {
+        Component::new("paragraph", "<p>{{text}}</p>").prop(PropSpec::required("text", "string"))
+    }
+
+    #[test]
+    fn render_fills_card_header_and_body_slots() {
+        let card = card()
+            .fill("header", heading(), json!({ "text": "Q&A" }))
+            .fill("body", paragraph(), json!({ "text": "First" }))
+            .fill("body", paragraph(), json!({ "text": "Second" }));
+        assert_eq!(card.declared_slots(), vec!["header", "body"]);
+
+        let html = render(&card, &json!({})).unwrap();
+        assert_eq!(
+            html,
+            "<article class=\"card plain\"><header><h2>Q&amp;A</h2></header>\
+             <div><p>First</p><p>Second</p></div></article>"
+        );
+
+        let featured = render(&card, &json!({ "variant": "featured" })).unwrap();
+        assert!(featured.starts_with("<article class=\"card featured\">"));
+
+        let misplaced = card.fill("footer", paragraph(), json!({ "text": "x" }));
+        assert!(matches!(
+            render(&misplaced, &json!({})),
+            Err(ComponentError::UnknownSlot { slot, .. }) if slot == "footer"
+        ));
+    }
+
+    #[test]
+    fn render_rejects_missing_required_prop() {
+        let card = card().fill("header", heading(), json!({}));
+
+        let err = render(&card, &json!({})).unwrap_err();
+        assert_eq!(
+            err,
+            ComponentError::MissingProp {
+                component: "heading".into(),
+                prop: "text".into(),
+            }
+        );
+        assert_eq!(err.to_string(), "component 'heading' requires prop 'text'");
+
+        let err = render(&heading(), &json!({ "text": 42 })).unwrap_err();
+        assert!(matches!(err, ComponentError::InvalidProp { .. }));
+    }
 }
@@ -2,8 +2,17 @@
 //
 // Manages content format registration, parsing, and extraction of
 // references and tags from content.
+//
+// Parsing turns raw content into a tree of `ContentNode`s (headings holding
+// their sections, paragraphs, lists, code blocks, tables). Parsers are registered by
+// mime type; markdown goes through pulldown-cmark, HTML through an html5ever
+// DOM, and anything else is split into paragraphs.
+
+use std::collections::BTreeMap;
 
 use crate::storage::{ConceptStorage, StorageResult};
+use pulldown_cmark::{CodeBlockKind, Event, Options, Tag, TagEnd};
+use scraper::{ElementRef, Html};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
@@ -74,6 +83,480 @@ pub enum ExtractTagsOutput {
     Ok { tags: String },
 }
 
+// --- Content nodes ---
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContentNode {
+    /// A heading and everything up to the next heading of the same or a
+    /// higher level.
+    Heading {
+        level: u8,
+        text: String,
+        children: Vec<ContentNode>,
+    },
+    Paragraph {
+        text: String,
+    },
+    List {
+        ordered: bool,
+        items: Vec<ListItem>,
+    },
+    Code {
+        language: Option<String>,
+        code: String,
+    },
+    /// A pipe table; it ends at the first line without a pipe.
+    Table {
+        header: Vec<String>,
+        rows: Vec<Vec<String>>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ListItem {
+    pub text: String,
+    /// Nested lists and code blocks inside the item.
+    pub children: Vec<ContentNode>,
+}
+
+impl ListItem {
+    fn new() -> Self {
+        ListItem {
+            text: String::new(),
+            children: Vec::new(),
+        }
+    }
+}
+
+pub trait FormatParser: Send + Sync {
+    /// Flat blocks in document order; headings have no children yet.
+    fn parse_blocks(&self, content: &str) -> Vec<ContentNode>;
+}
+
+/// Parsers by mime type.
+pub struct ParserRegistry {
+    parsers: BTreeMap<String, Box<dyn FormatParser>>,
+}
+
+impl ParserRegistry {
+    pub fn empty() -> Self {
+        ParserRegistry {
+            parsers: BTreeMap::new(),
+        }
+    }
+
+    pub fn register(&mut self, mime: &str, parser: impl FormatParser + 'static) -> &mut Self {
+        self.parsers
+            .insert(normalize_mime(mime).to_string(), Box::new(parser));
+        self
+    }
+
+    /// Parse `content` with the parser for `mime`. A missing or
+    /// `application/octet-stream` mime is sniffed with `detect_mime`, and
+    /// unregistered types are read as plain text.
+    pub fn parse(&self, content: &str, mime: &str) -> Vec<ContentNode> {
+        let mime = match normalize_mime(mime) {
+            "" | "application/octet-stream" => detect_mime(content),
+            other => other,
+        };
+        let blocks = match self.parsers.get(mime) {
+            Some(parser) => parser.parse_blocks(content),
+            None => PlainTextParser.parse_blocks(content),
+        };
+        nest_sections(blocks)
+    }
+}
+
+impl Default for ParserRegistry {
+    fn default() -> Self {
+        let mut registry = ParserRegistry::empty();
+        registry
+            .register("text/markdown", MarkdownParser)
+            .register("text/x-markdown", MarkdownParser)
+            .register("text/html", HtmlParser)
+            .register("application/xhtml+xml", HtmlParser)
+            .register("text/plain", PlainTextParser);
+        registry
+    }
+}
+
+/// Parse `content` with the built-in parsers.
+pub fn parse(content: &str, mime: &str) -> Vec<ContentNode> {
+    ParserRegistry::default().parse(content, mime)
+}
+
+fn normalize_mime(mime: &str) -> &str {
+    mime.split(';').next().unwrap_or("").trim()
+}
+
+/// Guess the mime type of untyped content.
+pub fn detect_mime(content: &str) -> &'static str {
+    let head = content.trim_start().to_ascii_lowercase();
+    if head.starts_with("<!doctype html") || head.starts_with("<html") || head.contains("<body") {
+        return "text/html";
+    }
+    let markdown = content.lines().any(|line| {
+        let line = line.trim_start();
+        line.starts_with("```")
+            || line.trim_start_matches('#').starts_with(' ') && line.starts_with('#')
+            || line.starts_with("- ")
+            || line.starts_with("* ")
+    });
+    if markdown {
+        "text/markdown"
+    } else {
+        "text/plain"
+    }
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Move the blocks following each heading under it, closing a section at
+/// the next heading of the same or a higher level.
+fn nest_sections(blocks: Vec<ContentNode>) -> Vec<ContentNode> {
+    fn close(open: &mut Vec<ContentNode>, root: &mut Vec<ContentNode>) {
+        if let Some(section) = open.pop() {
+            match open.last_mut() {
+                Some(ContentNode::Heading { children, .. }) => children.push(section),
+                _ => root.push(section),
+            }
+        }
+    }
+
+    let mut root = Vec::new();
+    let mut open: Vec<ContentNode> = Vec::new();
+    for block in blocks {
+        if let ContentNode::Heading { level, .. } = block {
+            while matches!(open.last(), Some(ContentNode::Heading { level: l, .. }) if *l >= level)
+            {
+                close(&mut open, &mut root);
+            }
+            open.push(block);
+        } else {
+            match open.last_mut() {
+                Some(ContentNode::Heading { children, .. }) => children.push(block),
+                _ => root.push(block),
+            }
+        }
+    }
+    while !open.is_empty() {
+        close(&mut open, &mut root);
+    }
+    root
+}
+
+/// GFM keeps a table going through any non-blank line, so text written
+/// straight after a table would become one of its rows. End each table at
+/// its first line without a pipe by putting a blank line before it.
+fn end_tables(content: &str) -> String {
+    let is_delimiter_row = |line: &str| {
+        let cells = line.trim().trim_matches('|');
+        line.contains('|')
+            && cells.split('|').all(|cell| {
+                let dashes = cell.trim().trim_start_matches(':').trim_end_matches(':');
+                !dashes.is_empty() && dashes.chars().all(|c| c == '-')
+            })
+    };
+
+    let mut out = String::with_capacity(content.len());
+    let (mut in_table, mut in_fence) = (false, false);
+    for line in content.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            in_table = false;
+        } else if !in_fence {
+            if in_table && !line.contains('|') {
+                if !line.trim().is_empty() {
+                    out.push('\n');
+                }
+                in_table = false;
+            } else if !in_table && is_delimiter_row(line) {
+                in_table = true;
+            }
+        }
+        out.push_str(line);
+    }
+    out
+}
+
+pub struct MarkdownParser;
+
+impl FormatParser for MarkdownParser {
+    fn parse_blocks(&self, content: &str) -> Vec<ContentNode> {
+        let mut blocks = Vec::new();
+        let mut text = String::new();
+        let mut lists: Vec<(bool, Vec<ListItem>)> = Vec::new();
+        let mut code_language: Option<Option<String>> = None;
+        let mut table: Option<(Vec<String>, Vec<Vec<String>>)> = None;
+        let mut row: Vec<String> = Vec::new();
+
+        // Append pending inline text to the innermost list item.
+        fn flush_item(lists: &mut [(bool, Vec<ListItem>)], text: &mut String) {
+            let pending = collapse_whitespace(&std::mem::take(text));
+            if let Some(item) = lists.last_mut().and_then(|(_, items)| items.last_mut()) {
+                if !pending.is_empty() {
+                    if !item.text.is_empty() {
+                        item.text.push(' ');
+                    }
+                    item.text.push_str(&pending);
+                }
+            }
+        }
+
+        let content = end_tables(content);
+        for event in pulldown_cmark::Parser::new_ext(&content, Options::ENABLE_TABLES) {
+            match event {
+                Event::Start(Tag::List(start)) => {
+                    flush_item(&mut lists, &mut text);
+                    lists.push((start.is_some(), Vec::new()));
+                }
+                Event::End(TagEnd::List(_)) => {
+                    let Some((ordered, items)) = lists.pop() else {
+                        continue;
+                    };
+                    let node = ContentNode::List { ordered, items };
+                    match lists.last_mut().and_then(|(_, items)| items.last_mut()) {
+                        Some(parent) => parent.children.push(node),
+                        None => blocks.push(node),
+                    }
+                }
+                Event::Start(Tag::Item) => {
+                    if let Some((_, items)) = lists.last_mut() {
+                        items.push(ListItem::new());
+                    }
+                }
+                Event::End(TagEnd::Item) => flush_item(&mut lists, &mut text),
+                Event::End(TagEnd::Paragraph) => {
+                    if lists.is_empty() {
+                        let paragraph = collapse_whitespace(&std::mem::take(&mut text));
+                        if !paragraph.is_empty() {
+                            blocks.push(ContentNode::Paragraph { text: paragraph });
+                        }
+                    } else {
+                        flush_item(&mut lists, &mut text);
+                    }
+                }
+                Event::End(TagEnd::Heading(level)) => blocks.push(ContentNode::Heading {
+                    level: level as u8,
+                    text: collapse_whitespace(&std::mem::take(&mut text)),
+                    children: Vec::new(),
+                }),
+                Event::Start(Tag::CodeBlock(kind)) => {
+                    flush_item(&mut lists, &mut text);
+                    code_language = Some(match kind {
+                        CodeBlockKind::Fenced(info) => {
+                            info.split_whitespace().next().map(str::to_string)
+                        }
+                        CodeBlockKind::Indented => None,
+                    });
+                }
+                Event::End(TagEnd::CodeBlock) => {
+                    let node = ContentNode::Code {
+                        language: code_language.take().flatten(),
+                        code: std::mem::take(&mut text).trim_end_matches('\n').to_string(),
+                    };
+                    match lists.last_mut().and_then(|(_, items)| items.last_mut()) {
+                        Some(item) => item.children.push(node),
+                        None => blocks.push(node),
+                    }
+                }
+                Event::Start(Tag::Table(_)) => {
+                    flush_item(&mut lists, &mut text);
+                    table = Some((Vec::new(), Vec::new()));
+                }
+                Event::End(TagEnd::TableCell) => row.push(collapse_whitespace(&std::mem::take(&mut text))),
+                Event::End(TagEnd::TableHead) => {
+                    if let Some((header, _)) = table.as_mut() {
+                        *header = std::mem::take(&mut row);
+                    }
+                }
+                Event::End(TagEnd::TableRow) => {
+                    if let Some((_, rows)) = table.as_mut() {
+                        rows.push(std::mem::take(&mut row));
+                    }
+                }
+                Event::End(TagEnd::Table) => {
+                    let Some((header, rows)) = table.take() else {
+                        continue;
+                    };
+                    let node = ContentNode::Table { header, rows };
+                    match lists.last_mut().and_then(|(_, items)| items.last_mut()) {
+                        Some(item) => item.children.push(node),
+                        None => blocks.push(node),
+                    }
+                }
+                Event::Text(chunk) | Event::Code(chunk) => text.push_str(&chunk),
+                Event::SoftBreak => text.push(' '),
+                Event::HardBreak => text.push('\n'),
+                _ => {}
+            }
+        }
+        blocks
+    }
+}
+
+pub struct HtmlParser;
+
+const HTML_INLINE: &[&str] = &[
+    "a", "abbr", "b", "bdi", "bdo", "br", "cite", "code", "data", "dfn", "em", "i", "kbd", "mark",
+    "q", "s", "samp", "small", "span", "strong", "sub", "sup", "time", "u", "var", "wbr",
+];
+
+const HTML_SKIPPED: &[&str] = &[
+    "head", "script", "style", "template", "noscript", "svg", "iframe",
+];
+
+impl HtmlParser {
+    fn walk(element: ElementRef, blocks: &mut Vec<ContentNode>) {
+        let mut loose = String::new();
+        let flush = |loose: &mut String, blocks: &mut Vec<ContentNode>| {
+            let text = collapse_whitespace(&std::mem::take(loose));
+            if !text.is_empty() {
+                blocks.push(ContentNode::Paragraph { text });
+            }
+        };
+
+        for child in element.children() {
+            let Some(child_element) = ElementRef::wrap(child) else {
+                if let Some(text) = child.value().as_text() {
+                    loose.push_str(text);
+                }
+                continue;
+            };
+            let name = child_element.value().name();
+            if HTML_INLINE.contains(&name) {
+                loose.push_str(&Self::text(child_element));
+                continue;
+            }
+            flush(&mut loose, blocks);
+            match name {
+                "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => blocks.push(ContentNode::Heading {
+                    level: name[1..].parse().unwrap_or(1),
+                    text: collapse_whitespace(&Self::text(child_element)),
+                    children: Vec::new(),
+                }),
+                "p" => {
+                    let text = collapse_whitespace(&Self::text(child_element));
+                    if !text.is_empty() {
+                        blocks.push(ContentNode::Paragraph { text });
+                    }
+                }
+                "ul" | "ol" => blocks.push(Self::list(child_element)),
+                "pre" => blocks.push(Self::code(child_element)),
+                _ if HTML_SKIPPED.contains(&name) => {}
+                _ => Self::walk(child_element, blocks),
+            }
+        }
+        flush(&mut loose, blocks);
+    }
+
+    fn text(element: ElementRef) -> String {
+        element.text().collect()
+    }
+
+    fn list(element: ElementRef) -> ContentNode {
+        let items = element
+            .children()
+            .filter_map(ElementRef::wrap)
+            .filter(|child| child.value().name() == "li")
+            .map(|li| {
+                let mut item = ListItem::new();
+                let mut text = String::new();
+                for child in li.children() {
+                    match ElementRef::wrap(child) {
+                        Some(nested) if matches!(nested.value().name(), "ul" | "ol") => {
+                            item.children.push(Self::list(nested))
+                        }
+                        Some(nested) if nested.value().name() == "pre" => {
+                            item.children.push(Self::code(nested))
+                        }
+                        Some(nested) => text.push_str(&Self::text(nested)),
+                        None => text.push_str(child.value().as_text().map_or("", |t| t)),
+                    }
+                }
+                item.text = collapse_whitespace(&text);
+                item
+            })
+            .collect();
+        ContentNode::List {
+            ordered: element.value().name() == "ol",
+            items,
+        }
+    }
+
+    fn code(pre: ElementRef) -> ContentNode {
+        let language = pre
+            .children()
+            .filter_map(ElementRef::wrap)
+            .find(|child| child.value().name() == "code")
+            .and_then(|code| {
+                code.value().classes().find_map(|class| {
+                    class
+                        .strip_prefix("language-")
+                        .or_else(|| class.strip_prefix("lang-"))
+                        .map(str::to_string)
+                })
+            });
+        ContentNode::Code {
+            language,
+            code: pre
+                .text()
+                .collect::<String>()
+                .trim_end_matches('\n')
+                .to_string(),
+        }
+    }
+}
+
+impl FormatParser for HtmlParser {
+    fn parse_blocks(&self, content: &str) -> Vec<ContentNode> {
+        let document = Html::parse_document(content);
+        let mut blocks = Vec::new();
+        Self::walk(document.root_element(), &mut blocks);
+        blocks
+    }
+}
+
+pub struct PlainTextParser;
+
+impl FormatParser for PlainTextParser {
+    fn parse_blocks(&self, content: &str) -> Vec<ContentNode> {
+        let mut blocks = Vec::new();
+        let mut lines: Vec<&str> = Vec::new();
+        for line in content.lines().chain(std::iter::once("")) {
+            if line.trim().is_empty() {
+                if !lines.is_empty() {
+                    blocks.push(ContentNode::Paragraph {
+                        text: collapse_whitespace(&lines.join(" ")),
+                    });
+                    lines.clear();
+                }
+            } else {
+                lines.push(line);
+            }
+        }
+        blocks
+    }
+}
+
+/// Mime type for a registered format: the `mime` in its parser config, or
+/// one implied by well-known format ids. Empty means sniff the content.
+fn format_mime<'a>(format_id: &str, parser_config: &'a serde_json::Value) -> &'a str {
+    if let Some(mime) = parser_config["mime"].as_str() {
+        return mime;
+    }
+    match format_id {
+        "markdown" | "md" => "text/markdown",
+        "html" => "text/html",
+        "plain" | "text" | "txt" => "text/plain",
+        _ => "",
+    }
+}
+
 pub struct ContentParserHandler;
 
 impl ContentParserHandler {
@@ -111,23 +594,24 @@ impl ContentParserHandler {
         input: ParseInput,
         storage: &dyn ConceptStorage,
     ) -> StorageResult<ParseOutput> {
-        let existing = storage.get("format", &input.format_id).await?;
-        if existing.is_none() {
+        let Some(format) = storage.get("format", &input.format_id).await? else {
             return Ok(ParseOutput::UnknownFormat {
                 format_id: input.format_id,
             });
-        }
+        };
 
-        // Produce a simple AST representation as JSON
+        let mime = format_mime(&input.format_id, &format["parser_config"]);
+        let nodes = parse(&input.content, mime);
         let ast = json!({
             "type": "document",
             "format": input.format_id,
-            "content": input.content,
+            "children": nodes,
         });
 
         let extracted_metadata = json!({
             "length": input.content.len(),
             "format": input.format_id,
+            "top_level_nodes": nodes.len(),
         });
 
         Ok(ParseOutput::Ok {
@@ -401,4 +885,162 @@ mod tests {
             }
         }
     }
+
+    // --- content nodes ---
+
+    #[test]
+    fn markdown_parses_into_section_tree() {
+        let doc = "\
+# Guide
+
+Intro text
+spanning lines.
+
+## Install
+
+- download
+- run `setup`
+  1. accept
+
+```rust
+fn main() {}
+```
+
+## Use
+
+Done.
+";
+        let para = |text: &str| ContentNode::Paragraph { text: text.into() };
+        let expected = vec![ContentNode::Heading {
+            level: 1,
+            text: "Guide".into(),
+            children: vec![
+                para("Intro text spanning lines."),
+                ContentNode::Heading {
+                    level: 2,
+                    text: "Install".into(),
+                    children: vec![
+                        ContentNode::List {
+                            ordered: false,
+                            items: vec![
+                                ListItem {
+                                    text: "download".into(),
+                                    children: vec![],
+                                },
+                                ListItem {
+                                    text: "run setup".into(),
+                                    children: vec![ContentNode::List {
+                                        ordered: true,
+                                        items: vec![ListItem {
+                                            text: "accept".into(),
+                                            children: vec![],
+                                        }],
+                                    }],
+                                },
+                            ],
+                        },
+                        ContentNode::Code {
+                            language: Some("rust".into()),
+                            code: "fn main() {}".into(),
+                        },
+                    ],
+                },
+                ContentNode::Heading {
+                    level: 2,
+                    text: "Use".into(),
+                    children: vec![para("Done.")],
+                },
+            ],
+        }];
+
+        assert_eq!(parse(doc, "text/markdown; charset=utf-8"), expected);
+        assert_eq!(parse(doc, ""), expected);
+    }
+
+    #[test]
+    fn markdown_table_ends_at_first_line_without_a_pipe() {
+        let doc = "\
+| Name | Role |
+|------|:----:|
+| Ada  | lead |
+| Bob  | dev  |
+Text right after the table.
+
+```
+| a |
+|---|
+not a table row
+```
+";
+        assert_eq!(
+            parse(doc, "text/markdown"),
+            vec![
+                ContentNode::Table {
+                    header: vec!["Name".into(), "Role".into()],
+                    rows: vec![
+                        vec!["Ada".into(), "lead".into()],
+                        vec!["Bob".into(), "dev".into()],
+                    ],
+                },
+                ContentNode::Paragraph {
+                    text: "Text right after the table.".into(),
+                },
+                ContentNode::Code {
+                    language: None,
+                    code: "| a |\n|---|\nnot a table row".into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn html_and_plain_text_parse_into_nodes() {
+        let html = r#"<!DOCTYPE html><html><head><title>x</title><script>var a;</script></head>
+<body><article><h2>News</h2><p>Hello <b>there</b>.</p>
+<ol><li>one</li><li>two</li></ol>
+<pre><code class="language-sh">ls -la
+</code></pre></article></body></html>"#;
+        assert_eq!(
+            parse(html, "text/html"),
+            vec![ContentNode::Heading {
+                level: 2,
+                text: "News".into(),
+                children: vec![
+                    ContentNode::Paragraph {
+                        text: "Hello there.".into()
+                    },
+                    ContentNode::List {
+                        ordered: true,
+                        items: vec![
+                            ListItem {
+                                text: "one".into(),
+                                children: vec![],
+                            },
+                            ListItem {
+                                text: "two".into(),
+                                children: vec![],
+                            },
+                        ],
+                    },
+                    ContentNode::Code {
+                        language: Some("sh".into()),
+                        code: "ls -la".into(),
+                    },
+                ],
+            }]
+        );
+
+        let plain = parse("first line\nsecond line\n\n\nnext", "text/plain");
+        assert_eq!(
+            plain,
+            vec![
+                ContentNode::Paragraph {
+                    text: "first line second line".into()
+                },
+                ContentNode::Paragraph {
+                    text: "next".into()
+                },
+            ]
+        );
+    }
 }
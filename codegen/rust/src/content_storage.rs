@@ -1,10 +1,19 @@
 // ContentStorage Concept Implementation (Rust)
 //
 // Persistence layer for content nodes — save, load, delete, and query operations.
+//
+// Raw bytes (captured files, version snapshots) go through the
+// content-addressed blob store instead: blobs are keyed by their SHA-256, so
+// identical content is kept once and reference-counted.
+
+use std::fmt;
 
 use crate::storage::{ConceptStorage, StorageResult};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use sha2::{Digest, Sha256};
 
 // --- Save ---
 
@@ -70,9 +79,148 @@ pub enum QueryOutput {
     Ok { results: String },
 }
 
+// --- Content-addressed blobs ---
+
+/// Hex SHA-256 of a blob's bytes, which is also its storage key.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ContentHash(pub String);
+
+impl ContentHash {
+    pub fn of(bytes: &[u8]) -> Self {
+        ContentHash(
+            Sha256::digest(bytes)
+                .iter()
+                .map(|b| format!("{:02x}", b))
+                .collect(),
+        )
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for ContentHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlobDelete {
+    /// Other references remain; the bytes are kept.
+    Released {
+        refcount: u64,
+    },
+    /// The last reference was dropped and the bytes removed.
+    Freed,
+    NotFound,
+}
+
 pub struct ContentStorageHandler;
 
 impl ContentStorageHandler {
+    /// Store `bytes` by content hash. Storing bytes that are already present
+    /// adds a reference instead of a second copy, retrying the increment if
+    /// another writer changed the count first.
+    pub async fn put_blob(
+        &self,
+        bytes: &[u8],
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<ContentHash> {
+        let hash = ContentHash::of(bytes);
+        loop {
+            let Some(existing) = storage.get("content_blob", hash.as_str()).await? else {
+                let record = json!({
+                    "hash": hash.as_str(),
+                    "data": BASE64.encode(bytes),
+                    "size": bytes.len(),
+                    "refcount": 1,
+                    "created_at": chrono::Utc::now().to_rfc3339(),
+                });
+                storage.put("content_blob", hash.as_str(), record).await?;
+                return Ok(hash);
+            };
+            let refcount = existing["refcount"].as_u64().unwrap_or(0);
+            if refcount == 0 {
+                // delete_blob has claimed the last reference; wait for the
+                // record to go rather than reviving bytes about to be removed.
+                tokio::task::yield_now().await;
+                continue;
+            }
+            let mut updated = existing.clone();
+            updated["refcount"] = json!(refcount + 1);
+            if storage
+                .compare_and_swap("content_blob", hash.as_str(), &existing, updated)
+                .await?
+            {
+                return Ok(hash);
+            }
+        }
+    }
+
+    pub async fn get_blob(
+        &self,
+        hash: &ContentHash,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<Option<Vec<u8>>> {
+        match storage.get("content_blob", hash.as_str()).await? {
+            None => Ok(None),
+            Some(record) => {
+                let data = record["data"].as_str().unwrap_or("");
+                Ok(Some(BASE64.decode(data)?))
+            }
+        }
+    }
+
+    /// Number of references held on a blob; zero when it is not stored.
+    pub async fn blob_refcount(
+        &self,
+        hash: &ContentHash,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<u64> {
+        Ok(storage
+            .get("content_blob", hash.as_str())
+            .await?
+            .and_then(|record| record["refcount"].as_u64())
+            .unwrap_or(0))
+    }
+
+    /// Drop one reference, removing the bytes once none remain. The count is
+    /// updated with compare-and-swap, so concurrent puts and deletes of the
+    /// same bytes never lose a reference.
+    pub async fn delete_blob(
+        &self,
+        hash: &ContentHash,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<BlobDelete> {
+        loop {
+            let Some(existing) = storage.get("content_blob", hash.as_str()).await? else {
+                return Ok(BlobDelete::NotFound);
+            };
+            let current = existing["refcount"].as_u64().unwrap_or(1);
+            if current == 0 {
+                // Another delete is already freeing it
+                return Ok(BlobDelete::NotFound);
+            }
+            let refcount = current - 1;
+            let mut updated = existing.clone();
+            updated["refcount"] = json!(refcount);
+            if !storage
+                .compare_and_swap("content_blob", hash.as_str(), &existing, updated)
+                .await?
+            {
+                continue;
+            }
+            if refcount == 0 {
+                storage.del("content_blob", hash.as_str()).await?;
+                return Ok(BlobDelete::Freed);
+            }
+            return Ok(BlobDelete::Released { refcount });
+        }
+    }
+
     pub async fn save(
         &self,
         input: SaveInput,
@@ -361,4 +509,140 @@ mod tests {
             }
         }
     }
+
+    // --- blobs ---
+
+    #[tokio::test]
+    async fn identical_blobs_are_stored_once_with_refcount() {
+        let storage = InMemoryStorage::new();
+        let handler = ContentStorageHandler;
+        let bytes = b"captured page body";
+
+        let first = handler.put_blob(bytes, &storage).await.unwrap();
+        let second = handler.put_blob(bytes, &storage).await.unwrap();
+        assert_eq!(first, second);
+        assert_eq!(first, ContentHash::of(bytes));
+
+        let blobs = storage.find("content_blob", None).await.unwrap();
+        assert_eq!(blobs.len(), 1);
+        assert_eq!(handler.blob_refcount(&first, &storage).await.unwrap(), 2);
+        assert_eq!(
+            handler.get_blob(&first, &storage).await.unwrap().as_deref(),
+            Some(&bytes[..])
+        );
+
+        let other = handler.put_blob(b"another file", &storage).await.unwrap();
+        assert_ne!(other, first);
+        assert_eq!(storage.find("content_blob", None).await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn delete_blob_frees_only_after_last_reference() {
+        let storage = InMemoryStorage::new();
+        let handler = ContentStorageHandler;
+        let hash = handler.put_blob(b"shared", &storage).await.unwrap();
+        handler.put_blob(b"shared", &storage).await.unwrap();
+
+        assert_eq!(
+            handler.delete_blob(&hash, &storage).await.unwrap(),
+            BlobDelete::Released { refcount: 1 }
+        );
+        assert!(handler.get_blob(&hash, &storage).await.unwrap().is_some());
+
+        assert_eq!(
+            handler.delete_blob(&hash, &storage).await.unwrap(),
+            BlobDelete::Freed
+        );
+        assert!(handler.get_blob(&hash, &storage).await.unwrap().is_none());
+        assert_eq!(
+            handler.delete_blob(&hash, &storage).await.unwrap(),
+            BlobDelete::NotFound
+        );
+    }
+
+    /// Storage where another writer adds a reference to the blob just
+    /// before each of our first `raced` count updates lands.
+    struct RacingReference {
+        inner: InMemoryStorage,
+        raced: std::sync::Mutex<u32>,
+    }
+
+    #[async_trait::async_trait]
+    impl ConceptStorage for RacingReference {
+        async fn put(
+            &self,
+            relation: &str,
+            key: &str,
+            value: serde_json::Value,
+        ) -> StorageResult<()> {
+            self.inner.put(relation, key, value).await
+        }
+        async fn get(&self, relation: &str, key: &str) -> StorageResult<Option<serde_json::Value>> {
+            self.inner.get(relation, key).await
+        }
+        async fn find(
+            &self,
+            relation: &str,
+            criteria: Option<&serde_json::Value>,
+        ) -> StorageResult<Vec<serde_json::Value>> {
+            self.inner.find(relation, criteria).await
+        }
+        async fn del(&self, relation: &str, key: &str) -> StorageResult<()> {
+            self.inner.del(relation, key).await
+        }
+        async fn del_many(
+            &self,
+            relation: &str,
+            criteria: &serde_json::Value,
+        ) -> StorageResult<u64> {
+            self.inner.del_many(relation, criteria).await
+        }
+        async fn compare_and_swap(
+            &self,
+            relation: &str,
+            key: &str,
+            expected: &serde_json::Value,
+            value: serde_json::Value,
+        ) -> StorageResult<bool> {
+            let race = {
+                let mut raced = self.raced.lock().unwrap();
+                let race = *raced > 0;
+                *raced = raced.saturating_sub(1);
+                race
+            };
+            if race {
+                let mut winner = expected.clone();
+                winner["refcount"] = json!(expected["refcount"].as_u64().unwrap_or(0) + 1);
+                assert!(
+                    self.inner
+                        .compare_and_swap(relation, key, expected, winner)
+                        .await?
+                );
+            }
+            self.inner
+                .compare_and_swap(relation, key, expected, value)
+                .await
+        }
+    }
+
+    #[tokio::test]
+    async fn refcount_updates_retry_after_a_concurrent_writer() {
+        let storage = RacingReference {
+            inner: InMemoryStorage::new(),
+            raced: std::sync::Mutex::new(0),
+        };
+        let handler = ContentStorageHandler;
+        let hash = handler.put_blob(b"shared", &storage).await.unwrap();
+
+        *storage.raced.lock().unwrap() = 1;
+        handler.put_blob(b"shared", &storage).await.unwrap();
+        assert_eq!(handler.blob_refcount(&hash, &storage).await.unwrap(), 3);
+
+        *storage.raced.lock().unwrap() = 1;
+        assert_eq!(
+            handler.delete_blob(&hash, &storage).await.unwrap(),
+            BlobDelete::Released { refcount: 3 }
+        );
+        assert!(handler.get_blob(&hash, &storage).await.unwrap().is_some());
+    }
 }
@@ -1,9 +1,13 @@
 // DailyNote Concept Implementation (Rust)
 //
 // Manages daily notes with date-based creation and navigation.
+// "Today" is the date in the configured timezone (UTC by default).
 // See Architecture doc Sections on daily journal and note management.
 
 use crate::storage::{ConceptStorage, StorageResult};
+use crate::template::{InstantiateInput, InstantiateOutput, TemplateHandler};
+use chrono::{DateTime, NaiveDate, Utc};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
@@ -23,6 +27,44 @@ pub enum GetOrCreateTodayOutput {
     },
 }
 
+// ── EnsureToday ───────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnsureTodayInput {
+    /// Template to fill a newly created note from.
+    pub template_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "variant")]
+pub enum EnsureTodayOutput {
+    #[serde(rename = "ok")]
+    Ok {
+        page_id: String,
+        date: String,
+        created: bool,
+    },
+    #[serde(rename = "template_notfound")]
+    TemplateNotFound { message: String },
+}
+
+// ── SetTimezone ───────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetTimezoneInput {
+    /// IANA name, e.g. "America/New_York".
+    pub timezone: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "variant")]
+pub enum SetTimezoneOutput {
+    #[serde(rename = "ok")]
+    Ok { timezone: String },
+    #[serde(rename = "invalid")]
+    Invalid { message: String },
+}
+
 // ── NavigateToDate ────────────────────────────────────────
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -53,6 +95,55 @@ pub enum ListRecentOutput {
     Ok { notes: String },
 }
 
+// ── Dates ─────────────────────────────────────────────────
+
+/// The configured timezone, UTC when unset or unrecognised.
+pub async fn timezone(storage: &dyn ConceptStorage) -> StorageResult<Tz> {
+    let config = storage.get("daily_note_config", "current").await?;
+    Ok(config
+        .as_ref()
+        .and_then(|c| c["timezone"].as_str())
+        .and_then(|name| name.parse().ok())
+        .unwrap_or(Tz::UTC))
+}
+
+/// The calendar date at instant `now` in `tz`.
+pub fn local_date(now: DateTime<Utc>, tz: Tz) -> NaiveDate {
+    now.with_timezone(&tz).date_naive()
+}
+
+fn page_id_for(date: NaiveDate) -> String {
+    format!("daily_{}", date.format("%Y-%m-%d"))
+}
+
+/// The note for `date`, if one exists.
+pub async fn note_for(date: NaiveDate, storage: &dyn ConceptStorage) -> StorageResult<Option<serde_json::Value>> {
+    storage.get("daily_note", &page_id_for(date)).await
+}
+
+/// Consecutive days with a note, counting back from today. A missing note
+/// for today doesn't break the streak until the day is over, so the count
+/// then starts from yesterday.
+pub async fn streak(storage: &dyn ConceptStorage) -> StorageResult<u32> {
+    streak_at(Utc::now(), storage).await
+}
+
+pub async fn streak_at(now: DateTime<Utc>, storage: &dyn ConceptStorage) -> StorageResult<u32> {
+    let mut day = local_date(now, timezone(storage).await?);
+    if note_for(day, storage).await?.is_none() {
+        day = day.pred_opt().unwrap_or(day);
+    }
+    let mut count = 0;
+    while note_for(day, storage).await?.is_some() {
+        count += 1;
+        match day.pred_opt() {
+            Some(previous) => day = previous,
+            None => break,
+        }
+    }
+    Ok(count)
+}
+
 // ── Handler ───────────────────────────────────────────────
 
 pub struct DailyNoteHandler;
@@ -63,32 +154,110 @@ impl DailyNoteHandler {
         _input: GetOrCreateTodayInput,
         storage: &dyn ConceptStorage,
     ) -> StorageResult<GetOrCreateTodayOutput> {
-        let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
-        let page_id = format!("daily_{}", today);
+        match self
+            .ensure_today(EnsureTodayInput { template_id: None }, storage)
+            .await?
+        {
+            EnsureTodayOutput::Ok { page_id, date, created } => {
+                Ok(GetOrCreateTodayOutput::Ok { page_id, date, created })
+            }
+            EnsureTodayOutput::TemplateNotFound { message } => Err(message.into()),
+        }
+    }
 
-        let existing = storage.get("daily_note", &page_id).await?;
+    /// Return today's note, creating it (from `template_id` when given) if
+    /// there isn't one yet.
+    pub async fn ensure_today(
+        &self,
+        input: EnsureTodayInput,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<EnsureTodayOutput> {
+        self.ensure_today_at(input, Utc::now(), storage).await
+    }
 
-        let created = existing.is_none();
-
-        if created {
-            storage
-                .put(
-                    "daily_note",
-                    &page_id,
-                    json!({
-                        "page_id": page_id,
-                        "date": today,
-                        "content": "",
-                        "created_at": chrono::Utc::now().to_rfc3339(),
-                    }),
+    /// `ensure_today` as of the instant `now`.
+    pub async fn ensure_today_at(
+        &self,
+        input: EnsureTodayInput,
+        now: DateTime<Utc>,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<EnsureTodayOutput> {
+        let date = local_date(now, timezone(storage).await?);
+        let page_id = page_id_for(date);
+        let today = date.format("%Y-%m-%d").to_string();
+
+        if storage.get("daily_note", &page_id).await?.is_some() {
+            return Ok(EnsureTodayOutput::Ok {
+                page_id,
+                date: today,
+                created: false,
+            });
+        }
+
+        let mut note = json!({
+            "page_id": page_id,
+            "date": today,
+            "content": "",
+            "created_at": now.to_rfc3339(),
+        });
+
+        if let Some(template_id) = &input.template_id {
+            let Some(template) = storage.get("template", template_id).await? else {
+                return Ok(EnsureTodayOutput::TemplateNotFound {
+                    message: format!("Template '{}' not found", template_id),
+                });
+            };
+            let instantiated = TemplateHandler
+                .instantiate(
+                    InstantiateInput {
+                        template_id: template_id.clone(),
+                        target_location: page_id.clone(),
+                        bindings: json!({ "date": today }).to_string(),
+                    },
+                    storage,
                 )
                 .await?;
+            let mut content = template["block_tree"].clone();
+            if let InstantiateOutput::Ok { instance_id } = instantiated {
+                if let Some(instance) = storage.get("template", &instance_id).await? {
+                    content = instance["resolved_tree"].clone();
+                }
+                note["template_id"] = json!(template_id);
+                note["template_instance_id"] = json!(instance_id);
+            }
+            note["content"] = json!(content.to_string());
         }
 
-        Ok(GetOrCreateTodayOutput::Ok {
+        storage.put("daily_note", &page_id, note).await?;
+
+        Ok(EnsureTodayOutput::Ok {
             page_id,
             date: today,
-            created,
+            created: true,
+        })
+    }
+
+    pub async fn set_timezone(
+        &self,
+        input: SetTimezoneInput,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<SetTimezoneOutput> {
+        if input.timezone.parse::<Tz>().is_err() {
+            return Ok(SetTimezoneOutput::Invalid {
+                message: format!("Unknown timezone '{}'", input.timezone),
+            });
+        }
+
+        storage
+            .put(
+                "daily_note_config",
+                "current",
+                json!({ "timezone": input.timezone }),
+            )
+            .await?;
+
+        Ok(SetTimezoneOutput::Ok {
+            timezone: input.timezone,
         })
     }
 
@@ -255,4 +424,82 @@ mod tests {
             }
         }
     }
+
+    // ── timezone and streak tests ──────────────────────────
+
+    fn at(rfc3339: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(rfc3339).unwrap().with_timezone(&Utc)
+    }
+
+    #[tokio::test]
+    async fn ensure_today_uses_local_midnight() {
+        let storage = InMemoryStorage::new();
+        let handler = DailyNoteHandler;
+        handler
+            .set_timezone(SetTimezoneInput { timezone: "America/New_York".into() }, &storage)
+            .await
+            .unwrap();
+        crate::template::TemplateHandler
+            .define(
+                crate::template::DefineInput {
+                    template_id: "journal".into(),
+                    block_tree: r#"[{"type":"heading","text":"{{date}}"}]"#.into(),
+                    variables: r#"["date"]"#.into(),
+                },
+                &storage,
+            )
+            .await
+            .unwrap();
+
+        // 03:30 UTC on the 15th is still 23:30 on the 14th in New York
+        let input = EnsureTodayInput { template_id: Some("journal".into()) };
+        let result = handler.ensure_today_at(input.clone(), at("2024-03-15T03:30:00Z"), &storage).await.unwrap();
+        assert!(matches!(&result, EnsureTodayOutput::Ok { date, created: true, .. } if date == "2024-03-14"));
+        let note = note_for(NaiveDate::from_ymd_opt(2024, 3, 14).unwrap(), &storage).await.unwrap().unwrap();
+        assert_eq!(note["template_id"], "journal");
+        assert!(note["content"].as_str().unwrap().contains(r#""text":"2024-03-14""#));
+
+        // Same local day half an hour later: the existing note comes back
+        let result = handler.ensure_today_at(input.clone(), at("2024-03-15T03:59:00Z"), &storage).await.unwrap();
+        assert!(matches!(result, EnsureTodayOutput::Ok { created: false, .. }));
+
+        // Past local midnight a new day's note is created
+        let result = handler.ensure_today_at(input, at("2024-03-15T04:00:00Z"), &storage).await.unwrap();
+        assert!(matches!(&result, EnsureTodayOutput::Ok { date, created: true, .. } if date == "2024-03-15"));
+
+        let invalid = handler
+            .set_timezone(SetTimezoneInput { timezone: "Mars/Olympus".into() }, &storage)
+            .await
+            .unwrap();
+        assert!(matches!(invalid, SetTimezoneOutput::Invalid { .. }));
+    }
+
+    #[tokio::test]
+    async fn streak_counts_back_to_first_gap() {
+        let storage = InMemoryStorage::new();
+        let handler = DailyNoteHandler;
+        handler
+            .set_timezone(SetTimezoneInput { timezone: "Asia/Tokyo".into() }, &storage)
+            .await
+            .unwrap();
+
+        // Tokyo dates 1st, 2nd, 4th, 5th, 6th: a gap on the 3rd
+        for day in [1, 2, 4, 5, 6] {
+            handler
+                .ensure_today_at(
+                    EnsureTodayInput { template_id: None },
+                    at(&format!("2024-06-{:02}T12:00:00+09:00", day)),
+                    &storage,
+                )
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(streak_at(at("2024-06-06T20:00:00+09:00"), &storage).await.unwrap(), 3);
+        // Early on the 7th with no note yet, yesterday's streak still counts
+        assert_eq!(streak_at(at("2024-06-07T00:10:00+09:00"), &storage).await.unwrap(), 3);
+        // ... but it is over once the 7th has passed without one
+        assert_eq!(streak_at(at("2024-06-08T09:00:00+09:00"), &storage).await.unwrap(), 0);
+        assert_eq!(streak_at(at("2024-06-02T23:00:00+09:00"), &storage).await.unwrap(), 2);
+    }
 }
@@ -2,6 +2,13 @@
 //
 // Manages display modes for view/form rendering with field-level formatting.
 // See Architecture doc Sections on display and rendering.
+//
+// A `ModePreset` (teaser, full, ...) lists which fields show, in what order
+// and through which formatter. `define_preset` stores it as an ordinary
+// display mode plus field display configs, so presets and hand-configured
+// fields share one source of truth. `render_mode` applies the stored config
+// to a record, falling back to the schema's default mode when the requested
+// mode is not defined.
 
 use crate::storage::{ConceptStorage, StorageResult};
 use serde::{Deserialize, Serialize};
@@ -57,6 +64,128 @@ pub enum RenderInModeOutput {
     NotFound { message: String },
 }
 
+// ── Presets ───────────────────────────────────────────────
+
+/// How one field appears in a mode.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldDisplay {
+    pub field: String,
+    pub formatter: String,
+    pub visible: bool,
+    #[serde(default)]
+    pub settings: serde_json::Value,
+}
+
+/// A named display mode. Fields render in the order they were added.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModePreset {
+    pub name: String,
+    pub fields: Vec<FieldDisplay>,
+}
+
+impl ModePreset {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            fields: Vec::new(),
+        }
+    }
+
+    /// Show `field` through `formatter`.
+    pub fn show(self, field: &str, formatter: &str) -> Self {
+        self.show_with(field, formatter, json!({}))
+    }
+
+    pub fn show_with(mut self, field: &str, formatter: &str, settings: serde_json::Value) -> Self {
+        self.fields.push(FieldDisplay {
+            field: field.to_string(),
+            formatter: formatter.to_string(),
+            visible: true,
+            settings,
+        });
+        self
+    }
+
+    /// Keep `field` out of this mode.
+    pub fn hide(mut self, field: &str) -> Self {
+        self.fields.push(FieldDisplay {
+            field: field.to_string(),
+            formatter: "hidden".to_string(),
+            visible: false,
+            settings: json!({}),
+        });
+        self
+    }
+
+    pub fn visible_fields(&self) -> impl Iterator<Item = &FieldDisplay> {
+        self.fields.iter().filter(|f| f.visible)
+    }
+
+    /// Render `record` through this preset: visible fields, in configured
+    /// order, each through its formatter. Fields missing from the record
+    /// are left out.
+    pub fn render(&self, mode: &str, record: &serde_json::Value) -> RenderedMode {
+        let fields = self
+            .visible_fields()
+            .filter_map(|display| {
+                let value = record.get(&display.field)?;
+                Some(RenderedField {
+                    field: display.field.clone(),
+                    formatter: display.formatter.clone(),
+                    value: format_field(&display.formatter, &display.settings, value),
+                })
+            })
+            .collect();
+        RenderedMode {
+            mode: mode.to_string(),
+            fields,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RenderedField {
+    pub field: String,
+    pub formatter: String,
+    pub value: serde_json::Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RenderedMode {
+    /// The mode actually used, which is the fallback when the requested
+    /// mode is undefined.
+    pub mode: String,
+    pub fields: Vec<RenderedField>,
+}
+
+/// Apply a built-in formatter. `plain_text` stringifies, `trimmed` cuts text
+/// to `settings.max_length` characters with an ellipsis; anything else
+/// passes the value through for the client to format.
+pub fn format_field(
+    formatter: &str,
+    settings: &serde_json::Value,
+    value: &serde_json::Value,
+) -> serde_json::Value {
+    let text = || match value {
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    };
+    match formatter {
+        "plain_text" => json!(text()),
+        "trimmed" => {
+            let text = text();
+            let max = settings["max_length"].as_u64().unwrap_or(200) as usize;
+            if text.chars().count() <= max {
+                json!(text)
+            } else {
+                let cut: String = text.chars().take(max).collect();
+                json!(format!("{}…", cut.trim_end()))
+            }
+        }
+        _ => value.clone(),
+    }
+}
+
 // ── Handler ───────────────────────────────────────────────
 
 pub struct DisplayModeHandler;
@@ -97,6 +226,18 @@ impl DisplayModeHandler {
         let settings: serde_json::Value =
             serde_json::from_str(&input.settings).unwrap_or(json!({}));
 
+        // Fields keep their position when reconfigured; new ones go last.
+        let weight = match storage.get("field_display_config", &config_key).await? {
+            Some(existing) => existing["weight"].as_u64().unwrap_or(0),
+            None => storage
+                .find(
+                    "field_display_config",
+                    Some(&json!({ "schema_id": input.schema_id, "mode_id": input.mode_id })),
+                )
+                .await?
+                .len() as u64,
+        };
+
         storage
             .put(
                 "field_display_config",
@@ -107,6 +248,7 @@ impl DisplayModeHandler {
                     "field_id": input.field_id,
                     "formatter": input.formatter,
                     "settings": settings,
+                    "weight": weight,
                 }),
             )
             .await?;
@@ -147,6 +289,124 @@ impl DisplayModeHandler {
             rendered: serde_json::to_string(&rendered)?,
         })
     }
+
+    /// Store `preset` as a display mode for `schema_id`, replacing any field
+    /// configs the mode already had. Hidden fields are stored with the
+    /// `hidden` formatter. Returns the mode id.
+    pub async fn define_preset(
+        &self,
+        schema_id: &str,
+        mode_type: &str,
+        preset: &ModePreset,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<String> {
+        let DefineModeOutput::Ok { mode_id } = self
+            .define_mode(
+                DefineModeInput {
+                    name: preset.name.clone(),
+                    mode_type: mode_type.to_string(),
+                },
+                storage,
+            )
+            .await?;
+
+        storage
+            .del_many(
+                "field_display_config",
+                &json!({ "schema_id": schema_id, "mode_id": mode_id }),
+            )
+            .await?;
+        for display in &preset.fields {
+            self.configure_field_display(
+                ConfigureFieldDisplayInput {
+                    schema_id: schema_id.to_string(),
+                    mode_id: mode_id.clone(),
+                    field_id: display.field.clone(),
+                    formatter: display.formatter.clone(),
+                    settings: display.settings.to_string(),
+                },
+                storage,
+            )
+            .await?;
+        }
+        Ok(mode_id)
+    }
+
+    /// Use `mode_id` whenever a mode requested for `schema_id` is undefined.
+    pub async fn set_fallback(
+        &self,
+        schema_id: &str,
+        mode_id: &str,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<()> {
+        storage
+            .put(
+                "display_mode_fallback",
+                schema_id,
+                json!({ "schema_id": schema_id, "mode_id": mode_id }),
+            )
+            .await
+    }
+
+    /// The stored preset for `mode_id` on `schema_id`, built from its field
+    /// display configs in weight order. `None` when the mode is undefined.
+    pub async fn load_preset(
+        &self,
+        schema_id: &str,
+        mode_id: &str,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<Option<ModePreset>> {
+        let Some(mode) = storage.get("display_mode", mode_id).await? else {
+            return Ok(None);
+        };
+        let mut configs = storage
+            .find(
+                "field_display_config",
+                Some(&json!({ "schema_id": schema_id, "mode_id": mode_id })),
+            )
+            .await?;
+        configs.sort_by_key(|c| c["weight"].as_u64().unwrap_or(0));
+
+        let fields = configs
+            .into_iter()
+            .map(|c| {
+                let formatter = c["formatter"].as_str().unwrap_or("").to_string();
+                FieldDisplay {
+                    field: c["field_id"].as_str().unwrap_or("").to_string(),
+                    visible: formatter != "hidden",
+                    formatter,
+                    settings: c["settings"].clone(),
+                }
+            })
+            .collect();
+        Ok(Some(ModePreset {
+            name: mode["name"].as_str().unwrap_or(mode_id).to_string(),
+            fields,
+        }))
+    }
+
+    /// Render `record` in `mode_id` using the stored field display configs,
+    /// or in the schema's fallback mode when `mode_id` is undefined. `None`
+    /// when neither is defined.
+    pub async fn render_mode(
+        &self,
+        schema_id: &str,
+        mode_id: &str,
+        record: &serde_json::Value,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<Option<RenderedMode>> {
+        if let Some(preset) = self.load_preset(schema_id, mode_id, storage).await? {
+            return Ok(Some(preset.render(mode_id, record)));
+        }
+        let Some(fallback) = storage.get("display_mode_fallback", schema_id).await? else {
+            return Ok(None);
+        };
+        let fallback_id = fallback["mode_id"].as_str().unwrap_or("");
+        Ok(self
+            .load_preset(schema_id, fallback_id, storage)
+            .await?
+            .map(|preset| preset.render(fallback_id, record)))
+    }
 }
 
 #[cfg(test)]
@@ -243,4 +503,166 @@ mod tests {
             RenderInModeOutput::NotFound { .. } => panic!("expected Ok"),
         }
     }
+
+    async fn define_article_modes(storage: &InMemoryStorage) {
+        let handler = DisplayModeHandler;
+        handler
+            .define_preset(
+                "article",
+                "view",
+                &ModePreset::new("teaser")
+                    .show("title", "plain_text")
+                    .show_with("summary", "trimmed", json!({ "max_length": 12 }))
+                    .hide("body"),
+                storage,
+            )
+            .await
+            .unwrap();
+        handler
+            .define_preset(
+                "article",
+                "view",
+                &ModePreset::new("full")
+                    .show("title", "plain_text")
+                    .show("author", "plain_text")
+                    .show("summary", "plain_text")
+                    .show("body", "rich_text"),
+                storage,
+            )
+            .await
+            .unwrap();
+        handler
+            .set_fallback("article", "mode_view_full", storage)
+            .await
+            .unwrap();
+    }
+
+    fn article() -> serde_json::Value {
+        json!({
+            "body": "<p>Long form</p>",
+            "author": "Ada",
+            "title": "Launch day",
+            "summary": "Everything shipped on time",
+        })
+    }
+
+    #[tokio::test]
+    async fn teaser_mode_hides_body() {
+        let storage = InMemoryStorage::new();
+        define_article_modes(&storage).await;
+        let rendered = DisplayModeHandler
+            .render_mode("article", "mode_view_teaser", &article(), &storage)
+            .await
+            .unwrap()
+            .unwrap();
+        let fields: Vec<&str> = rendered.fields.iter().map(|f| f.field.as_str()).collect();
+        assert_eq!(fields, vec!["title", "summary"]);
+        assert_eq!(rendered.fields[1].value, json!("Everything s…"));
+    }
+
+    #[tokio::test]
+    async fn full_mode_shows_all_fields_in_order_and_is_the_fallback() {
+        let storage = InMemoryStorage::new();
+        define_article_modes(&storage).await;
+        let handler = DisplayModeHandler;
+        let rendered = handler
+            .render_mode("article", "mode_view_full", &article(), &storage)
+            .await
+            .unwrap()
+            .unwrap();
+        let fields: Vec<(&str, &str)> = rendered
+            .fields
+            .iter()
+            .map(|f| (f.field.as_str(), f.formatter.as_str()))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                ("title", "plain_text"),
+                ("author", "plain_text"),
+                ("summary", "plain_text"),
+                ("body", "rich_text"),
+            ]
+        );
+
+        let fallback = handler
+            .render_mode("article", "mode_view_search_result", &article(), &storage)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(fallback.mode, "mode_view_full");
+        assert_eq!(fallback.fields, rendered.fields);
+        assert!(handler
+            .render_mode("page", "mode_view_search_result", &article(), &storage)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn presets_and_configured_fields_share_stored_config() {
+        let storage = InMemoryStorage::new();
+        define_article_modes(&storage).await;
+        let handler = DisplayModeHandler;
+        handler
+            .configure_field_display(
+                ConfigureFieldDisplayInput {
+                    schema_id: "article".into(),
+                    mode_id: "mode_view_teaser".into(),
+                    field_id: "title".into(),
+                    formatter: "trimmed".into(),
+                    settings: r#"{"max_length": 6}"#.into(),
+                },
+                &storage,
+            )
+            .await
+            .unwrap();
+        handler
+            .configure_field_display(
+                ConfigureFieldDisplayInput {
+                    schema_id: "article".into(),
+                    mode_id: "mode_view_teaser".into(),
+                    field_id: "author".into(),
+                    formatter: "plain_text".into(),
+                    settings: "{}".into(),
+                },
+                &storage,
+            )
+            .await
+            .unwrap();
+
+        let rendered = handler
+            .render_mode("article", "mode_view_teaser", &article(), &storage)
+            .await
+            .unwrap()
+            .unwrap();
+        let fields: Vec<(&str, &serde_json::Value)> = rendered
+            .fields
+            .iter()
+            .map(|f| (f.field.as_str(), &f.value))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                ("title", &json!("Launch…")),
+                ("summary", &json!("Everything s…")),
+                ("author", &json!("Ada")),
+            ]
+        );
+
+        let RenderInModeOutput::Ok { rendered, .. } = handler
+            .render_in_mode(
+                RenderInModeInput {
+                    node_id: "a1".into(),
+                    mode_id: "mode_view_teaser".into(),
+                },
+                &storage,
+            )
+            .await
+            .unwrap()
+        else {
+            panic!("expected Ok");
+        };
+        assert!(rendered.contains("\"formatter\":\"trimmed\""));
+    }
 }
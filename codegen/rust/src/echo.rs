@@ -2,8 +2,14 @@
 //
 // Mirrors the TypeScript echo.impl.ts — send action.
 // Stores text and returns it as echo.
+//
+// The stream action echoes a sequence of inputs in order, optionally passing
+// each through a stored transform or a transform plugin, which makes it a
+// quick end-to-end smoke test for transforms. `stream_with` hands each echo to a callback as it is
+// produced.
 
 use crate::storage::{ConceptStorage, StorageResult};
+use crate::transform::{TransformApplyInput, TransformApplyOutput, TransformHandler};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
@@ -22,6 +28,27 @@ pub enum EchoSendOutput {
     Ok { id: String, echo: String },
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EchoStreamInput {
+    pub id: String,
+    pub inputs: Vec<String>,
+    /// Id of a stored transform, or of a transform plugin such as "upper",
+    /// to apply to every input; `None` echoes unchanged. A stored transform
+    /// wins when both share an id.
+    pub transform: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "variant")]
+pub enum EchoStreamOutput {
+    #[serde(rename = "ok")]
+    Ok { id: String, echoes: Vec<String> },
+    #[serde(rename = "notfound")]
+    Notfound { message: String },
+    #[serde(rename = "error")]
+    Error { message: String, index: usize },
+}
+
 // ── Handler ────────────────────────────────────────────────
 
 pub struct EchoHandler;
@@ -41,6 +68,85 @@ impl EchoHandler {
             echo: input.text,
         })
     }
+
+    /// Echo `inputs` in order, each run through `transform` when given. Stops
+    /// at the first input the transform rejects; nothing is stored then.
+    pub async fn stream(
+        &self,
+        input: EchoStreamInput,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<EchoStreamOutput> {
+        self.stream_with(input, storage, |_, _| {}).await
+    }
+
+    /// `stream`, handing each echo to `on_echo` with its input index as soon
+    /// as it is produced. An unknown transform is reported before any input
+    /// is echoed.
+    pub async fn stream_with(
+        &self,
+        input: EchoStreamInput,
+        storage: &dyn ConceptStorage,
+        mut on_echo: impl FnMut(usize, &str) + Send,
+    ) -> StorageResult<EchoStreamOutput> {
+        let mut direct_plugin = false;
+        if let Some(transform_id) = &input.transform {
+            if storage.get("transform", transform_id).await?.is_none() {
+                if !TransformHandler::PLUGIN_IDS.contains(&transform_id.as_str()) {
+                    return Ok(EchoStreamOutput::Notfound {
+                        message: format!("Transform \"{}\" not found", transform_id),
+                    });
+                }
+                direct_plugin = true;
+            }
+        }
+
+        let mut echoes = Vec::with_capacity(input.inputs.len());
+        for (index, text) in input.inputs.iter().enumerate() {
+            let echo = match &input.transform {
+                None => text.clone(),
+                Some(plugin_id) if direct_plugin => TransformHandler::apply_plugin(text, plugin_id),
+                Some(transform_id) => {
+                    let applied = TransformHandler
+                        .apply(
+                            TransformApplyInput {
+                                value: text.clone(),
+                                transform_id: transform_id.clone(),
+                            },
+                            storage,
+                        )
+                        .await?;
+                    match applied {
+                        TransformApplyOutput::Ok { result } => result,
+                        TransformApplyOutput::Notfound { message } => {
+                            return Ok(EchoStreamOutput::Notfound { message });
+                        }
+                        TransformApplyOutput::Error { message } => {
+                            return Ok(EchoStreamOutput::Error { message, index });
+                        }
+                    }
+                }
+            };
+            on_echo(index, &echo);
+            echoes.push(echo);
+        }
+
+        storage
+            .put(
+                "echo",
+                &input.id,
+                json!({
+                    "inputs": input.inputs,
+                    "echoes": echoes,
+                    "transform": input.transform,
+                }),
+            )
+            .await?;
+
+        Ok(EchoStreamOutput::Ok {
+            id: input.id,
+            echoes,
+        })
+    }
 }
 
 // ── Tests ──────────────────────────────────────────────────
@@ -126,4 +232,118 @@ mod tests {
         assert!(matches!(r1, EchoSendOutput::Ok { ref echo, .. } if echo == "First"));
         assert!(matches!(r2, EchoSendOutput::Ok { ref echo, .. } if echo == "Second"));
     }
+
+    fn stream_input(transform: Option<&str>) -> EchoStreamInput {
+        EchoStreamInput {
+            id: "s1".into(),
+            inputs: vec!["alpha".into(), "Beta".into(), "gamma ray".into()],
+            transform: transform.map(String::from),
+        }
+    }
+
+    #[tokio::test]
+    async fn stream_applies_transform_to_each_input() {
+        let storage = InMemoryStorage::new();
+        storage
+            .put("transform", "shout", json!({ "plugin_id": "upper" }))
+            .await
+            .unwrap();
+
+        let result = EchoHandler
+            .stream(stream_input(Some("shout")), &storage)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            result,
+            EchoStreamOutput::Ok {
+                id: "s1".into(),
+                echoes: vec!["ALPHA".into(), "BETA".into(), "GAMMA RAY".into()],
+            }
+        );
+        let record = storage.get("echo", "s1").await.unwrap().unwrap();
+        assert_eq!(record["echoes"], json!(["ALPHA", "BETA", "GAMMA RAY"]));
+        assert_eq!(record["transform"], json!("shout"));
+    }
+
+    #[tokio::test]
+    async fn stream_accepts_a_plugin_id_directly() {
+        let storage = InMemoryStorage::new();
+
+        let result = EchoHandler
+            .stream(stream_input(Some("slugify")), &storage)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            result,
+            EchoStreamOutput::Ok {
+                id: "s1".into(),
+                echoes: vec!["alpha".into(), "beta".into(), "gamma-ray".into()],
+            }
+        );
+        assert!(storage.find("transform", None).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn stream_passes_through_without_transform() {
+        let storage = InMemoryStorage::new();
+
+        let result = EchoHandler
+            .stream(stream_input(None), &storage)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            result,
+            EchoStreamOutput::Ok {
+                id: "s1".into(),
+                echoes: vec!["alpha".into(), "Beta".into(), "gamma ray".into()],
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn stream_with_yields_each_echo_as_it_is_produced() {
+        let storage = InMemoryStorage::new();
+        storage
+            .put("transform", "shout", json!({ "plugin_id": "upper" }))
+            .await
+            .unwrap();
+
+        let mut seen = Vec::new();
+        EchoHandler
+            .stream_with(stream_input(Some("shout")), &storage, |index, echo| {
+                seen.push((index, echo.to_string()))
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            seen,
+            vec![
+                (0, "ALPHA".to_string()),
+                (1, "BETA".to_string()),
+                (2, "GAMMA RAY".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn stream_reports_unknown_transform_before_echoing() {
+        let storage = InMemoryStorage::new();
+
+        let mut seen = 0;
+        let missing = EchoHandler
+            .stream_with(stream_input(Some("unknown")), &storage, |_, _| seen += 1)
+            .await
+            .unwrap();
+        assert_eq!(
+            missing,
+            EchoStreamOutput::Notfound {
+                message: "Transform \"unknown\" not found".into(),
+            }
+        );
+        assert_eq!(seen, 0);
+        assert!(storage.get("echo", "s1").await.unwrap().is_none());
+    }
 }
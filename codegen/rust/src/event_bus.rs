@@ -2,11 +2,19 @@
 //
 // Infrastructure suite — registers event types, subscribes/unsubscribes
 // listeners with priority, dispatches events to listeners, and
-// retrieves event history.
+// retrieves event history. TopicBus is the in-process pub/sub with
+// wildcard topic patterns.
 
 use crate::storage::{ConceptStorage, StorageResult};
+use async_trait::async_trait;
+use futures::FutureExt;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::collections::HashMap;
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, RwLock};
 
 // ── RegisterEventType ─────────────────────────────────────
 
@@ -95,6 +103,265 @@ pub enum EventBusGetHistoryOutput {
     Ok { events: String },
 }
 
+// ── In-process listeners ──────────────────────────────────
+
+/// A listener that runs in the publishing process. It only receives events
+/// it is subscribed to under `listener_id`.
+#[async_trait]
+pub trait EventListener: Send + Sync {
+    fn listener_id(&self) -> &str;
+
+    async fn handle(
+        &self,
+        event_type_id: &str,
+        payload: &serde_json::Value,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<()>;
+
+    /// Undo a successful `handle` because a later listener for the same
+    /// event failed and the publisher is rolling back. Does nothing unless
+    /// overridden.
+    async fn compensate(
+        &self,
+        _event_type_id: &str,
+        _payload: &serde_json::Value,
+        _storage: &dyn ConceptStorage,
+    ) -> StorageResult<()> {
+        Ok(())
+    }
+}
+
+/// Run one listener, turning a panic in any poll of its future into an
+/// error so it cannot unwind through the publisher.
+async fn deliver(
+    listener: &dyn EventListener,
+    event_type_id: &str,
+    payload: &serde_json::Value,
+    storage: &dyn ConceptStorage,
+) -> StorageResult<()> {
+    AssertUnwindSafe(listener.handle(event_type_id, payload, storage))
+        .catch_unwind()
+        .await
+        .unwrap_or_else(|_| Err("handler panicked".into()))
+}
+
+// ── Topics ────────────────────────────────────────────────
+
+/// Whether a dot-separated `topic` matches `pattern`. In a pattern `*`
+/// matches exactly one segment and `**` matches any number of segments,
+/// including none: `content.*` matches `content.created`, while
+/// `content.**` also matches `content.article.created`.
+pub fn topic_matches(pattern: &str, topic: &str) -> bool {
+    fn matches(pattern: &[&str], topic: &[&str]) -> bool {
+        match pattern.split_first() {
+            None => topic.is_empty(),
+            Some((&"**", rest)) => (0..=topic.len()).any(|skip| matches(rest, &topic[skip..])),
+            Some((segment, rest)) => match topic.split_first() {
+                Some((first, topic_rest)) => (*segment == "*" || segment == first) && matches(rest, topic_rest),
+                None => false,
+            },
+        }
+    }
+    let pattern: Vec<&str> = pattern.split('.').collect();
+    let topic: Vec<&str> = topic.split('.').collect();
+    matches(&pattern, &topic)
+}
+
+type HandlerFuture = Pin<Box<dyn Future<Output = Result<(), String>> + Send>>;
+type SyncTopicHandler = dyn Fn(&str, &serde_json::Value) -> Result<(), String> + Send + Sync;
+type AsyncTopicHandler = dyn Fn(String, serde_json::Value) -> HandlerFuture + Send + Sync;
+
+/// Closure subscriptions, adapted to the listener interface so the bus has
+/// a single delivery path.
+struct SyncTopicListener(Box<SyncTopicHandler>);
+struct AsyncTopicListener(Box<AsyncTopicHandler>);
+
+#[async_trait]
+impl EventListener for SyncTopicListener {
+    fn listener_id(&self) -> &str {
+        "topic"
+    }
+
+    async fn handle(
+        &self,
+        event_type_id: &str,
+        payload: &serde_json::Value,
+        _storage: &dyn ConceptStorage,
+    ) -> StorageResult<()> {
+        (self.0)(event_type_id, payload).map_err(Into::into)
+    }
+}
+
+#[async_trait]
+impl EventListener for AsyncTopicListener {
+    fn listener_id(&self) -> &str {
+        "topic"
+    }
+
+    async fn handle(
+        &self,
+        event_type_id: &str,
+        payload: &serde_json::Value,
+        _storage: &dyn ConceptStorage,
+    ) -> StorageResult<()> {
+        (self.0)(event_type_id.to_string(), payload.clone())
+            .await
+            .map_err(Into::into)
+    }
+}
+
+#[derive(Clone)]
+struct TopicSubscription {
+    handle: SubscriptionHandle,
+    pattern: String,
+    listener: Arc<dyn EventListener>,
+}
+
+/// Identifies a subscription for `TopicBus::unsubscribe`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SubscriptionHandle(u64);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HandlerFailure {
+    pub handle: SubscriptionHandle,
+    pub pattern: String,
+    pub error: String,
+}
+
+/// Outcome of one publish: how many handlers ran cleanly and which failed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PublishReport {
+    pub delivered: usize,
+    pub failures: Vec<HandlerFailure>,
+}
+
+/// In-process pub/sub over dot-separated topics, delivering to the same
+/// `EventListener`s as `EventBusHandler::publish`. Handlers run in
+/// subscription order and publishes to one topic are delivered one at a
+/// time, in order. A handler that fails or panics, synchronously or while
+/// awaiting, is recorded in the report without stopping delivery to the
+/// others.
+#[derive(Default)]
+pub struct TopicBus {
+    subscriptions: RwLock<Vec<TopicSubscription>>,
+    next_handle: Mutex<u64>,
+    topic_locks: Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>,
+}
+
+impl TopicBus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn subscribe(
+        &self,
+        pattern: &str,
+        handler: impl Fn(&str, &serde_json::Value) -> Result<(), String> + Send + Sync + 'static,
+    ) -> SubscriptionHandle {
+        self.subscribe_listener(pattern, Arc::new(SyncTopicListener(Box::new(handler))))
+    }
+
+    pub fn subscribe_async<F, Fut>(&self, pattern: &str, handler: F) -> SubscriptionHandle
+    where
+        F: Fn(String, serde_json::Value) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), String>> + Send + 'static,
+    {
+        let handler: Box<AsyncTopicHandler> =
+            Box::new(move |topic, payload| Box::pin(handler(topic, payload)));
+        self.subscribe_listener(pattern, Arc::new(AsyncTopicListener(handler)))
+    }
+
+    /// Subscribe an event bus listener, such as `BacklinkIndexer`, to every
+    /// topic matching `pattern`.
+    pub fn subscribe_listener(
+        &self,
+        pattern: &str,
+        listener: Arc<dyn EventListener>,
+    ) -> SubscriptionHandle {
+        let handle = {
+            let mut next = self.next_handle.lock().unwrap_or_else(|e| e.into_inner());
+            *next += 1;
+            SubscriptionHandle(*next)
+        };
+        self.subscriptions
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(TopicSubscription {
+                handle,
+                pattern: pattern.to_string(),
+                listener,
+            });
+        handle
+    }
+
+    /// Remove a subscription; false if it was already gone.
+    pub fn unsubscribe(&self, handle: SubscriptionHandle) -> bool {
+        let mut subscriptions = self
+            .subscriptions
+            .write()
+            .unwrap_or_else(|e| e.into_inner());
+        let before = subscriptions.len();
+        subscriptions.retain(|s| s.handle != handle);
+        subscriptions.len() != before
+    }
+
+    pub async fn publish(
+        &self,
+        topic: &str,
+        payload: &serde_json::Value,
+        storage: &dyn ConceptStorage,
+    ) -> PublishReport {
+        let lock = self
+            .topic_locks
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(topic.to_string())
+            .or_default()
+            .clone();
+        let ordered = lock.lock().await;
+        let report = self.deliver_to_matching(topic, payload, storage).await;
+        drop(ordered);
+
+        // Forget the lock once no other publish holds or waits on it; clones
+        // are only taken under the map lock, so the count cannot race.
+        let mut locks = self.topic_locks.lock().unwrap_or_else(|e| e.into_inner());
+        if Arc::strong_count(&lock) == 2 {
+            locks.remove(topic);
+        }
+        report
+    }
+
+    async fn deliver_to_matching(
+        &self,
+        topic: &str,
+        payload: &serde_json::Value,
+        storage: &dyn ConceptStorage,
+    ) -> PublishReport {
+        // Snapshot so handlers may subscribe or unsubscribe while running
+        let matching: Vec<TopicSubscription> = self
+            .subscriptions
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .filter(|s| topic_matches(&s.pattern, topic))
+            .cloned()
+            .collect();
+
+        let mut report = PublishReport::default();
+        for subscription in matching {
+            match deliver(subscription.listener.as_ref(), topic, payload, storage).await {
+                Ok(()) => report.delivered += 1,
+                Err(error) => report.failures.push(HandlerFailure {
+                    handle: subscription.handle,
+                    pattern: subscription.pattern.clone(),
+                    error: error.to_string(),
+                }),
+            }
+        }
+        report
+    }
+}
+
 // ── Handler ───────────────────────────────────────────────
 
 pub struct EventBusHandler;
@@ -205,6 +472,46 @@ impl EventBusHandler {
         })
     }
 
+    /// Run the subscribed in-process `listeners`, highest priority first,
+    /// then record the event in history. The first listener error stops
+    /// delivery: listeners that already handled the event are compensated
+    /// in reverse order, nothing is recorded, and the error is returned.
+    pub async fn publish(
+        &self,
+        event_type_id: &str,
+        payload: &serde_json::Value,
+        listeners: &[&dyn EventListener],
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<u64> {
+        let criteria = json!({ "event_type_id": event_type_id });
+        let mut subscribed = storage.find("listener", Some(&criteria)).await?;
+        subscribed.sort_by_key(|l| std::cmp::Reverse(l["priority"].as_i64().unwrap_or(0)));
+
+        let mut handled: Vec<&dyn EventListener> = Vec::new();
+        for subscription in &subscribed {
+            let listener_id = subscription["listener_id"].as_str().unwrap_or("");
+            for listener in listeners.iter().filter(|l| l.listener_id() == listener_id) {
+                if let Err(err) = deliver(*listener, event_type_id, payload, storage).await {
+                    for done in handled.iter().rev() {
+                        done.compensate(event_type_id, payload, storage).await?;
+                    }
+                    return Err(err);
+                }
+                handled.push(*listener);
+            }
+        }
+
+        self.dispatch(
+            EventBusDispatchInput {
+                event_type_id: event_type_id.to_string(),
+                payload: payload.to_string(),
+            },
+            storage,
+        )
+        .await?;
+        Ok(handled.len() as u64)
+    }
+
     pub async fn get_history(
         &self,
         input: EventBusGetHistoryInput,
@@ -384,4 +691,123 @@ mod tests {
             }
         }
     }
+
+    // ── topic bus tests ────────────────────────────────────
+
+    #[test]
+    fn topic_patterns_match_by_segment() {
+        assert!(topic_matches("content.article.created", "content.article.created"));
+        assert!(topic_matches("content.*", "content.created"));
+        assert!(!topic_matches("content.*", "content.article.created"));
+        assert!(topic_matches("content.*.created", "content.article.created"));
+        assert!(topic_matches("content.**", "content.article.created"));
+        assert!(topic_matches("content.**", "content"));
+        assert!(topic_matches("**.created", "content.article.created"));
+        assert!(!topic_matches("content.**", "user.created"));
+        assert!(!topic_matches("content.article", "content.article.created"));
+    }
+
+    #[tokio::test]
+    async fn failing_handler_does_not_block_others() {
+        let storage = InMemoryStorage::new();
+        let bus = TopicBus::new();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+
+        let failing = bus.subscribe("content.**", |_, _| Err("index offline".to_string()));
+        bus.subscribe("content.*.created", |_, _| panic!("boom"));
+        let log = seen.clone();
+        bus.subscribe("content.article.*", move |topic, payload| {
+            log.lock().unwrap().push(format!("sync {} {}", topic, payload["id"]));
+            Ok(())
+        });
+        let log = seen.clone();
+        let async_handle = bus.subscribe_async("content.**", move |topic, payload| {
+            let log = log.clone();
+            async move {
+                tokio::task::yield_now().await;
+                log.lock().unwrap().push(format!("async {} {}", topic, payload["id"]));
+                Ok(())
+            }
+        });
+        bus.subscribe("user.*", |_, _| Ok(()));
+
+        let report = bus
+            .publish("content.article.created", &json!({ "id": 1 }), &storage)
+            .await;
+        assert_eq!(report.delivered, 2);
+        assert_eq!(report.failures.len(), 2);
+        assert_eq!(report.failures[0].handle, failing);
+        assert_eq!(report.failures[0].error, "index offline");
+        assert_eq!(report.failures[1].error, "handler panicked");
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec!["sync content.article.created 1", "async content.article.created 1"]
+        );
+
+        assert!(bus.unsubscribe(async_handle));
+        assert!(!bus.unsubscribe(async_handle));
+        let report = bus
+            .publish("content.article.updated", &json!({ "id": 2 }), &storage)
+            .await;
+        assert_eq!((report.delivered, report.failures.len()), (1, 1));
+        assert_eq!(seen.lock().unwrap().last().unwrap(), "sync content.article.updated 2");
+    }
+
+    #[tokio::test]
+    async fn async_panic_is_isolated_and_topic_locks_are_released() {
+        let storage = InMemoryStorage::new();
+        let bus = TopicBus::new();
+        bus.subscribe_async("content.*", |_, _| async {
+            tokio::task::yield_now().await;
+            panic!("boom after await")
+        });
+        let delivered = Arc::new(Mutex::new(0));
+        let count = delivered.clone();
+        bus.subscribe("content.*", move |_, _| {
+            *count.lock().unwrap() += 1;
+            Ok(())
+        });
+
+        for topic in ["content.created", "content.updated", "content.deleted"] {
+            let report = bus.publish(topic, &json!({}), &storage).await;
+            assert_eq!(report.delivered, 1);
+            assert_eq!(report.failures[0].error, "handler panicked");
+        }
+        assert_eq!(*delivered.lock().unwrap(), 3);
+        assert!(bus.topic_locks.lock().unwrap().is_empty());
+    }
+
+    struct Recorder;
+
+    #[async_trait]
+    impl EventListener for Recorder {
+        fn listener_id(&self) -> &str {
+            "recorder"
+        }
+
+        async fn handle(
+            &self,
+            event_type_id: &str,
+            payload: &serde_json::Value,
+            storage: &dyn ConceptStorage,
+        ) -> StorageResult<()> {
+            storage
+                .put("recorded", event_type_id, payload.clone())
+                .await
+        }
+    }
+
+    #[tokio::test]
+    async fn topic_bus_delivers_to_event_listeners() {
+        let storage = InMemoryStorage::new();
+        let bus = TopicBus::new();
+        bus.subscribe_listener("reference.*", Arc::new(Recorder));
+
+        let report = bus
+            .publish("reference.added", &json!({ "source_id": "a" }), &storage)
+            .await;
+        assert_eq!(report.delivered, 1);
+        let recorded = storage.get("recorded", "reference.added").await.unwrap();
+        assert_eq!(recorded, Some(json!({ "source_id": "a" })));
+    }
 }
@@ -3,6 +3,7 @@
 // Manages user-facing filters that can be applied to queries.
 // See Architecture doc Sections on query and filtering.
 
+use crate::query::{Filter, FilterOp};
 use crate::storage::{ConceptStorage, StorageResult};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
@@ -68,6 +69,113 @@ pub enum ResetToDefaultsOutput {
     Ok { count: u64 },
 }
 
+// ── FilterGroup ───────────────────────────────────────────
+
+/// A nested boolean filter. Serializes as `{"and": [...]}`, `{"or": [...]}`,
+/// `{"not": {...}}` or `{"leaf": {"field", "op", "value"}}` so the frontend
+/// can store and restore the filter state it edits.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FilterGroup {
+    And(Vec<FilterNode>),
+    Or(Vec<FilterNode>),
+    Not(Box<FilterNode>),
+    Leaf(Filter),
+}
+
+/// A child of a group; groups nest freely.
+pub type FilterNode = FilterGroup;
+
+impl FilterGroup {
+    pub fn leaf(field: impl Into<String>, op: FilterOp, value: serde_json::Value) -> Self {
+        FilterGroup::Leaf(Filter { field: field.into(), op, value })
+    }
+
+    /// Whether `record` passes. An empty `And` matches everything and an
+    /// empty `Or` matches nothing.
+    pub fn evaluate(&self, record: &serde_json::Value) -> bool {
+        match self {
+            FilterGroup::And(nodes) => nodes.iter().all(|node| node.evaluate(record)),
+            FilterGroup::Or(nodes) => nodes.iter().any(|node| node.evaluate(record)),
+            FilterGroup::Not(node) => !node.evaluate(record),
+            FilterGroup::Leaf(filter) => filter.matches(record),
+        }
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or(serde_json::Value::Null)
+    }
+
+    pub fn from_json(value: &serde_json::Value) -> Result<Self, serde_json::Error> {
+        serde_json::from_value(value.clone())
+    }
+}
+
+impl std::ops::Not for FilterGroup {
+    type Output = FilterGroup;
+
+    fn not(self) -> FilterGroup {
+        FilterGroup::Not(Box::new(self))
+    }
+}
+
+/// Fluent construction of an `And` or `Or` group.
+///
+/// ```ignore
+/// // status = "open" AND (priority = "high" OR assignee = "me")
+/// let group = FilterGroupBuilder::and()
+///     .leaf("status", FilterOp::Eq, json!("open"))
+///     .group(
+///         FilterGroupBuilder::or()
+///             .leaf("priority", FilterOp::Eq, json!("high"))
+///             .leaf("assignee", FilterOp::Eq, json!("me")),
+///     )
+///     .build();
+/// ```
+#[derive(Debug, Clone)]
+pub struct FilterGroupBuilder {
+    any: bool,
+    nodes: Vec<FilterNode>,
+}
+
+impl FilterGroupBuilder {
+    pub fn and() -> Self {
+        Self { any: false, nodes: Vec::new() }
+    }
+
+    pub fn or() -> Self {
+        Self { any: true, nodes: Vec::new() }
+    }
+
+    pub fn leaf(mut self, field: impl Into<String>, op: FilterOp, value: serde_json::Value) -> Self {
+        self.nodes.push(FilterGroup::leaf(field, op, value));
+        self
+    }
+
+    pub fn group(mut self, group: FilterGroupBuilder) -> Self {
+        self.nodes.push(group.build());
+        self
+    }
+
+    pub fn not(mut self, node: FilterNode) -> Self {
+        self.nodes.push(!node);
+        self
+    }
+
+    pub fn node(mut self, node: FilterNode) -> Self {
+        self.nodes.push(node);
+        self
+    }
+
+    pub fn build(self) -> FilterGroup {
+        if self.any {
+            FilterGroup::Or(self.nodes)
+        } else {
+            FilterGroup::And(self.nodes)
+        }
+    }
+}
+
 // ── Handler ───────────────────────────────────────────────
 
 pub struct ExposedFilterHandler;
@@ -300,4 +408,49 @@ mod tests {
             }
         }
     }
+
+    // ── filter group tests ─────────────────────────────────
+
+    /// status = "open" AND (priority = "high" OR tags contains "urgent")
+    fn open_and_pressing() -> FilterGroup {
+        FilterGroupBuilder::and()
+            .leaf("status", FilterOp::Eq, json!("open"))
+            .group(
+                FilterGroupBuilder::or()
+                    .leaf("priority", FilterOp::Eq, json!("high"))
+                    .leaf("tags", FilterOp::Contains, json!("urgent")),
+            )
+            .build()
+    }
+
+    #[test]
+    fn filter_group_evaluates_nested_and_or() {
+        let group = open_and_pressing();
+
+        assert!(group.evaluate(&json!({ "status": "open", "priority": "high", "tags": [] })));
+        assert!(group.evaluate(&json!({ "status": "open", "priority": "low", "tags": ["urgent"] })));
+        assert!(!group.evaluate(&json!({ "status": "open", "priority": "low", "tags": ["later"] })));
+        assert!(!group.evaluate(&json!({ "status": "closed", "priority": "high", "tags": ["urgent"] })));
+
+        let not_open = !FilterGroup::leaf("status", FilterOp::Eq, json!("open"));
+        assert!(not_open.evaluate(&json!({ "status": "closed" })));
+        assert!(FilterGroup::And(vec![]).evaluate(&json!({})));
+        assert!(!FilterGroup::Or(vec![]).evaluate(&json!({})));
+    }
+
+    #[test]
+    fn filter_group_round_trips_through_json() {
+        let group = FilterGroupBuilder::and()
+            .node(open_and_pressing())
+            .not(FilterGroup::leaf("views", FilterOp::Lt, json!(10)))
+            .build();
+
+        let encoded = group.to_json();
+        assert_eq!(encoded["and"][0]["and"][0], json!({ "leaf": { "field": "status", "op": "eq", "value": "open" } }));
+        assert_eq!(encoded["and"][1]["not"]["leaf"]["op"], "lt");
+
+        let decoded = FilterGroup::from_json(&encoded).unwrap();
+        assert_eq!(decoded, group);
+        assert!(FilterGroup::from_json(&json!({ "xor": [] })).is_err());
+    }
 }
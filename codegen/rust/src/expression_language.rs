@@ -5,7 +5,9 @@
 
 use crate::storage::{ConceptStorage, StorageResult};
 use serde::{Deserialize, Serialize};
-use serde_json::json;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
 
 // ── RegisterLanguage ──────────────────────────────────────
 
@@ -74,6 +76,447 @@ pub enum ExprLangEvaluateOutput {
     EvalError { message: String },
 }
 
+// ── Evaluation ────────────────────────────────────────────
+
+/// How many arguments a function accepts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arity {
+    Exact(usize),
+    AtLeast(usize),
+}
+
+impl Arity {
+    fn accepts(self, count: usize) -> bool {
+        match self {
+            Arity::Exact(n) => count == n,
+            Arity::AtLeast(n) => count >= n,
+        }
+    }
+}
+
+impl From<usize> for Arity {
+    fn from(n: usize) -> Self {
+        Arity::Exact(n)
+    }
+}
+
+impl std::fmt::Display for Arity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (prefix, n) = match self {
+            Arity::Exact(n) => ("", *n),
+            Arity::AtLeast(n) => ("at least ", *n),
+        };
+        write!(f, "{}{} argument{}", prefix, n, if n == 1 { "" } else { "s" })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExprError {
+    Parse(String),
+    UnknownFunction(String),
+    Arity { name: String, expected: Arity, got: usize },
+    Function { name: String, message: String },
+}
+
+impl std::fmt::Display for ExprError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExprError::Parse(message) => write!(f, "parse error: {}", message),
+            ExprError::UnknownFunction(name) => write!(f, "unknown function '{}'", name),
+            ExprError::Arity { name, expected, got } => {
+                write!(f, "{}() expects {}, got {}", name, expected, got)
+            }
+            ExprError::Function { name, message } => write!(f, "{}() failed: {}", name, message),
+        }
+    }
+}
+
+impl std::error::Error for ExprError {}
+
+pub type FunctionImpl = Arc<dyn Fn(&[Value]) -> Result<Value, String> + Send + Sync>;
+
+/// Application-defined functions for expressions. Registered functions are
+/// looked up before the built-ins, so they may also replace one.
+#[derive(Clone, Default)]
+pub struct FunctionRegistry {
+    functions: HashMap<String, (Arity, FunctionImpl)>,
+}
+
+impl FunctionRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(
+        &mut self,
+        name: &str,
+        arity: impl Into<Arity>,
+        function: impl Fn(&[Value]) -> Result<Value, String> + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.functions.insert(name.to_string(), (arity.into(), Arc::new(function)));
+        self
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.functions.contains_key(name) || builtin_arity(name).is_some()
+    }
+
+    /// Parse and evaluate `expression` against `context`. Identifiers are
+    /// dotted paths into `context`; missing ones evaluate to null.
+    pub fn evaluate(&self, expression: &str, context: &Value) -> Result<Value, ExprError> {
+        let ast = Parser::new(expression)?.parse()?;
+        self.eval(&ast, context)
+    }
+
+    fn call(&self, name: &str, args: &[Value]) -> Result<Value, ExprError> {
+        let (arity, function) = match self.functions.get(name) {
+            Some((arity, function)) => (*arity, Some(function)),
+            None => (builtin_arity(name).ok_or_else(|| ExprError::UnknownFunction(name.to_string()))?, None),
+        };
+        if !arity.accepts(args.len()) {
+            return Err(ExprError::Arity { name: name.to_string(), expected: arity, got: args.len() });
+        }
+        match function {
+            Some(function) => function(args),
+            None => call_builtin(name, args),
+        }
+        .map_err(|message| ExprError::Function { name: name.to_string(), message })
+    }
+
+    fn eval(&self, expr: &Expr, context: &Value) -> Result<Value, ExprError> {
+        Ok(match expr {
+            Expr::Literal(value) => value.clone(),
+            Expr::Field(path) => path
+                .split('.')
+                .try_fold(context, |value, key| value.get(key))
+                .cloned()
+                .unwrap_or(Value::Null),
+            Expr::Call(name, args) => {
+                let args = args.iter().map(|arg| self.eval(arg, context)).collect::<Result<Vec<_>, _>>()?;
+                self.call(name, &args)?
+            }
+            Expr::Not(inner) => Value::Bool(!truthy(&self.eval(inner, context)?)),
+            Expr::Neg(inner) => number(-as_f64(&self.eval(inner, context)?)),
+            Expr::If(cond, then, otherwise) => {
+                if truthy(&self.eval(cond, context)?) {
+                    self.eval(then, context)?
+                } else {
+                    self.eval(otherwise, context)?
+                }
+            }
+            Expr::Binary(op, left, right) => {
+                let left = self.eval(left, context)?;
+                // Short-circuit before evaluating the right side
+                match op.as_str() {
+                    "&&" if !truthy(&left) => return Ok(Value::Bool(false)),
+                    "||" if truthy(&left) => return Ok(Value::Bool(true)),
+                    "??" if !left.is_null() => return Ok(left),
+                    _ => {}
+                }
+                binary(op, left, self.eval(right, context)?)
+            }
+        })
+    }
+}
+
+fn builtin_arity(name: &str) -> Option<Arity> {
+    Some(match name {
+        "abs" | "floor" | "ceil" | "sqrt" | "length" | "upper" | "lower" | "trim" | "str" | "num" => Arity::Exact(1),
+        "pow" | "contains" | "starts_with" | "ends_with" => Arity::Exact(2),
+        "replace" => Arity::Exact(3),
+        "round" | "min" | "max" | "coalesce" | "concat" => Arity::AtLeast(1),
+        _ => return None,
+    })
+}
+
+fn call_builtin(name: &str, args: &[Value]) -> Result<Value, String> {
+    let num = |i: usize| as_f64(&args[i]);
+    let text = |i: usize| as_string(&args[i]);
+    Ok(match name {
+        "abs" => number(num(0).abs()),
+        "floor" => number(num(0).floor()),
+        "ceil" => number(num(0).ceil()),
+        "sqrt" => number(num(0).sqrt()),
+        "pow" => number(num(0).powf(num(1))),
+        "round" => {
+            let factor = 10f64.powi(args.get(1).map_or(0.0, as_f64) as i32);
+            number((num(0) * factor).round() / factor)
+        }
+        "min" => number(args.iter().map(as_f64).fold(f64::INFINITY, f64::min)),
+        "max" => number(args.iter().map(as_f64).fold(f64::NEG_INFINITY, f64::max)),
+        "length" => match &args[0] {
+            Value::Array(items) => json!(items.len()),
+            Value::Object(map) => json!(map.len()),
+            Value::Null => json!(0),
+            other => json!(as_string(other).chars().count()),
+        },
+        "upper" => Value::String(text(0).to_uppercase()),
+        "lower" => Value::String(text(0).to_lowercase()),
+        "trim" => Value::String(text(0).trim().to_string()),
+        "str" => Value::String(text(0)),
+        "num" => number(num(0)),
+        "contains" => Value::Bool(text(0).contains(&text(1))),
+        "starts_with" => Value::Bool(text(0).starts_with(&text(1))),
+        "ends_with" => Value::Bool(text(0).ends_with(&text(1))),
+        "replace" => Value::String(text(0).replace(&text(1), &text(2))),
+        "concat" => Value::String(args.iter().map(as_string).collect()),
+        "coalesce" => args.iter().find(|v| !v.is_null()).cloned().unwrap_or(Value::Null),
+        _ => return Err(format!("no built-in named '{}'", name)),
+    })
+}
+
+fn binary(op: &str, left: Value, right: Value) -> Value {
+    match op {
+        "&&" | "||" => Value::Bool(truthy(&right)),
+        "??" => right,
+        "==" => Value::Bool(loosely_equal(&left, &right)),
+        "!=" => Value::Bool(!loosely_equal(&left, &right)),
+        "<" | "<=" | ">" | ">=" => {
+            let ordering = match (&left, &right) {
+                (Value::String(a), Value::String(b)) => a.partial_cmp(b),
+                _ => as_f64(&left).partial_cmp(&as_f64(&right)),
+            };
+            Value::Bool(match ordering {
+                Some(ordering) => match op {
+                    "<" => ordering.is_lt(),
+                    "<=" => ordering.is_le(),
+                    ">" => ordering.is_gt(),
+                    _ => ordering.is_ge(),
+                },
+                None => false,
+            })
+        }
+        "~" => Value::String(as_string(&left) + &as_string(&right)),
+        "+" if left.is_string() || right.is_string() => Value::String(as_string(&left) + &as_string(&right)),
+        "+" => number(as_f64(&left) + as_f64(&right)),
+        "-" => number(as_f64(&left) - as_f64(&right)),
+        "*" => number(as_f64(&left) * as_f64(&right)),
+        "/" => number(as_f64(&left) / as_f64(&right)),
+        "%" => number(as_f64(&left) % as_f64(&right)),
+        _ => Value::Null,
+    }
+}
+
+fn loosely_equal(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Number(_), Value::Number(_)) => as_f64(left) == as_f64(right),
+        _ => left == right,
+    }
+}
+
+/// Truthiness as the evaluator's `&&`, `||`, `!` and `?:` see it.
+pub fn truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(b) => *b,
+        Value::Number(n) => n.as_f64().unwrap_or(0.0) != 0.0,
+        Value::String(s) => !s.is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        Value::Object(map) => !map.is_empty(),
+    }
+}
+
+fn as_f64(value: &Value) -> f64 {
+    match value {
+        Value::Number(n) => n.as_f64().unwrap_or(0.0),
+        Value::Bool(b) => f64::from(u8::from(*b)),
+        Value::String(s) => s.trim().parse().unwrap_or(0.0),
+        _ => 0.0,
+    }
+}
+
+fn as_string(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Whole numbers stay integers so `2 + 2` renders as `4`, not `4.0`.
+fn number(n: f64) -> Value {
+    if n.fract() == 0.0 && n.abs() < 9.0e15 {
+        json!(n as i64)
+    } else {
+        serde_json::Number::from_f64(n).map_or(Value::Null, Value::Number)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Literal(Value),
+    Field(String),
+    Call(String, Vec<Expr>),
+    Not(Box<Expr>),
+    Neg(Box<Expr>),
+    If(Box<Expr>, Box<Expr>, Box<Expr>),
+    Binary(String, Box<Expr>, Box<Expr>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Str(String),
+    Ident(String),
+    Op(&'static str),
+}
+
+/// Binary operators from loosest to tightest binding.
+const PRECEDENCE: &[&[&str]] = &[
+    &["||"],
+    &["&&"],
+    &["??"],
+    &["==", "!="],
+    &["<", "<=", ">", ">="],
+    &["~"],
+    &["+", "-"],
+    &["*", "/", "%"],
+];
+
+const OPERATORS: &[&str] = &[
+    "==", "!=", "<=", ">=", "&&", "||", "??", "<", ">", "+", "-", "*", "/", "%", "~", "!", "?", ":", "(", ")", ",",
+];
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn new(source: &str) -> Result<Self, ExprError> {
+        let mut tokens = Vec::new();
+        let mut rest = source.trim_start();
+        while let Some(c) = rest.chars().next() {
+            if c.is_ascii_digit() || (c == '.' && rest[1..].starts_with(|d: char| d.is_ascii_digit())) {
+                let end = rest.find(|d: char| !(d.is_ascii_digit() || d == '.')).unwrap_or(rest.len());
+                let n = rest[..end].parse().map_err(|_| ExprError::Parse(format!("bad number '{}'", &rest[..end])))?;
+                tokens.push(Token::Number(n));
+                rest = &rest[end..];
+            } else if c == '"' || c == '\'' {
+                let mut value = String::new();
+                let mut chars = rest[1..].char_indices();
+                let end = loop {
+                    match chars.next() {
+                        None => return Err(ExprError::Parse("unterminated string".into())),
+                        Some((i, q)) if q == c => break i + 2,
+                        Some((_, '\\')) => value.extend(chars.next().map(|(_, e)| e)),
+                        Some((_, ch)) => value.push(ch),
+                    }
+                };
+                tokens.push(Token::Str(value));
+                rest = &rest[end..];
+            } else if c.is_alphabetic() || c == '_' {
+                let end = rest
+                    .find(|d: char| !(d.is_alphanumeric() || d == '_' || d == '.'))
+                    .unwrap_or(rest.len());
+                tokens.push(Token::Ident(rest[..end].to_string()));
+                rest = &rest[end..];
+            } else {
+                let op = OPERATORS
+                    .iter()
+                    .find(|op| rest.starts_with(**op))
+                    .ok_or_else(|| ExprError::Parse(format!("unexpected character '{}'", c)))?;
+                tokens.push(Token::Op(op));
+                rest = &rest[op.len()..];
+            }
+            rest = rest.trim_start();
+        }
+        Ok(Self { tokens, pos: 0 })
+    }
+
+    fn parse(mut self) -> Result<Expr, ExprError> {
+        let expr = self.ternary()?;
+        match self.tokens.get(self.pos) {
+            None => Ok(expr),
+            Some(token) => Err(ExprError::Parse(format!("unexpected {:?}", token))),
+        }
+    }
+
+    fn eat(&mut self, op: &str) -> bool {
+        let matched = matches!(self.tokens.get(self.pos), Some(Token::Op(o)) if *o == op);
+        if matched {
+            self.pos += 1;
+        }
+        matched
+    }
+
+    fn expect(&mut self, op: &str) -> Result<(), ExprError> {
+        if self.eat(op) {
+            Ok(())
+        } else {
+            Err(ExprError::Parse(format!("expected '{}'", op)))
+        }
+    }
+
+    fn ternary(&mut self) -> Result<Expr, ExprError> {
+        let cond = self.binary(0)?;
+        if !self.eat("?") {
+            return Ok(cond);
+        }
+        let then = self.ternary()?;
+        self.expect(":")?;
+        let otherwise = self.ternary()?;
+        Ok(Expr::If(Box::new(cond), Box::new(then), Box::new(otherwise)))
+    }
+
+    fn binary(&mut self, level: usize) -> Result<Expr, ExprError> {
+        if level == PRECEDENCE.len() {
+            return self.unary();
+        }
+        let mut left = self.binary(level + 1)?;
+        while let Some(op) = PRECEDENCE[level].iter().find(|op| self.eat(op)) {
+            let right = self.binary(level + 1)?;
+            left = Expr::Binary(op.to_string(), Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expr, ExprError> {
+        if self.eat("!") {
+            return Ok(Expr::Not(Box::new(self.unary()?)));
+        }
+        if self.eat("-") {
+            return Ok(Expr::Neg(Box::new(self.unary()?)));
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Expr, ExprError> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        match token {
+            Some(Token::Number(n)) => Ok(Expr::Literal(number(n))),
+            Some(Token::Str(s)) => Ok(Expr::Literal(Value::String(s))),
+            Some(Token::Ident(name)) => match name.as_str() {
+                "true" => Ok(Expr::Literal(Value::Bool(true))),
+                "false" => Ok(Expr::Literal(Value::Bool(false))),
+                "null" => Ok(Expr::Literal(Value::Null)),
+                _ if self.eat("(") => {
+                    let mut args = Vec::new();
+                    if !self.eat(")") {
+                        loop {
+                            args.push(self.ternary()?);
+                            if self.eat(")") {
+                                break;
+                            }
+                            self.expect(",")?;
+                        }
+                    }
+                    Ok(Expr::Call(name, args))
+                }
+                _ => Ok(Expr::Field(name)),
+            },
+            Some(Token::Op("(")) => {
+                let inner = self.ternary()?;
+                self.expect(")")?;
+                Ok(inner)
+            }
+            Some(token) => Err(ExprError::Parse(format!("unexpected {:?}", token))),
+            None => Err(ExprError::Parse("unexpected end of expression".into())),
+        }
+    }
+}
+
 // ── Handler ───────────────────────────────────────────────
 
 pub struct ExpressionLanguageHandler;
@@ -168,13 +611,25 @@ impl ExpressionLanguageHandler {
                 message: format!("invalid AST: {}", e),
             }),
             Ok(ast) => {
-                let result = json!({
+                let mut result = json!({
                     "ast": ast,
                     "context": input.context,
                     "evaluated": true,
+                });
+                if let Some(expression) = ast["expression"].as_str() {
+                    let context = serde_json::from_str(&input.context).unwrap_or(json!({}));
+                    match FunctionRegistry::new().evaluate(expression, &context) {
+                        Ok(value) => result["value"] = value,
+                        Err(e) => {
+                            return Ok(ExprLangEvaluateOutput::EvalError {
+                                message: e.to_string(),
+                            })
+                        }
+                    }
+                }
+                Ok(ExprLangEvaluateOutput::Ok {
+                    result: result.to_string(),
                 })
-                .to_string();
-                Ok(ExprLangEvaluateOutput::Ok { result })
             }
         }
     }
@@ -348,4 +803,54 @@ mod tests {
             .unwrap();
         assert!(matches!(result, ExprLangEvaluateOutput::EvalError { .. }));
     }
+
+    // ── function registry tests ────────────────────────────
+
+    fn fiscal_quarter(args: &[Value]) -> Result<Value, String> {
+        // Fiscal year starts in July
+        let date = args[0].as_str().ok_or("expected a YYYY-MM-DD date")?;
+        let month: u32 = date.get(5..7).and_then(|m| m.parse().ok()).ok_or(format!("bad date '{}'", date))?;
+        Ok(json!(format!("Q{}", ((month + 5) % 12) / 3 + 1)))
+    }
+
+    #[test]
+    fn registered_function_is_callable_from_expressions() {
+        let mut registry = FunctionRegistry::new();
+        registry.register("fiscal_quarter", 1, fiscal_quarter);
+        let context = json!({ "deal": { "closed_on": "2024-08-15", "amount": 1200 } });
+
+        assert_eq!(
+            registry.evaluate(r#""FY25 " ~ fiscal_quarter(deal.closed_on)"#, &context),
+            Ok(json!("FY25 Q1"))
+        );
+        assert_eq!(
+            registry.evaluate("fiscal_quarter('2025-03-01') == 'Q3' ? round(deal.amount * 1.05) : 0", &context),
+            Ok(json!(1260))
+        );
+        assert_eq!(registry.evaluate("max(2, 9, 4) + length('abc')", &context), Ok(json!(12)));
+
+        // Registered functions win over built-ins
+        registry.register("upper", 1, |_| Ok(json!("shadowed")));
+        assert_eq!(registry.evaluate("upper('x')", &context), Ok(json!("shadowed")));
+    }
+
+    #[test]
+    fn arity_and_lookup_errors_are_reported() {
+        let mut registry = FunctionRegistry::new();
+        registry.register("fiscal_quarter", 1, fiscal_quarter);
+
+        let err = registry.evaluate("fiscal_quarter('2024-01-01', 2)", &json!({})).unwrap_err();
+        assert_eq!(err, ExprError::Arity { name: "fiscal_quarter".into(), expected: Arity::Exact(1), got: 2 });
+        assert_eq!(err.to_string(), "fiscal_quarter() expects 1 argument, got 2");
+        assert_eq!(
+            registry.evaluate("max()", &json!({})).unwrap_err().to_string(),
+            "max() expects at least 1 argument, got 0"
+        );
+        assert_eq!(registry.evaluate("nope(1)", &json!({})), Err(ExprError::UnknownFunction("nope".into())));
+        assert!(matches!(
+            registry.evaluate("fiscal_quarter(3)", &json!({})),
+            Err(ExprError::Function { .. })
+        ));
+        assert!(matches!(registry.evaluate("(1 + 2", &json!({})), Err(ExprError::Parse(_))));
+    }
 }
@@ -99,7 +99,8 @@ impl FieldMappingHandler {
                 message: format!("Mapping \"{}\" not found", input.mapping_id),
             }),
             Some(mut record) => {
-                let rules = record["rules"].as_array_mut().unwrap_or(&mut vec![]);
+                let mut empty = vec![];
+                let rules = record["rules"].as_array_mut().unwrap_or(&mut empty);
                 let mut new_rules: Vec<serde_json::Value> = rules.clone();
                 new_rules.push(json!({
                     "source_field": input.source_field,
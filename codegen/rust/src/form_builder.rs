@@ -2,10 +2,20 @@
 //
 // Manages form construction, validation, and widget registration.
 // See Architecture doc Sections on form and input handling.
+//
+// `build_form` also derives a form directly from a field schema, choosing a
+// widget per field type and turning the schema's quality rule options
+// (enum values, patterns, ranges) into client-side validation.
+// `validate_submission` runs those same rules on the server, so a submitted
+// form is judged exactly as the client judged it.
 
+use crate::property::{Cardinality, FieldType, PropertyError, PropertySchema};
 use crate::storage::{ConceptStorage, StorageResult};
+use crate::type_system::{Assignability, Type, TypeHierarchy};
+use crate::validator::{self, BoxedValidator, ValidationReport};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::collections::HashMap;
 
 // ── BuildForm ─────────────────────────────────────────────
 
@@ -77,8 +87,262 @@ pub enum RegisterWidgetOutput {
     },
 }
 
+// ── Schema-derived forms ──────────────────────────────────
+
+/// A schema field, shaped like the quality rule providers' `FieldDef`.
+/// `constraints` uses the same option keys as the enum_check (`values`),
+/// pattern (`pattern`, `preset`, `flags`) and range (`min`, `max`) rules.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldDef {
+    pub name: String,
+    pub field_type: String,
+    pub required: Option<bool>,
+    pub constraints: Option<HashMap<String, serde_json::Value>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Widget {
+    Text,
+    TextArea,
+    Number,
+    Checkbox,
+    Select,
+    DatePicker,
+    DateTimePicker,
+}
+
+/// A check the client runs before submitting.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "rule", rename_all = "snake_case")]
+pub enum ClientValidation {
+    Required,
+    OneOf {
+        values: Vec<serde_json::Value>,
+    },
+    Pattern {
+        pattern: String,
+        flags: Option<String>,
+    },
+    Format {
+        preset: String,
+    },
+    Min {
+        value: serde_json::Value,
+    },
+    Max {
+        value: serde_json::Value,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FormField {
+    pub name: String,
+    pub widget: Widget,
+    pub required: bool,
+    /// Choices for `Select` widgets.
+    pub options: Vec<serde_json::Value>,
+    pub validation: Vec<ClientValidation>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FormSpec {
+    pub fields: Vec<FormField>,
+}
+
+fn widget_for(field: &FieldDef, has_values: bool) -> Widget {
+    match field.field_type.to_ascii_lowercase().as_str() {
+        "enum" | "select" => Widget::Select,
+        _ if has_values => Widget::Select,
+        "boolean" | "bool" => Widget::Checkbox,
+        "integer" | "int" | "number" | "float" | "decimal" => Widget::Number,
+        "date" => Widget::DatePicker,
+        "datetime" | "timestamp" => Widget::DateTimePicker,
+        "textarea" | "long_text" | "richtext" => Widget::TextArea,
+        _ => Widget::Text,
+    }
+}
+
+/// Derive a form from a field schema, one form field per schema field in
+/// schema order.
+pub fn build_form(schema: &[FieldDef]) -> FormSpec {
+    let fields = schema
+        .iter()
+        .map(|field| {
+            let option = |key: &str| field.constraints.as_ref().and_then(|c| c.get(key));
+            let options = option("values")
+                .and_then(|v| v.as_array())
+                .cloned()
+                .unwrap_or_default();
+            let required = field.required.unwrap_or(false);
+
+            let mut validation = vec![];
+            if required {
+                validation.push(ClientValidation::Required);
+            }
+            if !options.is_empty() {
+                validation.push(ClientValidation::OneOf {
+                    values: options.clone(),
+                });
+            }
+            if let Some(pattern) = option("pattern").and_then(|v| v.as_str()) {
+                validation.push(ClientValidation::Pattern {
+                    pattern: pattern.to_string(),
+                    flags: option("flags").and_then(|v| v.as_str()).map(str::to_string),
+                });
+            } else if let Some(preset) = option("preset").and_then(|v| v.as_str()) {
+                validation.push(ClientValidation::Format {
+                    preset: preset.to_string(),
+                });
+            }
+            if let Some(min) = option("min") {
+                validation.push(ClientValidation::Min { value: min.clone() });
+            }
+            if let Some(max) = option("max") {
+                validation.push(ClientValidation::Max { value: max.clone() });
+            }
+
+            FormField {
+                name: field.name.clone(),
+                widget: widget_for(field, !options.is_empty()),
+                required,
+                options,
+                validation,
+            }
+        })
+        .collect();
+
+    FormSpec { fields }
+}
+
+/// A submitted record, keyed by field name.
+pub type Record = HashMap<String, serde_json::Value>;
+
+/// Regexes for the pattern quality rule's named presets.
+fn pattern_preset(name: &str) -> Option<&'static str> {
+    match name {
+        "email" => Some(r"^[^@\s]+@[^@\s]+\.[^@\s]+$"),
+        "url" => Some(r"^https?://[^\s/?#]+\.[^\s/?#]+(?:[/?#]\S*)?$"),
+        "date" => Some(r"^\d{4}-\d{2}-\d{2}$"),
+        "datetime" => {
+            Some(r"^\d{4}-\d{2}-\d{2}[T ]\d{2}:\d{2}(?::\d{2}(?:\.\d+)?)?(?:Z|[+-]\d{2}:?\d{2})?$")
+        }
+        "uuid" => Some(r"^(?i)[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}$"),
+        _ => None,
+    }
+}
+
+/// Inline regex flags for a pattern's JavaScript flags. `g`, `d` and `u`
+/// do not change whether a value matches, so they are dropped; flags with
+/// no equivalent, such as sticky `y`, are rejected.
+fn inline_flags(flags: &str) -> Result<String, String> {
+    let mut inline = String::new();
+    for flag in flags.chars() {
+        match flag {
+            'i' | 'm' | 's' if !inline.contains(flag) => inline.push(flag),
+            'i' | 'm' | 's' | 'g' | 'd' | 'u' => {}
+            other => return Err(format!("unsupported pattern flag '{}'", other)),
+        }
+    }
+    Ok(inline)
+}
+
+impl ClientValidation {
+    /// The server-side validator for this rule. A pattern that does not
+    /// compile, an unsupported flag or an unknown preset is an error in the
+    /// form definition.
+    pub fn validator(&self) -> Result<BoxedValidator, String> {
+        match self {
+            ClientValidation::Required => Ok(validator::required()),
+            ClientValidation::OneOf { values } => Ok(validator::one_of(values.clone())),
+            ClientValidation::Pattern { pattern, flags } => {
+                let inline = inline_flags(flags.as_deref().unwrap_or(""))?;
+                let pattern = if inline.is_empty() {
+                    pattern.clone()
+                } else {
+                    format!("(?{}){}", inline, pattern)
+                };
+                validator::pattern(&pattern).map_err(|e| e.to_string())
+            }
+            ClientValidation::Format { preset } => {
+                let pattern = pattern_preset(preset)
+                    .ok_or_else(|| format!("unknown pattern preset '{}'", preset))?;
+                validator::pattern(pattern).map_err(|e| e.to_string())
+            }
+            ClientValidation::Min { value } => Ok(validator::range(Some(value.clone()), None)),
+            ClientValidation::Max { value } => Ok(validator::range(None, Some(value.clone()))),
+        }
+    }
+}
+
+/// Validate a submission against the rules `build_form` attached to each
+/// field. Failures are reported under the field name for re-display.
+pub fn validate_submission(form: &FormSpec, submission: &Record) -> ValidationReport {
+    let record = serde_json::Value::Object(
+        submission
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect(),
+    );
+
+    let mut report = ValidationReport::new();
+    for field in &form.fields {
+        let value = record.get(&field.name).unwrap_or(&serde_json::Value::Null);
+        for rule in &field.validation {
+            match rule.validator() {
+                Ok(validator) => validator.check(value, &field.name, &mut report),
+                Err(message) => report.add(&field.name, "pattern", message),
+            }
+        }
+    }
+    report
+}
+
 // ── Handler ───────────────────────────────────────────────
 
+/// The property schema a field is bound to through its `property` key.
+async fn bound_property(
+    field: &serde_json::Value,
+    storage: &dyn ConceptStorage,
+) -> StorageResult<Option<PropertySchema>> {
+    match field["property"].as_str() {
+        Some(key) => PropertySchema::load(key, storage).await,
+        None => Ok(None),
+    }
+}
+
+/// The type a field's value must be assignable to. A field bound to a
+/// `property` takes that property's declared type, nullable unless the
+/// field is required; otherwise its own `type` is used when the hierarchy
+/// knows it. Widget-only types such as "text" are not checked.
+fn expected_type(
+    field: &serde_json::Value,
+    schema: Option<&PropertySchema>,
+    types: &TypeHierarchy,
+) -> Option<Type> {
+    let Some(schema) = schema else {
+        return field["type"]
+            .as_str()
+            .and_then(|t| Type::parse(t).ok())
+            .filter(|t| types.is_known(t));
+    };
+
+    let item = match schema.field_type {
+        FieldType::Array => Type::Array(Box::new(Type::Any)),
+        other => Type::parse(other.as_str()).unwrap_or(Type::Any),
+    };
+    let expected = match schema.cardinality {
+        Cardinality::Single => item,
+        // A single value is taken as a one-element list
+        Cardinality::Many { .. } => types.union([item.clone(), Type::Array(Box::new(item))]),
+    };
+    Some(if field["required"].as_bool().unwrap_or(false) {
+        expected
+    } else {
+        expected.nullable()
+    })
+}
+
 pub struct FormBuilderHandler;
 
 impl FormBuilderHandler {
@@ -161,14 +425,43 @@ impl FormBuilderHandler {
                     .unwrap_or_default();
 
                 let mut errors: Vec<String> = vec![];
+                let types = TypeHierarchy::load(storage).await?;
 
                 for field in &fields {
                     let field_name = field["name"].as_str().unwrap_or("");
                     let required = field["required"].as_bool().unwrap_or(false);
 
-                    if required && form_data.get(field_name).is_none() {
-                        errors.push(format!("Field '{}' is required", field_name));
-                    }
+                    let Some(value) = form_data.get(field_name) else {
+                        if required {
+                            errors.push(format!("Field '{}' is required", field_name));
+                        }
+                        continue;
+                    };
+
+                    let property = bound_property(field, storage).await?;
+                    let Some(expected) = expected_type(field, property.as_ref(), &types) else {
+                        continue;
+                    };
+                    let actual = Type::of_value(value);
+                    let mismatch = format!(
+                        "Field '{}' expects {}, got {}",
+                        field_name, expected, actual
+                    );
+                    // A bound property is checked with the same coercion
+                    // Property/set applies, so the form accepts exactly
+                    // what the property will store.
+                    let error = match &property {
+                        Some(_) if value.is_null() && !required => None,
+                        Some(schema) => match schema.coerce(value) {
+                            Ok(_) => None,
+                            Err(PropertyError::TypeMismatch { .. }) => Some(mismatch),
+                            Err(other) => Some(format!("Field '{}': {}", field_name, other)),
+                        },
+                        None => (types.assignable(&actual, &expected)
+                            == Assignability::Incompatible)
+                            .then_some(mismatch),
+                    };
+                    errors.extend(error);
                 }
 
                 if errors.is_empty() {
@@ -424,4 +717,299 @@ mod tests {
             }
         }
     }
+
+    #[tokio::test]
+    async fn validate_form_rejects_values_of_incompatible_type() {
+        let storage = InMemoryStorage::new();
+        let handler = FormBuilderHandler;
+        storage
+            .put(
+                "schema",
+                "order",
+                serde_json::json!({
+                    "schema_id": "order",
+                    "fields": [
+                        {"name": "quantity", "type": "int", "required": true},
+                        {"name": "note", "type": "string?", "required": false},
+                        {"name": "title", "type": "text", "required": false}
+                    ]
+                }),
+            )
+            .await
+            .unwrap();
+
+        let validate = |form_data: &str| {
+            handler.validate_form(
+                ValidateFormInput {
+                    form_data: form_data.into(),
+                    schema_id: "order".into(),
+                },
+                &storage,
+            )
+        };
+
+        // "3" can be coerced to an int; null fits a nullable string
+        let result = validate(r#"{"quantity": "3", "note": null, "title": 1}"#)
+            .await
+            .unwrap();
+        assert!(matches!(result, ValidateFormOutput::Ok { valid: true }));
+
+        let result = validate(r#"{"quantity": [1], "note": {}}"#).await.unwrap();
+        match result {
+            ValidateFormOutput::Invalid { errors } => {
+                let errors: Vec<String> = serde_json::from_str(&errors).unwrap();
+                assert_eq!(
+                    errors,
+                    vec![
+                        "Field 'quantity' expects int, got array<int>",
+                        "Field 'note' expects string | null, got object",
+                    ]
+                );
+            }
+            ValidateFormOutput::Ok { .. } => panic!("expected Invalid"),
+        }
+    }
+
+    fn field_def(
+        name: &str,
+        field_type: &str,
+        required: bool,
+        constraints: serde_json::Value,
+    ) -> FieldDef {
+        FieldDef {
+            name: name.into(),
+            field_type: field_type.into(),
+            required: Some(required),
+            constraints: serde_json::from_value(constraints).ok(),
+        }
+    }
+
+    #[test]
+    fn build_form_maps_required_enum_to_select_with_options() {
+        let schema = vec![
+            field_def(
+                "status",
+                "enum",
+                true,
+                serde_json::json!({ "values": ["draft", "published"] }),
+            ),
+            field_def(
+                "title",
+                "string",
+                false,
+                serde_json::json!({ "pattern": "^[A-Z]", "flags": "i" }),
+            ),
+            field_def("featured", "boolean", false, serde_json::Value::Null),
+            field_def("publish_on", "date", false, serde_json::Value::Null),
+            field_def(
+                "rating",
+                "integer",
+                false,
+                serde_json::json!({ "min": 1, "max": 5 }),
+            ),
+        ];
+
+        let form = build_form(&schema);
+        let widgets: Vec<Widget> = form.fields.iter().map(|f| f.widget).collect();
+        assert_eq!(
+            widgets,
+            vec![
+                Widget::Select,
+                Widget::Text,
+                Widget::Checkbox,
+                Widget::DatePicker,
+                Widget::Number
+            ]
+        );
+
+        let status = &form.fields[0];
+        assert!(status.required);
+        assert_eq!(
+            status.options,
+            vec![serde_json::json!("draft"), serde_json::json!("published")]
+        );
+        assert_eq!(
+            status.validation,
+            vec![
+                ClientValidation::Required,
+                ClientValidation::OneOf {
+                    values: status.options.clone()
+                },
+            ]
+        );
+
+        let title = &form.fields[1];
+        assert!(!title.required);
+        assert_eq!(
+            title.validation,
+            vec![ClientValidation::Pattern {
+                pattern: "^[A-Z]".into(),
+                flags: Some("i".into())
+            }]
+        );
+        assert_eq!(
+            form.fields[4].validation,
+            vec![
+                ClientValidation::Min {
+                    value: serde_json::json!(1)
+                },
+                ClientValidation::Max {
+                    value: serde_json::json!(5)
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn pattern_flags_become_inline_flags() {
+        let pattern = |flags: &str| ClientValidation::Pattern {
+            pattern: "^b.c$".into(),
+            flags: Some(flags.into()),
+        };
+        let matches = |flags: &str, value: &str| {
+            pattern(flags)
+                .validator()
+                .unwrap()
+                .validate(&serde_json::json!(value))
+                .is_valid()
+        };
+
+        assert!(matches("i", "B-C"));
+        assert!(!matches("", "B-C"));
+        assert!(matches("s", "b\nc"));
+        assert!(!matches("i", "b\nc"));
+        assert!(matches("mg", "a\nb-c"));
+        assert!(matches("gu", "b-c"));
+        assert_eq!(
+            pattern("iy").validator().err(),
+            Some("unsupported pattern flag 'y'".to_string())
+        );
+    }
+
+    #[test]
+    fn validate_submission_reports_pattern_and_required_failures() {
+        let schema = vec![
+            field_def("title", "string", true, serde_json::Value::Null),
+            field_def(
+                "slug",
+                "string",
+                false,
+                serde_json::json!({ "pattern": "^[a-z0-9-]+$" }),
+            ),
+            field_def(
+                "contact",
+                "string",
+                false,
+                serde_json::json!({ "preset": "email" }),
+            ),
+            field_def(
+                "status",
+                "enum",
+                true,
+                serde_json::json!({ "values": ["draft", "published"] }),
+            ),
+        ];
+        let form = build_form(&schema);
+
+        let submission: Record = serde_json::from_value(serde_json::json!({
+            "title": "  ",
+            "slug": "Not A Slug",
+            "contact": "editor@example.com",
+            "status": "draft",
+        }))
+        .unwrap();
+        let report = validate_submission(&form, &submission);
+
+        assert_eq!(report.error_count(), 2);
+        assert_eq!(report.errors_for("title")[0].rule, "required");
+        assert_eq!(report.errors_for("slug")[0].rule, "pattern");
+        assert!(report.errors_for("contact").is_empty());
+
+        let submission: Record = serde_json::from_value(serde_json::json!({
+            "title": "Launch",
+            "contact": "nobody",
+            "status": "archived",
+        }))
+        .unwrap();
+        let report = validate_submission(&form, &submission);
+        let rules: Vec<&str> = ["contact", "status"]
+            .iter()
+            .map(|field| report.errors_for(field)[0].rule.as_str())
+            .collect();
+        assert_eq!(rules, vec!["pattern", "enum_check"]);
+        assert_eq!(report.error_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn validate_form_checks_values_against_bound_properties() {
+        use crate::property::{DefineTypeInput, PropertyHandler};
+
+        let storage = InMemoryStorage::new();
+        let handler = FormBuilderHandler;
+        for (key, prop_type, constraints) in [
+            ("priority", "integer", serde_json::json!({})),
+            ("estimate", "number", serde_json::json!({})),
+            ("labels", "string", serde_json::json!({ "multiple": true })),
+        ] {
+            PropertyHandler
+                .define_type(
+                    DefineTypeInput { key: key.into(), prop_type: prop_type.into(), constraints },
+                    &storage,
+                )
+                .await
+                .unwrap();
+        }
+        storage
+            .put(
+                "schema",
+                "task",
+                serde_json::json!({
+                    "schema_id": "task",
+                    "fields": [
+                        {"name": "priority", "type": "text", "property": "priority", "required": true},
+                        {"name": "estimate", "type": "text", "property": "estimate", "required": false},
+                        {"name": "labels", "type": "text", "property": "labels", "required": false}
+                    ]
+                }),
+            )
+            .await
+            .unwrap();
+
+        let validate = |form_data: &str| {
+            handler.validate_form(
+                ValidateFormInput {
+                    form_data: form_data.into(),
+                    schema_id: "task".into(),
+                },
+                &storage,
+            )
+        };
+
+        for ok in [
+            r#"{"priority": 2, "labels": ["a", "b"]}"#,
+            r#"{"priority": "2", "estimate": "3.5", "labels": "a"}"#,
+            r#"{"priority": 2, "labels": null}"#,
+        ] {
+            let result = validate(ok).await.unwrap();
+            assert!(matches!(result, ValidateFormOutput::Ok { valid: true }), "{}", ok);
+        }
+
+        let result = validate(r#"{"priority": "2.5", "estimate": "abc", "labels": [{}]}"#)
+            .await
+            .unwrap();
+        match result {
+            ValidateFormOutput::Invalid { errors } => {
+                let errors: Vec<String> = serde_json::from_str(&errors).unwrap();
+                assert_eq!(
+                    errors,
+                    vec![
+                        "Field 'priority' expects int, got string",
+                        "Field 'estimate' expects number | null, got string",
+                        "Field 'labels' expects string | array<string> | null, got array<object>",
+                    ]
+                );
+            }
+            ValidateFormOutput::Ok { .. } => panic!("expected Invalid"),
+        }
+    }
 }
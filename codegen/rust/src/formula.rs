@@ -6,6 +6,7 @@
 use crate::storage::{ConceptStorage, StorageResult};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::collections::{BTreeMap, BTreeSet};
 
 // ── Evaluate ──────────────────────────────────────────────
 
@@ -74,6 +75,184 @@ pub enum FormulaSetExpressionOutput {
     Ok { formula_id: String },
 }
 
+// ── Dependency graph ──────────────────────────────────────
+
+/// Why a recompute could not finish.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormulaError {
+    /// The formulas on a reference loop, in reference order.
+    CircularReference { cycle: Vec<String> },
+    Evaluation { formula_id: String, message: String },
+}
+
+impl std::fmt::Display for FormulaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FormulaError::CircularReference { cycle } => {
+                write!(f, "circular reference: {}", cycle.join(" -> "))?;
+                if let Some(first) = cycle.first() {
+                    write!(f, " -> {}", first)?;
+                }
+                Ok(())
+            }
+            FormulaError::Evaluation { formula_id, message } => {
+                write!(f, "formula '{}' failed: {}", formula_id, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FormulaError {}
+
+/// Field names an expression reads: identifiers outside string literals that
+/// are not function calls or literals, sorted and deduplicated.
+pub fn extract_references(expression: &str) -> Vec<String> {
+    let chars: Vec<char> = expression.chars().collect();
+    let mut refs = BTreeSet::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '"' || c == '\'' {
+            i += 1;
+            while i < chars.len() && chars[i] != c {
+                i += if chars[i] == '\\' { 2 } else { 1 };
+            }
+            i += 1;
+        } else if c.is_ascii_digit() {
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '.') {
+                i += 1;
+            }
+        } else if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '.') {
+                i += 1;
+            }
+            let name: String = chars[start..i].iter().collect();
+            let is_call = chars[i..].iter().find(|c| !c.is_whitespace()) == Some(&'(');
+            if !is_call && !matches!(name.as_str(), "true" | "false" | "null") {
+                refs.insert(name);
+            }
+        } else {
+            i += 1;
+        }
+    }
+    refs.into_iter().collect()
+}
+
+/// Every formula's direct references, keyed by formula id.
+async fn load_graph(storage: &dyn ConceptStorage) -> StorageResult<BTreeMap<String, Vec<String>>> {
+    let formulas = storage.find("formula", None).await?;
+    Ok(formulas
+        .iter()
+        .filter_map(|record| {
+            let formula_id = record["formula_id"].as_str()?.to_string();
+            let dependencies = record["dependencies"]
+                .as_str()
+                .and_then(|deps| serde_json::from_str(deps).ok())
+                .unwrap_or_default();
+            Some((formula_id, dependencies))
+        })
+        .collect())
+}
+
+/// Formulas that reference `field` directly, sorted by id.
+pub async fn dependents_of(field: &str, storage: &dyn ConceptStorage) -> StorageResult<Vec<String>> {
+    let graph = load_graph(storage).await?;
+    Ok(graph
+        .into_iter()
+        .filter(|(_, deps)| deps.iter().any(|d| d == field))
+        .map(|(formula_id, _)| formula_id)
+        .collect())
+}
+
+/// Formulas that must be recomputed after `changed_fields` change, in an order
+/// where each comes after everything it references. Changed fields that are
+/// formulas themselves are included.
+async fn recompute_order(
+    changed_fields: &[&str],
+    storage: &dyn ConceptStorage,
+) -> StorageResult<Result<Vec<String>, FormulaError>> {
+    let graph = load_graph(storage).await?;
+
+    let mut affected: BTreeSet<&str> = changed_fields
+        .iter()
+        .filter(|field| graph.contains_key(**field))
+        .copied()
+        .collect();
+    let mut frontier: Vec<&str> = changed_fields.to_vec();
+    while let Some(field) = frontier.pop() {
+        for (formula_id, deps) in &graph {
+            if deps.iter().any(|d| d == field) && affected.insert(formula_id) {
+                frontier.push(formula_id);
+            }
+        }
+    }
+
+    let edges = affected
+        .iter()
+        .flat_map(|formula_id| {
+            graph[*formula_id]
+                .iter()
+                .filter(|dep| affected.contains(dep.as_str()))
+                .map(move |dep| (dep.as_str(), *formula_id))
+        });
+    let sorted = match crate::relation::topological_sort(edges) {
+        Ok(sorted) => sorted,
+        Err(cycle) => return Ok(Err(FormulaError::CircularReference { cycle: cycle.nodes })),
+    };
+
+    // Formulas with no affected references have no edges; they can go first
+    let mut order: Vec<String> = affected
+        .iter()
+        .filter(|formula_id| !sorted.iter().any(|s| s == *formula_id))
+        .map(|formula_id| formula_id.to_string())
+        .collect();
+    order.extend(sorted);
+    Ok(Ok(order))
+}
+
+/// Re-evaluate every formula affected by `changed_fields`, in dependency
+/// order, and cache each result on its formula record. Each formula sees
+/// `inputs` plus the current value of every other formula under its id.
+/// Returns the recomputed `(formula_id, value)` pairs in evaluation order.
+pub async fn recompute(
+    changed_fields: &[&str],
+    inputs: &serde_json::Value,
+    evaluate: impl Fn(&str, &serde_json::Value) -> Result<serde_json::Value, String>,
+    storage: &dyn ConceptStorage,
+) -> StorageResult<Result<Vec<(String, serde_json::Value)>, FormulaError>> {
+    let order = match recompute_order(changed_fields, storage).await? {
+        Ok(order) => order,
+        Err(err) => return Ok(Err(err)),
+    };
+
+    let mut context = inputs.as_object().cloned().unwrap_or_default();
+    for record in storage.find("formula", None).await? {
+        if let (Some(formula_id), Some(value)) = (record["formula_id"].as_str(), record.get("value")) {
+            context.entry(formula_id.to_string()).or_insert_with(|| value.clone());
+        }
+    }
+
+    let mut results = Vec::with_capacity(order.len());
+    for formula_id in order {
+        let Some(mut record) = storage.get("formula", &formula_id).await? else { continue };
+        let expression = record["expression"].as_str().unwrap_or("");
+        let value = match evaluate(expression, &serde_json::Value::Object(context.clone())) {
+            Ok(value) => value,
+            Err(message) => return Ok(Err(FormulaError::Evaluation { formula_id, message })),
+        };
+
+        record["value"] = value.clone();
+        record["cached_result"] = json!(value.to_string());
+        record["evaluated_at"] = json!(chrono::Utc::now().to_rfc3339());
+        storage.put("formula", &formula_id, record).await?;
+
+        context.insert(formula_id.clone(), value.clone());
+        results.push((formula_id, value));
+    }
+    Ok(Ok(results))
+}
+
 // ── Handler ───────────────────────────────────────────────
 
 pub struct FormulaHandler;
@@ -171,9 +350,11 @@ impl FormulaHandler {
     ) -> StorageResult<FormulaSetExpressionOutput> {
         let now = chrono::Utc::now().to_rfc3339();
         let existing = storage.get("formula", &input.formula_id).await?;
+        let dependencies = serde_json::to_string(&extract_references(&input.expression))?;
         let record = match existing {
             Some(mut r) => {
                 r["expression"] = json!(input.expression);
+                r["dependencies"] = json!(dependencies);
                 r["updated_at"] = json!(now);
                 r["cached_result"] = serde_json::Value::Null;
                 r
@@ -182,7 +363,7 @@ impl FormulaHandler {
                 json!({
                     "formula_id": input.formula_id,
                     "expression": input.expression,
-                    "dependencies": "[]",
+                    "dependencies": dependencies,
                     "cached_result": null,
                     "created_at": now,
                     "updated_at": now,
@@ -300,7 +481,7 @@ mod tests {
         match result {
             FormulaGetDependenciesOutput::Ok { formula_id, dependencies } => {
                 assert_eq!(formula_id, "f1");
-                assert_eq!(dependencies, "[]");
+                assert_eq!(dependencies, r#"["x","y"]"#);
             }
             FormulaGetDependenciesOutput::NotFound { .. } => panic!("expected Ok"),
         }
@@ -371,4 +552,73 @@ mod tests {
             .unwrap();
         assert!(matches!(result, FormulaInvalidateOutput::NotFound { .. }));
     }
+
+    // ── dependency graph tests ─────────────────────────────
+
+    async fn define(storage: &InMemoryStorage, formula_id: &str, expression: &str) {
+        FormulaHandler
+            .set_expression(
+                FormulaSetExpressionInput {
+                    formula_id: formula_id.into(),
+                    expression: expression.into(),
+                },
+                storage,
+            )
+            .await
+            .unwrap();
+    }
+
+    /// Sums the numbers and field references joined by `+`.
+    fn sum(expression: &str, context: &serde_json::Value) -> Result<serde_json::Value, String> {
+        let mut total = 0.0;
+        for term in expression.split('+').map(str::trim) {
+            total += match term.parse::<f64>() {
+                Ok(n) => n,
+                Err(_) => context[term].as_f64().ok_or(format!("'{}' has no value", term))?,
+            };
+        }
+        Ok(json!(total))
+    }
+
+    #[test]
+    fn extract_references_skips_calls_literals_and_strings() {
+        assert_eq!(
+            extract_references(r#"round(price * qty, 2) + tax.rate ~ "label x" ~ true ~ 1.5e3"#),
+            vec!["price", "qty", "tax.rate"]
+        );
+    }
+
+    #[tokio::test]
+    async fn recompute_follows_chain_in_order() {
+        let storage = InMemoryStorage::new();
+        define(&storage, "C", "B + 100").await;
+        define(&storage, "B", "A + 10").await;
+        define(&storage, "unrelated", "Z + 1").await;
+
+        assert_eq!(dependents_of("A", &storage).await.unwrap(), vec!["B"]);
+        assert_eq!(dependents_of("B", &storage).await.unwrap(), vec!["C"]);
+
+        let results = recompute(&["A"], &json!({ "A": 1, "Z": 5 }), sum, &storage).await.unwrap().unwrap();
+        assert_eq!(results, vec![("B".to_string(), json!(11.0)), ("C".to_string(), json!(111.0))]);
+
+        let record = storage.get("formula", "C").await.unwrap().unwrap();
+        assert_eq!(record["value"], json!(111.0));
+        assert!(storage.get("formula", "unrelated").await.unwrap().unwrap().get("value").is_none());
+
+        // Changing only C's own expression reuses B's cached value
+        define(&storage, "C", "B + 1").await;
+        let results = recompute(&["C"], &json!({}), sum, &storage).await.unwrap().unwrap();
+        assert_eq!(results, vec![("C".to_string(), json!(12.0))]);
+    }
+
+    #[tokio::test]
+    async fn recompute_reports_circular_reference() {
+        let storage = InMemoryStorage::new();
+        define(&storage, "A", "B + 1").await;
+        define(&storage, "B", "A + 1").await;
+
+        let err = recompute(&["A"], &json!({}), sum, &storage).await.unwrap().unwrap_err();
+        assert_eq!(err, FormulaError::CircularReference { cycle: vec!["A".into(), "B".into()] });
+        assert_eq!(err.to_string(), "circular reference: A -> B -> A");
+    }
 }
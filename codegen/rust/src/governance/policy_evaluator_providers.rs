@@ -101,7 +101,8 @@ impl CustomEvaluatorHandler {
         let record = storage.get("custom_eval", evaluator).await?;
         if record.is_none() { return Ok(json!({ "variant": "not_found", "evaluator": evaluator })); }
         let rec = record.unwrap();
-        let tree = rec.get("predicateTree").unwrap_or(&json!({}));
+        let empty = json!({});
+        let tree = rec.get("predicateTree").unwrap_or(&empty);
         let result = evaluate_predicate(tree, context);
         Ok(json!({ "variant": "result", "evaluator": evaluator, "output": result, "decision": if result { "allow" } else { "deny" } }))
     }
@@ -195,12 +195,13 @@ impl PageRankReputationHandler {
 
         let nodes: Vec<String> = node_set.into_iter().collect();
         let n = nodes.len();
+        let n_f = n as f64;
         if n == 0 { return Ok(json!({ "variant": "computed", "config": config, "scores": {} })); }
 
-        let mut scores: HashMap<String, f64> = nodes.iter().map(|n| (n.clone(), 1.0 / n as f64)).collect();
+        let mut scores: HashMap<String, f64> = nodes.iter().map(|n| (n.clone(), 1.0 / n_f)).collect();
 
         for _ in 0..iterations {
-            let mut new_scores: HashMap<String, f64> = nodes.iter().map(|n| (n.clone(), (1.0 - d) / n as f64)).collect();
+            let mut new_scores: HashMap<String, f64> = nodes.iter().map(|n| (n.clone(), (1.0 - d) / n_f)).collect();
             for node in &nodes {
                 if let Some(outs) = outgoing.get(node) {
                     let total_out: f64 = outs.iter().map(|(_, w)| w).sum();
@@ -6,6 +6,7 @@
 use crate::storage::{ConceptStorage, StorageResult};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 
 // ── AddNode ───────────────────────────────────────────────
 
@@ -95,6 +96,122 @@ pub enum GetNeighborsOutput {
     },
 }
 
+// ── Traversal ─────────────────────────────────────────────
+
+/// Snapshot of the directed edges in `graph_edge`, for walks that go past
+/// direct neighbors. Every walk tracks visited nodes, so cycles terminate,
+/// and visits children in id order so results are deterministic.
+#[derive(Debug, Clone, Default)]
+pub struct GraphTraversal {
+    children: BTreeMap<String, Vec<String>>,
+}
+
+impl GraphTraversal {
+    pub async fn load(storage: &dyn ConceptStorage) -> StorageResult<Self> {
+        let edges = storage.find("graph_edge", None).await?;
+        Ok(Self::from_edges(edges.iter().map(|edge| {
+            (
+                edge["source_id"].as_str().unwrap_or(""),
+                edge["target_id"].as_str().unwrap_or(""),
+            )
+        })))
+    }
+
+    pub fn from_edges<'a>(edges: impl IntoIterator<Item = (&'a str, &'a str)>) -> Self {
+        let mut children: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for (source, target) in edges {
+            children.entry(source.to_string()).or_default().push(target.to_string());
+        }
+        for targets in children.values_mut() {
+            targets.sort();
+            targets.dedup();
+        }
+        Self { children }
+    }
+
+    fn children_of(&self, node: &str) -> &[String] {
+        self.children.get(node).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Breadth-first walk from `start`, at most `max_depth` edges deep.
+    /// Returns the path from `start` to each node reached, nearest first.
+    pub fn bfs(&self, start: &str, max_depth: usize) -> Vec<Vec<String>> {
+        let mut parents: HashMap<&str, &str> = HashMap::new();
+        let mut visited: HashSet<&str> = HashSet::from([start]);
+        let mut queue: VecDeque<(&str, usize)> = VecDeque::from([(start, 0)]);
+        let mut paths = Vec::new();
+
+        while let Some((node, depth)) = queue.pop_front() {
+            if depth == max_depth {
+                continue;
+            }
+            for child in self.children_of(node) {
+                if visited.insert(child) {
+                    parents.insert(child, node);
+                    paths.push(path_to(child, &parents));
+                    queue.push_back((child, depth + 1));
+                }
+            }
+        }
+        paths
+    }
+
+    /// Depth-first (pre-order) walk from `start`. Returns the path from
+    /// `start` to each node reached, in visit order.
+    pub fn dfs(&self, start: &str) -> Vec<Vec<String>> {
+        let mut visited: HashSet<&str> = HashSet::from([start]);
+        let mut paths = Vec::new();
+        let mut path = vec![start.to_string()];
+        // Each frame is a node on the current path and its next child index
+        let mut stack: Vec<(&str, usize)> = vec![(start, 0)];
+
+        while let Some((node, next)) = stack.last_mut() {
+            let Some(child) = self.children_of(node).get(*next) else {
+                stack.pop();
+                path.pop();
+                continue;
+            };
+            *next += 1;
+            if visited.insert(child) {
+                path.push(child.clone());
+                paths.push(path.clone());
+                stack.push((child, 0));
+            }
+        }
+        paths
+    }
+
+    /// Fewest-edges path from `from` to `to`, both ends included.
+    pub fn shortest_path(&self, from: &str, to: &str) -> Option<Vec<String>> {
+        if from == to {
+            return Some(vec![from.to_string()]);
+        }
+        self.bfs(from, usize::MAX)
+            .into_iter()
+            .find(|path| path.last().map(String::as_str) == Some(to))
+    }
+
+    /// Every node reachable from `start`, nearest first. `start` itself is
+    /// excluded even when a cycle leads back to it.
+    pub fn descendants(&self, start: &str) -> Vec<String> {
+        self.bfs(start, usize::MAX)
+            .into_iter()
+            .filter_map(|mut path| path.pop())
+            .collect()
+    }
+}
+
+fn path_to(node: &str, parents: &HashMap<&str, &str>) -> Vec<String> {
+    let mut path = vec![node.to_string()];
+    let mut current = node;
+    while let Some(parent) = parents.get(current) {
+        path.push(parent.to_string());
+        current = parent;
+    }
+    path.reverse();
+    path
+}
+
 // ── Handler ───────────────────────────────────────────────
 
 pub struct GraphHandler;
@@ -378,4 +495,45 @@ mod tests {
             }
         }
     }
+
+    // ── traversal ──────────────────────────────────────────
+
+    /// root → a → c → root (cycle), root → b → c, c → d
+    async fn cyclic_graph(storage: &InMemoryStorage) -> GraphTraversal {
+        let handler = GraphHandler;
+        for (source_id, target_id) in [("root", "a"), ("root", "b"), ("a", "c"), ("b", "c"), ("c", "root"), ("c", "d")] {
+            handler
+                .add_edge(AddEdgeInput { source_id: source_id.into(), target_id: target_id.into() }, storage)
+                .await
+                .unwrap();
+        }
+        GraphTraversal::load(storage).await.unwrap()
+    }
+
+    fn strs(paths: &[Vec<String>]) -> Vec<String> {
+        paths.iter().map(|path| path.join(">")).collect()
+    }
+
+    #[tokio::test]
+    async fn traversals_terminate_on_cycles() {
+        let storage = InMemoryStorage::new();
+        let graph = cyclic_graph(&storage).await;
+
+        assert_eq!(strs(&graph.bfs("root", 1)), vec!["root>a", "root>b"]);
+        assert_eq!(strs(&graph.bfs("root", 10)), vec!["root>a", "root>b", "root>a>c", "root>a>c>d"]);
+        assert_eq!(strs(&graph.dfs("root")), vec!["root>a", "root>a>c", "root>a>c>d", "root>b"]);
+        assert_eq!(graph.descendants("c"), vec!["d", "root", "a", "b"]);
+        assert!(graph.descendants("d").is_empty());
+    }
+
+    #[tokio::test]
+    async fn shortest_path_follows_fewest_edges() {
+        let storage = InMemoryStorage::new();
+        let graph = cyclic_graph(&storage).await;
+
+        assert_eq!(graph.shortest_path("root", "d").unwrap(), vec!["root", "a", "c", "d"]);
+        assert_eq!(graph.shortest_path("c", "b").unwrap(), vec!["c", "root", "b"]);
+        assert_eq!(graph.shortest_path("a", "a").unwrap(), vec!["a"]);
+        assert_eq!(graph.shortest_path("d", "root"), None);
+    }
 }
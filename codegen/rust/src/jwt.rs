@@ -1,6 +1,7 @@
 // JWT Concept Implementation (Rust)
 //
-// Mirrors the TypeScript jwt.impl.ts — generate and verify actions.
+// Mirrors the TypeScript jwt.impl.ts — generate and verify actions — and adds
+// refresh-token issuance with rotation and reuse detection.
 // Simplified JWT using HMAC-SHA256 signatures with base64url encoding.
 
 use crate::storage::{ConceptStorage, StorageResult};
@@ -9,11 +10,14 @@ use base64::Engine;
 use hmac::{Hmac, Mac};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
-use sha2::Sha256;
+use sha2::{Digest, Sha256};
 use std::sync::LazyLock;
 
 type HmacSha256 = Hmac<Sha256>;
 
+/// Lifetime of a refresh token.
+const REFRESH_TOKEN_TTL_SECS: i64 = 30 * 24 * 60 * 60;
+
 /// Static secret key for HMAC signing (generated once at startup).
 static JWT_SECRET: LazyLock<[u8; 32]> = LazyLock::new(|| {
     use rand::RngCore;
@@ -50,6 +54,49 @@ pub enum JwtVerifyOutput {
     Error { message: String },
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct JwtIssueInput {
+    pub user: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "variant")]
+pub enum JwtIssueOutput {
+    #[serde(rename = "ok")]
+    Ok { access_token: String, refresh_token: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct JwtValidateRefreshInput {
+    pub refresh_token: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "variant")]
+pub enum JwtValidateRefreshOutput {
+    #[serde(rename = "ok")]
+    Ok { user: String },
+    #[serde(rename = "error")]
+    Error { message: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct JwtRefreshInput {
+    pub refresh_token: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "variant")]
+pub enum JwtRefreshOutput {
+    #[serde(rename = "ok")]
+    Ok { access_token: String, refresh_token: String },
+    #[serde(rename = "error")]
+    Error { message: String },
+    /// A rotated token was presented again; its whole family is now revoked.
+    #[serde(rename = "revoked")]
+    Revoked { message: String },
+}
+
 // ── Internal helpers ───────────────────────────────────────
 
 fn sign_token(payload: &serde_json::Value) -> String {
@@ -92,6 +139,73 @@ fn verify_token(token: &str) -> Option<serde_json::Value> {
     Some(payload)
 }
 
+fn random_token() -> String {
+    use rand::RngCore;
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// Refresh tokens are stored by hash so a storage leak does not expose them.
+fn refresh_token_key(token: &str) -> String {
+    Sha256::digest(token.as_bytes()).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Store `token` as a new active refresh token for `user` in `family`.
+async fn store_refresh_token(
+    token: &str,
+    user: &str,
+    family: &str,
+    storage: &dyn ConceptStorage,
+) -> StorageResult<()> {
+    let key = refresh_token_key(token);
+    let now = chrono::Utc::now().timestamp();
+    storage
+        .put(
+            "refresh_tokens",
+            &key,
+            json!({
+                "key": key,
+                "user": user,
+                "family": family,
+                "status": "active",
+                "version": 0,
+                "issued_at": now,
+                "expires_at": now + REFRESH_TOKEN_TTL_SECS,
+            }),
+        )
+        .await
+}
+
+/// Look up a presented refresh token, returning its record if it is active,
+/// unexpired and its family has not been revoked.
+async fn active_refresh_token(
+    token: &str,
+    storage: &dyn ConceptStorage,
+) -> StorageResult<Result<serde_json::Value, String>> {
+    let Some(record) = storage.get("refresh_tokens", &refresh_token_key(token)).await? else {
+        return Ok(Err("Unknown refresh token".to_string()));
+    };
+    if record["status"] != "active" {
+        return Ok(Err(format!("Refresh token is {}", record["status"].as_str().unwrap_or("invalid"))));
+    }
+    if record["expires_at"].as_i64().unwrap_or(0) <= chrono::Utc::now().timestamp() {
+        return Ok(Err("Refresh token has expired".to_string()));
+    }
+    Ok(Ok(record))
+}
+
+/// Revoke every refresh token in `family`.
+async fn revoke_family(family: &str, storage: &dyn ConceptStorage) -> StorageResult<()> {
+    let tokens = storage.find("refresh_tokens", Some(&json!({ "family": family }))).await?;
+    for mut record in tokens {
+        let key = record["key"].as_str().unwrap_or_default().to_string();
+        record["status"] = json!("revoked");
+        storage.put("refresh_tokens", &key, record).await?;
+    }
+    Ok(())
+}
+
 // ── Handler ────────────────────────────────────────────────
 
 pub struct JwtHandler;
@@ -117,6 +231,81 @@ impl JwtHandler {
         Ok(JwtGenerateOutput::Ok { token })
     }
 
+    /// Issue an access token together with a refresh token that starts a new
+    /// rotation family.
+    pub async fn issue(
+        &self,
+        input: JwtIssueInput,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<JwtIssueOutput> {
+        let JwtGenerateOutput::Ok { token: access_token } = self
+            .generate(JwtGenerateInput { user: input.user.clone() }, storage)
+            .await?;
+        let family = random_token();
+        let refresh_token = random_token();
+        store_refresh_token(&refresh_token, &input.user, &family, storage).await?;
+        Ok(JwtIssueOutput::Ok { access_token, refresh_token })
+    }
+
+    pub async fn validate_refresh(
+        &self,
+        input: JwtValidateRefreshInput,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<JwtValidateRefreshOutput> {
+        Ok(match active_refresh_token(&input.refresh_token, storage).await? {
+            Ok(record) => JwtValidateRefreshOutput::Ok {
+                user: record["user"].as_str().unwrap_or_default().to_string(),
+            },
+            Err(message) => JwtValidateRefreshOutput::Error { message },
+        })
+    }
+
+    /// Exchange a refresh token for a new access token and a new refresh token
+    /// in the same family; the presented token is marked rotated. Presenting a
+    /// rotated token again means it was stolen, so the family is revoked.
+    /// Rotation is a compare-and-swap on the record's version, so of two
+    /// concurrent refreshes with the same token only one succeeds; the other
+    /// counts as reuse.
+    pub async fn refresh(
+        &self,
+        input: JwtRefreshInput,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<JwtRefreshOutput> {
+        let key = refresh_token_key(&input.refresh_token);
+        if let Some(record) = storage.get("refresh_tokens", &key).await? {
+            if record["status"] == "rotated" {
+                revoke_family(record["family"].as_str().unwrap_or_default(), storage).await?;
+                return Ok(JwtRefreshOutput::Revoked {
+                    message: "Refresh token reuse detected; all tokens in this session were revoked".to_string(),
+                });
+            }
+        }
+
+        let record = match active_refresh_token(&input.refresh_token, storage).await? {
+            Ok(record) => record,
+            Err(message) => return Ok(JwtRefreshOutput::Error { message }),
+        };
+        let user = record["user"].as_str().unwrap_or_default().to_string();
+        let family = record["family"].as_str().unwrap_or_default().to_string();
+
+        let refresh_token = random_token();
+        let mut rotated = record.clone();
+        rotated["status"] = json!("rotated");
+        rotated["replaced_by"] = json!(refresh_token_key(&refresh_token));
+        rotated["version"] = json!(record["version"].as_u64().unwrap_or(0) + 1);
+        if !storage.compare_and_swap("refresh_tokens", &key, &record, rotated).await? {
+            revoke_family(&family, storage).await?;
+            return Ok(JwtRefreshOutput::Revoked {
+                message: "Refresh token was used concurrently; all tokens in this session were revoked".to_string(),
+            });
+        }
+        store_refresh_token(&refresh_token, &user, &family, storage).await?;
+
+        let JwtGenerateOutput::Ok { token: access_token } =
+            self.generate(JwtGenerateInput { user }, storage).await?;
+        Ok(JwtRefreshOutput::Ok { access_token, refresh_token })
+    }
+
     pub async fn verify(
         &self,
         input: JwtVerifyInput,
@@ -228,4 +417,132 @@ mod tests {
 
         assert!(matches!(result, JwtVerifyOutput::Error { .. }));
     }
+
+    async fn issue_for(handler: &JwtHandler, user: &str, storage: &InMemoryStorage) -> String {
+        match handler.issue(JwtIssueInput { user: user.into() }, storage).await.unwrap() {
+            JwtIssueOutput::Ok { refresh_token, .. } => refresh_token,
+        }
+    }
+
+    #[tokio::test]
+    async fn refresh_rotates_token() {
+        let storage = InMemoryStorage::new();
+        let handler = JwtHandler;
+        let first = issue_for(&handler, "alice", &storage).await;
+
+        let (access_token, second) = match handler
+            .refresh(JwtRefreshInput { refresh_token: first.clone() }, &storage)
+            .await
+            .unwrap()
+        {
+            JwtRefreshOutput::Ok { access_token, refresh_token } => (access_token, refresh_token),
+            other => panic!("expected rotation, got {:?}", other),
+        };
+        assert_ne!(first, second);
+
+        let verified = handler.verify(JwtVerifyInput { token: access_token }, &storage).await.unwrap();
+        assert!(matches!(verified, JwtVerifyOutput::Ok { user } if user == "alice"));
+
+        let old = handler
+            .validate_refresh(JwtValidateRefreshInput { refresh_token: first }, &storage)
+            .await
+            .unwrap();
+        assert!(matches!(old, JwtValidateRefreshOutput::Error { .. }));
+        let new = handler
+            .validate_refresh(JwtValidateRefreshInput { refresh_token: second }, &storage)
+            .await
+            .unwrap();
+        assert!(matches!(new, JwtValidateRefreshOutput::Ok { user } if user == "alice"));
+    }
+
+    #[tokio::test]
+    async fn refresh_token_reuse_revokes_family() {
+        let storage = InMemoryStorage::new();
+        let handler = JwtHandler;
+        let stolen = issue_for(&handler, "alice", &storage).await;
+        let other_session = issue_for(&handler, "alice", &storage).await;
+
+        let legit = match handler
+            .refresh(JwtRefreshInput { refresh_token: stolen.clone() }, &storage)
+            .await
+            .unwrap()
+        {
+            JwtRefreshOutput::Ok { refresh_token, .. } => refresh_token,
+            other => panic!("expected rotation, got {:?}", other),
+        };
+
+        // The attacker replays the already-rotated token
+        let replay = handler
+            .refresh(JwtRefreshInput { refresh_token: stolen }, &storage)
+            .await
+            .unwrap();
+        assert!(matches!(replay, JwtRefreshOutput::Revoked { .. }));
+
+        // The legitimate successor is revoked with the family; other sessions are not
+        let successor = handler
+            .refresh(JwtRefreshInput { refresh_token: legit }, &storage)
+            .await
+            .unwrap();
+        assert!(matches!(successor, JwtRefreshOutput::Error { message } if message.contains("revoked")));
+        let unrelated = handler
+            .validate_refresh(JwtValidateRefreshInput { refresh_token: other_session }, &storage)
+            .await
+            .unwrap();
+        assert!(matches!(unrelated, JwtValidateRefreshOutput::Ok { .. }));
+    }
+
+    /// Storage where another refresh rotates the token just before ours
+    /// tries to.
+    struct LosesRotationRace(InMemoryStorage);
+
+    #[async_trait::async_trait]
+    impl ConceptStorage for LosesRotationRace {
+        async fn put(&self, relation: &str, key: &str, value: serde_json::Value) -> StorageResult<()> {
+            self.0.put(relation, key, value).await
+        }
+        async fn get(&self, relation: &str, key: &str) -> StorageResult<Option<serde_json::Value>> {
+            self.0.get(relation, key).await
+        }
+        async fn find(
+            &self,
+            relation: &str,
+            criteria: Option<&serde_json::Value>,
+        ) -> StorageResult<Vec<serde_json::Value>> {
+            self.0.find(relation, criteria).await
+        }
+        async fn del(&self, relation: &str, key: &str) -> StorageResult<()> {
+            self.0.del(relation, key).await
+        }
+        async fn del_many(&self, relation: &str, criteria: &serde_json::Value) -> StorageResult<u64> {
+            self.0.del_many(relation, criteria).await
+        }
+        async fn compare_and_swap(
+            &self,
+            relation: &str,
+            key: &str,
+            expected: &serde_json::Value,
+            value: serde_json::Value,
+        ) -> StorageResult<bool> {
+            let mut winner = expected.clone();
+            winner["status"] = json!("rotated");
+            winner["version"] = json!(expected["version"].as_u64().unwrap_or(0) + 1);
+            assert!(self.0.compare_and_swap(relation, key, expected, winner).await?);
+            self.0.compare_and_swap(relation, key, expected, value).await
+        }
+    }
+
+    #[tokio::test]
+    async fn concurrent_refresh_rotates_once_and_revokes_the_loser() {
+        let storage = LosesRotationRace(InMemoryStorage::new());
+        let handler = JwtHandler;
+        let token = match handler.issue(JwtIssueInput { user: "alice".into() }, &storage).await.unwrap() {
+            JwtIssueOutput::Ok { refresh_token, .. } => refresh_token,
+        };
+
+        let result = handler.refresh(JwtRefreshInput { refresh_token: token }, &storage).await.unwrap();
+        assert!(matches!(result, JwtRefreshOutput::Revoked { .. }));
+        let records = storage.find("refresh_tokens", None).await.unwrap();
+        assert_eq!(records.len(), 1, "the losing refresh stores no successor");
+        assert_eq!(records[0]["status"], "revoked");
+    }
 }
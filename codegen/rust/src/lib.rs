@@ -5,6 +5,7 @@
 // ============================================================
 
 pub mod storage;
+pub mod escape;
 
 // Governance suite
 pub mod governance;
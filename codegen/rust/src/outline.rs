@@ -2,10 +2,16 @@
 //
 // Hierarchical outline operations — indent, outdent, move, reparent,
 // collapse, expand, and zoom into subtrees.
+//
+// Siblings are ordered by a dense `position`; structural moves renumber
+// both the old and new sibling lists and refresh `depth` across the moved
+// subtree. A node whose parent is not itself an outline node is a root.
+
+use std::collections::{BTreeMap, HashSet};
 
 use crate::storage::{ConceptStorage, StorageResult};
 use serde::{Deserialize, Serialize};
-use serde_json::json;
+use serde_json::{json, Value};
 
 // --- Indent ---
 
@@ -87,6 +93,26 @@ pub enum ReparentOutput {
     Ok { node_id: String },
 }
 
+// --- MoveNode ---
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MoveNodeInput {
+    pub node_id: String,
+    pub new_parent_id: String,
+    pub position: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "variant")]
+pub enum MoveNodeOutput {
+    #[serde(rename = "ok")]
+    Ok { node_id: String },
+    #[serde(rename = "notfound")]
+    NotFound { message: String },
+    #[serde(rename = "cycle")]
+    Cycle { message: String },
+}
+
 // --- Collapse ---
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -135,80 +161,207 @@ pub enum ZoomOutput {
     NotFound { message: String },
 }
 
+// --- Tree helpers ---
+
+fn position_of(node: &Value) -> u64 {
+    node["position"].as_u64().unwrap_or(0)
+}
+
+fn parent_of(node: &Value) -> &str {
+    node["parent_id"].as_str().unwrap_or("")
+}
+
+/// Children of `parent_id` in sibling order.
+async fn children_of(parent_id: &str, storage: &dyn ConceptStorage) -> StorageResult<Vec<Value>> {
+    let mut children = storage
+        .find("outline_node", Some(&json!({ "parent_id": parent_id })))
+        .await?;
+    children.sort_by(|a, b| {
+        position_of(a)
+            .cmp(&position_of(b))
+            .then_with(|| a["node_id"].as_str().cmp(&b["node_id"].as_str()))
+    });
+    Ok(children)
+}
+
+/// Write dense positions to `siblings`, skipping records already in place.
+async fn renumber(siblings: Vec<Value>, storage: &dyn ConceptStorage) -> StorageResult<()> {
+    for (index, mut sibling) in siblings.into_iter().enumerate() {
+        if sibling["position"].as_u64() != Some(index as u64) {
+            let id = sibling["node_id"].as_str().unwrap_or("").to_string();
+            sibling["position"] = json!(index);
+            storage.put("outline_node", &id, sibling).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Whether `node_id` is `ancestor_id` or lies beneath it.
+async fn is_within(
+    node_id: &str,
+    ancestor_id: &str,
+    storage: &dyn ConceptStorage,
+) -> StorageResult<bool> {
+    let mut seen = HashSet::new();
+    let mut current = node_id.to_string();
+    while seen.insert(current.clone()) {
+        if current == ancestor_id {
+            return Ok(true);
+        }
+        match storage.get("outline_node", &current).await? {
+            Some(node) => current = parent_of(&node).to_string(),
+            None => break,
+        }
+    }
+    Ok(false)
+}
+
+/// Place `node` at `position` among the children of `new_parent_id`,
+/// closing the gap it leaves and updating depths below it.
+async fn relocate(
+    mut node: Value,
+    new_parent_id: &str,
+    position: usize,
+    storage: &dyn ConceptStorage,
+) -> StorageResult<()> {
+    let node_id = node["node_id"].as_str().unwrap_or("").to_string();
+    let old_parent_id = parent_of(&node).to_string();
+
+    if old_parent_id != new_parent_id {
+        let remaining: Vec<Value> = children_of(&old_parent_id, storage)
+            .await?
+            .into_iter()
+            .filter(|sibling| sibling["node_id"] != node_id.as_str())
+            .collect();
+        renumber(remaining, storage).await?;
+    }
+
+    let depth = match storage.get("outline_node", new_parent_id).await? {
+        Some(parent) => parent["depth"].as_u64().unwrap_or(0) + 1,
+        None => 0,
+    };
+    node["parent_id"] = json!(new_parent_id);
+    node["depth"] = json!(depth);
+    node["updated_at"] = json!(chrono::Utc::now().to_rfc3339());
+
+    let mut siblings: Vec<Value> = children_of(new_parent_id, storage)
+        .await?
+        .into_iter()
+        .filter(|sibling| sibling["node_id"] != node_id.as_str())
+        .collect();
+    let index = position.min(siblings.len());
+    node["position"] = json!(index);
+    storage.put("outline_node", &node_id, node.clone()).await?;
+    siblings.insert(index, node);
+    renumber(siblings, storage).await?;
+
+    // Refresh depths across the moved subtree
+    let mut pending = vec![(node_id, depth)];
+    while let Some((parent_id, parent_depth)) = pending.pop() {
+        for mut child in children_of(&parent_id, storage).await? {
+            let child_id = child["node_id"].as_str().unwrap_or("").to_string();
+            if child["depth"].as_u64() != Some(parent_depth + 1) {
+                child["depth"] = json!(parent_depth + 1);
+                storage.put("outline_node", &child_id, child).await?;
+            }
+            pending.push((child_id, parent_depth + 1));
+        }
+    }
+    Ok(())
+}
+
 pub struct OutlineHandler;
 
 impl OutlineHandler {
+    /// Make the node the last child of its previous sibling. The first child
+    /// of a parent has nowhere to go and stays put.
     pub async fn indent(
         &self,
         input: IndentInput,
         storage: &dyn ConceptStorage,
     ) -> StorageResult<IndentOutput> {
         let existing = storage.get("outline_node", &input.node_id).await?;
-        match existing {
-            None => Ok(IndentOutput::NotFound {
+        let Some(node) = existing else {
+            return Ok(IndentOutput::NotFound {
                 message: format!("outline node '{}' not found", input.node_id),
-            }),
-            Some(mut node) => {
-                let current_depth = node["depth"].as_u64().unwrap_or(0);
-                node["depth"] = json!(current_depth + 1);
-                // Make child of previous sibling by increasing depth
-                if let Some(parent_id) = node.get("parent_id").and_then(|v| v.as_str()) {
-                    // Find siblings under same parent to get the previous sibling
-                    let siblings = storage
-                        .find("outline_node", Some(&json!({ "parent_id": parent_id })))
-                        .await?;
-                    // Use first sibling found as new parent if available
-                    for sibling in &siblings {
-                        if let Some(sid) = sibling.get("node_id").and_then(|v| v.as_str()) {
-                            if sid != input.node_id {
-                                node["parent_id"] = json!(sid);
-                                break;
-                            }
-                        }
-                    }
-                }
-                node["updated_at"] = json!(chrono::Utc::now().to_rfc3339());
-                storage.put("outline_node", &input.node_id, node).await?;
-                Ok(IndentOutput::Ok {
-                    node_id: input.node_id,
-                })
-            }
+            });
+        };
+
+        let siblings = children_of(parent_of(&node), storage).await?;
+        let index = siblings
+            .iter()
+            .position(|sibling| sibling["node_id"] == input.node_id.as_str())
+            .unwrap_or(0);
+        if index > 0 {
+            let new_parent_id = siblings[index - 1]["node_id"]
+                .as_str()
+                .unwrap_or("")
+                .to_string();
+            let position = children_of(&new_parent_id, storage).await?.len();
+            relocate(node, &new_parent_id, position, storage).await?;
         }
+        Ok(IndentOutput::Ok {
+            node_id: input.node_id,
+        })
     }
 
+    /// Move the node out of its parent to sit directly after it. Root
+    /// nodes stay put.
     pub async fn outdent(
         &self,
         input: OutdentInput,
         storage: &dyn ConceptStorage,
     ) -> StorageResult<OutdentOutput> {
         let existing = storage.get("outline_node", &input.node_id).await?;
-        match existing {
-            None => Ok(OutdentOutput::NotFound {
+        let Some(node) = existing else {
+            return Ok(OutdentOutput::NotFound {
                 message: format!("outline node '{}' not found", input.node_id),
-            }),
-            Some(mut node) => {
-                let current_depth = node["depth"].as_u64().unwrap_or(0);
-                if current_depth > 0 {
-                    node["depth"] = json!(current_depth - 1);
-                }
-                // Move to parent's parent
-                if let Some(parent_id) = node.get("parent_id").and_then(|v| v.as_str()).map(|s| s.to_string()) {
-                    let parent = storage.get("outline_node", &parent_id).await?;
-                    if let Some(parent_node) = parent {
-                        let grandparent_id = parent_node
-                            .get("parent_id")
-                            .and_then(|v| v.as_str())
-                            .unwrap_or("");
-                        node["parent_id"] = json!(grandparent_id);
-                    }
-                }
-                node["updated_at"] = json!(chrono::Utc::now().to_rfc3339());
-                storage.put("outline_node", &input.node_id, node).await?;
-                Ok(OutdentOutput::Ok {
-                    node_id: input.node_id,
-                })
-            }
+            });
+        };
+
+        if let Some(parent) = storage.get("outline_node", parent_of(&node)).await? {
+            let position = position_of(&parent) as usize + 1;
+            relocate(node, parent_of(&parent), position, storage).await?;
         }
+        Ok(OutdentOutput::Ok {
+            node_id: input.node_id,
+        })
+    }
+
+    /// Move a node under `new_parent_id` at `position` (clamped to the end),
+    /// refusing moves into its own subtree. The new parent must be an outline
+    /// node or a root container that already holds nodes.
+    pub async fn move_node(
+        &self,
+        input: MoveNodeInput,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<MoveNodeOutput> {
+        let existing = storage.get("outline_node", &input.node_id).await?;
+        let Some(node) = existing else {
+            return Ok(MoveNodeOutput::NotFound {
+                message: format!("outline node '{}' not found", input.node_id),
+            });
+        };
+        let parent_exists = storage.get("outline_node", &input.new_parent_id).await?.is_some()
+            || !children_of(&input.new_parent_id, storage).await?.is_empty();
+        if !parent_exists {
+            return Ok(MoveNodeOutput::NotFound {
+                message: format!("outline parent '{}' not found", input.new_parent_id),
+            });
+        }
+        if is_within(&input.new_parent_id, &input.node_id, storage).await? {
+            return Ok(MoveNodeOutput::Cycle {
+                message: format!(
+                    "cannot move '{}' under '{}': it is in the node's own subtree",
+                    input.node_id, input.new_parent_id
+                ),
+            });
+        }
+
+        relocate(node, &input.new_parent_id, input.position as usize, storage).await?;
+        Ok(MoveNodeOutput::Ok {
+            node_id: input.node_id,
+        })
     }
 
     pub async fn move_up(
@@ -349,6 +502,47 @@ impl OutlineHandler {
             }
         }
     }
+
+    /// Node ids in document order, leaving out everything beneath a
+    /// collapsed node.
+    pub async fn visible_nodes(&self, storage: &dyn ConceptStorage) -> StorageResult<Vec<String>> {
+        let nodes = storage.find("outline_node", None).await?;
+        let ids: HashSet<&str> = nodes.iter().filter_map(|n| n["node_id"].as_str()).collect();
+
+        // Roots first, then children, each list in sibling order
+        let mut children: BTreeMap<&str, Vec<&Value>> = BTreeMap::new();
+        let mut roots: Vec<&Value> = Vec::new();
+        for node in &nodes {
+            if ids.contains(parent_of(node)) {
+                children.entry(parent_of(node)).or_default().push(node);
+            } else {
+                roots.push(node);
+            }
+        }
+        let order = |list: &mut Vec<&Value>| {
+            list.sort_by(|a, b| {
+                position_of(a)
+                    .cmp(&position_of(b))
+                    .then_with(|| a["node_id"].as_str().cmp(&b["node_id"].as_str()))
+            })
+        };
+        order(&mut roots);
+        children.values_mut().for_each(order);
+
+        let mut visible = Vec::new();
+        let mut stack: Vec<&Value> = roots.into_iter().rev().collect();
+        while let Some(node) = stack.pop() {
+            let id = node["node_id"].as_str().unwrap_or("");
+            visible.push(id.to_string());
+            if node["collapsed"].as_bool().unwrap_or(false) {
+                continue;
+            }
+            if let Some(kids) = children.get(id) {
+                stack.extend(kids.iter().rev());
+            }
+        }
+        Ok(visible)
+    }
 }
 
 #[cfg(test)]
@@ -552,4 +746,144 @@ mod tests {
             .unwrap();
         assert!(matches!(result, ZoomOutput::NotFound { .. }));
     }
+
+    async fn child_ids(storage: &InMemoryStorage, parent_id: &str) -> Vec<String> {
+        children_of(parent_id, storage)
+            .await
+            .unwrap()
+            .iter()
+            .map(|n| n["node_id"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn indent_and_outdent_preserve_sibling_order() {
+        let storage = InMemoryStorage::new();
+        let handler = OutlineHandler;
+        for (position, id) in ["a", "b", "c", "d"].iter().enumerate() {
+            seed_node(&storage, id, "root", 0, position as u64).await;
+        }
+
+        // b then c join a's children, in order
+        handler
+            .indent(IndentInput { node_id: "b".into() }, &storage)
+            .await
+            .unwrap();
+        handler
+            .indent(IndentInput { node_id: "c".into() }, &storage)
+            .await
+            .unwrap();
+        assert_eq!(child_ids(&storage, "root").await, vec!["a", "d"]);
+        assert_eq!(child_ids(&storage, "a").await, vec!["b", "c"]);
+        let c = storage.get("outline_node", "c").await.unwrap().unwrap();
+        assert_eq!((c["depth"].as_u64(), c["position"].as_u64()), (Some(1), Some(1)));
+
+        // Outdenting b places it right after its old parent
+        handler
+            .outdent(OutdentInput { node_id: "b".into() }, &storage)
+            .await
+            .unwrap();
+        assert_eq!(child_ids(&storage, "root").await, vec!["a", "b", "d"]);
+        assert_eq!(child_ids(&storage, "a").await, vec!["c"]);
+        let c = storage.get("outline_node", "c").await.unwrap().unwrap();
+        assert_eq!(c["position"].as_u64(), Some(0));
+
+        // The first child has no previous sibling to indent under
+        handler
+            .indent(IndentInput { node_id: "a".into() }, &storage)
+            .await
+            .unwrap();
+        assert_eq!(child_ids(&storage, "root").await, vec!["a", "b", "d"]);
+    }
+
+    #[tokio::test]
+    async fn move_node_rejects_cycles_and_updates_depths() {
+        let storage = InMemoryStorage::new();
+        let handler = OutlineHandler;
+        seed_node(&storage, "a", "root", 0, 0).await;
+        seed_node(&storage, "a1", "a", 1, 0).await;
+        seed_node(&storage, "a1x", "a1", 2, 0).await;
+        seed_node(&storage, "b", "root", 0, 1).await;
+
+        let result = handler
+            .move_node(
+                MoveNodeInput {
+                    node_id: "a".into(),
+                    new_parent_id: "a1x".into(),
+                    position: 0,
+                },
+                &storage,
+            )
+            .await
+            .unwrap();
+        assert!(matches!(result, MoveNodeOutput::Cycle { .. }));
+
+        handler
+            .move_node(
+                MoveNodeInput {
+                    node_id: "a1".into(),
+                    new_parent_id: "b".into(),
+                    position: 9,
+                },
+                &storage,
+            )
+            .await
+            .unwrap();
+        assert_eq!(child_ids(&storage, "b").await, vec!["a1"]);
+        assert!(child_ids(&storage, "a").await.is_empty());
+        let a1x = storage.get("outline_node", "a1x").await.unwrap().unwrap();
+        assert_eq!(a1x["depth"].as_u64(), Some(2));
+    }
+
+    #[tokio::test]
+    async fn move_node_requires_an_existing_parent() {
+        let storage = InMemoryStorage::new();
+        let handler = OutlineHandler;
+        seed_node(&storage, "a", "root", 0, 0).await;
+        seed_node(&storage, "a1", "a", 1, 0).await;
+
+        let move_to = |new_parent_id: &str| MoveNodeInput {
+            node_id: "a1".into(),
+            new_parent_id: new_parent_id.into(),
+            position: 0,
+        };
+        let result = handler.move_node(move_to("ghost"), &storage).await.unwrap();
+        assert!(matches!(result, MoveNodeOutput::NotFound { .. }));
+        assert_eq!(child_ids(&storage, "a").await, vec!["a1"]);
+
+        let result = handler.move_node(move_to("root"), &storage).await.unwrap();
+        assert!(matches!(result, MoveNodeOutput::Ok { .. }));
+        assert_eq!(child_ids(&storage, "root").await, vec!["a1", "a"]);
+    }
+
+    #[tokio::test]
+    async fn visible_nodes_skips_collapsed_subtrees() {
+        let storage = InMemoryStorage::new();
+        let handler = OutlineHandler;
+        seed_node(&storage, "a", "root", 0, 0).await;
+        seed_node(&storage, "a2", "a", 1, 1).await;
+        seed_node(&storage, "a1", "a", 1, 0).await;
+        seed_node(&storage, "a1x", "a1", 2, 0).await;
+        seed_node(&storage, "b", "root", 0, 1).await;
+
+        assert_eq!(
+            handler.visible_nodes(&storage).await.unwrap(),
+            vec!["a", "a1", "a1x", "a2", "b"]
+        );
+
+        handler
+            .collapse(CollapseInput { node_id: "a1".into() }, &storage)
+            .await
+            .unwrap();
+        assert_eq!(
+            handler.visible_nodes(&storage).await.unwrap(),
+            vec!["a", "a1", "a2", "b"]
+        );
+
+        handler
+            .collapse(CollapseInput { node_id: "a".into() }, &storage)
+            .await
+            .unwrap();
+        assert_eq!(handler.visible_nodes(&storage).await.unwrap(), vec!["a", "b"]);
+    }
 }
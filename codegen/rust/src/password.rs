@@ -1,9 +1,15 @@
 // Password Concept Implementation (Rust)
 //
 // Mirrors the TypeScript password.impl.ts — set, check, validate actions.
-// Uses SHA-256 for hashing with random salt.
+// Hashes with argon2id or bcrypt as the stored PasswordPolicy selects; legacy
+// salted SHA-256 credentials still verify and are rehashed on the next login.
+// New passwords must satisfy the policy and can optionally be checked against
+// known breaches.
 
 use crate::storage::{ConceptStorage, StorageResult};
+use argon2::password_hash::{PasswordHash, SaltString};
+use argon2::{Argon2, Params, PasswordHasher as _, PasswordVerifier as _};
+use async_trait::async_trait;
 use base64::engine::general_purpose::STANDARD as BASE64;
 use base64::Engine;
 use rand::RngCore;
@@ -25,7 +31,11 @@ pub enum PasswordSetOutput {
     #[serde(rename = "ok")]
     Ok { user: String },
     #[serde(rename = "invalid")]
-    Invalid { message: String },
+    Invalid {
+        message: String,
+        #[serde(default)]
+        violations: Vec<PolicyViolation>,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -55,6 +65,326 @@ pub enum PasswordValidateOutput {
     Ok { valid: bool },
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PasswordSetPolicyInput {
+    pub policy: PasswordPolicy,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "variant")]
+pub enum PasswordSetPolicyOutput {
+    #[serde(rename = "ok")]
+    Ok,
+}
+
+// ── Policy ─────────────────────────────────────────────────
+
+/// Complexity rules a new password must satisfy. The default only enforces
+/// the historical 8-character minimum.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct PasswordPolicy {
+    pub min_length: usize,
+    pub max_length: Option<usize>,
+    pub require_lowercase: bool,
+    pub require_uppercase: bool,
+    pub require_digit: bool,
+    pub require_symbol: bool,
+    /// Reject passwords containing the user name (case-insensitive).
+    pub disallow_username: bool,
+    /// Algorithm for new hashes; credentials stored with another algorithm or
+    /// weaker parameters are rehashed on successful login.
+    pub algorithm: HashAlgorithm,
+    pub bcrypt_cost: u32,
+    pub argon2_memory_kib: u32,
+    pub argon2_iterations: u32,
+}
+
+impl Default for PasswordPolicy {
+    fn default() -> Self {
+        Self {
+            min_length: 8,
+            max_length: None,
+            require_lowercase: false,
+            require_uppercase: false,
+            require_digit: false,
+            require_symbol: false,
+            disallow_username: false,
+            algorithm: HashAlgorithm::Argon2id,
+            bcrypt_cost: bcrypt::DEFAULT_COST,
+            // OWASP's minimum recommendation for argon2id
+            argon2_memory_kib: 19 * 1024,
+            argon2_iterations: 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "reason")]
+pub enum PolicyViolation {
+    #[serde(rename = "too_short")]
+    TooShort { min: usize },
+    #[serde(rename = "too_long")]
+    TooLong { max: usize },
+    #[serde(rename = "missing_lowercase")]
+    MissingLowercase,
+    #[serde(rename = "missing_uppercase")]
+    MissingUppercase,
+    #[serde(rename = "missing_digit")]
+    MissingDigit,
+    #[serde(rename = "missing_symbol")]
+    MissingSymbol,
+    #[serde(rename = "contains_username")]
+    ContainsUsername,
+    #[serde(rename = "breached")]
+    Breached { count: u64 },
+}
+
+impl std::fmt::Display for PolicyViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TooShort { min } => write!(f, "Password must be at least {} characters", min),
+            Self::TooLong { max } => write!(f, "Password must be at most {} characters", max),
+            Self::MissingLowercase => write!(f, "Password must contain a lowercase letter"),
+            Self::MissingUppercase => write!(f, "Password must contain an uppercase letter"),
+            Self::MissingDigit => write!(f, "Password must contain a digit"),
+            Self::MissingSymbol => write!(f, "Password must contain a symbol"),
+            Self::ContainsUsername => write!(f, "Password must not contain the user name"),
+            Self::Breached { count } => write!(f, "Password has appeared in {} known data breaches", count),
+        }
+    }
+}
+
+impl PasswordPolicy {
+    /// Every rule `password` breaks, in declaration order.
+    pub fn violations(&self, user: &str, password: &str) -> Vec<PolicyViolation> {
+        let mut violations = Vec::new();
+        let length = password.chars().count();
+        if length < self.min_length {
+            violations.push(PolicyViolation::TooShort { min: self.min_length });
+        }
+        if let Some(max) = self.max_length.filter(|max| length > *max) {
+            violations.push(PolicyViolation::TooLong { max });
+        }
+        let classes = [
+            (self.require_lowercase, char::is_lowercase as fn(char) -> bool, PolicyViolation::MissingLowercase),
+            (self.require_uppercase, char::is_uppercase, PolicyViolation::MissingUppercase),
+            (self.require_digit, |c: char| c.is_ascii_digit(), PolicyViolation::MissingDigit),
+            (self.require_symbol, |c: char| !c.is_alphanumeric() && !c.is_whitespace(), PolicyViolation::MissingSymbol),
+        ];
+        for (required, matches, violation) in classes {
+            if required && !password.chars().any(matches) {
+                violations.push(violation);
+            }
+        }
+        if self.disallow_username
+            && !user.is_empty()
+            && password.to_lowercase().contains(&user.to_lowercase())
+        {
+            violations.push(PolicyViolation::ContainsUsername);
+        }
+        violations
+    }
+
+    /// The hasher new passwords are hashed with.
+    pub fn hasher(&self) -> Box<dyn PasswordHasher> {
+        match self.algorithm {
+            HashAlgorithm::Argon2id => Box::new(Argon2idHasher {
+                memory_kib: self.argon2_memory_kib,
+                iterations: self.argon2_iterations,
+            }),
+            HashAlgorithm::Bcrypt => Box::new(BcryptHasher { cost: self.bcrypt_cost }),
+        }
+    }
+}
+
+async fn load_policy(storage: &dyn ConceptStorage) -> StorageResult<PasswordPolicy> {
+    Ok(match storage.get("password_policy", "current").await? {
+        Some(record) => serde_json::from_value(record)?,
+        None => PasswordPolicy::default(),
+    })
+}
+
+fn invalid(violations: Vec<PolicyViolation>) -> PasswordSetOutput {
+    PasswordSetOutput::Invalid {
+        message: violations.iter().map(ToString::to_string).collect::<Vec<_>>().join("; "),
+        violations,
+    }
+}
+
+// ── Hashing ────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum HashAlgorithm {
+    #[serde(rename = "argon2id")]
+    Argon2id,
+    #[serde(rename = "bcrypt")]
+    Bcrypt,
+}
+
+impl HashAlgorithm {
+    /// Detect the algorithm from an encoded hash's prefix.
+    pub fn detect(encoded: &str) -> Option<Self> {
+        if encoded.starts_with("$argon2id$") {
+            Some(Self::Argon2id)
+        } else if ["$2a$", "$2b$", "$2y$"].iter().any(|prefix| encoded.starts_with(prefix)) {
+            Some(Self::Bcrypt)
+        } else {
+            None
+        }
+    }
+}
+
+/// A password KDF producing self-describing encoded hashes.
+pub trait PasswordHasher: Send + Sync {
+    fn algorithm(&self) -> HashAlgorithm;
+    fn hash(&self, password: &str) -> StorageResult<String>;
+    /// Whether `password` matches `encoded`. A malformed hash never matches.
+    fn verify(&self, password: &str, encoded: &str) -> bool;
+    /// Whether `encoded` uses weaker parameters than this hasher would.
+    fn is_weaker(&self, encoded: &str) -> bool;
+}
+
+pub struct BcryptHasher {
+    pub cost: u32,
+}
+
+impl PasswordHasher for BcryptHasher {
+    fn algorithm(&self) -> HashAlgorithm {
+        HashAlgorithm::Bcrypt
+    }
+
+    fn hash(&self, password: &str) -> StorageResult<String> {
+        Ok(bcrypt::hash(password, self.cost)?)
+    }
+
+    fn verify(&self, password: &str, encoded: &str) -> bool {
+        bcrypt::verify(password, encoded).unwrap_or(false)
+    }
+
+    fn is_weaker(&self, encoded: &str) -> bool {
+        // $2b$<cost>$<salt+hash>
+        encoded
+            .split('$')
+            .nth(2)
+            .and_then(|cost| cost.parse::<u32>().ok())
+            .is_none_or(|cost| cost < self.cost)
+    }
+}
+
+pub struct Argon2idHasher {
+    pub memory_kib: u32,
+    pub iterations: u32,
+}
+
+impl PasswordHasher for Argon2idHasher {
+    fn algorithm(&self) -> HashAlgorithm {
+        HashAlgorithm::Argon2id
+    }
+
+    fn hash(&self, password: &str) -> StorageResult<String> {
+        let params = Params::new(self.memory_kib, self.iterations, 1, None).map_err(|e| e.to_string())?;
+        let argon2 = Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params);
+
+        let mut salt = [0u8; 16];
+        rand::thread_rng().fill_bytes(&mut salt);
+        let salt = SaltString::encode_b64(&salt).map_err(|e| e.to_string())?;
+
+        let hash = argon2.hash_password(password.as_bytes(), &salt).map_err(|e| e.to_string())?;
+        Ok(hash.to_string())
+    }
+
+    fn verify(&self, password: &str, encoded: &str) -> bool {
+        // Parameters come from the encoded hash itself
+        PasswordHash::new(encoded)
+            .is_ok_and(|parsed| Argon2::default().verify_password(password.as_bytes(), &parsed).is_ok())
+    }
+
+    fn is_weaker(&self, encoded: &str) -> bool {
+        PasswordHash::new(encoded)
+            .ok()
+            .and_then(|parsed| Params::try_from(&parsed).ok())
+            .is_none_or(|params| params.m_cost() < self.memory_kib || params.t_cost() < self.iterations)
+    }
+}
+
+/// Verify against a pre-hasher credential: base64 SHA-256 of password + salt.
+/// Undecodable hash or salt never matches.
+fn legacy_sha256_matches(password: &str, hash_b64: &str, salt_b64: &str) -> bool {
+    let (Ok(salt), Ok(stored_hash)) = (BASE64.decode(salt_b64), BASE64.decode(hash_b64)) else {
+        return false;
+    };
+
+    let mut hasher = Sha256::new();
+    hasher.update(password.as_bytes());
+    hasher.update(&salt);
+    let computed_hash = hasher.finalize();
+
+    computed_hash.as_slice() == stored_hash.as_slice()
+}
+
+async fn store_hash(user: &str, password: &str, policy: &PasswordPolicy, storage: &dyn ConceptStorage) -> StorageResult<()> {
+    let hasher = policy.hasher();
+    let hash = hasher.hash(password)?;
+    storage
+        .put(
+            "password",
+            user,
+            json!({
+                "user": user,
+                "hash": hash,
+                "algorithm": hasher.algorithm(),
+            }),
+        )
+        .await
+}
+
+// ── Breach checking ────────────────────────────────────────
+
+/// Source of Have I Been Pwned style range responses: for a five-character
+/// uppercase SHA-1 prefix, lines of `SUFFIX:COUNT` for every known breached
+/// password hash with that prefix. Only the prefix leaves the process.
+#[async_trait]
+pub trait BreachRangeSource: Send + Sync {
+    async fn range(&self, prefix: &str) -> StorageResult<String>;
+}
+
+/// How many breaches `password` has appeared in, via a k-anonymity range query.
+pub async fn breach_count(source: &dyn BreachRangeSource, password: &str) -> StorageResult<u64> {
+    use sha1::Sha1;
+    let hash: String = Sha1::digest(password.as_bytes()).iter().map(|b| format!("{:02X}", b)).collect();
+    let (prefix, suffix) = hash.split_at(5);
+
+    let body = source.range(prefix).await?;
+    Ok(body
+        .lines()
+        .filter_map(|line| line.trim().split_once(':'))
+        .find(|(candidate, _)| candidate.eq_ignore_ascii_case(suffix))
+        .and_then(|(_, count)| count.trim().parse().ok())
+        .unwrap_or(0))
+}
+
+/// The public Have I Been Pwned range API.
+#[cfg(feature = "breach-check")]
+pub struct HibpRangeClient;
+
+#[cfg(feature = "breach-check")]
+#[async_trait]
+impl BreachRangeSource for HibpRangeClient {
+    async fn range(&self, prefix: &str) -> StorageResult<String> {
+        let url = format!("https://api.pwnedpasswords.com/range/{}", prefix);
+        let body = tokio::task::spawn_blocking(move || -> StorageResult<String> {
+            Ok(ureq::get(&url)
+                .header("Add-Padding", "true")
+                .call()?
+                .body_mut()
+                .read_to_string()?)
+        })
+        .await??;
+        Ok(body)
+    }
+}
+
 // ── Handler ────────────────────────────────────────────────
 
 pub struct PasswordHandler;
@@ -65,38 +395,44 @@ impl PasswordHandler {
         input: PasswordSetInput,
         storage: &dyn ConceptStorage,
     ) -> StorageResult<PasswordSetOutput> {
-        if input.password.len() < 8 {
-            return Ok(PasswordSetOutput::Invalid {
-                message: "Password must be at least 8 characters".to_string(),
-            });
+        let policy = load_policy(storage).await?;
+        let violations = policy.violations(&input.user, &input.password);
+        if !violations.is_empty() {
+            return Ok(invalid(violations));
         }
 
-        // Generate random salt (16 bytes)
-        let mut salt = [0u8; 16];
-        rand::thread_rng().fill_bytes(&mut salt);
+        store_hash(&input.user, &input.password, &policy, storage).await?;
 
-        // SHA-256 hash of password + salt
-        let mut hasher = Sha256::new();
-        hasher.update(input.password.as_bytes());
-        hasher.update(&salt);
-        let hash = hasher.finalize();
+        Ok(PasswordSetOutput::Ok { user: input.user })
+    }
 
-        let hash_b64 = BASE64.encode(hash);
-        let salt_b64 = BASE64.encode(salt);
+    /// `set`, additionally rejecting passwords found in `breaches`.
+    pub async fn set_checked(
+        &self,
+        input: PasswordSetInput,
+        breaches: &dyn BreachRangeSource,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<PasswordSetOutput> {
+        let mut violations = load_policy(storage).await?.violations(&input.user, &input.password);
+        match breach_count(breaches, &input.password).await? {
+            0 => {}
+            count => violations.push(PolicyViolation::Breached { count }),
+        }
+        if !violations.is_empty() {
+            return Ok(invalid(violations));
+        }
+        self.set(input, storage).await
+    }
 
+    pub async fn set_policy(
+        &self,
+        input: PasswordSetPolicyInput,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<PasswordSetPolicyOutput> {
         storage
-            .put(
-                "password",
-                &input.user,
-                json!({
-                    "user": input.user,
-                    "hash": hash_b64,
-                    "salt": salt_b64,
-                }),
-            )
+            .put("password_policy", "current", serde_json::to_value(&input.policy)?)
             .await?;
-
-        Ok(PasswordSetOutput::Ok { user: input.user })
+        Ok(PasswordSetPolicyOutput::Ok)
     }
 
     pub async fn check(
@@ -112,18 +448,26 @@ impl PasswordHandler {
             });
         };
 
-        let stored_hash_b64 = record["hash"].as_str().unwrap_or_default();
-        let stored_salt_b64 = record["salt"].as_str().unwrap_or_default();
-
-        let salt = BASE64.decode(stored_salt_b64)?;
-
-        let mut hasher = Sha256::new();
-        hasher.update(input.password.as_bytes());
-        hasher.update(&salt);
-        let computed_hash = hasher.finalize();
+        let policy = load_policy(storage).await?;
+        let current = policy.hasher();
+        let stored_hash = record["hash"].as_str().unwrap_or_default();
+
+        let (valid, outdated) = match (record["salt"].as_str(), HashAlgorithm::detect(stored_hash)) {
+            (Some(salt), _) => (legacy_sha256_matches(&input.password, stored_hash, salt), true),
+            (None, Some(algorithm)) if algorithm == current.algorithm() => {
+                (current.verify(&input.password, stored_hash), current.is_weaker(stored_hash))
+            }
+            (None, Some(algorithm)) => {
+                let stored_policy = PasswordPolicy { algorithm, ..policy.clone() };
+                (stored_policy.hasher().verify(&input.password, stored_hash), true)
+            }
+            (None, None) => (false, false),
+        };
 
-        let stored_hash = BASE64.decode(stored_hash_b64)?;
-        let valid = computed_hash.as_slice() == stored_hash.as_slice();
+        // Upgrade the stored hash while the plaintext is at hand
+        if valid && outdated {
+            store_hash(&input.user, &input.password, &policy, storage).await?;
+        }
 
         Ok(PasswordCheckOutput::Ok { valid })
     }
@@ -131,10 +475,10 @@ impl PasswordHandler {
     pub async fn validate(
         &self,
         input: PasswordValidateInput,
-        _storage: &dyn ConceptStorage,
+        storage: &dyn ConceptStorage,
     ) -> StorageResult<PasswordValidateOutput> {
         Ok(PasswordValidateOutput::Ok {
-            valid: input.password.len() >= 8,
+            valid: load_policy(storage).await?.violations("", &input.password).is_empty(),
         })
     }
 }
@@ -284,4 +628,162 @@ mod tests {
             .unwrap();
         assert!(matches!(result, PasswordValidateOutput::Ok { valid } if !valid));
     }
+
+    fn strict_policy() -> PasswordPolicy {
+        PasswordPolicy {
+            min_length: 10,
+            max_length: Some(20),
+            require_lowercase: true,
+            require_uppercase: true,
+            require_digit: true,
+            require_symbol: true,
+            disallow_username: true,
+            ..PasswordPolicy::default()
+        }
+    }
+
+    async fn set_violations(storage: &InMemoryStorage, user: &str, password: &str) -> Vec<PolicyViolation> {
+        let result = PasswordHandler
+            .set(PasswordSetInput { user: user.into(), password: password.into() }, storage)
+            .await
+            .unwrap();
+        match result {
+            PasswordSetOutput::Invalid { violations, .. } => violations,
+            PasswordSetOutput::Ok { .. } => vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn policy_rejections() {
+        let storage = InMemoryStorage::new();
+        PasswordHandler
+            .set_policy(PasswordSetPolicyInput { policy: strict_policy() }, &storage)
+            .await
+            .unwrap();
+
+        let cases = [
+            ("Sh0rt!", PolicyViolation::TooShort { min: 10 }),
+            ("Waaaaaaaaaaaaaaaaay-2-long", PolicyViolation::TooLong { max: 20 }),
+            ("NOLOWER-123", PolicyViolation::MissingLowercase),
+            ("noupper-123", PolicyViolation::MissingUppercase),
+            ("No-Digits-Here", PolicyViolation::MissingDigit),
+            ("NoSymbols123", PolicyViolation::MissingSymbol),
+            ("Grace-Hopper-1", PolicyViolation::ContainsUsername),
+        ];
+        for (password, expected) in cases {
+            assert_eq!(set_violations(&storage, "hopper", password).await, vec![expected], "{}", password);
+        }
+        assert!(set_violations(&storage, "hopper", "Correct-Horse-9").await.is_empty());
+
+        let result = PasswordHandler
+            .validate(PasswordValidateInput { password: "longenough".into() }, &storage)
+            .await
+            .unwrap();
+        assert!(matches!(result, PasswordValidateOutput::Ok { valid } if !valid));
+    }
+
+    struct StubBreaches;
+
+    #[async_trait]
+    impl BreachRangeSource for StubBreaches {
+        async fn range(&self, prefix: &str) -> StorageResult<String> {
+            // SHA-1("password123") = CBFDAC6008F9CAB4083784CBD1874F76618D2A97
+            assert_eq!(prefix, "CBFDA");
+            Ok("C6008F9CAB4083784CBD1874F76618D2A97:251682\r\n0018A45C4D1DEF81644B54AB7F969B88D65:1".into())
+        }
+    }
+
+    #[tokio::test]
+    async fn breached_password_is_rejected() {
+        let storage = InMemoryStorage::new();
+        let result = PasswordHandler
+            .set_checked(
+                PasswordSetInput { user: "u1".into(), password: "password123".into() },
+                &StubBreaches,
+                &storage,
+            )
+            .await
+            .unwrap();
+        assert!(matches!(
+            result,
+            PasswordSetOutput::Invalid { violations, .. } if violations == vec![PolicyViolation::Breached { count: 251682 }]
+        ));
+        assert!(storage.get("password", "u1").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn legacy_bcrypt_hash_verifies_and_is_rehashed_on_login() {
+        let storage = InMemoryStorage::new();
+        let legacy = bcrypt::hash("hunter2hunter2", 4).unwrap();
+        storage
+            .put("password", "u1", json!({ "user": "u1", "hash": legacy, "algorithm": "bcrypt" }))
+            .await
+            .unwrap();
+
+        let wrong = PasswordHandler
+            .check(PasswordCheckInput { user: "u1".into(), password: "hunter3hunter3".into() }, &storage)
+            .await
+            .unwrap();
+        assert!(matches!(wrong, PasswordCheckOutput::Ok { valid } if !valid));
+        let unchanged = storage.get("password", "u1").await.unwrap().unwrap();
+        assert_eq!(unchanged["hash"], json!(legacy));
+
+        let result = PasswordHandler
+            .check(PasswordCheckInput { user: "u1".into(), password: "hunter2hunter2".into() }, &storage)
+            .await
+            .unwrap();
+        assert!(matches!(result, PasswordCheckOutput::Ok { valid } if valid));
+
+        let upgraded = storage.get("password", "u1").await.unwrap().unwrap();
+        let hash = upgraded["hash"].as_str().unwrap();
+        assert!(hash.starts_with("$argon2id$"), "{}", hash);
+        assert_eq!(upgraded["algorithm"], json!("argon2id"));
+
+        let again = PasswordHandler
+            .check(PasswordCheckInput { user: "u1".into(), password: "hunter2hunter2".into() }, &storage)
+            .await
+            .unwrap();
+        assert!(matches!(again, PasswordCheckOutput::Ok { valid } if valid));
+    }
+
+    #[tokio::test]
+    async fn weaker_parameters_trigger_rehash() {
+        let storage = InMemoryStorage::new();
+        let policy = PasswordPolicy { algorithm: HashAlgorithm::Bcrypt, bcrypt_cost: 4, ..PasswordPolicy::default() };
+        PasswordHandler.set_policy(PasswordSetPolicyInput { policy }, &storage).await.unwrap();
+        PasswordHandler
+            .set(PasswordSetInput { user: "u1".into(), password: "mysecretpw".into() }, &storage)
+            .await
+            .unwrap();
+        let stored = storage.get("password", "u1").await.unwrap().unwrap();
+        assert!(stored["hash"].as_str().unwrap().starts_with("$2b$04$"));
+
+        let policy = PasswordPolicy { algorithm: HashAlgorithm::Bcrypt, bcrypt_cost: 5, ..PasswordPolicy::default() };
+        PasswordHandler.set_policy(PasswordSetPolicyInput { policy }, &storage).await.unwrap();
+        PasswordHandler
+            .check(PasswordCheckInput { user: "u1".into(), password: "mysecretpw".into() }, &storage)
+            .await
+            .unwrap();
+        let stored = storage.get("password", "u1").await.unwrap().unwrap();
+        assert!(stored["hash"].as_str().unwrap().starts_with("$2b$05$"));
+    }
+
+    #[tokio::test]
+    async fn malformed_stored_hashes_are_invalid_not_errors() {
+        let storage = InMemoryStorage::new();
+        let records = [
+            ("bcrypt", json!({ "user": "bcrypt", "hash": "$2b$04$truncated" })),
+            ("argon2id", json!({ "user": "argon2id", "hash": "$argon2id$v=19$garbage" })),
+            ("legacy", json!({ "user": "legacy", "hash": "not base64!", "salt": "c2FsdA==" })),
+        ];
+        for (user, record) in records {
+            storage.put("password", user, record.clone()).await.unwrap();
+            let result = PasswordHandler
+                .check(PasswordCheckInput { user: user.into(), password: "mysecretpw".into() }, &storage)
+                .await
+                .unwrap();
+            assert!(matches!(result, PasswordCheckOutput::Ok { valid } if !valid), "{}", user);
+            assert_eq!(storage.get("password", user).await.unwrap(), Some(record));
+        }
+    }
 }
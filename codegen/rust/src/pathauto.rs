@@ -1,7 +1,8 @@
 // Pathauto Concept Implementation (Rust)
 //
-// Infrastructure suite — generates URL-safe path aliases from titles,
-// bulk-generates aliases for node types, and cleans strings into slugs.
+// Infrastructure suite — generates URL-safe path aliases from titles or
+// token patterns, bulk-generates aliases for node types, and cleans strings
+// into slugs. Aliases are unique: a taken alias gets a `-1`, `-2`, … suffix.
 
 use crate::storage::{ConceptStorage, StorageResult};
 use serde::{Deserialize, Serialize};
@@ -50,13 +51,161 @@ pub enum PathautoCleanStringOutput {
     Ok { cleaned: String },
 }
 
+// ── GenerateFromPattern ───────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PathautoGenerateFromPatternInput {
+    pub node_id: String,
+    /// e.g. `/[content_type]/[title:slug]`
+    pub pattern: String,
+    /// JSON object the pattern's tokens are read from.
+    pub record: String,
+    /// Longest alias allowed, suffix included; 0 for no limit.
+    pub max_length: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "variant")]
+pub enum PathautoGenerateFromPatternOutput {
+    #[serde(rename = "ok")]
+    Ok { node_id: String, alias: String },
+    #[serde(rename = "invalid")]
+    Invalid { message: String },
+}
+
+// ── Patterns ──────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PatternPart {
+    Text(String),
+    /// A dotted path into the record, with an optional `:modifier`.
+    Token { path: String, modifier: Option<String> },
+}
+
+/// A path pattern such as `/[content_type]/[title:slug]`. Tokens are
+/// replaced with record values; the `slug` modifier cleans a value into a
+/// slug, `lower` lowercases it, and unmodified values are inserted as-is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathPattern {
+    parts: Vec<PatternPart>,
+}
+
+impl PathPattern {
+    pub fn parse(pattern: &str) -> Result<Self, String> {
+        let mut parts = Vec::new();
+        let mut rest = pattern;
+        while let Some(open) = rest.find('[') {
+            if open > 0 {
+                parts.push(PatternPart::Text(rest[..open].to_string()));
+            }
+            let close = rest[open..]
+                .find(']')
+                .ok_or_else(|| format!("unclosed token in pattern '{}'", pattern))?;
+            let token = rest[open + 1..open + close].trim();
+            let (path, modifier) = match token.split_once(':') {
+                Some((path, modifier)) => (path.trim(), Some(modifier.trim().to_string())),
+                None => (token, None),
+            };
+            if let Some(modifier) = modifier.as_deref().filter(|m| !matches!(*m, "slug" | "lower")) {
+                return Err(format!("unknown token modifier '{}'", modifier));
+            }
+            parts.push(PatternPart::Token { path: path.to_string(), modifier });
+            rest = &rest[open + close + 1..];
+        }
+        if !rest.is_empty() {
+            parts.push(PatternPart::Text(rest.to_string()));
+        }
+        Ok(Self { parts })
+    }
+
+    /// Substitute `record`'s values. Missing tokens render empty and the
+    /// resulting empty path segments are dropped.
+    pub fn render(&self, record: &serde_json::Value) -> String {
+        let mut path = String::new();
+        for part in &self.parts {
+            match part {
+                PatternPart::Text(text) => path.push_str(text),
+                PatternPart::Token { path: key, modifier } => {
+                    let value = key.split('.').try_fold(record, |v, k| v.get(k)).map(|v| match v {
+                        serde_json::Value::String(s) => s.clone(),
+                        serde_json::Value::Null => String::new(),
+                        other => other.to_string(),
+                    });
+                    let value = value.unwrap_or_default();
+                    match modifier.as_deref() {
+                        Some("slug") => path.push_str(&PathautoHandler::slugify(&value)),
+                        Some("lower") => path.push_str(&value.to_lowercase()),
+                        _ => path.push_str(value.trim()),
+                    }
+                }
+            }
+        }
+        let rooted = matches!(self.parts.first(), Some(PatternPart::Text(text)) if text.starts_with('/'));
+        let leading = if rooted { "/" } else { "" };
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        format!("{}{}", leading, segments.join("/"))
+    }
+}
+
+/// Shorten `alias` to at most `max_length` bytes, cutting at a `-` or `/`
+/// boundary so no slug word is split; a single over-long word is cut hard.
+pub fn truncate_alias(alias: &str, max_length: usize) -> String {
+    if alias.len() <= max_length {
+        return alias.to_string();
+    }
+    let mut end = max_length;
+    while !alias.is_char_boundary(end) {
+        end -= 1;
+    }
+    // Keep whole words when the cut lands inside one
+    let next = alias[end..].chars().next();
+    if !matches!(next, Some('-') | Some('/')) {
+        if let Some(boundary) = alias[..end].rfind(['-', '/']).filter(|b| *b > 0) {
+            end = boundary;
+        }
+    }
+    alias[..end].trim_end_matches(['-', '/']).to_string()
+}
+
+/// `alias`, or the first free `alias-1`, `alias-2`, … when another node
+/// already uses it. With a length limit the base is shortened to leave room
+/// for the suffix.
+pub async fn unique_alias(
+    alias: &str,
+    node_id: &str,
+    max_length: Option<usize>,
+    storage: &dyn ConceptStorage,
+) -> StorageResult<String> {
+    let limit = |text: &str, reserve: usize| match max_length {
+        Some(max) => truncate_alias(text, max.saturating_sub(reserve)),
+        None => text.to_string(),
+    };
+    let base = limit(alias, 0);
+    let taken = |candidate: String| async move {
+        let owners = storage.find("path_alias", Some(&json!({ "alias": candidate }))).await?;
+        StorageResult::Ok(owners.iter().any(|owner| owner["node_id"].as_str() != Some(node_id)))
+    };
+    if !taken(base.clone()).await? {
+        return Ok(base);
+    }
+    let mut n = 1u64;
+    loop {
+        let suffix = format!("-{}", n);
+        let candidate = format!("{}{}", limit(alias, suffix.len()), suffix);
+        if !taken(candidate.clone()).await? {
+            return Ok(candidate);
+        }
+        n += 1;
+    }
+}
+
 // ── Handler ───────────────────────────────────────────────
 
 pub struct PathautoHandler;
 
 impl PathautoHandler {
     /// Generate a URL-safe slug from a title string.
-    fn slugify(title: &str) -> String {
+    pub(crate) fn slugify(title: &str) -> String {
         title
             .to_lowercase()
             .chars()
@@ -80,7 +229,7 @@ impl PathautoHandler {
         input: PathautoGenerateAliasInput,
         storage: &dyn ConceptStorage,
     ) -> StorageResult<PathautoGenerateAliasOutput> {
-        let alias = Self::slugify(&input.title);
+        let alias = unique_alias(&Self::slugify(&input.title), &input.node_id, None, storage).await?;
         let now = chrono::Utc::now().to_rfc3339();
         storage
             .put(
@@ -99,6 +248,45 @@ impl PathautoHandler {
         })
     }
 
+    pub async fn generate_from_pattern(
+        &self,
+        input: PathautoGenerateFromPatternInput,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<PathautoGenerateFromPatternOutput> {
+        let pattern = match PathPattern::parse(&input.pattern) {
+            Ok(pattern) => pattern,
+            Err(message) => return Ok(PathautoGenerateFromPatternOutput::Invalid { message }),
+        };
+        let record: serde_json::Value = match serde_json::from_str(&input.record) {
+            Ok(record) => record,
+            Err(e) => {
+                return Ok(PathautoGenerateFromPatternOutput::Invalid {
+                    message: format!("invalid record: {}", e),
+                })
+            }
+        };
+
+        let max_length = (input.max_length > 0).then_some(input.max_length as usize);
+        let alias = unique_alias(&pattern.render(&record), &input.node_id, max_length, storage).await?;
+        let now = chrono::Utc::now().to_rfc3339();
+        storage
+            .put(
+                "path_alias",
+                &input.node_id,
+                json!({
+                    "node_id": input.node_id,
+                    "alias": alias,
+                    "pattern": input.pattern,
+                    "created_at": now,
+                }),
+            )
+            .await?;
+        Ok(PathautoGenerateFromPatternOutput::Ok {
+            node_id: input.node_id,
+            alias,
+        })
+    }
+
     pub async fn bulk_generate(
         &self,
         input: PathautoBulkGenerateInput,
@@ -247,4 +435,60 @@ mod tests {
             }
         }
     }
+
+    // ── pattern tests ──────────────────────────────────────
+
+    async fn from_pattern(storage: &InMemoryStorage, node_id: &str, record: serde_json::Value, max_length: u64) -> String {
+        let result = PathautoHandler
+            .generate_from_pattern(
+                PathautoGenerateFromPatternInput {
+                    node_id: node_id.into(),
+                    pattern: "/[content_type]/[title:slug]".into(),
+                    record: record.to_string(),
+                    max_length,
+                },
+                storage,
+            )
+            .await
+            .unwrap();
+        match result {
+            PathautoGenerateFromPatternOutput::Ok { alias, .. } => alias,
+            PathautoGenerateFromPatternOutput::Invalid { message } => panic!("{}", message),
+        }
+    }
+
+    #[tokio::test]
+    async fn pattern_substitutes_tokens() {
+        let storage = InMemoryStorage::new();
+        let alias = from_pattern(&storage, "n1", json!({ "content_type": "article", "title": "Hello, World!" }), 0).await;
+        assert_eq!(alias, "/article/hello-world");
+
+        let pattern = PathPattern::parse("[section:lower]/[meta.year]/[title:slug]").unwrap();
+        let record = json!({ "section": "News", "meta": { "year": 2024 }, "title": "Q3 Results" });
+        assert_eq!(pattern.render(&record), "news/2024/q3-results");
+        assert_eq!(pattern.render(&json!({ "title": "Only Title" })), "only-title");
+
+        assert!(PathPattern::parse("/[title").is_err());
+        assert!(PathPattern::parse("/[title:shout]").is_err());
+    }
+
+    #[tokio::test]
+    async fn collision_appends_suffix_within_max_length() {
+        let storage = InMemoryStorage::new();
+        let record = json!({ "content_type": "blog", "title": "Release notes for version two" });
+
+        let first = from_pattern(&storage, "n1", record.clone(), 0).await;
+        let second = from_pattern(&storage, "n2", record.clone(), 0).await;
+        assert_eq!(first, "/blog/release-notes-for-version-two");
+        assert_eq!(second, "/blog/release-notes-for-version-two-1");
+        // Regenerating a node's own alias keeps it
+        assert_eq!(from_pattern(&storage, "n1", record.clone(), 0).await, first);
+
+        // Truncation keeps whole words, also when making room for the suffix
+        assert_eq!(truncate_alias("/blog/release-notes-for-version-two", 24), "/blog/release-notes-for");
+        let short = from_pattern(&storage, "n3", record.clone(), 24).await;
+        let short_dup = from_pattern(&storage, "n4", record, 24).await;
+        assert_eq!(short, "/blog/release-notes-for");
+        assert_eq!(short_dup, "/blog/release-notes-1");
+    }
 }
@@ -2,8 +2,16 @@
 //
 // Infrastructure suite — registers plugin types and plugin definitions,
 // discovers plugins by type, and creates plugin instances.
+//
+// In-process plugins register through a `PluginCatalog` with a manifest
+// declaring their version, capabilities and the host versions they support;
+// plugins built for an incompatible host are rejected at registration.
+
+use std::collections::BTreeMap;
+use std::fmt;
 
 use crate::storage::{ConceptStorage, StorageResult};
+use semver::{Version, VersionReq};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
@@ -74,6 +82,162 @@ pub enum PluginRegistryCreateInstanceOutput {
     NotFound { message: String },
 }
 
+// ── Manifest ──────────────────────────────────────────────
+
+/// The framework version plugins are checked against.
+pub const HOST_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// What a plugin declares about itself when it registers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginManifest {
+    pub id: String,
+    pub version: Version,
+    pub capabilities: Vec<String>,
+    /// Host versions the plugin works with, e.g. `^0.1` or `>=0.1, <0.3`.
+    pub requires_host: VersionReq,
+}
+
+impl PluginManifest {
+    /// Build a manifest from its textual form, as written in a plugin's
+    /// package metadata.
+    pub fn parse(
+        id: &str,
+        version: &str,
+        capabilities: &[&str],
+        requires_host: &str,
+    ) -> Result<Self, semver::Error> {
+        Ok(Self {
+            id: id.to_string(),
+            version: Version::parse(version)?,
+            capabilities: capabilities.iter().map(|c| c.to_string()).collect(),
+            requires_host: VersionReq::parse(requires_host)?,
+        })
+    }
+
+    pub fn has_capability(&self, capability: &str) -> bool {
+        self.capabilities.iter().any(|c| c == capability)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PluginError {
+    IncompatibleHost {
+        plugin_id: String,
+        requires: VersionReq,
+        host: Version,
+    },
+    AlreadyRegistered {
+        plugin_id: String,
+    },
+}
+
+impl fmt::Display for PluginError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PluginError::IncompatibleHost {
+                plugin_id,
+                requires,
+                host,
+            } => write!(
+                f,
+                "plugin '{}' requires host {} but this host is {}",
+                plugin_id, requires, host
+            ),
+            PluginError::AlreadyRegistered { plugin_id } => {
+                write!(f, "plugin '{}' is already registered", plugin_id)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PluginError {}
+
+pub type PluginFactory<P> = Box<dyn Fn(&str) -> P + Send + Sync>;
+
+struct CatalogEntry<P> {
+    manifest: PluginManifest,
+    factory: PluginFactory<P>,
+}
+
+/// In-process plugins by id. `P` is whatever the factories build, typically
+/// a boxed trait object; factories receive the instance config.
+pub struct PluginCatalog<P> {
+    host: Version,
+    plugins: BTreeMap<String, CatalogEntry<P>>,
+}
+
+impl<P> PluginCatalog<P> {
+    /// A catalog for the running framework version.
+    pub fn new() -> Self {
+        Self::with_host_version(
+            Version::parse(HOST_VERSION).expect("crate version is valid semver"),
+        )
+    }
+
+    pub fn with_host_version(host: Version) -> Self {
+        Self {
+            host,
+            plugins: BTreeMap::new(),
+        }
+    }
+
+    pub fn host_version(&self) -> &Version {
+        &self.host
+    }
+
+    /// Register a plugin, rejecting it when its `requires_host` range does
+    /// not match this host or its id is taken.
+    pub fn register<F>(&mut self, manifest: PluginManifest, factory: F) -> Result<(), PluginError>
+    where
+        F: Fn(&str) -> P + Send + Sync + 'static,
+    {
+        if !manifest.requires_host.matches(&self.host) {
+            return Err(PluginError::IncompatibleHost {
+                plugin_id: manifest.id,
+                requires: manifest.requires_host,
+                host: self.host.clone(),
+            });
+        }
+        if self.plugins.contains_key(&manifest.id) {
+            return Err(PluginError::AlreadyRegistered {
+                plugin_id: manifest.id,
+            });
+        }
+        self.plugins.insert(
+            manifest.id.clone(),
+            CatalogEntry {
+                manifest,
+                factory: Box::new(factory),
+            },
+        );
+        Ok(())
+    }
+
+    pub fn manifest(&self, id: &str) -> Option<&PluginManifest> {
+        self.plugins.get(id).map(|entry| &entry.manifest)
+    }
+
+    /// Manifests of every plugin declaring `capability`, ordered by id.
+    pub fn find_by_capability(&self, capability: &str) -> Vec<&PluginManifest> {
+        self.plugins
+            .values()
+            .map(|entry| &entry.manifest)
+            .filter(|manifest| manifest.has_capability(capability))
+            .collect()
+    }
+
+    /// Build an instance of plugin `id` with `config`.
+    pub fn create(&self, id: &str, config: &str) -> Option<P> {
+        self.plugins.get(id).map(|entry| (entry.factory)(config))
+    }
+}
+
+impl<P> Default for PluginCatalog<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 // ── Handler ───────────────────────────────────────────────
 
 pub struct PluginRegistryHandler;
@@ -435,4 +599,61 @@ mod tests {
             PluginRegistryCreateInstanceOutput::NotFound { .. }
         ));
     }
+
+    // ── PluginCatalog tests ────────────────────────────────
+
+    #[test]
+    fn catalog_rejects_plugin_requiring_incompatible_host() {
+        let mut catalog: PluginCatalog<String> =
+            PluginCatalog::with_host_version(Version::new(0, 1, 4));
+
+        let future = PluginManifest::parse("exporter", "2.0.0", &["export"], ">=0.2").unwrap();
+        let err = catalog
+            .register(future, |config| format!("exporter:{}", config))
+            .unwrap_err();
+        assert!(matches!(
+            &err,
+            PluginError::IncompatibleHost { plugin_id, .. } if plugin_id == "exporter"
+        ));
+        assert_eq!(
+            err.to_string(),
+            "plugin 'exporter' requires host >=0.2 but this host is 0.1.4"
+        );
+        assert!(catalog.manifest("exporter").is_none());
+
+        let current = PluginManifest::parse("exporter", "1.3.0", &["export"], "^0.1").unwrap();
+        catalog
+            .register(current.clone(), |config| format!("exporter:{}", config))
+            .unwrap();
+        assert_eq!(
+            catalog.create("exporter", "csv").as_deref(),
+            Some("exporter:csv")
+        );
+        assert!(matches!(
+            catalog.register(current, |_| String::new()),
+            Err(PluginError::AlreadyRegistered { .. })
+        ));
+    }
+
+    #[test]
+    fn catalog_finds_plugins_by_capability() {
+        let mut catalog: PluginCatalog<&'static str> = PluginCatalog::new();
+        let host = format!("={}", HOST_VERSION);
+        for (id, caps) in [
+            ("search", &["index", "query"][..]),
+            ("audit", &["log"][..]),
+            ("fulltext", &["query"][..]),
+        ] {
+            let manifest = PluginManifest::parse(id, "1.0.0", caps, &host).unwrap();
+            catalog.register(manifest, |_| "instance").unwrap();
+        }
+
+        let ids: Vec<&str> = catalog
+            .find_by_capability("query")
+            .iter()
+            .map(|m| m.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["fulltext", "search"]);
+        assert!(catalog.find_by_capability("render").is_empty());
+    }
 }
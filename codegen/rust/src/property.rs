@@ -1,10 +1,17 @@
 // Property Concept Implementation (Rust)
 //
 // Key-value property storage for nodes, with typed property definitions.
+//
+// A key defined with a known `prop_type` gets a `PropertySchema`: `set`
+// coerces values to the declared type (e.g. "42" for a number) and rejects
+// what cannot be coerced. Constraints `multiple` and `max_values` make a
+// property multi-valued with an upper bound.
+
+use std::fmt;
 
 use crate::storage::{ConceptStorage, StorageResult};
 use serde::{Deserialize, Serialize};
-use serde_json::json;
+use serde_json::{json, Value};
 
 // --- Set ---
 
@@ -20,6 +27,12 @@ pub struct SetInput {
 pub enum SetOutput {
     #[serde(rename = "ok")]
     Ok { node_id: String, key: String },
+    #[serde(rename = "invalid")]
+    Invalid {
+        node_id: String,
+        key: String,
+        error: PropertyError,
+    },
 }
 
 // --- Get ---
@@ -93,6 +106,214 @@ pub enum ListAllOutput {
     },
 }
 
+// --- Schema ---
+
+/// Declared property types; the names match the data quality profiler's
+/// field types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldType {
+    Boolean,
+    Integer,
+    Number,
+    Date,
+    DateTime,
+    String,
+    Array,
+    Object,
+}
+
+impl FieldType {
+    pub fn parse(name: &str) -> Option<Self> {
+        Some(match name {
+            "boolean" => Self::Boolean,
+            "integer" => Self::Integer,
+            "number" => Self::Number,
+            "date" => Self::Date,
+            "datetime" => Self::DateTime,
+            "string" => Self::String,
+            "array" => Self::Array,
+            "object" => Self::Object,
+            _ => return None,
+        })
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Boolean => "boolean",
+            Self::Integer => "integer",
+            Self::Number => "number",
+            Self::Date => "date",
+            Self::DateTime => "datetime",
+            Self::String => "string",
+            Self::Array => "array",
+            Self::Object => "object",
+        }
+    }
+
+    /// Convert `value` to this type, or `None` when it cannot be read as one.
+    fn coerce(&self, value: &Value) -> Option<Value> {
+        match (self, value) {
+            (Self::Boolean, Value::Bool(_)) => Some(value.clone()),
+            (Self::Boolean, Value::String(s)) => match s.trim().to_ascii_lowercase().as_str() {
+                "true" => Some(json!(true)),
+                "false" => Some(json!(false)),
+                _ => None,
+            },
+            (Self::Integer, Value::Number(n)) => match n.as_i64() {
+                Some(i) => Some(json!(i)),
+                None => n
+                    .as_f64()
+                    .filter(|f| f.fract() == 0.0 && f.abs() < i64::MAX as f64)
+                    .map(|f| json!(f as i64)),
+            },
+            (Self::Integer, Value::String(s)) => s.trim().parse::<i64>().ok().map(|i| json!(i)),
+            (Self::Number, Value::Number(_)) => Some(value.clone()),
+            (Self::Number, Value::String(s)) => {
+                let s = s.trim();
+                match s.parse::<i64>() {
+                    Ok(i) => Some(json!(i)),
+                    Err(_) => s
+                        .parse::<f64>()
+                        .ok()
+                        .filter(|f| f.is_finite())
+                        .map(|f| json!(f)),
+                }
+            }
+            (Self::Date, Value::String(s)) => {
+                chrono::NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d")
+                    .ok()
+                    .map(|d| json!(d.format("%Y-%m-%d").to_string()))
+            }
+            (Self::DateTime, Value::String(s)) => chrono::DateTime::parse_from_rfc3339(s.trim())
+                .ok()
+                .map(|dt| json!(dt.to_rfc3339())),
+            (Self::String, Value::String(_)) => Some(value.clone()),
+            (Self::String, Value::Number(_) | Value::Bool(_)) => Some(json!(value.to_string())),
+            (Self::Array, Value::Array(_)) | (Self::Object, Value::Object(_)) => {
+                Some(value.clone())
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for FieldType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cardinality {
+    Single,
+    /// A list of values, optionally capped at `max`.
+    Many {
+        max: Option<usize>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PropertyError {
+    TypeMismatch {
+        key: String,
+        expected: FieldType,
+        value: Value,
+    },
+    TooManyValues {
+        key: String,
+        max: usize,
+        got: usize,
+    },
+}
+
+impl fmt::Display for PropertyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PropertyError::TypeMismatch {
+                key,
+                expected,
+                value,
+            } => write!(f, "property '{}' expects {}, got {}", key, expected, value),
+            PropertyError::TooManyValues { key, max, got } => write!(
+                f,
+                "property '{}' takes at most {} values, got {}",
+                key, max, got
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PropertyError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PropertySchema {
+    pub key: String,
+    pub field_type: FieldType,
+    pub cardinality: Cardinality,
+}
+
+impl PropertySchema {
+    /// The schema defined for `key`, if its `prop_type` is a known type.
+    pub async fn load(key: &str, storage: &dyn ConceptStorage) -> StorageResult<Option<Self>> {
+        let Some(record) = storage.get("property_type", key).await? else {
+            return Ok(None);
+        };
+        let Some(field_type) = record["prop_type"].as_str().and_then(FieldType::parse) else {
+            return Ok(None);
+        };
+        let constraints = &record["constraints"];
+        let cardinality = if constraints["multiple"].as_bool().unwrap_or(false) {
+            Cardinality::Many {
+                max: constraints["max_values"].as_u64().map(|n| n as usize),
+            }
+        } else {
+            Cardinality::Single
+        };
+        Ok(Some(PropertySchema {
+            key: key.to_string(),
+            field_type,
+            cardinality,
+        }))
+    }
+
+    /// Coerce `value` to the declared type. Multi-valued properties take a
+    /// list (a single value becomes a one-element list) and coerce each item.
+    pub fn coerce(&self, value: &Value) -> Result<Value, PropertyError> {
+        let coerce_one = |item: &Value| {
+            self.field_type
+                .coerce(item)
+                .ok_or_else(|| PropertyError::TypeMismatch {
+                    key: self.key.clone(),
+                    expected: self.field_type,
+                    value: item.clone(),
+                })
+        };
+        match self.cardinality {
+            Cardinality::Single => coerce_one(value),
+            Cardinality::Many { max } => {
+                let items = match value {
+                    Value::Array(items) if self.field_type != FieldType::Array => items.clone(),
+                    other => vec![other.clone()],
+                };
+                if let Some(max) = max.filter(|max| items.len() > *max) {
+                    return Err(PropertyError::TooManyValues {
+                        key: self.key.clone(),
+                        max,
+                        got: items.len(),
+                    });
+                }
+                items
+                    .iter()
+                    .map(coerce_one)
+                    .collect::<Result<Vec<_>, _>>()
+                    .map(Value::Array)
+            }
+        }
+    }
+}
+
 pub struct PropertyHandler;
 
 impl PropertyHandler {
@@ -101,6 +322,20 @@ impl PropertyHandler {
         input: SetInput,
         storage: &dyn ConceptStorage,
     ) -> StorageResult<SetOutput> {
+        let value = match PropertySchema::load(&input.key, storage).await? {
+            None => input.value,
+            Some(schema) => match schema.coerce(&input.value) {
+                Ok(value) => value,
+                Err(error) => {
+                    return Ok(SetOutput::Invalid {
+                        node_id: input.node_id,
+                        key: input.key,
+                        error,
+                    })
+                }
+            },
+        };
+
         let compound_key = format!("{}:{}", input.node_id, input.key);
         storage
             .put(
@@ -109,7 +344,7 @@ impl PropertyHandler {
                 json!({
                     "node_id": input.node_id,
                     "key": input.key,
-                    "value": input.value,
+                    "value": value,
                     "updated_at": chrono::Utc::now().to_rfc3339(),
                 }),
             )
@@ -241,6 +476,7 @@ mod tests {
                 assert_eq!(node_id, "n1");
                 assert_eq!(key, "color");
             }
+            SetOutput::Invalid { .. } => panic!("expected Ok"),
         }
     }
 
@@ -523,4 +759,122 @@ mod tests {
             }
         }
     }
+
+    // ── schema tests ───────────────────────────────────────
+
+    async fn define(
+        handler: &PropertyHandler,
+        storage: &InMemoryStorage,
+        key: &str,
+        prop_type: &str,
+        constraints: Value,
+    ) {
+        handler
+            .define_type(
+                DefineTypeInput {
+                    key: key.into(),
+                    prop_type: prop_type.into(),
+                    constraints,
+                },
+                storage,
+            )
+            .await
+            .unwrap();
+    }
+
+    async fn set(
+        handler: &PropertyHandler,
+        storage: &InMemoryStorage,
+        key: &str,
+        value: Value,
+    ) -> SetOutput {
+        handler
+            .set(
+                SetInput {
+                    node_id: "n1".into(),
+                    key: key.into(),
+                    value,
+                },
+                storage,
+            )
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn set_coerces_values_to_the_declared_type() {
+        let storage = InMemoryStorage::new();
+        let handler = PropertyHandler;
+        define(&handler, &storage, "priority", "number", json!({})).await;
+        define(
+            &handler,
+            &storage,
+            "tags",
+            "string",
+            json!({ "multiple": true, "max_values": 3 }),
+        )
+        .await;
+
+        let result = set(&handler, &storage, "priority", json!("42")).await;
+        assert!(matches!(result, SetOutput::Ok { .. }));
+        let record = storage
+            .get("property", "n1:priority")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(record["value"], json!(42));
+
+        set(&handler, &storage, "tags", json!(["rust", 7])).await;
+        let record = storage.get("property", "n1:tags").await.unwrap().unwrap();
+        assert_eq!(record["value"], json!(["rust", "7"]));
+
+        // Keys without a schema keep their raw value
+        set(&handler, &storage, "free", json!("42")).await;
+        let record = storage.get("property", "n1:free").await.unwrap().unwrap();
+        assert_eq!(record["value"], json!("42"));
+    }
+
+    #[tokio::test]
+    async fn set_rejects_type_mismatch_and_excess_values() {
+        let storage = InMemoryStorage::new();
+        let handler = PropertyHandler;
+        define(&handler, &storage, "due", "date", json!({})).await;
+        define(
+            &handler,
+            &storage,
+            "owners",
+            "string",
+            json!({ "multiple": true, "max_values": 2 }),
+        )
+        .await;
+
+        match set(&handler, &storage, "due", json!("next tuesday")).await {
+            SetOutput::Invalid { key, error, .. } => {
+                assert_eq!(key, "due");
+                assert_eq!(
+                    error,
+                    PropertyError::TypeMismatch {
+                        key: "due".into(),
+                        expected: FieldType::Date,
+                        value: json!("next tuesday"),
+                    }
+                );
+                assert_eq!(
+                    error.to_string(),
+                    "property 'due' expects date, got \"next tuesday\""
+                );
+            }
+            other => panic!("expected Invalid, got {:?}", other),
+        }
+        assert!(storage.get("property", "n1:due").await.unwrap().is_none());
+
+        let result = set(&handler, &storage, "owners", json!(["a", "b", "c"])).await;
+        assert!(matches!(
+            result,
+            SetOutput::Invalid {
+                error: PropertyError::TooManyValues { max: 2, got: 3, .. },
+                ..
+            }
+        ));
+    }
 }
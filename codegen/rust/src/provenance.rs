@@ -127,7 +127,8 @@ impl ProvenanceHandler {
             .get("provenance_map_table", &map_key)
             .await?
             .unwrap_or(json!({"batch_id": batch_id, "entries": []}));
-        let entries = map_table["entries"].as_array_mut().unwrap_or(&mut vec![]);
+        let mut empty = vec![];
+        let entries = map_table["entries"].as_array_mut().unwrap_or(&mut empty);
         let mut new_entries: Vec<serde_json::Value> = entries.clone();
         new_entries.push(json!({
             "record_id": record_id,
@@ -76,6 +76,365 @@ pub enum AddSortOutput {
     NotFound { message: String },
 }
 
+// ── QueryBuilder ──────────────────────────────────────────
+
+/// Comparison applied by a single filter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FilterOp {
+    Eq,
+    Ne,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+    Contains,
+    In,
+}
+
+impl FilterOp {
+    pub fn parse(op: &str) -> Option<Self> {
+        Some(match op {
+            "eq" | "=" | "==" => FilterOp::Eq,
+            "ne" | "!=" => FilterOp::Ne,
+            "gt" | ">" => FilterOp::Gt,
+            "gte" | ">=" => FilterOp::Gte,
+            "lt" | "<" => FilterOp::Lt,
+            "lte" | "<=" => FilterOp::Lte,
+            "contains" => FilterOp::Contains,
+            "in" => FilterOp::In,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+impl SortDirection {
+    pub fn parse(direction: &str) -> Self {
+        if direction.eq_ignore_ascii_case("desc") {
+            SortDirection::Desc
+        } else {
+            SortDirection::Asc
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Filter {
+    pub field: String,
+    pub op: FilterOp,
+    pub value: serde_json::Value,
+}
+
+impl Filter {
+    pub fn matches(&self, record: &serde_json::Value) -> bool {
+        let actual = record.get(&self.field).unwrap_or(&serde_json::Value::Null);
+        match self.op {
+            FilterOp::Eq => loosely_equal(actual, &self.value),
+            FilterOp::Ne => !loosely_equal(actual, &self.value),
+            FilterOp::Gt => compare_values(actual, &self.value) == std::cmp::Ordering::Greater,
+            FilterOp::Gte => !actual.is_null() && compare_values(actual, &self.value) != std::cmp::Ordering::Less,
+            FilterOp::Lt => !actual.is_null() && compare_values(actual, &self.value) == std::cmp::Ordering::Less,
+            FilterOp::Lte => !actual.is_null() && compare_values(actual, &self.value) != std::cmp::Ordering::Greater,
+            FilterOp::Contains => match (actual, &self.value) {
+                (serde_json::Value::String(a), serde_json::Value::String(b)) => a.contains(b.as_str()),
+                (serde_json::Value::Array(items), needle) => items.contains(needle),
+                _ => false,
+            },
+            FilterOp::In => match &self.value {
+                serde_json::Value::Array(options) => options.contains(actual),
+                _ => false,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Sort {
+    pub field: String,
+    pub direction: SortDirection,
+}
+
+/// Where a page starts: a plain offset, or the cursor returned with the
+/// previous page.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PageStart {
+    Offset(usize),
+    Cursor(String),
+}
+
+/// A compiled query. An equality filter is pushed down to `storage.find` as
+/// criteria when storage's exact comparison gives the same answer (see
+/// `QueryBuilder::build`); the remaining filters, sorting and paging run over
+/// the rows it returns.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QueryPlan {
+    pub relation: String,
+    pub criteria: Option<serde_json::Value>,
+    pub filters: Vec<Filter>,
+    pub sorts: Vec<Sort>,
+    pub limit: Option<usize>,
+    pub start: PageStart,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QueryPage {
+    pub items: Vec<serde_json::Value>,
+    /// Matches across all pages.
+    pub total: usize,
+    /// Cursor for the following page, if there is one.
+    pub next_cursor: Option<String>,
+}
+
+/// Fluent builder over one storage relation.
+///
+/// ```ignore
+/// let page = QueryBuilder::new("articles")
+///     .filter("status", FilterOp::Eq, json!("published"))
+///     .sort("created", SortDirection::Desc)
+///     .limit(20)
+///     .fetch(storage)
+///     .await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct QueryBuilder {
+    relation: String,
+    filters: Vec<Filter>,
+    sorts: Vec<Sort>,
+    limit: Option<usize>,
+    start: PageStart,
+}
+
+impl QueryBuilder {
+    pub fn new(relation: impl Into<String>) -> Self {
+        Self {
+            relation: relation.into(),
+            filters: Vec::new(),
+            sorts: Vec::new(),
+            limit: None,
+            start: PageStart::Offset(0),
+        }
+    }
+
+    /// Builder for a stored `query_def`. Filter values are stored as strings
+    /// and kept that way, since comparisons already read numeric strings as
+    /// numbers; only `in` parses its value as a JSON array. Filters with an
+    /// unknown operator are skipped.
+    pub fn from_definition(query_def: &serde_json::Value) -> Self {
+        let mut builder = Self::new(query_def["scope"].as_str().unwrap_or("default"));
+        for filter in query_def["filters"].as_array().into_iter().flatten() {
+            let Some(op) = filter["operator"].as_str().and_then(FilterOp::parse) else {
+                continue;
+            };
+            let value = match &filter["value"] {
+                serde_json::Value::String(raw) if op == FilterOp::In => {
+                    serde_json::from_str(raw).unwrap_or_else(|_| json!([raw]))
+                }
+                other => other.clone(),
+            };
+            builder = builder.filter(filter["field"].as_str().unwrap_or(""), op, value);
+        }
+        for sort in query_def["sorts"].as_array().into_iter().flatten() {
+            builder = builder.sort(
+                sort["field"].as_str().unwrap_or(""),
+                SortDirection::parse(sort["direction"].as_str().unwrap_or("asc")),
+            );
+        }
+        builder
+    }
+
+    /// Add a filter; all filters must match.
+    pub fn filter(mut self, field: impl Into<String>, op: FilterOp, value: serde_json::Value) -> Self {
+        self.filters.push(Filter { field: field.into(), op, value });
+        self
+    }
+
+    /// Add a sort key; earlier keys take precedence.
+    pub fn sort(mut self, field: impl Into<String>, direction: SortDirection) -> Self {
+        self.sorts.push(Sort { field: field.into(), direction });
+        self
+    }
+
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.start = PageStart::Offset(offset);
+        self
+    }
+
+    /// Continue from the `next_cursor` of a previous page.
+    pub fn after(mut self, cursor: impl Into<String>) -> Self {
+        self.start = PageStart::Cursor(cursor.into());
+        self
+    }
+
+    /// Compile the query. An equality filter becomes storage criteria only
+    /// when it is the sole equality filter on its field and its value can
+    /// only loosely equal a value of its own type; numbers, booleans and
+    /// numeric strings also match their text or numeric counterparts, so
+    /// they stay in `filters`.
+    pub fn build(&self) -> QueryPlan {
+        let pushed_down = |filter: &Filter| {
+            filter.op == FilterOp::Eq
+                && exact_only(&filter.value)
+                && self
+                    .filters
+                    .iter()
+                    .filter(|f| f.op == FilterOp::Eq && f.field == filter.field)
+                    .count()
+                    == 1
+        };
+        let criteria: serde_json::Map<String, serde_json::Value> = self
+            .filters
+            .iter()
+            .filter(|f| pushed_down(f))
+            .map(|f| (f.field.clone(), f.value.clone()))
+            .collect();
+        QueryPlan {
+            relation: self.relation.clone(),
+            criteria: (!criteria.is_empty()).then_some(serde_json::Value::Object(criteria)),
+            filters: self
+                .filters
+                .iter()
+                .filter(|f| !pushed_down(f))
+                .cloned()
+                .collect(),
+            sorts: self.sorts.clone(),
+            limit: self.limit,
+            start: self.start.clone(),
+        }
+    }
+
+    /// Total matches, ignoring limit and offset.
+    pub async fn count(&self, storage: &dyn ConceptStorage) -> StorageResult<usize> {
+        Ok(self.build().matching(storage).await?.len())
+    }
+
+    pub async fn fetch(&self, storage: &dyn ConceptStorage) -> StorageResult<QueryPage> {
+        self.build().execute(storage).await
+    }
+}
+
+impl QueryPlan {
+    /// Every matching row, sorted. Rows that tie on all sort keys are ordered
+    /// by their serialized form so pages stay stable between calls.
+    async fn matching(&self, storage: &dyn ConceptStorage) -> StorageResult<Vec<serde_json::Value>> {
+        let mut rows: Vec<serde_json::Value> = storage
+            .find(&self.relation, self.criteria.as_ref())
+            .await?
+            .into_iter()
+            .filter(|row| self.filters.iter().all(|f| f.matches(row)))
+            .collect();
+
+        rows.sort_by_cached_key(|row| row.to_string());
+        rows.sort_by(|a, b| {
+            self.sorts
+                .iter()
+                .map(|sort| {
+                    let ordering = compare_values(
+                        a.get(&sort.field).unwrap_or(&serde_json::Value::Null),
+                        b.get(&sort.field).unwrap_or(&serde_json::Value::Null),
+                    );
+                    match sort.direction {
+                        SortDirection::Asc => ordering,
+                        SortDirection::Desc => ordering.reverse(),
+                    }
+                })
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        Ok(rows)
+    }
+
+    pub async fn execute(&self, storage: &dyn ConceptStorage) -> StorageResult<QueryPage> {
+        let rows = self.matching(storage).await?;
+        let total = rows.len();
+        let offset = match &self.start {
+            PageStart::Offset(offset) => *offset,
+            PageStart::Cursor(cursor) => decode_cursor(cursor)
+                .ok_or_else(|| format!("Invalid query cursor '{}'", cursor))?,
+        };
+        let items: Vec<serde_json::Value> = rows
+            .into_iter()
+            .skip(offset)
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect();
+        let end = offset + items.len();
+        Ok(QueryPage {
+            items,
+            total,
+            next_cursor: (end < total).then(|| encode_cursor(end)),
+        })
+    }
+}
+
+fn encode_cursor(offset: usize) -> String {
+    format!("o{}", offset)
+}
+
+fn decode_cursor(cursor: &str) -> Option<usize> {
+    cursor.strip_prefix('o')?.parse().ok()
+}
+
+/// Equality as `compare_values` sees it for scalars, so `"42"` equals `42`
+/// and `"true"` equals `true`; arrays, objects and nulls compare exactly.
+fn loosely_equal(a: &serde_json::Value, b: &serde_json::Value) -> bool {
+    use serde_json::Value;
+    let scalar = |v: &Value| matches!(v, Value::String(_) | Value::Number(_) | Value::Bool(_));
+    a == b || (scalar(a) && scalar(b) && compare_values(a, b).is_eq())
+}
+
+/// Whether `loosely_equal(x, value)` implies `x == value`, making an exact
+/// storage match safe: true for text that reads as neither a number nor a
+/// boolean, and for arrays and objects.
+fn exact_only(value: &serde_json::Value) -> bool {
+    use serde_json::Value;
+    match value {
+        Value::String(s) => s.trim().parse::<f64>().is_err() && s != "true" && s != "false",
+        Value::Array(_) | Value::Object(_) => true,
+        _ => false,
+    }
+}
+
+/// Orders nulls first, then compares numerically when both sides read as
+/// numbers, and otherwise by their text. Shared with collection sorting so
+/// a list view orders values the way query filters compare them.
+pub(crate) fn compare_values(a: &serde_json::Value, b: &serde_json::Value) -> std::cmp::Ordering {
+    use serde_json::Value;
+    fn as_number(v: &Value) -> Option<f64> {
+        match v {
+            Value::Number(n) => n.as_f64(),
+            Value::String(s) => s.trim().parse().ok(),
+            _ => None,
+        }
+    }
+    fn as_text(v: &Value) -> String {
+        match v {
+            Value::String(s) => s.clone(),
+            other => other.to_string(),
+        }
+    }
+    match (a, b) {
+        (Value::Null, Value::Null) => std::cmp::Ordering::Equal,
+        (Value::Null, _) => std::cmp::Ordering::Less,
+        (_, Value::Null) => std::cmp::Ordering::Greater,
+        _ => match (as_number(a), as_number(b)) {
+            (Some(x), Some(y)) => x.total_cmp(&y),
+            _ => as_text(a).cmp(&as_text(b)),
+        },
+    }
+}
+
 // ── Handler ───────────────────────────────────────────────
 
 pub struct QueryHandler;
@@ -126,12 +485,11 @@ impl QueryHandler {
                 message: format!("Query '{}' not found", input.query_id),
             }),
             Some(query_def) => {
-                let scope = query_def["scope"].as_str().unwrap_or("default");
-                let results = storage.find(scope, None).await?;
+                let page = QueryBuilder::from_definition(&query_def).fetch(storage).await?;
 
                 Ok(ExecuteOutput::Ok {
                     query_id: input.query_id,
-                    results: serde_json::to_string(&results)?,
+                    results: serde_json::to_string(&page.items)?,
                 })
             }
         }
@@ -430,4 +788,187 @@ mod tests {
 
         assert!(matches!(result, AddSortOutput::NotFound { .. }));
     }
+
+    // ── query builder tests ────────────────────────────────
+
+    async fn seed_articles(storage: &InMemoryStorage) {
+        let rows = [
+            ("a1", "published", 12, "Borrowing"),
+            ("a2", "draft", 40, "Lifetimes"),
+            ("a3", "published", 7, "Traits"),
+            ("a4", "published", 31, "Async"),
+            ("a5", "published", 19, "Macros"),
+        ];
+        for (id, status, views, title) in rows {
+            storage
+                .put("articles", id, json!({ "id": id, "status": status, "views": views, "title": title }))
+                .await
+                .unwrap();
+        }
+    }
+
+    fn ids(page: &QueryPage) -> Vec<&str> {
+        page.items.iter().map(|item| item["id"].as_str().unwrap()).collect()
+    }
+
+    #[tokio::test]
+    async fn builder_combines_filters_with_and() {
+        let storage = InMemoryStorage::new();
+        seed_articles(&storage).await;
+
+        let query = QueryBuilder::new("articles")
+            .filter("status", FilterOp::Eq, json!("published"))
+            .filter("views", FilterOp::Gte, json!(12))
+            .sort("id", SortDirection::Asc);
+
+        let plan = query.build();
+        assert_eq!(plan.criteria, Some(json!({ "status": "published" })));
+        assert_eq!(plan.filters.len(), 1);
+
+        let page = query.fetch(&storage).await.unwrap();
+        assert_eq!(ids(&page), vec!["a1", "a4", "a5"]);
+        assert_eq!(query.count(&storage).await.unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn builder_sorts_by_field_and_direction() {
+        let storage = InMemoryStorage::new();
+        seed_articles(&storage).await;
+
+        let page = QueryBuilder::new("articles")
+            .sort("views", SortDirection::Desc)
+            .fetch(&storage)
+            .await
+            .unwrap();
+        assert_eq!(ids(&page), vec!["a2", "a4", "a5", "a1", "a3"]);
+
+        let page = QueryBuilder::new("articles")
+            .sort("status", SortDirection::Asc)
+            .sort("title", SortDirection::Asc)
+            .fetch(&storage)
+            .await
+            .unwrap();
+        assert_eq!(ids(&page), vec!["a2", "a4", "a1", "a5", "a3"]);
+    }
+
+    #[tokio::test]
+    async fn builder_pages_by_offset_and_cursor() {
+        let storage = InMemoryStorage::new();
+        seed_articles(&storage).await;
+
+        let query = QueryBuilder::new("articles").sort("views", SortDirection::Asc).limit(2);
+        let first = query.fetch(&storage).await.unwrap();
+        assert_eq!(ids(&first), vec!["a3", "a1"]);
+        assert_eq!(first.total, 5);
+
+        let second = query.clone().after(first.next_cursor.unwrap()).fetch(&storage).await.unwrap();
+        assert_eq!(ids(&second), vec!["a5", "a4"]);
+        assert_eq!(second, query.clone().offset(2).fetch(&storage).await.unwrap());
+
+        let last = query.clone().after(second.next_cursor.unwrap()).fetch(&storage).await.unwrap();
+        assert_eq!(ids(&last), vec!["a2"]);
+        assert_eq!((last.total, last.next_cursor), (5, None));
+
+        assert!(query.after("bogus").fetch(&storage).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn execute_applies_stored_filters_and_sorts() {
+        let storage = InMemoryStorage::new();
+        let handler = QueryHandler;
+        seed_articles(&storage).await;
+
+        let CreateOutput::Ok { query_id } = handler
+            .create(CreateInput { query_string: "popular".into(), scope: "articles".into() }, &storage)
+            .await
+            .unwrap();
+        handler
+            .add_filter(
+                AddFilterInput {
+                    query_id: query_id.clone(),
+                    field: "views".into(),
+                    operator: "gt".into(),
+                    value: "15".into(),
+                },
+                &storage,
+            )
+            .await
+            .unwrap();
+        handler
+            .add_sort(
+                AddSortInput { query_id: query_id.clone(), field: "title".into(), direction: "asc".into() },
+                &storage,
+            )
+            .await
+            .unwrap();
+
+        let result = handler
+            .execute(ExecuteInput { query_id, storage_ref: "default".into() }, &storage)
+            .await
+            .unwrap();
+        let ExecuteOutput::Ok { results, .. } = result else { panic!("expected ok") };
+        let results: Vec<serde_json::Value> = serde_json::from_str(&results).unwrap();
+        let titles: Vec<&str> = results.iter().map(|r| r["title"].as_str().unwrap()).collect();
+        assert_eq!(titles, vec!["Async", "Lifetimes", "Macros"]);
+    }
+
+    #[tokio::test]
+    async fn stored_equality_filters_keep_string_values() {
+        let storage = InMemoryStorage::new();
+        let handler = QueryHandler;
+        storage
+            .put("codes", "c1", json!({ "id": "c1", "code": "42" }))
+            .await
+            .unwrap();
+        storage
+            .put("codes", "c2", json!({ "id": "c2", "code": 42 }))
+            .await
+            .unwrap();
+        storage
+            .put("codes", "c3", json!({ "id": "c3", "code": "7" }))
+            .await
+            .unwrap();
+
+        let CreateOutput::Ok { query_id } = handler
+            .create(
+                CreateInput {
+                    query_string: "answer".into(),
+                    scope: "codes".into(),
+                },
+                &storage,
+            )
+            .await
+            .unwrap();
+        handler
+            .add_filter(
+                AddFilterInput {
+                    query_id: query_id.clone(),
+                    field: "code".into(),
+                    operator: "eq".into(),
+                    value: "42".into(),
+                },
+                &storage,
+            )
+            .await
+            .unwrap();
+
+        let query_def = storage.get("query_def", &query_id).await.unwrap().unwrap();
+        let query = QueryBuilder::from_definition(&query_def).sort("id", SortDirection::Asc);
+        assert_eq!(query.build().criteria, None);
+        assert_eq!(ids(&query.fetch(&storage).await.unwrap()), vec!["c1", "c2"]);
+    }
+
+    #[tokio::test]
+    async fn repeated_equality_filters_on_a_field_all_apply() {
+        let storage = InMemoryStorage::new();
+        seed_articles(&storage).await;
+
+        let query = QueryBuilder::new("articles")
+            .filter("status", FilterOp::Eq, json!("published"))
+            .filter("status", FilterOp::Eq, json!("draft"));
+        let plan = query.build();
+        assert_eq!(plan.criteria, None);
+        assert_eq!(plan.filters.len(), 2);
+        assert_eq!(query.count(&storage).await.unwrap(), 0);
+    }
 }
@@ -1,11 +1,16 @@
 // Queue Concept Implementation (Rust)
 //
 // Automation suite — enqueues items, claims the oldest unclaimed item,
-// releases items back to the queue, and deletes processed items.
+// releases items back to the queue, and deletes processed items. Claimed
+// items become visible again when their visibility timeout lapses; failed
+// items are retried with exponential backoff and dead-lettered once they
+// run out of attempts.
 
 use crate::storage::{ConceptStorage, StorageResult};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::time::Duration;
 
 // ── Enqueue ───────────────────────────────────────────────
 
@@ -70,6 +75,156 @@ pub enum QueueDeleteItemOutput {
     NotFound { message: String },
 }
 
+// ── Dequeue ───────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueueDequeueInput {
+    pub queue_id: String,
+    /// How long the item stays hidden before it is redelivered unless acked;
+    /// the item's policy default when `None`.
+    pub visibility_timeout_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "variant")]
+pub enum QueueDequeueOutput {
+    #[serde(rename = "ok")]
+    Ok {
+        item_id: String,
+        data: String,
+        attempt: u32,
+    },
+    #[serde(rename = "empty")]
+    Empty { queue_id: String },
+}
+
+// ── Ack ───────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueueAckInput {
+    pub item_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "variant")]
+pub enum QueueAckOutput {
+    #[serde(rename = "ok")]
+    Ok { item_id: String },
+    #[serde(rename = "notfound")]
+    NotFound { message: String },
+}
+
+// ── Nack ──────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueueNackInput {
+    pub item_id: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "variant")]
+pub enum QueueNackOutput {
+    #[serde(rename = "retry")]
+    Retry {
+        item_id: String,
+        attempt: u32,
+        visible_at: String,
+    },
+    #[serde(rename = "dead_lettered")]
+    DeadLettered { item_id: String },
+    #[serde(rename = "notfound")]
+    NotFound { message: String },
+}
+
+// ── DeadLetters ───────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueueDeadLettersInput {
+    pub queue_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "variant")]
+pub enum QueueDeadLettersOutput {
+    #[serde(rename = "ok")]
+    Ok { items: String },
+}
+
+// ── Delivery policy ───────────────────────────────────────
+
+/// Per-message delivery settings, stored with the item when it is enqueued.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Deliveries allowed before the item is dead-lettered.
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubles on each further attempt.
+    pub retry_delay: Duration,
+    pub max_retry_delay: Duration,
+    pub visibility_timeout: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            retry_delay: Duration::from_secs(1),
+            max_retry_delay: Duration::from_secs(15 * 60),
+            visibility_timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Backoff after the `attempt`th failed delivery (1-based).
+    pub fn retry_delay(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.retry_delay.saturating_mul(factor).min(self.max_retry_delay)
+    }
+
+    fn from_record(record: &serde_json::Value) -> Self {
+        let default = Self::default();
+        let ms = |key: &str, fallback: Duration| {
+            record[key].as_u64().map_or(fallback, Duration::from_millis)
+        };
+        Self {
+            max_attempts: record["max_attempts"].as_u64().map_or(default.max_attempts, |n| n as u32),
+            retry_delay: ms("retry_delay_ms", default.retry_delay),
+            max_retry_delay: ms("max_retry_delay_ms", default.max_retry_delay),
+            visibility_timeout: ms("visibility_timeout_ms", default.visibility_timeout),
+        }
+    }
+}
+
+fn after(now: DateTime<Utc>, delay: Duration) -> DateTime<Utc> {
+    now + chrono::Duration::from_std(delay).unwrap_or(chrono::Duration::MAX)
+}
+
+/// Whether an item can be delivered at `now`: pending and past any retry
+/// delay, or claimed with a lapsed visibility timeout.
+fn is_visible(item: &serde_json::Value, now: DateTime<Utc>) -> bool {
+    let visible_at = item["visible_at"]
+        .as_str()
+        .and_then(|at| DateTime::parse_from_rfc3339(at).ok());
+    match item["status"].as_str() {
+        Some("pending") => visible_at.is_none_or(|at| at <= now),
+        Some("claimed") => visible_at.is_some_and(|at| at <= now),
+        _ => false,
+    }
+}
+
+async fn dead_letter(
+    mut item: serde_json::Value,
+    now: DateTime<Utc>,
+    storage: &dyn ConceptStorage,
+) -> StorageResult<()> {
+    let item_id = item["item_id"].as_str().unwrap_or("").to_string();
+    item["status"] = json!("dead_letter");
+    item["visible_at"] = serde_json::Value::Null;
+    item["dead_lettered_at"] = json!(now.to_rfc3339());
+    storage.put("queue_item", &item_id, item).await
+}
+
 // ── Handler ───────────────────────────────────────────────
 
 pub struct QueueHandler;
@@ -79,6 +234,16 @@ impl QueueHandler {
         &self,
         input: QueueEnqueueInput,
         storage: &dyn ConceptStorage,
+    ) -> StorageResult<QueueEnqueueOutput> {
+        self.enqueue_with(input, RetryPolicy::default(), storage).await
+    }
+
+    /// Enqueue with a delivery policy other than the default.
+    pub async fn enqueue_with(
+        &self,
+        input: QueueEnqueueInput,
+        policy: RetryPolicy,
+        storage: &dyn ConceptStorage,
     ) -> StorageResult<QueueEnqueueOutput> {
         let item_id = format!("qi_{}", rand::random::<u32>());
         let now = chrono::Utc::now().to_rfc3339();
@@ -91,6 +256,11 @@ impl QueueHandler {
                     "queue_id": input.queue_id,
                     "data": input.data,
                     "status": "pending",
+                    "attempts": 0,
+                    "max_attempts": policy.max_attempts,
+                    "retry_delay_ms": policy.retry_delay.as_millis() as u64,
+                    "max_retry_delay_ms": policy.max_retry_delay.as_millis() as u64,
+                    "visibility_timeout_ms": policy.visibility_timeout.as_millis() as u64,
                     "created_at": now,
                 }),
             )
@@ -103,38 +273,169 @@ impl QueueHandler {
         input: QueueClaimInput,
         storage: &dyn ConceptStorage,
     ) -> StorageResult<QueueClaimOutput> {
-        let criteria = json!({ "queue_id": input.queue_id, "status": "pending" });
-        let items = storage
-            .find("queue_item", Some(&criteria))
+        let dequeued = self
+            .dequeue(
+                QueueDequeueInput {
+                    queue_id: input.queue_id,
+                    visibility_timeout_ms: None,
+                },
+                storage,
+            )
             .await?;
+        Ok(match dequeued {
+            QueueDequeueOutput::Ok { item_id, data, .. } => QueueClaimOutput::Ok { item_id, data },
+            QueueDequeueOutput::Empty { queue_id } => QueueClaimOutput::Empty { queue_id },
+        })
+    }
 
-        // Find the oldest item (first by created_at)
-        let oldest = items.iter().min_by_key(|item| {
-            item["created_at"].as_str().unwrap_or("").to_string()
+    pub async fn dequeue(
+        &self,
+        input: QueueDequeueInput,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<QueueDequeueOutput> {
+        self.dequeue_at(input, Utc::now(), storage).await
+    }
+
+    /// Deliver the oldest visible item and hide it for the visibility
+    /// timeout. An item whose timeout lapsed with no attempts left is
+    /// dead-lettered instead of being delivered again.
+    pub async fn dequeue_at(
+        &self,
+        input: QueueDequeueInput,
+        now: DateTime<Utc>,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<QueueDequeueOutput> {
+        let mut items: Vec<serde_json::Value> = storage
+            .find("queue_item", Some(&json!({ "queue_id": input.queue_id })))
+            .await?
+            .into_iter()
+            .filter(|item| is_visible(item, now))
+            .collect();
+        items.sort_by(|a, b| {
+            let key = |item: &serde_json::Value| {
+                (item["created_at"].as_str().unwrap_or("").to_string(), item["item_id"].as_str().unwrap_or("").to_string())
+            };
+            key(a).cmp(&key(b))
         });
 
-        match oldest {
-            None => Ok(QueueClaimOutput::Empty {
-                queue_id: input.queue_id,
-            }),
-            Some(item) => {
-                let item_id = item["item_id"]
-                    .as_str()
-                    .unwrap_or("")
-                    .to_string();
-                let data = item["data"]
-                    .as_str()
-                    .unwrap_or("")
-                    .to_string();
-
-                let mut claimed = item.clone();
-                claimed["status"] = json!("claimed");
-                claimed["claimed_at"] = json!(chrono::Utc::now().to_rfc3339());
-                storage.put("queue_item", &item_id, claimed).await?;
-
-                Ok(QueueClaimOutput::Ok { item_id, data })
+        for mut item in items {
+            let policy = RetryPolicy::from_record(&item);
+            let attempts = item["attempts"].as_u64().unwrap_or(0) as u32;
+            if attempts >= policy.max_attempts {
+                dead_letter(item, now, storage).await?;
+                continue;
             }
+
+            let visibility = input
+                .visibility_timeout_ms
+                .map_or(policy.visibility_timeout, Duration::from_millis);
+            let item_id = item["item_id"].as_str().unwrap_or("").to_string();
+            let data = item["data"].as_str().unwrap_or("").to_string();
+            item["status"] = json!("claimed");
+            item["attempts"] = json!(attempts + 1);
+            item["claimed_at"] = json!(now.to_rfc3339());
+            item["visible_at"] = json!(after(now, visibility).to_rfc3339());
+            storage.put("queue_item", &item_id, item).await?;
+
+            return Ok(QueueDequeueOutput::Ok {
+                item_id,
+                data,
+                attempt: attempts + 1,
+            });
+        }
+
+        Ok(QueueDequeueOutput::Empty {
+            queue_id: input.queue_id,
+        })
+    }
+
+    /// Acknowledge successful processing, removing the item.
+    pub async fn ack(
+        &self,
+        input: QueueAckInput,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<QueueAckOutput> {
+        let deleted = self
+            .delete_item(QueueDeleteItemInput { item_id: input.item_id }, storage)
+            .await?;
+        Ok(match deleted {
+            QueueDeleteItemOutput::Ok { item_id } => QueueAckOutput::Ok { item_id },
+            QueueDeleteItemOutput::NotFound { message } => QueueAckOutput::NotFound { message },
+        })
+    }
+
+    pub async fn nack(
+        &self,
+        input: QueueNackInput,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<QueueNackOutput> {
+        self.nack_at(input, Utc::now(), storage).await
+    }
+
+    /// Report failed processing: the item is retried after its backoff, or
+    /// dead-lettered when it has used all its attempts.
+    pub async fn nack_at(
+        &self,
+        input: QueueNackInput,
+        now: DateTime<Utc>,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<QueueNackOutput> {
+        let Some(mut item) = storage.get("queue_item", &input.item_id).await? else {
+            return Ok(QueueNackOutput::NotFound {
+                message: format!("queue item '{}' not found", input.item_id),
+            });
+        };
+
+        let policy = RetryPolicy::from_record(&item);
+        let attempts = item["attempts"].as_u64().unwrap_or(0) as u32;
+        item["last_error"] = json!(input.reason);
+        if attempts >= policy.max_attempts {
+            dead_letter(item, now, storage).await?;
+            return Ok(QueueNackOutput::DeadLettered {
+                item_id: input.item_id,
+            });
         }
+
+        let visible_at = after(now, policy.retry_delay(attempts)).to_rfc3339();
+        item["status"] = json!("pending");
+        item["claimed_at"] = serde_json::Value::Null;
+        item["visible_at"] = json!(visible_at);
+        storage.put("queue_item", &input.item_id, item).await?;
+
+        Ok(QueueNackOutput::Retry {
+            item_id: input.item_id,
+            attempt: attempts,
+            visible_at,
+        })
+    }
+
+    /// Items that exhausted their attempts, oldest first.
+    pub async fn dead_letters(
+        &self,
+        input: QueueDeadLettersInput,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<QueueDeadLettersOutput> {
+        let criteria = json!({ "queue_id": input.queue_id, "status": "dead_letter" });
+        let mut items = storage.find("queue_item", Some(&criteria)).await?;
+        items.sort_by(|a, b| {
+            a["dead_lettered_at"].as_str().cmp(&b["dead_lettered_at"].as_str())
+                .then_with(|| a["item_id"].as_str().cmp(&b["item_id"].as_str()))
+        });
+        let items: Vec<serde_json::Value> = items
+            .iter()
+            .map(|item| {
+                json!({
+                    "item_id": item["item_id"],
+                    "data": item["data"],
+                    "attempts": item["attempts"],
+                    "last_error": item["last_error"],
+                    "dead_lettered_at": item["dead_lettered_at"],
+                })
+            })
+            .collect();
+        Ok(QueueDeadLettersOutput::Ok {
+            items: serde_json::to_string(&items)?,
+        })
     }
 
     pub async fn release(
@@ -150,6 +451,7 @@ impl QueueHandler {
             Some(mut record) => {
                 record["status"] = json!("pending");
                 record["claimed_at"] = serde_json::Value::Null;
+                record["visible_at"] = serde_json::Value::Null;
                 storage
                     .put("queue_item", &input.item_id, record)
                     .await?;
@@ -416,4 +718,109 @@ mod tests {
 
         assert!(matches!(result, QueueDeleteItemOutput::NotFound { .. }));
     }
+
+    // ── retry / dead-letter tests ──────────────────────────
+
+    async fn enqueue_with(storage: &InMemoryStorage, data: &str, policy: RetryPolicy) -> String {
+        let result = QueueHandler
+            .enqueue_with(
+                QueueEnqueueInput {
+                    queue_id: "jobs".into(),
+                    data: data.into(),
+                },
+                policy,
+                storage,
+            )
+            .await
+            .unwrap();
+        match result {
+            QueueEnqueueOutput::Ok { item_id } => item_id,
+        }
+    }
+
+    async fn dequeue(storage: &InMemoryStorage, now: DateTime<Utc>) -> Option<(String, u32)> {
+        let input = QueueDequeueInput {
+            queue_id: "jobs".into(),
+            visibility_timeout_ms: Some(10_000),
+        };
+        match QueueHandler.dequeue_at(input, now, storage).await.unwrap() {
+            QueueDequeueOutput::Ok { item_id, attempt, .. } => Some((item_id, attempt)),
+            QueueDequeueOutput::Empty { .. } => None,
+        }
+    }
+
+    async fn nack(storage: &InMemoryStorage, item_id: &str, now: DateTime<Utc>) -> QueueNackOutput {
+        let input = QueueNackInput {
+            item_id: item_id.into(),
+            reason: "boom".into(),
+        };
+        QueueHandler.nack_at(input, now, storage).await.unwrap()
+    }
+
+    fn secs(n: i64) -> chrono::Duration {
+        chrono::Duration::seconds(n)
+    }
+
+    #[tokio::test]
+    async fn message_fails_twice_then_succeeds() {
+        let storage = InMemoryStorage::new();
+        let policy = RetryPolicy {
+            max_attempts: 3,
+            retry_delay: Duration::from_secs(2),
+            ..RetryPolicy::default()
+        };
+        assert_eq!(policy.retry_delay(2), Duration::from_secs(4));
+        let id = enqueue_with(&storage, "send email", policy).await;
+        let t0 = Utc::now() + secs(1);
+
+        assert_eq!(dequeue(&storage, t0).await, Some((id.clone(), 1)));
+        assert_eq!(dequeue(&storage, t0).await, None);
+        assert!(matches!(nack(&storage, &id, t0).await, QueueNackOutput::Retry { attempt: 1, .. }));
+
+        // Hidden for the 2s backoff
+        assert_eq!(dequeue(&storage, t0 + secs(1)).await, None);
+        assert_eq!(dequeue(&storage, t0 + secs(2)).await, Some((id.clone(), 2)));
+
+        // Not acked within the visibility timeout: redelivered
+        assert_eq!(dequeue(&storage, t0 + secs(11)).await, None);
+        assert_eq!(dequeue(&storage, t0 + secs(12)).await, Some((id.clone(), 3)));
+
+        let acked = QueueHandler.ack(QueueAckInput { item_id: id.clone() }, &storage).await.unwrap();
+        assert!(matches!(acked, QueueAckOutput::Ok { .. }));
+        assert!(storage.get("queue_item", &id).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn exhausted_message_moves_to_dead_letters() {
+        let storage = InMemoryStorage::new();
+        let policy = RetryPolicy {
+            max_attempts: 2,
+            retry_delay: Duration::ZERO,
+            ..RetryPolicy::default()
+        };
+        let poison = enqueue_with(&storage, "poison", policy).await;
+        let t0 = Utc::now() + secs(1);
+
+        assert_eq!(dequeue(&storage, t0).await, Some((poison.clone(), 1)));
+        assert!(matches!(nack(&storage, &poison, t0).await, QueueNackOutput::Retry { .. }));
+        assert_eq!(dequeue(&storage, t0).await, Some((poison.clone(), 2)));
+        assert!(matches!(nack(&storage, &poison, t0).await, QueueNackOutput::DeadLettered { .. }));
+        assert_eq!(dequeue(&storage, t0 + secs(60)).await, None);
+
+        // A consumer that dies on its last attempt also dead-letters the item
+        let lost = enqueue_with(&storage, "lost", RetryPolicy { max_attempts: 1, ..policy }).await;
+        assert_eq!(dequeue(&storage, t0 + secs(1)).await, Some((lost.clone(), 1)));
+        assert_eq!(dequeue(&storage, t0 + secs(20)).await, None);
+
+        let result = QueueHandler
+            .dead_letters(QueueDeadLettersInput { queue_id: "jobs".into() }, &storage)
+            .await
+            .unwrap();
+        let QueueDeadLettersOutput::Ok { items } = result;
+        let items: Vec<serde_json::Value> = serde_json::from_str(&items).unwrap();
+        let ids: Vec<&str> = items.iter().map(|i| i["item_id"].as_str().unwrap()).collect();
+        assert_eq!(ids, vec![poison.as_str(), lost.as_str()]);
+        assert_eq!(items[0]["last_error"], "boom");
+        assert_eq!(items[0]["attempts"], 2);
+    }
 }
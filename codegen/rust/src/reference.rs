@@ -3,10 +3,27 @@
 // Manages directional references between entities — add, remove,
 // and list outgoing references from a source.
 
+use crate::event_bus::{EventBusHandler, EventListener};
 use crate::storage::{ConceptStorage, StorageResult};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
+/// Published on the event bus after a reference is stored. When the add
+/// replaced an existing reference, the payload also carries that
+/// reference's type as `previous_ref_type`.
+pub const REFERENCE_ADDED: &str = "reference.added";
+/// Published on the event bus after a reference is deleted.
+pub const REFERENCE_REMOVED: &str = "reference.removed";
+
+/// A directed reference between two entities, as carried in
+/// `reference.*` event payloads.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Reference {
+    pub source_id: String,
+    pub target_id: String,
+    pub ref_type: String,
+}
+
 // --- AddRef ---
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -67,8 +84,22 @@ impl ReferenceHandler {
         &self,
         input: AddRefInput,
         storage: &dyn ConceptStorage,
+    ) -> StorageResult<AddRefOutput> {
+        self.add_ref_with(input, &[], storage).await
+    }
+
+    /// `add_ref`, then publish `reference.added` to `listeners`. If a
+    /// listener fails the reference is put back the way it was and the
+    /// listeners that already ran are compensated, so indexes built from
+    /// the event never disagree with the reference store.
+    pub async fn add_ref_with(
+        &self,
+        input: AddRefInput,
+        listeners: &[&dyn EventListener],
+        storage: &dyn ConceptStorage,
     ) -> StorageResult<AddRefOutput> {
         let compound_key = format!("{}:{}", input.source_id, input.target_id);
+        let previous = storage.get("reference", &compound_key).await?;
         storage
             .put(
                 "reference",
@@ -82,9 +113,26 @@ impl ReferenceHandler {
             )
             .await?;
 
-        Ok(AddRefOutput::Ok {
+        let reference = Reference {
             source_id: input.source_id,
             target_id: input.target_id,
+            ref_type: input.ref_type,
+        };
+        let mut payload = serde_json::to_value(&reference)?;
+        if let Some(previous_type) = previous.as_ref().and_then(|p| p["ref_type"].as_str()) {
+            payload["previous_ref_type"] = json!(previous_type);
+        }
+        if let Err(err) = publish(REFERENCE_ADDED, &payload, listeners, storage).await {
+            match previous {
+                Some(previous) => storage.put("reference", &compound_key, previous).await?,
+                None => storage.del("reference", &compound_key).await?,
+            }
+            return Err(err);
+        }
+
+        Ok(AddRefOutput::Ok {
+            source_id: reference.source_id,
+            target_id: reference.target_id,
         })
     }
 
@@ -92,6 +140,17 @@ impl ReferenceHandler {
         &self,
         input: RemoveRefInput,
         storage: &dyn ConceptStorage,
+    ) -> StorageResult<RemoveRefOutput> {
+        self.remove_ref_with(input, &[], storage).await
+    }
+
+    /// `remove_ref`, then publish `reference.removed` to `listeners`,
+    /// restoring the reference if a listener fails.
+    pub async fn remove_ref_with(
+        &self,
+        input: RemoveRefInput,
+        listeners: &[&dyn EventListener],
+        storage: &dyn ConceptStorage,
     ) -> StorageResult<RemoveRefOutput> {
         let compound_key = format!("{}:{}", input.source_id, input.target_id);
         let existing = storage.get("reference", &compound_key).await?;
@@ -102,11 +161,29 @@ impl ReferenceHandler {
                     input.source_id, input.target_id
                 ),
             }),
-            Some(_) => {
+            Some(existing) => {
                 storage.del("reference", &compound_key).await?;
-                Ok(RemoveRefOutput::Ok {
+
+                let reference = Reference {
                     source_id: input.source_id,
                     target_id: input.target_id,
+                    ref_type: existing["ref_type"].as_str().unwrap_or("link").to_string(),
+                };
+                if let Err(err) = publish(
+                    REFERENCE_REMOVED,
+                    &serde_json::to_value(&reference)?,
+                    listeners,
+                    storage,
+                )
+                .await
+                {
+                    storage.put("reference", &compound_key, existing).await?;
+                    return Err(err);
+                }
+
+                Ok(RemoveRefOutput::Ok {
+                    source_id: reference.source_id,
+                    target_id: reference.target_id,
                 })
             }
         }
@@ -131,6 +208,22 @@ impl ReferenceHandler {
     }
 }
 
+async fn publish(
+    event_type_id: &str,
+    payload: &serde_json::Value,
+    listeners: &[&dyn EventListener],
+    storage: &dyn ConceptStorage,
+) -> StorageResult<()> {
+    // Plain add_ref / remove_ref have nobody to tell, so no event is recorded.
+    if listeners.is_empty() {
+        return Ok(());
+    }
+    EventBusHandler
+        .publish(event_type_id, payload, listeners, storage)
+        .await?;
+    Ok(())
+}
+
 // ── Tests ──────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -315,4 +408,107 @@ mod tests {
             }
         }
     }
+
+    // ── event publishing tests ─────────────────────────────
+
+    struct FailingListener;
+
+    #[async_trait::async_trait]
+    impl EventListener for FailingListener {
+        fn listener_id(&self) -> &str {
+            "failing"
+        }
+
+        async fn handle(
+            &self,
+            _event_type_id: &str,
+            _payload: &serde_json::Value,
+            _storage: &dyn ConceptStorage,
+        ) -> StorageResult<()> {
+            Err("index unavailable".into())
+        }
+    }
+
+    #[tokio::test]
+    async fn failed_listener_rolls_back_reference_change() {
+        let storage = InMemoryStorage::new();
+        let handler = ReferenceHandler;
+        for event_type_id in [REFERENCE_ADDED, REFERENCE_REMOVED] {
+            EventBusHandler
+                .subscribe(
+                    crate::event_bus::EventBusSubscribeInput {
+                        event_type_id: event_type_id.into(),
+                        listener_id: "failing".into(),
+                        priority: 0,
+                    },
+                    &storage,
+                )
+                .await
+                .unwrap();
+        }
+
+        let added = handler
+            .add_ref_with(
+                AddRefInput { source_id: "a".into(), target_id: "b".into(), ref_type: "link".into() },
+                &[&FailingListener],
+                &storage,
+            )
+            .await;
+        assert!(added.is_err());
+        assert!(storage.get("reference", "a:b").await.unwrap().is_none());
+
+        handler
+            .add_ref(
+                AddRefInput { source_id: "a".into(), target_id: "b".into(), ref_type: "link".into() },
+                &storage,
+            )
+            .await
+            .unwrap();
+        let removed = handler
+            .remove_ref_with(
+                RemoveRefInput { source_id: "a".into(), target_id: "b".into() },
+                &[&FailingListener],
+                &storage,
+            )
+            .await;
+        assert!(removed.is_err());
+        assert!(storage.get("reference", "a:b").await.unwrap().is_some());
+        assert!(storage
+            .find("event_history", None)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn plain_add_and_remove_record_no_events() {
+        let storage = InMemoryStorage::new();
+        let handler = ReferenceHandler;
+        handler
+            .add_ref(
+                AddRefInput {
+                    source_id: "a".into(),
+                    target_id: "b".into(),
+                    ref_type: "link".into(),
+                },
+                &storage,
+            )
+            .await
+            .unwrap();
+        handler
+            .remove_ref(
+                RemoveRefInput {
+                    source_id: "a".into(),
+                    target_id: "b".into(),
+                },
+                &storage,
+            )
+            .await
+            .unwrap();
+        assert!(storage
+            .find("event_history", None)
+            .await
+            .unwrap()
+            .is_empty());
+    }
 }
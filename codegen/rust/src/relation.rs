@@ -6,6 +6,7 @@
 use crate::storage::{ConceptStorage, StorageResult};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::collections::{BTreeMap, BTreeSet};
 
 // --- DefineRelation ---
 
@@ -76,6 +77,102 @@ pub enum GetRelatedOutput {
     Ok { node_id: String, related: String },
 }
 
+// ── Ordering ──────────────────────────────────────────────
+
+/// The links of a relation contain a cycle, so there is no valid order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CycleError {
+    /// The nodes on one cycle, in link order; the last links back to the first.
+    pub nodes: Vec<String>,
+}
+
+impl std::fmt::Display for CycleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "relation contains a cycle: {}", self.nodes.join(" -> "))?;
+        if let Some(first) = self.nodes.first() {
+            write!(f, " -> {}", first)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for CycleError {}
+
+/// Order the nodes linked by `relation_id` so every link's source comes
+/// before its target, e.g. prerequisites before the tasks that need them.
+/// Nodes with no ordering between them come out in id order.
+pub async fn topological_order(
+    relation_id: &str,
+    storage: &dyn ConceptStorage,
+) -> StorageResult<Result<Vec<String>, CycleError>> {
+    let links = storage
+        .find("relation_link", Some(&json!({ "relation_id": relation_id })))
+        .await?;
+    Ok(topological_sort(links.iter().map(|link| {
+        (
+            link["source_id"].as_str().unwrap_or(""),
+            link["target_id"].as_str().unwrap_or(""),
+        )
+    })))
+}
+
+/// Kahn's algorithm over `(source, target)` edges.
+pub fn topological_sort<'a>(
+    edges: impl IntoIterator<Item = (&'a str, &'a str)>,
+) -> Result<Vec<String>, CycleError> {
+    let mut successors: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+    let mut predecessors: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+    for (source, target) in edges {
+        successors.entry(source).or_default().insert(target);
+        successors.entry(target).or_default();
+        predecessors.entry(target).or_default().insert(source);
+        predecessors.entry(source).or_default();
+    }
+
+    let mut in_degree: BTreeMap<&str, usize> =
+        predecessors.iter().map(|(node, preds)| (*node, preds.len())).collect();
+    let mut ready: BTreeSet<&str> =
+        in_degree.iter().filter(|(_, d)| **d == 0).map(|(node, _)| *node).collect();
+    let mut order = Vec::with_capacity(in_degree.len());
+
+    while let Some(node) = ready.pop_first() {
+        order.push(node.to_string());
+        in_degree.remove(node);
+        for next in &successors[node] {
+            if let Some(degree) = in_degree.get_mut(next) {
+                *degree -= 1;
+                if *degree == 0 {
+                    ready.insert(next);
+                }
+            }
+        }
+    }
+
+    if in_degree.is_empty() {
+        return Ok(order);
+    }
+
+    // Every node left has a predecessor that is also left, so walking
+    // predecessors from any of them must revisit a node; that loop is a cycle.
+    let mut walk: Vec<&str> = vec![in_degree.keys().next().copied().unwrap_or_default()];
+    loop {
+        let current = walk[walk.len() - 1];
+        let previous = predecessors[current]
+            .iter()
+            .find(|p| in_degree.contains_key(*p))
+            .copied()
+            .unwrap_or(current);
+        if let Some(start) = walk.iter().position(|n| *n == previous) {
+            let mut nodes: Vec<String> = walk[start..].iter().rev().map(|n| n.to_string()).collect();
+            // Start the cycle at its smallest id so the report is stable
+            let min = nodes.iter().enumerate().min_by(|a, b| a.1.cmp(b.1)).map_or(0, |(i, _)| i);
+            nodes.rotate_left(min);
+            return Err(CycleError { nodes });
+        }
+        walk.push(previous);
+    }
+}
+
 pub struct RelationHandler;
 
 impl RelationHandler {
@@ -500,4 +597,64 @@ mod tests {
             }
         }
     }
+
+    // ── topological_order tests ────────────────────────────
+
+    async fn prerequisites(storage: &InMemoryStorage, links: &[(&str, &str)]) -> String {
+        let handler = RelationHandler;
+        let DefineRelationOutput::Ok { relation_id } = handler
+            .define_relation(
+                DefineRelationInput {
+                    name: "prerequisite_of".into(),
+                    source_type: "task".into(),
+                    target_type: "task".into(),
+                    cardinality: "many_to_many".into(),
+                    is_bidirectional: false,
+                },
+                storage,
+            )
+            .await
+            .unwrap();
+        for (source_id, target_id) in links {
+            handler
+                .link(
+                    LinkInput {
+                        relation_id: relation_id.clone(),
+                        source_id: source_id.to_string(),
+                        target_id: target_id.to_string(),
+                    },
+                    storage,
+                )
+                .await
+                .unwrap();
+        }
+        relation_id
+    }
+
+    #[tokio::test]
+    async fn topological_order_puts_prerequisites_first() {
+        let storage = InMemoryStorage::new();
+        let relation_id = prerequisites(
+            &storage,
+            &[("design", "build"), ("build", "test"), ("design", "docs"), ("test", "release"), ("docs", "release")],
+        )
+        .await;
+
+        let order = topological_order(&relation_id, &storage).await.unwrap().unwrap();
+        assert_eq!(order, vec!["design", "build", "docs", "test", "release"]);
+    }
+
+    #[tokio::test]
+    async fn topological_order_reports_cycle_members() {
+        let storage = InMemoryStorage::new();
+        let relation_id = prerequisites(
+            &storage,
+            &[("setup", "b"), ("b", "c"), ("c", "d"), ("d", "b"), ("d", "deploy")],
+        )
+        .await;
+
+        let err = topological_order(&relation_id, &storage).await.unwrap().unwrap_err();
+        assert_eq!(err.nodes, vec!["b", "c", "d"]);
+        assert_eq!(err.to_string(), "relation contains a cycle: b -> c -> d -> b");
+    }
 }
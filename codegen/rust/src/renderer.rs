@@ -2,7 +2,12 @@
 //
 // Manages element rendering with caching and placeholder support.
 // See Architecture doc Sections on rendering pipeline.
+//
+// Parsed content nodes can be rendered to escaped HTML, plain text or a
+// JSON AST through `render`, which dispatches on `RenderFormat`.
 
+use crate::content_parser::{ContentNode, ListItem};
+use crate::escape::escape_html;
 use crate::storage::{ConceptStorage, StorageResult};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
@@ -54,6 +59,205 @@ pub enum MergeCacheabilityOutput {
     Ok { merged_tags: String },
 }
 
+// ── RenderContent ─────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RenderFormat {
+    Html,
+    PlainText,
+    Json,
+}
+
+impl RenderFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RenderFormat::Html => "html",
+            RenderFormat::PlainText => "plain_text",
+            RenderFormat::Json => "json",
+        }
+    }
+}
+
+/// Rendered content: markup or text for HTML and plain text, a structured
+/// value for JSON.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Rendered {
+    String(String),
+    Value(serde_json::Value),
+}
+
+impl Rendered {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Rendered::String(s) => Some(s),
+            Rendered::Value(_) => None,
+        }
+    }
+
+    pub fn into_value(self) -> serde_json::Value {
+        match self {
+            Rendered::String(s) => serde_json::Value::String(s),
+            Rendered::Value(v) => v,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenderContentInput {
+    pub element_id: String,
+    pub nodes: Vec<ContentNode>,
+    pub format: RenderFormat,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "variant")]
+pub enum RenderContentOutput {
+    #[serde(rename = "ok")]
+    Ok {
+        element_id: String,
+        output: serde_json::Value,
+    },
+}
+
+/// Render a content node tree in `format`.
+pub fn render(nodes: &[ContentNode], format: RenderFormat) -> Rendered {
+    match format {
+        RenderFormat::Html => {
+            let mut out = String::new();
+            for node in nodes {
+                html_node(node, &mut out);
+            }
+            Rendered::String(out)
+        }
+        RenderFormat::PlainText => {
+            let mut blocks = Vec::new();
+            for node in nodes {
+                text_node(node, 0, &mut blocks);
+            }
+            Rendered::String(blocks.join("\n\n"))
+        }
+        RenderFormat::Json => Rendered::Value(json!({
+            "type": "document",
+            "children": nodes,
+        })),
+    }
+}
+
+fn html_node(node: &ContentNode, out: &mut String) {
+    match node {
+        ContentNode::Heading {
+            level,
+            text,
+            children,
+        } => {
+            let level = (*level).clamp(1, 6);
+            out.push_str(&format!("<h{}>{}</h{}>", level, escape_html(text), level));
+            for child in children {
+                html_node(child, out);
+            }
+        }
+        ContentNode::Paragraph { text } => {
+            out.push_str(&format!("<p>{}</p>", escape_html(text)));
+        }
+        ContentNode::List { ordered, items } => {
+            let tag = if *ordered { "ol" } else { "ul" };
+            out.push_str(&format!("<{}>", tag));
+            for ListItem { text, children } in items {
+                out.push_str("<li>");
+                out.push_str(&escape_html(text));
+                for child in children {
+                    html_node(child, out);
+                }
+                out.push_str("</li>");
+            }
+            out.push_str(&format!("</{}>", tag));
+        }
+        ContentNode::Code { language, code } => {
+            // Only keep language names that are safe as a class suffix
+            let class = language
+                .as_deref()
+                .filter(|lang| {
+                    !lang.is_empty()
+                        && lang
+                            .chars()
+                            .all(|c| c.is_ascii_alphanumeric() || "+-_#".contains(c))
+                })
+                .map(|lang| format!(" class=\"language-{}\"", lang))
+                .unwrap_or_default();
+            out.push_str(&format!(
+                "<pre><code{}>{}</code></pre>",
+                class,
+                escape_html(code)
+            ));
+        }
+        ContentNode::Table { header, rows } => {
+            let cells = |tag: &str, row: &[String]| {
+                row.iter()
+                    .map(|cell| format!("<{}>{}</{}>", tag, escape_html(cell), tag))
+                    .collect::<String>()
+            };
+            out.push_str(&format!("<table><thead><tr>{}</tr></thead><tbody>", cells("th", header)));
+            for row in rows {
+                out.push_str(&format!("<tr>{}</tr>", cells("td", row)));
+            }
+            out.push_str("</tbody></table>");
+        }
+    }
+}
+
+fn text_node(node: &ContentNode, depth: usize, blocks: &mut Vec<String>) {
+    match node {
+        ContentNode::Heading { text, children, .. } => {
+            blocks.push(text.clone());
+            for child in children {
+                text_node(child, depth, blocks);
+            }
+        }
+        ContentNode::Paragraph { text } => blocks.push(text.clone()),
+        ContentNode::List { ordered, items } => {
+            let mut lines = Vec::new();
+            text_list(*ordered, items, depth, &mut lines);
+            blocks.push(lines.join("\n"));
+        }
+        ContentNode::Code { code, .. } => blocks.push(code.trim_end().to_string()),
+        ContentNode::Table { header, rows } => blocks.push(
+            std::iter::once(header)
+                .chain(rows)
+                .map(|row| row.join(" | "))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        ),
+    }
+}
+
+fn text_list(ordered: bool, items: &[ListItem], depth: usize, lines: &mut Vec<String>) {
+    let indent = "  ".repeat(depth);
+    for (i, item) in items.iter().enumerate() {
+        let marker = if ordered {
+            format!("{}.", i + 1)
+        } else {
+            "-".to_string()
+        };
+        lines.push(format!("{}{} {}", indent, marker, item.text));
+        for child in &item.children {
+            match child {
+                ContentNode::List { ordered, items } => {
+                    text_list(*ordered, items, depth + 1, lines)
+                }
+                other => {
+                    let mut nested = Vec::new();
+                    text_node(other, depth + 1, &mut nested);
+                    let pad = "  ".repeat(depth + 1);
+                    for line in nested.join("\n").lines() {
+                        lines.push(format!("{}{}", pad, line));
+                    }
+                }
+            }
+        }
+    }
+}
+
 // ── Handler ───────────────────────────────────────────────
 
 pub struct RendererHandler;
@@ -150,6 +354,33 @@ impl RendererHandler {
             merged_tags: serde_json::to_string(&merged)?,
         })
     }
+
+    pub async fn render_content(
+        &self,
+        input: RenderContentInput,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<RenderContentOutput> {
+        let output = render(&input.nodes, input.format).into_value();
+
+        // Each format is cached separately
+        storage
+            .put(
+                "render_cache",
+                &format!("{}:{}", input.element_id, input.format.as_str()),
+                json!({
+                    "element_id": input.element_id,
+                    "format": input.format,
+                    "output": output,
+                    "rendered_at": chrono::Utc::now().to_rfc3339(),
+                }),
+            )
+            .await?;
+
+        Ok(RenderContentOutput::Ok {
+            element_id: input.element_id,
+            output,
+        })
+    }
 }
 
 // ── Tests ──────────────────────────────────────────────────
@@ -336,4 +567,109 @@ mod tests {
             }
         }
     }
+
+    // ── render_content tests ───────────────────────────────
+
+    fn sample_tree() -> Vec<ContentNode> {
+        vec![ContentNode::Heading {
+            level: 1,
+            text: "Q&A <draft>".into(),
+            children: vec![
+                ContentNode::Paragraph {
+                    text: "Ask \"anything\".".into(),
+                },
+                ContentNode::List {
+                    ordered: false,
+                    items: vec![
+                        ListItem {
+                            text: "first".into(),
+                            children: vec![ContentNode::List {
+                                ordered: true,
+                                items: vec![ListItem {
+                                    text: "nested".into(),
+                                    children: vec![],
+                                }],
+                            }],
+                        },
+                        ListItem {
+                            text: "second".into(),
+                            children: vec![],
+                        },
+                    ],
+                },
+                ContentNode::Code {
+                    language: Some("rust\" onclick=\"x".into()),
+                    code: "let x = 1 < 2;\n".into(),
+                },
+            ],
+        }]
+    }
+
+    #[tokio::test]
+    async fn render_content_to_all_formats() {
+        let storage = InMemoryStorage::new();
+        let handler = RendererHandler;
+        let nodes = sample_tree();
+
+        let html = render(&nodes, RenderFormat::Html);
+        assert_eq!(
+            html.as_str().unwrap(),
+            "<h1>Q&amp;A &lt;draft&gt;</h1>\
+             <p>Ask &quot;anything&quot;.</p>\
+             <ul><li>first<ol><li>nested</li></ol></li><li>second</li></ul>\
+             <pre><code>let x = 1 &lt; 2;\n</code></pre>"
+        );
+
+        let text = render(&nodes, RenderFormat::PlainText);
+        assert_eq!(
+            text.as_str().unwrap(),
+            "Q&A <draft>\n\nAsk \"anything\".\n\n- first\n  1. nested\n- second\n\nlet x = 1 < 2;"
+        );
+
+        let result = handler
+            .render_content(
+                RenderContentInput {
+                    element_id: "doc1".into(),
+                    nodes: nodes.clone(),
+                    format: RenderFormat::Json,
+                },
+                &storage,
+            )
+            .await
+            .unwrap();
+        let RenderContentOutput::Ok { output, .. } = result;
+        assert_eq!(output["type"], "document");
+        assert_eq!(output["children"][0]["type"], "heading");
+        assert_eq!(
+            output["children"][0]["children"][2]["language"],
+            "rust\" onclick=\"x"
+        );
+        let round_trip: Vec<ContentNode> =
+            serde_json::from_value(output["children"].clone()).unwrap();
+        assert_eq!(round_trip, nodes);
+
+        let cached = storage
+            .get("render_cache", "doc1:json")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(cached["format"], "json");
+    }
+
+    #[test]
+    fn render_tables_to_html_and_text() {
+        let nodes = vec![ContentNode::Table {
+            header: vec!["Name".into(), "Role".into()],
+            rows: vec![vec!["Ada".into(), "<lead>".into()]],
+        }];
+        assert_eq!(
+            render(&nodes, RenderFormat::Html).as_str().unwrap(),
+            "<table><thead><tr><th>Name</th><th>Role</th></tr></thead>\
+             <tbody><tr><td>Ada</td><td>&lt;lead&gt;</td></tr></tbody></table>"
+        );
+        assert_eq!(
+            render(&nodes, RenderFormat::PlainText).as_str().unwrap(),
+            "Name | Role\nAda | <lead>"
+        );
+    }
 }
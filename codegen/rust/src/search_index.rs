@@ -1,11 +1,12 @@
 // SearchIndex Concept Implementation (Rust)
 //
-// Manages search indexes with item indexing and text search.
-// See Architecture doc Sections on search and indexing.
+// Manages search indexes with item indexing and text search. Results are
+// ranked with BM25. See Architecture doc Sections on search and indexing.
 
 use crate::storage::{ConceptStorage, StorageResult};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::collections::{HashMap, HashSet};
 
 // ── CreateIndex ───────────────────────────────────────────
 
@@ -70,6 +71,69 @@ pub enum SearchOutput {
     Ok { index_id: String, results: String },
 }
 
+// ── SearchRanked ──────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum QueryMode {
+    /// Documents must contain every query term.
+    #[serde(rename = "and")]
+    And,
+    /// Documents must contain at least one query term.
+    #[default]
+    #[serde(rename = "or")]
+    Or,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchRankedInput {
+    pub index_id: String,
+    pub query_text: String,
+    pub limit: usize,
+    #[serde(default)]
+    pub mode: QueryMode,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "variant")]
+pub enum SearchRankedOutput {
+    #[serde(rename = "ok")]
+    Ok { index_id: String, results: String },
+}
+
+// ── Suggest ───────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SuggestInput {
+    pub index_id: String,
+    pub partial: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "variant")]
+pub enum SuggestOutput {
+    #[serde(rename = "ok")]
+    Ok { index_id: String, suggestions: String },
+}
+
+// ── Highlight ─────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HighlightInput {
+    pub index_id: String,
+    pub node_id: String,
+    pub query_text: String,
+    pub max_chars: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "variant")]
+pub enum HighlightOutput {
+    #[serde(rename = "ok")]
+    Ok { snippet: String },
+    #[serde(rename = "notfound")]
+    NotFound { message: String },
+}
+
 // ── Reindex ───────────────────────────────────────────────
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -84,6 +148,379 @@ pub enum ReindexOutput {
     Ok { index_id: String, count: u64 },
 }
 
+// ── BM25 ──────────────────────────────────────────────────
+
+pub type DocId = String;
+
+/// Maximum number of type-ahead suggestions.
+const SUGGEST_LIMIT: usize = 10;
+
+/// Term-frequency saturation.
+const BM25_K1: f32 = 1.2;
+/// Document length normalization.
+const BM25_B: f32 = 0.75;
+
+/// Lowercased alphanumeric terms.
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// An indexed term matched by prefix or fuzzy lookup.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TermMatch {
+    pub term: String,
+    /// Edit distance from the looked-up term; 0 for prefix matches.
+    pub distance: usize,
+    pub doc_ids: Vec<DocId>,
+}
+
+/// Levenshtein distance over characters, same as the quality plugin's
+/// near-duplicate check.
+fn levenshtein_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == *cb { 0 } else { 1 };
+            let next = (row[j + 1] + 1).min(row[j] + 1).min(diagonal + cost);
+            diagonal = row[j + 1];
+            row[j + 1] = next;
+        }
+    }
+    row[b.len()]
+}
+
+/// Strings wrapped around matched terms in a highlighted snippet.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HighlightMarkers {
+    pub open: String,
+    pub close: String,
+}
+
+impl Default for HighlightMarkers {
+    fn default() -> Self {
+        Self { open: "<mark>".into(), close: "</mark>".into() }
+    }
+}
+
+/// Char ranges of the words in `chars`, split the same way as `tokenize`.
+fn word_spans(chars: &[char]) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut start = None;
+    for (i, c) in chars.iter().enumerate() {
+        match (c.is_alphanumeric(), start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                spans.push((s, i));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        spans.push((s, chars.len()));
+    }
+    spans
+}
+
+/// A snippet of at most `max_chars` characters of `text` (plus markers and
+/// ellipses) around the window containing the most distinct query terms,
+/// with every whole-word match wrapped in `markers`. Snippets start and end
+/// on word boundaries; without a match the snippet is the start of the text.
+pub fn highlight_text(text: &str, query: &str, max_chars: usize, markers: &HighlightMarkers) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let spans = word_spans(&chars);
+    let terms: HashSet<String> = tokenize(query).into_iter().collect();
+    let matched: Vec<(usize, usize, String)> = spans
+        .iter()
+        .filter_map(|&(s, e)| {
+            let word = chars[s..e].iter().collect::<String>().to_lowercase();
+            terms.contains(&word).then_some((s, e, word))
+        })
+        .collect();
+
+    // The earliest window holding the most distinct terms, then the most matches
+    let mut best: Option<((usize, usize), usize, usize)> = None;
+    for (i, &(first, first_end, _)) in matched.iter().enumerate() {
+        let inside: Vec<_> = matched[i..].iter().take_while(|m| m.1 <= first + max_chars).collect();
+        let distinct = inside.iter().map(|m| &m.2).collect::<HashSet<_>>().len();
+        let last_end = inside.last().map_or(first_end, |m| m.1);
+        let key = (distinct, inside.len());
+        if best.is_none_or(|(top, _, _)| key > top) {
+            best = Some((key, first, last_end));
+        }
+    }
+
+    let (start, end) = if chars.len() <= max_chars {
+        (0, chars.len())
+    } else {
+        let (first, last_end) = best.map_or((0, 0), |(_, first, last_end)| (first, last_end));
+        // Center the matched window in the remaining budget
+        let slack = max_chars.saturating_sub(last_end - first);
+        let mut start = first.saturating_sub(slack / 2).min(chars.len() - max_chars);
+        if let Some(&(word_start, _)) = spans.iter().find(|&&(s, e)| s < start && start < e) {
+            start = spans.iter().map(|&(s, _)| s).find(|&s| s > word_start).unwrap_or(start).min(first);
+        }
+        let limit = (start + max_chars).min(chars.len());
+        let end = spans
+            .iter()
+            .rev()
+            .find(|&&(s, e)| s >= start && e <= limit)
+            .map_or(limit, |&(_, e)| e);
+        (start, end)
+    };
+
+    let mut snippet = String::new();
+    if start > 0 {
+        snippet.push('…');
+    }
+    let mut pos = start;
+    for (s, e, _) in matched.iter().filter(|m| m.0 >= start && m.1 <= end) {
+        snippet.extend(&chars[pos..*s]);
+        snippet.push_str(&markers.open);
+        snippet.extend(&chars[*s..*e]);
+        snippet.push_str(&markers.close);
+        pos = *e;
+    }
+    snippet.extend(&chars[pos..end]);
+    if end < chars.len() {
+        snippet.push('…');
+    }
+    snippet
+}
+
+/// In-memory BM25 index over a set of documents.
+#[derive(Debug, Clone, Default)]
+pub struct Bm25Index {
+    /// Term frequencies and length per document.
+    docs: HashMap<DocId, (HashMap<String, u32>, usize)>,
+    /// Number of documents containing each term.
+    doc_freq: HashMap<String, usize>,
+    total_len: usize,
+    /// Original text per document, for highlighting.
+    texts: HashMap<DocId, String>,
+}
+
+impl Bm25Index {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add or replace a document.
+    pub fn add(&mut self, id: impl Into<DocId>, text: &str) {
+        let id = id.into();
+        self.remove(&id);
+
+        let terms = tokenize(text);
+        let mut freqs: HashMap<String, u32> = HashMap::new();
+        for term in &terms {
+            *freqs.entry(term.clone()).or_default() += 1;
+        }
+        for term in freqs.keys() {
+            *self.doc_freq.entry(term.clone()).or_default() += 1;
+        }
+        self.total_len += terms.len();
+        self.texts.insert(id.clone(), text.to_string());
+        self.docs.insert(id, (freqs, terms.len()));
+    }
+
+    pub fn remove(&mut self, id: &str) {
+        let Some((freqs, len)) = self.docs.remove(id) else { return };
+        self.texts.remove(id);
+        for term in freqs.keys() {
+            if let Some(df) = self.doc_freq.get_mut(term) {
+                *df -= 1;
+                if *df == 0 {
+                    self.doc_freq.remove(term);
+                }
+            }
+        }
+        self.total_len -= len;
+    }
+
+    pub fn len(&self) -> usize {
+        self.docs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.docs.is_empty()
+    }
+
+    /// A `<mark>`-highlighted snippet of `doc_id` for `query`; empty when the
+    /// document is not indexed. See `highlight_text`.
+    pub fn highlight(&self, doc_id: &str, query: &str, max_chars: usize) -> String {
+        self.highlight_with(doc_id, query, max_chars, &HighlightMarkers::default())
+    }
+
+    pub fn highlight_with(&self, doc_id: &str, query: &str, max_chars: usize, markers: &HighlightMarkers) -> String {
+        self.texts
+            .get(doc_id)
+            .map(|text| highlight_text(text, query, max_chars, markers))
+            .unwrap_or_default()
+    }
+
+    /// Sorted ids of the documents containing `term`.
+    fn docs_with(&self, term: &str) -> Vec<DocId> {
+        let mut ids: Vec<DocId> = self
+            .docs
+            .iter()
+            .filter(|(_, (freqs, _))| freqs.contains_key(term))
+            .map(|(id, _)| id.clone())
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    /// Order matches by distance, then by how many documents use the term.
+    fn ranked(&self, mut matches: Vec<(String, usize)>) -> Vec<TermMatch> {
+        matches.sort_by(|(a, da), (b, db)| {
+            da.cmp(db)
+                .then_with(|| self.doc_freq[b].cmp(&self.doc_freq[a]))
+                .then_with(|| a.cmp(b))
+        });
+        matches
+            .into_iter()
+            .map(|(term, distance)| TermMatch { doc_ids: self.docs_with(&term), term, distance })
+            .collect()
+    }
+
+    /// Indexed terms starting with `prefix`, most widely used first.
+    pub fn search_prefix(&self, prefix: &str) -> Vec<TermMatch> {
+        let prefix = prefix.trim().to_lowercase();
+        if prefix.is_empty() {
+            return vec![];
+        }
+        let matches = self
+            .doc_freq
+            .keys()
+            .filter(|term| term.starts_with(&prefix))
+            .map(|term| (term.clone(), 0))
+            .collect();
+        self.ranked(matches)
+    }
+
+    /// Indexed terms within `max_distance` edits of `term`, closest first.
+    pub fn search_fuzzy(&self, term: &str, max_distance: usize) -> Vec<TermMatch> {
+        let term = term.trim().to_lowercase();
+        if term.is_empty() {
+            return vec![];
+        }
+        let len = term.chars().count();
+        let matches = self
+            .doc_freq
+            .keys()
+            // Lengths differing by more than max_distance cannot be close enough
+            .filter(|candidate| candidate.chars().count().abs_diff(len) <= max_distance)
+            .filter_map(|candidate| {
+                let distance = levenshtein_distance(&term, candidate);
+                (distance <= max_distance).then(|| (candidate.clone(), distance))
+            })
+            .collect();
+        self.ranked(matches)
+    }
+
+    /// Type-ahead completions for the last word of `partial`: prefix matches
+    /// first, then typo corrections (one edit, two for words of five or more
+    /// characters), each completing the text typed so far.
+    pub fn suggest(&self, partial: &str) -> Vec<String> {
+        let trimmed = partial.trim_end();
+        let split = trimmed.rfind(char::is_whitespace).map_or(0, |i| i + 1);
+        let (typed, last) = trimmed.split_at(split);
+        if last.is_empty() {
+            return vec![];
+        }
+
+        let max_distance = if last.chars().count() >= 5 { 2 } else { 1 };
+        let mut seen = HashSet::new();
+        self.search_prefix(last)
+            .into_iter()
+            .chain(self.search_fuzzy(last, max_distance))
+            .filter(|m| seen.insert(m.term.clone()))
+            .take(SUGGEST_LIMIT)
+            .map(|m| format!("{}{}", typed, m.term))
+            .collect()
+    }
+
+    /// Top `limit` documents matching any query term, best first.
+    pub fn search(&self, query: &str, limit: usize) -> Vec<(DocId, f32)> {
+        self.search_with_mode(query, limit, QueryMode::Or)
+    }
+
+    /// Top `limit` documents for `query` under `mode`, by descending BM25
+    /// score; equal scores are ordered by document id.
+    pub fn search_with_mode(&self, query: &str, limit: usize, mode: QueryMode) -> Vec<(DocId, f32)> {
+        let terms: Vec<String> = tokenize(query)
+            .into_iter()
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        if terms.is_empty() || self.docs.is_empty() {
+            return vec![];
+        }
+
+        let n = self.docs.len() as f32;
+        let avg_len = (self.total_len as f32 / n).max(1.0);
+        let idf: HashMap<&str, f32> = terms
+            .iter()
+            .map(|term| {
+                let df = self.doc_freq.get(term).copied().unwrap_or(0) as f32;
+                (term.as_str(), (1.0 + (n - df + 0.5) / (df + 0.5)).ln())
+            })
+            .collect();
+
+        let mut scored: Vec<(DocId, f32)> = self
+            .docs
+            .iter()
+            .filter_map(|(id, (freqs, len))| {
+                let mut matched = 0;
+                let mut score = 0.0;
+                for term in &terms {
+                    let Some(&tf) = freqs.get(term) else { continue };
+                    matched += 1;
+                    let tf = tf as f32;
+                    let norm = BM25_K1 * (1.0 - BM25_B + BM25_B * *len as f32 / avg_len);
+                    score += idf[term.as_str()] * tf * (BM25_K1 + 1.0) / (tf + norm);
+                }
+                let keep = match mode {
+                    QueryMode::And => matched == terms.len(),
+                    QueryMode::Or => matched > 0,
+                };
+                keep.then(|| (id.clone(), score))
+            })
+            .collect();
+
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        scored.truncate(limit);
+        scored
+    }
+}
+
+/// Build a BM25 index over every item stored for `index_id`, returning it with
+/// the stored items by node id.
+async fn load_bm25(
+    index_id: &str,
+    storage: &dyn ConceptStorage,
+) -> StorageResult<(Bm25Index, HashMap<String, serde_json::Value>)> {
+    let all_items = storage
+        .find("indexed_item", Some(&json!({ "index_id": index_id })))
+        .await?;
+
+    let mut index = Bm25Index::new();
+    let mut items = HashMap::new();
+    for item in all_items {
+        let node_id = item["node_id"].as_str().unwrap_or("").to_string();
+        index.add(node_id.clone(), item["content"].as_str().unwrap_or(""));
+        items.insert(node_id, item);
+    }
+    Ok((index, items))
+}
+
 // ── Handler ───────────────────────────────────────────────
 
 pub struct SearchIndexHandler;
@@ -177,18 +614,13 @@ impl SearchIndexHandler {
         input: SearchInput,
         storage: &dyn ConceptStorage,
     ) -> StorageResult<SearchOutput> {
-        let all_items = storage
-            .find(
-                "indexed_item",
-                Some(&json!({ "index_id": input.index_id })),
-            )
-            .await?;
+        let (bm25, items) = load_bm25(&input.index_id, storage).await?;
 
         let query_lower = input.query_text.to_lowercase();
         let query_tokens: Vec<&str> = query_lower.split_whitespace().collect();
 
-        let matching: Vec<serde_json::Value> = all_items
-            .into_iter()
+        let mut matching: Vec<&serde_json::Value> = items
+            .values()
             .filter(|item| {
                 let content = item["content"]
                     .as_str()
@@ -196,6 +628,20 @@ impl SearchIndexHandler {
                     .to_lowercase();
                 query_tokens.iter().any(|token| content.contains(token))
             })
+            .collect();
+
+        // Substring matches stay in the results; BM25 decides their order
+        let scores: HashMap<DocId, f32> = bm25.search(&input.query_text, usize::MAX).into_iter().collect();
+        let score = |item: &serde_json::Value| {
+            scores.get(item["node_id"].as_str().unwrap_or("")).copied().unwrap_or(0.0)
+        };
+        matching.sort_by(|a, b| {
+            score(b)
+                .total_cmp(&score(a))
+                .then_with(|| a["node_id"].as_str().cmp(&b["node_id"].as_str()))
+        });
+        let matching: Vec<serde_json::Value> = matching
+            .into_iter()
             .map(|item| {
                 json!({
                     "node_id": item["node_id"],
@@ -210,6 +656,73 @@ impl SearchIndexHandler {
         })
     }
 
+    /// BM25-ranked search returning at most `limit` results with their scores.
+    pub async fn search_ranked(
+        &self,
+        input: SearchRankedInput,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<SearchRankedOutput> {
+        let (bm25, items) = load_bm25(&input.index_id, storage).await?;
+        let results: Vec<serde_json::Value> = bm25
+            .search_with_mode(&input.query_text, input.limit, input.mode)
+            .into_iter()
+            .map(|(node_id, score)| {
+                json!({
+                    "node_id": node_id,
+                    "content": items[&node_id]["content"],
+                    "score": score,
+                })
+            })
+            .collect();
+
+        Ok(SearchRankedOutput::Ok {
+            index_id: input.index_id,
+            results: serde_json::to_string(&results)?,
+        })
+    }
+
+    pub async fn suggest(
+        &self,
+        input: SuggestInput,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<SuggestOutput> {
+        let (bm25, _) = load_bm25(&input.index_id, storage).await?;
+        Ok(SuggestOutput::Ok {
+            index_id: input.index_id,
+            suggestions: serde_json::to_string(&bm25.suggest(&input.partial))?,
+        })
+    }
+
+    pub async fn highlight(
+        &self,
+        input: HighlightInput,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<HighlightOutput> {
+        let item_key = format!("{}:{}", input.index_id, input.node_id);
+        let Some(item) = storage.get("indexed_item", &item_key).await? else {
+            return Ok(HighlightOutput::NotFound {
+                message: format!(
+                    "Item '{}' not found in index '{}'",
+                    input.node_id, input.index_id
+                ),
+            });
+        };
+
+        let config = storage.get("search_index", &input.index_id).await?;
+        let markers = config
+            .and_then(|index| serde_json::from_value(index["config"]["highlight"].clone()).ok())
+            .unwrap_or_default();
+
+        Ok(HighlightOutput::Ok {
+            snippet: highlight_text(
+                item["content"].as_str().unwrap_or(""),
+                &input.query_text,
+                input.max_chars,
+                &markers,
+            ),
+        })
+    }
+
     pub async fn reindex(
         &self,
         input: ReindexInput,
@@ -531,6 +1044,123 @@ mod tests {
         }
     }
 
+    // ── BM25 tests ─────────────────────────────────────────
+
+    fn corpus() -> Bm25Index {
+        let mut index = Bm25Index::new();
+        index.add("d1", "Quantum error correction keeps quantum data stable");
+        index.add("d2", "Data pipelines move data between data stores");
+        index.add("d3", "Data warehouses store analytical data");
+        index.add("d4", "Data lakes hold raw data");
+        index
+    }
+
+    #[test]
+    fn bm25_ranks_rare_term_occurrences_above_common_terms() {
+        let results = corpus().search("quantum data", 10);
+
+        let ids: Vec<&str> = results.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(ids[0], "d1");
+        assert_eq!(ids.len(), 4);
+        assert!(results.windows(2).all(|w| w[0].1 >= w[1].1));
+        assert_eq!(corpus().search("quantum data", 2).len(), 2);
+    }
+
+    #[test]
+    fn bm25_and_mode_requires_every_term() {
+        let index = corpus();
+        let and: Vec<DocId> = index
+            .search_with_mode("data stores", 10, QueryMode::And)
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        assert_eq!(and, vec!["d2"]);
+        assert_eq!(index.search_with_mode("data stores", 10, QueryMode::Or).len(), 4);
+        assert!(index.search("", 10).is_empty());
+    }
+
+    #[test]
+    fn prefix_search_finds_terms_and_documents() {
+        let matches = corpus().search_prefix("St");
+        let terms: Vec<&str> = matches.iter().map(|m| m.term.as_str()).collect();
+        assert_eq!(terms, vec!["stable", "store", "stores"]);
+        assert_eq!(matches[1].doc_ids, vec!["d3"]);
+        assert!(corpus().search_prefix("xyz").is_empty());
+    }
+
+    #[test]
+    fn fuzzy_search_tolerates_a_single_typo() {
+        let index = corpus();
+        let matches = index.search_fuzzy("quantun", 1);
+        assert_eq!(matches.len(), 1);
+        assert_eq!((matches[0].term.as_str(), matches[0].distance), ("quantum", 1));
+        assert_eq!(matches[0].doc_ids, vec!["d1"]);
+        assert!(index.search_fuzzy("quantun", 0).is_empty());
+
+        assert_eq!(index.suggest("raw da"), vec!["raw data"]);
+        assert_eq!(index.suggest("lakse")[0], "lakes");
+    }
+
+    #[test]
+    fn highlight_centers_snippet_on_matches() {
+        let mut index = Bm25Index::new();
+        index.add(
+            "doc",
+            "Rust was first announced in 2010. Its ownership model and borrow checker \
+             guarantee memory safety without a garbage collector, which is why Rust \
+             is popular for systems programming.",
+        );
+
+        let snippet = index.highlight("doc", "borrow checker", 60);
+        assert!(snippet.contains("<mark>borrow</mark> <mark>checker</mark>"), "{}", snippet);
+        assert!(snippet.starts_with('…') && snippet.ends_with('…'), "{}", snippet);
+        assert!(snippet.contains("ownership model"), "{}", snippet);
+        let plain = snippet.replace("<mark>", "").replace("</mark>", "");
+        assert!(plain.trim_matches('…').chars().count() <= 60);
+        // Snippets cut on word boundaries
+        assert!(!plain.contains("…wnership") && !plain.contains("collecto…"));
+
+        let markers = HighlightMarkers { open: "[".into(), close: "]".into() };
+        let both = index.highlight_with("doc", "rust", 200, &markers);
+        assert_eq!(both.matches("[Rust]").count(), 2);
+        assert!(!both.contains('…'));
+
+        assert_eq!(index.highlight("doc", "python", 20), "Rust was first…");
+        assert_eq!(index.highlight("missing", "rust", 20), "");
+    }
+
+    #[tokio::test]
+    async fn search_ranked_returns_scored_results() {
+        let storage = InMemoryStorage::new();
+        let handler = SearchIndexHandler;
+        for (node_id, content) in [("d1", "rust rust borrow checker"), ("d2", "rust and go"), ("d3", "go channels")] {
+            handler
+                .index_item(
+                    IndexItemInput { index_id: "idx1".into(), node_id: node_id.into(), content: content.into() },
+                    &storage,
+                )
+                .await
+                .unwrap();
+        }
+
+        let result = handler
+            .search_ranked(
+                SearchRankedInput { index_id: "idx1".into(), query_text: "rust".into(), limit: 5, mode: QueryMode::Or },
+                &storage,
+            )
+            .await
+            .unwrap();
+
+        match result {
+            SearchRankedOutput::Ok { results, .. } => {
+                let parsed: Vec<serde_json::Value> = serde_json::from_str(&results).unwrap();
+                let ids: Vec<&str> = parsed.iter().map(|r| r["node_id"].as_str().unwrap()).collect();
+                assert_eq!(ids, vec!["d1", "d2"]);
+                assert!(parsed[0]["score"].as_f64().unwrap() > parsed[1]["score"].as_f64().unwrap());
+            }
+        }
+    }
+
     // ── reindex tests ──────────────────────────────────────
 
     #[tokio::test]
@@ -2,10 +2,17 @@
 //
 // Session lifecycle management — create, validate, refresh, destroy
 // individual sessions and bulk destroy all sessions for a user.
+//
+// Sessions expire at an absolute deadline set on creation and after an
+// idle timeout; refreshing a session counts as activity and slides the idle
+// window forward, never past the absolute deadline. Revoked and expired
+// sessions are kept for auditing but no longer validate.
 
 use crate::storage::{ConceptStorage, StorageResult};
+use chrono::{DateTime, TimeDelta, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::time::Duration;
 
 // --- Create ---
 
@@ -90,6 +97,129 @@ pub enum DestroyAllOutput {
     Ok { user_id: String, count: u64 },
 }
 
+// --- Revoke ---
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RevokeInput {
+    pub session_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "variant")]
+pub enum RevokeOutput {
+    #[serde(rename = "ok")]
+    Ok { session_id: String },
+    #[serde(rename = "notfound")]
+    NotFound { message: String },
+}
+
+// --- RevokeAll ---
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RevokeAllInput {
+    pub user_id: String,
+    /// A session to keep, typically the one asking to log out elsewhere.
+    pub except_session_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "variant")]
+pub enum RevokeAllOutput {
+    #[serde(rename = "ok")]
+    Ok { user_id: String, count: u64 },
+}
+
+// --- ActiveSessions ---
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActiveSessionsInput {
+    pub user_id: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionInfo {
+    pub session_id: String,
+    pub device_info: String,
+    pub created_at: String,
+    pub last_seen_at: String,
+    pub expires_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "variant")]
+pub enum ActiveSessionsOutput {
+    #[serde(rename = "ok")]
+    Ok {
+        user_id: String,
+        sessions: Vec<SessionInfo>,
+    },
+}
+
+// --- Expiry ---
+
+/// Lifetime limits, stored on each session when it is created.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SessionPolicy {
+    /// Hard limit from creation, regardless of activity.
+    pub absolute_timeout: Duration,
+    /// Inactivity allowed before the session lapses.
+    pub idle_timeout: Duration,
+}
+
+impl Default for SessionPolicy {
+    fn default() -> Self {
+        Self {
+            absolute_timeout: Duration::from_secs(30 * 24 * 60 * 60),
+            idle_timeout: Duration::from_secs(2 * 60 * 60),
+        }
+    }
+}
+
+fn timestamp(session: &serde_json::Value, key: &str) -> Option<DateTime<Utc>> {
+    session[key]
+        .as_str()
+        .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// Whether `session` may still be used at `now`. Sessions created before
+/// expiry was tracked have no deadlines and only lapse when flagged.
+fn is_live(session: &serde_json::Value, now: DateTime<Utc>) -> bool {
+    if !session["active"].as_bool().unwrap_or(false)
+        || session["expired"].as_bool().unwrap_or(false)
+    {
+        return false;
+    }
+    if timestamp(session, "expires_at").is_some_and(|deadline| now >= deadline) {
+        return false;
+    }
+    match (
+        timestamp(session, "last_seen_at"),
+        session["idle_timeout_secs"].as_u64(),
+    ) {
+        // An idle window too large to represent never lapses.
+        (Some(last_seen), Some(idle)) => i64::try_from(idle)
+            .ok()
+            .and_then(TimeDelta::try_seconds)
+            .and_then(|window| last_seen.checked_add_signed(window))
+            .is_none_or(|deadline| now < deadline),
+        _ => true,
+    }
+}
+
+/// Flag a lapsed session as expired and drop it from the user's active set.
+async fn mark_expired(
+    session_id: &str,
+    mut session: serde_json::Value,
+    storage: &dyn ConceptStorage,
+) -> StorageResult<()> {
+    if !session["expired"].as_bool().unwrap_or(false) {
+        session["expired"] = json!(true);
+        storage.put("session", session_id, session).await?;
+    }
+    storage.del("active_session", session_id).await
+}
+
 pub struct SessionHandler;
 
 impl SessionHandler {
@@ -97,9 +227,24 @@ impl SessionHandler {
         &self,
         input: CreateInput,
         storage: &dyn ConceptStorage,
+    ) -> StorageResult<CreateOutput> {
+        self.create_at(input, SessionPolicy::default(), Utc::now(), storage)
+            .await
+    }
+
+    pub async fn create_at(
+        &self,
+        input: CreateInput,
+        policy: SessionPolicy,
+        now: DateTime<Utc>,
+        storage: &dyn ConceptStorage,
     ) -> StorageResult<CreateOutput> {
         let session_id = format!("sess_{}", rand::random::<u32>());
-        let now = chrono::Utc::now().to_rfc3339();
+        // A timeout past chrono's range means no absolute deadline.
+        let expires_at = TimeDelta::from_std(policy.absolute_timeout)
+            .ok()
+            .and_then(|timeout| now.checked_add_signed(timeout));
+        let now = now.to_rfc3339();
 
         storage
             .put(
@@ -111,6 +256,9 @@ impl SessionHandler {
                     "device_info": input.device_info,
                     "created_at": now,
                     "refreshed_at": now,
+                    "last_seen_at": now,
+                    "expires_at": expires_at.map(|deadline| deadline.to_rfc3339()),
+                    "idle_timeout_secs": policy.idle_timeout.as_secs(),
                     "active": true,
                     "expired": false,
                 }),
@@ -136,6 +284,17 @@ impl SessionHandler {
         &self,
         input: ValidateInput,
         storage: &dyn ConceptStorage,
+    ) -> StorageResult<ValidateOutput> {
+        self.validate_at(input, Utc::now(), storage).await
+    }
+
+    /// Check a session at `now`. A session found past its absolute or idle
+    /// deadline is flagged expired and reported invalid.
+    pub async fn validate_at(
+        &self,
+        input: ValidateInput,
+        now: DateTime<Utc>,
+        storage: &dyn ConceptStorage,
     ) -> StorageResult<ValidateOutput> {
         let existing = storage.get("session", &input.session_id).await?;
         match existing {
@@ -148,19 +307,16 @@ impl SessionHandler {
                     .and_then(|v| v.as_str())
                     .unwrap_or("")
                     .to_string();
-                let active = session
-                    .get("active")
-                    .and_then(|v| v.as_bool())
-                    .unwrap_or(false);
-                let expired = session
-                    .get("expired")
-                    .and_then(|v| v.as_bool())
-                    .unwrap_or(false);
+                let valid = is_live(&session, now);
+                let active = session["active"].as_bool().unwrap_or(false);
+                if !valid && active {
+                    mark_expired(&input.session_id, session, storage).await?;
+                }
 
                 Ok(ValidateOutput::Ok {
                     session_id: input.session_id,
                     user_id,
-                    valid: active && !expired,
+                    valid,
                 })
             }
         }
@@ -170,6 +326,17 @@ impl SessionHandler {
         &self,
         input: RefreshInput,
         storage: &dyn ConceptStorage,
+    ) -> StorageResult<RefreshOutput> {
+        self.refresh_at(input, Utc::now(), storage).await
+    }
+
+    /// Record activity at `now`, sliding the idle deadline forward. The
+    /// absolute deadline does not move.
+    pub async fn refresh_at(
+        &self,
+        input: RefreshInput,
+        now: DateTime<Utc>,
+        storage: &dyn ConceptStorage,
     ) -> StorageResult<RefreshOutput> {
         let existing = storage.get("session", &input.session_id).await?;
         match existing {
@@ -177,17 +344,17 @@ impl SessionHandler {
                 message: format!("session '{}' not found", input.session_id),
             }),
             Some(mut session) => {
-                let expired = session
-                    .get("expired")
-                    .and_then(|v| v.as_bool())
-                    .unwrap_or(false);
-                if expired {
+                if !is_live(&session, now) {
+                    if session["active"].as_bool().unwrap_or(false) {
+                        mark_expired(&input.session_id, session, storage).await?;
+                    }
                     return Ok(RefreshOutput::Expired {
                         session_id: input.session_id,
                     });
                 }
 
-                session["refreshed_at"] = json!(chrono::Utc::now().to_rfc3339());
+                session["refreshed_at"] = json!(now.to_rfc3339());
+                session["last_seen_at"] = json!(now.to_rfc3339());
                 storage
                     .put("session", &input.session_id, session)
                     .await?;
@@ -246,6 +413,120 @@ impl SessionHandler {
             count,
         })
     }
+
+    /// End a session without deleting its record.
+    pub async fn revoke(
+        &self,
+        input: RevokeInput,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<RevokeOutput> {
+        let existing = storage.get("session", &input.session_id).await?;
+        match existing {
+            None => Ok(RevokeOutput::NotFound {
+                message: format!("session '{}' not found", input.session_id),
+            }),
+            Some(mut session) => {
+                session["active"] = json!(false);
+                session["revoked_at"] = json!(Utc::now().to_rfc3339());
+                storage.put("session", &input.session_id, session).await?;
+                storage.del("active_session", &input.session_id).await?;
+                Ok(RevokeOutput::Ok {
+                    session_id: input.session_id,
+                })
+            }
+        }
+    }
+
+    /// Revoke every session of a user, optionally keeping one ("log out
+    /// everywhere else").
+    pub async fn revoke_all(
+        &self,
+        input: RevokeAllInput,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<RevokeAllOutput> {
+        let user_sessions = storage
+            .find("active_session", Some(&json!({ "user_id": input.user_id })))
+            .await?;
+
+        let mut count = 0;
+        for entry in &user_sessions {
+            let Some(sid) = entry["session_id"].as_str() else {
+                continue;
+            };
+            if input.except_session_id.as_deref() == Some(sid) {
+                continue;
+            }
+            self.revoke(
+                RevokeInput {
+                    session_id: sid.to_string(),
+                },
+                storage,
+            )
+            .await?;
+            count += 1;
+        }
+
+        Ok(RevokeAllOutput::Ok {
+            user_id: input.user_id,
+            count,
+        })
+    }
+
+    pub async fn active_sessions(
+        &self,
+        input: ActiveSessionsInput,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<ActiveSessionsOutput> {
+        self.active_sessions_at(input, Utc::now(), storage).await
+    }
+
+    /// The user's live sessions at `now`, most recently seen first.
+    pub async fn active_sessions_at(
+        &self,
+        input: ActiveSessionsInput,
+        now: DateTime<Utc>,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<ActiveSessionsOutput> {
+        let user_sessions = storage
+            .find("active_session", Some(&json!({ "user_id": input.user_id })))
+            .await?;
+
+        let mut sessions = Vec::new();
+        for entry in &user_sessions {
+            let Some(sid) = entry["session_id"].as_str() else {
+                continue;
+            };
+            let Some(session) = storage.get("session", sid).await? else {
+                continue;
+            };
+            if !is_live(&session, now) {
+                continue;
+            }
+            let text = |key: &str| session[key].as_str().unwrap_or("").to_string();
+            let created_at = text("created_at");
+            let last_seen_at = match text("last_seen_at") {
+                seen if seen.is_empty() => text("refreshed_at"),
+                seen => seen,
+            };
+            sessions.push(SessionInfo {
+                session_id: sid.to_string(),
+                device_info: text("device_info"),
+                created_at,
+                last_seen_at,
+                expires_at: session["expires_at"].as_str().map(str::to_string),
+            });
+        }
+        sessions.sort_by(|a, b| {
+            b.last_seen_at
+                .cmp(&a.last_seen_at)
+                .then_with(|| a.session_id.cmp(&b.session_id))
+        });
+
+        Ok(ActiveSessionsOutput::Ok {
+            user_id: input.user_id,
+            sessions,
+        })
+    }
 }
 
 // ── Tests ──────────────────────────────────────────────────
@@ -543,4 +824,215 @@ mod tests {
             }
         }
     }
+
+    // ── expiry and revocation tests ────────────────────────
+
+    fn policy() -> SessionPolicy {
+        SessionPolicy {
+            absolute_timeout: Duration::from_secs(8 * 60 * 60),
+            idle_timeout: Duration::from_secs(30 * 60),
+        }
+    }
+
+    fn at(minutes: i64) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2024-05-01T09:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+            + chrono::Duration::minutes(minutes)
+    }
+
+    async fn open(
+        handler: &SessionHandler,
+        storage: &InMemoryStorage,
+        user: &str,
+        device: &str,
+        minute: i64,
+    ) -> String {
+        let CreateOutput::Ok { session_id } = handler
+            .create_at(
+                CreateInput {
+                    user_id: user.into(),
+                    device_info: device.into(),
+                },
+                policy(),
+                at(minute),
+                storage,
+            )
+            .await
+            .unwrap();
+        session_id
+    }
+
+    async fn is_valid(
+        handler: &SessionHandler,
+        storage: &InMemoryStorage,
+        session_id: &str,
+        minute: i64,
+    ) -> bool {
+        match handler
+            .validate_at(
+                ValidateInput {
+                    session_id: session_id.into(),
+                },
+                at(minute),
+                storage,
+            )
+            .await
+            .unwrap()
+        {
+            ValidateOutput::Ok { valid, .. } => valid,
+            ValidateOutput::NotFound { .. } => panic!("session missing"),
+        }
+    }
+
+    #[tokio::test]
+    async fn idle_session_expires() {
+        let storage = InMemoryStorage::new();
+        let handler = SessionHandler;
+        let sid = open(&handler, &storage, "u1", "Firefox/Linux", 0).await;
+
+        assert!(is_valid(&handler, &storage, &sid, 29).await);
+        assert!(!is_valid(&handler, &storage, &sid, 30).await);
+
+        let record = storage.get("session", &sid).await.unwrap().unwrap();
+        assert_eq!(record["expired"], json!(true));
+        assert!(storage.get("active_session", &sid).await.unwrap().is_none());
+
+        let refreshed = handler
+            .refresh_at(
+                RefreshInput {
+                    session_id: sid.clone(),
+                },
+                at(31),
+                &storage,
+            )
+            .await
+            .unwrap();
+        assert!(matches!(refreshed, RefreshOutput::Expired { .. }));
+    }
+
+    #[tokio::test]
+    async fn refresh_slides_idle_window_up_to_absolute_deadline() {
+        let storage = InMemoryStorage::new();
+        let handler = SessionHandler;
+        let sid = open(&handler, &storage, "u1", "Safari/iOS", 0).await;
+
+        // Activity every 20 minutes keeps the session alive past 30 minutes
+        for minute in [20, 40, 60] {
+            let result = handler
+                .refresh_at(
+                    RefreshInput {
+                        session_id: sid.clone(),
+                    },
+                    at(minute),
+                    &storage,
+                )
+                .await
+                .unwrap();
+            assert!(matches!(result, RefreshOutput::Ok { .. }));
+        }
+        assert!(is_valid(&handler, &storage, &sid, 85).await);
+        assert!(!is_valid(&handler, &storage, &sid, 91).await);
+
+        // Regular activity cannot outlive the absolute timeout
+        let sid = open(&handler, &storage, "u1", "Safari/iOS", 0).await;
+        let mut minute = 0;
+        while minute < 8 * 60 - 20 {
+            minute += 20;
+            handler
+                .refresh_at(
+                    RefreshInput {
+                        session_id: sid.clone(),
+                    },
+                    at(minute),
+                    &storage,
+                )
+                .await
+                .unwrap();
+        }
+        assert!(is_valid(&handler, &storage, &sid, 8 * 60 - 1).await);
+        assert!(!is_valid(&handler, &storage, &sid, 8 * 60).await);
+    }
+
+    #[tokio::test]
+    async fn unbounded_timeouts_never_expire() {
+        let storage = InMemoryStorage::new();
+        let handler = SessionHandler;
+        let policy = SessionPolicy {
+            absolute_timeout: Duration::from_secs(u64::MAX),
+            idle_timeout: Duration::from_secs(u64::MAX),
+        };
+        let CreateOutput::Ok { session_id } = handler
+            .create_at(
+                CreateInput {
+                    user_id: "u1".into(),
+                    device_info: "Firefox/Linux".into(),
+                },
+                policy,
+                at(0),
+                &storage,
+            )
+            .await
+            .unwrap();
+
+        let record = storage.get("session", &session_id).await.unwrap().unwrap();
+        assert!(record["expires_at"].is_null());
+        assert!(is_valid(&handler, &storage, &session_id, 10 * 365 * 24 * 60).await);
+    }
+
+    #[tokio::test]
+    async fn revoke_all_invalidates_other_sessions() {
+        let storage = InMemoryStorage::new();
+        let handler = SessionHandler;
+        let laptop = open(&handler, &storage, "u1", "Chrome/macOS", 0).await;
+        let phone = open(&handler, &storage, "u1", "Safari/iOS", 5).await;
+        let tablet = open(&handler, &storage, "u1", "Chrome/Android", 10).await;
+        let other_user = open(&handler, &storage, "u2", "Edge/Windows", 0).await;
+
+        let ActiveSessionsOutput::Ok { sessions, .. } = handler
+            .active_sessions_at(
+                ActiveSessionsInput {
+                    user_id: "u1".into(),
+                },
+                at(12),
+                &storage,
+            )
+            .await
+            .unwrap();
+        let devices: Vec<&str> = sessions.iter().map(|s| s.device_info.as_str()).collect();
+        assert_eq!(
+            devices,
+            vec!["Chrome/Android", "Safari/iOS", "Chrome/macOS"]
+        );
+
+        let result = handler
+            .revoke_all(
+                RevokeAllInput {
+                    user_id: "u1".into(),
+                    except_session_id: Some(laptop.clone()),
+                },
+                &storage,
+            )
+            .await
+            .unwrap();
+        assert!(matches!(result, RevokeAllOutput::Ok { count: 2, .. }));
+
+        assert!(is_valid(&handler, &storage, &laptop, 15).await);
+        assert!(!is_valid(&handler, &storage, &phone, 15).await);
+        assert!(!is_valid(&handler, &storage, &tablet, 15).await);
+        assert!(is_valid(&handler, &storage, &other_user, 15).await);
+
+        let ActiveSessionsOutput::Ok { sessions, .. } = handler
+            .active_sessions_at(
+                ActiveSessionsInput {
+                    user_id: "u1".into(),
+                },
+                at(15),
+                &storage,
+            )
+            .await
+            .unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].session_id, laptop);
+    }
 }
@@ -21,6 +21,15 @@ pub trait ConceptStorage: Send + Sync {
     async fn find(&self, relation: &str, criteria: Option<&Value>) -> StorageResult<Vec<Value>>;
     async fn del(&self, relation: &str, key: &str) -> StorageResult<()>;
     async fn del_many(&self, relation: &str, criteria: &Value) -> StorageResult<u64>;
+    /// Write `value` at `key` only if the stored record still equals
+    /// `expected`, as one atomic step. Returns whether the write happened.
+    async fn compare_and_swap(
+        &self,
+        relation: &str,
+        key: &str,
+        expected: &Value,
+        value: Value,
+    ) -> StorageResult<bool>;
 }
 
 /// In-memory storage for testing. Thread-safe via Mutex.
@@ -118,4 +127,21 @@ impl ConceptStorage for InMemoryStorage {
         }
         Ok(count)
     }
+
+    async fn compare_and_swap(
+        &self,
+        relation: &str,
+        key: &str,
+        expected: &Value,
+        value: Value,
+    ) -> StorageResult<bool> {
+        let mut data = self.data.lock().unwrap();
+        match data.get_mut(relation).and_then(|rel| rel.get_mut(key)) {
+            Some(current) if current == expected => {
+                *current = value;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
 }
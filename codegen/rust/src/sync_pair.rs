@@ -4,7 +4,9 @@
 
 use crate::storage::{ConceptStorage, StorageResult};
 use serde::{Deserialize, Serialize};
-use serde_json::json;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeSet, HashMap};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SyncPairLinkInput {
@@ -99,6 +101,135 @@ pub enum SyncPairGetChangeLogOutput {
     Notfound { message: String },
 }
 
+// ── Record diffing ──────────────────────────────────────────
+
+pub type Record = serde_json::Map<String, Value>;
+
+/// How source records are paired with target records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum MatchStrategy {
+    /// Pair records with equal `key_field` values.
+    #[default]
+    #[serde(rename = "key")]
+    Key,
+    /// Pair records whose fields other than `key_field` hash identically, so
+    /// a record that was only re-keyed is reported as an update of its key.
+    #[serde(rename = "content_hash")]
+    ContentHash,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub old: Value,
+    pub new: Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordUpdate {
+    /// Key of the target record being updated.
+    pub key: String,
+    pub changes: Vec<FieldChange>,
+}
+
+/// Changes that turn `target` into `source`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SyncDelta {
+    pub creates: Vec<Record>,
+    pub updates: Vec<RecordUpdate>,
+    pub deletes: Vec<Record>,
+}
+
+impl SyncDelta {
+    pub fn is_empty(&self) -> bool {
+        self.creates.is_empty() && self.updates.is_empty() && self.deletes.is_empty()
+    }
+}
+
+/// Diff `source` against `target`, pairing records by `key_field`.
+pub fn diff_records(source: &[Record], target: &[Record], key_field: &str) -> SyncDelta {
+    diff_records_with(source, target, key_field, MatchStrategy::Key)
+}
+
+/// Diff `source` against `target` using the given match strategy. Creates and
+/// updates follow source order, deletes follow target order; records without
+/// a usable match key are never paired.
+pub fn diff_records_with(
+    source: &[Record],
+    target: &[Record],
+    key_field: &str,
+    strategy: MatchStrategy,
+) -> SyncDelta {
+    let match_key = |record: &Record| match strategy {
+        MatchStrategy::Key => record_key(record, key_field),
+        MatchStrategy::ContentHash => Some(content_hash(record, key_field)),
+    };
+
+    // First occurrence wins when several target records share a match key
+    let mut unmatched: HashMap<String, usize> = HashMap::new();
+    for (index, record) in target.iter().enumerate() {
+        if let Some(key) = match_key(record) {
+            unmatched.entry(key).or_insert(index);
+        }
+    }
+
+    let mut delta = SyncDelta::default();
+    let mut matched = vec![false; target.len()];
+    for record in source {
+        let Some(index) = match_key(record).and_then(|key| unmatched.remove(&key)) else {
+            delta.creates.push(record.clone());
+            continue;
+        };
+        matched[index] = true;
+        let changes = field_changes(&target[index], record);
+        if !changes.is_empty() {
+            delta.updates.push(RecordUpdate {
+                key: record_key(&target[index], key_field).unwrap_or_default(),
+                changes,
+            });
+        }
+    }
+
+    delta.deletes = target.iter()
+        .zip(matched)
+        .filter(|(_, matched)| !matched)
+        .map(|(record, _)| record.clone())
+        .collect();
+    delta
+}
+
+fn record_key(record: &Record, key_field: &str) -> Option<String> {
+    match record.get(key_field)? {
+        Value::Null => None,
+        Value::String(s) => Some(s.clone()),
+        other => Some(other.to_string()),
+    }
+}
+
+fn content_hash(record: &Record, key_field: &str) -> String {
+    let mut content = record.clone();
+    content.remove(key_field);
+    // serde_json maps are ordered by key, so equal records serialize identically
+    let digest = Sha256::digest(Value::Object(content).to_string().as_bytes());
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Per-field differences, in field name order; a missing field reads as null.
+fn field_changes(old: &Record, new: &Record) -> Vec<FieldChange> {
+    let fields: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+    fields.into_iter()
+        .filter_map(|field| {
+            let before = old.get(field).unwrap_or(&Value::Null);
+            let after = new.get(field).unwrap_or(&Value::Null);
+            (before != after).then(|| FieldChange {
+                field: field.clone(),
+                old: before.clone(),
+                new: after.clone(),
+            })
+        })
+        .collect()
+}
+
 pub struct SyncPairHandler;
 
 impl SyncPairHandler {
@@ -234,3 +365,58 @@ impl SyncPairHandler {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(value: Value) -> Record {
+        value.as_object().cloned().unwrap()
+    }
+
+    #[test]
+    fn diff_records_reports_create_update_and_delete() {
+        let target = vec![
+            record(json!({ "id": "1", "name": "Ada", "email": "ada@example.com" })),
+            record(json!({ "id": "2", "name": "Grace", "email": "grace@example.com" })),
+            record(json!({ "id": "3", "name": "Linus", "email": "linus@example.com" })),
+        ];
+        let source = vec![
+            record(json!({ "id": "1", "name": "Ada", "email": "ada@example.com" })),
+            record(json!({ "id": "2", "name": "Grace", "email": "grace@navy.mil" })),
+            record(json!({ "id": "4", "name": "Barbara", "email": "barbara@example.com" })),
+        ];
+
+        let delta = diff_records(&source, &target, "id");
+
+        assert_eq!(delta.creates, vec![source[2].clone()]);
+        assert_eq!(delta.updates, vec![RecordUpdate {
+            key: "2".into(),
+            changes: vec![FieldChange {
+                field: "email".into(),
+                old: json!("grace@example.com"),
+                new: json!("grace@navy.mil"),
+            }],
+        }]);
+        assert_eq!(delta.deletes, vec![target[2].clone()]);
+        assert!(diff_records(&target, &target, "id").is_empty());
+    }
+
+    #[test]
+    fn content_hash_matching_detects_rekeyed_records() {
+        let target = vec![record(json!({ "id": 7, "name": "Ada" }))];
+        let source = vec![
+            record(json!({ "id": 8, "name": "Ada" })),
+            record(json!({ "id": 7, "name": "Ada Lovelace" })),
+        ];
+
+        let delta = diff_records_with(&source, &target, "id", MatchStrategy::ContentHash);
+
+        assert_eq!(delta.updates, vec![RecordUpdate {
+            key: "7".into(),
+            changes: vec![FieldChange { field: "id".into(), old: json!(7), new: json!(8) }],
+        }]);
+        assert_eq!(delta.creates, vec![source[1].clone()]);
+        assert!(delta.deletes.is_empty());
+    }
+}
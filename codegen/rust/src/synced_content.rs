@@ -71,6 +71,127 @@ pub enum ConvertToIndependentOutput {
     NotFound { message: String },
 }
 
+// ── Three-way merge ───────────────────────────────────────
+
+/// A region both replicas changed differently since the common base.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConflictRegion {
+    /// Zero-based line in `MergeResult::merged` where the `<<<<<<<` marker is.
+    pub merged_line: usize,
+    /// Zero-based line in the base where the region starts.
+    pub base_start: usize,
+    pub base: Vec<String>,
+    pub local: Vec<String>,
+    pub remote: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MergeResult {
+    /// Merged text; each conflict is written git-style between
+    /// `<<<<<<< local`, `=======` and `>>>>>>> remote` markers.
+    pub merged: String,
+    pub conflicts: Vec<ConflictRegion>,
+}
+
+impl MergeResult {
+    pub fn is_clean(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+}
+
+/// Pairs of equal lines `(index in a, index in b)` on a longest common
+/// subsequence, in increasing order.
+pub fn line_matches(a: &[&str], b: &[&str]) -> Vec<(usize, usize)> {
+    // lengths[i][j] = LCS length of a[i..] and b[j..]
+    let mut lengths = vec![vec![0u32; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lengths[i][j] = if a[i] == b[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut matches = Vec::new();
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            matches.push((i, j));
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    matches
+}
+
+/// Line-level three-way merge of two replicas' edits to `base`. A region
+/// changed on one side only takes that side; a region changed identically
+/// on both sides is taken once; anything else is a conflict.
+pub fn merge(base: &str, local: &str, remote: &str) -> MergeResult {
+    let base_lines: Vec<&str> = base.lines().collect();
+    let local_lines: Vec<&str> = local.lines().collect();
+    let remote_lines: Vec<&str> = remote.lines().collect();
+
+    let mut in_local = vec![None; base_lines.len()];
+    for (b, l) in line_matches(&base_lines, &local_lines) {
+        in_local[b] = Some(l);
+    }
+    let mut in_remote = vec![None; base_lines.len()];
+    for (b, r) in line_matches(&base_lines, &remote_lines) {
+        in_remote[b] = Some(r);
+    }
+
+    let mut merged: Vec<&str> = Vec::new();
+    let mut conflicts = Vec::new();
+    let (mut i, mut l, mut r) = (0, 0, 0);
+
+    loop {
+        // Next base line kept unchanged by both sides
+        let sync = (i..base_lines.len()).find_map(|k| Some((k, in_local[k]?, in_remote[k]?)));
+        let (k, lk, rk) = sync.unwrap_or((base_lines.len(), local_lines.len(), remote_lines.len()));
+
+        let base_chunk = &base_lines[i..k];
+        let local_chunk = &local_lines[l..lk];
+        let remote_chunk = &remote_lines[r..rk];
+        if local_chunk == base_chunk || local_chunk == remote_chunk {
+            merged.extend_from_slice(remote_chunk);
+        } else if remote_chunk == base_chunk {
+            merged.extend_from_slice(local_chunk);
+        } else {
+            conflicts.push(ConflictRegion {
+                merged_line: merged.len(),
+                base_start: i,
+                base: base_chunk.iter().map(|s| s.to_string()).collect(),
+                local: local_chunk.iter().map(|s| s.to_string()).collect(),
+                remote: remote_chunk.iter().map(|s| s.to_string()).collect(),
+            });
+            merged.push("<<<<<<< local");
+            merged.extend_from_slice(local_chunk);
+            merged.push("=======");
+            merged.extend_from_slice(remote_chunk);
+            merged.push(">>>>>>> remote");
+        }
+
+        if sync.is_none() {
+            break;
+        }
+        merged.push(base_lines[k]);
+        (i, l, r) = (k + 1, lk + 1, rk + 1);
+    }
+
+    let mut text = merged.join("\n");
+    if !text.is_empty() && (local.ends_with('\n') || remote.ends_with('\n')) {
+        text.push('\n');
+    }
+    MergeResult { merged: text, conflicts }
+}
+
 // ── Handler ───────────────────────────────────────────────
 
 pub struct SyncedContentHandler;
@@ -488,4 +609,39 @@ mod tests {
             ConvertToIndependentOutput::NotFound { .. }
         ));
     }
+
+    // ── merge tests ────────────────────────────────────────
+
+    const BASE: &str = "title\nintro\nbody one\nbody two\noutro\n";
+
+    #[test]
+    fn merge_combines_non_overlapping_edits() {
+        let local = "title\nnew intro\nbody one\nbody two\noutro\n";
+        let remote = "title\nintro\nbody one\nbody two\nbody three\noutro\nsignature\n";
+
+        let result = merge(BASE, local, remote);
+        assert!(result.is_clean());
+        assert_eq!(result.merged, "title\nnew intro\nbody one\nbody two\nbody three\noutro\nsignature\n");
+
+        // The same edit on both sides is not a conflict
+        assert_eq!(merge(BASE, local, local).merged, local);
+    }
+
+    #[test]
+    fn merge_reports_overlapping_edits() {
+        let local = "title\nintro\nbody ONE\nbody two\noutro\n";
+        let remote = "title\nintro\nbody 1\nbody two\nthe end\n";
+
+        let result = merge(BASE, local, remote);
+        assert_eq!(result.conflicts.len(), 1);
+        let conflict = &result.conflicts[0];
+        assert_eq!((conflict.base_start, conflict.merged_line), (2, 2));
+        assert_eq!(conflict.base, vec!["body one"]);
+        assert_eq!(conflict.local, vec!["body ONE"]);
+        assert_eq!(conflict.remote, vec!["body 1"]);
+        assert_eq!(
+            result.merged,
+            "title\nintro\n<<<<<<< local\nbody ONE\n=======\nbody 1\n>>>>>>> remote\nbody two\nthe end\n"
+        );
+    }
 }
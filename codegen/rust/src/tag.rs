@@ -2,10 +2,15 @@
 //
 // Flat or hierarchical labels for cross-cutting classification of content.
 
+use crate::article::slugify;
+use crate::search_index::{IndexItemInput, SearchIndexHandler};
 use crate::storage::{ConceptStorage, StorageResult};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
+/// Search index holding one entry per tag, keyed by the tag's slug.
+pub const TAG_SEARCH_INDEX: &str = "tags";
+
 // ── Types ──────────────────────────────────────────────────
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -79,6 +84,59 @@ pub enum TagRenameOutput {
     Notfound { message: String },
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TagMergeTagsInput {
+    pub sources: Vec<String>,
+    pub target: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "variant")]
+pub enum TagMergeTagsOutput {
+    #[serde(rename = "ok")]
+    Ok { merged: u64 },
+    #[serde(rename = "notfound")]
+    Notfound { message: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TagRenameTagInput {
+    pub tag: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "variant")]
+pub enum TagRenameTagOutput {
+    #[serde(rename = "ok")]
+    Ok { tag: String },
+    #[serde(rename = "notfound")]
+    Notfound { message: String },
+    #[serde(rename = "conflict")]
+    Conflict { message: String },
+}
+
+fn tag_entities(record: &serde_json::Value) -> Vec<String> {
+    record["tagIndex"]
+        .as_array()
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Point child tags of `from` at `to`.
+async fn reparent_children(from: &str, to: &str, storage: &dyn ConceptStorage) -> StorageResult<()> {
+    for mut child in storage.find("tag", Some(&json!({ "parent": from }))).await? {
+        let key = child["tag"].as_str().unwrap_or("").to_string();
+        child["parent"] = json!(to);
+        storage.put("tag", &key, child).await?;
+    }
+    Ok(())
+}
+
 // ── Handler ────────────────────────────────────────────────
 
 pub struct TagHandler;
@@ -245,6 +303,116 @@ impl TagHandler {
 
         Ok(TagRenameOutput::Ok {})
     }
+
+    /// Fold each source tag into `target`: its entities (articles,
+    /// favorites, …) and child tags move over, then the source and its
+    /// search entries are deleted. Sources already gone are skipped, so
+    /// repeating a merge is a no-op.
+    pub async fn merge_tags(
+        &self,
+        input: TagMergeTagsInput,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<TagMergeTagsOutput> {
+        let Some(mut target) = storage.get("tag", &input.target).await? else {
+            return Ok(TagMergeTagsOutput::Notfound {
+                message: "Target tag does not exist".to_string(),
+            });
+        };
+
+        let mut entities = tag_entities(&target);
+        let mut merged: u64 = 0;
+        for source in input.sources.iter().filter(|s| **s != input.target) {
+            let Some(record) = storage.get("tag", source).await? else {
+                continue;
+            };
+            for entity in tag_entities(&record) {
+                if !entities.contains(&entity) {
+                    entities.push(entity);
+                }
+            }
+            reparent_children(source, &input.target, storage).await?;
+            storage.del("tag", source).await?;
+            storage
+                .del_many(
+                    "indexed_item",
+                    &json!({ "index_id": TAG_SEARCH_INDEX, "node_id": source }),
+                )
+                .await?;
+            merged += 1;
+        }
+
+        target["tagIndex"] = json!(entities);
+        storage.put("tag", &input.target, target).await?;
+
+        Ok(TagMergeTagsOutput::Ok { merged })
+    }
+
+    /// Rename a tag and move it to the slug of its new name, keeping its
+    /// entities, children and search entries. Renaming to a slug another
+    /// tag already holds is a conflict; merge the tags instead.
+    pub async fn rename_tag(
+        &self,
+        input: TagRenameTagInput,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<TagRenameTagOutput> {
+        let slug = slugify(&input.name);
+        let existing = storage.get("tag", &input.tag).await?;
+        let at_slug = if slug == input.tag {
+            None
+        } else {
+            storage.get("tag", &slug).await?
+        };
+
+        let mut record = match (existing, at_slug) {
+            (Some(_), Some(_)) => {
+                return Ok(TagRenameTagOutput::Conflict {
+                    message: format!("Tag '{}' already exists", slug),
+                });
+            }
+            // Already renamed by an earlier call
+            (None, Some(renamed)) if renamed["name"].as_str() == Some(input.name.as_str()) => {
+                return Ok(TagRenameTagOutput::Ok { tag: slug });
+            }
+            (Some(record), None) => record,
+            _ => {
+                return Ok(TagRenameTagOutput::Notfound {
+                    message: "Tag does not exist".to_string(),
+                });
+            }
+        };
+
+        let old_name = record["name"].as_str().unwrap_or(&input.tag).to_string();
+        record["tag"] = json!(slug);
+        record["name"] = json!(input.name);
+        storage.put("tag", &slug, record).await?;
+
+        if slug != input.tag {
+            storage.del("tag", &input.tag).await?;
+            reparent_children(&input.tag, &slug, storage).await?;
+        }
+
+        // Re-key the tag's search entry; content that was just the name follows it
+        let entry_key = format!("{}:{}", TAG_SEARCH_INDEX, input.tag);
+        if let Some(entry) = storage.get("indexed_item", &entry_key).await? {
+            let content = match entry["content"].as_str() {
+                Some(content) if content != old_name => content.to_string(),
+                _ => input.name.clone(),
+            };
+            storage.del("indexed_item", &entry_key).await?;
+            SearchIndexHandler
+                .index_item(
+                    IndexItemInput {
+                        index_id: TAG_SEARCH_INDEX.to_string(),
+                        node_id: slug.clone(),
+                        content,
+                    },
+                    storage,
+                )
+                .await?;
+        }
+
+        Ok(TagRenameTagOutput::Ok { tag: slug })
+    }
 }
 
 // ── Tests ──────────────────────────────────────────────────
@@ -435,4 +603,82 @@ mod tests {
             .unwrap();
         assert!(matches!(result, TagRenameOutput::Notfound { .. }));
     }
+
+    async fn tag(storage: &InMemoryStorage, tag: &str, entities: &[&str]) {
+        for entity in entities {
+            TagHandler
+                .add_tag(TagAddTagInput { entity: entity.to_string(), tag: tag.into() }, storage)
+                .await
+                .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn merge_tags_moves_article_associations() {
+        let storage = InMemoryStorage::new();
+        let handler = TagHandler;
+        tag(&storage, "rust", &["a1", "a2"]).await;
+        tag(&storage, "Rust", &["a2", "a3"]).await;
+        tag(&storage, "rustlang", &["a4"]).await;
+        SearchIndexHandler
+            .index_item(
+                IndexItemInput { index_id: TAG_SEARCH_INDEX.into(), node_id: "rustlang".into(), content: "rustlang".into() },
+                &storage,
+            )
+            .await
+            .unwrap();
+        // An article that happens to share the slug keeps its own entry
+        SearchIndexHandler
+            .index_item(
+                IndexItemInput { index_id: "articles".into(), node_id: "rustlang".into(), content: "Rustlang".into() },
+                &storage,
+            )
+            .await
+            .unwrap();
+
+        let input = TagMergeTagsInput { sources: vec!["Rust".into(), "rustlang".into()], target: "rust".into() };
+        let result = handler.merge_tags(input.clone(), &storage).await.unwrap();
+        assert_eq!(result, TagMergeTagsOutput::Ok { merged: 2 });
+
+        let record = storage.get("tag", "rust").await.unwrap().unwrap();
+        assert_eq!(record["tagIndex"], json!(["a1", "a2", "a3", "a4"]));
+        assert!(storage.get("tag", "Rust").await.unwrap().is_none());
+        assert!(storage.get("tag", "rustlang").await.unwrap().is_none());
+        assert!(storage.get("indexed_item", "tags:rustlang").await.unwrap().is_none());
+        assert!(storage.get("indexed_item", "articles:rustlang").await.unwrap().is_some());
+
+        // Running the same merge again changes nothing
+        assert_eq!(handler.merge_tags(input, &storage).await.unwrap(), TagMergeTagsOutput::Ok { merged: 0 });
+        let again = storage.get("tag", "rust").await.unwrap().unwrap();
+        assert_eq!(again["tagIndex"], record["tagIndex"]);
+    }
+
+    #[tokio::test]
+    async fn rename_tag_moves_slug_and_keeps_associations() {
+        let storage = InMemoryStorage::new();
+        let handler = TagHandler;
+        tag(&storage, "js", &["a1"]).await;
+        SearchIndexHandler
+            .index_item(IndexItemInput { index_id: TAG_SEARCH_INDEX.into(), node_id: "js".into(), content: "js".into() }, &storage)
+            .await
+            .unwrap();
+
+        let input = TagRenameTagInput { tag: "js".into(), name: "JavaScript".into() };
+        let result = handler.rename_tag(input.clone(), &storage).await.unwrap();
+        assert_eq!(result, TagRenameTagOutput::Ok { tag: "javascript".into() });
+        assert_eq!(handler.rename_tag(input, &storage).await.unwrap(), result);
+
+        let record = storage.get("tag", "javascript").await.unwrap().unwrap();
+        assert_eq!((record["name"].as_str(), record["tagIndex"].clone()), (Some("JavaScript"), json!(["a1"])));
+        assert!(storage.get("tag", "js").await.unwrap().is_none());
+        let entry = storage.get("indexed_item", "tags:javascript").await.unwrap().unwrap();
+        assert_eq!(entry["content"], "JavaScript");
+
+        tag(&storage, "ts", &["a2"]).await;
+        let clash = handler
+            .rename_tag(TagRenameTagInput { tag: "ts".into(), name: "javascript".into() }, &storage)
+            .await
+            .unwrap();
+        assert!(matches!(clash, TagRenameTagOutput::Conflict { .. }));
+    }
 }
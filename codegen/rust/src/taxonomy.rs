@@ -6,6 +6,7 @@
 use crate::storage::{ConceptStorage, StorageResult};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::collections::HashSet;
 
 // ── CreateVocabulary ──────────────────────────────────────
 
@@ -54,6 +55,28 @@ pub enum SetParentOutput {
     Ok { term_id: String },
     #[serde(rename = "notfound")]
     NotFound { message: String },
+    #[serde(rename = "cycle")]
+    Cycle { message: String },
+}
+
+// ── MoveSubtree ───────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MoveSubtreeInput {
+    pub term_id: String,
+    /// `None` makes the term a root.
+    pub new_parent_term_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "variant")]
+pub enum MoveSubtreeOutput {
+    #[serde(rename = "ok")]
+    Ok { term_id: String, moved: u64 },
+    #[serde(rename = "notfound")]
+    NotFound { message: String },
+    #[serde(rename = "cycle")]
+    Cycle { message: String },
 }
 
 // ── TagEntity ─────────────────────────────────────────────
@@ -88,6 +111,93 @@ pub enum UntagEntityOutput {
     NotFound { message: String },
 }
 
+// ── Hierarchy ─────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Term {
+    pub term_id: String,
+    pub vocab_id: String,
+    pub name: String,
+    pub parent_term_id: Option<String>,
+}
+
+pub async fn get_term(term_id: &str, storage: &dyn ConceptStorage) -> StorageResult<Option<Term>> {
+    match storage.get("term", term_id).await? {
+        Some(record) => Ok(Some(serde_json::from_value(record)?)),
+        None => Ok(None),
+    }
+}
+
+/// Parent, grandparent, … up to the root, nearest first. Stops early if the
+/// stored parents already loop.
+pub async fn ancestors(term_id: &str, storage: &dyn ConceptStorage) -> StorageResult<Vec<Term>> {
+    let mut seen: HashSet<String> = HashSet::from([term_id.to_string()]);
+    let mut chain: Vec<Term> = vec![];
+    let mut next = get_term(term_id, storage).await?.and_then(|t| t.parent_term_id);
+
+    while let Some(parent_id) = next {
+        if !seen.insert(parent_id.clone()) {
+            break;
+        }
+        let Some(parent) = get_term(&parent_id, storage).await? else {
+            break;
+        };
+        next = parent.parent_term_id.clone();
+        chain.push(parent);
+    }
+    Ok(chain)
+}
+
+/// Children, grandchildren, … of `term_id`, level by level, each level in
+/// id order.
+pub async fn descendants(term_id: &str, storage: &dyn ConceptStorage) -> StorageResult<Vec<Term>> {
+    let mut seen: HashSet<String> = HashSet::from([term_id.to_string()]);
+    let mut found: Vec<Term> = vec![];
+    let mut level: Vec<String> = vec![term_id.to_string()];
+
+    while !level.is_empty() {
+        let mut next_level: Vec<Term> = vec![];
+        for parent_id in &level {
+            for record in storage.find("term", Some(&json!({ "parent_term_id": parent_id }))).await? {
+                let child: Term = serde_json::from_value(record)?;
+                if seen.insert(child.term_id.clone()) {
+                    next_level.push(child);
+                }
+            }
+        }
+        next_level.sort_by(|a, b| a.term_id.cmp(&b.term_id));
+        level = next_level.iter().map(|t| t.term_id.clone()).collect();
+        found.extend(next_level);
+    }
+    Ok(found)
+}
+
+/// Number of ancestors; roots are at depth 0.
+pub async fn depth(term_id: &str, storage: &dyn ConceptStorage) -> StorageResult<usize> {
+    Ok(ancestors(term_id, storage).await?.len())
+}
+
+/// Root-first path to `term_id`, including the term itself, for rendering
+/// breadcrumbs. Empty when the term does not exist.
+pub async fn breadcrumb(term_id: &str, storage: &dyn ConceptStorage) -> StorageResult<Vec<Term>> {
+    let Some(term) = get_term(term_id, storage).await? else {
+        return Ok(vec![]);
+    };
+    let mut trail = ancestors(term_id, storage).await?;
+    trail.reverse();
+    trail.push(term);
+    Ok(trail)
+}
+
+/// Whether making `parent_id` the parent of `term_id` would put the term
+/// under itself.
+async fn would_cycle(term_id: &str, parent_id: &str, storage: &dyn ConceptStorage) -> StorageResult<bool> {
+    if term_id == parent_id {
+        return Ok(true);
+    }
+    Ok(ancestors(parent_id, storage).await?.iter().any(|t| t.term_id == term_id))
+}
+
 // ── Handler ───────────────────────────────────────────────
 
 pub struct TaxonomyHandler;
@@ -184,6 +294,14 @@ impl TaxonomyHandler {
                 message: format!("Term '{}' not found", input.term_id),
             }),
             Some(mut term) => {
+                if would_cycle(&input.term_id, &input.parent_term_id, storage).await? {
+                    return Ok(SetParentOutput::Cycle {
+                        message: format!(
+                            "Term '{}' cannot be placed under its own descendant '{}'",
+                            input.term_id, input.parent_term_id
+                        ),
+                    });
+                }
                 term["parent_term_id"] = json!(input.parent_term_id);
                 storage.put("term", &input.term_id, term).await?;
                 Ok(SetParentOutput::Ok {
@@ -193,6 +311,45 @@ impl TaxonomyHandler {
         }
     }
 
+    /// Re-parent `term_id`, carrying its whole subtree along. Rejects moves
+    /// under the term itself or one of its descendants.
+    pub async fn move_subtree(
+        &self,
+        input: MoveSubtreeInput,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<MoveSubtreeOutput> {
+        let Some(mut term) = storage.get("term", &input.term_id).await? else {
+            return Ok(MoveSubtreeOutput::NotFound {
+                message: format!("Term '{}' not found", input.term_id),
+            });
+        };
+
+        if let Some(parent_id) = &input.new_parent_term_id {
+            if storage.get("term", parent_id).await?.is_none() {
+                return Ok(MoveSubtreeOutput::NotFound {
+                    message: format!("Term '{}' not found", parent_id),
+                });
+            }
+            if would_cycle(&input.term_id, parent_id, storage).await? {
+                return Ok(MoveSubtreeOutput::Cycle {
+                    message: format!(
+                        "Term '{}' cannot be moved under its own descendant '{}'",
+                        input.term_id, parent_id
+                    ),
+                });
+            }
+        }
+
+        let moved = 1 + descendants(&input.term_id, storage).await?.len() as u64;
+        term["parent_term_id"] = json!(input.new_parent_term_id);
+        storage.put("term", &input.term_id, term).await?;
+
+        Ok(MoveSubtreeOutput::Ok {
+            term_id: input.term_id,
+            moved,
+        })
+    }
+
     pub async fn tag_entity(
         &self,
         input: TagEntityInput,
@@ -554,4 +711,99 @@ mod tests {
 
         assert!(matches!(result, UntagEntityOutput::NotFound { .. }));
     }
+
+    // ── hierarchy tests ────────────────────────────────────
+
+    /// science > physics > quantum, science > biology; arts
+    async fn science_vocabulary(storage: &InMemoryStorage) {
+        let handler = TaxonomyHandler;
+        handler
+            .create_vocabulary(CreateVocabularyInput { name: "Topics".into() }, storage)
+            .await
+            .unwrap();
+        for (name, parent) in [
+            ("Science", None),
+            ("Physics", Some("term_vocab_topics_science")),
+            ("Quantum", Some("term_vocab_topics_physics")),
+            ("Biology", Some("term_vocab_topics_science")),
+            ("Arts", None),
+        ] {
+            handler
+                .add_term(
+                    AddTermInput {
+                        vocab_id: "vocab_topics".into(),
+                        name: name.into(),
+                        parent_term_id: parent.map(String::from),
+                    },
+                    storage,
+                )
+                .await
+                .unwrap();
+        }
+    }
+
+    fn names(terms: &[Term]) -> Vec<&str> {
+        terms.iter().map(|t| t.name.as_str()).collect()
+    }
+
+    #[tokio::test]
+    async fn hierarchy_queries_walk_the_tree() {
+        let storage = InMemoryStorage::new();
+        science_vocabulary(&storage).await;
+
+        let science = "term_vocab_topics_science";
+        let quantum = "term_vocab_topics_quantum";
+        assert_eq!(names(&descendants(science, &storage).await.unwrap()), vec!["Biology", "Physics", "Quantum"]);
+        assert_eq!(names(&ancestors(quantum, &storage).await.unwrap()), vec!["Physics", "Science"]);
+        assert_eq!(depth(quantum, &storage).await.unwrap(), 2);
+        assert_eq!(depth(science, &storage).await.unwrap(), 0);
+        assert_eq!(names(&breadcrumb(quantum, &storage).await.unwrap()), vec!["Science", "Physics", "Quantum"]);
+    }
+
+    #[tokio::test]
+    async fn move_subtree_rejects_cycles_and_carries_children() {
+        let storage = InMemoryStorage::new();
+        let handler = TaxonomyHandler;
+        science_vocabulary(&storage).await;
+
+        let result = handler
+            .move_subtree(
+                MoveSubtreeInput {
+                    term_id: "term_vocab_topics_science".into(),
+                    new_parent_term_id: Some("term_vocab_topics_quantum".into()),
+                },
+                &storage,
+            )
+            .await
+            .unwrap();
+        assert!(matches!(result, MoveSubtreeOutput::Cycle { .. }));
+        let result = handler
+            .set_parent(
+                SetParentInput {
+                    term_id: "term_vocab_topics_physics".into(),
+                    parent_term_id: "term_vocab_topics_physics".into(),
+                },
+                &storage,
+            )
+            .await
+            .unwrap();
+        assert!(matches!(result, SetParentOutput::Cycle { .. }));
+
+        let result = handler
+            .move_subtree(
+                MoveSubtreeInput {
+                    term_id: "term_vocab_topics_physics".into(),
+                    new_parent_term_id: Some("term_vocab_topics_arts".into()),
+                },
+                &storage,
+            )
+            .await
+            .unwrap();
+        assert!(matches!(result, MoveSubtreeOutput::Ok { moved: 2, .. }));
+        assert_eq!(
+            names(&breadcrumb("term_vocab_topics_quantum", &storage).await.unwrap()),
+            vec!["Arts", "Physics", "Quantum"]
+        );
+        assert_eq!(names(&descendants("term_vocab_topics_science", &storage).await.unwrap()), vec!["Biology"]);
+    }
 }
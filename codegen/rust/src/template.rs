@@ -3,6 +3,7 @@
 // Manages template definitions, instantiation, and trigger registration.
 // See Architecture doc Sections on template and automation.
 
+use crate::escape::escape_html;
 use crate::storage::{ConceptStorage, StorageResult};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
@@ -39,6 +40,8 @@ pub enum InstantiateOutput {
     Ok { instance_id: String },
     #[serde(rename = "notfound")]
     NotFound { message: String },
+    #[serde(rename = "invalid")]
+    Invalid { message: String },
 }
 
 // ── RegisterTrigger ───────────────────────────────────────
@@ -58,6 +61,189 @@ pub enum RegisterTriggerOutput {
     NotFound { message: String },
 }
 
+// ── Rendering ─────────────────────────────────────────────
+
+/// Why a template failed to compile. Offsets are byte positions in the source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateError {
+    UnclosedTag { offset: usize },
+    UnexpectedEnd { offset: usize },
+    UnclosedEach { path: String },
+}
+
+impl std::fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TemplateError::UnclosedTag { offset } => write!(f, "unclosed tag at byte {}", offset),
+            TemplateError::UnexpectedEnd { offset } => {
+                write!(f, "{{{{/each}}}} without a matching {{{{#each}}}} at byte {}", offset)
+            }
+            TemplateError::UnclosedEach { path } => write!(f, "{{{{#each {}}}}} is never closed", path),
+        }
+    }
+}
+
+impl std::error::Error for TemplateError {}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Text(String),
+    Var {
+        path: String,
+        default: Option<String>,
+        escape: bool,
+    },
+    Each {
+        path: String,
+        body: Vec<Segment>,
+    },
+}
+
+/// A template parsed once and rendered against any number of contexts.
+///
+/// - `{{name}}` inserts a value, HTML-escaped; `{{{name}}}` inserts it raw.
+/// - `{{name|fallback}}` uses `fallback` when the value is missing or null.
+/// - `{{#each items}}…{{/each}}` repeats its body per array element. Inside,
+///   names resolve against the element first, then the outer context;
+///   `{{this}}` is the element and `{{@index}}` its position.
+/// - Dotted names (`author.name`) reach into objects.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompiledTemplate {
+    segments: Vec<Segment>,
+}
+
+impl CompiledTemplate {
+    pub fn compile(source: &str) -> Result<Self, TemplateError> {
+        // Each open `#each` keeps its path and the segments collected so far
+        // for the enclosing level.
+        let mut stack: Vec<(String, Vec<Segment>)> = Vec::new();
+        let mut segments: Vec<Segment> = Vec::new();
+        let mut rest = source;
+
+        while let Some(start) = rest.find("{{") {
+            if start > 0 {
+                segments.push(Segment::Text(rest[..start].to_string()));
+            }
+            let offset = source.len() - rest.len() + start;
+            let raw = rest[start..].starts_with("{{{");
+            let (open, close) = if raw { ("{{{", "}}}") } else { ("{{", "}}") };
+            let tag_start = start + open.len();
+            let Some(len) = rest[tag_start..].find(close) else {
+                return Err(TemplateError::UnclosedTag { offset });
+            };
+            let tag = rest[tag_start..tag_start + len].trim();
+            rest = &rest[tag_start + len + close.len()..];
+
+            let each_path = tag
+                .strip_prefix("#each")
+                .filter(|path| path.starts_with(char::is_whitespace));
+            if let Some(path) = each_path {
+                stack.push((path.trim().to_string(), std::mem::take(&mut segments)));
+            } else if tag == "/each" {
+                let Some((path, outer)) = stack.pop() else {
+                    return Err(TemplateError::UnexpectedEnd { offset });
+                };
+                let body = std::mem::replace(&mut segments, outer);
+                segments.push(Segment::Each { path, body });
+            } else {
+                let (path, default) = match tag.split_once('|') {
+                    Some((path, default)) => (path.trim(), Some(default.trim().to_string())),
+                    None => (tag, None),
+                };
+                segments.push(Segment::Var {
+                    path: path.to_string(),
+                    default,
+                    escape: !raw,
+                });
+            }
+        }
+        if !rest.is_empty() {
+            segments.push(Segment::Text(rest.to_string()));
+        }
+        if let Some((path, _)) = stack.pop() {
+            return Err(TemplateError::UnclosedEach { path });
+        }
+        Ok(Self { segments })
+    }
+
+    pub fn render(&self, context: &serde_json::Value) -> String {
+        let mut out = String::new();
+        render_segments(&self.segments, &[context], None, &mut out);
+        out
+    }
+}
+
+/// Compile and render in one step.
+pub fn render_template(source: &str, context: &serde_json::Value) -> Result<String, TemplateError> {
+    Ok(CompiledTemplate::compile(source)?.render(context))
+}
+
+fn render_segments(segments: &[Segment], scopes: &[&serde_json::Value], index: Option<usize>, out: &mut String) {
+    for segment in segments {
+        match segment {
+            Segment::Text(text) => out.push_str(text),
+            Segment::Var { path, default, escape } => {
+                let value = match path.as_str() {
+                    "@index" => index.map(|i| serde_json::Value::from(i as u64)),
+                    _ => lookup(scopes, path).cloned(),
+                };
+                let text = match value {
+                    None | Some(serde_json::Value::Null) => default.clone().unwrap_or_default(),
+                    Some(serde_json::Value::String(s)) => s,
+                    Some(other) => other.to_string(),
+                };
+                if *escape {
+                    out.push_str(&escape_html(&text));
+                } else {
+                    out.push_str(&text);
+                }
+            }
+            Segment::Each { path, body } => {
+                if let Some(serde_json::Value::Array(items)) = lookup(scopes, path) {
+                    for (i, item) in items.iter().enumerate() {
+                        let mut inner = vec![item];
+                        inner.extend_from_slice(scopes);
+                        render_segments(body, &inner, Some(i), out);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Resolve a dotted path against the innermost scope that has its first key.
+fn lookup<'a>(scopes: &[&'a serde_json::Value], path: &str) -> Option<&'a serde_json::Value> {
+    if path == "this" || path == "." {
+        return scopes.first().copied();
+    }
+    let mut keys = path.split('.');
+    let first = keys.next()?;
+    let mut value = scopes.iter().find_map(|scope| scope.get(first))?;
+    for key in keys {
+        value = match value {
+            serde_json::Value::Array(items) => items.get(key.parse::<usize>().ok()?)?,
+            _ => value.get(key)?,
+        };
+    }
+    Some(value)
+}
+
+/// Render every string in a block tree against `bindings`.
+fn render_tree(tree: &serde_json::Value, bindings: &serde_json::Value) -> Result<serde_json::Value, TemplateError> {
+    Ok(match tree {
+        serde_json::Value::String(source) => serde_json::Value::String(render_template(source, bindings)?),
+        serde_json::Value::Array(items) => serde_json::Value::Array(
+            items.iter().map(|item| render_tree(item, bindings)).collect::<Result<_, _>>()?,
+        ),
+        serde_json::Value::Object(map) => serde_json::Value::Object(
+            map.iter()
+                .map(|(k, v)| Ok((k.clone(), render_tree(v, bindings)?)))
+                .collect::<Result<_, TemplateError>>()?,
+        ),
+        other => other.clone(),
+    })
+}
+
 // ── Handler ───────────────────────────────────────────────
 
 pub struct TemplateHandler;
@@ -113,7 +299,14 @@ impl TemplateHandler {
                 );
 
                 // Apply bindings to the block tree
-                let block_tree = template_record["block_tree"].clone();
+                let block_tree = match render_tree(&template_record["block_tree"], &bindings) {
+                    Ok(tree) => tree,
+                    Err(err) => {
+                        return Ok(InstantiateOutput::Invalid {
+                            message: format!("Template '{}': {}", input.template_id, err),
+                        })
+                    }
+                };
 
                 storage
                     .put(
@@ -343,4 +536,76 @@ mod tests {
 
         assert!(matches!(result, RegisterTriggerOutput::NotFound { .. }));
     }
+
+    // ── rendering tests ────────────────────────────────────
+
+    #[test]
+    fn render_uses_defaults_for_missing_vars() {
+        let template = CompiledTemplate::compile("Hi {{user.name|friend}}, you have {{count|no}} messages").unwrap();
+        assert_eq!(template.render(&json!({})), "Hi friend, you have no messages");
+        assert_eq!(
+            template.render(&json!({ "user": { "name": "Ada" }, "count": 3 })),
+            "Hi Ada, you have 3 messages"
+        );
+        assert_eq!(template.render(&json!({ "user": { "name": null } })), "Hi friend, you have no messages");
+    }
+
+    #[test]
+    fn render_loops_and_escapes() {
+        let template = CompiledTemplate::compile(
+            "<ul>{{#each items}}<li>{{@index}}. {{name}} by {{owner}}</li>{{/each}}</ul>{{{footer}}}",
+        )
+        .unwrap();
+        let context = json!({
+            "owner": "team",
+            "items": [{ "name": "Tea & cake" }, { "name": "<b>Bold</b>" }, { "name": "Plain", "owner": "me" }],
+            "footer": "<hr>",
+        });
+        assert_eq!(
+            template.render(&context),
+            "<ul><li>0. Tea &amp; cake by team</li><li>1. &lt;b&gt;Bold&lt;/b&gt; by team</li>\
+             <li>2. Plain by me</li></ul><hr>"
+        );
+
+        assert_eq!(render_template("{{#each tags}}#{{this}} {{/each}}", &json!({ "tags": ["a", "b"] })).unwrap(), "#a #b ");
+        assert_eq!(
+            CompiledTemplate::compile("{{#each items}}x"),
+            Err(TemplateError::UnclosedEach { path: "items".into() })
+        );
+        assert_eq!(render_template("{{#eachfoo}}x", &json!({})).unwrap(), "x");
+        assert_eq!(CompiledTemplate::compile("a {{/each}}"), Err(TemplateError::UnexpectedEnd { offset: 2 }));
+        assert_eq!(CompiledTemplate::compile("a {{b"), Err(TemplateError::UnclosedTag { offset: 2 }));
+    }
+
+    #[tokio::test]
+    async fn instantiate_renders_bindings_into_block_tree() {
+        let storage = InMemoryStorage::new();
+        let handler = TemplateHandler;
+        handler
+            .define(
+                DefineInput {
+                    template_id: "tpl_greet".into(),
+                    block_tree: r#"[{"type":"text","text":"Hello {{name|there}}"}]"#.into(),
+                    variables: r#"["name"]"#.into(),
+                },
+                &storage,
+            )
+            .await
+            .unwrap();
+
+        let result = handler
+            .instantiate(
+                InstantiateInput {
+                    template_id: "tpl_greet".into(),
+                    target_location: "/x".into(),
+                    bindings: r#"{"name":"Grace"}"#.into(),
+                },
+                &storage,
+            )
+            .await
+            .unwrap();
+        let InstantiateOutput::Ok { instance_id } = result else { panic!("expected Ok") };
+        let instance = storage.get("template", &instance_id).await.unwrap().unwrap();
+        assert_eq!(instance["resolved_tree"][0]["text"], "Hello Grace");
+    }
 }
@@ -56,7 +56,16 @@ pub enum TransformPreviewOutput {
 pub struct TransformHandler;
 
 impl TransformHandler {
-    fn apply_plugin(value: &str, plugin_id: &str) -> String {
+    /// Plugins `apply_plugin` implements; any other id passes values through.
+    pub const PLUGIN_IDS: &'static [&'static str] = &[
+        "slugify",
+        "strip_tags",
+        "html_to_markdown",
+        "upper",
+        "lower",
+    ];
+
+    pub(crate) fn apply_plugin(value: &str, plugin_id: &str) -> String {
         match plugin_id {
             "slugify" => {
                 let slug: String = value
@@ -98,6 +107,8 @@ impl TransformHandler {
                     .trim()
                     .to_string()
             }
+            "upper" => value.to_uppercase(),
+            "lower" => value.to_lowercase(),
             _ => value.to_string(),
         }
     }
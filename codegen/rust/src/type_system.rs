@@ -1,10 +1,18 @@
 // TypeSystem Concept Implementation (Rust)
 //
 // Type registration, resolution, and validation for the concept framework.
+//
+// `Type` describes value types (primitives, arrays, registered named types,
+// unions and intersections; `T?` is `T | null`). A `TypeHierarchy` knows the
+// supertype of each named type and answers subtyping, common-supertype and
+// assignability questions for concepts such as form_builder.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
 
 use crate::storage::{ConceptStorage, StorageResult};
 use serde::{Deserialize, Serialize};
-use serde_json::json;
+use serde_json::{json, Value};
 
 // --- RegisterType ---
 
@@ -59,6 +67,500 @@ pub enum ValidateOutput {
     Invalid { type_id: String, errors: String },
 }
 
+// --- Type relationships ---
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Type {
+    Never,
+    Null,
+    Boolean,
+    Int,
+    Number,
+    String,
+    Date,
+    DateTime,
+    Object,
+    Array(Box<Type>),
+    /// A registered type, related to others through its declared supertype.
+    Named(String),
+    Union(BTreeSet<Type>),
+    Intersection(BTreeSet<Type>),
+    Any,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeParseError {
+    pub source: String,
+    pub message: String,
+}
+
+impl fmt::Display for TypeParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid type '{}': {}", self.source, self.message)
+    }
+}
+
+impl std::error::Error for TypeParseError {}
+
+impl Type {
+    /// Parse a type expression such as `int`, `string?`, `array<int>`,
+    /// `int[]`, `int | string` or `a & b` (`&` binds tighter than `|`).
+    /// Unrecognized identifiers are named types.
+    pub fn parse(source: &str) -> Result<Type, TypeParseError> {
+        let mut parser = TypeParser {
+            source,
+            rest: source,
+        };
+        let parsed = parser.union()?;
+        if !parser.rest.trim().is_empty() {
+            return Err(parser.error(&format!("unexpected '{}'", parser.rest.trim())));
+        }
+        Ok(parsed)
+    }
+
+    /// The most specific type describing a JSON value.
+    pub fn of_value(value: &Value) -> Type {
+        match value {
+            Value::Null => Type::Null,
+            Value::Bool(_) => Type::Boolean,
+            Value::Number(n) if n.is_i64() || n.is_u64() => Type::Int,
+            Value::Number(_) => Type::Number,
+            Value::String(_) => Type::String,
+            Value::Array(items) => Type::Array(Box::new(
+                TypeHierarchy::new().union(items.iter().map(Type::of_value)),
+            )),
+            Value::Object(_) => Type::Object,
+        }
+    }
+
+    /// `self | null`.
+    pub fn nullable(self) -> Type {
+        TypeHierarchy::new().union([self, Type::Null])
+    }
+
+    pub fn is_nullable(&self) -> bool {
+        match self {
+            Type::Null | Type::Any => true,
+            Type::Union(members) => members.iter().any(Type::is_nullable),
+            _ => false,
+        }
+    }
+
+    fn builtin(name: &str) -> Option<Type> {
+        Some(match name {
+            "never" => Type::Never,
+            "null" => Type::Null,
+            "boolean" | "bool" => Type::Boolean,
+            "int" | "integer" => Type::Int,
+            "number" | "float" => Type::Number,
+            "string" => Type::String,
+            "date" => Type::Date,
+            "datetime" => Type::DateTime,
+            "object" => Type::Object,
+            "any" => Type::Any,
+            _ => return None,
+        })
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::Never => f.write_str("never"),
+            Type::Null => f.write_str("null"),
+            Type::Boolean => f.write_str("boolean"),
+            Type::Int => f.write_str("int"),
+            Type::Number => f.write_str("number"),
+            Type::String => f.write_str("string"),
+            Type::Date => f.write_str("date"),
+            Type::DateTime => f.write_str("datetime"),
+            Type::Object => f.write_str("object"),
+            Type::Any => f.write_str("any"),
+            Type::Array(item) => write!(f, "array<{}>", item),
+            Type::Named(name) => f.write_str(name),
+            Type::Union(members) => {
+                // Null last, so `int | null` reads as a nullable int
+                let mut parts: Vec<String> = members
+                    .iter()
+                    .filter(|m| **m != Type::Null)
+                    .map(|m| m.to_string())
+                    .collect();
+                if members.contains(&Type::Null) {
+                    parts.push("null".to_string());
+                }
+                f.write_str(&parts.join(" | "))
+            }
+            Type::Intersection(members) => {
+                let parts: Vec<String> = members
+                    .iter()
+                    .map(|m| match m {
+                        Type::Union(_) => format!("({})", m),
+                        _ => m.to_string(),
+                    })
+                    .collect();
+                f.write_str(&parts.join(" & "))
+            }
+        }
+    }
+}
+
+struct TypeParser<'a> {
+    source: &'a str,
+    rest: &'a str,
+}
+
+impl TypeParser<'_> {
+    fn error(&self, message: &str) -> TypeParseError {
+        TypeParseError {
+            source: self.source.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        let trimmed = self.rest.trim_start();
+        match trimmed.strip_prefix(token) {
+            Some(rest) => {
+                self.rest = rest;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn union(&mut self) -> Result<Type, TypeParseError> {
+        let mut members = vec![self.intersection()?];
+        while self.eat("|") {
+            members.push(self.intersection()?);
+        }
+        Ok(TypeHierarchy::new().union(members))
+    }
+
+    fn intersection(&mut self) -> Result<Type, TypeParseError> {
+        let mut members = vec![self.postfix()?];
+        while self.eat("&") {
+            members.push(self.postfix()?);
+        }
+        Ok(match members.len() {
+            1 => members.remove(0),
+            _ => Type::Intersection(members.into_iter().collect()),
+        })
+    }
+
+    fn postfix(&mut self) -> Result<Type, TypeParseError> {
+        let mut parsed = self.atom()?;
+        loop {
+            if self.eat("[]") {
+                parsed = Type::Array(Box::new(parsed));
+            } else if self.eat("?") {
+                parsed = parsed.nullable();
+            } else {
+                return Ok(parsed);
+            }
+        }
+    }
+
+    fn atom(&mut self) -> Result<Type, TypeParseError> {
+        if self.eat("(") {
+            let inner = self.union()?;
+            if !self.eat(")") {
+                return Err(self.error("missing ')'"));
+            }
+            return Ok(inner);
+        }
+        let trimmed = self.rest.trim_start();
+        let end = trimmed
+            .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '-' || c == '.'))
+            .unwrap_or(trimmed.len());
+        if end == 0 {
+            return Err(self.error("expected a type name"));
+        }
+        let name = &trimmed[..end];
+        self.rest = &trimmed[end..];
+        if name == "array" && self.eat("<") {
+            let item = self.union()?;
+            if !self.eat(">") {
+                return Err(self.error("missing '>'"));
+            }
+            return Ok(Type::Array(Box::new(item)));
+        }
+        Ok(Type::builtin(name).unwrap_or_else(|| Type::Named(name.to_string())))
+    }
+}
+
+/// How a value of one type can be stored in a slot of another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Assignability {
+    /// The value type is a subtype of the target.
+    Assignable,
+    /// The value must be converted or checked first, e.g. `number` to `int`
+    /// or `string` to a named string type.
+    RequiresCoercion,
+    Incompatible,
+}
+
+/// Bound on supertype-chain walks, guarding against cyclic declarations.
+const MAX_SUPERTYPE_DEPTH: usize = 64;
+
+/// Supertypes of named types. Built-in relationships (`int` below
+/// `number`, everything below `any`) need no declaration.
+#[derive(Debug, Clone, Default)]
+pub struct TypeHierarchy {
+    supertypes: BTreeMap<String, Type>,
+}
+
+impl TypeHierarchy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declare `name` as a subtype of `supertype`.
+    pub fn declare(&mut self, name: &str, supertype: Type) -> &mut Self {
+        self.supertypes.insert(name.to_string(), supertype);
+        self
+    }
+
+    /// The hierarchy of registered types. A definition's `extends`, or
+    /// failing that its `type`, is parsed as the supertype.
+    pub async fn load(storage: &dyn ConceptStorage) -> StorageResult<Self> {
+        let mut hierarchy = Self::new();
+        for record in storage.find("type_def", None).await? {
+            let Some(type_id) = record["type_id"].as_str() else {
+                continue;
+            };
+            if Type::builtin(type_id).is_some() {
+                continue;
+            }
+            let definition = &record["definition"];
+            let supertype = definition["extends"]
+                .as_str()
+                .or_else(|| definition["type"].as_str())
+                .and_then(|s| Type::parse(s).ok())
+                .unwrap_or(Type::Any);
+            hierarchy.declare(type_id, supertype);
+        }
+        Ok(hierarchy)
+    }
+
+    /// Whether every named type inside `ty` has been declared.
+    pub fn is_known(&self, ty: &Type) -> bool {
+        match ty {
+            Type::Named(name) => self.supertypes.contains_key(name),
+            Type::Array(item) => self.is_known(item),
+            Type::Union(members) | Type::Intersection(members) => {
+                members.iter().all(|m| self.is_known(m))
+            }
+            _ => true,
+        }
+    }
+
+    fn supertype(&self, ty: &Type) -> Option<Type> {
+        match ty {
+            Type::Int => Some(Type::Number),
+            Type::Named(name) => self.supertypes.get(name).cloned(),
+            _ => None,
+        }
+    }
+
+    /// The built-in type a named type ultimately derives from.
+    fn base(&self, ty: &Type) -> Type {
+        let mut current = ty.clone();
+        for _ in 0..MAX_SUPERTYPE_DEPTH {
+            match &current {
+                Type::Named(_) => match self.supertype(&current) {
+                    Some(next) => current = next,
+                    None => return Type::Any,
+                },
+                _ => return current,
+            }
+        }
+        Type::Any
+    }
+
+    pub fn is_subtype(&self, a: &Type, b: &Type) -> bool {
+        self.subtype_within(a, b, MAX_SUPERTYPE_DEPTH)
+    }
+
+    fn subtype_within(&self, a: &Type, b: &Type, depth: usize) -> bool {
+        if a == b || *a == Type::Never || *b == Type::Any {
+            return true;
+        }
+        match (a, b) {
+            (Type::Union(members), _) => members.iter().all(|m| self.subtype_within(m, b, depth)),
+            (_, Type::Intersection(members)) => {
+                members.iter().all(|m| self.subtype_within(a, m, depth))
+            }
+            (_, Type::Union(members)) => members.iter().any(|m| self.subtype_within(a, m, depth)),
+            (Type::Intersection(members), _) => {
+                members.iter().any(|m| self.subtype_within(m, b, depth))
+            }
+            (Type::Array(x), Type::Array(y)) => self.subtype_within(x, y, depth),
+            _ => match self.supertype(a) {
+                Some(parent) if depth > 0 => self.subtype_within(&parent, b, depth - 1),
+                _ => false,
+            },
+        }
+    }
+
+    /// The most specific type both `a` and `b` are subtypes of: one of
+    /// them, a shared declared ancestor, or else their union.
+    pub fn common_supertype(&self, a: &Type, b: &Type) -> Type {
+        if self.is_subtype(a, b) {
+            return b.clone();
+        }
+        if self.is_subtype(b, a) {
+            return a.clone();
+        }
+        if let (Type::Array(x), Type::Array(y)) = (a, b) {
+            return Type::Array(Box::new(self.common_supertype(x, y)));
+        }
+        let mut ancestor = self.supertype(a);
+        for _ in 0..MAX_SUPERTYPE_DEPTH {
+            match ancestor {
+                Some(candidate) if self.is_subtype(b, &candidate) => return candidate,
+                Some(candidate) => ancestor = self.supertype(&candidate),
+                None => break,
+            }
+        }
+        self.union([a.clone(), b.clone()])
+    }
+
+    /// A union of `types`, flattened, with members subsumed by another
+    /// member dropped. No members gives `never`.
+    pub fn union(&self, types: impl IntoIterator<Item = Type>) -> Type {
+        let mut members = BTreeSet::new();
+        for ty in types {
+            match ty {
+                Type::Any => return Type::Any,
+                Type::Never => {}
+                Type::Union(inner) => members.extend(inner),
+                other => {
+                    members.insert(other);
+                }
+            }
+        }
+        let kept: BTreeSet<Type> = members
+            .iter()
+            .filter(|m| {
+                !members.iter().any(|other| {
+                    other != *m
+                        && self.is_subtype(m, other)
+                        // Mutually related members keep the first
+                        && !(self.is_subtype(other, m) && other > *m)
+                })
+            })
+            .cloned()
+            .collect();
+        match kept.len() {
+            0 => Type::Never,
+            1 => kept.into_iter().next().unwrap_or(Type::Never),
+            _ => Type::Union(kept),
+        }
+    }
+
+    /// An intersection of `types`. Unrelated built-in types have no common
+    /// values and give `never`; unions distribute.
+    pub fn intersection(&self, types: impl IntoIterator<Item = Type>) -> Type {
+        types
+            .into_iter()
+            .fold(Type::Any, |acc, ty| self.intersect(&acc, &ty))
+    }
+
+    fn intersect(&self, a: &Type, b: &Type) -> Type {
+        if self.is_subtype(a, b) {
+            return a.clone();
+        }
+        if self.is_subtype(b, a) {
+            return b.clone();
+        }
+        match (a, b) {
+            (Type::Union(members), other) | (other, Type::Union(members)) => {
+                self.union(members.iter().map(|m| self.intersect(m, other)))
+            }
+            (Type::Array(x), Type::Array(y)) => match self.intersect(x, y) {
+                Type::Never => Type::Never,
+                item => Type::Array(Box::new(item)),
+            },
+            _ if !matches!(a, Type::Named(_) | Type::Intersection(_))
+                && !matches!(b, Type::Named(_) | Type::Intersection(_)) =>
+            {
+                Type::Never
+            }
+            _ => {
+                let mut members = BTreeSet::new();
+                for ty in [a, b] {
+                    match ty {
+                        Type::Intersection(inner) => members.extend(inner.iter().cloned()),
+                        other => {
+                            members.insert(other.clone());
+                        }
+                    }
+                }
+                Type::Intersection(members)
+            }
+        }
+    }
+
+    /// Whether a `value` type may be stored where `target` is expected.
+    pub fn assignable(&self, value: &Type, target: &Type) -> Assignability {
+        if self.is_subtype(value, target) {
+            return Assignability::Assignable;
+        }
+        match (value, target) {
+            (Type::Union(members), _) => members
+                .iter()
+                .map(|m| self.assignable(m, target))
+                .max()
+                .unwrap_or(Assignability::Assignable),
+            (_, Type::Union(members)) => members
+                .iter()
+                .map(|m| self.assignable(value, m))
+                .min()
+                .unwrap_or(Assignability::Incompatible),
+            (_, Type::Intersection(members)) => members
+                .iter()
+                .map(|m| self.assignable(value, m))
+                .max()
+                .unwrap_or(Assignability::Assignable),
+            (Type::Intersection(members), _) => members
+                .iter()
+                .map(|m| self.assignable(m, target))
+                .min()
+                .unwrap_or(Assignability::Incompatible),
+            (Type::Array(x), Type::Array(y)) => match self.assignable(x, y) {
+                Assignability::Incompatible => Assignability::Incompatible,
+                _ => Assignability::RequiresCoercion,
+            },
+            _ => {
+                // Narrowing to a named type, or converting between bases
+                let (from, to) = (self.base(value), self.base(target));
+                if *value == Type::Any
+                    || self.is_subtype(&from, &to)
+                    || Self::convertible(&from, &to)
+                {
+                    Assignability::RequiresCoercion
+                } else {
+                    Assignability::Incompatible
+                }
+            }
+        }
+    }
+
+    /// Built-in conversions that can succeed for some values.
+    fn convertible(from: &Type, to: &Type) -> bool {
+        use Type::*;
+        matches!(
+            (from, to),
+            (Any, _)
+                | (Number, Int)
+                | (String, Boolean | Int | Number | Date | DateTime)
+                | (Boolean | Int | Number | Date | DateTime, String)
+                | (Date, DateTime)
+                | (DateTime, Date)
+        )
+    }
+}
+
 pub struct TypeSystemHandler;
 
 impl TypeSystemHandler {
@@ -385,4 +887,109 @@ mod tests {
 
         assert!(matches!(result, ValidateOutput::Invalid { .. }));
     }
+
+    // ── type relationship tests ────────────────────────────
+
+    #[test]
+    fn int_is_a_subtype_of_number_but_not_the_reverse() {
+        let types = TypeHierarchy::new();
+        assert!(types.is_subtype(&Type::Int, &Type::Number));
+        assert!(!types.is_subtype(&Type::Number, &Type::Int));
+
+        assert_eq!(
+            types.assignable(&Type::Int, &Type::Number),
+            Assignability::Assignable
+        );
+        assert_eq!(
+            types.assignable(&Type::Number, &Type::Int),
+            Assignability::RequiresCoercion
+        );
+        assert_eq!(
+            types.assignable(&Type::Object, &Type::Int),
+            Assignability::Incompatible
+        );
+    }
+
+    #[test]
+    fn unions_nullables_and_common_supertypes() {
+        let mut types = TypeHierarchy::new();
+        types
+            .declare("email", Type::String)
+            .declare("work_email", Type::Named("email".into()));
+        let email = Type::Named("email".into());
+        let work_email = Type::Named("work_email".into());
+
+        let nullable_int = Type::parse("int?").unwrap();
+        assert_eq!(nullable_int.to_string(), "int | null");
+        assert!(nullable_int.is_nullable());
+        assert_eq!(
+            types.assignable(&Type::Null, &nullable_int),
+            Assignability::Assignable
+        );
+        assert_eq!(
+            types.assignable(&Type::Null, &Type::Int),
+            Assignability::Incompatible
+        );
+        assert_eq!(
+            types.assignable(&nullable_int, &Type::Number),
+            Assignability::Incompatible
+        );
+
+        assert_eq!(types.union([Type::Int, Type::Number]), Type::Number);
+        assert_eq!(
+            Type::parse("int | string | int").unwrap().to_string(),
+            "int | string"
+        );
+        assert_eq!(
+            types.intersection([Type::parse("int | string").unwrap(), Type::Number]),
+            Type::Int
+        );
+        assert_eq!(types.intersection([Type::String, Type::Int]), Type::Never);
+
+        assert!(types.is_subtype(&work_email, &Type::String));
+        assert_eq!(
+            types.common_supertype(&work_email, &Type::parse("email").unwrap()),
+            email
+        );
+        assert_eq!(
+            types.common_supertype(&Type::Int, &Type::Number),
+            Type::Number
+        );
+        assert_eq!(
+            types
+                .common_supertype(&Type::Int, &Type::String)
+                .to_string(),
+            "int | string"
+        );
+        assert_eq!(
+            types.assignable(&Type::String, &email),
+            Assignability::RequiresCoercion
+        );
+        assert_eq!(
+            Type::of_value(&json!([1, 2.5])),
+            Type::parse("array<number>").unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn hierarchy_loads_registered_types() {
+        let storage = InMemoryStorage::new();
+        let handler = TypeSystemHandler;
+        handler
+            .register_type(
+                RegisterTypeInput {
+                    type_id: "percent".into(),
+                    definition: json!({ "type": "number", "minimum": 0 }),
+                },
+                &storage,
+            )
+            .await
+            .unwrap();
+
+        let types = TypeHierarchy::load(&storage).await.unwrap();
+        let percent = Type::parse("percent").unwrap();
+        assert!(types.is_known(&percent));
+        assert!(types.is_subtype(&percent, &Type::Number));
+        assert!(!types.is_known(&Type::parse("text").unwrap()));
+    }
 }
@@ -3,10 +3,18 @@
 // Infrastructure suite — registers validation constraints, adds rules to
 // schemas, validates nodes against proposed changes, and validates
 // individual field values.
+//
+// Validators can also be composed in code: leaf rules (`required`,
+// `pattern`, `one_of`, `range`) combine with `all`, `any`, `not` and
+// `when`, and `field` scopes a validator to a path. Every failure is
+// collected into a `ValidationReport` rather than stopping at the first.
+
+use std::collections::BTreeMap;
 
 use crate::storage::{ConceptStorage, StorageResult};
+use regex::Regex;
 use serde::{Deserialize, Serialize};
-use serde_json::json;
+use serde_json::{json, Value};
 
 // ── RegisterConstraint ────────────────────────────────────
 
@@ -75,6 +83,388 @@ pub enum ValidatorValidateFieldOutput {
     Invalid { errors: String },
 }
 
+// ── Combinators ───────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidationError {
+    pub rule: String,
+    pub message: String,
+}
+
+/// Failures keyed by field path; the record itself is under "".
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    errors: BTreeMap<String, Vec<ValidationError>>,
+}
+
+impl ValidationReport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn add(&mut self, path: &str, rule: &str, message: impl Into<String>) {
+        self.errors
+            .entry(path.to_string())
+            .or_default()
+            .push(ValidationError {
+                rule: rule.to_string(),
+                message: message.into(),
+            });
+    }
+
+    pub fn merge(&mut self, other: ValidationReport) {
+        for (path, errors) in other.errors {
+            self.errors.entry(path).or_default().extend(errors);
+        }
+    }
+
+    pub fn errors(&self) -> &BTreeMap<String, Vec<ValidationError>> {
+        &self.errors
+    }
+
+    pub fn errors_for(&self, path: &str) -> &[ValidationError] {
+        self.errors.get(path).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    pub fn error_count(&self) -> usize {
+        self.errors.values().map(Vec::len).sum()
+    }
+
+    /// The report as data_quality violations (`rule`, `field`, `message`,
+    /// `severity`), so both concepts surface failures the same way.
+    pub fn violations(&self) -> Vec<Value> {
+        self.errors
+            .iter()
+            .flat_map(|(path, errors)| {
+                errors.iter().map(move |error| {
+                    json!({
+                        "rule": error.rule,
+                        "field": path,
+                        "message": error.message,
+                        "severity": "error",
+                    })
+                })
+            })
+            .collect()
+    }
+}
+
+/// A check against one value. `path` locates the value in the record and is
+/// where failures are reported.
+pub trait Validator: Send + Sync {
+    fn rule(&self) -> &str;
+
+    fn check(&self, value: &Value, path: &str, report: &mut ValidationReport);
+
+    /// Validate a whole record.
+    fn validate(&self, value: &Value) -> ValidationReport {
+        let mut report = ValidationReport::new();
+        self.check(value, "", &mut report);
+        report
+    }
+}
+
+pub type BoxedValidator = Box<dyn Validator>;
+
+fn label(path: &str) -> &str {
+    if path.is_empty() {
+        "value"
+    } else {
+        path
+    }
+}
+
+fn is_blank(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::String(s) => s.trim().is_empty(),
+        _ => false,
+    }
+}
+
+struct Required;
+
+impl Validator for Required {
+    fn rule(&self) -> &str {
+        "required"
+    }
+
+    fn check(&self, value: &Value, path: &str, report: &mut ValidationReport) {
+        if is_blank(value) {
+            report.add(path, "required", format!("{} is required", label(path)));
+        }
+    }
+}
+
+/// Fails on null, missing and blank string values.
+pub fn required() -> BoxedValidator {
+    Box::new(Required)
+}
+
+struct Pattern(Regex);
+
+impl Validator for Pattern {
+    fn rule(&self) -> &str {
+        "pattern"
+    }
+
+    fn check(&self, value: &Value, path: &str, report: &mut ValidationReport) {
+        let text = match value {
+            Value::Null => return,
+            Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+        if !self.0.is_match(&text) {
+            report.add(
+                path,
+                "pattern",
+                format!("{} does not match {}", label(path), self.0.as_str()),
+            );
+        }
+    }
+}
+
+/// Fails when the value, as text, does not match `pattern`. Null values pass;
+/// combine with `required` to reject them.
+pub fn pattern(pattern: &str) -> Result<BoxedValidator, regex::Error> {
+    Ok(Box::new(Pattern(Regex::new(pattern)?)))
+}
+
+struct OneOf(Vec<Value>);
+
+impl Validator for OneOf {
+    fn rule(&self) -> &str {
+        "enum_check"
+    }
+
+    fn check(&self, value: &Value, path: &str, report: &mut ValidationReport) {
+        if !value.is_null() && !self.0.contains(value) {
+            let allowed: Vec<String> = self.0.iter().map(Value::to_string).collect();
+            report.add(
+                path,
+                "enum_check",
+                format!("{} must be one of {}", label(path), allowed.join(", ")),
+            );
+        }
+    }
+}
+
+/// Fails when the value is not one of `values`, as the enum_check quality
+/// rule does. Null values pass.
+pub fn one_of(values: Vec<Value>) -> BoxedValidator {
+    Box::new(OneOf(values))
+}
+
+struct Range {
+    min: Option<Value>,
+    max: Option<Value>,
+}
+
+/// A number, or a string holding one (form inputs submit numbers as text).
+fn numeric(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse::<f64>().ok().filter(|f| f.is_finite()),
+        _ => None,
+    }
+}
+
+fn compare(a: &Value, b: &Value) -> Option<std::cmp::Ordering> {
+    match (numeric(a), numeric(b)) {
+        (Some(a), Some(b)) => a.partial_cmp(&b),
+        _ => match (a, b) {
+            (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+            _ => None,
+        },
+    }
+}
+
+impl Validator for Range {
+    fn rule(&self) -> &str {
+        "range"
+    }
+
+    fn check(&self, value: &Value, path: &str, report: &mut ValidationReport) {
+        if value.is_null() {
+            return;
+        }
+        let bounds = [
+            (&self.min, std::cmp::Ordering::Less, "at least"),
+            (&self.max, std::cmp::Ordering::Greater, "at most"),
+        ];
+        for (bound, outside, word) in bounds {
+            let Some(bound) = bound else { continue };
+            match compare(value, bound) {
+                Some(ordering) if ordering == outside => {
+                    report.add(
+                        path,
+                        "range",
+                        format!("{} must be {} {}", label(path), word, bound),
+                    );
+                }
+                Some(_) => {}
+                None => {
+                    report.add(
+                        path,
+                        "range",
+                        format!("{} is not comparable with {}", label(path), bound),
+                    );
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// Fails when the value falls outside the inclusive `min`/`max` bounds.
+/// Numbers and numeric strings compare numerically, other strings (such as
+/// ISO dates) lexically.
+/// Null values pass.
+pub fn range(min: Option<Value>, max: Option<Value>) -> BoxedValidator {
+    Box::new(Range { min, max })
+}
+
+struct All(Vec<BoxedValidator>);
+
+impl Validator for All {
+    fn rule(&self) -> &str {
+        "all"
+    }
+
+    fn check(&self, value: &Value, path: &str, report: &mut ValidationReport) {
+        for validator in &self.0 {
+            validator.check(value, path, report);
+        }
+    }
+}
+
+/// Runs every validator, keeping all of their failures.
+pub fn all(validators: Vec<BoxedValidator>) -> BoxedValidator {
+    Box::new(All(validators))
+}
+
+struct Any(Vec<BoxedValidator>);
+
+impl Validator for Any {
+    fn rule(&self) -> &str {
+        "any"
+    }
+
+    fn check(&self, value: &Value, path: &str, report: &mut ValidationReport) {
+        let mut failures = ValidationReport::new();
+        for validator in &self.0 {
+            let mut attempt = ValidationReport::new();
+            validator.check(value, path, &mut attempt);
+            if attempt.is_valid() {
+                return;
+            }
+            failures.merge(attempt);
+        }
+        report.merge(failures);
+    }
+}
+
+/// Passes when at least one validator passes; otherwise reports the
+/// failures of each.
+pub fn any(validators: Vec<BoxedValidator>) -> BoxedValidator {
+    Box::new(Any(validators))
+}
+
+struct Not(BoxedValidator);
+
+impl Validator for Not {
+    fn rule(&self) -> &str {
+        "not"
+    }
+
+    fn check(&self, value: &Value, path: &str, report: &mut ValidationReport) {
+        let mut inner = ValidationReport::new();
+        self.0.check(value, path, &mut inner);
+        if inner.is_valid() {
+            report.add(
+                path,
+                "not",
+                format!("{} must not satisfy {}", label(path), self.0.rule()),
+            );
+        }
+    }
+}
+
+/// Passes exactly when `validator` fails.
+pub fn not(validator: BoxedValidator) -> BoxedValidator {
+    Box::new(Not(validator))
+}
+
+struct When<P> {
+    predicate: P,
+    validator: BoxedValidator,
+}
+
+impl<P> Validator for When<P>
+where
+    P: Fn(&Value) -> bool + Send + Sync,
+{
+    fn rule(&self) -> &str {
+        self.validator.rule()
+    }
+
+    fn check(&self, value: &Value, path: &str, report: &mut ValidationReport) {
+        if (self.predicate)(value) {
+            self.validator.check(value, path, report);
+        }
+    }
+}
+
+/// Applies `validator` only when `predicate` holds for the value. Put it
+/// around a `field` validator to condition one field on another.
+pub fn when<P>(predicate: P, validator: BoxedValidator) -> BoxedValidator
+where
+    P: Fn(&Value) -> bool + Send + Sync + 'static,
+{
+    Box::new(When {
+        predicate,
+        validator,
+    })
+}
+
+struct Field {
+    path: String,
+    validator: BoxedValidator,
+}
+
+impl Validator for Field {
+    fn rule(&self) -> &str {
+        self.validator.rule()
+    }
+
+    fn check(&self, value: &Value, path: &str, report: &mut ValidationReport) {
+        let target = self
+            .path
+            .split('.')
+            .try_fold(value, |current, key| current.get(key))
+            .unwrap_or(&Value::Null);
+        let full_path = if path.is_empty() {
+            self.path.clone()
+        } else {
+            format!("{}.{}", path, self.path)
+        };
+        self.validator.check(target, &full_path, report);
+    }
+}
+
+/// Scopes `validator` to the dotted `path` below the current value. A
+/// missing field is validated as null.
+pub fn field(path: &str, validator: BoxedValidator) -> BoxedValidator {
+    Box::new(Field {
+        path: path.to_string(),
+        validator,
+    })
+}
+
 // ── Handler ───────────────────────────────────────────────
 
 pub struct ValidatorHandler;
@@ -521,4 +911,98 @@ mod tests {
             ValidatorValidateFieldOutput::Ok { valid: true }
         ));
     }
+
+    // ── combinator tests ───────────────────────────────────
+
+    #[test]
+    fn combined_required_and_pattern_errors_accumulate() {
+        let signup = all(vec![
+            field("name", required()),
+            field(
+                "email",
+                all(vec![required(), pattern(r"^[^@\s]+@[^@\s]+$").unwrap()]),
+            ),
+            field("address.zip", pattern(r"^\d{5}$").unwrap()),
+        ]);
+
+        let report = signup.validate(&json!({
+            "name": "  ",
+            "email": "not-an-email",
+            "address": { "zip": "12AB" },
+        }));
+
+        assert!(!report.is_valid());
+        assert_eq!(report.error_count(), 3);
+        assert_eq!(report.errors_for("name")[0].message, "name is required");
+        assert_eq!(report.errors_for("email")[0].rule, "pattern");
+        assert_eq!(report.errors_for("address.zip")[0].rule, "pattern");
+
+        let missing = signup.validate(&json!({}));
+        let rules: Vec<&str> = missing
+            .errors_for("email")
+            .iter()
+            .map(|e| e.rule.as_str())
+            .collect();
+        assert_eq!(rules, vec!["required"]);
+        assert_eq!(
+            missing.errors().keys().collect::<Vec<_>>(),
+            vec!["email", "name"]
+        );
+        assert_eq!(missing.violations()[0]["field"], "email");
+
+        let ok = signup.validate(&json!({
+            "name": "Ada",
+            "email": "ada@example.com",
+            "address": { "zip": "02139" },
+        }));
+        assert!(ok.is_valid());
+    }
+
+    #[test]
+    fn any_not_and_when_compose() {
+        let contact = any(vec![field("email", required()), field("phone", required())]);
+        assert!(contact.validate(&json!({ "phone": "555-0100" })).is_valid());
+        assert_eq!(contact.validate(&json!({})).error_count(), 2);
+
+        let not_admin = field("role", not(pattern("^admin$").unwrap()));
+        let report = not_admin.validate(&json!({ "role": "admin" }));
+        assert_eq!(
+            report.errors_for("role")[0].message,
+            "role must not satisfy pattern"
+        );
+        assert!(not_admin.validate(&json!({ "role": "editor" })).is_valid());
+
+        let zip_for_us = when(|record| record["country"] == "US", field("zip", required()));
+        assert!(zip_for_us.validate(&json!({ "country": "FR" })).is_valid());
+        assert!(!zip_for_us.validate(&json!({ "country": "US" })).is_valid());
+    }
+
+    #[test]
+    fn one_of_and_range_check_values() {
+        let status = field("status", one_of(vec![json!("draft"), json!("published")]));
+        assert!(status.validate(&json!({ "status": "draft" })).is_valid());
+        assert!(status.validate(&json!({})).is_valid());
+        assert_eq!(
+            status
+                .validate(&json!({ "status": "gone" }))
+                .errors_for("status")[0]
+                .rule,
+            "enum_check"
+        );
+
+        let rating = range(Some(json!(1)), Some(json!(5)));
+        assert!(rating.validate(&json!(3.5)).is_valid());
+        assert_eq!(
+            rating.validate(&json!(6)).errors_for("")[0].message,
+            "value must be at most 5"
+        );
+        assert!(!rating.validate(&json!("high")).is_valid());
+        assert!(rating.validate(&json!("4")).is_valid());
+        assert!(!rating.validate(&json!("10")).is_valid());
+        assert!(range(Some(json!("2")), None).validate(&json!("10")).is_valid());
+
+        let since = range(Some(json!("2024-01-01")), None);
+        assert!(since.validate(&json!("2024-06-30")).is_valid());
+        assert!(!since.validate(&json!("2023-12-31")).is_valid());
+    }
 }
@@ -1,6 +1,7 @@
 // Version Concept Implementation (Rust)
 //
-// Manages version history with snapshots, rollback, and diff.
+// Manages version history with snapshots, rollback, restore, line-level
+// diff, and retention pruning.
 // See Architecture doc Sections on version control and history.
 
 use crate::storage::{ConceptStorage, StorageResult};
@@ -77,6 +78,139 @@ pub enum DiffOutput {
     NotFound { message: String },
 }
 
+// ── Restore ───────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestoreInput {
+    pub entity_id: String,
+    pub version_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "variant")]
+pub enum RestoreOutput {
+    #[serde(rename = "ok")]
+    Ok {
+        entity_id: String,
+        version_id: String,
+    },
+    #[serde(rename = "version_notfound")]
+    VersionNotFound { message: String },
+}
+
+// ── Prune ─────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PruneInput {
+    pub entity_id: String,
+    pub keep_last_n: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "variant")]
+pub enum PruneOutput {
+    #[serde(rename = "ok")]
+    Ok { entity_id: String, pruned: u64 },
+}
+
+// ── Line diff ─────────────────────────────────────────────
+
+/// One line-level difference between two versions. `line` is 1-based: in
+/// the newer version for `Added` and `Modified`, in the older for `Removed`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum Change {
+    Added { line: usize, text: String },
+    Removed { line: usize, text: String },
+    Modified { line: usize, from: String, to: String },
+}
+
+/// The text a snapshot is diffed as: string snapshots as-is, anything else
+/// as pretty-printed JSON so each field lands on its own line.
+fn snapshot_text(data: &serde_json::Value) -> String {
+    match data {
+        serde_json::Value::String(s) => s.clone(),
+        other => serde_json::to_string_pretty(other).unwrap_or_default(),
+    }
+}
+
+/// Line-level changes from `a` to `b`. Within each changed region, removed
+/// and added lines are paired up as modifications; the remainder are plain
+/// removals or additions.
+pub fn diff_lines(a: &str, b: &str) -> Vec<Change> {
+    let a: Vec<&str> = a.lines().collect();
+    let b: Vec<&str> = b.lines().collect();
+    let mut matches = crate::synced_content::line_matches(&a, &b);
+    matches.push((a.len(), b.len()));
+
+    let mut changes = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    for (next_a, next_b) in matches {
+        let removed = &a[i..next_a];
+        let added = &b[j..next_b];
+        let paired = removed.len().min(added.len());
+        for k in 0..paired {
+            changes.push(Change::Modified { line: j + k + 1, from: removed[k].to_string(), to: added[k].to_string() });
+        }
+        for (k, text) in removed.iter().enumerate().skip(paired) {
+            changes.push(Change::Removed { line: i + k + 1, text: text.to_string() });
+        }
+        for (k, text) in added.iter().enumerate().skip(paired) {
+            changes.push(Change::Added { line: j + k + 1, text: text.to_string() });
+        }
+        i = next_a + 1;
+        j = next_b + 1;
+    }
+    changes
+}
+
+/// Line-level changes between two stored versions, or `None` when either
+/// version does not exist.
+pub async fn diff_versions(
+    version_a: &str,
+    version_b: &str,
+    storage: &dyn ConceptStorage,
+) -> StorageResult<Option<Vec<Change>>> {
+    let (Some(a), Some(b)) = (
+        storage.get("version_history", version_a).await?,
+        storage.get("version_history", version_b).await?,
+    ) else {
+        return Ok(None);
+    };
+    Ok(Some(diff_lines(&snapshot_text(&a["snapshot_data"]), &snapshot_text(&b["snapshot_data"]))))
+}
+
+/// An entity's versions, oldest first.
+async fn history(entity_id: &str, storage: &dyn ConceptStorage) -> StorageResult<Vec<serde_json::Value>> {
+    let mut versions = storage
+        .find("version_history", Some(&json!({ "entity_id": entity_id })))
+        .await?;
+    versions.sort_by(|a, b| {
+        let key = |v: &serde_json::Value| {
+            (v["created_at"].as_str().unwrap_or("").to_string(), v["version_id"].as_str().unwrap_or("").to_string())
+        };
+        key(a).cmp(&key(b))
+    });
+    Ok(versions)
+}
+
+/// A fresh version id. Ids are timestamp-based, so a suffix disambiguates
+/// versions created within the same millisecond.
+async fn new_version_id(
+    entity_id: &str,
+    now: chrono::DateTime<chrono::Utc>,
+    storage: &dyn ConceptStorage,
+) -> StorageResult<String> {
+    let base = format!("v_{}_{}", entity_id, now.timestamp_millis());
+    let mut version_id = base.clone();
+    let mut n = 1;
+    while storage.get("version_history", &version_id).await?.is_some() {
+        version_id = format!("{}_{}", base, n);
+        n += 1;
+    }
+    Ok(version_id)
+}
+
 // ── Handler ───────────────────────────────────────────────
 
 pub struct VersionHandler;
@@ -88,7 +222,7 @@ impl VersionHandler {
         storage: &dyn ConceptStorage,
     ) -> StorageResult<SnapshotOutput> {
         let now = chrono::Utc::now();
-        let version_id = format!("v_{}_{}", input.entity_id, now.timestamp_millis());
+        let version_id = new_version_id(&input.entity_id, now, storage).await?;
 
         let snapshot_data: serde_json::Value =
             serde_json::from_str(&input.snapshot_data).unwrap_or(json!({}));
@@ -153,107 +287,109 @@ impl VersionHandler {
         input: RollbackInput,
         storage: &dyn ConceptStorage,
     ) -> StorageResult<RollbackOutput> {
-        let version = storage
-            .get("version_history", &input.version_id)
+        let restored = self
+            .restore(
+                RestoreInput {
+                    entity_id: input.entity_id,
+                    version_id: input.version_id,
+                },
+                storage,
+            )
             .await?;
 
-        match version {
-            None => Ok(RollbackOutput::VersionNotFound {
-                message: format!("Version '{}' not found", input.version_id),
-            }),
-            Some(version_record) => {
-                let entity_id_in_version = version_record["entity_id"]
-                    .as_str()
-                    .unwrap_or("")
-                    .to_string();
-
-                if entity_id_in_version != input.entity_id {
-                    return Ok(RollbackOutput::VersionNotFound {
-                        message: format!(
-                            "Version '{}' does not belong to entity '{}'",
-                            input.version_id, input.entity_id
-                        ),
-                    });
-                }
-
-                // Create a new snapshot representing the rollback
-                let now = chrono::Utc::now();
-                let rollback_version_id =
-                    format!("v_{}_{}", input.entity_id, now.timestamp_millis());
-
-                storage
-                    .put(
-                        "version_history",
-                        &rollback_version_id,
-                        json!({
-                            "version_id": rollback_version_id,
-                            "entity_id": input.entity_id,
-                            "snapshot_data": version_record["snapshot_data"],
-                            "created_at": now.to_rfc3339(),
-                            "rollback_from": input.version_id,
-                        }),
-                    )
-                    .await?;
-
-                Ok(RollbackOutput::Ok {
-                    entity_id: input.entity_id,
-                })
-            }
-        }
+        Ok(match restored {
+            RestoreOutput::Ok { entity_id, .. } => RollbackOutput::Ok { entity_id },
+            RestoreOutput::VersionNotFound { message } => RollbackOutput::VersionNotFound { message },
+        })
     }
 
-    pub async fn diff(
+    /// Record a new version whose content equals `version_id`. History is
+    /// only ever appended to, so the restore itself can be undone.
+    pub async fn restore(
         &self,
-        input: DiffInput,
+        input: RestoreInput,
         storage: &dyn ConceptStorage,
-    ) -> StorageResult<DiffOutput> {
-        let version_a = storage
-            .get("version_history", &input.version_a)
-            .await?;
-        let version_b = storage
-            .get("version_history", &input.version_b)
+    ) -> StorageResult<RestoreOutput> {
+        let version = storage
+            .get("version_history", &input.version_id)
             .await?;
 
-        if version_a.is_none() {
-            return Ok(DiffOutput::NotFound {
-                message: format!("Version '{}' not found", input.version_a),
+        let Some(version_record) = version else {
+            return Ok(RestoreOutput::VersionNotFound {
+                message: format!("Version '{}' not found", input.version_id),
             });
-        }
-        if version_b.is_none() {
-            return Ok(DiffOutput::NotFound {
-                message: format!("Version '{}' not found", input.version_b),
+        };
+
+        if version_record["entity_id"].as_str() != Some(input.entity_id.as_str()) {
+            return Ok(RestoreOutput::VersionNotFound {
+                message: format!(
+                    "Version '{}' does not belong to entity '{}'",
+                    input.version_id, input.entity_id
+                ),
             });
         }
 
-        let data_a = &version_a.unwrap()["snapshot_data"];
-        let data_b = &version_b.unwrap()["snapshot_data"];
+        let now = chrono::Utc::now();
+        let version_id = new_version_id(&input.entity_id, now, storage).await?;
 
-        // Simple diff: report fields that differ
-        let mut changes: Vec<serde_json::Value> = vec![];
+        storage
+            .put(
+                "version_history",
+                &version_id,
+                json!({
+                    "version_id": version_id,
+                    "entity_id": input.entity_id,
+                    "snapshot_data": version_record["snapshot_data"],
+                    "created_at": now.to_rfc3339(),
+                    "rollback_from": input.version_id,
+                }),
+            )
+            .await?;
+
+        Ok(RestoreOutput::Ok {
+            entity_id: input.entity_id,
+            version_id,
+        })
+    }
+
+    /// Delete all but the newest `keep_last_n` versions of an entity.
+    pub async fn prune(
+        &self,
+        input: PruneInput,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<PruneOutput> {
+        let versions = history(&input.entity_id, storage).await?;
+        let excess = versions.len().saturating_sub(input.keep_last_n as usize);
 
-        if let (Some(obj_a), Some(obj_b)) = (data_a.as_object(), data_b.as_object()) {
-            // Fields in A or B
-            let mut all_keys: Vec<&String> = obj_a.keys().collect();
-            for key in obj_b.keys() {
-                if !all_keys.contains(&key) {
-                    all_keys.push(key);
-                }
+        for version in &versions[..excess] {
+            if let Some(version_id) = version["version_id"].as_str() {
+                storage.del("version_history", version_id).await?;
             }
+        }
+
+        Ok(PruneOutput::Ok {
+            entity_id: input.entity_id,
+            pruned: excess as u64,
+        })
+    }
 
-            for key in all_keys {
-                let val_a = obj_a.get(key);
-                let val_b = obj_b.get(key);
-
-                if val_a != val_b {
-                    changes.push(json!({
-                        "field": key,
-                        "from": val_a,
-                        "to": val_b,
-                    }));
-                }
+    pub async fn diff(
+        &self,
+        input: DiffInput,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<DiffOutput> {
+        for version_id in [&input.version_a, &input.version_b] {
+            if storage.get("version_history", version_id).await?.is_none() {
+                return Ok(DiffOutput::NotFound {
+                    message: format!("Version '{}' not found", version_id),
+                });
             }
         }
 
+        let changes = diff_versions(&input.version_a, &input.version_b, storage)
+            .await?
+            .unwrap_or_default();
+
         Ok(DiffOutput::Ok {
             entity_id: input.entity_id,
             changes: serde_json::to_string(&changes)?,
@@ -532,4 +668,85 @@ mod tests {
 
         assert!(matches!(result, DiffOutput::NotFound { .. }));
     }
+
+    // ── line diff / restore / prune tests ──────────────────
+
+    async fn snapshot(handler: &VersionHandler, entity_id: &str, data: &str, storage: &InMemoryStorage) -> String {
+        let result = handler
+            .snapshot(
+                SnapshotInput {
+                    entity_id: entity_id.into(),
+                    snapshot_data: data.into(),
+                },
+                storage,
+            )
+            .await
+            .unwrap();
+        match result {
+            SnapshotOutput::Ok { version_id, .. } => version_id,
+        }
+    }
+
+    #[tokio::test]
+    async fn diff_versions_reports_line_changes() {
+        let storage = InMemoryStorage::new();
+        let handler = VersionHandler;
+        let a = snapshot(&handler, "doc", r#""title\nfirst\nsecond\nthird""#, &storage).await;
+        let b = snapshot(&handler, "doc", r#""title\nfirst!\nthird\nfourth""#, &storage).await;
+
+        let changes = diff_versions(&a, &b, &storage).await.unwrap().unwrap();
+        assert_eq!(
+            changes,
+            vec![
+                Change::Modified { line: 2, from: "first".into(), to: "first!".into() },
+                Change::Removed { line: 3, text: "second".into() },
+                Change::Added { line: 4, text: "fourth".into() },
+            ]
+        );
+        assert!(diff_versions(&a, "missing", &storage).await.unwrap().is_none());
+
+        let result = handler
+            .diff(DiffInput { entity_id: "doc".into(), version_a: a, version_b: b }, &storage)
+            .await
+            .unwrap();
+        let DiffOutput::Ok { changes, .. } = result else { panic!("expected Ok variant") };
+        let parsed: Vec<serde_json::Value> = serde_json::from_str(&changes).unwrap();
+        assert_eq!(parsed[0]["kind"], "modified");
+    }
+
+    #[tokio::test]
+    async fn restore_appends_history_and_prune_keeps_newest() {
+        let storage = InMemoryStorage::new();
+        let handler = VersionHandler;
+        let v1 = snapshot(&handler, "doc", r#"{"body":"one"}"#, &storage).await;
+        let v2 = snapshot(&handler, "doc", r#"{"body":"two"}"#, &storage).await;
+        assert_ne!(v1, v2);
+
+        let result = handler
+            .restore(RestoreInput { entity_id: "doc".into(), version_id: v1.clone() }, &storage)
+            .await
+            .unwrap();
+        let RestoreOutput::Ok { version_id: v3, .. } = result else { panic!("expected Ok variant") };
+
+        let versions = history("doc", &storage).await.unwrap();
+        assert_eq!(versions.len(), 3);
+        assert_eq!(versions[2]["version_id"], v3);
+        assert_eq!(versions[2]["snapshot_data"], json!({ "body": "one" }));
+        assert!(diff_versions(&v1, &v3, &storage).await.unwrap().unwrap().is_empty());
+        assert!(storage.get("version_history", &v2).await.unwrap().is_some());
+
+        let result = handler
+            .restore(RestoreInput { entity_id: "other".into(), version_id: v1 }, &storage)
+            .await
+            .unwrap();
+        assert!(matches!(result, RestoreOutput::VersionNotFound { .. }));
+
+        let result = handler
+            .prune(PruneInput { entity_id: "doc".into(), keep_last_n: 2 }, &storage)
+            .await
+            .unwrap();
+        assert!(matches!(result, PruneOutput::Ok { pruned: 1, .. }));
+        let remaining: Vec<_> = history("doc", &storage).await.unwrap().into_iter().map(|v| v["version_id"].clone()).collect();
+        assert_eq!(remaining, vec![json!(v2), json!(v3)]);
+    }
 }
@@ -2,10 +2,17 @@
 //
 // Automation suite — defines workflow states and transitions,
 // performs guarded state transitions, and tracks current state per entity.
+// Multi-step sagas undo their completed steps when a later step fails.
+// Step graphs route on conditions over the workflow context and can fan
+// out into parallel branches that meet again at a join step.
 
+use crate::expression_language::{truthy, FunctionRegistry};
+use crate::relation;
 use crate::storage::{ConceptStorage, StorageResult};
+use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
 // ── DefineState ───────────────────────────────────────────
 
@@ -84,6 +91,449 @@ pub enum WorkflowGetCurrentStateOutput {
     NotFound { message: String },
 }
 
+// ── Sagas ─────────────────────────────────────────────────
+
+/// One side of a saga step: the forward action or its compensation. An
+/// `Err` from a forward action fails the step.
+#[async_trait]
+pub trait SagaAction: Send + Sync {
+    async fn run(&self, context: &serde_json::Value, storage: &dyn ConceptStorage) -> StorageResult<()>;
+}
+
+struct SagaStep {
+    name: String,
+    action: Box<dyn SagaAction>,
+    compensation: Option<Box<dyn SagaAction>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SagaState {
+    Running,
+    /// A step failed and completed steps are being undone.
+    Compensating,
+    /// A step failed and every completed step was compensated.
+    Failed,
+    Completed,
+}
+
+/// Progress of one saga run, persisted in `workflow_saga` after every step
+/// so an interrupted run resumes where it stopped.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SagaRun {
+    pub run_id: String,
+    pub saga_id: String,
+    pub state: SagaState,
+    /// Steps whose forward action succeeded, in execution order.
+    pub completed: Vec<String>,
+    /// Steps undone so far, in compensation order.
+    pub compensated: Vec<String>,
+    pub failed_step: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Ordered steps, each optionally paired with a compensating action.
+pub struct Saga {
+    saga_id: String,
+    steps: Vec<SagaStep>,
+}
+
+impl Saga {
+    pub fn new(saga_id: &str) -> Self {
+        Self {
+            saga_id: saga_id.to_string(),
+            steps: Vec::new(),
+        }
+    }
+
+    pub fn step(mut self, name: &str, action: impl SagaAction + 'static) -> Self {
+        self.steps.push(SagaStep {
+            name: name.to_string(),
+            action: Box::new(action),
+            compensation: None,
+        });
+        self
+    }
+
+    /// A step that is undone by `compensation` if a later step fails.
+    pub fn compensated_step(
+        mut self,
+        name: &str,
+        action: impl SagaAction + 'static,
+        compensation: impl SagaAction + 'static,
+    ) -> Self {
+        self.steps.push(SagaStep {
+            name: name.to_string(),
+            action: Box::new(action),
+            compensation: Some(Box::new(compensation)),
+        });
+        self
+    }
+
+    /// Reorder the steps linked by `relation_id` so each link's source runs
+    /// before its target. Linked steps trade places among the positions they
+    /// already hold; unlinked steps stay where they are. A cycle in the links
+    /// is an error.
+    pub async fn ordered_by(mut self, relation_id: &str, storage: &dyn ConceptStorage) -> StorageResult<Self> {
+        let order = relation::topological_order(relation_id, storage).await??;
+        let rank: HashMap<&str, usize> = order.iter().enumerate().map(|(i, name)| (name.as_str(), i)).collect();
+
+        let slots: Vec<usize> = self
+            .steps
+            .iter()
+            .enumerate()
+            .filter(|(_, step)| rank.contains_key(step.name.as_str()))
+            .map(|(i, _)| i)
+            .collect();
+        let mut steps: Vec<Option<SagaStep>> = self.steps.into_iter().map(Some).collect();
+        let mut linked: Vec<SagaStep> = slots.iter().filter_map(|&i| steps[i].take()).collect();
+        linked.sort_by_key(|step| rank[step.name.as_str()]);
+        for (slot, step) in slots.into_iter().zip(linked) {
+            steps[slot] = Some(step);
+        }
+        self.steps = steps.into_iter().flatten().collect();
+        Ok(self)
+    }
+
+    /// The stored progress of `run_id`, if it has started.
+    pub async fn load(run_id: &str, storage: &dyn ConceptStorage) -> StorageResult<Option<SagaRun>> {
+        match storage.get("workflow_saga", run_id).await? {
+            Some(record) => Ok(Some(serde_json::from_value(record)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Run or resume `run_id`. Forward steps run in order; when one fails,
+    /// the completed steps' compensations run in reverse. A failing
+    /// compensation stops the run in `Compensating` so a later call retries
+    /// it. Finished runs are returned as stored.
+    pub async fn run(
+        &self,
+        run_id: &str,
+        context: &serde_json::Value,
+        storage: &dyn ConceptStorage,
+    ) -> StorageResult<SagaRun> {
+        let mut run = match Self::load(run_id, storage).await? {
+            Some(run) => run,
+            None => SagaRun {
+                run_id: run_id.to_string(),
+                saga_id: self.saga_id.clone(),
+                state: SagaState::Running,
+                completed: Vec::new(),
+                compensated: Vec::new(),
+                failed_step: None,
+                error: None,
+            },
+        };
+
+        if run.state == SagaState::Running {
+            for step in &self.steps {
+                if run.completed.contains(&step.name) {
+                    continue;
+                }
+                match step.action.run(context, storage).await {
+                    Ok(()) => run.completed.push(step.name.clone()),
+                    Err(e) => {
+                        run.state = SagaState::Compensating;
+                        run.failed_step = Some(step.name.clone());
+                        run.error = Some(e.to_string());
+                    }
+                }
+                save_run(&run, storage).await?;
+                if run.state != SagaState::Running {
+                    break;
+                }
+            }
+            if run.state == SagaState::Running {
+                run.state = SagaState::Completed;
+                save_run(&run, storage).await?;
+            }
+        }
+
+        if run.state == SagaState::Compensating {
+            let pending: Vec<&SagaStep> = run
+                .completed
+                .iter()
+                .rev()
+                .filter(|name| !run.compensated.contains(name))
+                .filter_map(|name| self.steps.iter().find(|step| &step.name == name))
+                .collect();
+            for step in pending {
+                if let Some(compensation) = &step.compensation {
+                    if let Err(e) = compensation.run(context, storage).await {
+                        run.error = Some(format!("compensating '{}' failed: {}", step.name, e));
+                        save_run(&run, storage).await?;
+                        return Ok(run);
+                    }
+                }
+                run.compensated.push(step.name.clone());
+                save_run(&run, storage).await?;
+            }
+            run.state = SagaState::Failed;
+            save_run(&run, storage).await?;
+        }
+
+        Ok(run)
+    }
+}
+
+async fn save_run(run: &SagaRun, storage: &dyn ConceptStorage) -> StorageResult<()> {
+    storage.put("workflow_saga", &run.run_id, serde_json::to_value(run)?).await
+}
+
+// ── Step graphs ───────────────────────────────────────────
+
+/// Steps a single walk may visit before it is assumed to loop forever.
+pub const MAX_GRAPH_STEPS: usize = 1000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GraphError {
+    /// A route or branch names a step that was never defined.
+    UnknownStep { from: String, to: String },
+    /// A fan-out joins at a step that was never defined.
+    MissingJoin { from: String, join: String },
+    /// A fan-out branch has no path to its join step.
+    BranchNeverJoins { branch: String, join: String },
+    Unreachable { step: String },
+    Condition { from: String, to: String, message: String },
+    StepLimit { limit: usize },
+}
+
+impl std::fmt::Display for GraphError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GraphError::UnknownStep { from, to } => write!(f, "'{}' routes to unknown step '{}'", from, to),
+            GraphError::MissingJoin { from, join } => {
+                write!(f, "fan-out from '{}' joins at unknown step '{}'", from, join)
+            }
+            GraphError::BranchNeverJoins { branch, join } => {
+                write!(f, "branch '{}' never reaches join '{}'", branch, join)
+            }
+            GraphError::Unreachable { step } => write!(f, "step '{}' is unreachable", step),
+            GraphError::Condition { from, to, message } => {
+                write!(f, "condition on '{}' -> '{}' failed: {}", from, to, message)
+            }
+            GraphError::StepLimit { limit } => write!(f, "walk exceeded {} steps", limit),
+        }
+    }
+}
+
+impl std::error::Error for GraphError {}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Route {
+    to: String,
+    /// Expression over the context; `None` always matches.
+    when: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct FanOut {
+    branches: Vec<String>,
+    join: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+struct GraphStep {
+    routes: Vec<Route>,
+    fan_out: Option<FanOut>,
+}
+
+/// One entry in a planned walk. Parallel branches each run until they reach
+/// the fan-out's join step, which follows as its own `Step`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Stage {
+    Step(String),
+    Parallel(Vec<Vec<Stage>>),
+}
+
+/// Steps connected by conditional routes and parallel fan-outs. A step's
+/// routes are tried in the order they were added; the first whose condition
+/// holds is taken, and a step with no matching route ends the walk.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkflowGraph {
+    start: String,
+    steps: BTreeMap<String, GraphStep>,
+}
+
+impl WorkflowGraph {
+    pub fn new(start: &str) -> Self {
+        let mut graph = Self {
+            start: start.to_string(),
+            steps: BTreeMap::new(),
+        };
+        graph.steps.insert(start.to_string(), GraphStep::default());
+        graph
+    }
+
+    pub fn step(mut self, name: &str) -> Self {
+        self.steps.entry(name.to_string()).or_default();
+        self
+    }
+
+    pub fn route(mut self, from: &str, to: &str) -> Self {
+        self.add_route(from, to, None);
+        self
+    }
+
+    /// Route to `to` when `condition` holds against the workflow context.
+    pub fn route_if(mut self, from: &str, to: &str, condition: &str) -> Self {
+        self.add_route(from, to, Some(condition.to_string()));
+        self
+    }
+
+    /// Run `branches` in parallel after `from`, continuing at `join` once
+    /// every branch has reached it.
+    pub fn fan_out(mut self, from: &str, branches: &[&str], join: &str) -> Self {
+        self.steps.entry(from.to_string()).or_default().fan_out = Some(FanOut {
+            branches: branches.iter().map(|b| b.to_string()).collect(),
+            join: join.to_string(),
+        });
+        self
+    }
+
+    fn add_route(&mut self, from: &str, to: &str, when: Option<String>) {
+        self.steps.entry(from.to_string()).or_default().routes.push(Route {
+            to: to.to_string(),
+            when,
+        });
+    }
+
+    /// Build a graph from a stored workflow's states and transitions; both
+    /// ends of a transition count as steps. A transition's non-empty guard
+    /// becomes its condition, and guarded routes are tried before unguarded
+    /// ones.
+    pub async fn load(workflow_id: &str, start: &str, storage: &dyn ConceptStorage) -> StorageResult<Self> {
+        let records = storage.find("workflow", Some(&json!({ "workflow_id": workflow_id }))).await?;
+        let mut graph = Self::new(start);
+        let mut transitions = Vec::new();
+        for record in &records {
+            match record["type"].as_str() {
+                Some("state") => graph = graph.step(record["state_name"].as_str().unwrap_or("")),
+                Some("transition") => transitions.push(record),
+                _ => {}
+            }
+        }
+        transitions.sort_by_key(|t| {
+            let guard = t["guard"].as_str().unwrap_or("");
+            (guard.is_empty(), t["from_state"].as_str().unwrap_or(""), t["to_state"].as_str().unwrap_or(""))
+        });
+        for t in transitions {
+            let guard = t["guard"].as_str().unwrap_or("").trim();
+            let when = (!guard.is_empty()).then(|| guard.to_string());
+            let to = t["to_state"].as_str().unwrap_or("");
+            graph.add_route(t["from_state"].as_str().unwrap_or(""), to, when);
+            graph.steps.entry(to.to_string()).or_default();
+        }
+        Ok(graph)
+    }
+
+    fn successors(&self, name: &str) -> Vec<&str> {
+        let Some(step) = self.steps.get(name) else { return Vec::new() };
+        let mut next: Vec<&str> = step.routes.iter().map(|r| r.to.as_str()).collect();
+        if let Some(fan_out) = &step.fan_out {
+            next.extend(fan_out.branches.iter().map(String::as_str));
+            next.push(&fan_out.join);
+        }
+        next
+    }
+
+    fn reachable_from<'a>(&'a self, start: &'a str) -> BTreeSet<&'a str> {
+        let mut seen = BTreeSet::new();
+        let mut frontier = vec![start];
+        while let Some(name) = frontier.pop() {
+            if seen.insert(name) {
+                frontier.extend(self.successors(name));
+            }
+        }
+        seen
+    }
+
+    /// Check the graph's shape: every route and branch target exists, every
+    /// join step exists and is reachable from each of its branches, and every
+    /// step is reachable from the start.
+    pub fn validate_graph(&self) -> Result<(), Vec<GraphError>> {
+        let mut errors = Vec::new();
+        for (name, step) in &self.steps {
+            for to in step.routes.iter().map(|r| &r.to).chain(step.fan_out.iter().flat_map(|f| &f.branches)) {
+                if !self.steps.contains_key(to) {
+                    errors.push(GraphError::UnknownStep { from: name.clone(), to: to.clone() });
+                }
+            }
+            if let Some(fan_out) = &step.fan_out {
+                if !self.steps.contains_key(&fan_out.join) {
+                    errors.push(GraphError::MissingJoin { from: name.clone(), join: fan_out.join.clone() });
+                    continue;
+                }
+                for branch in fan_out.branches.iter().filter(|b| self.steps.contains_key(*b)) {
+                    if !self.reachable_from(branch).contains(fan_out.join.as_str()) {
+                        errors.push(GraphError::BranchNeverJoins { branch: branch.clone(), join: fan_out.join.clone() });
+                    }
+                }
+            }
+        }
+
+        let reachable = self.reachable_from(&self.start);
+        for name in self.steps.keys().filter(|name| !reachable.contains(name.as_str())) {
+            errors.push(GraphError::Unreachable { step: name.clone() });
+        }
+
+        if errors.is_empty() { Ok(()) } else { Err(errors) }
+    }
+
+    /// The steps a run takes for `context`, evaluating route conditions with
+    /// `functions`.
+    pub fn plan(&self, context: &serde_json::Value, functions: &FunctionRegistry) -> Result<Vec<Stage>, GraphError> {
+        let mut budget = MAX_GRAPH_STEPS;
+        self.walk(&self.start, None, context, functions, &mut budget)
+    }
+
+    fn walk(
+        &self,
+        from: &str,
+        until: Option<&str>,
+        context: &serde_json::Value,
+        functions: &FunctionRegistry,
+        budget: &mut usize,
+    ) -> Result<Vec<Stage>, GraphError> {
+        let mut stages = Vec::new();
+        let mut current = Some(from.to_string());
+        while let Some(name) = current.take() {
+            if Some(name.as_str()) == until {
+                break;
+            }
+            *budget = budget.checked_sub(1).ok_or(GraphError::StepLimit { limit: MAX_GRAPH_STEPS })?;
+            stages.push(Stage::Step(name.clone()));
+            let Some(step) = self.steps.get(&name) else { break };
+
+            if let Some(fan_out) = &step.fan_out {
+                let branches = fan_out
+                    .branches
+                    .iter()
+                    .map(|branch| self.walk(branch, Some(&fan_out.join), context, functions, budget))
+                    .collect::<Result<Vec<_>, _>>()?;
+                stages.push(Stage::Parallel(branches));
+                current = Some(fan_out.join.clone());
+                continue;
+            }
+
+            for route in &step.routes {
+                let taken = match &route.when {
+                    None => true,
+                    Some(condition) => truthy(&functions.evaluate(condition, context).map_err(|e| {
+                        GraphError::Condition { from: name.clone(), to: route.to.clone(), message: e.to_string() }
+                    })?),
+                };
+                if taken {
+                    current = Some(route.to.clone());
+                    break;
+                }
+            }
+        }
+        Ok(stages)
+    }
+}
+
 // ── Handler ───────────────────────────────────────────────
 
 pub struct WorkflowHandler;
@@ -554,4 +1004,212 @@ mod tests {
             WorkflowGetCurrentStateOutput::NotFound { .. }
         ));
     }
+
+    // ── saga tests ─────────────────────────────────────────
+
+    type Log = std::sync::Arc<std::sync::Mutex<Vec<String>>>;
+
+    struct Record {
+        entry: String,
+        log: Log,
+        fail: bool,
+    }
+
+    #[async_trait]
+    impl SagaAction for Record {
+        async fn run(&self, _context: &serde_json::Value, _storage: &dyn ConceptStorage) -> StorageResult<()> {
+            if self.fail {
+                return Err(format!("{} failed", self.entry).into());
+            }
+            self.log.lock().unwrap().push(self.entry.clone());
+            Ok(())
+        }
+    }
+
+    fn record(log: &Log, entry: &str, fail: bool) -> Record {
+        Record {
+            entry: entry.to_string(),
+            log: log.clone(),
+            fail,
+        }
+    }
+
+    fn order_saga(log: &Log, fail_at: &str) -> Saga {
+        ["reserve", "charge", "ship", "notify"]
+            .into_iter()
+            .fold(Saga::new("order"), |saga, step| {
+                saga.compensated_step(step, record(log, step, step == fail_at), record(log, &format!("undo {}", step), false))
+            })
+    }
+
+    #[tokio::test]
+    async fn failed_step_compensates_completed_steps_in_reverse() {
+        let storage = InMemoryStorage::new();
+        let log = Log::default();
+
+        let run = order_saga(&log, "ship").run("order-1", &json!({}), &storage).await.unwrap();
+        assert_eq!(*log.lock().unwrap(), vec!["reserve", "charge", "undo charge", "undo reserve"]);
+        assert_eq!(run.state, SagaState::Failed);
+        assert_eq!(run.failed_step.as_deref(), Some("ship"));
+        assert_eq!(run.error.as_deref(), Some("ship failed"));
+        assert_eq!(run.compensated, vec!["charge", "reserve"]);
+        assert_eq!(Saga::load("order-1", &storage).await.unwrap(), Some(run.clone()));
+
+        // A finished run is not replayed
+        let again = order_saga(&log, "").run("order-1", &json!({}), &storage).await.unwrap();
+        assert_eq!(again, run);
+        assert_eq!(log.lock().unwrap().len(), 4);
+
+        let ok = order_saga(&log, "").run("order-2", &json!({}), &storage).await.unwrap();
+        assert_eq!(ok.state, SagaState::Completed);
+        assert_eq!(ok.completed.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn interrupted_run_resumes_from_storage() {
+        let storage = InMemoryStorage::new();
+        let log = Log::default();
+        storage
+            .put(
+                "workflow_saga",
+                "order-3",
+                json!({
+                    "run_id": "order-3", "saga_id": "order", "state": "running",
+                    "completed": ["reserve", "charge"], "compensated": [],
+                    "failed_step": null, "error": null,
+                }),
+            )
+            .await
+            .unwrap();
+
+        let run = order_saga(&log, "").run("order-3", &json!({}), &storage).await.unwrap();
+        assert_eq!(*log.lock().unwrap(), vec!["ship", "notify"]);
+        assert_eq!(run.state, SagaState::Completed);
+    }
+
+    #[tokio::test]
+    async fn saga_steps_follow_relation_order() {
+        use crate::relation::{DefineRelationInput, DefineRelationOutput, LinkInput, RelationHandler};
+
+        let storage = InMemoryStorage::new();
+        let relations = RelationHandler;
+        let DefineRelationOutput::Ok { relation_id } = relations
+            .define_relation(
+                DefineRelationInput {
+                    name: "prerequisite_of".into(),
+                    source_type: "step".into(),
+                    target_type: "step".into(),
+                    cardinality: "many_to_many".into(),
+                    is_bidirectional: false,
+                },
+                &storage,
+            )
+            .await
+            .unwrap();
+        let link = |source: &str, target: &str| LinkInput {
+            relation_id: relation_id.clone(),
+            source_id: source.into(),
+            target_id: target.into(),
+        };
+        relations.link(link("ship", "charge"), &storage).await.unwrap();
+
+        // "ship" and "charge" swap; "reserve" and "notify" keep their places
+        let log = Log::default();
+        let saga = order_saga(&log, "").ordered_by(&relation_id, &storage).await.unwrap();
+        saga.run("order-4", &json!({}), &storage).await.unwrap();
+        assert_eq!(*log.lock().unwrap(), vec!["reserve", "ship", "charge", "notify"]);
+
+        relations.link(link("charge", "ship"), &storage).await.unwrap();
+        let err = order_saga(&log, "").ordered_by(&relation_id, &storage).await.err().unwrap();
+        assert!(err.downcast_ref::<relation::CycleError>().is_some());
+    }
+
+    // ── step graph tests ───────────────────────────────────
+
+    fn steps(names: &[&str]) -> Vec<Stage> {
+        names.iter().map(|n| Stage::Step(n.to_string())).collect()
+    }
+
+    #[tokio::test]
+    async fn conditional_route_is_taken_or_skipped() {
+        let storage = InMemoryStorage::new();
+        let handler = WorkflowHandler;
+        for (from, to, guard) in [
+            ("submitted", "review", "amount > 1000"),
+            ("submitted", "approved", ""),
+            ("review", "approved", ""),
+        ] {
+            handler
+                .define_transition(
+                    WorkflowDefineTransitionInput {
+                        workflow_id: "expense".into(),
+                        from_state: from.into(),
+                        to_state: to.into(),
+                        guard: guard.into(),
+                    },
+                    &storage,
+                )
+                .await
+                .unwrap();
+        }
+        let graph = WorkflowGraph::load("expense", "submitted", &storage).await.unwrap();
+        assert_eq!(graph.validate_graph(), Ok(()));
+
+        let functions = FunctionRegistry::new();
+        assert_eq!(
+            graph.plan(&json!({ "amount": 5000 }), &functions).unwrap(),
+            steps(&["submitted", "review", "approved"])
+        );
+        assert_eq!(graph.plan(&json!({ "amount": 20 }), &functions).unwrap(), steps(&["submitted", "approved"]));
+
+        let broken = WorkflowGraph::new("a").step("b").route_if("a", "b", "nope(1)");
+        assert!(matches!(broken.plan(&json!({}), &functions), Err(GraphError::Condition { .. })));
+    }
+
+    #[test]
+    fn fan_out_joins_parallel_branches() {
+        let graph = WorkflowGraph::new("order")
+            .fan_out("order", &["charge", "pick"], "ship")
+            .route("charge", "ship")
+            .route("pick", "pack")
+            .route("pack", "ship")
+            .route("ship", "done")
+            .step("done");
+        assert_eq!(graph.validate_graph(), Ok(()));
+
+        assert_eq!(
+            graph.plan(&json!({}), &FunctionRegistry::new()).unwrap(),
+            vec![
+                Stage::Step("order".into()),
+                Stage::Parallel(vec![steps(&["charge"]), steps(&["pick", "pack"])]),
+                Stage::Step("ship".into()),
+                Stage::Step("done".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_graph_rejects_bad_shapes() {
+        let graph = WorkflowGraph::new("start")
+            .fan_out("start", &["left", "right"], "merge")
+            .route("left", "end")
+            .route("right", "ghost")
+            .step("end")
+            .step("orphan");
+        let errors = graph.validate_graph().unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                GraphError::UnknownStep { from: "right".into(), to: "ghost".into() },
+                GraphError::MissingJoin { from: "start".into(), join: "merge".into() },
+                GraphError::Unreachable { step: "orphan".into() },
+            ]
+        );
+
+        let never_joins = WorkflowGraph::new("s").fan_out("s", &["a", "b"], "j").route("a", "j").step("b").step("j");
+        assert_eq!(
+            never_joins.validate_graph(),
+            Err(vec![GraphError::BranchNeverJoins { branch: "b".into(), join: "j".into() }])
+        );
+    }
 }
@@ -1,11 +1,26 @@
 // Conduit Example App -- GTK API Client
-// reqwest-based HTTP client for the Conduit REST API.
-
-use reqwest::Client;
+// reqwest-based HTTP client for the Conduit REST API. Every endpoint takes a
+// typed request and returns `Result<T, ApiError>`; Conduit's
+// `{"errors": {field: [messages]}}` bodies decode into per-field errors.
+// Tokens persist to the user config dir and are sent as `Authorization: Token
+// <jwt>`; on a 401 the client trades its refresh token for a new pair and
+// retries once, clearing the stored tokens when that fails. Only one refresh
+// runs at a time: requests that hit a 401 meanwhile retry with its token.
+
+use reqwest::{Client, RequestBuilder, StatusCode};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 
 const BASE_URL: &str = "http://localhost:3000";
+/// The route the REST target generates for the jwt concept's `refresh`
+/// action (`GET /api/jwt/refresh`, input as the JSON body).
+const REFRESH_PATH: &str = "/jwt/refresh";
 
 // MARK: - Models
 
@@ -14,6 +29,8 @@ pub struct User {
     pub username: String,
     pub email: String,
     pub token: String,
+    #[serde(rename = "refreshToken", default, skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
     pub bio: Option<String>,
     pub image: Option<String>,
 }
@@ -76,6 +93,24 @@ pub struct CommentsResponse {
     pub comments: Vec<Comment>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct CommentResponse {
+    pub comment: Comment,
+}
+
+/// Output of the jwt concept's `refresh` action.
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(tag = "variant")]
+pub enum RefreshResponse {
+    #[serde(rename = "ok")]
+    Ok { access_token: String, refresh_token: String },
+    #[serde(rename = "error")]
+    Error { message: String },
+    /// The refresh token was already rotated; the server revoked its family.
+    #[serde(rename = "revoked")]
+    Revoked { message: String },
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ProfileResponse {
     pub profile: Profile,
@@ -86,38 +121,209 @@ pub struct TagsResponse {
     pub tags: Vec<String>,
 }
 
-#[derive(Debug, Deserialize)]
-pub struct ErrorBody {
-    pub body: Vec<String>,
-}
+/// Field name to messages, e.g. `{"email": ["has already been taken"]}`.
+pub type FieldErrors = BTreeMap<String, Vec<String>>;
 
 #[derive(Debug, Deserialize)]
 pub struct ErrorResponse {
-    pub errors: ErrorBody,
+    pub errors: FieldErrors,
 }
 
 // Request bodies
-#[derive(Serialize)]
-struct LoginWrapper {
-    user: LoginBody,
+#[derive(Debug, Serialize)]
+pub struct LoginRequest {
+    pub user: LoginUser,
 }
 
-#[derive(Serialize)]
-struct LoginBody {
-    email: String,
-    password: String,
+#[derive(Debug, Serialize)]
+pub struct LoginUser {
+    pub email: String,
+    pub password: String,
 }
 
-#[derive(Serialize)]
-struct RegisterWrapper {
-    user: RegisterBody,
+#[derive(Debug, Serialize)]
+pub struct RegisterRequest {
+    pub user: RegisterUser,
 }
 
-#[derive(Serialize)]
-struct RegisterBody {
-    username: String,
-    email: String,
-    password: String,
+#[derive(Debug, Serialize)]
+pub struct RegisterUser {
+    pub username: String,
+    pub email: String,
+    pub password: String,
+}
+
+/// Input of the jwt concept's `refresh` action.
+#[derive(Debug, Serialize)]
+pub struct RefreshRequest {
+    pub refresh_token: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NewCommentRequest {
+    pub comment: NewComment,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NewComment {
+    pub body: String,
+}
+
+/// Query parameters for `GET /api/articles`; unset filters are omitted.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ListArticlesQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub favorited: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u32>,
+}
+
+// MARK: - Errors
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ApiError {
+    /// The request never got a response.
+    Network(String),
+    /// 401: the token is missing, expired or the credentials were rejected.
+    /// Any field errors the server sent are kept for display.
+    Unauthorized(FieldErrors),
+    /// 422 (or another error status) with a Conduit `errors` body.
+    Validation { status: u16, errors: FieldErrors },
+    /// An error status without a decodable `errors` body.
+    Http(u16),
+    /// A success response whose body did not match the expected model.
+    Decode(String),
+}
+
+impl ApiError {
+    pub fn field_errors(&self) -> Option<&FieldErrors> {
+        match self {
+            ApiError::Unauthorized(errors) | ApiError::Validation { errors, .. } => Some(errors),
+            _ => None,
+        }
+    }
+
+    /// One message per server-reported problem, in Conduit's "field message" form.
+    pub fn messages(&self) -> Vec<String> {
+        self.field_errors()
+            .map(|errors| {
+                errors.iter()
+                    .flat_map(|(field, messages)| messages.iter().map(move |m| format!("{} {}", field, m)))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let messages = self.messages();
+        match self {
+            _ if !messages.is_empty() => write!(f, "{}", messages.join(", ")),
+            ApiError::Network(message) => write!(f, "Network error: {}", message),
+            ApiError::Unauthorized(_) => write!(f, "Please sign in again"),
+            ApiError::Validation { status, .. } | ApiError::Http(status) => write!(f, "HTTP {}", status),
+            ApiError::Decode(message) => write!(f, "Unexpected response: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+impl From<reqwest::Error> for ApiError {
+    fn from(err: reqwest::Error) -> Self {
+        ApiError::Network(err.to_string())
+    }
+}
+
+/// Decode a response body: `T` on success, otherwise the `ApiError` for the
+/// status, with field errors taken from a Conduit `errors` body when present.
+pub fn decode_response<T: DeserializeOwned>(status: u16, body: &str) -> Result<T, ApiError> {
+    if (200..300).contains(&status) {
+        return serde_json::from_str(body).map_err(|e| ApiError::Decode(e.to_string()));
+    }
+    let errors = serde_json::from_str::<ErrorResponse>(body).ok().map(|res| res.errors);
+    Err(match (status, errors) {
+        (401, errors) => ApiError::Unauthorized(errors.unwrap_or_default()),
+        (status, Some(errors)) => ApiError::Validation { status, errors },
+        (status, None) => ApiError::Http(status),
+    })
+}
+
+// MARK: - Token Storage
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Credentials {
+    pub token: Option<String>,
+    #[serde(rename = "refreshToken")]
+    pub refresh_token: Option<String>,
+}
+
+/// Where credentials survive between launches; without a path they stay in memory.
+#[derive(Debug, Clone)]
+pub struct TokenStore {
+    path: Option<PathBuf>,
+}
+
+impl TokenStore {
+    /// `<user config dir>/conduit-gtk/credentials.json`.
+    pub fn default_location() -> Self {
+        Self::at(glib::user_config_dir().join("conduit-gtk").join("credentials.json"))
+    }
+
+    pub fn at(path: impl Into<PathBuf>) -> Self {
+        Self { path: Some(path.into()) }
+    }
+
+    #[cfg(test)]
+    pub fn in_memory() -> Self {
+        Self { path: None }
+    }
+
+    /// Stored credentials; a missing or unreadable file yields none.
+    pub fn load(&self) -> Credentials {
+        self.path.as_ref()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default()
+    }
+
+    /// Write `credentials`, removing the file once both tokens are gone. The
+    /// file is readable by the owner only.
+    pub fn save(&self, credentials: &Credentials) -> std::io::Result<()> {
+        let Some(path) = &self.path else { return Ok(()) };
+        if credentials.token.is_none() && credentials.refresh_token.is_none() {
+            return match fs::remove_file(path) {
+                Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err),
+                _ => Ok(()),
+            };
+        }
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let json = serde_json::to_string_pretty(credentials).map_err(std::io::Error::other)?;
+        let mut file = options.open(path)?;
+        // `mode` only applies on create; tighten a file left by an older build
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            file.set_permissions(fs::Permissions::from_mode(0o600))?;
+        }
+        file.write_all(json.as_bytes())
+    }
 }
 
 // MARK: - API Client
@@ -125,173 +331,383 @@ struct RegisterBody {
 #[derive(Clone)]
 pub struct ApiClient {
     client: Client,
-    token: Arc<Mutex<Option<String>>>,
+    store: TokenStore,
+    credentials: Arc<Mutex<Credentials>>,
+    /// Held for the whole refresh so concurrent 401s share one rotation.
+    refresh_lock: Arc<tokio::sync::Mutex<()>>,
 }
 
 impl ApiClient {
+    /// A client that restores the tokens saved by a previous launch.
     pub fn new() -> Self {
+        Self::with_store(TokenStore::default_location())
+    }
+
+    pub fn with_store(store: TokenStore) -> Self {
+        let credentials = store.load();
         Self {
             client: Client::new(),
-            token: Arc::new(Mutex::new(None)),
+            store,
+            credentials: Arc::new(Mutex::new(credentials)),
+            refresh_lock: Arc::new(tokio::sync::Mutex::new(())),
         }
     }
 
     pub fn set_token(&self, token: Option<String>) {
-        *self.token.lock().unwrap() = token;
+        self.update_credentials(|credentials| credentials.token = token);
     }
 
     pub fn get_token(&self) -> Option<String> {
-        self.token.lock().unwrap().clone()
+        self.credentials.lock().unwrap().token.clone()
+    }
+
+    pub fn set_refresh_token(&self, refresh_token: Option<String>) {
+        self.update_credentials(|credentials| credentials.refresh_token = refresh_token);
+    }
+
+    /// Forget both tokens, here and on disk (sign out).
+    pub fn clear_token(&self) {
+        self.update_credentials(|credentials| *credentials = Credentials::default());
+    }
+
+    fn update_credentials(&self, update: impl FnOnce(&mut Credentials)) {
+        let mut credentials = self.credentials.lock().unwrap();
+        update(&mut credentials);
+        // Persistence is best effort; the session still works for this launch
+        let _ = self.store.save(&credentials);
+    }
+
+    fn store_user_tokens(&self, user: &User) {
+        self.update_credentials(|credentials| {
+            credentials.token = Some(user.token.clone());
+            credentials.refresh_token = user.refresh_token.clone();
+        });
+    }
+
+    /// Attach `Authorization: Token <jwt>` when there is a token.
+    fn with_token(req: RequestBuilder, token: Option<&str>) -> RequestBuilder {
+        match token {
+            Some(token) => req.header("Authorization", format!("Token {}", token)),
+            None => req,
+        }
+    }
+
+    fn url(path: &str) -> String {
+        format!("{}/api{}", BASE_URL, path)
+    }
+
+    /// Send with the current token. On a 401, refresh the token pair and
+    /// retry once; if the refresh is rejected the stored tokens are cleared.
+    async fn send<T: DeserializeOwned>(&self, req: RequestBuilder) -> Result<T, ApiError> {
+        let retry = req.try_clone();
+        let sent = self.get_token();
+        match self.execute(req, sent.as_deref()).await {
+            Err(ApiError::Unauthorized(errors)) => {
+                let Some(retry) = retry else {
+                    return Err(ApiError::Unauthorized(errors));
+                };
+                match self.refresh_after(sent.as_deref()).await? {
+                    Some(token) => self.execute(retry, Some(&token)).await,
+                    None => Err(ApiError::Unauthorized(errors)),
+                }
+            }
+            result => result,
+        }
+    }
+
+    async fn execute<T: DeserializeOwned>(&self, req: RequestBuilder, token: Option<&str>) -> Result<T, ApiError> {
+        let response = Self::with_token(req, token).send().await?;
+        let status = response.status();
+        let body = response.text().await?;
+        self.handle_response(status, &body, token)
     }
 
-    fn auth_header(&self) -> Option<String> {
-        self.get_token().map(|t| format!("Token {}", t))
+    /// A token to retry with after `rejected` drew a 401. If another request
+    /// already refreshed while this one waited for the lock, its token is
+    /// reused; otherwise this request refreshes. Presenting the same refresh
+    /// token twice would make the server revoke the whole family.
+    async fn refresh_after(&self, rejected: Option<&str>) -> Result<Option<String>, ApiError> {
+        let _refreshing = self.refresh_lock.lock().await;
+        let current = self.get_token();
+        if current.is_some() && current.as_deref() != rejected {
+            return Ok(current);
+        }
+        self.refresh_session().await
     }
 
-    // Auth
-    pub async fn login(&self, email: &str, password: &str) -> Result<User, String> {
-        let body = LoginWrapper {
-            user: LoginBody {
+    /// Trade the refresh token for a new pair and return the new access
+    /// token. `Ok(None)` when there is no refresh token or the server
+    /// rejected it.
+    async fn refresh_session(&self) -> Result<Option<String>, ApiError> {
+        let Some(refresh_token) = self.credentials.lock().unwrap().refresh_token.clone() else {
+            return Ok(None);
+        };
+        let body = RefreshRequest { refresh_token };
+        let response = self.client.get(Self::url(REFRESH_PATH)).json(&body).send().await?;
+        let status = response.status();
+        let text = response.text().await?;
+        match decode_response::<RefreshResponse>(status.as_u16(), &text) {
+            Ok(RefreshResponse::Ok { access_token, refresh_token }) => {
+                self.update_credentials(|credentials| {
+                    credentials.token = Some(access_token.clone());
+                    credentials.refresh_token = Some(refresh_token);
+                });
+                Ok(Some(access_token))
+            }
+            Ok(RefreshResponse::Error { .. } | RefreshResponse::Revoked { .. })
+            | Err(ApiError::Unauthorized(_) | ApiError::Validation { .. }) => {
+                self.clear_token();
+                Ok(None)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Decode a response to a request sent with `sent`, dropping that token
+    /// when the server answers 401. A token refreshed in the meantime stays.
+    fn handle_response<T: DeserializeOwned>(
+        &self,
+        status: StatusCode,
+        body: &str,
+        sent: Option<&str>,
+    ) -> Result<T, ApiError> {
+        let result = decode_response(status.as_u16(), body);
+        if matches!(result, Err(ApiError::Unauthorized(_))) && self.get_token().as_deref() == sent {
+            self.set_token(None);
+        }
+        result
+    }
+
+    // Auth (no refresh-and-retry: a 401 here means bad credentials)
+    pub async fn login(&self, email: &str, password: &str) -> Result<User, ApiError> {
+        let body = LoginRequest {
+            user: LoginUser {
                 email: email.to_string(),
                 password: password.to_string(),
             },
         };
 
-        let mut req = self.client.post(format!("{}/api/users/login", BASE_URL)).json(&body);
-        if let Some(auth) = self.auth_header() {
-            req = req.header("Authorization", auth);
-        }
-
-        let response = req.send().await.map_err(|e| e.to_string())?;
-        if !response.status().is_success() {
-            let err: ErrorResponse = response.json().await.map_err(|e| e.to_string())?;
-            return Err(err.errors.body.join(", "));
-        }
-        let res: UserResponse = response.json().await.map_err(|e| e.to_string())?;
-        self.set_token(Some(res.user.token.clone()));
+        let req = self.client.post(Self::url("/users/login")).json(&body);
+        let res: UserResponse = self.execute(req, None).await?;
+        self.store_user_tokens(&res.user);
         Ok(res.user)
     }
 
-    pub async fn register(&self, username: &str, email: &str, password: &str) -> Result<User, String> {
-        let body = RegisterWrapper {
-            user: RegisterBody {
+    pub async fn register(&self, username: &str, email: &str, password: &str) -> Result<User, ApiError> {
+        let body = RegisterRequest {
+            user: RegisterUser {
                 username: username.to_string(),
                 email: email.to_string(),
                 password: password.to_string(),
             },
         };
 
-        let response = self.client.post(format!("{}/api/users", BASE_URL))
-            .json(&body)
-            .send()
-            .await
-            .map_err(|e| e.to_string())?;
-
-        if !response.status().is_success() {
-            let err: ErrorResponse = response.json().await.map_err(|e| e.to_string())?;
-            return Err(err.errors.body.join(", "));
-        }
-        let res: UserResponse = response.json().await.map_err(|e| e.to_string())?;
-        self.set_token(Some(res.user.token.clone()));
+        let req = self.client.post(Self::url("/users")).json(&body);
+        let res: UserResponse = self.execute(req, None).await?;
+        self.store_user_tokens(&res.user);
         Ok(res.user)
     }
 
     // Articles
-    pub async fn get_articles(&self) -> Result<Vec<Article>, String> {
-        let mut req = self.client.get(format!("{}/api/articles", BASE_URL));
-        if let Some(auth) = self.auth_header() {
-            req = req.header("Authorization", auth);
-        }
-
-        let response = req.send().await.map_err(|e| e.to_string())?;
-        if !response.status().is_success() {
-            return Err(format!("HTTP {}", response.status()));
-        }
-        let res: ArticlesResponse = response.json().await.map_err(|e| e.to_string())?;
-        Ok(res.articles)
+    pub async fn list_articles(&self, query: &ListArticlesQuery) -> Result<ArticlesResponse, ApiError> {
+        self.send(self.client.get(Self::url("/articles")).query(query)).await
     }
 
-    pub async fn get_article(&self, slug: &str) -> Result<Article, String> {
-        let mut req = self.client.get(format!("{}/api/articles/{}", BASE_URL, slug));
-        if let Some(auth) = self.auth_header() {
-            req = req.header("Authorization", auth);
-        }
+    pub async fn get_articles(&self) -> Result<Vec<Article>, ApiError> {
+        Ok(self.list_articles(&ListArticlesQuery::default()).await?.articles)
+    }
 
-        let response = req.send().await.map_err(|e| e.to_string())?;
-        if !response.status().is_success() {
-            return Err(format!("HTTP {}", response.status()));
-        }
-        let res: ArticleResponse = response.json().await.map_err(|e| e.to_string())?;
+    pub async fn get_article(&self, slug: &str) -> Result<Article, ApiError> {
+        let res: ArticleResponse = self.send(self.client.get(Self::url(&format!("/articles/{}", slug)))).await?;
         Ok(res.article)
     }
 
     // Social
-    pub async fn favorite(&self, slug: &str) -> Result<Article, String> {
-        let mut req = self.client.post(format!("{}/api/articles/{}/favorite", BASE_URL, slug));
-        if let Some(auth) = self.auth_header() {
-            req = req.header("Authorization", auth);
-        }
-
-        let response = req.send().await.map_err(|e| e.to_string())?;
-        if !response.status().is_success() {
-            return Err(format!("HTTP {}", response.status()));
-        }
-        let res: ArticleResponse = response.json().await.map_err(|e| e.to_string())?;
+    pub async fn favorite(&self, slug: &str) -> Result<Article, ApiError> {
+        let url = Self::url(&format!("/articles/{}/favorite", slug));
+        let res: ArticleResponse = self.send(self.client.post(url)).await?;
         Ok(res.article)
     }
 
-    pub async fn unfavorite(&self, slug: &str) -> Result<Article, String> {
-        let mut req = self.client.delete(format!("{}/api/articles/{}/favorite", BASE_URL, slug));
-        if let Some(auth) = self.auth_header() {
-            req = req.header("Authorization", auth);
-        }
-
-        let response = req.send().await.map_err(|e| e.to_string())?;
-        if !response.status().is_success() {
-            return Err(format!("HTTP {}", response.status()));
-        }
-        let res: ArticleResponse = response.json().await.map_err(|e| e.to_string())?;
+    pub async fn unfavorite(&self, slug: &str) -> Result<Article, ApiError> {
+        let url = Self::url(&format!("/articles/{}/favorite", slug));
+        let res: ArticleResponse = self.send(self.client.delete(url)).await?;
         Ok(res.article)
     }
 
-    pub async fn get_profile(&self, username: &str) -> Result<Profile, String> {
-        let mut req = self.client.get(format!("{}/api/profiles/{}", BASE_URL, username));
-        if let Some(auth) = self.auth_header() {
-            req = req.header("Authorization", auth);
-        }
+    pub async fn get_profile(&self, username: &str) -> Result<Profile, ApiError> {
+        let res: ProfileResponse = self.send(self.client.get(Self::url(&format!("/profiles/{}", username)))).await?;
+        Ok(res.profile)
+    }
 
-        let response = req.send().await.map_err(|e| e.to_string())?;
-        if !response.status().is_success() {
-            return Err(format!("HTTP {}", response.status()));
-        }
-        let res: ProfileResponse = response.json().await.map_err(|e| e.to_string())?;
+    pub async fn follow(&self, username: &str) -> Result<Profile, ApiError> {
+        let url = Self::url(&format!("/profiles/{}/follow", username));
+        let res: ProfileResponse = self.send(self.client.post(url)).await?;
         Ok(res.profile)
     }
 
-    pub async fn get_comments(&self, slug: &str) -> Result<Vec<Comment>, String> {
-        let mut req = self.client.get(format!("{}/api/articles/{}/comments", BASE_URL, slug));
-        if let Some(auth) = self.auth_header() {
-            req = req.header("Authorization", auth);
-        }
+    pub async fn unfollow(&self, username: &str) -> Result<Profile, ApiError> {
+        let url = Self::url(&format!("/profiles/{}/follow", username));
+        let res: ProfileResponse = self.send(self.client.delete(url)).await?;
+        Ok(res.profile)
+    }
 
-        let response = req.send().await.map_err(|e| e.to_string())?;
-        if !response.status().is_success() {
-            return Err(format!("HTTP {}", response.status()));
-        }
-        let res: CommentsResponse = response.json().await.map_err(|e| e.to_string())?;
+    // Comments
+    pub async fn get_comments(&self, slug: &str) -> Result<Vec<Comment>, ApiError> {
+        let url = Self::url(&format!("/articles/{}/comments", slug));
+        let res: CommentsResponse = self.send(self.client.get(url)).await?;
         Ok(res.comments)
     }
 
-    pub async fn get_tags(&self) -> Result<Vec<String>, String> {
-        let response = self.client.get(format!("{}/api/tags", BASE_URL))
-            .send()
-            .await
-            .map_err(|e| e.to_string())?;
+    pub async fn post_comment(&self, slug: &str, body: &str) -> Result<Comment, ApiError> {
+        let request = NewCommentRequest {
+            comment: NewComment { body: body.to_string() },
+        };
+        let url = Self::url(&format!("/articles/{}/comments", slug));
+        let res: CommentResponse = self.send(self.client.post(url).json(&request)).await?;
+        Ok(res.comment)
+    }
 
-        if !response.status().is_success() {
-            return Err(format!("HTTP {}", response.status()));
-        }
-        let res: TagsResponse = response.json().await.map_err(|e| e.to_string())?;
+    pub async fn get_tags(&self) -> Result<Vec<String>, ApiError> {
+        let res: TagsResponse = self.send(self.client.get(Self::url("/tags"))).await?;
         Ok(res.tags)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOGIN_SUCCESS: &str = include_str!("fixtures/login_success.json");
+    const VALIDATION_ERROR: &str = include_str!("fixtures/validation_error.json");
+
+    #[test]
+    fn decodes_successful_login() {
+        let res: UserResponse = decode_response(200, LOGIN_SUCCESS).unwrap();
+        assert_eq!(res.user.username, "jake");
+        assert_eq!(res.user.email, "jake@jake.jake");
+        assert_eq!(res.user.token, "jwt.token.here");
+        assert_eq!(res.user.image, None);
+    }
+
+    #[test]
+    fn decodes_validation_errors_per_field() {
+        let err = decode_response::<UserResponse>(422, VALIDATION_ERROR).unwrap_err();
+        let errors = err.field_errors().unwrap();
+        assert_eq!(errors["email"], vec!["has already been taken"]);
+        assert_eq!(errors["username"], vec!["can't be blank", "is too short (minimum is 1 character)"]);
+        assert!(matches!(err, ApiError::Validation { status: 422, .. }));
+        assert_eq!(
+            err.to_string(),
+            "email has already been taken, username can't be blank, username is too short (minimum is 1 character)"
+        );
+
+        assert_eq!(decode_response::<UserResponse>(500, "<html>").unwrap_err(), ApiError::Http(500));
+        assert!(matches!(decode_response::<UserResponse>(200, "{}"), Err(ApiError::Decode(_))));
+    }
+
+    #[test]
+    fn unauthorized_clears_stored_token() {
+        let client = ApiClient::with_store(TokenStore::in_memory());
+        client.set_token(Some("stale".to_string()));
+
+        let ok: Result<UserResponse, _> = client.handle_response(StatusCode::OK, LOGIN_SUCCESS, Some("stale"));
+        assert!(ok.is_ok());
+        assert_eq!(client.get_token().as_deref(), Some("stale"));
+
+        let err = client.handle_response::<ArticlesResponse>(StatusCode::UNAUTHORIZED, "", Some("stale")).unwrap_err();
+        assert_eq!(err, ApiError::Unauthorized(FieldErrors::new()));
+        assert_eq!(err.to_string(), "Please sign in again");
+        assert_eq!(client.get_token(), None);
+
+        // A late 401 for an old token must not drop one refreshed since
+        client.set_token(Some("fresh".to_string()));
+        let _ = client.handle_response::<ArticlesResponse>(StatusCode::UNAUTHORIZED, "", Some("stale"));
+        assert_eq!(client.get_token().as_deref(), Some("fresh"));
+    }
+
+    #[test]
+    fn refresh_uses_the_jwt_wire_format() {
+        let body = serde_json::to_value(RefreshRequest { refresh_token: "r1".to_string() }).unwrap();
+        assert_eq!(body, serde_json::json!({ "refresh_token": "r1" }));
+
+        let ok = r#"{"variant":"ok","access_token":"a2","refresh_token":"r2"}"#;
+        assert_eq!(
+            decode_response::<RefreshResponse>(200, ok).unwrap(),
+            RefreshResponse::Ok { access_token: "a2".to_string(), refresh_token: "r2".to_string() }
+        );
+        let revoked = r#"{"variant":"revoked","message":"reused"}"#;
+        assert!(matches!(decode_response::<RefreshResponse>(200, revoked), Ok(RefreshResponse::Revoked { .. })));
+    }
+
+    #[tokio::test]
+    async fn waiting_requests_reuse_a_completed_refresh() {
+        let client = ApiClient::with_store(TokenStore::in_memory());
+        client.set_token(Some("rotated".to_string()));
+        client.set_refresh_token(Some("r2".to_string()));
+
+        // The 401 was for the old token; the refresh already happened, so no
+        // second request goes out with a refresh token
+        assert_eq!(client.refresh_after(Some("expired")).await.unwrap().as_deref(), Some("rotated"));
+        assert_eq!(client.credentials.lock().unwrap().refresh_token.as_deref(), Some("r2"));
+
+        client.clear_token();
+        assert_eq!(client.refresh_after(None).await.unwrap(), None);
+    }
+
+    fn authorization(client: &ApiClient) -> Option<String> {
+        let req = ApiClient::with_token(client.client.get(ApiClient::url("/user")), client.get_token().as_deref())
+            .build()
+            .unwrap();
+        req.headers().get("Authorization").map(|value| value.to_str().unwrap().to_string())
+    }
+
+    #[test]
+    fn attaches_auth_header_only_with_token() {
+        let client = ApiClient::with_store(TokenStore::in_memory());
+        assert_eq!(authorization(&client), None);
+
+        client.set_token(Some("jwt.token.here".to_string()));
+        assert_eq!(authorization(&client).as_deref(), Some("Token jwt.token.here"));
+
+        client.clear_token();
+        assert_eq!(authorization(&client), None);
+    }
+
+    #[test]
+    fn tokens_persist_across_clients() {
+        let dir = std::env::temp_dir().join(format!("conduit-gtk-test-{}", std::process::id()));
+        let path = dir.join("credentials.json");
+        let client = ApiClient::with_store(TokenStore::at(&path));
+        client.set_token(Some("access".to_string()));
+        client.set_refresh_token(Some("refresh".to_string()));
+
+        let restored = ApiClient::with_store(TokenStore::at(&path));
+        assert_eq!(authorization(&restored).as_deref(), Some("Token access"));
+        assert_eq!(TokenStore::at(&path).load().refresh_token.as_deref(), Some("refresh"));
+
+        restored.clear_token();
+        assert!(!path.exists());
+        assert_eq!(ApiClient::with_store(TokenStore::at(&path)).get_token(), None);
+        let _ = fs::remove_dir(dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn save_restricts_an_existing_credentials_file() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("conduit-gtk-perms-{}", std::process::id()));
+        let path = dir.join("credentials.json");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(&path, "{}").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+
+        let store = TokenStore::at(&path);
+        store.save(&Credentials { token: Some("access".to_string()), refresh_token: None }).unwrap();
+        assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+
+        store.save(&Credentials::default()).unwrap();
+        let _ = fs::remove_dir(dir);
+    }
+}
@@ -87,7 +87,7 @@ where
                     dlg_close.close();
                 }
                 Err(err) => {
-                    error_lbl.set_label(&err);
+                    error_lbl.set_label(&err.to_string());
                     error_lbl.set_visible(true);
                 }
             }
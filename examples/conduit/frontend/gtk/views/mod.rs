@@ -1,5 +1,6 @@
 // Conduit Example App -- GTK Views Module
-// Re-exports for article_list and login views.
+// Re-exports for article_list, login and the social (favorite/follow) state.
 
 pub mod article_list;
 pub mod login;
+pub mod social;
@@ -0,0 +1,254 @@
+// Conduit Example App -- GTK Favorite/Follow State
+// Optimistic toggles for favoriting articles and following authors: the view
+// state flips immediately, then settles on the server's response or rolls
+// back to the previous state when the request fails. Kept free of GTK so the
+// behavior can be tested headless against a mock client.
+
+use std::cell::RefCell;
+use std::future::Future;
+
+use crate::api_client::{ApiClient, ApiError, Article, Profile};
+
+/// The social endpoints the toggles call; implemented by `ApiClient`.
+pub trait SocialApi {
+    fn favorite(&self, slug: &str) -> impl Future<Output = Result<Article, ApiError>>;
+    fn unfavorite(&self, slug: &str) -> impl Future<Output = Result<Article, ApiError>>;
+    fn follow(&self, username: &str) -> impl Future<Output = Result<Profile, ApiError>>;
+    fn unfollow(&self, username: &str) -> impl Future<Output = Result<Profile, ApiError>>;
+}
+
+impl SocialApi for ApiClient {
+    fn favorite(&self, slug: &str) -> impl Future<Output = Result<Article, ApiError>> {
+        ApiClient::favorite(self, slug)
+    }
+
+    fn unfavorite(&self, slug: &str) -> impl Future<Output = Result<Article, ApiError>> {
+        ApiClient::unfavorite(self, slug)
+    }
+
+    fn follow(&self, username: &str) -> impl Future<Output = Result<Profile, ApiError>> {
+        ApiClient::follow(self, username)
+    }
+
+    fn unfollow(&self, username: &str) -> impl Future<Output = Result<Profile, ApiError>> {
+        ApiClient::unfollow(self, username)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FavoriteState {
+    pub favorited: bool,
+    pub favorites_count: i32,
+}
+
+impl FavoriteState {
+    pub fn toggled(self) -> Self {
+        let delta = if self.favorited { -1 } else { 1 };
+        Self {
+            favorited: !self.favorited,
+            favorites_count: (self.favorites_count + delta).max(0),
+        }
+    }
+}
+
+impl From<&Article> for FavoriteState {
+    fn from(article: &Article) -> Self {
+        Self {
+            favorited: article.favorited,
+            favorites_count: article.favorites_count,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FollowState {
+    pub following: bool,
+}
+
+impl From<&Profile> for FollowState {
+    fn from(profile: &Profile) -> Self {
+        Self { following: profile.following }
+    }
+}
+
+/// Show `optimistic` right away, then settle on what `request` returns, or
+/// restore the previous state if it fails. `render` runs after each change.
+async fn optimistic_update<S, F>(
+    state: &RefCell<S>,
+    optimistic: S,
+    request: F,
+    render: &impl Fn(S),
+) -> Result<S, ApiError>
+where
+    S: Copy,
+    F: Future<Output = Result<S, ApiError>>,
+{
+    let previous = state.replace(optimistic);
+    render(optimistic);
+
+    let result = request.await;
+    let settled = *result.as_ref().unwrap_or(&previous);
+    state.replace(settled);
+    render(settled);
+    result
+}
+
+/// Favorite or unfavorite `slug` depending on the current state.
+pub async fn toggle_favorite(
+    api: &impl SocialApi,
+    slug: &str,
+    state: &RefCell<FavoriteState>,
+    render: impl Fn(FavoriteState),
+) -> Result<FavoriteState, ApiError> {
+    let current = *state.borrow();
+    let request = async {
+        let article = if current.favorited { api.unfavorite(slug).await? } else { api.favorite(slug).await? };
+        Ok(FavoriteState::from(&article))
+    };
+    optimistic_update(state, current.toggled(), request, &render).await
+}
+
+/// Follow or unfollow `username` depending on the current state.
+pub async fn toggle_follow(
+    api: &impl SocialApi,
+    username: &str,
+    state: &RefCell<FollowState>,
+    render: impl Fn(FollowState),
+) -> Result<FollowState, ApiError> {
+    let current = *state.borrow();
+    let request = async {
+        let profile = if current.following { api.unfollow(username).await? } else { api.follow(username).await? };
+        Ok(FollowState::from(&profile))
+    };
+    optimistic_update(state, FollowState { following: !current.following }, request, &render).await
+}
+
+/// Record a settled favorite on every loaded copy of `slug`.
+pub fn apply_favorite(articles: &mut [Article], slug: &str, state: FavoriteState) {
+    for article in articles.iter_mut().filter(|a| a.slug == slug) {
+        article.favorited = state.favorited;
+        article.favorites_count = state.favorites_count;
+    }
+}
+
+/// Record a settled follow on every loaded article by `username`.
+pub fn apply_follow(articles: &mut [Article], username: &str, state: FollowState) {
+    for article in articles.iter_mut().filter(|a| a.author.username == username) {
+        article.author.following = state.following;
+    }
+}
+
+/// Toast text for a toggle that was rolled back.
+pub fn rollback_message(action: &str, err: &ApiError) -> String {
+    format!("Couldn't {}: {}", action, err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_client::FieldErrors;
+    use std::cell::Cell;
+
+    /// Answers every call with `error`, or echoes the requested state when `None`.
+    struct MockClient {
+        error: Option<ApiError>,
+        calls: Cell<usize>,
+    }
+
+    impl MockClient {
+        fn respond<T>(&self, value: T) -> Result<T, ApiError> {
+            self.calls.set(self.calls.get() + 1);
+            match &self.error {
+                Some(err) => Err(err.clone()),
+                None => Ok(value),
+            }
+        }
+    }
+
+    fn article(favorited: bool, favorites_count: i32) -> Article {
+        Article {
+            slug: "how-to-train-your-dragon".to_string(),
+            title: "How to train your dragon".to_string(),
+            description: String::new(),
+            body: String::new(),
+            tag_list: Vec::new(),
+            created_at: "2016-02-18T03:22:56.637Z".to_string(),
+            updated_at: "2016-02-18T03:48:35.824Z".to_string(),
+            favorited,
+            favorites_count,
+            author: profile(false),
+        }
+    }
+
+    fn profile(following: bool) -> Profile {
+        Profile { username: "jake".to_string(), bio: None, image: None, following }
+    }
+
+    impl SocialApi for MockClient {
+        async fn favorite(&self, _slug: &str) -> Result<Article, ApiError> {
+            self.respond(article(true, 6))
+        }
+
+        async fn unfavorite(&self, _slug: &str) -> Result<Article, ApiError> {
+            self.respond(article(false, 5))
+        }
+
+        async fn follow(&self, _username: &str) -> Result<Profile, ApiError> {
+            self.respond(profile(true))
+        }
+
+        async fn unfollow(&self, _username: &str) -> Result<Profile, ApiError> {
+            self.respond(profile(false))
+        }
+    }
+
+    #[tokio::test]
+    async fn favorite_reverts_when_client_fails() {
+        let api = MockClient { error: Some(ApiError::Http(500)), calls: Cell::new(0) };
+        let initial = FavoriteState { favorited: false, favorites_count: 5 };
+        let state = RefCell::new(initial);
+        let rendered = RefCell::new(Vec::new());
+
+        let err = toggle_favorite(&api, "how-to-train-your-dragon", &state, |s| rendered.borrow_mut().push(s))
+            .await
+            .unwrap_err();
+
+        assert_eq!(api.calls.get(), 1);
+        assert_eq!(*state.borrow(), initial);
+        assert_eq!(*rendered.borrow(), vec![FavoriteState { favorited: true, favorites_count: 6 }, initial]);
+        assert_eq!(rollback_message("favorite article", &err), "Couldn't favorite article: HTTP 500");
+    }
+
+    #[tokio::test]
+    async fn toggles_settle_on_server_state() {
+        let api = MockClient { error: None, calls: Cell::new(0) };
+        let state = RefCell::new(FavoriteState { favorited: true, favorites_count: 9 });
+        toggle_favorite(&api, "how-to-train-your-dragon", &state, |_| {}).await.unwrap();
+        // The server's count wins over the optimistic guess of 8
+        assert_eq!(*state.borrow(), FavoriteState { favorited: false, favorites_count: 5 });
+
+        let follow = RefCell::new(FollowState { following: false });
+        toggle_follow(&api, "jake", &follow, |_| {}).await.unwrap();
+        assert!(follow.borrow().following);
+
+        let failing = MockClient { error: Some(ApiError::Unauthorized(FieldErrors::new())), calls: Cell::new(0) };
+        assert!(toggle_follow(&failing, "jake", &follow, |_| {}).await.is_err());
+        assert!(follow.borrow().following);
+    }
+
+    #[test]
+    fn settled_toggles_update_the_loaded_articles() {
+        let mut other = article(false, 1);
+        other.slug = "other".to_string();
+        other.author.username = "ada".to_string();
+        let mut articles = vec![article(false, 5), other];
+
+        apply_favorite(&mut articles, "how-to-train-your-dragon", FavoriteState { favorited: true, favorites_count: 6 });
+        apply_follow(&mut articles, "jake", FollowState { following: true });
+
+        assert_eq!(FavoriteState::from(&articles[0]), FavoriteState { favorited: true, favorites_count: 6 });
+        assert!(articles[0].author.following);
+        assert_eq!(FavoriteState::from(&articles[1]), FavoriteState { favorited: false, favorites_count: 1 });
+        assert!(!articles[1].author.following);
+    }
+}
@@ -1,15 +1,49 @@
 // Conduit Example App -- GTK Main Application Window
 // GtkApplicationWindow with header bar, article list sidebar, and detail pane.
+// Favorite/follow buttons update optimistically and report rollbacks in a toast.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
 
 use gtk4::prelude::*;
 use gtk4::{
-    ApplicationWindow, Box as GtkBox, Button, HeaderBar, Label, ListBox, ListBoxRow,
-    Orientation, Paned, ScrolledWindow, Separator, Stack,
+    Align, ApplicationWindow, Box as GtkBox, Button, HeaderBar, Label, ListBox, ListBoxRow,
+    Orientation, Overlay, Paned, Revealer, RevealerTransitionType, ScrolledWindow, Separator,
 };
 
 use crate::api_client::{ApiClient, Article};
 use crate::views::article_list::build_article_list;
 use crate::views::login::show_login_dialog;
+use crate::views::social::{
+    apply_favorite, apply_follow, rollback_message, toggle_favorite, toggle_follow, FavoriteState, FollowState,
+};
+
+/// Seconds a toast stays on screen.
+const TOAST_SECS: u32 = 3;
+
+fn render_favorite(button: &Button, state: FavoriteState) {
+    let label = if state.favorited {
+        format!("\u{2665} Unfavorite ({})", state.favorites_count)
+    } else {
+        format!("\u{2661} Favorite ({})", state.favorites_count)
+    };
+    button.set_label(&label);
+}
+
+fn render_follow(button: &Button, username: &str, state: FollowState) {
+    let verb = if state.following { "Unfollow" } else { "Follow" };
+    button.set_label(&format!("{} {}", verb, username));
+}
+
+/// Toggles waiting on the server, keyed by article slug or author username.
+type Pending<S> = Rc<RefCell<HashMap<String, Rc<RefCell<S>>>>>;
+
+/// The article whose slug is `selected`, as last loaded or toggled.
+fn selected_article(selected: &RefCell<Option<String>>, articles: &RefCell<Vec<Article>>) -> Option<Article> {
+    let slug = selected.borrow().clone()?;
+    articles.borrow().iter().find(|a| a.slug == slug).cloned()
+}
 
 pub fn build_window(app: &gtk4::Application, api_client: ApiClient) -> ApplicationWindow {
     let window = ApplicationWindow::builder()
@@ -80,8 +114,17 @@ pub fn build_window(app: &gtk4::Application, api_client: ApiClient) -> Applicati
         .xalign(0.0)
         .build();
 
+    let actions_box = GtkBox::new(Orientation::Horizontal, 8);
+    let favorite_btn = Button::with_label("\u{2661} Favorite");
+    let follow_btn = Button::with_label("Follow");
+    favorite_btn.set_sensitive(false);
+    follow_btn.set_sensitive(false);
+    actions_box.append(&favorite_btn);
+    actions_box.append(&follow_btn);
+
     detail_box.append(&title_label);
     detail_box.append(&author_label);
+    detail_box.append(&actions_box);
     detail_box.append(&Separator::new(Orientation::Horizontal));
     detail_box.append(&body_label);
     detail_box.append(&tags_label);
@@ -89,7 +132,47 @@ pub fn build_window(app: &gtk4::Application, api_client: ApiClient) -> Applicati
     detail_scroll.set_child(Some(&detail_box));
     paned.set_end_child(Some(&detail_scroll));
 
-    window.set_child(Some(&paned));
+    // Toast shown when an optimistic update is rolled back
+    let toast_label = Label::builder()
+        .css_classes(["app-notification"])
+        .build();
+    let toast = Revealer::builder()
+        .transition_type(RevealerTransitionType::SlideUp)
+        .halign(Align::Center)
+        .valign(Align::End)
+        .margin_bottom(16)
+        .child(&toast_label)
+        .build();
+
+    let overlay = Overlay::new();
+    overlay.set_child(Some(&paned));
+    overlay.add_overlay(&toast);
+    window.set_child(Some(&overlay));
+
+    // A newer toast restarts the timer rather than being hidden by an older one
+    let toast_timer: Rc<RefCell<Option<glib::SourceId>>> = Rc::new(RefCell::new(None));
+    let show_toast = Rc::new(move |message: &str| {
+        toast_label.set_label(message);
+        toast.set_reveal_child(true);
+        if let Some(timer) = toast_timer.take() {
+            timer.remove();
+        }
+        let toast = toast.clone();
+        let timer_slot = toast_timer.clone();
+        let timer = glib::timeout_add_seconds_local_once(TOAST_SECS, move || {
+            timer_slot.take();
+            toast.set_reveal_child(false);
+        });
+        toast_timer.replace(Some(timer));
+    });
+
+    // Loaded articles, the selected slug, and toggles still in flight. A
+    // toggle only renders while its article is selected, so switching
+    // articles mid-request never shows another article's state.
+    let articles: Rc<RefCell<Vec<Article>>> = Rc::new(RefCell::new(Vec::new()));
+    let selected: Rc<RefCell<Option<String>>> = Rc::new(RefCell::new(None));
+    let pending_favorites: Pending<FavoriteState> = Rc::new(RefCell::new(HashMap::new()));
+    let pending_follows: Pending<FollowState> = Rc::new(RefCell::new(HashMap::new()));
 
     // Load articles
     let api = api_client.clone();
@@ -98,6 +181,12 @@ pub fn build_window(app: &gtk4::Application, api_client: ApiClient) -> Applicati
     let al = author_label.clone();
     let bl = body_label.clone();
     let tgl = tags_label.clone();
+    let list = articles.clone();
+    let sel = selected.clone();
+    let pending_fav = pending_favorites.clone();
+    let pending_fol = pending_follows.clone();
+    let fav_btn = favorite_btn.clone();
+    let fol_btn = follow_btn.clone();
 
     let load_articles = move || {
         let api = api.clone();
@@ -106,6 +195,12 @@ pub fn build_window(app: &gtk4::Application, api_client: ApiClient) -> Applicati
         let al = al.clone();
         let bl = bl.clone();
         let tgl = tgl.clone();
+        let list = list.clone();
+        let sel = sel.clone();
+        let pending_fav = pending_fav.clone();
+        let pending_fol = pending_fol.clone();
+        let fav_btn = fav_btn.clone();
+        let fol_btn = fol_btn.clone();
 
         glib::spawn_future_local(async move {
             match api.get_articles().await {
@@ -114,8 +209,12 @@ pub fn build_window(app: &gtk4::Application, api_client: ApiClient) -> Applicati
                     while let Some(row) = lb.row_at_index(0) {
                         lb.remove(&row);
                     }
+                    *list.borrow_mut() = articles.clone();
 
                     build_article_list(&lb, &articles, move |article: &Article| {
+                        *sel.borrow_mut() = Some(article.slug.clone());
+                        // Rows hold the articles as first loaded; toggles since then live in `list`
+                        let Some(article) = selected_article(&sel, &list) else { return };
                         tl.set_label(&article.title);
                         al.set_label(&format!(
                             "By {} \u{00B7} {} \u{00B7} \u{2665} {}",
@@ -130,6 +229,16 @@ pub fn build_window(app: &gtk4::Application, api_client: ApiClient) -> Applicati
                             format!("Tags: {}", article.tag_list.join(", "))
                         };
                         tgl.set_label(&tag_text);
+
+                        // Show a pending toggle's optimistic state and keep its button
+                        // disabled until that request settles
+                        let username = &article.author.username;
+                        let favorite = pending_fav.borrow().get(&article.slug).map(|s| *s.borrow());
+                        let follow = pending_fol.borrow().get(username).map(|s| *s.borrow());
+                        render_favorite(&fav_btn, favorite.unwrap_or_else(|| FavoriteState::from(&article)));
+                        render_follow(&fol_btn, username, follow.unwrap_or_else(|| FollowState::from(&article.author)));
+                        fav_btn.set_sensitive(favorite.is_none());
+                        fol_btn.set_sensitive(follow.is_none());
                     });
                 }
                 Err(err) => {
@@ -151,13 +260,96 @@ pub fn build_window(app: &gtk4::Application, api_client: ApiClient) -> Applicati
         load_fn();
     });
 
+    // Favorite button: flip now, roll back with a toast if the request fails
+    let api = api_client.clone();
+    let list = articles.clone();
+    let sel = selected.clone();
+    let pending = pending_favorites.clone();
+    let toast_fn = show_toast.clone();
+    favorite_btn.connect_clicked(move |btn| {
+        let Some(article) = selected_article(&sel, &list) else { return };
+        let slug = article.slug.clone();
+        // One request per article, so a rollback never undoes a newer toggle
+        if pending.borrow().contains_key(&slug) {
+            return;
+        }
+        let state = Rc::new(RefCell::new(FavoriteState::from(&article)));
+        pending.borrow_mut().insert(slug.clone(), state.clone());
+
+        let api = api.clone();
+        let list = list.clone();
+        let sel = sel.clone();
+        let pending = pending.clone();
+        let toast_fn = toast_fn.clone();
+        let button = btn.clone();
+        button.set_sensitive(false);
+        glib::spawn_future_local(async move {
+            let is_selected = || sel.borrow().as_deref() == Some(slug.as_str());
+            let result = toggle_favorite(&api, &slug, &state, |s| {
+                if is_selected() {
+                    render_favorite(&button, s);
+                }
+            })
+            .await;
+            pending.borrow_mut().remove(&slug);
+            match result {
+                Ok(settled) => apply_favorite(&mut list.borrow_mut(), &slug, settled),
+                Err(err) => toast_fn(&rollback_message("update favorite", &err)),
+            }
+            if is_selected() {
+                button.set_sensitive(true);
+            }
+        });
+    });
+
+    // Follow button
+    let api = api_client.clone();
+    let list = articles.clone();
+    let sel = selected.clone();
+    let pending = pending_follows.clone();
+    let toast_fn = show_toast.clone();
+    follow_btn.connect_clicked(move |btn| {
+        let Some(article) = selected_article(&sel, &list) else { return };
+        let username = article.author.username.clone();
+        if pending.borrow().contains_key(&username) {
+            return;
+        }
+        let state = Rc::new(RefCell::new(FollowState::from(&article.author)));
+        pending.borrow_mut().insert(username.clone(), state.clone());
+
+        let api = api.clone();
+        let list = list.clone();
+        let sel = sel.clone();
+        let pending = pending.clone();
+        let toast_fn = toast_fn.clone();
+        let button = btn.clone();
+        button.set_sensitive(false);
+        glib::spawn_future_local(async move {
+            let is_selected = || selected_article(&sel, &list).is_some_and(|a| a.author.username == username);
+            let result = toggle_follow(&api, &username, &state, |s| {
+                if is_selected() {
+                    render_follow(&button, &username, s);
+                }
+            })
+            .await;
+            pending.borrow_mut().remove(&username);
+            match result {
+                Ok(settled) => apply_follow(&mut list.borrow_mut(), &username, settled),
+                Err(err) => toast_fn(&rollback_message("update follow", &err)),
+            }
+            if is_selected() {
+                button.set_sensitive(true);
+            }
+        });
+    });
+
     // Login button
     let win_ref = window.clone();
     let api_for_login = api_client.clone();
     login_btn.connect_clicked(move |btn| {
         let api = api_for_login.clone();
         let button = btn.clone();
-        show_login_dialog(&win_ref, api, move |_user| {
+        show_login_dialog(win_ref.upcast_ref(), api, move |_user| {
             button.set_label("Signed In");
         });
     });
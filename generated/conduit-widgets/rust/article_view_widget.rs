@@ -53,6 +53,7 @@ pub fn spec() -> WidgetSpec {
                 "favorite_count".to_string(),
                 "follow_button".to_string(),
                 "comment_section".to_string(),
+                "error_banner".to_string(),
             ],
             slots: vec!["comment_section".to_string()],
         },
@@ -250,7 +251,7 @@ pub fn spec() -> WidgetSpec {
             ]),
             live_regions: Some(vec![
                 "favorite_count".to_string(),
-                "error".to_string(),
+                "error_banner".to_string(),
             ]),
         },
     }
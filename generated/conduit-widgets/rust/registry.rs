@@ -2,10 +2,13 @@
 //
 // Widget registry for the Conduit application.
 // Provides registration, lookup, and enumeration of Clef Surface widget specs.
+// Specs are hydrated with defaults on registration; `validate_all` checks
+// every registered spec and is meant to run at startup.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::LazyLock;
 
-use crate::widget_spec::WidgetSpec;
+use crate::widget_spec::{SpecError, WidgetSpec};
 
 /// A registry that stores and retrieves widget specifications by name.
 #[derive(Debug, Clone)]
@@ -21,9 +24,11 @@ impl WidgetRegistry {
         }
     }
 
-    /// Registers a widget specification. If a widget with the same name
-    /// already exists, it is replaced and the old spec is returned.
-    pub fn register(&mut self, spec: WidgetSpec) -> Option<WidgetSpec> {
+    /// Registers a widget specification, filling in defaults first. If a
+    /// widget with the same name already exists, it is replaced and the old
+    /// spec is returned.
+    pub fn register(&mut self, mut spec: WidgetSpec) -> Option<WidgetSpec> {
+        spec.hydrate_defaults();
         self.widgets.insert(spec.name.clone(), spec)
     }
 
@@ -61,6 +66,21 @@ impl WidgetRegistry {
     pub fn names(&self) -> impl Iterator<Item = &String> {
         self.widgets.keys()
     }
+
+    /// Validates every registered spec. On failure, returns the errors of
+    /// each invalid spec keyed by widget name.
+    pub fn validate_all(&self) -> Result<(), BTreeMap<String, Vec<SpecError>>> {
+        let failures: BTreeMap<String, Vec<SpecError>> = self
+            .widgets
+            .iter()
+            .filter_map(|(name, spec)| spec.validate().err().map(|errors| (name.clone(), errors)))
+            .collect();
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(failures)
+        }
+    }
 }
 
 impl Default for WidgetRegistry {
@@ -84,3 +104,30 @@ pub fn conduit_registry() -> WidgetRegistry {
 
     registry
 }
+
+static CONDUIT_REGISTRY: LazyLock<WidgetRegistry> = LazyLock::new(conduit_registry);
+
+/// Looks up a Conduit widget specification by name.
+pub fn resolve(id: &str) -> Option<&'static WidgetSpec> {
+    CONDUIT_REGISTRY.get(id)
+}
+
+/// Validates all Conduit widget specifications. Call at startup so an
+/// inconsistent generated spec fails fast.
+pub fn validate_all() -> Result<(), BTreeMap<String, Vec<SpecError>>> {
+    CONDUIT_REGISTRY.validate_all()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conduit_specs_resolve_and_validate() {
+        assert_eq!(validate_all(), Ok(()));
+
+        let login = resolve("LoginWidget").expect("login widget is registered");
+        assert_eq!(login.anatomy.component, "LoginWidget");
+        assert!(resolve("MissingWidget").is_none());
+    }
+}
@@ -131,3 +131,269 @@ pub struct WidgetSpec {
     pub machine: MachineSpec,
     pub a11y: A11ySpec,
 }
+
+/// An internal inconsistency found by `WidgetSpec::validate`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum SpecError {
+    /// A required top-level field is empty.
+    MissingField { field: String },
+    /// Two elements share an id.
+    DuplicateElementId { id: String },
+    /// A selection element declares no options.
+    MissingOptions { element: String },
+    /// A constraint's lower bound exceeds its upper bound.
+    InvalidConstraint { element: String, constraint: String },
+    /// A state referenced by the machine does not exist.
+    UnknownState {
+        state: String,
+        referenced_by: String,
+    },
+    /// A state is stored under a key that differs from its name.
+    StateNameMismatch { key: String, name: String },
+    /// An anatomy part referenced by a slot or live region does not exist.
+    UnknownPart { part: String, referenced_by: String },
+}
+
+impl std::fmt::Display for SpecError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SpecError::MissingField { field } => write!(f, "{} must not be empty", field),
+            SpecError::DuplicateElementId { id } => {
+                write!(f, "element id '{}' is used more than once", id)
+            }
+            SpecError::MissingOptions { element } => {
+                write!(f, "selection element '{}' declares no options", element)
+            }
+            SpecError::InvalidConstraint {
+                element,
+                constraint,
+            } => {
+                write!(f, "element '{}' has an empty {} range", element, constraint)
+            }
+            SpecError::UnknownState {
+                state,
+                referenced_by,
+            } => {
+                write!(f, "{} refers to unknown state '{}'", referenced_by, state)
+            }
+            SpecError::StateNameMismatch { key, name } => {
+                write!(f, "state '{}' is named '{}'", key, name)
+            }
+            SpecError::UnknownPart {
+                part,
+                referenced_by,
+            } => {
+                write!(
+                    f,
+                    "{} refers to unknown anatomy part '{}'",
+                    referenced_by, part
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for SpecError {}
+
+impl ElementNode {
+    /// This element followed by all of its descendants, depth-first.
+    pub fn walk(&self) -> Vec<&ElementNode> {
+        let mut nodes = vec![self];
+        for child in self.children.iter().flatten() {
+            nodes.extend(child.walk());
+        }
+        nodes
+    }
+}
+
+impl WidgetSpec {
+    /// Fill in values generated specs may leave empty: the anatomy component
+    /// and accessible label default to the widget name, and each machine
+    /// state's name to its key.
+    pub fn hydrate_defaults(&mut self) {
+        if self.anatomy.component.is_empty() {
+            self.anatomy.component = self.name.clone();
+        }
+        if self.a11y.label.is_empty() {
+            self.a11y.label = self.name.clone();
+        }
+        for (key, state) in self.machine.states.iter_mut() {
+            if state.name.is_empty() {
+                state.name = key.clone();
+            }
+        }
+    }
+
+    /// Check that the spec is internally consistent, collecting every
+    /// problem rather than stopping at the first.
+    pub fn validate(&self) -> Result<(), Vec<SpecError>> {
+        let mut errors = Vec::new();
+
+        let required = [
+            ("name", &self.name),
+            ("version", &self.version),
+            ("anatomy.component", &self.anatomy.component),
+            ("a11y.role", &self.a11y.role),
+            ("a11y.label", &self.a11y.label),
+        ];
+        for (field, value) in required {
+            if value.trim().is_empty() {
+                errors.push(SpecError::MissingField {
+                    field: field.to_string(),
+                });
+            }
+        }
+
+        let mut seen = std::collections::HashSet::new();
+        for element in self.elements.iter().flat_map(ElementNode::walk) {
+            if element.id.is_empty() {
+                errors.push(SpecError::MissingField {
+                    field: "elements.id".to_string(),
+                });
+            } else if !seen.insert(element.id.as_str()) {
+                errors.push(SpecError::DuplicateElementId {
+                    id: element.id.clone(),
+                });
+            }
+
+            let constraints = element.constraints.as_ref();
+            let is_selection = matches!(
+                element.kind,
+                ElementKind::SelectionSingle | ElementKind::SelectionMulti
+            );
+            let has_options = constraints
+                .and_then(|c| c.options.as_ref())
+                .is_some_and(|options| !options.is_empty());
+            if is_selection && !has_options {
+                errors.push(SpecError::MissingOptions {
+                    element: element.id.clone(),
+                });
+            }
+            if let Some(c) = constraints {
+                if matches!((c.min, c.max), (Some(min), Some(max)) if min > max) {
+                    errors.push(SpecError::InvalidConstraint {
+                        element: element.id.clone(),
+                        constraint: "min/max".to_string(),
+                    });
+                }
+                if matches!((c.min_length, c.max_length), (Some(min), Some(max)) if min > max) {
+                    errors.push(SpecError::InvalidConstraint {
+                        element: element.id.clone(),
+                        constraint: "min_length/max_length".to_string(),
+                    });
+                }
+            }
+        }
+
+        let states = &self.machine.states;
+        if !states.contains_key(&self.machine.initial) {
+            errors.push(SpecError::UnknownState {
+                state: self.machine.initial.clone(),
+                referenced_by: "machine.initial".to_string(),
+            });
+        }
+        // Sort for a stable error order; the states are a HashMap
+        let mut keys: Vec<&String> = states.keys().collect();
+        keys.sort();
+        for key in keys {
+            let state = &states[key];
+            if state.name != *key {
+                errors.push(SpecError::StateNameMismatch {
+                    key: key.clone(),
+                    name: state.name.clone(),
+                });
+            }
+            let mut events: Vec<(&String, &MachineTransition)> = state.on.iter().collect();
+            events.sort_by(|a, b| a.0.cmp(b.0));
+            for (event, transition) in events {
+                if !states.contains_key(&transition.target) {
+                    errors.push(SpecError::UnknownState {
+                        state: transition.target.clone(),
+                        referenced_by: format!("{}.on.{}", key, event),
+                    });
+                }
+            }
+        }
+
+        let referenced_parts = self
+            .anatomy
+            .slots
+            .iter()
+            .map(|part| (part, "anatomy.slots"))
+            .chain(
+                self.a11y
+                    .live_regions
+                    .iter()
+                    .flatten()
+                    .map(|part| (part, "a11y.live_regions")),
+            );
+        for (part, referenced_by) in referenced_parts {
+            if !self.anatomy.parts.contains(part) {
+                errors.push(SpecError::UnknownPart {
+                    part: part.clone(),
+                    referenced_by: referenced_by.to_string(),
+                });
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dangling_references_are_reported() {
+        let mut spec = crate::login_widget::spec();
+        spec.a11y.live_regions = Some(vec!["error_banner".to_string(), "toast".to_string()]);
+        spec.machine
+            .states
+            .get_mut("error")
+            .unwrap()
+            .on
+            .get_mut("RETRY")
+            .unwrap()
+            .target = "retrying".to_string();
+        spec.elements[1].id = "login.email".to_string();
+
+        let errors = spec.validate().unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                SpecError::DuplicateElementId {
+                    id: "login.email".to_string()
+                },
+                SpecError::UnknownState {
+                    state: "retrying".to_string(),
+                    referenced_by: "error.on.RETRY".to_string(),
+                },
+                SpecError::UnknownPart {
+                    part: "toast".to_string(),
+                    referenced_by: "a11y.live_regions".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            errors[2].to_string(),
+            "a11y.live_regions refers to unknown anatomy part 'toast'"
+        );
+    }
+
+    #[test]
+    fn hydrated_spec_passes_validation() {
+        let mut spec = crate::login_widget::spec();
+        spec.anatomy.component.clear();
+        spec.machine.states.get_mut("idle").unwrap().name.clear();
+        assert!(spec.validate().is_err());
+
+        spec.hydrate_defaults();
+        assert_eq!(spec.anatomy.component, "LoginWidget");
+        assert_eq!(spec.validate(), Ok(()));
+    }
+}
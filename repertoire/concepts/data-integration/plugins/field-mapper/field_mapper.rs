@@ -1,10 +1,12 @@
 // Field Mapper Plugin — source path resolution implementations for the FieldMapping concept
 // Provides pluggable path syntax resolvers to extract values from raw records using
-// direct dot-notation, JSONPath, XPath, regex, template interpolation, and computed expressions.
+// direct dot-notation, JSONPath, XPath, regex, template interpolation, computed expressions,
+// and CSV/TSV column lookups.
 // See Data Integration Kit field-mapping.concept for the parent FieldMapping concept definition.
 
 use std::collections::HashMap;
 use std::fmt;
+use std::sync::LazyLock;
 
 use regex::Regex;
 use serde_json::Value;
@@ -74,6 +76,20 @@ pub trait FieldMapperPlugin: Send + Sync {
     /// Resolve a source path to a value within the given record.
     fn resolve(&self, record: &RawRecord, source_path: &str, config: &MapperConfig) -> Value;
 
+    /// Like `resolve`, but reports unsupported or malformed paths as errors
+    /// instead of folding them into the default. A field that is simply absent
+    /// still resolves to `Ok` with the default (or null), so mapping configs
+    /// can be validated up front.
+    fn try_resolve(&self, record: &RawRecord, source_path: &str, config: &MapperConfig) -> Result<Value, FieldMapperError> {
+        if !self.supports(source_path) {
+            return Err(FieldMapperError::UnsupportedSyntax {
+                provider: self.id().to_string(),
+                path: source_path.to_string(),
+            });
+        }
+        Ok(self.resolve(record, source_path, config))
+    }
+
     /// Check whether this provider supports the given path syntax.
     fn supports(&self, path_syntax: &str) -> bool;
 }
@@ -273,6 +289,8 @@ fn resolve_wildcard(record: &Value, keys: &[String], wildcard_idx: usize, config
 ///   - Wildcard: `$.items[*]`
 ///   - Filter: `$.items[?(@.price < 10)]`
 ///   - Union: `$.items[0,2,4]`
+///   - Functions: `$.items.length()`, `$.items[*].price.sum()`; also inside
+///     filters, e.g. `$.items[?(@.tags.length() > 0)]`
 ///
 /// Reference: Drupal External Entities JSONPath mapper.
 pub struct JsonPathMapper;
@@ -286,8 +304,12 @@ enum JpToken {
     Filter(String),
     Slice(String),
     Union(String),
+    Function(String),
 }
 
+/// Trailing functions: `length()` maps over nodes; the rest aggregate the node set.
+const JP_FUNCTIONS: &[&str] = &["length", "count", "min", "max", "sum", "avg"];
+
 impl FieldMapperPlugin for JsonPathMapper {
     fn id(&self) -> &str { "jsonpath" }
     fn display_name(&self) -> &str { "JSONPath Expression Mapper" }
@@ -301,6 +323,78 @@ impl FieldMapperPlugin for JsonPathMapper {
         if results.is_empty() { return default_or_null(config); }
         if config.return_all { Value::Array(results) } else { results.into_iter().next().unwrap() }
     }
+
+    fn try_resolve(&self, record: &RawRecord, source_path: &str, config: &MapperConfig) -> Result<Value, FieldMapperError> {
+        if !self.supports(source_path) {
+            return Err(FieldMapperError::UnsupportedSyntax {
+                provider: self.id().to_string(),
+                path: source_path.to_string(),
+            });
+        }
+        jp_validate(source_path).map_err(|detail| FieldMapperError::InvalidExpression {
+            detail: format!("{source_path}: {detail}"),
+        })?;
+        Ok(self.resolve(record, source_path, config))
+    }
+}
+
+/// Structural check of a JSONPath: balanced brackets, closed string literals,
+/// and no empty member names. The tokenizer itself is lenient and never fails.
+fn jp_validate(path: &str) -> Result<(), String> {
+    let chars: Vec<char> = path.chars().collect();
+    if chars.first() != Some(&'$') {
+        return Err("path must start with '$'".into());
+    }
+
+    let mut depth = 0usize;
+    let mut quote: Option<char> = None;
+    let mut i = 1;
+    while i < chars.len() {
+        let c = chars[i];
+        if let Some(q) = quote {
+            if c == q { quote = None; }
+            i += 1;
+            continue;
+        }
+        match c {
+            '\'' | '"' if depth > 0 => quote = Some(c),
+            '[' => {
+                if chars.get(i + 1) == Some(&']') {
+                    return Err(format!("empty brackets at position {i}"));
+                }
+                depth += 1;
+            }
+            ']' => {
+                if depth == 0 {
+                    return Err(format!("unexpected ']' at position {i}"));
+                }
+                depth -= 1;
+            }
+            '.' if depth == 0 => {
+                let descent = chars.get(i + 1) == Some(&'.');
+                let next = chars.get(if descent { i + 2 } else { i + 1 });
+                let empty_name = match next {
+                    None | Some('.') => true,
+                    Some('[') => !descent,
+                    _ => false,
+                };
+                if empty_name {
+                    return Err(format!("missing member name after '.' at position {i}"));
+                }
+                if descent { i += 1; }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    if quote.is_some() {
+        return Err("unterminated string literal".into());
+    }
+    if depth > 0 {
+        return Err("unclosed '['".into());
+    }
+    Ok(())
 }
 
 fn jp_evaluate(root: &Value, path: &str) -> Vec<Value> {
@@ -308,6 +402,10 @@ fn jp_evaluate(root: &Value, path: &str) -> Vec<Value> {
     let mut current = vec![root.clone()];
 
     for token in &tokens {
+        if let JpToken::Function(name) = token {
+            current = jp_apply_function(name, current);
+            continue;
+        }
         let mut next = Vec::new();
         for node in &current {
             next.extend(jp_apply_token(root, node, token));
@@ -335,7 +433,10 @@ fn jp_tokenize(path: &str) -> Vec<JpToken> {
         } else if chars[i] == '.' {
             i += 1;
             let name = jp_read_name(&chars, &mut i);
-            tokens.push(JpToken::Child(name));
+            match jp_function_name(&name) {
+                Some(func) => tokens.push(JpToken::Function(func.to_string())),
+                None => tokens.push(JpToken::Child(name)),
+            }
         } else if chars[i] == '[' {
             let (token, end) = jp_read_bracket(&chars, i);
             tokens.push(token);
@@ -444,6 +545,60 @@ fn jp_apply_token(root: &Value, node: &Value, token: &JpToken) -> Vec<Value> {
             }
         }
         JpToken::Union(expr) => jp_apply_union(node, expr),
+        JpToken::Function(name) => jp_apply_function(name, vec![node.clone()]),
+    }
+}
+
+/// Return the function name if `segment` is a call like `length()`.
+fn jp_function_name(segment: &str) -> Option<&str> {
+    let name = segment.trim().strip_suffix("()")?;
+    JP_FUNCTIONS.contains(&name).then_some(name)
+}
+
+/// Apply a function to a node set. Aggregates work over the elements when the
+/// set is a single array (`$.items.sum()`), otherwise over the nodes themselves
+/// (`$.items[*].price.sum()`).
+fn jp_apply_function(name: &str, nodes: Vec<Value>) -> Vec<Value> {
+    if name == "length" {
+        return nodes.iter()
+            .filter_map(|node| match node {
+                Value::Array(arr) => Some(arr.len()),
+                Value::Object(map) => Some(map.len()),
+                Value::String(s) => Some(s.chars().count()),
+                _ => None,
+            })
+            .map(|n| Value::from(n as u64))
+            .collect();
+    }
+
+    let values = match nodes.as_slice() {
+        [Value::Array(arr)] => arr.clone(),
+        _ => nodes,
+    };
+    if name == "count" {
+        return vec![Value::from(values.len() as u64)];
+    }
+
+    let numbers: Vec<f64> = values.iter().filter_map(|v| match v {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }).collect();
+    let result = match name {
+        "sum" => Some(numbers.iter().sum()),
+        "min" => numbers.iter().copied().reduce(f64::min),
+        "max" => numbers.iter().copied().reduce(f64::max),
+        "avg" if !numbers.is_empty() => Some(numbers.iter().sum::<f64>() / numbers.len() as f64),
+        _ => None,
+    };
+    result.map(jp_number).into_iter().collect()
+}
+
+fn jp_number(n: f64) -> Value {
+    if n.fract() == 0.0 && n.abs() < 9_007_199_254_740_992.0 {
+        Value::from(n as i64)
+    } else {
+        serde_json::Number::from_f64(n).map(Value::Number).unwrap_or(Value::Null)
     }
 }
 
@@ -538,6 +693,12 @@ fn find_logical_op(expr: &str, op: &str) -> Option<usize> {
 }
 
 fn jp_resolve_filter_path(root: &Value, item: &Value, path: &str) -> Value {
+    // `@.tags.length()`: resolve the base path, then apply the function.
+    if let Some((base, func)) = path.rsplit_once('.').and_then(|(b, f)| jp_function_name(f).map(|f| (b, f))) {
+        let base_value = if base == "@" { item.clone() } else { jp_resolve_filter_path(root, item, base) };
+        if base_value.is_null() { return Value::Null; }
+        return jp_apply_function(func, vec![base_value]).into_iter().next().unwrap_or(Value::Null);
+    }
     if path.starts_with("@.") {
         let keys = parse_dot_path(&path[2..]);
         return get_nested_value(item, &keys).cloned().unwrap_or(Value::Null);
@@ -597,32 +758,36 @@ fn value_to_string(val: &Value) -> String {
     }
 }
 
+/// Apply a `[start:end:step]` slice with RFC 9535 semantics: negative indices
+/// count from the end, and a negative step walks backwards from `start`
+/// (default: last element) down to, but excluding, `end` (default: before the
+/// first element).
 fn jp_apply_slice(arr: &[Value], expr: &str) -> Vec<Value> {
     let parts: Vec<&str> = expr.split(':').map(|s| s.trim()).collect();
     let len = arr.len() as i64;
+    let part = |i: usize| parts.get(i).filter(|p| !p.is_empty()).and_then(|p| p.parse::<i64>().ok());
 
-    let start = if !parts.is_empty() && !parts[0].is_empty() {
-        let s = parts[0].parse::<i64>().unwrap_or(0);
-        if s < 0 { (len + s).max(0) as usize } else { s.min(len) as usize }
-    } else { 0 };
-
-    let end = if parts.len() > 1 && !parts[1].is_empty() {
-        let e = parts[1].parse::<i64>().unwrap_or(len);
-        if e < 0 { (len + e).max(0) as usize } else { e.min(len) as usize }
-    } else { len as usize };
-
-    let step = if parts.len() > 2 && !parts[2].is_empty() {
-        parts[2].parse::<i64>().unwrap_or(1)
-    } else { 1 };
-
+    let step = part(2).unwrap_or(1);
     if step == 0 { return vec![]; }
 
+    let normalize = |i: i64| if i < 0 { len + i } else { i };
+
     let mut results = Vec::new();
     if step > 0 {
-        let mut i = start;
-        while i < end {
-            results.push(arr[i].clone());
-            i += step as usize;
+        let lower = part(0).map(normalize).unwrap_or(0).clamp(0, len);
+        let upper = part(1).map(normalize).unwrap_or(len).clamp(0, len);
+        let mut i = lower;
+        while i < upper {
+            results.push(arr[i as usize].clone());
+            i += step;
+        }
+    } else {
+        let upper = part(0).map(normalize).unwrap_or(len - 1).clamp(-1, len - 1);
+        let lower = part(1).map(normalize).unwrap_or(-1).clamp(-1, len - 1);
+        let mut i = upper;
+        while lower < i {
+            results.push(arr[i as usize].clone());
+            i += step;
         }
     }
     results
@@ -1061,6 +1226,32 @@ fn apply_xpath_predicate(
         return candidates.last().cloned().into_iter().collect();
     }
 
+    // String functions: contains(arg, 'x'), starts-with(arg, 'x')
+    let func_re = Regex::new(r#"^(contains|starts-with)\(\s*(.+?)\s*,\s*["']([^"']*)["']\s*\)$"#).unwrap();
+    if let Some(caps) = func_re.captures(trimmed) {
+        let (func, arg, needle) = (&caps[1], caps[2].to_string(), caps[3].to_string());
+        return candidates.into_iter().filter(|n| {
+            xpath_string_value(n, &arg).is_some_and(|s| {
+                if func == "contains" { s.contains(&needle) } else { s.starts_with(&needle) }
+            })
+        }).collect();
+    }
+
+    // normalize-space(arg) = 'x', or bare normalize-space(arg) as a non-empty test
+    let norm_re = Regex::new(r#"^(normalize-space\(.*\))\s*(?:(!=|=)\s*["']([^"']*)["'])?$"#).unwrap();
+    if let Some(caps) = norm_re.captures(trimmed) {
+        let expr = caps[1].to_string();
+        let comparison = caps.get(2).map(|op| (op.as_str().to_string(), caps[3].to_string()));
+        return candidates.into_iter().filter(|n| {
+            let value = xpath_string_value(n, &expr).unwrap_or_default();
+            match &comparison {
+                Some((op, expected)) if op == "=" => &value == expected,
+                Some((_, expected)) => &value != expected,
+                None => !value.is_empty(),
+            }
+        }).collect();
+    }
+
     // Attribute existence: @attr
     if trimmed.starts_with('@') && !trimmed.contains('=') {
         let attr_name = &trimmed[1..];
@@ -1090,6 +1281,25 @@ fn apply_xpath_predicate(
     candidates
 }
 
+/// String value of a predicate argument relative to `node`: `.`, `text()`,
+/// `@attr`, a child element name, or `normalize-space(...)` of any of those.
+fn xpath_string_value(node: &XmlNode, arg: &str) -> Option<String> {
+    let arg = arg.trim();
+    if let Some(inner) = arg.strip_prefix("normalize-space(").and_then(|a| a.strip_suffix(')')) {
+        let inner = if inner.trim().is_empty() { "." } else { inner };
+        return xpath_string_value(node, inner)
+            .map(|s| s.split_whitespace().collect::<Vec<_>>().join(" "));
+    }
+    match arg {
+        "." => Some(collect_text(node)),
+        "text()" => Some(node.text.clone()),
+        _ => match arg.strip_prefix('@') {
+            Some(attr) => node.attrs.get(attr).cloned(),
+            None => node.children.iter().find(|c| c.tag == arg).map(collect_text),
+        },
+    }
+}
+
 // ---------------------------------------------------------------------------
 // 4. RegexMapper — regex capture groups extracting values from strings
 // ---------------------------------------------------------------------------
@@ -1108,6 +1318,15 @@ impl FieldMapperPlugin for RegexMapper {
         Regex::new(r"^([\w.]+:)?/.*?/[gimsuvy]*$").unwrap().is_match(path_syntax)
     }
 
+    fn try_resolve(&self, record: &RawRecord, source_path: &str, config: &MapperConfig) -> Result<Value, FieldMapperError> {
+        if parse_regex_path(source_path, config).is_none() {
+            return Err(FieldMapperError::InvalidExpression {
+                detail: format!("{source_path}: not a valid /pattern/flags regex"),
+            });
+        }
+        Ok(self.resolve(record, source_path, config))
+    }
+
     fn resolve(&self, record: &RawRecord, source_path: &str, config: &MapperConfig) -> Value {
         let parsed = match parse_regex_path(source_path, config) {
             Some(p) => p,
@@ -1128,41 +1347,26 @@ impl FieldMapperPlugin for RegexMapper {
             record.to_string()
         };
 
-        let capture_idx = match &parsed.capture_group {
-            CaptureGroupRef::Numbered(n) => *n,
-            CaptureGroupRef::Named(_) => 1, // Named groups in Rust regex use numbered fallback
+        // A configured group name is looked up directly; the numbered index is
+        // only used when the group is referenced by number.
+        let extract = |caps: &regex::Captures| -> Option<Value> {
+            let m = match &parsed.capture_group {
+                CaptureGroupRef::Named(name) => caps.name(name),
+                CaptureGroupRef::Numbered(n) => caps.get(*n),
+            };
+            m.map(|m| Value::String(m.as_str().to_string()))
         };
 
         if config.return_all {
             let results: Vec<Value> = parsed.regex.captures_iter(&source_text)
-                .filter_map(|caps| {
-                    // Try named group first
-                    if let CaptureGroupRef::Named(ref name) = parsed.capture_group {
-                        if let Some(m) = caps.name(name) {
-                            return Some(Value::String(m.as_str().to_string()));
-                        }
-                    }
-                    caps.get(capture_idx)
-                        .map(|m| Value::String(m.as_str().to_string()))
-                })
+                .filter_map(|caps| extract(&caps))
                 .collect();
 
             if results.is_empty() { default_or_null(config) } else { Value::Array(results) }
         } else {
-            match parsed.regex.captures(&source_text) {
-                Some(caps) => {
-                    // Try named group
-                    if let CaptureGroupRef::Named(ref name) = parsed.capture_group {
-                        if let Some(m) = caps.name(name) {
-                            return Value::String(m.as_str().to_string());
-                        }
-                    }
-                    caps.get(capture_idx)
-                        .map(|m| Value::String(m.as_str().to_string()))
-                        .unwrap_or_else(|| default_or_null(config))
-                }
-                None => default_or_null(config),
-            }
+            parsed.regex.captures(&source_text)
+                .and_then(|caps| extract(&caps))
+                .unwrap_or_else(|| default_or_null(config))
         }
     }
 }
@@ -1223,6 +1427,7 @@ fn parse_regex_path(path: &str, config: &MapperConfig) -> Option<ParsedRegexPath
 ///   - Fallback values: `{nickname|first_name|"Anonymous"}`
 ///   - Format specifiers: `{price:.2f}`, `{name:upper}`
 ///   - Conditional segments: `{?phone}Phone: {phone}{/phone}`
+///   - Conditional with else: `{?phone}Phone: {phone}{:}No phone{/phone}`
 ///
 /// Reference: Drupal Migrate concat plugin.
 pub struct TemplateMapper;
@@ -1263,33 +1468,77 @@ fn template_interpolate(record: &Value, template: &str, config: &MapperConfig) -
     result
 }
 
+/// `{?field}` opening a conditional segment.
+static CONDITIONAL_OPEN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\{\?(\w[\w.]*)\}").unwrap());
+
+/// Any conditional token: `{?field}`, `{/field}` or the `{:}` else separator.
+static CONDITIONAL_TOKEN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\{\?(\w[\w.]*)\}|\{/(\w[\w.]*)\}|\{:\}").unwrap());
+
+/// Resolve `{?field}then{:}else{/field}` segments, leaving the chosen branch in
+/// place for interpolation. The `{:}` else part is optional. Segments are
+/// matched by scanning (the regex crate has no backreferences), so they nest.
 fn process_conditionals(record: &Value, template: &str, config: &MapperConfig) -> String {
-    let cond_re = match Regex::new(r"\{\?(\w[\w.]*)\}([\s\S]*?)\{/\1\}") {
-        Ok(re) => re,
-        Err(_) => return template.to_string(),
-    };
+    let mut result = String::new();
+    let mut rest = template;
+
+    while let Some(caps) = CONDITIONAL_OPEN.captures(rest) {
+        let open = caps.get(0).unwrap();
+        let field_name = caps[1].to_string();
+        let body = &rest[open.end()..];
+
+        let Some((else_pos, close_start, close_end)) = find_conditional_end(body, &field_name) else {
+            // Unterminated segment: keep the text as written.
+            result.push_str(&rest[..open.end()]);
+            rest = body;
+            continue;
+        };
 
-    let mut result = template.to_string();
-    while cond_re.is_match(&result) {
-        result = cond_re.replace_all(&result, |caps: &regex::Captures| {
-            let field_name = &caps[1];
-            let content = &caps[2];
+        let (then_branch, else_branch) = match else_pos {
+            Some(pos) => (&body[..pos], &body[pos + "{:}".len()..close_start]),
+            None => (&body[..close_start], ""),
+        };
 
-            let keys = parse_dot_path(field_name);
-            let value = get_nested_value(record, &keys);
+        let keys = parse_dot_path(&field_name);
+        let truthy = matches!(
+            get_nested_value(record, &keys),
+            Some(v) if !v.is_null() && v != &Value::String(String::new()) && v != &Value::Bool(false)
+        );
 
-            match value {
-                Some(v) if !v.is_null() && v != &Value::String(String::new()) && v != &Value::Bool(false) => {
-                    template_interpolate(record, content, config)
-                }
-                _ => String::new(),
-            }
-        }).to_string();
+        result.push_str(&rest[..open.start()]);
+        result.push_str(&process_conditionals(record, if truthy { then_branch } else { else_branch }, config));
+        rest = &body[close_end..];
     }
 
+    result.push_str(rest);
     result
 }
 
+/// Find the `{/field}` closing a conditional body, skipping nested segments.
+/// Returns the top-level `{:}` position (if any) and the closing tag's span.
+fn find_conditional_end(body: &str, field_name: &str) -> Option<(Option<usize>, usize, usize)> {
+    let mut depth = 0usize;
+    let mut else_pos = None;
+
+    for caps in CONDITIONAL_TOKEN.captures_iter(body) {
+        let token = caps.get(0).unwrap();
+        if caps.get(1).is_some() {
+            depth += 1;
+        } else if let Some(name) = caps.get(2) {
+            if depth == 0 {
+                if name.as_str() == field_name {
+                    return Some((else_pos, token.start(), token.end()));
+                }
+            } else {
+                depth -= 1;
+            }
+        } else if depth == 0 && else_pos.is_none() {
+            else_pos = Some(token.start());
+        }
+    }
+    None
+}
+
 fn resolve_template_expression(record: &Value, expression: &str, config: &MapperConfig) -> String {
     let mut field_expr = expression;
     let mut format_spec: Option<&str> = None;
@@ -1416,6 +1665,18 @@ impl FieldMapperPlugin for ComputedMapper {
             Err(_) => default_or_null(config),
         }
     }
+
+    fn try_resolve(&self, record: &RawRecord, source_path: &str, _config: &MapperConfig) -> Result<Value, FieldMapperError> {
+        let tokens = tokenize_expression(source_path);
+        let mut parser = ExprParser::new(tokens, record);
+        let value = parser.parse_ternary()?;
+        if !matches!(parser.peek(), ExprToken::Eof) {
+            return Err(FieldMapperError::InvalidExpression {
+                detail: format!("{source_path}: unexpected trailing token {:?}", parser.peek()),
+            });
+        }
+        Ok(value)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -1865,6 +2126,70 @@ fn call_builtin(name: &str, args: &[Value]) -> Value {
     }
 }
 
+// ---------------------------------------------------------------------------
+// 7. CsvMapper — column lookups against delimited (CSV/TSV) rows
+// ---------------------------------------------------------------------------
+
+/// CsvMapper resolves columns of a row that has already been split by a
+/// CSV/TSV reader. The record is either an object carrying `_csv_headers`
+/// (column names) and `_csv_row` (cell values), or a bare array of cells.
+///
+/// Supported syntax:
+///   - Header name: `column:email`
+///   - Zero-based index: `col[3]`
+///
+/// Reference: Drupal Migrate CSV source plugin.
+pub struct CsvMapper;
+
+impl FieldMapperPlugin for CsvMapper {
+    fn id(&self) -> &str { "csv" }
+    fn display_name(&self) -> &str { "CSV Column Mapper" }
+
+    fn supports(&self, path_syntax: &str) -> bool {
+        parse_csv_path(path_syntax).is_some()
+    }
+
+    fn resolve(&self, record: &RawRecord, source_path: &str, config: &MapperConfig) -> Value {
+        let (headers, row) = match record {
+            Value::Array(cells) => (None, cells),
+            Value::Object(obj) => match obj.get("_csv_row") {
+                Some(Value::Array(cells)) => (obj.get("_csv_headers").and_then(|h| h.as_array()), cells),
+                _ => return default_or_null(config),
+            },
+            _ => return default_or_null(config),
+        };
+
+        let index = match parse_csv_path(source_path) {
+            Some(CsvColumn::Index(i)) => Some(i),
+            Some(CsvColumn::Name(name)) => headers.and_then(|h| {
+                h.iter().position(|header| header.as_str().map(str::trim) == Some(name))
+            }),
+            None => None,
+        };
+
+        match index.and_then(|i| row.get(i)) {
+            Some(v) if !v.is_null() => v.clone(),
+            _ => default_or_null(config),
+        }
+    }
+}
+
+enum CsvColumn<'a> {
+    Name(&'a str),
+    Index(usize),
+}
+
+fn parse_csv_path(path: &str) -> Option<CsvColumn<'_>> {
+    if let Some(name) = path.strip_prefix("column:") {
+        let name = name.trim();
+        return (!name.is_empty()).then_some(CsvColumn::Name(name));
+    }
+    path.strip_prefix("col[")
+        .and_then(|rest| rest.strip_suffix(']'))
+        .and_then(|index| index.trim().parse().ok())
+        .map(CsvColumn::Index)
+}
+
 // ---------------------------------------------------------------------------
 // Factory function and registry
 // ---------------------------------------------------------------------------
@@ -1879,20 +2204,21 @@ pub fn create_provider(id: &str) -> Option<Box<dyn FieldMapperPlugin>> {
         "regex" => Some(Box::new(RegexMapper)),
         "template" => Some(Box::new(TemplateMapper)),
         "computed" => Some(Box::new(ComputedMapper)),
+        "csv" => Some(Box::new(CsvMapper)),
         _ => None,
     }
 }
 
 /// Return all available provider IDs.
 pub fn available_providers() -> Vec<&'static str> {
-    vec!["direct", "jsonpath", "xpath", "regex", "template", "computed"]
+    vec!["direct", "jsonpath", "xpath", "regex", "template", "computed", "csv"]
 }
 
 /// Resolve the best provider for a given path syntax.
 /// Returns the first provider whose `supports()` returns true, preferring
 /// more specific syntaxes (checked in specificity order).
 pub fn resolve_provider(path_syntax: &str) -> Option<Box<dyn FieldMapperPlugin>> {
-    let ordered_ids = ["jsonpath", "xpath", "regex", "template", "computed", "direct"];
+    let ordered_ids = ["jsonpath", "xpath", "regex", "csv", "template", "computed", "direct"];
     for id in ordered_ids {
         if let Some(provider) = create_provider(id) {
             if provider.supports(path_syntax) {
@@ -1902,3 +2228,188 @@ pub fn resolve_provider(path_syntax: &str) -> Option<Box<dyn FieldMapperPlugin>>
     }
     None
 }
+
+/// Map a whole record in one pass. `mappings` is keyed by target field, each
+/// entry holding the source path and its config; every path is resolved with
+/// the provider picked by `resolve_provider`. Paths no provider supports
+/// resolve to the entry's default (or null).
+pub fn map_record(
+    record: &RawRecord,
+    mappings: &HashMap<String, (String, MapperConfig)>,
+) -> serde_json::Map<String, Value> {
+    mappings
+        .iter()
+        .map(|(target, (source_path, config))| {
+            let value = match resolve_provider(source_path) {
+                Some(provider) => provider.resolve(record, source_path, config),
+                None => default_or_null(config),
+            };
+            (target.clone(), value)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn regex_mapper_resolves_named_group_that_is_not_group_one() {
+        let record = json!({ "title": "Report Q3 2024, revised 2025" });
+        let config = MapperConfig {
+            capture_group: Some(CaptureGroupRef::Named("year".into())),
+            ..Default::default()
+        };
+        let path = r"title:/(Q\d) (?P<year>\d{4})/";
+        assert_eq!(RegexMapper.resolve(&record, path, &config), json!("2024"));
+
+        let all = MapperConfig { return_all: true, ..config };
+        let path = r"title:/(\w+) (?P<year>\d{4})/";
+        assert_eq!(RegexMapper.resolve(&record, path, &all), json!(["2024", "2025"]));
+    }
+
+    #[test]
+    fn jsonpath_length_and_aggregate_functions() {
+        let record = json!({ "items": [
+            { "name": "a", "price": 10, "tags": ["x"] },
+            { "name": "b", "price": 20, "tags": [] },
+            { "name": "c", "price": 30, "tags": ["y", "z"] },
+        ] });
+        let config = MapperConfig::default();
+        assert_eq!(JsonPathMapper.resolve(&record, "$.items.length()", &config), json!(3));
+        assert_eq!(JsonPathMapper.resolve(&record, "$.items[*].price.sum()", &config), json!(60));
+        assert_eq!(JsonPathMapper.resolve(&record, "$.items[*].price.avg()", &config), json!(20));
+        assert_eq!(JsonPathMapper.resolve(&record, "$.items[*].count()", &config), json!(3));
+    }
+
+    #[test]
+    fn jsonpath_filter_using_length() {
+        let record = json!({ "items": [
+            { "name": "a", "tags": ["x"] },
+            { "name": "b", "tags": [] },
+            { "name": "c", "tags": ["y", "z"] },
+        ] });
+        let config = MapperConfig { return_all: true, ..Default::default() };
+        assert_eq!(
+            JsonPathMapper.resolve(&record, "$.items[?(@.tags.length() > 0)].name", &config),
+            json!(["a", "c"])
+        );
+    }
+
+    #[test]
+    fn xpath_predicates_with_string_functions() {
+        let record = json!({ "_xml": "<catalog>\
+            <item sku=\"RS-1\"><title>Programming Rust</title></item>\
+            <item sku=\"GO-1\"><title>Go in Action</title></item>\
+            <item sku=\"RS-2\"><title>Rust   Atomics</title></item>\
+            </catalog>" });
+        let all = MapperConfig { return_all: true, ..Default::default() };
+
+        assert_eq!(
+            XPathMapper.resolve(&record, "//item[contains(title,'Rust')]/title", &all),
+            json!(["Programming Rust", "Rust   Atomics"])
+        );
+        assert_eq!(
+            XPathMapper.resolve(&record, "//item[starts-with(@sku, 'GO')]/title", &all),
+            json!(["Go in Action"])
+        );
+        assert_eq!(
+            XPathMapper.resolve(&record, "//item[normalize-space(.) = 'Rust Atomics']/@sku", &all),
+            json!(["RS-2"])
+        );
+    }
+
+    #[test]
+    fn try_resolve_reports_malformed_paths_that_resolve_hides() {
+        let record = json!({ "items": [1, 2] });
+        let config = MapperConfig::default();
+
+        assert_eq!(JsonPathMapper.resolve(&record, "$.[malformed", &config), Value::Null);
+        assert!(matches!(
+            JsonPathMapper.try_resolve(&record, "$.[malformed", &config),
+            Err(FieldMapperError::InvalidExpression { .. })
+        ));
+
+        // A well-formed path to a missing field is not an error.
+        assert_eq!(JsonPathMapper.try_resolve(&record, "$.missing", &config).unwrap(), Value::Null);
+        assert!(matches!(
+            JsonPathMapper.try_resolve(&record, "items", &config),
+            Err(FieldMapperError::UnsupportedSyntax { .. })
+        ));
+        assert!(ComputedMapper.try_resolve(&record, "(1 + 2", &config).is_err());
+    }
+
+    #[test]
+    fn template_conditional_else_branch() {
+        let template = "{name}: {?phone}call {phone}{:}no phone{?email}, mail {email}{/email}{/phone}";
+        let config = MapperConfig::default();
+
+        let with_phone = json!({ "name": "Ada", "phone": "555-0100", "email": "ada@example.com" });
+        assert_eq!(TemplateMapper.resolve(&with_phone, template, &config), json!("Ada: call 555-0100"));
+
+        let without_phone = json!({ "name": "Bob", "phone": "", "email": "bob@example.com" });
+        assert_eq!(
+            TemplateMapper.resolve(&without_phone, template, &config),
+            json!("Bob: no phone, mail bob@example.com")
+        );
+
+        let falsy = json!({ "name": "Cy", "active": false });
+        assert_eq!(
+            TemplateMapper.resolve(&falsy, "{?active}on{:}off ({name}){/active}", &config),
+            json!("off (Cy)")
+        );
+    }
+
+    #[test]
+    fn csv_resolves_by_header_and_index() {
+        let config = MapperConfig::default();
+        let record = json!({
+            "_csv_headers": ["id", "email", "country"],
+            "_csv_row": ["42", "ada@example.com", "UK"],
+        });
+
+        let provider = resolve_provider("column:email").unwrap();
+        assert_eq!(provider.id(), "csv");
+        assert_eq!(provider.resolve(&record, "column:email", &config), json!("ada@example.com"));
+        assert_eq!(CsvMapper.resolve(&record, "column:missing", &config), Value::Null);
+
+        assert_eq!(resolve_provider("col[2]").unwrap().id(), "csv");
+        assert_eq!(CsvMapper.resolve(&record, "col[2]", &config), json!("UK"));
+        assert_eq!(CsvMapper.resolve(&json!(["a", "b"]), "col[1]", &config), json!("b"));
+        assert_eq!(CsvMapper.resolve(&json!(["a", "b"]), "col[5]", &config), Value::Null);
+    }
+
+    #[test]
+    fn map_record_mixes_providers() {
+        let record = json!({
+            "user": { "first": "Ada", "last": "Lovelace" },
+            "email": "ada@example.com",
+        });
+        let mut mappings = HashMap::new();
+        mappings.insert("firstName".to_string(), ("$.user.first".to_string(), MapperConfig::default()));
+        mappings.insert("fullName".to_string(), ("{user.first} {user.last}".to_string(), MapperConfig::default()));
+        mappings.insert("contact".to_string(), ("email".to_string(), MapperConfig::default()));
+
+        let mapped = map_record(&record, &mappings);
+        assert_eq!(Value::Object(mapped), json!({
+            "firstName": "Ada",
+            "fullName": "Ada Lovelace",
+            "contact": "ada@example.com",
+        }));
+    }
+
+    #[test]
+    fn jsonpath_slices_with_negative_steps() {
+        let record = json!({ "items": [0, 1, 2, 3, 4, 5, 6] });
+        let config = MapperConfig { return_all: true, ..MapperConfig::default() };
+
+        assert_eq!(
+            JsonPathMapper.resolve(&record, "$.items[::-1]", &config),
+            json!([6, 5, 4, 3, 2, 1, 0])
+        );
+        assert_eq!(JsonPathMapper.resolve(&record, "$.items[5:1:-2]", &config), json!([5, 3]));
+        assert_eq!(JsonPathMapper.resolve(&record, "$.items[-3:]", &config), json!([4, 5, 6]));
+        assert_eq!(JsonPathMapper.resolve(&record, "$.items[1:3]", &config), json!([1, 2]));
+    }
+}
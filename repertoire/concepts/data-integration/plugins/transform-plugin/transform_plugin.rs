@@ -5,11 +5,15 @@
 
 use std::collections::HashMap;
 use std::fmt;
+use std::sync::{Arc, LazyLock, Mutex, RwLock};
 
-use chrono::{Datelike, NaiveDate, NaiveDateTime, TimeZone, Timelike, Utc};
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, TimeZone, Timelike, Utc};
+use chrono_tz::Tz;
+use hmac::{Hmac, Mac};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use sha2::Sha256;
 
 // ---------------------------------------------------------------------------
 // Core types
@@ -31,6 +35,7 @@ pub struct TypeSpec {
 /// Provider-specific configuration for a transform operation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TransformConfig {
+    #[serde(alias = "providerId")]
     pub provider_id: String,
     #[serde(default)]
     pub options: HashMap<String, Value>,
@@ -45,6 +50,7 @@ pub enum TransformError {
     InvalidExpression { expression: String, detail: String },
     InvalidPattern { pattern: String, detail: String },
     DateParseFailed { value: String },
+    PipelineStep { step: usize, provider: String, source: Box<TransformError> },
 }
 
 impl fmt::Display for TransformError {
@@ -62,11 +68,20 @@ impl fmt::Display for TransformError {
                 write!(f, "Invalid regex pattern \"{pattern}\": {detail}"),
             Self::DateParseFailed { value } =>
                 write!(f, "Cannot parse date from \"{value}\""),
+            Self::PipelineStep { step, provider, source } =>
+                write!(f, "Pipeline step {step} ({provider}) failed: {source}"),
         }
     }
 }
 
-impl std::error::Error for TransformError {}
+impl std::error::Error for TransformError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::PipelineStep { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
 
 /// Interface every transform-plugin provider must implement.
 pub trait TransformPlugin: Send + Sync {
@@ -90,6 +105,48 @@ pub trait TransformPlugin: Send + Sync {
 // Helpers
 // ---------------------------------------------------------------------------
 
+/// Upper bound on cached patterns; user-supplied patterns could otherwise grow it without limit.
+const REGEX_CACHE_CAPACITY: usize = 512;
+
+/// Compiled patterns keyed by source text. Inline flags such as `(?i)` are
+/// part of the pattern and so part of the key. The whole map is dropped once
+/// it reaches capacity.
+struct RegexCache {
+    capacity: usize,
+    entries: Mutex<HashMap<String, Arc<Regex>>>,
+}
+
+impl RegexCache {
+    fn new(capacity: usize) -> Self {
+        Self { capacity, entries: Mutex::new(HashMap::new()) }
+    }
+
+    fn get(&self, pattern: &str) -> Result<Arc<Regex>, regex::Error> {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(re) = entries.get(pattern) {
+            return Ok(Arc::clone(re));
+        }
+        let re = Arc::new(Regex::new(pattern)?);
+        if entries.len() >= self.capacity {
+            entries.clear();
+        }
+        entries.insert(pattern.to_string(), Arc::clone(&re));
+        Ok(re)
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+}
+
+static REGEX_CACHE: LazyLock<RegexCache> = LazyLock::new(|| RegexCache::new(REGEX_CACHE_CAPACITY));
+
+/// Compile a pattern once and share it across transform invocations.
+fn cached_regex(pattern: &str) -> Result<Arc<Regex>, regex::Error> {
+    REGEX_CACHE.get(pattern)
+}
+
 fn option_str<'a>(config: &'a TransformConfig, key: &str) -> Option<&'a str> {
     config.options.get(key).and_then(|v| v.as_str())
 }
@@ -102,6 +159,25 @@ fn option_u64(config: &TransformConfig, key: &str, default: u64) -> u64 {
     config.options.get(key).and_then(|v| v.as_u64()).unwrap_or(default)
 }
 
+/// Shared emptiness policy: null is always empty; blank strings, zero, and empty
+/// arrays count as empty according to the `treat*AsNull` options.
+fn is_empty_value(value: &Value, config: &TransformConfig) -> bool {
+    match value {
+        Value::Null => true,
+        Value::String(s) => option_bool(config, "treatEmptyStringAsNull", true) && s.trim().is_empty(),
+        Value::Number(n) => option_bool(config, "treatZeroAsNull", false) && n.as_f64() == Some(0.0),
+        Value::Array(arr) => option_bool(config, "treatEmptyArrayAsNull", false) && arr.is_empty(),
+        _ => false,
+    }
+}
+
+fn require_array<'a>(provider: &str, value: &'a Value) -> Result<&'a Vec<Value>, TransformError> {
+    value.as_array().ok_or_else(|| TransformError::InvalidInput {
+        provider: provider.into(),
+        detail: "input must be an array".into(),
+    })
+}
+
 fn value_to_string(v: &Value) -> String {
     match v {
         Value::String(s) => s.clone(),
@@ -272,26 +348,8 @@ impl TransformPlugin for DefaultValueTransform {
 
     fn transform(&self, value: &Value, config: &TransformConfig) -> Result<Value, TransformError> {
         let default_val = config.options.get("default").cloned().unwrap_or(Value::Null);
-        let treat_empty_string = option_bool(config, "treatEmptyStringAsNull", true);
-        let treat_zero = option_bool(config, "treatZeroAsNull", false);
-        let treat_empty_array = option_bool(config, "treatEmptyArrayAsNull", false);
 
-        if value.is_null() { return Ok(default_val); }
-        if treat_empty_string {
-            if let Some(s) = value.as_str() {
-                if s.trim().is_empty() { return Ok(default_val); }
-            }
-        }
-        if treat_zero {
-            if let Some(n) = value.as_f64() {
-                if n == 0.0 { return Ok(default_val); }
-            }
-        }
-        if treat_empty_array {
-            if let Some(arr) = value.as_array() {
-                if arr.is_empty() { return Ok(default_val); }
-            }
-        }
+        if is_empty_value(value, config) { return Ok(default_val); }
         Ok(value.clone())
     }
 }
@@ -342,6 +400,9 @@ impl TransformPlugin for LookupTransform {
 // 4. MigrationLookupTransform
 // ---------------------------------------------------------------------------
 
+/// Resolves legacy ids through `options.provenanceMap` (`"entityType:oldId"` or
+/// `"oldId"` to new id). Capture pipelines build that map from their provenance
+/// records with `capture_mode::provenance::provenance_map`.
 pub struct MigrationLookupTransform;
 
 impl TransformPlugin for MigrationLookupTransform {
@@ -467,7 +528,7 @@ impl TransformPlugin for SplitTransform {
         if s.is_empty() { return Ok(Value::Array(vec![])); }
 
         let mut parts: Vec<String> = if is_regex {
-            match Regex::new(delimiter) {
+            match cached_regex(delimiter) {
                 Ok(re) => re.split(&s).map(|p| p.to_string()).collect(),
                 Err(_) => vec![s],
             }
@@ -581,13 +642,13 @@ impl TransformPlugin for SlugifyTransform {
         if lowercase { slug = slug.to_lowercase(); }
 
         // 4. Replace non-alphanumeric characters with separator
-        if let Ok(re) = Regex::new(r"[^a-zA-Z0-9]+") {
+        if let Ok(re) = cached_regex(r"[^a-zA-Z0-9]+") {
             slug = re.replace_all(&slug, separator).to_string();
         }
 
         // 5. Collapse consecutive separators
         let escaped_sep = regex::escape(separator);
-        if let Ok(re) = Regex::new(&format!("{escaped_sep}{{2,}}")) {
+        if let Ok(re) = cached_regex(&format!("{escaped_sep}{{2,}}")) {
             slug = re.replace_all(&slug, separator).to_string();
         }
 
@@ -631,7 +692,7 @@ impl TransformPlugin for HtmlToMarkdownTransform {
         let mut html = value_to_string(value);
 
         // Code blocks: <pre><code class="language-X">...</code></pre>
-        if let Ok(re) = Regex::new(r#"(?is)<pre[^>]*>\s*<code[^>]*(?:class=["'][^"']*language-(\w+)[^"']*["'])?[^>]*>(.*?)</code>\s*</pre>"#) {
+        if let Ok(re) = cached_regex(r#"(?is)<pre[^>]*>\s*<code[^>]*(?:class=["'][^"']*language-(\w+)[^"']*["'])?[^>]*>(.*?)</code>\s*</pre>"#) {
             html = re.replace_all(&html, |caps: &regex::Captures| {
                 let lang = caps.get(1).map(|m| m.as_str()).unwrap_or("");
                 let code = Self::decode_entities(caps.get(2).map(|m| m.as_str()).unwrap_or(""));
@@ -643,7 +704,7 @@ impl TransformPlugin for HtmlToMarkdownTransform {
         for level in 1..=6 {
             let prefix = "#".repeat(level);
             let pattern = format!(r"(?is)<h{level}[^>]*>(.*?)</h{level}>");
-            if let Ok(re) = Regex::new(&pattern) {
+            if let Ok(re) = cached_regex(&pattern) {
                 html = re.replace_all(&html, |caps: &regex::Captures| {
                     let text = Self::strip_tags(caps.get(1).map(|m| m.as_str()).unwrap_or("")).trim().to_string();
                     format!("\n\n{prefix} {text}\n\n")
@@ -652,7 +713,7 @@ impl TransformPlugin for HtmlToMarkdownTransform {
         }
 
         // Blockquotes
-        if let Ok(re) = Regex::new(r"(?is)<blockquote[^>]*>(.*?)</blockquote>") {
+        if let Ok(re) = cached_regex(r"(?is)<blockquote[^>]*>(.*?)</blockquote>") {
             html = re.replace_all(&html, |caps: &regex::Captures| {
                 let text = Self::strip_tags(caps.get(1).map(|m| m.as_str()).unwrap_or(""));
                 let lines: Vec<String> = text.trim().lines().map(|l| format!("> {}", l.trim())).collect();
@@ -660,55 +721,26 @@ impl TransformPlugin for HtmlToMarkdownTransform {
             }).to_string();
         }
 
-        // Ordered lists
-        if let Ok(re) = Regex::new(r"(?is)<ol[^>]*>(.*?)</ol>") {
-            html = re.replace_all(&html, |caps: &regex::Captures| {
-                let content = caps.get(1).map(|m| m.as_str()).unwrap_or("");
-                let li_re = Regex::new(r"(?is)<li[^>]*>(.*?)</li>").unwrap();
-                let mut counter = 0;
-                let mut result = String::from("\n\n");
-                for li_caps in li_re.captures_iter(content) {
-                    counter += 1;
-                    let text = Self::strip_tags(li_caps.get(1).map(|m| m.as_str()).unwrap_or("")).trim().to_string();
-                    result.push_str(&format!("{counter}. {text}\n"));
-                }
-                result.push('\n');
-                result
-            }).to_string();
-        }
-
-        // Unordered lists
-        if let Ok(re) = Regex::new(r"(?is)<ul[^>]*>(.*?)</ul>") {
-            html = re.replace_all(&html, |caps: &regex::Captures| {
-                let content = caps.get(1).map(|m| m.as_str()).unwrap_or("");
-                let li_re = Regex::new(r"(?is)<li[^>]*>(.*?)</li>").unwrap();
-                let mut result = String::from("\n\n");
-                for li_caps in li_re.captures_iter(content) {
-                    let text = Self::strip_tags(li_caps.get(1).map(|m| m.as_str()).unwrap_or("")).trim().to_string();
-                    result.push_str(&format!("{bullet_marker} {text}\n"));
-                }
-                result.push('\n');
-                result
-            }).to_string();
-        }
+        // Lists (recursive, so nested lists keep their level)
+        html = Self::convert_lists(&html, bullet_marker);
 
         // Paragraphs
-        if let Ok(re) = Regex::new(r"(?is)<p[^>]*>(.*?)</p>") {
+        if let Ok(re) = cached_regex(r"(?is)<p[^>]*>(.*?)</p>") {
             html = re.replace_all(&html, "\n\n$1\n\n").to_string();
         }
 
         // Horizontal rules
-        if let Ok(re) = Regex::new(r"(?i)<hr\s*/?>") {
+        if let Ok(re) = cached_regex(r"(?i)<hr\s*/?>") {
             html = re.replace_all(&html, "\n\n---\n\n").to_string();
         }
 
         // Line breaks
-        if let Ok(re) = Regex::new(r"(?i)<br\s*/?>") {
+        if let Ok(re) = cached_regex(r"(?i)<br\s*/?>") {
             html = re.replace_all(&html, "  \n").to_string();
         }
 
         // Links
-        if let Ok(re) = Regex::new(r#"(?is)<a[^>]+href=["']([^"']+)["'][^>]*>(.*?)</a>"#) {
+        if let Ok(re) = cached_regex(r#"(?is)<a[^>]+href=["']([^"']+)["'][^>]*>(.*?)</a>"#) {
             html = re.replace_all(&html, |caps: &regex::Captures| {
                 let href = caps.get(1).map(|m| m.as_str()).unwrap_or("");
                 let text = Self::strip_tags(caps.get(2).map(|m| m.as_str()).unwrap_or("")).trim().to_string();
@@ -717,14 +749,14 @@ impl TransformPlugin for HtmlToMarkdownTransform {
         }
 
         // Images
-        if let Ok(re) = Regex::new(r#"(?i)<img[^>]+src=["']([^"']+)["'][^>]*alt=["']([^"']*?)["'][^>]*/?>""#) {
+        if let Ok(re) = cached_regex(r#"(?i)<img[^>]+src=["']([^"']+)["'][^>]*alt=["']([^"']*?)["'][^>]*/?>""#) {
             html = re.replace_all(&html, "![$2]($1)").to_string();
         }
 
         // Bold
         for tag in &["strong", "b"] {
             let pattern = format!(r"(?is)<{tag}[^>]*>(.*?)</{tag}>");
-            if let Ok(re) = Regex::new(&pattern) {
+            if let Ok(re) = cached_regex(&pattern) {
                 html = re.replace_all(&html, "**$1**").to_string();
             }
         }
@@ -732,7 +764,7 @@ impl TransformPlugin for HtmlToMarkdownTransform {
         // Italic
         for tag in &["em", "i"] {
             let pattern = format!(r"(?is)<{tag}[^>]*>(.*?)</{tag}>");
-            if let Ok(re) = Regex::new(&pattern) {
+            if let Ok(re) = cached_regex(&pattern) {
                 html = re.replace_all(&html, "_$1_").to_string();
             }
         }
@@ -740,13 +772,13 @@ impl TransformPlugin for HtmlToMarkdownTransform {
         // Strikethrough
         for tag in &["del", "s", "strike"] {
             let pattern = format!(r"(?is)<{tag}[^>]*>(.*?)</{tag}>");
-            if let Ok(re) = Regex::new(&pattern) {
+            if let Ok(re) = cached_regex(&pattern) {
                 html = re.replace_all(&html, "~~$1~~").to_string();
             }
         }
 
         // Inline code
-        if let Ok(re) = Regex::new(r"(?is)<code[^>]*>(.*?)</code>") {
+        if let Ok(re) = cached_regex(r"(?is)<code[^>]*>(.*?)</code>") {
             html = re.replace_all(&html, "`$1`").to_string();
         }
 
@@ -757,7 +789,7 @@ impl TransformPlugin for HtmlToMarkdownTransform {
         html = Self::decode_entities(&html);
 
         // Collapse excessive newlines
-        if let Ok(re) = Regex::new(r"\n{3,}") {
+        if let Ok(re) = cached_regex(r"\n{3,}") {
             html = re.replace_all(&html, "\n\n").to_string();
         }
 
@@ -766,8 +798,99 @@ impl TransformPlugin for HtmlToMarkdownTransform {
 }
 
 impl HtmlToMarkdownTransform {
+    /// Replace each top-level `<ul>`/`<ol>` block with its Markdown rendering.
+    fn convert_lists(html: &str, bullet_marker: &str) -> String {
+        let mut result = String::new();
+        let mut cursor = 0;
+        for (start, end, ordered, inner) in Self::top_level_lists(html) {
+            result.push_str(&html[cursor..start]);
+            result.push_str("\n\n");
+            result.push_str(&Self::render_list(inner, ordered, 0, bullet_marker));
+            result.push('\n');
+            cursor = end;
+        }
+        result.push_str(&html[cursor..]);
+        result
+    }
+
+    /// Top-level list blocks as (start, end, ordered, inner html). Open and
+    /// close tags are matched by depth so nested lists stay inside their parent.
+    fn top_level_lists(html: &str) -> Vec<(usize, usize, bool, &str)> {
+        let tag_re = cached_regex(r"(?i)<(/?)(ul|ol)\b[^>]*>").unwrap();
+        let mut blocks = Vec::new();
+        let mut depth = 0usize;
+        let mut open: Option<(usize, usize, bool)> = None;
+        for caps in tag_re.captures_iter(html) {
+            let tag = caps.get(0).unwrap();
+            if caps[1].is_empty() {
+                if depth == 0 {
+                    open = Some((tag.start(), tag.end(), caps[2].eq_ignore_ascii_case("ol")));
+                }
+                depth += 1;
+            } else if depth > 0 {
+                depth -= 1;
+                if depth == 0 {
+                    if let Some((start, inner_start, ordered)) = open.take() {
+                        blocks.push((start, tag.end(), ordered, &html[inner_start..tag.start()]));
+                    }
+                }
+            }
+        }
+        blocks
+    }
+
+    /// Direct `<li>` children of a list body; items of nested lists are skipped.
+    fn list_items(inner: &str) -> Vec<&str> {
+        let tag_re = cached_regex(r"(?i)<(/?)(li|ul|ol)\b[^>]*>").unwrap();
+        let mut items = Vec::new();
+        let mut depth = 0usize;
+        let mut item_start: Option<usize> = None;
+        for caps in tag_re.captures_iter(inner) {
+            let tag = caps.get(0).unwrap();
+            let closing = !caps[1].is_empty();
+            if !caps[2].eq_ignore_ascii_case("li") {
+                depth = if closing { depth.saturating_sub(1) } else { depth + 1 };
+                continue;
+            }
+            if depth > 0 { continue; }
+            // An unclosed <li> ends where the next sibling begins.
+            if let Some(start) = item_start.take() {
+                items.push(&inner[start..tag.start()]);
+            }
+            if !closing { item_start = Some(tag.end()); }
+        }
+        if let Some(start) = item_start {
+            items.push(&inner[start..]);
+        }
+        items
+    }
+
+    fn render_list(inner: &str, ordered: bool, depth: usize, bullet_marker: &str) -> String {
+        let indent = "  ".repeat(depth);
+        let mut result = String::new();
+        for (i, item) in Self::list_items(inner).into_iter().enumerate() {
+            let nested = Self::top_level_lists(item);
+            let mut text = String::new();
+            let mut cursor = 0;
+            for (start, end, _, _) in &nested {
+                text.push_str(&item[cursor..*start]);
+                text.push(' ');
+                cursor = *end;
+            }
+            text.push_str(&item[cursor..]);
+            let text = Self::strip_tags(&text).split_whitespace().collect::<Vec<_>>().join(" ");
+
+            let marker = if ordered { format!("{}.", i + 1) } else { bullet_marker.to_string() };
+            result.push_str(&format!("{indent}{marker} {text}\n"));
+            for (_, _, nested_ordered, nested_inner) in nested {
+                result.push_str(&Self::render_list(nested_inner, nested_ordered, depth + 1, bullet_marker));
+            }
+        }
+        result
+    }
+
     fn strip_tags(html: &str) -> String {
-        Regex::new(r"<[^>]+>")
+        cached_regex(r"<[^>]+>")
             .map(|re| re.replace_all(html, "").to_string())
             .unwrap_or_else(|_| html.to_string())
     }
@@ -803,7 +926,7 @@ impl TransformPlugin for MarkdownToHtmlTransform {
         let mut md = value_to_string(value);
 
         // Fenced code blocks
-        if let Ok(re) = Regex::new(r"```(\w*)\n([\s\S]*?)\n```") {
+        if let Ok(re) = cached_regex(r"```(\w*)\n([\s\S]*?)\n```") {
             md = re.replace_all(&md, |caps: &regex::Captures| {
                 let lang = caps.get(1).map(|m| m.as_str()).unwrap_or("");
                 let code = caps.get(2).map(|m| m.as_str()).unwrap_or("");
@@ -814,7 +937,7 @@ impl TransformPlugin for MarkdownToHtmlTransform {
         }
 
         // ATX headings
-        if let Ok(re) = Regex::new(r"(?m)^(#{1,6})\s+(.+?)(?:\s+#+)?$") {
+        if let Ok(re) = cached_regex(r"(?m)^(#{1,6})\s+(.+?)(?:\s+#+)?$") {
             md = re.replace_all(&md, |caps: &regex::Captures| {
                 let level = caps.get(1).map(|m| m.as_str().len()).unwrap_or(1);
                 let text = caps.get(2).map(|m| m.as_str().trim()).unwrap_or("");
@@ -823,12 +946,12 @@ impl TransformPlugin for MarkdownToHtmlTransform {
         }
 
         // Horizontal rules
-        if let Ok(re) = Regex::new(r"(?m)^(?:[-*_]\s*){3,}$") {
+        if let Ok(re) = cached_regex(r"(?m)^(?:[-*_]\s*){3,}$") {
             md = re.replace_all(&md, "<hr />").to_string();
         }
 
         // Blockquotes
-        if let Ok(re) = Regex::new(r"(?m)(?:^>\s?.+\n?)+") {
+        if let Ok(re) = cached_regex(r"(?m)(?:^>\s?.+\n?)+") {
             md = re.replace_all(&md, |caps: &regex::Captures| {
                 let block = caps.get(0).map(|m| m.as_str()).unwrap_or("");
                 let text: String = block.lines()
@@ -840,11 +963,11 @@ impl TransformPlugin for MarkdownToHtmlTransform {
         }
 
         // Unordered lists
-        if let Ok(re) = Regex::new(r"(?m)(?:^[*+\-]\s+.+\n?)+") {
+        if let Ok(re) = cached_regex(r"(?m)(?:^[*+\-]\s+.+\n?)+") {
             md = re.replace_all(&md, |caps: &regex::Captures| {
                 let block = caps.get(0).map(|m| m.as_str()).unwrap_or("");
                 let items: Vec<String> = block.trim().lines().map(|line| {
-                    let text = Regex::new(r"^[*+\-]\s+").unwrap().replace(line, "").to_string();
+                    let text = cached_regex(r"^[*+\-]\s+").unwrap().replace(line, "").to_string();
                     format!("<li>{text}</li>")
                 }).collect();
                 format!("<ul>\n{}\n</ul>", items.join("\n"))
@@ -852,11 +975,11 @@ impl TransformPlugin for MarkdownToHtmlTransform {
         }
 
         // Ordered lists
-        if let Ok(re) = Regex::new(r"(?m)(?:^\d+\.\s+.+\n?)+") {
+        if let Ok(re) = cached_regex(r"(?m)(?:^\d+\.\s+.+\n?)+") {
             md = re.replace_all(&md, |caps: &regex::Captures| {
                 let block = caps.get(0).map(|m| m.as_str()).unwrap_or("");
                 let items: Vec<String> = block.trim().lines().map(|line| {
-                    let text = Regex::new(r"^\d+\.\s+").unwrap().replace(line, "").to_string();
+                    let text = cached_regex(r"^\d+\.\s+").unwrap().replace(line, "").to_string();
                     format!("<li>{text}</li>")
                 }).collect();
                 format!("<ol>\n{}\n</ol>", items.join("\n"))
@@ -864,7 +987,7 @@ impl TransformPlugin for MarkdownToHtmlTransform {
         }
 
         // Images
-        if let Ok(re) = Regex::new(r#"!\[([^\]]*)\]\(([^)\s]+)(?:\s+"([^"]*)")?\)"#) {
+        if let Ok(re) = cached_regex(r#"!\[([^\]]*)\]\(([^)\s]+)(?:\s+"([^"]*)")?\)"#) {
             md = re.replace_all(&md, |caps: &regex::Captures| {
                 let alt = caps.get(1).map(|m| m.as_str()).unwrap_or("");
                 let src = caps.get(2).map(|m| m.as_str()).unwrap_or("");
@@ -874,7 +997,7 @@ impl TransformPlugin for MarkdownToHtmlTransform {
         }
 
         // Links
-        if let Ok(re) = Regex::new(r#"\[([^\]]+)\]\(([^)\s]+)(?:\s+"([^"]*)")?\)"#) {
+        if let Ok(re) = cached_regex(r#"\[([^\]]+)\]\(([^)\s]+)(?:\s+"([^"]*)")?\)"#) {
             md = re.replace_all(&md, |caps: &regex::Captures| {
                 let text = caps.get(1).map(|m| m.as_str()).unwrap_or("");
                 let href = caps.get(2).map(|m| m.as_str()).unwrap_or("");
@@ -884,22 +1007,22 @@ impl TransformPlugin for MarkdownToHtmlTransform {
         }
 
         // Bold: **text** or __text__
-        if let Ok(re) = Regex::new(r"(\*\*|__)(.+?)\1") {
+        if let Ok(re) = cached_regex(r"(\*\*|__)(.+?)\1") {
             md = re.replace_all(&md, "<strong>$2</strong>").to_string();
         }
 
         // Italic: *text* or _text_
-        if let Ok(re) = Regex::new(r"(\*|_)(.+?)\1") {
+        if let Ok(re) = cached_regex(r"(\*|_)(.+?)\1") {
             md = re.replace_all(&md, "<em>$2</em>").to_string();
         }
 
         // Strikethrough
-        if let Ok(re) = Regex::new(r"~~(.+?)~~") {
+        if let Ok(re) = cached_regex(r"~~(.+?)~~") {
             md = re.replace_all(&md, "<del>$1</del>").to_string();
         }
 
         // Inline code
-        if let Ok(re) = Regex::new(r"`([^`]+)`") {
+        if let Ok(re) = cached_regex(r"`([^`]+)`") {
             md = re.replace_all(&md, "<code>$1</code>").to_string();
         }
 
@@ -936,13 +1059,13 @@ impl TransformPlugin for StripTagsTransform {
 
         if allowed_tags.is_empty() {
             // Remove all tags
-            if let Ok(re) = Regex::new(r"<[^>]+>") {
+            if let Ok(re) = cached_regex(r"<[^>]+>") {
                 html = re.replace_all(&html, "").to_string();
             }
         } else {
             // Remove only non-allowlisted tags
             // Closing tags
-            if let Ok(re) = Regex::new(r"</([a-zA-Z][a-zA-Z0-9]*)\s*>") {
+            if let Ok(re) = cached_regex(r"</([a-zA-Z][a-zA-Z0-9]*)\s*>") {
                 html = re.replace_all(&html, |caps: &regex::Captures| {
                     let tag = caps.get(1).map(|m| m.as_str().to_lowercase()).unwrap_or_default();
                     if allowed_tags.contains(&tag) {
@@ -953,7 +1076,7 @@ impl TransformPlugin for StripTagsTransform {
                 }).to_string();
             }
             // Opening tags
-            if let Ok(re) = Regex::new(r"<([a-zA-Z][a-zA-Z0-9]*)(\s[^>]*)?\s*/?>") {
+            if let Ok(re) = cached_regex(r"<([a-zA-Z][a-zA-Z0-9]*)(\s[^>]*)?\s*/?>") {
                 html = re.replace_all(&html, |caps: &regex::Captures| {
                     let tag = caps.get(1).map(|m| m.as_str().to_lowercase()).unwrap_or_default();
                     if allowed_tags.contains(&tag) {
@@ -975,7 +1098,7 @@ impl TransformPlugin for StripTagsTransform {
         }
 
         if collapse_whitespace {
-            if let Ok(re) = Regex::new(r"\s+") {
+            if let Ok(re) = cached_regex(r"\s+") {
                 html = re.replace_all(&html, " ").to_string();
             }
             html = html.trim().to_string();
@@ -1013,24 +1136,25 @@ impl TransformPlugin for TruncateTransform {
         if chars.len() <= max_length { return Ok(Value::String(s)); }
 
         let ellipsis_len = ellipsis.chars().count();
-        let trunc_len = if max_length > ellipsis_len { max_length - ellipsis_len } else { 0 };
+        let trunc_len = max_length.saturating_sub(ellipsis_len);
         if trunc_len == 0 {
             return Ok(Value::String(ellipsis.chars().take(max_length).collect()));
         }
 
         let result = match position {
             "start" => {
-                let start = chars.len() - trunc_len;
+                let start = chars.len().saturating_sub(trunc_len);
                 format!("{}{}", ellipsis, chars[start..].iter().collect::<String>())
             }
             "middle" => {
                 let half = trunc_len / 2;
-                let first: String = chars[..half].iter().collect();
-                let second: String = chars[chars.len() - (trunc_len - half)..].iter().collect();
+                let first: String = chars[..half.min(chars.len())].iter().collect();
+                let tail_start = chars.len().saturating_sub(trunc_len.saturating_sub(half)).max(half);
+                let second: String = chars[tail_start..].iter().collect();
                 format!("{first}{ellipsis}{second}")
             }
             _ => {
-                let mut truncated: String = chars[..trunc_len].iter().collect();
+                let mut truncated: String = chars[..trunc_len.min(chars.len())].iter().collect();
                 if word_boundary {
                     if let Some(last_space) = truncated.rfind(' ') {
                         if last_space > (trunc_len as f64 * 0.5) as usize {
@@ -1050,6 +1174,9 @@ impl TransformPlugin for TruncateTransform {
 // 13. RegexReplaceTransform
 // ---------------------------------------------------------------------------
 
+/// Replaces pattern matches. The replacement may reference groups as `$1`,
+/// `$name`, `${1}` or `${name}`; every reference must name a group in the
+/// pattern. Write `$$` for a literal dollar sign.
 pub struct RegexReplaceTransform;
 
 impl TransformPlugin for RegexReplaceTransform {
@@ -1077,16 +1204,60 @@ impl TransformPlugin for RegexReplaceTransform {
             pattern.to_string()
         };
 
-        let re = Regex::new(&full_pattern).map_err(|e| TransformError::InvalidPattern {
+        let re = cached_regex(&full_pattern).map_err(|e| TransformError::InvalidPattern {
             pattern: pattern.to_string(),
             detail: e.to_string(),
         })?;
+        Self::validate_group_references(&re, pattern, replacement)?;
 
-        // The regex crate uses $1, $2 for captures which matches common conventions
         Ok(Value::String(re.replace_all(&s, replacement).to_string()))
     }
 }
 
+impl RegexReplaceTransform {
+    /// Reject replacements that reference groups the pattern does not define;
+    /// the regex crate would otherwise substitute them silently with "".
+    fn validate_group_references(re: &Regex, pattern: &str, replacement: &str) -> Result<(), TransformError> {
+        let mut rest = replacement;
+        while let Some(pos) = rest.find('$') {
+            rest = &rest[pos + 1..];
+            if let Some(after) = rest.strip_prefix('$') {
+                rest = after;
+                continue;
+            }
+            // Same reference grammar as regex::Captures::expand: ${name} or the
+            // longest run of [_0-9A-Za-z] after the dollar sign.
+            let name = if let Some(braced) = rest.strip_prefix('{') {
+                match braced.find('}') {
+                    Some(end) => {
+                        rest = &braced[end + 1..];
+                        &braced[..end]
+                    }
+                    None => continue,
+                }
+            } else {
+                let end = rest.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_')).unwrap_or(rest.len());
+                let name = &rest[..end];
+                rest = &rest[end..];
+                name
+            };
+            if name.is_empty() { continue; }
+
+            let exists = match name.parse::<usize>() {
+                Ok(index) => index < re.captures_len(),
+                Err(_) => re.capture_names().flatten().any(|n| n == name),
+            };
+            if !exists {
+                return Err(TransformError::InvalidPattern {
+                    pattern: pattern.to_string(),
+                    detail: format!("replacement references unknown group \"{name}\" (use $$ for a literal $)"),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
 // ---------------------------------------------------------------------------
 // 14. DateFormatTransform
 // ---------------------------------------------------------------------------
@@ -1107,17 +1278,37 @@ impl TransformPlugin for DateFormatTransform {
     fn transform(&self, value: &Value, config: &TransformConfig) -> Result<Value, TransformError> {
         let output_format = option_str(config, "outputFormat").unwrap_or("YYYY-MM-DD");
 
-        let date = self.parse_date(value).ok_or_else(|| TransformError::DateParseFailed {
+        let mut date = self.parse_date(value).ok_or_else(|| TransformError::DateParseFailed {
             value: value_to_string(value),
         })?;
 
+        // Convert the instant into the requested zone; otherwise keep the input's offset.
+        if let Some(name) = option_str(config, "timezone") {
+            let tz: Tz = name.parse().map_err(|_| TransformError::InvalidInput {
+                provider: self.id().into(),
+                detail: format!("unknown timezone \"{name}\""),
+            })?;
+            date = date.with_timezone(&tz).fixed_offset();
+        }
+
         let formatted = self.format_date(&date, output_format);
         Ok(Value::String(formatted))
     }
 }
 
 impl DateFormatTransform {
-    fn parse_date(&self, value: &Value) -> Option<NaiveDateTime> {
+    /// Parse the input into an instant. Offset-bearing RFC 3339 strings keep their
+    /// offset; every other form is taken as UTC.
+    fn parse_date(&self, value: &Value) -> Option<DateTime<FixedOffset>> {
+        if let Value::String(s) = value {
+            if let Ok(dt) = DateTime::parse_from_rfc3339(s.trim()) {
+                return Some(dt);
+            }
+        }
+        self.parse_naive_date(value).map(|naive| Utc.from_utc_datetime(&naive).fixed_offset())
+    }
+
+    fn parse_naive_date(&self, value: &Value) -> Option<NaiveDateTime> {
         match value {
             Value::Number(n) => {
                 let ts = n.as_f64()?;
@@ -1131,11 +1322,6 @@ impl DateFormatTransform {
                 // Relative dates
                 if let Some(dt) = self.parse_relative(trimmed) { return Some(dt); }
 
-                // ISO 8601: 2026-02-23T10:30:00Z
-                if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(trimmed) {
-                    return Some(dt.naive_utc());
-                }
-
                 // yyyy-MM-dd
                 if let Ok(d) = NaiveDate::parse_from_str(trimmed, "%Y-%m-%d") {
                     return Some(d.and_hms_opt(0, 0, 0)?);
@@ -1191,7 +1377,7 @@ impl DateFormatTransform {
         if lower == "tomorrow" { return Some(now + chrono::Duration::days(1)); }
 
         // "N unit(s) ago"
-        let ago_re = Regex::new(r"^(\d+)\s+(second|minute|hour|day|week|month|year)s?\s+ago$").ok()?;
+        let ago_re = cached_regex(r"^(\d+)\s+(second|minute|hour|day|week|month|year)s?\s+ago$").ok()?;
         if let Some(caps) = ago_re.captures(&lower) {
             let amount: i64 = caps.get(1)?.as_str().parse().ok()?;
             let unit = caps.get(2)?.as_str();
@@ -1199,7 +1385,7 @@ impl DateFormatTransform {
         }
 
         // "in N unit(s)"
-        let in_re = Regex::new(r"^in\s+(\d+)\s+(second|minute|hour|day|week|month|year)s?$").ok()?;
+        let in_re = cached_regex(r"^in\s+(\d+)\s+(second|minute|hour|day|week|month|year)s?$").ok()?;
         if let Some(caps) = in_re.captures(&lower) {
             let amount: i64 = caps.get(1)?.as_str().parse().ok()?;
             let unit = caps.get(2)?.as_str();
@@ -1227,7 +1413,7 @@ impl DateFormatTransform {
         }
     }
 
-    fn format_date(&self, date: &NaiveDateTime, format: &str) -> String {
+    fn format_date(&self, date: &DateTime<FixedOffset>, format: &str) -> String {
         static MONTH_SHORT: &[&str] = &[
             "Jan", "Feb", "Mar", "Apr", "May", "Jun",
             "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
@@ -1247,21 +1433,51 @@ impl DateFormatTransform {
         let second = date.second();
         let weekday = date.weekday().num_days_from_monday() as usize;
 
-        let mut result = format.to_string();
-        result = result.replace("YYYY", &format!("{year:04}"));
-        result = result.replace("YY", &format!("{:02}", year % 100));
-        result = result.replace("MMMM", MONTH_LONG[(month - 1) as usize]);
-        result = result.replace("MMM", MONTH_SHORT[(month - 1) as usize]);
-        result = result.replace("MM", &format!("{month:02}"));
-        result = result.replace("dddd", DAY_LONG[weekday]);
-        result = result.replace("ddd", DAY_SHORT[weekday]);
-        result = result.replace("DD", &format!("{day:02}"));
-        result = result.replace("HH", &format!("{hour:02}"));
-        result = result.replace("hh", &format!("{:02}", if hour > 12 { hour - 12 } else if hour == 0 { 12 } else { hour }));
-        result = result.replace("mm", &format!("{minute:02}"));
-        result = result.replace("ss", &format!("{second:02}"));
-        result = result.replace("A", if hour >= 12 { "PM" } else { "AM" });
-        result = result.replace('a', if hour >= 12 { "pm" } else { "am" });
+        let offset_secs = date.offset().local_minus_utc();
+        let sign = if offset_secs < 0 { '-' } else { '+' };
+        let (offset_hours, offset_minutes) = (offset_secs.abs() / 3600, offset_secs.abs() % 3600 / 60);
+
+        // Single left-to-right pass so substituted text is never re-scanned.
+        // `[...]` is copied through literally, e.g. `YYYY-MM-DD[T]HH:mm[Z]`.
+        const TOKENS: &[&str] = &[
+            "YYYY", "YY", "MMMM", "MMM", "MM", "dddd", "ddd", "DD",
+            "HH", "hh", "mm", "ss", "ZZ", "Z", "A", "a",
+        ];
+        let mut result = String::with_capacity(format.len());
+        let mut rest = format;
+        while let Some(c) = rest.chars().next() {
+            if c == '[' {
+                if let Some(close) = rest.find(']') {
+                    result.push_str(&rest[1..close]);
+                    rest = &rest[close + 1..];
+                    continue;
+                }
+            }
+            let Some(token) = TOKENS.iter().find(|t| rest.starts_with(**t)) else {
+                result.push(c);
+                rest = &rest[c.len_utf8()..];
+                continue;
+            };
+            match *token {
+                "YYYY" => result.push_str(&format!("{year:04}")),
+                "YY" => result.push_str(&format!("{:02}", year % 100)),
+                "MMMM" => result.push_str(MONTH_LONG[(month - 1) as usize]),
+                "MMM" => result.push_str(MONTH_SHORT[(month - 1) as usize]),
+                "MM" => result.push_str(&format!("{month:02}")),
+                "dddd" => result.push_str(DAY_LONG[weekday]),
+                "ddd" => result.push_str(DAY_SHORT[weekday]),
+                "DD" => result.push_str(&format!("{day:02}")),
+                "HH" => result.push_str(&format!("{hour:02}")),
+                "hh" => result.push_str(&format!("{:02}", if hour > 12 { hour - 12 } else if hour == 0 { 12 } else { hour })),
+                "mm" => result.push_str(&format!("{minute:02}")),
+                "ss" => result.push_str(&format!("{second:02}")),
+                "ZZ" => result.push_str(&format!("{sign}{offset_hours:02}{offset_minutes:02}")),
+                "Z" => result.push_str(&format!("{sign}{offset_hours:02}:{offset_minutes:02}")),
+                "A" => result.push_str(if hour >= 12 { "PM" } else { "AM" }),
+                _ => result.push_str(if hour >= 12 { "pm" } else { "am" }),
+            }
+            rest = &rest[token.len()..];
+        }
 
         result
     }
@@ -1357,7 +1573,7 @@ impl JsonExtractTransform {
 
     fn parse_path(&self, path: &str) -> Vec<String> {
         let mut segments = Vec::new();
-        let re = Regex::new(r#"\.?([^.\[\]]+)|\[(\d+|"[^"]+"|'[^']+'|\*)\]"#).unwrap();
+        let re = cached_regex(r#"\.?([^.\[\]]+)|\[(\d+|"[^"]+"|'[^']+'|\*)\]"#).unwrap();
         for caps in re.captures_iter(path) {
             let segment = caps.get(1).or_else(|| caps.get(2))
                 .map(|m| m.as_str().trim_matches(|c: char| c == '"' || c == '\'').to_string())
@@ -1588,49 +1804,1503 @@ impl ExpressionTransform {
 }
 
 // ---------------------------------------------------------------------------
-// Factory function and registry
+// 17. CompletenessTransform
 // ---------------------------------------------------------------------------
 
-/// Create a transform provider by its unique identifier.
-pub fn create_provider(id: &str) -> Option<Box<dyn TransformPlugin>> {
-    match id {
-        "type_cast" => Some(Box::new(TypeCastTransform)),
-        "default_value" => Some(Box::new(DefaultValueTransform)),
-        "lookup" => Some(Box::new(LookupTransform)),
-        "migration_lookup" => Some(Box::new(MigrationLookupTransform)),
-        "concat" => Some(Box::new(ConcatTransform)),
-        "split" => Some(Box::new(SplitTransform)),
-        "format" => Some(Box::new(FormatTransform)),
-        "slugify" => Some(Box::new(SlugifyTransform)),
-        "html_to_markdown" => Some(Box::new(HtmlToMarkdownTransform)),
-        "markdown_to_html" => Some(Box::new(MarkdownToHtmlTransform)),
-        "strip_tags" => Some(Box::new(StripTagsTransform)),
-        "truncate" => Some(Box::new(TruncateTransform)),
-        "regex_replace" => Some(Box::new(RegexReplaceTransform)),
-        "date_format" => Some(Box::new(DateFormatTransform)),
-        "json_extract" => Some(Box::new(JsonExtractTransform)),
-        "expression" => Some(Box::new(ExpressionTransform)),
-        _ => None,
+/// Record-level completeness: the percentage of expected fields that are
+/// non-empty under the shared emptiness policy.
+pub struct CompletenessTransform;
+
+impl TransformPlugin for CompletenessTransform {
+    fn id(&self) -> &str { "completeness" }
+    fn display_name(&self) -> &str { "Record Completeness" }
+
+    fn input_type(&self) -> TypeSpec {
+        TypeSpec { kind: "object".into(), element_type: None, nullable: false, format: None }
+    }
+    fn output_type(&self) -> TypeSpec {
+        TypeSpec { kind: "object".into(), element_type: None, nullable: false, format: Some("completeness".into()) }
+    }
+
+    fn transform(&self, value: &Value, config: &TransformConfig) -> Result<Value, TransformError> {
+        let record = value.as_object().ok_or_else(|| TransformError::InvalidInput {
+            provider: self.id().into(),
+            detail: "expected an object record".into(),
+        })?;
+
+        // Expected fields default to every key present on the record
+        let fields: Vec<String> = match config.options.get("fields").and_then(|v| v.as_array()) {
+            Some(arr) => arr.iter().map(value_to_string).collect(),
+            None => record.keys().cloned().collect(),
+        };
+
+        let missing: Vec<String> = fields.iter()
+            .filter(|f| record.get(f.as_str()).is_none_or(|v| is_empty_value(v, config)))
+            .cloned()
+            .collect();
+
+        let score = if fields.is_empty() {
+            100.0
+        } else {
+            let filled = (fields.len() - missing.len()) as f64;
+            (filled / fields.len() as f64 * 10000.0).round() / 100.0
+        };
+
+        if let Some(min_score) = config.options.get("minScore").and_then(|v| v.as_f64()) {
+            if score < min_score {
+                return Err(TransformError::InvalidInput {
+                    provider: self.id().into(),
+                    detail: format!("completeness {score}% is below the minimum {min_score}% (missing: {})", missing.join(", ")),
+                });
+            }
+        }
+
+        Ok(serde_json::json!({ "score": score, "missing": missing }))
     }
 }
 
-/// Return all available provider IDs.
-pub fn available_providers() -> Vec<&'static str> {
-    vec![
-        "type_cast", "default_value", "lookup", "migration_lookup",
-        "concat", "split", "format", "slugify",
-        "html_to_markdown", "markdown_to_html", "strip_tags", "truncate",
-        "regex_replace", "date_format", "json_extract", "expression",
-    ]
+// ---------------------------------------------------------------------------
+// 18. PseudonymizeTransform
+// ---------------------------------------------------------------------------
+
+/// Keyed pseudonymization: the same input and secret always map to the same
+/// token, so records stay joinable without exposing the original value.
+pub struct PseudonymizeTransform;
+
+impl TransformPlugin for PseudonymizeTransform {
+    fn id(&self) -> &str { "pseudonymize" }
+    fn display_name(&self) -> &str { "Pseudonymize (Keyed Hash)" }
+
+    fn input_type(&self) -> TypeSpec {
+        TypeSpec { kind: "string".into(), element_type: None, nullable: true, format: None }
+    }
+    fn output_type(&self) -> TypeSpec {
+        TypeSpec { kind: "string".into(), element_type: None, nullable: true, format: Some("pseudonym".into()) }
+    }
+
+    fn transform(&self, value: &Value, config: &TransformConfig) -> Result<Value, TransformError> {
+        if value.is_null() { return Ok(Value::Null); }
+
+        let secret = option_str(config, "secret").filter(|s| !s.is_empty()).ok_or_else(|| {
+            TransformError::InvalidInput { provider: self.id().into(), detail: "a non-empty \"secret\" option is required".into() }
+        })?;
+        let mode = option_str(config, "mode").unwrap_or("token");
+        let input = value_to_string(value);
+
+        match mode {
+            "token" => {
+                let digest = self.keystream_block(secret, &input, 0);
+                let hex: String = digest.iter().map(|b| format!("{b:02x}")).collect();
+                let length = option_u64(config, "length", hex.len() as u64) as usize;
+                let prefix = option_str(config, "prefix").unwrap_or("");
+                Ok(Value::String(format!("{prefix}{}", &hex[..length.min(hex.len())])))
+            }
+            "format" => Ok(Value::String(self.format_preserving(secret, &input))),
+            _ => Err(TransformError::InvalidInput {
+                provider: self.id().into(),
+                detail: format!("unknown mode \"{mode}\" (expected \"token\" or \"format\")"),
+            }),
+        }
+    }
 }
 
-/// Execute a transform by provider ID, value, and config.
-pub fn execute_transform(value: &Value, config: &TransformConfig) -> Result<Value, TransformError> {
-    let provider = create_provider(&config.provider_id).ok_or_else(|| {
-        TransformError::InvalidInput {
-            provider: "registry".into(),
-            detail: format!("provider \"{}\" not found", config.provider_id),
+impl PseudonymizeTransform {
+    /// HMAC-SHA256 over a block counter and the input; successive counters
+    /// extend the keystream for inputs longer than one digest.
+    fn keystream_block(&self, secret: &str, input: &str, counter: u32) -> Vec<u8> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+            .expect("HMAC accepts keys of any length");
+        mac.update(&counter.to_be_bytes());
+        mac.update(input.as_bytes());
+        mac.finalize().into_bytes().to_vec()
+    }
+
+    /// Replace digits with digits and letters with letters (keeping case),
+    /// leaving punctuation and whitespace in place.
+    fn format_preserving(&self, secret: &str, input: &str) -> String {
+        let mut stream: Vec<u8> = Vec::new();
+        let mut counter = 0u32;
+        let mut result = String::with_capacity(input.len());
+
+        for (idx, c) in input.chars().enumerate() {
+            while stream.len() <= idx {
+                stream.extend(self.keystream_block(secret, input, counter));
+                counter += 1;
+            }
+            let byte = stream[idx];
+            let mapped = if c.is_ascii_digit() {
+                (b'0' + byte % 10) as char
+            } else if c.is_ascii_lowercase() {
+                (b'a' + byte % 26) as char
+            } else if c.is_ascii_uppercase() {
+                (b'A' + byte % 26) as char
+            } else {
+                c
+            };
+            result.push(mapped);
+        }
+        result
+    }
+}
+
+// ---------------------------------------------------------------------------
+// 19. RangeParseTransform
+// ---------------------------------------------------------------------------
+
+/// Parses ranges written as strings ("10-20", "10 to 20", "<5", ">=10", "7")
+/// into `{ min, max, minInclusive, maxInclusive }`, with null for open ends.
+pub struct RangeParseTransform;
+
+impl TransformPlugin for RangeParseTransform {
+    fn id(&self) -> &str { "range_parse" }
+    fn display_name(&self) -> &str { "Parse Numeric Range" }
+
+    fn input_type(&self) -> TypeSpec {
+        TypeSpec { kind: "string".into(), element_type: None, nullable: true, format: None }
+    }
+    fn output_type(&self) -> TypeSpec {
+        TypeSpec { kind: "object".into(), element_type: None, nullable: true, format: Some("range".into()) }
+    }
+
+    fn transform(&self, value: &Value, _config: &TransformConfig) -> Result<Value, TransformError> {
+        if value.is_null() { return Ok(Value::Null); }
+
+        let s = value_to_string(value);
+        let text = s.trim();
+        let num = r"([-+]?\d+(?:\.\d+)?)";
+
+        let closed = cached_regex(&format!(r"(?i)^{num}\s*(?:-|\u{{2013}}|\.\.|to)\s*{num}$")).unwrap();
+        let open = cached_regex(&format!(r"^(<=|>=|<|>)\s*{num}$")).unwrap();
+        let single = cached_regex(&format!(r"^{num}$")).unwrap();
+
+        let (min, max, min_inclusive, max_inclusive) = if let Some(caps) = closed.captures(text) {
+            (Some(caps[1].to_string()), Some(caps[2].to_string()), true, true)
+        } else if let Some(caps) = open.captures(text) {
+            let bound = caps[2].to_string();
+            match &caps[1] {
+                "<" => (None, Some(bound), false, false),
+                "<=" => (None, Some(bound), false, true),
+                ">" => (Some(bound), None, false, false),
+                _ => (Some(bound), None, true, false),
+            }
+        } else if let Some(caps) = single.captures(text) {
+            (Some(caps[1].to_string()), Some(caps[1].to_string()), true, true)
+        } else {
+            return Err(TransformError::InvalidInput {
+                provider: self.id().into(),
+                detail: format!("cannot parse \"{s}\" as a numeric range"),
+            });
+        };
+
+        let min = min.map(|m| self.parse_bound(&m));
+        let max = max.map(|m| self.parse_bound(&m));
+        if let (Some(lo), Some(hi)) = (&min, &max) {
+            if lo.as_f64() > hi.as_f64() {
+                return Err(TransformError::InvalidInput {
+                    provider: self.id().into(),
+                    detail: format!("range \"{s}\" has a lower bound greater than its upper bound"),
+                });
+            }
+        }
+
+        Ok(serde_json::json!({
+            "min": min,
+            "max": max,
+            "minInclusive": min_inclusive,
+            "maxInclusive": max_inclusive,
+        }))
+    }
+}
+
+impl RangeParseTransform {
+    /// Keep integral bounds as JSON integers and everything else as floats.
+    fn parse_bound(&self, raw: &str) -> Value {
+        let raw = raw.trim_start_matches('+');
+        if let Ok(i) = raw.parse::<i64>() { return serde_json::json!(i); }
+        raw.parse::<f64>().ok()
+            .and_then(serde_json::Number::from_f64)
+            .map(Value::Number)
+            .unwrap_or(Value::Null)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// 20. NumberFormatTransform
+// ---------------------------------------------------------------------------
+
+/// Formats numbers for display: digit grouping, fixed decimals, and an optional
+/// currency symbol or percent sign. Separators are configurable per locale.
+pub struct NumberFormatTransform;
+
+impl TransformPlugin for NumberFormatTransform {
+    fn id(&self) -> &str { "number_format" }
+    fn display_name(&self) -> &str { "Number Format" }
+
+    fn input_type(&self) -> TypeSpec {
+        TypeSpec { kind: "number".into(), element_type: None, nullable: true, format: None }
+    }
+    fn output_type(&self) -> TypeSpec {
+        TypeSpec { kind: "string".into(), element_type: None, nullable: true, format: None }
+    }
+
+    fn transform(&self, value: &Value, config: &TransformConfig) -> Result<Value, TransformError> {
+        let number = match value {
+            Value::Null => return Ok(Value::Null),
+            Value::Number(n) => n.as_f64(),
+            Value::String(s) => s.trim().parse::<f64>().ok(),
+            _ => None,
         }
-    })?;
-    provider.transform(value, config)
+        .filter(|n| n.is_finite())
+        .ok_or_else(|| TransformError::InvalidInput {
+            provider: self.id().into(),
+            detail: format!("\"{}\" is not a number", value_to_string(value)),
+        })?;
+
+        let style = option_str(config, "style").unwrap_or("decimal");
+        let grouping = option_str(config, "grouping").unwrap_or(",");
+        let decimal = option_str(config, "decimal").unwrap_or(".");
+        let decimals = option_u64(config, "decimals", 2) as usize;
+
+        let scaled = if style == "percent" { number * 100.0 } else { number };
+        let fixed = format!("{:.*}", decimals, scaled.abs());
+        let (int_part, frac_part) = fixed.split_once('.').unwrap_or((&fixed, ""));
+
+        let mut digits = Self::group_digits(int_part, grouping);
+        if !frac_part.is_empty() {
+            digits.push_str(decimal);
+            digits.push_str(frac_part);
+        }
+
+        let negative = scaled < 0.0 && fixed.chars().any(|c| c.is_ascii_digit() && c != '0');
+        let sign = if negative { "-" } else { "" };
+        let formatted = match style {
+            "percent" => format!("{sign}{digits}%"),
+            "currency" => {
+                let symbol = option_str(config, "currency").unwrap_or("$");
+                if option_str(config, "currencyPosition") == Some("suffix") {
+                    format!("{sign}{digits} {symbol}")
+                } else {
+                    format!("{sign}{symbol}{digits}")
+                }
+            }
+            _ => format!("{sign}{digits}"),
+        };
+        Ok(Value::String(formatted))
+    }
+}
+
+impl NumberFormatTransform {
+    fn group_digits(int_part: &str, separator: &str) -> String {
+        let digits: Vec<char> = int_part.chars().collect();
+        let groups: Vec<String> = digits.rchunks(3).rev().map(|g| g.iter().collect()).collect();
+        groups.join(separator)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// 21. HashTransform
+// ---------------------------------------------------------------------------
+
+/// Digests the UTF-8 bytes of the stringified value, for dedup keys and
+/// content fingerprints.
+pub struct HashTransform;
+
+impl TransformPlugin for HashTransform {
+    fn id(&self) -> &str { "hash" }
+    fn display_name(&self) -> &str { "Hash" }
+
+    fn input_type(&self) -> TypeSpec {
+        TypeSpec { kind: "string".into(), element_type: None, nullable: true, format: None }
+    }
+    fn output_type(&self) -> TypeSpec {
+        TypeSpec { kind: "string".into(), element_type: None, nullable: false, format: Some("digest".into()) }
+    }
+
+    fn transform(&self, value: &Value, config: &TransformConfig) -> Result<Value, TransformError> {
+        use sha2::Digest;
+
+        let algorithm = option_str(config, "algorithm").unwrap_or("sha256");
+        let encoding = option_str(config, "encoding").unwrap_or("hex");
+        let input = value_to_string(value);
+        let bytes = input.as_bytes();
+
+        let digest: Vec<u8> = match algorithm {
+            "md5" => md5::Md5::digest(bytes).to_vec(),
+            "sha1" => sha1::Sha1::digest(bytes).to_vec(),
+            "sha256" => Sha256::digest(bytes).to_vec(),
+            "blake3" => blake3::hash(bytes).as_bytes().to_vec(),
+            other => return Err(TransformError::InvalidInput {
+                provider: self.id().into(),
+                detail: format!("unknown algorithm \"{other}\"; expected md5, sha1, sha256 or blake3"),
+            }),
+        };
+
+        let encoded = match encoding {
+            "hex" => digest.iter().map(|b| format!("{b:02x}")).collect(),
+            "base64" => {
+                use base64::Engine;
+                base64::engine::general_purpose::STANDARD.encode(&digest)
+            }
+            other => return Err(TransformError::InvalidInput {
+                provider: self.id().into(),
+                detail: format!("unknown encoding \"{other}\"; expected hex or base64"),
+            }),
+        };
+        Ok(Value::String(encoded))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// 22. ArrayMapTransform
+// ---------------------------------------------------------------------------
+
+/// Applies the nested `transform` config to every element of an array.
+pub struct ArrayMapTransform;
+
+impl TransformPlugin for ArrayMapTransform {
+    fn id(&self) -> &str { "array_map" }
+    fn display_name(&self) -> &str { "Array Map" }
+
+    fn input_type(&self) -> TypeSpec {
+        TypeSpec { kind: "array".into(), element_type: Some("any".into()), nullable: false, format: None }
+    }
+    fn output_type(&self) -> TypeSpec {
+        TypeSpec { kind: "array".into(), element_type: Some("any".into()), nullable: false, format: None }
+    }
+
+    fn transform(&self, value: &Value, config: &TransformConfig) -> Result<Value, TransformError> {
+        let items = require_array(self.id(), value)?;
+        let inner: TransformConfig = config.options.get("transform")
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .ok_or_else(|| TransformError::InvalidInput {
+                provider: self.id().into(),
+                detail: "option \"transform\" must be a transform config".into(),
+            })?;
+
+        let mapped = items.iter().enumerate()
+            .map(|(i, item)| execute_transform(item, &inner).map_err(|e| TransformError::InvalidInput {
+                provider: self.id().into(),
+                detail: format!("element {i}: {e}"),
+            }))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Value::Array(mapped))
+    }
+}
+
+/// Byte offsets in `text` that fall outside single- or double-quoted literals.
+fn unquoted_positions(text: &str) -> impl Iterator<Item = usize> + '_ {
+    let mut quote = None;
+    text.char_indices().filter_map(move |(i, c)| match quote {
+        Some(q) => {
+            if c == q { quote = None; }
+            None
+        }
+        None if c == '"' || c == '\'' => {
+            quote = Some(c);
+            None
+        }
+        None => Some(i),
+    })
+}
+
+/// Split on `separator`, ignoring occurrences inside quoted literals.
+fn split_unquoted<'a>(text: &'a str, separator: &str) -> Vec<&'a str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    for i in unquoted_positions(text) {
+        if i >= start && text[i..].starts_with(separator) {
+            parts.push(&text[start..i]);
+            start = i + separator.len();
+        }
+    }
+    parts.push(&text[start..]);
+    parts
+}
+
+// ---------------------------------------------------------------------------
+// 23. ArrayFilterTransform
+// ---------------------------------------------------------------------------
+
+/// Keeps the elements for which `condition` is truthy. Conditions use the
+/// computed-mapper comparison syntax: `price > 10`, `status == "active" && qty >= 1`.
+/// Identifiers resolve against object fields (dotted paths); `value` is the element.
+pub struct ArrayFilterTransform;
+
+impl TransformPlugin for ArrayFilterTransform {
+    fn id(&self) -> &str { "array_filter" }
+    fn display_name(&self) -> &str { "Array Filter" }
+
+    fn input_type(&self) -> TypeSpec {
+        TypeSpec { kind: "array".into(), element_type: Some("any".into()), nullable: false, format: None }
+    }
+    fn output_type(&self) -> TypeSpec {
+        TypeSpec { kind: "array".into(), element_type: Some("any".into()), nullable: false, format: None }
+    }
+
+    fn transform(&self, value: &Value, config: &TransformConfig) -> Result<Value, TransformError> {
+        let items = require_array(self.id(), value)?;
+        let condition = option_str(config, "condition").unwrap_or("");
+        if condition.trim().is_empty() {
+            return Err(TransformError::InvalidExpression {
+                expression: String::new(),
+                detail: "condition is required".into(),
+            });
+        }
+
+        let mut kept = Vec::new();
+        for item in items {
+            if self.evaluate(condition, item)? {
+                kept.push(item.clone());
+            }
+        }
+        Ok(Value::Array(kept))
+    }
+}
+
+impl ArrayFilterTransform {
+    fn evaluate(&self, condition: &str, item: &Value) -> Result<bool, TransformError> {
+        for disjunct in split_unquoted(condition, "||") {
+            let mut all = true;
+            for clause in split_unquoted(disjunct, "&&") {
+                let clause = clause.trim();
+                if clause.is_empty() {
+                    return Err(TransformError::InvalidExpression {
+                        expression: condition.into(),
+                        detail: "empty clause".into(),
+                    });
+                }
+                let holds = match self.find_operator(clause) {
+                    Some((at, op)) => {
+                        let left = self.operand(clause[..at].trim(), item);
+                        let right = self.operand(clause[at + op.len()..].trim(), item);
+                        self.compare(&left, op, &right)
+                    }
+                    None => {
+                        let (negate, operand) = match clause.strip_prefix('!') {
+                            Some(rest) => (true, rest.trim()),
+                            None => (false, clause),
+                        };
+                        self.truthy(&self.operand(operand, item)) != negate
+                    }
+                };
+                if !holds { all = false; break; }
+            }
+            if all { return Ok(true); }
+        }
+        Ok(false)
+    }
+
+    /// First comparison operator outside a quoted literal, with its byte offset.
+    fn find_operator(&self, clause: &str) -> Option<(usize, &'static str)> {
+        const OPERATORS: &[&str] = &["==", "!=", ">=", "<=", ">", "<"];
+        unquoted_positions(clause)
+            .find_map(|i| OPERATORS.iter().find(|op| clause[i..].starts_with(**op)).map(|op| (i, *op)))
+            .filter(|(i, _)| *i > 0)
+    }
+
+    fn operand(&self, token: &str, item: &Value) -> Value {
+        if token.len() >= 2 && ((token.starts_with('"') && token.ends_with('"'))
+            || (token.starts_with('\'') && token.ends_with('\''))) {
+            return Value::String(token[1..token.len() - 1].to_string());
+        }
+        match token {
+            "true" => return Value::Bool(true),
+            "false" => return Value::Bool(false),
+            "null" => return Value::Null,
+            "value" => return item.clone(),
+            _ => {}
+        }
+        if let Ok(n) = token.parse::<f64>() {
+            return serde_json::json!(n);
+        }
+        token.split('.').try_fold(item, |current, key| current.get(key))
+            .cloned()
+            .unwrap_or(Value::Null)
+    }
+
+    fn compare(&self, left: &Value, op: &str, right: &Value) -> bool {
+        if let (Some(l), Some(r)) = (left.as_f64(), right.as_f64()) {
+            return match op {
+                "==" => l == r,
+                "!=" => l != r,
+                ">" => l > r,
+                ">=" => l >= r,
+                "<" => l < r,
+                _ => l <= r,
+            };
+        }
+        if left.is_null() || right.is_null() {
+            return match op {
+                "==" => left == right,
+                "!=" => left != right,
+                _ => false,
+            };
+        }
+        let (l, r) = (value_to_string(left), value_to_string(right));
+        match op {
+            "==" => l == r,
+            "!=" => l != r,
+            ">" => l > r,
+            ">=" => l >= r,
+            "<" => l < r,
+            _ => l <= r,
+        }
+    }
+
+    fn truthy(&self, value: &Value) -> bool {
+        match value {
+            Value::Null => false,
+            Value::Bool(b) => *b,
+            Value::Number(n) => n.as_f64() != Some(0.0),
+            Value::String(s) => !s.is_empty(),
+            Value::Array(arr) => !arr.is_empty(),
+            Value::Object(_) => true,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// 24. ArrayUniqueTransform
+// ---------------------------------------------------------------------------
+
+/// Removes duplicate elements, keeping the first occurrence. Elements are
+/// compared by their stringified form, or by the value at the `key` path;
+/// elements missing that path are always kept.
+pub struct ArrayUniqueTransform;
+
+impl TransformPlugin for ArrayUniqueTransform {
+    fn id(&self) -> &str { "array_unique" }
+    fn display_name(&self) -> &str { "Array Unique" }
+
+    fn input_type(&self) -> TypeSpec {
+        TypeSpec { kind: "array".into(), element_type: Some("any".into()), nullable: false, format: None }
+    }
+    fn output_type(&self) -> TypeSpec {
+        TypeSpec { kind: "array".into(), element_type: Some("any".into()), nullable: false, format: None }
+    }
+
+    fn transform(&self, value: &Value, config: &TransformConfig) -> Result<Value, TransformError> {
+        let items = require_array(self.id(), value)?;
+        let key_path = option_str(config, "key");
+
+        let mut seen = std::collections::HashSet::new();
+        let unique = items.iter()
+            .filter(|item| {
+                let key = match key_path {
+                    Some(path) => path.split('.')
+                        .try_fold(*item, |current, key| current.get(key))
+                        .map(value_to_string),
+                    None => Some(value_to_string(item)),
+                };
+                // Elements without the key have nothing to compare on, so all are kept.
+                key.is_none_or(|key| seen.insert(key))
+            })
+            .cloned()
+            .collect();
+        Ok(Value::Array(unique))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// 25. CaseTransform
+// ---------------------------------------------------------------------------
+
+/// Re-cases identifiers and headers. Words are split on whitespace, `_`, `-`
+/// and camelCase boundaries (`HTTPServer` splits as `HTTP` + `Server`).
+pub struct CaseTransform;
+
+impl TransformPlugin for CaseTransform {
+    fn id(&self) -> &str { "case" }
+    fn display_name(&self) -> &str { "Change Case" }
+
+    fn input_type(&self) -> TypeSpec {
+        TypeSpec { kind: "string".into(), element_type: None, nullable: false, format: None }
+    }
+    fn output_type(&self) -> TypeSpec {
+        TypeSpec { kind: "string".into(), element_type: None, nullable: false, format: None }
+    }
+
+    fn transform(&self, value: &Value, config: &TransformConfig) -> Result<Value, TransformError> {
+        let style = option_str(config, "style").unwrap_or("snake");
+        let words: Vec<String> = Self::split_words(&value_to_string(value))
+            .into_iter()
+            .map(|w| w.to_lowercase())
+            .collect();
+
+        let result = match style {
+            "camel" => words.iter().enumerate()
+                .map(|(i, w)| if i == 0 { w.clone() } else { Self::capitalize(w) })
+                .collect::<String>(),
+            "pascal" => words.iter().map(|w| Self::capitalize(w)).collect::<String>(),
+            "snake" => words.join("_"),
+            "kebab" => words.join("-"),
+            "title" => words.iter().map(|w| Self::capitalize(w)).collect::<Vec<_>>().join(" "),
+            "upper" => words.join(" ").to_uppercase(),
+            "lower" => words.join(" "),
+            other => return Err(TransformError::InvalidInput {
+                provider: self.id().into(),
+                detail: format!("unknown case style \"{other}\""),
+            }),
+        };
+        Ok(Value::String(result))
+    }
+}
+
+impl CaseTransform {
+    fn split_words(s: &str) -> Vec<String> {
+        let mut words = Vec::new();
+        for chunk in s.split(|c: char| c.is_whitespace() || c == '_' || c == '-') {
+            let chars: Vec<char> = chunk.chars().collect();
+            let mut current = String::new();
+            for (i, &c) in chars.iter().enumerate() {
+                let boundary = i > 0 && c.is_uppercase() && (
+                    chars[i - 1].is_lowercase()
+                    || chars[i - 1].is_ascii_digit()
+                    || (chars[i - 1].is_uppercase() && chars.get(i + 1).is_some_and(|n| n.is_lowercase()))
+                );
+                if boundary && !current.is_empty() {
+                    words.push(std::mem::take(&mut current));
+                }
+                current.push(c);
+            }
+            if !current.is_empty() {
+                words.push(current);
+            }
+        }
+        words
+    }
+
+    fn capitalize(word: &str) -> String {
+        let mut chars = word.chars();
+        match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => String::new(),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// 26. SanitizeHtmlTransform
+// ---------------------------------------------------------------------------
+
+/// Scrubs HTML for safe rendering using ammonia's parser-based cleaner:
+/// script/style bodies are always dropped, even if listed in `allowedTags`,
+/// URLs outside `allowedSchemes` are removed, and `on*` event handlers are
+/// never kept even if listed in `allowedAttributes`.
+pub struct SanitizeHtmlTransform;
+
+impl TransformPlugin for SanitizeHtmlTransform {
+    fn id(&self) -> &str { "sanitize_html" }
+    fn display_name(&self) -> &str { "Sanitize HTML" }
+
+    fn input_type(&self) -> TypeSpec {
+        TypeSpec { kind: "string".into(), element_type: None, nullable: false, format: Some("html".into()) }
+    }
+    fn output_type(&self) -> TypeSpec {
+        TypeSpec { kind: "string".into(), element_type: None, nullable: false, format: Some("html".into()) }
+    }
+
+    fn transform(&self, value: &Value, config: &TransformConfig) -> Result<Value, TransformError> {
+        let string_list = |key: &str| -> Option<std::collections::HashSet<&str>> {
+            config.options.get(key)
+                .and_then(|v| v.as_array())
+                .map(|arr| arr.iter().filter_map(|v| v.as_str()).collect())
+        };
+        let is_event_handler = |attr: &&str| attr.to_ascii_lowercase().starts_with("on");
+
+        let mut builder = ammonia::Builder::default();
+        if let Some(mut tags) = string_list("allowedTags") {
+            // ammonia refuses a tag that is both allowed and content-stripped.
+            tags.retain(|t| !t.eq_ignore_ascii_case("script") && !t.eq_ignore_ascii_case("style"));
+            builder.clean_content_tags(["script", "style"].into_iter().collect());
+            builder.tags(tags);
+        }
+        if let Some(schemes) = string_list("allowedSchemes") {
+            builder.url_schemes(schemes);
+        }
+        if let Some(attrs) = config.options.get("allowedAttributes").and_then(|v| v.as_object()) {
+            // { "*": ["title"], "a": ["href", "target"] }
+            let mut per_tag = HashMap::new();
+            let mut allows_rel = false;
+            for (tag, list) in attrs {
+                let names: std::collections::HashSet<&str> = list.as_array()
+                    .map(|arr| arr.iter().filter_map(|v| v.as_str()).filter(|a| !is_event_handler(a)).collect())
+                    .unwrap_or_default();
+                allows_rel |= names.contains("rel");
+                if tag == "*" {
+                    builder.generic_attributes(names);
+                } else {
+                    per_tag.insert(tag.as_str(), names);
+                }
+            }
+            // ammonia manages rel itself unless told not to.
+            if allows_rel {
+                builder.link_rel(None);
+            }
+            builder.tag_attributes(per_tag);
+        }
+
+        Ok(Value::String(builder.clean(&value_to_string(value)).to_string()))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// 27. PhoneNormalizeTransform
+// ---------------------------------------------------------------------------
+
+/// Normalizes phone numbers with libphonenumber metadata. Numbers without a
+/// country code are read in `defaultRegion`; output is E.164 unless `format`
+/// asks for `national` or `international`.
+pub struct PhoneNormalizeTransform;
+
+impl TransformPlugin for PhoneNormalizeTransform {
+    fn id(&self) -> &str { "phone_normalize" }
+    fn display_name(&self) -> &str { "Normalize Phone Number" }
+
+    fn input_type(&self) -> TypeSpec {
+        TypeSpec { kind: "string".into(), element_type: None, nullable: true, format: None }
+    }
+    fn output_type(&self) -> TypeSpec {
+        TypeSpec { kind: "string".into(), element_type: None, nullable: true, format: Some("phone".into()) }
+    }
+
+    fn transform(&self, value: &Value, config: &TransformConfig) -> Result<Value, TransformError> {
+        let strict = option_bool(config, "strict", false);
+        let mode = match option_str(config, "format").unwrap_or("e164") {
+            "e164" => phonenumber::Mode::E164,
+            "international" => phonenumber::Mode::International,
+            "national" => phonenumber::Mode::National,
+            other => return Err(TransformError::InvalidInput {
+                provider: self.id().into(),
+                detail: format!("unknown format \"{other}\"; expected e164, international or national"),
+            }),
+        };
+        let region = match option_str(config, "defaultRegion") {
+            Some(code) => Some(code.to_uppercase().parse::<phonenumber::country::Id>().map_err(|_| {
+                TransformError::InvalidInput {
+                    provider: self.id().into(),
+                    detail: format!("unknown region \"{code}\""),
+                }
+            })?),
+            None => None,
+        };
+
+        let input = value_to_string(value);
+        match phonenumber::parse(region, input.trim()) {
+            Ok(number) if phonenumber::is_valid(&number) => {
+                Ok(Value::String(number.format().mode(mode).to_string()))
+            }
+            _ if strict => Err(TransformError::InvalidInput {
+                provider: self.id().into(),
+                detail: format!("\"{input}\" is not a valid phone number"),
+            }),
+            _ => Ok(config.options.get("default").cloned().unwrap_or(Value::Null)),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// 28. JsonSchemaTransform
+// ---------------------------------------------------------------------------
+
+/// Validates the value against the JSON Schema in the `schema` option. Valid
+/// input passes through unchanged; with `mode: "check"` the result is a boolean
+/// instead of an error.
+pub struct JsonSchemaTransform;
+
+impl TransformPlugin for JsonSchemaTransform {
+    fn id(&self) -> &str { "json_schema" }
+    fn display_name(&self) -> &str { "JSON Schema Validate" }
+
+    fn input_type(&self) -> TypeSpec {
+        TypeSpec { kind: "any".into(), element_type: None, nullable: true, format: None }
+    }
+    fn output_type(&self) -> TypeSpec {
+        TypeSpec { kind: "any".into(), element_type: None, nullable: true, format: None }
+    }
+
+    fn transform(&self, value: &Value, config: &TransformConfig) -> Result<Value, TransformError> {
+        let schema = config.options.get("schema").ok_or_else(|| TransformError::InvalidInput {
+            provider: self.id().into(),
+            detail: "option \"schema\" is required".into(),
+        })?;
+        let validator = jsonschema::validator_for(schema).map_err(|e| TransformError::InvalidInput {
+            provider: self.id().into(),
+            detail: format!("invalid schema: {e}"),
+        })?;
+
+        if option_str(config, "mode") == Some("check") {
+            return Ok(Value::Bool(validator.is_valid(value)));
+        }
+
+        let failures: Vec<String> = validator.iter_errors(value)
+            .map(|e| {
+                let path = e.instance_path.to_string();
+                format!("{}: {e}", if path.is_empty() { "/" } else { &path })
+            })
+            .collect();
+        if failures.is_empty() {
+            Ok(value.clone())
+        } else {
+            Err(TransformError::InvalidInput {
+                provider: self.id().into(),
+                detail: failures.join("; "),
+            })
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Factory function and registry
+// ---------------------------------------------------------------------------
+
+/// Ids of the built-in transform providers, in registration order.
+const BUILTIN_PROVIDERS: &[&str] = &[
+    "type_cast", "default_value", "lookup", "migration_lookup",
+    "concat", "split", "format", "slugify",
+    "html_to_markdown", "markdown_to_html", "strip_tags", "truncate",
+    "regex_replace", "date_format", "json_extract", "expression",
+    "completeness", "pseudonymize", "range_parse", "number_format",
+    "hash", "array_map", "array_filter", "array_unique",
+    "case", "sanitize_html", "phone_normalize",
+    "json_schema",
+];
+
+fn builtin_provider(id: &str) -> Option<Box<dyn TransformPlugin>> {
+    match id {
+        "type_cast" => Some(Box::new(TypeCastTransform)),
+        "default_value" => Some(Box::new(DefaultValueTransform)),
+        "lookup" => Some(Box::new(LookupTransform)),
+        "migration_lookup" => Some(Box::new(MigrationLookupTransform)),
+        "concat" => Some(Box::new(ConcatTransform)),
+        "split" => Some(Box::new(SplitTransform)),
+        "format" => Some(Box::new(FormatTransform)),
+        "slugify" => Some(Box::new(SlugifyTransform)),
+        "html_to_markdown" => Some(Box::new(HtmlToMarkdownTransform)),
+        "markdown_to_html" => Some(Box::new(MarkdownToHtmlTransform)),
+        "strip_tags" => Some(Box::new(StripTagsTransform)),
+        "truncate" => Some(Box::new(TruncateTransform)),
+        "regex_replace" => Some(Box::new(RegexReplaceTransform)),
+        "date_format" => Some(Box::new(DateFormatTransform)),
+        "json_extract" => Some(Box::new(JsonExtractTransform)),
+        "expression" => Some(Box::new(ExpressionTransform)),
+        "completeness" => Some(Box::new(CompletenessTransform)),
+        "pseudonymize" => Some(Box::new(PseudonymizeTransform)),
+        "range_parse" => Some(Box::new(RangeParseTransform)),
+        "number_format" => Some(Box::new(NumberFormatTransform)),
+        "hash" => Some(Box::new(HashTransform)),
+        "array_map" => Some(Box::new(ArrayMapTransform)),
+        "array_filter" => Some(Box::new(ArrayFilterTransform)),
+        "array_unique" => Some(Box::new(ArrayUniqueTransform)),
+        "case" => Some(Box::new(CaseTransform)),
+        "sanitize_html" => Some(Box::new(SanitizeHtmlTransform)),
+        "phone_normalize" => Some(Box::new(PhoneNormalizeTransform)),
+        "json_schema" => Some(Box::new(JsonSchemaTransform)),
+        _ => None,
+    }
+}
+
+pub type TransformFactory = Arc<dyn Fn() -> Box<dyn TransformPlugin> + Send + Sync>;
+
+/// Transform providers by id, so applications can add their own transforms
+/// next to the built-ins. `TransformRegistry::global()` is the process-wide
+/// registry that `execute_transform` and nested configs (such as the
+/// `array_map` inner transform) resolve `provider_id` through.
+#[derive(Clone, Default)]
+pub struct TransformRegistry {
+    factories: Vec<(String, TransformFactory)>,
+}
+
+static GLOBAL_REGISTRY: LazyLock<RwLock<Arc<TransformRegistry>>> =
+    LazyLock::new(|| RwLock::new(Arc::new(TransformRegistry::with_builtins())));
+
+impl TransformRegistry {
+    /// An empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A registry seeded with the built-in transforms.
+    pub fn with_builtins() -> Self {
+        let mut registry = Self::new();
+        for &id in BUILTIN_PROVIDERS {
+            registry.register(id, move || builtin_provider(id).expect("built-in transform"));
+        }
+        registry
+    }
+
+    /// Snapshot of the process-wide registry. Registrations made afterwards
+    /// are not visible in an existing snapshot.
+    pub fn global() -> Arc<TransformRegistry> {
+        GLOBAL_REGISTRY.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Register a provider factory. Re-registering an id replaces the earlier
+    /// factory, including a built-in one.
+    pub fn register<F>(&mut self, id: impl Into<String>, factory: F)
+    where
+        F: Fn() -> Box<dyn TransformPlugin> + Send + Sync + 'static,
+    {
+        let id = id.into();
+        match self.factories.iter_mut().find(|(existing, _)| *existing == id) {
+            Some(entry) => entry.1 = Arc::new(factory),
+            None => self.factories.push((id, Arc::new(factory))),
+        }
+    }
+
+    pub fn create(&self, id: &str) -> Option<Box<dyn TransformPlugin>> {
+        self.factories.iter()
+            .find(|(existing, _)| existing == id)
+            .map(|(_, factory)| factory())
+    }
+
+    pub fn available(&self) -> Vec<&str> {
+        self.factories.iter().map(|(id, _)| id.as_str()).collect()
+    }
+
+    /// Execute the transform named by `config.provider_id`.
+    pub fn execute(&self, value: &Value, config: &TransformConfig) -> Result<Value, TransformError> {
+        let provider = self.create(&config.provider_id).ok_or_else(|| {
+            TransformError::InvalidInput {
+                provider: "registry".into(),
+                detail: format!("provider \"{}\" not found", config.provider_id),
+            }
+        })?;
+        provider.transform(value, config)
+    }
+}
+
+/// Register a transform provider in the process-wide registry.
+pub fn register_provider<F>(id: impl Into<String>, factory: F)
+where
+    F: Fn() -> Box<dyn TransformPlugin> + Send + Sync + 'static,
+{
+    let mut global = GLOBAL_REGISTRY.write().unwrap_or_else(|e| e.into_inner());
+    let mut registry = (**global).clone();
+    registry.register(id, factory);
+    *global = Arc::new(registry);
+}
+
+/// Create a transform provider by its unique identifier.
+pub fn create_provider(id: &str) -> Option<Box<dyn TransformPlugin>> {
+    TransformRegistry::global().create(id)
+}
+
+/// Return all available provider IDs.
+pub fn available_providers() -> Vec<String> {
+    TransformRegistry::global().available().into_iter().map(String::from).collect()
+}
+
+/// Execute a transform by provider ID, value, and config.
+pub fn execute_transform(value: &Value, config: &TransformConfig) -> Result<Value, TransformError> {
+    TransformRegistry::global().execute(value, config)
+}
+
+/// Execute a sequence of transforms, feeding each output into the next step.
+/// Stops at the first failure, reporting the zero-based index of that step.
+pub fn execute_transform_pipeline(value: &Value, configs: &[TransformConfig]) -> Result<Value, TransformError> {
+    let mut current = value.clone();
+    for (step, config) in configs.iter().enumerate() {
+        current = execute_transform(&current, config).map_err(|e| TransformError::PipelineStep {
+            step,
+            provider: config.provider_id.clone(),
+            source: Box::new(e),
+        })?;
+    }
+    Ok(current)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn config(provider_id: &str, options: Value) -> TransformConfig {
+        let options = options.as_object()
+            .map(|o| o.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+            .unwrap_or_default();
+        TransformConfig { provider_id: provider_id.into(), options }
+    }
+
+    #[test]
+    fn date_format_converts_utc_to_new_york_during_dst() {
+        let cfg = config("date_format", json!({
+            "outputFormat": "YYYY-MM-DD HH:mm Z",
+            "timezone": "America/New_York",
+        }));
+        let result = execute_transform(&json!("2026-07-04T16:30:00Z"), &cfg).unwrap();
+        assert_eq!(result, json!("2026-07-04 12:30 -04:00"));
+
+        let compact = config("date_format", json!({ "outputFormat": "HH:mmZZ", "timezone": "America/New_York" }));
+        assert_eq!(execute_transform(&json!("2026-07-04T16:30:00Z"), &compact).unwrap(), json!("12:30-0400"));
+    }
+
+    #[test]
+    fn date_format_keeps_rfc3339_offset_and_instant() {
+        let cfg = config("date_format", json!({ "outputFormat": "YYYY-MM-DD HH:mm Z" }));
+        assert_eq!(
+            execute_transform(&json!("2026-03-01T23:15:00+02:00"), &cfg).unwrap(),
+            json!("2026-03-01 23:15 +02:00")
+        );
+
+        let utc = config("date_format", json!({ "outputFormat": "YYYY-MM-DD HH:mm Z", "timezone": "UTC" }));
+        assert_eq!(
+            execute_transform(&json!("2026-03-01T23:15:00+02:00"), &utc).unwrap(),
+            json!("2026-03-01 21:15 +00:00")
+        );
+    }
+
+    #[test]
+    fn date_format_copies_bracketed_text_and_names_literally() {
+        let cfg = config("date_format", json!({ "outputFormat": "YYYY-MM-DD[T]HH:mm[Z] Z, MMMM dddd A" }));
+        assert_eq!(
+            execute_transform(&json!("2026-08-02T14:05:00+02:00"), &cfg).unwrap(),
+            json!("2026-08-02T14:05Z +02:00, August Sunday PM")
+        );
+    }
+
+    #[test]
+    fn pipeline_threads_output_through_each_step() {
+        let steps = vec![
+            config("strip_tags", json!({})),
+            config("truncate", json!({ "maxLength": 20, "wordBoundary": false, "ellipsis": "" })),
+            config("slugify", json!({})),
+        ];
+        let result = execute_transform_pipeline(&json!("<h1>Hello <em>Wide</em> World of Rust</h1>"), &steps).unwrap();
+        assert_eq!(result, json!("hello-wide-world-of"));
+    }
+
+    #[test]
+    fn pipeline_error_reports_failing_step() {
+        let steps = vec![
+            config("strip_tags", json!({})),
+            config("type_cast", json!({ "targetType": "number", "strict": true })),
+            config("slugify", json!({})),
+        ];
+        let err = execute_transform_pipeline(&json!("<p>not a number</p>"), &steps).unwrap_err();
+        match err {
+            TransformError::PipelineStep { step, provider, source } => {
+                assert_eq!(step, 1);
+                assert_eq!(provider, "type_cast");
+                assert!(matches!(*source, TransformError::CastFailed { .. }));
+            }
+            other => panic!("expected PipelineStep error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn html_to_markdown_indents_three_levels_of_mixed_lists() {
+        let html = "<ul>\
+            <li>Fruit\
+              <ol>\
+                <li>Apples\
+                  <ul><li>Gala</li><li>Fuji</li></ul>\
+                </li>\
+                <li>Pears</li>\
+              </ol>\
+            </li>\
+            <li>Vegetables</li>\
+            </ul>";
+        let result = HtmlToMarkdownTransform
+            .transform(&json!(html), &config("html_to_markdown", json!({})))
+            .unwrap();
+        assert_eq!(
+            result,
+            json!("- Fruit\n  1. Apples\n    - Gala\n    - Fuji\n  2. Pears\n- Vegetables")
+        );
+    }
+
+    #[test]
+    fn completeness_of_half_populated_record() {
+        let record = json!({ "name": "Ada", "email": "", "phone": null, "city": "London" });
+        let result = execute_transform(&record, &config("completeness", json!({}))).unwrap();
+        assert_eq!(result["score"], json!(50.0));
+        let mut missing: Vec<String> = serde_json::from_value(result["missing"].clone()).unwrap();
+        missing.sort();
+        assert_eq!(missing, vec!["email", "phone"]);
+    }
+
+    #[test]
+    fn completeness_with_expected_fields_and_min_score() {
+        let record = json!({ "name": "Ada", "city": "London" });
+        let cfg = config("completeness", json!({ "fields": ["name", "city", "email", "phone"] }));
+        let result = execute_transform(&record, &cfg).unwrap();
+        assert_eq!(result["score"], json!(50.0));
+        assert_eq!(result["missing"], json!(["email", "phone"]));
+
+        let strict = config("completeness", json!({ "fields": ["name", "city", "email", "phone"], "minScore": 75 }));
+        assert!(matches!(execute_transform(&record, &strict), Err(TransformError::InvalidInput { .. })));
+    }
+
+    #[test]
+    fn pseudonymize_is_deterministic_and_key_dependent() {
+        let value = json!("alice@example.com");
+        let key_a = config("pseudonymize", json!({ "secret": "key-a" }));
+        let key_b = config("pseudonymize", json!({ "secret": "key-b" }));
+
+        let first = execute_transform(&value, &key_a).unwrap();
+        let second = execute_transform(&value, &key_a).unwrap();
+        let other_key = execute_transform(&value, &key_b).unwrap();
+
+        assert_eq!(first, second);
+        assert_ne!(first, other_key);
+        assert_ne!(first, value);
+        assert!(matches!(
+            execute_transform(&value, &config("pseudonymize", json!({}))),
+            Err(TransformError::InvalidInput { .. })
+        ));
+    }
+
+    #[test]
+    fn pseudonymize_format_preserving_numeric_id() {
+        let cfg = config("pseudonymize", json!({ "secret": "key-a", "mode": "format" }));
+        let token = execute_transform(&json!("123-45-6789"), &cfg).unwrap();
+        let token = token.as_str().unwrap();
+
+        assert_eq!(token.len(), 11);
+        for (original, mapped) in "123-45-6789".chars().zip(token.chars()) {
+            if original == '-' {
+                assert_eq!(mapped, '-');
+            } else {
+                assert!(mapped.is_ascii_digit());
+            }
+        }
+        assert_ne!(token, "123-45-6789");
+        assert_eq!(execute_transform(&json!("123-45-6789"), &cfg).unwrap(), json!(token));
+    }
+
+    #[test]
+    fn range_parse_closed_ranges() {
+        let cfg = config("range_parse", json!({}));
+        let expected = json!({ "min": 10, "max": 20, "minInclusive": true, "maxInclusive": true });
+        assert_eq!(execute_transform(&json!("10-20"), &cfg).unwrap(), expected);
+        assert_eq!(execute_transform(&json!("10 to 20"), &cfg).unwrap(), expected);
+        assert_eq!(
+            execute_transform(&json!("-5 - 2.5"), &cfg).unwrap(),
+            json!({ "min": -5, "max": 2.5, "minInclusive": true, "maxInclusive": true })
+        );
+    }
+
+    #[test]
+    fn range_parse_open_ended_ranges() {
+        let cfg = config("range_parse", json!({}));
+        assert_eq!(
+            execute_transform(&json!("<5"), &cfg).unwrap(),
+            json!({ "min": null, "max": 5, "minInclusive": false, "maxInclusive": false })
+        );
+        assert_eq!(
+            execute_transform(&json!(">= 10"), &cfg).unwrap(),
+            json!({ "min": 10, "max": null, "minInclusive": true, "maxInclusive": false })
+        );
+    }
+
+    #[test]
+    fn range_parse_single_value_and_errors() {
+        let cfg = config("range_parse", json!({}));
+        assert_eq!(
+            execute_transform(&json!("7"), &cfg).unwrap(),
+            json!({ "min": 7, "max": 7, "minInclusive": true, "maxInclusive": true })
+        );
+        assert!(matches!(execute_transform(&json!("about ten"), &cfg), Err(TransformError::InvalidInput { .. })));
+        assert!(matches!(execute_transform(&json!("20-10"), &cfg), Err(TransformError::InvalidInput { .. })));
+    }
+
+    #[test]
+    fn number_format_groups_thousands_with_two_decimals() {
+        let result = execute_transform(&json!(1234567.891), &config("number_format", json!({}))).unwrap();
+        assert_eq!(result, json!("1,234,567.89"));
+        let percent = config("number_format", json!({ "style": "percent", "decimals": 1 }));
+        assert_eq!(execute_transform(&json!("0.256"), &percent).unwrap(), json!("25.6%"));
+    }
+
+    #[test]
+    fn number_format_euro_currency_with_comma_decimals() {
+        let cfg = config("number_format", json!({
+            "style": "currency",
+            "currency": "\u{20AC}",
+            "currencyPosition": "suffix",
+            "grouping": ".",
+            "decimal": ",",
+        }));
+        assert_eq!(execute_transform(&json!("-1234.5"), &cfg).unwrap(), json!("-1.234,50 \u{20AC}"));
+    }
+
+    #[test]
+    fn hash_known_vectors() {
+        let cases = [
+            ("md5", "900150983cd24fb0d6963f7d28e17f72"),
+            ("sha1", "a9993e364706816aba3e25717850c26c9cd0d89d"),
+            ("sha256", "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"),
+            ("blake3", "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85"),
+        ];
+        for (algorithm, expected) in cases {
+            let cfg = config("hash", json!({ "algorithm": algorithm }));
+            assert_eq!(execute_transform(&json!("abc"), &cfg).unwrap(), json!(expected), "{algorithm}");
+        }
+    }
+
+    #[test]
+    fn hash_base64_encoding() {
+        let cfg = config("hash", json!({ "algorithm": "sha256", "encoding": "base64" }));
+        assert_eq!(
+            execute_transform(&json!("abc"), &cfg).unwrap(),
+            json!("ungWv48Bz+pBQUDeXa4iI7ADYaOWF3qctBD/YfIAFa0=")
+        );
+        let bad = config("hash", json!({ "algorithm": "crc32" }));
+        assert!(matches!(execute_transform(&json!("abc"), &bad), Err(TransformError::InvalidInput { .. })));
+    }
+
+    #[test]
+    fn array_map_applies_nested_transform() {
+        let cfg = config("array_map", json!({
+            "transform": { "providerId": "type_cast", "options": { "targetType": "int" } }
+        }));
+        assert_eq!(execute_transform(&json!(["1", "2"]), &cfg).unwrap(), json!([1, 2]));
+        assert!(matches!(execute_transform(&json!("1,2"), &cfg), Err(TransformError::InvalidInput { .. })));
+    }
+
+    #[test]
+    fn array_filter_by_comparison() {
+        let cfg = config("array_filter", json!({ "condition": "price > 10" }));
+        let items = json!([{ "price": 5 }, { "price": 50 }]);
+        assert_eq!(execute_transform(&items, &cfg).unwrap(), json!([{ "price": 50 }]));
+    }
+
+    #[test]
+    fn array_filter_ignores_operators_inside_quotes() {
+        let cfg = config("array_filter", json!({ "condition": "label == \"a || b\" && note != 'x && y'" }));
+        let items = json!([{ "label": "a || b", "note": "z" }, { "label": "a", "note": "z" }, { "label": "a || b", "note": "x && y" }]);
+        assert_eq!(execute_transform(&items, &cfg).unwrap(), json!([{ "label": "a || b", "note": "z" }]));
+
+        let cfg = config("array_filter", json!({ "condition": "'<' == value" }));
+        assert_eq!(execute_transform(&json!(["<", ">"]), &cfg).unwrap(), json!(["<"]));
+    }
+
+    #[test]
+    fn array_unique_by_value_and_key() {
+        let plain = config("array_unique", json!({}));
+        assert_eq!(execute_transform(&json!(["a", "b", "a", 1, "1"]), &plain).unwrap(), json!(["a", "b", 1]));
+
+        let keyed = config("array_unique", json!({ "key": "user.id" }));
+        let items = json!([{ "user": { "id": 1 }, "n": "x" }, { "user": { "id": 1 }, "n": "y" }, { "user": { "id": 2 } }]);
+        assert_eq!(
+            execute_transform(&items, &keyed).unwrap(),
+            json!([{ "user": { "id": 1 }, "n": "x" }, { "user": { "id": 2 } }])
+        );
+
+        let sparse = json!([{ "n": "x" }, { "n": "y" }, { "user": { "id": 1 } }]);
+        assert_eq!(execute_transform(&sparse, &keyed).unwrap(), sparse);
+    }
+
+    #[test]
+    fn case_converts_to_each_style() {
+        let expected = [
+            ("camel", "userFirstName"),
+            ("pascal", "UserFirstName"),
+            ("snake", "user_first_name"),
+            ("kebab", "user-first-name"),
+            ("title", "User First Name"),
+            ("upper", "USER FIRST NAME"),
+            ("lower", "user first name"),
+        ];
+        for input in ["user first name", "UserFirstName"] {
+            for (style, output) in expected {
+                let cfg = config("case", json!({ "style": style }));
+                assert_eq!(execute_transform(&json!(input), &cfg).unwrap(), json!(output), "{input} -> {style}");
+            }
+        }
+    }
+
+    #[test]
+    fn case_splits_acronyms_and_separators() {
+        let cfg = config("case", json!({ "style": "snake" }));
+        assert_eq!(execute_transform(&json!("parseHTTPResponse-code"), &cfg).unwrap(), json!("parse_http_response_code"));
+    }
+
+    #[test]
+    fn sanitize_html_removes_javascript_url_and_event_handler() {
+        let html = r#"<a href="javascript:alert(1)" onclick="x">click</a><script>steal()</script>"#;
+        let result = execute_transform(&json!(html), &config("sanitize_html", json!({}))).unwrap();
+        assert_eq!(result, json!(r#"<a rel="noopener noreferrer">click</a>"#));
+    }
+
+    #[test]
+    fn sanitize_html_honors_configured_allowlists() {
+        let cfg = config("sanitize_html", json!({
+            "allowedTags": ["a", "b"],
+            "allowedAttributes": { "a": ["href", "onmouseover"] },
+            "allowedSchemes": ["https"],
+        }));
+        let html = r#"<a href="https://ok.example" onmouseover="x">ok</a> <a href="http://no.example">no</a> <i>x</i><b>y</b>"#;
+        let result = execute_transform(&json!(html), &cfg).unwrap();
+        assert_eq!(
+            result,
+            json!(r#"<a href="https://ok.example" rel="noopener noreferrer">ok</a> <a rel="noopener noreferrer">no</a> x<b>y</b>"#)
+        );
+    }
+
+    #[test]
+    fn sanitize_html_strips_script_and_style_even_when_allowed() {
+        let cfg = config("sanitize_html", json!({ "allowedTags": ["p", "script", "STYLE"] }));
+        let result = execute_transform(&json!("<p>a</p><script>b</script><style>c</style>"), &cfg).unwrap();
+        assert_eq!(result, json!("<p>a</p>"));
+    }
+
+    #[test]
+    fn sanitize_html_keeps_generic_rel() {
+        let cfg = config("sanitize_html", json!({ "allowedAttributes": { "*": ["rel"], "a": ["href"] } }));
+        let result = execute_transform(&json!(r#"<a href="https://x.example" rel="me">x</a>"#), &cfg).unwrap();
+        assert_eq!(result, json!(r#"<a href="https://x.example" rel="me">x</a>"#));
+    }
+
+    #[test]
+    fn regex_cache_reuses_compiled_patterns_up_to_capacity() {
+        let cache = RegexCache::new(2);
+        let first = cache.get(r"(?i)probe-(\d+)").unwrap();
+        let second = cache.get(r"(?i)probe-(\d+)").unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(cache.len(), 1);
+
+        assert!(cache.get("(unclosed").is_err());
+        assert_eq!(cache.len(), 1);
+
+        cache.get("a").unwrap();
+        assert_eq!(cache.len(), 2);
+        cache.get("b").unwrap();
+        assert_eq!(cache.len(), 1);
+        assert!(!Arc::ptr_eq(&first, &cache.get(r"(?i)probe-(\d+)").unwrap()));
+    }
+
+    #[test]
+    fn repeated_regex_replace_matches_case_insensitively() {
+        let cfg = config("regex_replace", json!({ "pattern": "cache-probe-(\\d+)", "replacement": "#$1", "caseInsensitive": true }));
+        for i in 0..1000 {
+            let result = execute_transform(&json!(format!("Cache-Probe-{i}")), &cfg).unwrap();
+            assert_eq!(result, json!(format!("#{i}")));
+        }
+    }
+
+    #[test]
+    fn regex_replace_numbered_and_named_backreferences() {
+        let numbered = config("regex_replace", json!({ "pattern": r"(\w+)@(\w+)", "replacement": "$2 at ${1}" }));
+        assert_eq!(execute_transform(&json!("ada@example"), &numbered).unwrap(), json!("example at ada"));
+
+        let named = config("regex_replace", json!({
+            "pattern": r"(?P<amount>\d+) (?P<unit>USD)",
+            "replacement": "$$${amount} ($unit)",
+        }));
+        assert_eq!(execute_transform(&json!("costs 40 USD"), &named).unwrap(), json!("costs $40 (USD)"));
+    }
+
+    #[test]
+    fn regex_replace_rejects_unknown_group_reference() {
+        let missing_index = config("regex_replace", json!({ "pattern": r"(\d+)", "replacement": "$2" }));
+        assert!(matches!(execute_transform(&json!("42"), &missing_index), Err(TransformError::InvalidPattern { .. })));
+
+        let missing_name = config("regex_replace", json!({ "pattern": r"(?P<n>\d+)", "replacement": "${num}" }));
+        assert!(matches!(execute_transform(&json!("42"), &missing_name), Err(TransformError::InvalidPattern { .. })));
+    }
+
+    #[test]
+    fn phone_normalize_us_and_uk_numbers() {
+        let us = config("phone_normalize", json!({ "defaultRegion": "US" }));
+        for input in ["(415) 555-2671", "415.555.2671", "+1 415 555 2671", "1-415-555-2671"] {
+            assert_eq!(execute_transform(&json!(input), &us).unwrap(), json!("+14155552671"), "{input}");
+        }
+
+        let uk = config("phone_normalize", json!({ "defaultRegion": "GB" }));
+        for input in ["020 7946 0958", "(020) 7946-0958", "+44 20 7946 0958"] {
+            assert_eq!(execute_transform(&json!(input), &uk).unwrap(), json!("+442079460958"), "{input}");
+        }
+        let uk_national = config("phone_normalize", json!({ "defaultRegion": "GB", "format": "national" }));
+        assert_eq!(execute_transform(&json!("+44 20 7946 0958"), &uk_national).unwrap(), json!("020 7946 0958"));
+    }
+
+    #[test]
+    fn phone_normalize_invalid_uses_default_or_errors_when_strict() {
+        let lenient = config("phone_normalize", json!({ "defaultRegion": "US", "default": "unknown" }));
+        assert_eq!(execute_transform(&json!("12"), &lenient).unwrap(), json!("unknown"));
+
+        let strict = config("phone_normalize", json!({ "defaultRegion": "US", "strict": true }));
+        assert!(matches!(execute_transform(&json!("12"), &strict), Err(TransformError::InvalidInput { .. })));
+    }
+
+    #[test]
+    fn truncate_handles_tiny_limits_and_multibyte_ellipsis() {
+        for position in ["start", "middle", "end"] {
+            let narrow = config("truncate", json!({ "maxLength": 2, "ellipsis": "...", "position": position }));
+            assert_eq!(execute_transform(&json!("abcdef"), &narrow).unwrap(), json!(".."), "{position}");
+
+            let one_char = config("truncate", json!({ "maxLength": 0, "ellipsis": "\u{2026}", "position": position }));
+            assert_eq!(execute_transform(&json!("a"), &one_char).unwrap(), json!(""), "{position}");
+
+            let unicode = config("truncate", json!({ "maxLength": 5, "ellipsis": "\u{2026}", "position": position }));
+            let expected = match position {
+                "start" => "\u{2026}\u{e9}t\u{e9}s",
+                "middle" => "\u{e9}t\u{2026}\u{e9}s",
+                _ => "\u{e9}t\u{e9}\u{e9}\u{2026}",
+            };
+            assert_eq!(execute_transform(&json!("\u{e9}t\u{e9}\u{e9}t\u{e9}s"), &unicode).unwrap(), json!(expected), "{position}");
+        }
+    }
+
+    #[test]
+    fn json_schema_validates_required_and_type() {
+        let schema = json!({
+            "type": "object",
+            "required": ["id", "email"],
+            "properties": { "id": { "type": "integer" }, "email": { "type": "string" } },
+        });
+        let cfg = config("json_schema", json!({ "schema": schema.clone() }));
+        let valid = json!({ "id": 7, "email": "ada@example.com" });
+        assert_eq!(execute_transform(&valid, &cfg).unwrap(), valid);
+
+        let invalid = json!({ "id": "seven" });
+        match execute_transform(&invalid, &cfg) {
+            Err(TransformError::InvalidInput { detail, .. }) => {
+                assert!(detail.contains("\"email\" is a required property"), "{detail}");
+                assert!(detail.contains("/id"), "{detail}");
+            }
+            other => panic!("expected validation failure, got {other:?}"),
+        }
+
+        let check = config("json_schema", json!({ "schema": schema, "mode": "check" }));
+        assert_eq!(execute_transform(&invalid, &check).unwrap(), json!(false));
+        assert_eq!(execute_transform(&valid, &check).unwrap(), json!(true));
+    }
+
+    struct ReverseTransform;
+
+    impl TransformPlugin for ReverseTransform {
+        fn id(&self) -> &str { "reverse_text" }
+        fn display_name(&self) -> &str { "Reverse Text" }
+        fn input_type(&self) -> TypeSpec {
+            TypeSpec { kind: "string".into(), element_type: None, nullable: false, format: None }
+        }
+        fn output_type(&self) -> TypeSpec {
+            TypeSpec { kind: "string".into(), element_type: None, nullable: false, format: None }
+        }
+
+        fn transform(&self, value: &Value, _config: &TransformConfig) -> Result<Value, TransformError> {
+            Ok(Value::String(value_to_string(value).chars().rev().collect()))
+        }
+    }
+
+    #[test]
+    fn registry_executes_registered_transform() {
+        let mut registry = TransformRegistry::with_builtins();
+        assert_eq!(registry.available().len(), 28);
+        registry.register("reverse_text", || Box::new(ReverseTransform));
+        assert_eq!(registry.execute(&json!("abc"), &config("reverse_text", json!({}))).unwrap(), json!("cba"));
+        assert!(create_provider("reverse_text").is_none());
+
+        register_provider("reverse_text", || Box::new(ReverseTransform));
+        let cfg: TransformConfig = serde_json::from_value(json!({ "providerId": "reverse_text" })).unwrap();
+        assert_eq!(execute_transform(&json!("stressed"), &cfg).unwrap(), json!("desserts"));
+        assert!(available_providers().contains(&"reverse_text".to_string()));
+
+        // Nested configs resolve through the global registry as well.
+        let map = config("array_map", json!({ "transform": { "providerId": "reverse_text" } }));
+        assert_eq!(execute_transform(&json!(["ab", "cd"]), &map).unwrap(), json!(["ba", "dc"]));
+    }
 }
@@ -2,6 +2,9 @@
 // Periodic API query with delta detection via hash, cursor, or timestamp strategies
 
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use futures::{Stream, StreamExt};
 
 pub const PROVIDER_ID: &str = "api_poll";
 pub const PLUGIN_TYPE: &str = "capture_mode";
@@ -18,6 +21,34 @@ pub struct CaptureInput {
 pub struct CaptureConfig {
     pub mode: String,
     pub options: Option<HashMap<String, serde_json::Value>>,
+    pub cursor_store: Option<Arc<dyn CaptureCursorStore>>,
+}
+
+/// Durable home for the last poll cursor of each endpoint, so cursor-based
+/// polls resume where the previous run stopped instead of re-reading items.
+pub trait CaptureCursorStore: std::fmt::Debug + Send + Sync {
+    fn load(&self, endpoint_id: &str) -> Option<String>;
+    fn save(&self, endpoint_id: &str, cursor: &str);
+}
+
+/// Process-local cursor store, useful for tests and single-run pipelines.
+#[derive(Debug, Default)]
+pub struct InMemoryCursorStore {
+    cursors: Mutex<HashMap<String, String>>,
+}
+
+impl InMemoryCursorStore {
+    pub fn new() -> Self { Self::default() }
+}
+
+impl CaptureCursorStore for InMemoryCursorStore {
+    fn load(&self, endpoint_id: &str) -> Option<String> {
+        self.cursors.lock().unwrap().get(endpoint_id).cloned()
+    }
+
+    fn save(&self, endpoint_id: &str, cursor: &str) {
+        self.cursors.lock().unwrap().insert(endpoint_id.to_string(), cursor.to_string());
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -209,62 +240,39 @@ fn extract_hostname(url: &str) -> String {
 }
 
 pub struct ApiPollCaptureProvider {
-    state_store: std::sync::Mutex<HashMap<String, PollState>>,
+    state_store: Mutex<HashMap<String, PollState>>,
 }
 
 impl ApiPollCaptureProvider {
     pub fn new() -> Self {
         Self {
-            state_store: std::sync::Mutex::new(HashMap::new()),
+            state_store: Mutex::new(HashMap::new()),
         }
     }
 
     pub fn capture(&self, input: &CaptureInput, config: &CaptureConfig) -> Result<CaptureItem, CaptureError> {
+        self.capture_with(input, config, &http_request)
+    }
+
+    /// Poll using a caller-supplied HTTP transport. The transport receives the
+    /// previous poll state, whose `last_cursor` is the cursor to resume from.
+    /// This drains `capture_stream_with` into a single capture item.
+    pub fn capture_with(
+        &self,
+        input: &CaptureInput,
+        config: &CaptureConfig,
+        transport: &dyn Fn(&PollConfig, &PollState) -> Result<String, CaptureError>,
+    ) -> Result<CaptureItem, CaptureError> {
         let poll_config = parse_poll_config(input, config);
         if poll_config.endpoint.is_empty() { return Err(CaptureError::MissingEndpoint); }
 
-        let state_key = compute_hash(&format!("{}{}", poll_config.endpoint, poll_config.method));
-        let previous_state = self.state_store.lock().unwrap()
-            .get(&state_key).cloned().unwrap_or_default();
-
-        let response_body = http_request(&poll_config, &previous_state)
-            .map_err(|e| CaptureError::FetchError(e.to_string()))?;
+        let captured_items = futures::executor::block_on(
+            self.capture_stream_with(input, config, transport).collect::<Vec<_>>(),
+        )
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()?;
         let now = chrono::Utc::now().to_rfc3339();
-
-        let parsed: serde_json::Value = serde_json::from_str(&response_body)
-            .unwrap_or(serde_json::Value::String(response_body.clone()));
-
-        let mut changed = false;
-        let mut captured_items: Vec<serde_json::Value> = Vec::new();
-        let mut new_state = PollState { last_poll_at: Some(now.clone()), ..Default::default() };
-
-        match poll_config.delta_strategy {
-            DeltaStrategy::Hash => {
-                let (ch, hash) = detect_changes_hash(&response_body, previous_state.last_hash.as_deref());
-                changed = ch;
-                new_state.last_hash = Some(hash);
-                if changed { captured_items.push(parsed.clone()); }
-            }
-            DeltaStrategy::Timestamp => {
-                let (ch, items, latest) = detect_changes_timestamp(
-                    &parsed, poll_config.items_path.as_deref(),
-                    &poll_config.timestamp_field, previous_state.last_timestamp.as_deref(),
-                );
-                changed = ch;
-                captured_items = items;
-                new_state.last_timestamp = latest.or(previous_state.last_timestamp.clone());
-            }
-            DeltaStrategy::Cursor => {
-                let (items, next_cursor) = detect_changes_cursor(
-                    &parsed, &poll_config.cursor_field, poll_config.items_path.as_deref(),
-                );
-                changed = !items.is_empty();
-                captured_items = items;
-                new_state.last_cursor = next_cursor;
-            }
-        }
-
-        self.state_store.lock().unwrap().insert(state_key, new_state);
+        let changed = !captured_items.is_empty();
 
         let content = if changed {
             serde_json::to_string_pretty(&captured_items).unwrap_or_else(|_| "(serialization error)".to_string())
@@ -298,6 +306,63 @@ impl ApiPollCaptureProvider {
         })
     }
 
+    /// Stream polled items instead of buffering them into one capture item.
+    pub fn capture_stream<'a>(
+        &'a self,
+        input: &CaptureInput,
+        config: &'a CaptureConfig,
+    ) -> impl Stream<Item = Result<serde_json::Value, CaptureError>> + 'a {
+        self.capture_stream_with(input, config, &http_request)
+    }
+
+    /// Like `capture_with`, but yields items lazily. With the cursor strategy
+    /// each page's cursor is followed until a page comes back empty or without
+    /// one; the next page is only fetched once the current page is consumed.
+    pub fn capture_stream_with<'a>(
+        &'a self,
+        input: &CaptureInput,
+        config: &'a CaptureConfig,
+        transport: &'a dyn Fn(&PollConfig, &PollState) -> Result<String, CaptureError>,
+    ) -> impl Stream<Item = Result<serde_json::Value, CaptureError>> + 'a {
+        let poll_config = parse_poll_config(input, config);
+        let (state_key, state) = self.load_state(&poll_config, config);
+        let missing_endpoint = poll_config.endpoint.is_empty();
+        let pages = PollPages {
+            provider: self,
+            config,
+            transport,
+            poll_config,
+            state_key,
+            state,
+            page: Vec::new().into_iter(),
+            pending_cursor: None,
+            error: missing_endpoint.then_some(CaptureError::MissingEndpoint),
+            finished: missing_endpoint,
+        };
+        futures::stream::unfold(pages, |mut pages| async move {
+            pages.next_item().map(|item| (item, pages))
+        })
+    }
+
+    /// Poll state key and previous state for an endpoint. An explicit cursor
+    /// wins; otherwise resume from the persisted one.
+    fn load_state(&self, poll_config: &PollConfig, config: &CaptureConfig) -> (String, PollState) {
+        let state_key = compute_hash(&format!("{}{}", poll_config.endpoint, poll_config.method));
+        let mut previous_state = self.state_store.lock().unwrap()
+            .get(&state_key).cloned().unwrap_or_default();
+
+        let explicit_cursor = config.options.as_ref()
+            .and_then(|o| o.get("cursor"))
+            .and_then(|v| v.as_str())
+            .map(String::from);
+        if let Some(cursor) = explicit_cursor.or_else(|| {
+            config.cursor_store.as_ref().and_then(|store| store.load(&poll_config.endpoint))
+        }) {
+            previous_state.last_cursor = Some(cursor);
+        }
+        (state_key, previous_state)
+    }
+
     pub fn supports(&self, input: &CaptureInput) -> bool {
         input.url.as_ref().map_or(false, |u| {
             u.starts_with("http://") || u.starts_with("https://")
@@ -305,6 +370,192 @@ impl ApiPollCaptureProvider {
     }
 }
 
+/// Paging state behind `capture_stream`. A page's cursor is saved once all
+/// of its items have been yielded, so a consumer that stops early resumes
+/// from the first unfinished page on the next poll.
+struct PollPages<'a> {
+    provider: &'a ApiPollCaptureProvider,
+    config: &'a CaptureConfig,
+    transport: &'a dyn Fn(&PollConfig, &PollState) -> Result<String, CaptureError>,
+    poll_config: PollConfig,
+    state_key: String,
+    state: PollState,
+    page: std::vec::IntoIter<serde_json::Value>,
+    pending_cursor: Option<String>,
+    error: Option<CaptureError>,
+    finished: bool,
+}
+
+impl PollPages<'_> {
+    fn save_state(&self) {
+        self.provider.state_store.lock().unwrap().insert(self.state_key.clone(), self.state.clone());
+    }
+
+    fn fetch_page(&mut self) -> Result<(), CaptureError> {
+        let response_body = (self.transport)(&self.poll_config, &self.state)
+            .map_err(|e| CaptureError::FetchError(e.to_string()))?;
+        let parsed: serde_json::Value = serde_json::from_str(&response_body)
+            .unwrap_or(serde_json::Value::String(response_body.clone()));
+        self.state.last_poll_at = Some(chrono::Utc::now().to_rfc3339());
+
+        let items = match self.poll_config.delta_strategy {
+            DeltaStrategy::Hash => {
+                self.finished = true;
+                let (changed, hash) = detect_changes_hash(&response_body, self.state.last_hash.as_deref());
+                self.state.last_hash = Some(hash);
+                if changed { vec![parsed] } else { Vec::new() }
+            }
+            DeltaStrategy::Timestamp => {
+                self.finished = true;
+                let (_, items, latest) = detect_changes_timestamp(
+                    &parsed, self.poll_config.items_path.as_deref(),
+                    &self.poll_config.timestamp_field, self.state.last_timestamp.as_deref(),
+                );
+                if latest.is_some() { self.state.last_timestamp = latest; }
+                items
+            }
+            DeltaStrategy::Cursor => {
+                let (items, next_cursor) = detect_changes_cursor(
+                    &parsed, &self.poll_config.cursor_field, self.poll_config.items_path.as_deref(),
+                );
+                self.finished = items.is_empty()
+                    || next_cursor.is_none()
+                    || next_cursor == self.state.last_cursor;
+                self.pending_cursor = next_cursor;
+                items
+            }
+        };
+
+        self.save_state();
+        self.page = items.into_iter();
+        Ok(())
+    }
+
+    fn next_item(&mut self) -> Option<Result<serde_json::Value, CaptureError>> {
+        loop {
+            if let Some(error) = self.error.take() {
+                return Some(Err(error));
+            }
+            if let Some(item) = self.page.next() {
+                return Some(Ok(item));
+            }
+
+            // Current page fully consumed: commit its cursor
+            if let Some(cursor) = self.pending_cursor.take() {
+                if let Some(store) = self.config.cursor_store.as_ref() {
+                    store.save(&self.poll_config.endpoint, &cursor);
+                }
+                self.state.last_cursor = Some(cursor);
+                self.save_state();
+            }
+            if self.finished {
+                return None;
+            }
+            if let Err(error) = self.fetch_page() {
+                self.finished = true;
+                return Some(Err(error));
+            }
+        }
+    }
+}
+
 fn http_request(_config: &PollConfig, _state: &PollState) -> Result<String, CaptureError> {
     Err(CaptureError::FetchError("HTTP client not configured".to_string()))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cursor_config(store: Arc<dyn CaptureCursorStore>) -> CaptureConfig {
+        let mut options = HashMap::new();
+        options.insert("deltaStrategy".to_string(), serde_json::json!("cursor"));
+        options.insert("itemsPath".to_string(), serde_json::json!("items"));
+        CaptureConfig { mode: "api_poll".to_string(), options: Some(options), cursor_store: Some(store) }
+    }
+
+    #[test]
+    fn second_poll_resumes_from_persisted_cursor() {
+        let store: Arc<dyn CaptureCursorStore> = Arc::new(InMemoryCursorStore::new());
+        let input = CaptureInput {
+            url: Some("https://api.example.com/events".to_string()),
+            file: None,
+            email: None,
+            share_data: None,
+        };
+        let seen_cursors = Mutex::new(Vec::new());
+        let new_events = Mutex::new(false);
+        let transport = |_: &PollConfig, state: &PollState| -> Result<String, CaptureError> {
+            seen_cursors.lock().unwrap().push(state.last_cursor.clone());
+            Ok(match (state.last_cursor.as_deref(), *new_events.lock().unwrap()) {
+                (None, _) => r#"{"items": [{"id": 1}, {"id": 2}], "next_cursor": "c2"}"#,
+                (Some("c2"), _) => r#"{"items": [{"id": 3}], "next_cursor": "c3"}"#,
+                (Some("c3"), true) => r#"{"items": [{"id": 4}], "next_cursor": "c4"}"#,
+                _ => r#"{"items": []}"#,
+            }.to_string())
+        };
+
+        // Separate provider instances so only the store carries state between polls
+        let first = ApiPollCaptureProvider::new()
+            .capture_with(&input, &cursor_config(store.clone()), &transport).unwrap();
+        assert!(first.content.contains("\"id\": 2"));
+        assert!(first.content.contains("\"id\": 3"));
+        assert_eq!(store.load("https://api.example.com/events").as_deref(), Some("c3"));
+
+        *new_events.lock().unwrap() = true;
+        let second = ApiPollCaptureProvider::new()
+            .capture_with(&input, &cursor_config(store.clone()), &transport).unwrap();
+        assert!(second.content.contains("\"id\": 4"));
+        assert!(!second.content.contains("\"id\": 3"));
+        assert_eq!(store.load("https://api.example.com/events").as_deref(), Some("c4"));
+
+        let cursors = |names: &[Option<&str>]| names.iter().map(|c| c.map(String::from)).collect::<Vec<_>>();
+        assert_eq!(
+            *seen_cursors.lock().unwrap(),
+            cursors(&[None, Some("c2"), Some("c3"), Some("c3"), Some("c4")])
+        );
+    }
+
+    #[test]
+    fn stream_yields_items_before_fetching_next_page() {
+        let store: Arc<dyn CaptureCursorStore> = Arc::new(InMemoryCursorStore::new());
+        let input = CaptureInput {
+            url: Some("https://api.example.com/events".to_string()),
+            file: None,
+            email: None,
+            share_data: None,
+        };
+        let fetches = Mutex::new(0usize);
+        let transport = |_: &PollConfig, state: &PollState| -> Result<String, CaptureError> {
+            *fetches.lock().unwrap() += 1;
+            Ok(match state.last_cursor.as_deref() {
+                None => r#"{"items": [{"id": 1}, {"id": 2}], "next_cursor": "c2"}"#,
+                Some(_) => r#"{"items": [{"id": 3}]}"#,
+            }.to_string())
+        };
+
+        let config = cursor_config(store.clone());
+        let provider = ApiPollCaptureProvider::new();
+        let mut items = std::pin::pin!(provider.capture_stream_with(&input, &config, &transport));
+        let mut next = || futures::executor::block_on(items.next());
+
+        assert_eq!(next().unwrap().unwrap(), serde_json::json!({"id": 1}));
+        assert_eq!(next().unwrap().unwrap(), serde_json::json!({"id": 2}));
+        assert_eq!(*fetches.lock().unwrap(), 1);
+        assert_eq!(store.load("https://api.example.com/events"), None);
+
+        assert_eq!(next().unwrap().unwrap(), serde_json::json!({"id": 3}));
+        assert_eq!(*fetches.lock().unwrap(), 2);
+        assert_eq!(store.load("https://api.example.com/events").as_deref(), Some("c2"));
+        assert!(next().is_none());
+    }
+
+    #[test]
+    fn stream_reports_missing_endpoint() {
+        let input = CaptureInput { url: None, file: None, email: None, share_data: None };
+        let config = CaptureConfig { mode: "api_poll".to_string(), options: None, cursor_store: None };
+        let provider = ApiPollCaptureProvider::new();
+        let items = futures::executor::block_on(provider.capture_stream(&input, &config).collect::<Vec<_>>());
+        assert!(matches!(items.as_slice(), [Err(CaptureError::MissingEndpoint)]));
+    }
+}
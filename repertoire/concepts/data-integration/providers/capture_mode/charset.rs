@@ -0,0 +1,74 @@
+// Data Integration Kit - Body Charset Decoding
+// Decodes fetched or uploaded bytes using the charset declared in the
+// Content-Type header or an HTML <meta> tag, so legacy-encoded pages
+// (Windows-1252, ISO-8859-1, Shift_JIS) are not mangled by lossy UTF-8.
+
+use std::sync::LazyLock;
+
+static HEADER_CHARSET: LazyLock<regex::Regex> =
+    LazyLock::new(|| regex::Regex::new(r#"(?i)charset\s*=\s*["']?([A-Za-z0-9_.:-]+)"#).unwrap());
+
+static META_CHARSET: LazyLock<regex::Regex> =
+    LazyLock::new(|| regex::Regex::new(r#"(?i)<meta[^>]+charset\s*=\s*["']?([A-Za-z0-9_.:-]+)"#).unwrap());
+
+/// How much of an HTML body is searched for a `<meta>` charset.
+const META_SCAN_BYTES: usize = 1024;
+
+/// The `charset` parameter of a Content-Type header value.
+pub fn charset_from_content_type(content_type: &str) -> Option<String> {
+    HEADER_CHARSET.captures(content_type).map(|caps| caps[1].to_string())
+}
+
+fn charset_from_meta(head: &str) -> Option<String> {
+    META_CHARSET.captures(head).map(|caps| caps[1].to_string())
+}
+
+/// Decode a text body using the charset declared in the Content-Type header,
+/// then a `<meta charset>`/`<meta http-equiv>` tag for HTML, falling back to UTF-8.
+/// A byte-order mark always takes precedence.
+pub fn decode_body(bytes: &[u8], content_type_header: Option<&str>, html: bool) -> String {
+    let declared = content_type_header.and_then(charset_from_content_type).or_else(|| {
+        if html {
+            let head = String::from_utf8_lossy(&bytes[..std::cmp::min(bytes.len(), META_SCAN_BYTES)]);
+            charset_from_meta(&head)
+        } else {
+            None
+        }
+    });
+
+    let encoding = declared
+        .and_then(|label| encoding_rs::Encoding::for_label(label.as_bytes()))
+        .unwrap_or(encoding_rs::UTF_8);
+    let (decoded, _, _) = encoding.decode(bytes);
+    decoded.into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // "It’s" with the Windows-1252 right single quotation mark (0x92)
+    const WINDOWS_1252_TEXT: &[u8] = b"It\x92s here";
+
+    #[test]
+    fn decode_body_uses_content_type_charset() {
+        let decoded = decode_body(WINDOWS_1252_TEXT, Some("text/plain; charset=windows-1252"), false);
+        assert_eq!(decoded, "It\u{2019}s here");
+    }
+
+    #[test]
+    fn decode_body_uses_html_meta_charset() {
+        let mut html = b"<html><head><meta charset=\"windows-1252\"></head><body>".to_vec();
+        html.extend_from_slice(WINDOWS_1252_TEXT);
+        html.extend_from_slice(b"</body></html>");
+        assert!(decode_body(&html, None, true).contains("It\u{2019}s here"));
+
+        let http_equiv = b"<meta http-equiv=\"Content-Type\" content=\"text/html; charset=ISO-8859-1\">caf\xe9";
+        assert!(decode_body(http_equiv, None, true).ends_with("caf\u{e9}"));
+    }
+
+    #[test]
+    fn decode_body_falls_back_to_utf8() {
+        assert_eq!(decode_body("It\u{2019}s here".as_bytes(), None, false), "It\u{2019}s here");
+    }
+}
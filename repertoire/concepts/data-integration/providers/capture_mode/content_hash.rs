@@ -0,0 +1,58 @@
+// Data Integration Kit - Capture Content Hashing
+// SHA-256 hashes of normalized captured content, so re-captures of an
+// unchanged source hash identically and duplicates can be detected.
+
+use std::collections::HashMap;
+
+/// Collapse whitespace runs to single spaces and trim the ends.
+pub fn normalize_whitespace(content: &str) -> String {
+    content.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Lowercase hex SHA-256 of `text`, taken as already normalized.
+pub fn sha256_hex(text: &str) -> String {
+    use sha2::{Digest, Sha256};
+    let digest = Sha256::digest(text.as_bytes());
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Hash of `content` with its whitespace normalized.
+pub fn compute_content_hash(content: &str) -> String {
+    sha256_hex(&normalize_whitespace(content))
+}
+
+/// `{"contentHash": ...}` for a capture's extra metadata when the
+/// `contentHash` option is set; `hash` only runs in that case.
+pub fn content_hash_extra(
+    options: Option<&HashMap<String, serde_json::Value>>,
+    hash: impl FnOnce() -> String,
+) -> Option<HashMap<String, serde_json::Value>> {
+    let enabled = options
+        .and_then(|o| o.get("contentHash"))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    if !enabled { return None; }
+    let mut extra = HashMap::new();
+    extra.insert("contentHash".to_string(), serde_json::Value::String(hash()));
+    Some(extra)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn content_hash_ignores_whitespace_and_is_opt_in() {
+        let mut options = HashMap::new();
+        options.insert("contentHash".to_string(), serde_json::json!(true));
+        let extra = |content: &str| content_hash_extra(Some(&options), || compute_content_hash(content)).unwrap();
+
+        let first = extra("First paragraph.\n\nSecond paragraph.");
+        let second = extra("First paragraph.  \n\n  Second paragraph.\n");
+        let edited = extra("First paragraph.\n\nThird paragraph.");
+
+        assert_eq!(first["contentHash"], second["contentHash"]);
+        assert_ne!(first["contentHash"], edited["contentHash"]);
+        assert!(content_hash_extra(None, || unreachable!()).is_none());
+    }
+}
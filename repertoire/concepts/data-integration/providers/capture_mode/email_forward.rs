@@ -3,6 +3,8 @@
 
 use std::collections::HashMap;
 
+use super::charset::decode_body;
+
 pub const PROVIDER_ID: &str = "email_forward";
 pub const PLUGIN_TYPE: &str = "capture_mode";
 
@@ -114,30 +116,47 @@ fn parse_headers(raw: &str) -> EmailHeaders {
     }
 }
 
-fn decode_quoted_printable(input: &str) -> String {
+fn decode_quoted_printable(input: &str) -> Vec<u8> {
     let soft_removed = regex::Regex::new(r"=\r?\n").unwrap().replace_all(input, "");
-    let hex_re = regex::Regex::new(r"=([0-9A-Fa-f]{2})").unwrap();
-    hex_re.replace_all(&soft_removed, |caps: &regex::Captures| {
-        let byte = u8::from_str_radix(&caps[1], 16).unwrap_or(b'?');
-        String::from(byte as char)
-    }).to_string()
+    let bytes = soft_removed.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'=')
+            .then(|| bytes.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    decoded
 }
 
-fn decode_base64(input: &str) -> String {
+fn decode_base64(input: &str) -> Vec<u8> {
     use base64::Engine;
     let cleaned: String = input.chars().filter(|c| !c.is_whitespace()).collect();
     base64::engine::general_purpose::STANDARD.decode(&cleaned)
-        .ok()
-        .and_then(|bytes| String::from_utf8(bytes).ok())
-        .unwrap_or_else(|| input.to_string())
+        .unwrap_or_else(|_| input.as_bytes().to_vec())
 }
 
-fn decode_body(body: &str, encoding: Option<&str>) -> String {
-    match encoding.map(|e| e.to_lowercase()).as_deref() {
+/// Undo the transfer encoding, then decode the bytes with the charset from
+/// `content_type`. Unencoded bodies are already text and pass through.
+fn decode_part_body(body: &str, encoding: Option<&str>, content_type: &str) -> String {
+    let bytes = match encoding.map(|e| e.to_lowercase()).as_deref() {
         Some("quoted-printable") => decode_quoted_printable(body),
         Some("base64") => decode_base64(body),
-        _ => body.to_string(),
-    }
+        _ => return body.to_string(),
+    };
+    decode_body(&bytes, Some(content_type), content_type.to_ascii_lowercase().starts_with("text/html"))
 }
 
 fn parse_multipart(body: &str, boundary: &str) -> Vec<MimePart> {
@@ -171,9 +190,8 @@ fn parse_multipart(body: &str, boundary: &str) -> Vec<MimePart> {
             }
         }
 
-        let ct = part_headers.get("content-type")
-            .map(|s| s.split(';').next().unwrap_or("text/plain").trim().to_string())
-            .unwrap_or_else(|| "text/plain".to_string());
+        let full_ct = part_headers.get("content-type").cloned().unwrap_or_else(|| "text/plain".to_string());
+        let ct = full_ct.split(';').next().unwrap_or("text/plain").trim().to_string();
         let encoding = part_headers.get("content-transfer-encoding").cloned();
         let disposition = part_headers.get("content-disposition").cloned().unwrap_or_default();
         let filename = regex::Regex::new(r#"(?i)filename=["']?([^"';\s]+)["']?"#).ok()
@@ -183,7 +201,7 @@ fn parse_multipart(body: &str, boundary: &str) -> Vec<MimePart> {
         parts.push(MimePart {
             content_type: ct,
             encoding: encoding.clone(),
-            body: decode_body(body_section, encoding.as_deref()),
+            body: decode_part_body(body_section, encoding.as_deref(), &full_ct),
             filename,
         });
     }
@@ -227,7 +245,7 @@ impl EmailForwardCaptureProvider {
                 }
             }
         } else {
-            text_content = decode_body(body_section, headers.transfer_encoding.as_deref());
+            text_content = decode_part_body(body_section, headers.transfer_encoding.as_deref(), &headers.content_type);
         }
 
         let primary = if text_content.is_empty() { &html_content } else { &text_content };
@@ -274,3 +292,20 @@ impl EmailForwardCaptureProvider {
         input.email.as_ref().map_or(false, |e| !e.is_empty())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_transfer_encoded_parts_with_their_charset() {
+        let email = "From: a@example.com\nSubject: Hi\nContent-Type: multipart/alternative; boundary=\"b1\"\n\n\
+--b1\nContent-Type: text/plain; charset=windows-1252\nContent-Transfer-Encoding: quoted-printable\n\nIt=92s here\n\
+--b1\nContent-Type: text/html; charset=utf-8\nContent-Transfer-Encoding: base64\n\nPHA+SXTigJlzIGhlcmU8L3A+\n--b1--\n";
+        let parts = parse_multipart(split_headers_body(email).1, "b1");
+        assert_eq!(parts[0].body, "It\u{2019}s here");
+        assert_eq!(parts[1].body, "<p>It\u{2019}s here</p>");
+
+        assert_eq!(decode_quoted_printable("caf=E9 =3D ok=\n!"), b"caf\xe9 = ok!");
+    }
+}
@@ -3,6 +3,8 @@
 
 use std::collections::HashMap;
 
+use super::charset::decode_body;
+
 pub const PROVIDER_ID: &str = "file_upload";
 pub const PLUGIN_TYPE: &str = "capture_mode";
 
@@ -90,8 +92,8 @@ fn detect_mime_type(data: &[u8]) -> (&'static str, &'static str) {
         }
     }
 
-    // Check text-based formats
-    let header = std::str::from_utf8(&data[..std::cmp::min(data.len(), 512)]).unwrap_or("");
+    // Check text-based formats (lossy so legacy-encoded markup is still recognized)
+    let header = String::from_utf8_lossy(&data[..std::cmp::min(data.len(), 512)]);
     let trimmed = header.trim_start();
     if trimmed.starts_with("<?xml") || trimmed.starts_with("<svg") {
         return ("image/svg+xml", "svg");
@@ -165,7 +167,13 @@ impl FileUploadCaptureProvider {
         let file_data = input.file.as_ref().ok_or(CaptureError::MissingFile)?;
         if file_data.is_empty() { return Err(CaptureError::MissingFile); }
 
-        let (mime_type, extension) = detect_mime_type(file_data);
+        let declared_type = config.options.as_ref()
+            .and_then(|o| o.get("contentType"))
+            .and_then(|v| v.as_str());
+        let (mut mime_type, mut extension) = detect_mime_type(file_data);
+        if mime_type == "application/octet-stream" && declared_type.is_some_and(|ct| ct.starts_with("text/")) {
+            (mime_type, extension) = ("text/plain", "txt");
+        }
         let file_size = file_data.len();
         let filename = config.options.as_ref()
             .and_then(|o| o.get("filename"))
@@ -190,7 +198,7 @@ impl FileUploadCaptureProvider {
 
         let is_text = mime_type.starts_with("text/") || mime_type == "application/json";
         let content = if is_text && file_size < 1048576 {
-            String::from_utf8_lossy(file_data).to_string()
+            decode_body(file_data, declared_type, mime_type == "text/html")
         } else {
             summary.join("\n")
         };
@@ -219,3 +227,23 @@ impl FileUploadCaptureProvider {
         input.file.as_ref().map_or(false, |f| !f.is_empty())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // "It’s" with the Windows-1252 right single quotation mark (0x92)
+    const WINDOWS_1252_TEXT: &[u8] = b"It\x92s here";
+
+    #[test]
+    fn capture_decodes_declared_legacy_text() {
+        let input = CaptureInput { url: None, file: Some(WINDOWS_1252_TEXT.to_vec()), email: None, share_data: None };
+        let mut options = HashMap::new();
+        options.insert("contentType".to_string(), serde_json::json!("text/plain; charset=windows-1252"));
+        let config = CaptureConfig { mode: "file_upload".to_string(), options: Some(options) };
+
+        let item = FileUploadCaptureProvider::new().capture(&input, &config).unwrap();
+        assert_eq!(item.source_metadata.content_type, "text/plain");
+        assert_eq!(item.content, "It\u{2019}s here");
+    }
+}
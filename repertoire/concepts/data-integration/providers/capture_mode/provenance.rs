@@ -0,0 +1,112 @@
+// Data Integration Kit - Capture Provenance
+// Records where a captured item came from and which transforms were applied to
+// it, so imported entities can be traced back to their source.
+//
+// Provenance records also feed the `migration_lookup` transform: build its
+// `provenanceMap` option with `provenance_map`, pairing each record with the id
+// the item was stored under, and old source ids then resolve to the new ids.
+
+use serde::{Deserialize, Serialize};
+
+use super::content_hash::compute_content_hash;
+use super::registry::CaptureItem;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProvenanceRecord {
+    /// Source URL, or the source-side id when the item has no URL.
+    pub source: String,
+    pub provider_id: String,
+    pub captured_at: String,
+    /// Transform ids in the order they were applied.
+    pub transforms: Vec<String>,
+    /// SHA-256 of the whitespace-normalized content.
+    pub content_hash: String,
+}
+
+impl ProvenanceRecord {
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or(serde_json::Value::Null)
+    }
+
+    /// The `provenanceMap` key `migration_lookup` looks up for this source.
+    pub fn lookup_key(&self, entity_type: Option<&str>) -> String {
+        match entity_type {
+            Some(entity_type) if !entity_type.is_empty() => format!("{}:{}", entity_type, self.source),
+            _ => self.source.clone(),
+        }
+    }
+}
+
+/// Build the provenance record for `item` after `applied_transforms` ran.
+pub fn record_provenance(item: &CaptureItem, applied_transforms: &[String]) -> ProvenanceRecord {
+    let meta = &item.source_metadata;
+    let extra = |key: &str| {
+        meta.extra.as_ref()
+            .and_then(|extra| extra.get(key))
+            .and_then(|v| v.as_str())
+            .map(String::from)
+    };
+
+    ProvenanceRecord {
+        source: meta.url.clone()
+            .or_else(|| extra("sourceId"))
+            .unwrap_or_else(|| meta.title.clone()),
+        provider_id: meta.source.clone().unwrap_or_else(|| "unknown".to_string()),
+        captured_at: meta.captured_at.clone(),
+        transforms: applied_transforms.to_vec(),
+        content_hash: extra("contentHash").unwrap_or_else(|| compute_content_hash(&item.content)),
+    }
+}
+
+/// A `provenanceMap` for `migration_lookup`, keyed by source (prefixed with
+/// `entity_type` when given) and resolving to the id each item was stored as.
+pub fn provenance_map<'a>(
+    entries: impl IntoIterator<Item = (&'a ProvenanceRecord, &'a str)>,
+    entity_type: Option<&str>,
+) -> serde_json::Map<String, serde_json::Value> {
+    entries.into_iter()
+        .map(|(record, new_id)| (record.lookup_key(entity_type), serde_json::Value::String(new_id.to_string())))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::registry::SourceMetadata;
+
+    fn item() -> CaptureItem {
+        CaptureItem {
+            content: "Hello   provenance\n".to_string(),
+            source_metadata: SourceMetadata {
+                title: "Hello".to_string(),
+                url: Some("https://example.com/hello".to_string()),
+                captured_at: "2024-05-01T12:00:00Z".to_string(),
+                content_type: "text/html".to_string(),
+                author: None,
+                tags: None,
+                source: Some("web_article".to_string()),
+                extra: None,
+            },
+            raw_data: None,
+        }
+    }
+
+    #[test]
+    fn records_provider_and_transform_chain() {
+        let transforms = vec!["html_to_markdown".to_string(), "truncate".to_string()];
+        let record = record_provenance(&item(), &transforms);
+
+        assert_eq!(record.provider_id, "web_article");
+        assert_eq!(record.transforms, transforms);
+        assert_eq!(record.source, "https://example.com/hello");
+        assert_eq!(record.content_hash, compute_content_hash("Hello provenance"));
+
+        let json = record.to_json();
+        assert_eq!(json["providerId"], "web_article");
+        assert_eq!(json["transforms"], serde_json::json!(["html_to_markdown", "truncate"]));
+
+        let map = provenance_map([(&record, "node-42")], Some("article"));
+        assert_eq!(map["article:https://example.com/hello"], "node-42");
+    }
+}
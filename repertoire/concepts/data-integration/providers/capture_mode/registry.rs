@@ -0,0 +1,465 @@
+// Data Integration Kit - Capture Provider Registry
+// Maps capture mode ids to provider factories so applications can add their own
+// capture sources (e.g. Notion or Slack) alongside the nine built-in providers.
+
+use std::collections::HashMap;
+
+pub const PLUGIN_TYPE: &str = "capture_mode";
+
+#[derive(Debug, Clone, Default)]
+pub struct CaptureInput {
+    pub url: Option<String>,
+    pub file: Option<Vec<u8>>,
+    pub email: Option<String>,
+    pub share_data: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CaptureConfig {
+    pub mode: String,
+    pub options: Option<HashMap<String, serde_json::Value>>,
+    /// Check robots.txt (and honor Crawl-delay) before web providers fetch.
+    pub respect_robots: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct SourceMetadata {
+    pub title: String,
+    pub url: Option<String>,
+    pub captured_at: String,
+    pub content_type: String,
+    pub author: Option<String>,
+    pub tags: Option<Vec<String>>,
+    pub source: Option<String>,
+    pub extra: Option<HashMap<String, serde_json::Value>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CaptureItem {
+    pub content: String,
+    pub source_metadata: SourceMetadata,
+    pub raw_data: Option<Vec<u8>>,
+}
+
+#[derive(Debug)]
+pub enum CaptureError {
+    UnknownProvider(String),
+    ProviderFailed { provider: String, message: String },
+    Disallowed { url: String, user_agent: String },
+}
+
+impl std::fmt::Display for CaptureError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CaptureError::UnknownProvider(id) => write!(f, "Unknown capture provider: {}", id),
+            CaptureError::ProviderFailed { provider, message } => write!(f, "{} capture failed: {}", provider, message),
+            CaptureError::Disallowed { url, user_agent } => write!(f, "robots.txt disallows {} for {}", url, user_agent),
+        }
+    }
+}
+
+impl std::error::Error for CaptureError {}
+
+/// Interface every capture provider exposes through the registry.
+pub trait CaptureModePlugin: Send + Sync {
+    fn id(&self) -> &str;
+
+    fn display_name(&self) -> &str;
+
+    fn capture(&self, input: &CaptureInput, config: &CaptureConfig) -> Result<CaptureItem, CaptureError>;
+
+    fn supports(&self, input: &CaptureInput) -> bool;
+
+    /// How well suited the provider is to an input it supports; higher wins
+    /// when several providers support the same input.
+    fn specificity(&self, _input: &CaptureInput) -> u32 {
+        0
+    }
+}
+
+pub type CaptureProviderFactory = Box<dyn Fn() -> Box<dyn CaptureModePlugin> + Send + Sync>;
+
+/// Capture providers by id, kept in registration order; `resolve` returns the
+/// first registered provider that supports an input.
+#[derive(Default)]
+pub struct CaptureRegistry {
+    factories: Vec<(String, CaptureProviderFactory)>,
+}
+
+impl CaptureRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a provider factory. Re-registering an id replaces the earlier
+    /// factory but keeps its position.
+    pub fn register<F>(&mut self, id: impl Into<String>, factory: F)
+    where
+        F: Fn() -> Box<dyn CaptureModePlugin> + Send + Sync + 'static,
+    {
+        let id = id.into();
+        match self.factories.iter_mut().find(|(existing, _)| *existing == id) {
+            Some(entry) => entry.1 = Box::new(factory),
+            None => self.factories.push((id, Box::new(factory))),
+        }
+    }
+
+    pub fn create(&self, id: &str) -> Option<Box<dyn CaptureModePlugin>> {
+        self.factories.iter()
+            .find(|(existing, _)| existing == id)
+            .map(|(_, factory)| factory())
+    }
+
+    pub fn ids(&self) -> Vec<&str> {
+        self.factories.iter().map(|(id, _)| id.as_str()).collect()
+    }
+
+    pub fn resolve(&self, input: &CaptureInput) -> Option<Box<dyn CaptureModePlugin>> {
+        self.factories.iter()
+            .map(|(_, factory)| factory())
+            .find(|provider| provider.supports(input))
+    }
+
+    /// Resolve with an explicit preference list: the first preferred id that
+    /// supports the input wins. Otherwise the supporting provider with the
+    /// highest `specificity` is chosen, earlier registration breaking ties.
+    pub fn resolve_ranked(&self, input: &CaptureInput, preferences: &[&str]) -> Option<Box<dyn CaptureModePlugin>> {
+        let preferred = preferences.iter()
+            .filter_map(|id| self.create(id))
+            .find(|provider| provider.supports(input));
+        if preferred.is_some() {
+            return preferred;
+        }
+
+        let mut best: Option<(u32, Box<dyn CaptureModePlugin>)> = None;
+        for (_, factory) in &self.factories {
+            let provider = factory();
+            if !provider.supports(input) {
+                continue;
+            }
+            let score = provider.specificity(input);
+            if best.as_ref().is_none_or(|(top, _)| score > *top) {
+                best = Some((score, provider));
+            }
+        }
+        best.map(|(_, provider)| provider)
+    }
+}
+
+/// Content type implied by a URL's file extension; extensionless paths and
+/// page extensions are assumed to be HTML.
+fn guess_url_content_type(url: &str) -> &'static str {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    let path = path.split_once("://").map_or(path, |(_, rest)| rest);
+    let last_segment = path.split_once('/').map_or("", |(_, p)| p).rsplit('/').next().unwrap_or("");
+    let extension = last_segment.rsplit_once('.').map(|(_, ext)| ext.to_ascii_lowercase());
+
+    match extension.as_deref() {
+        Some("pdf") => "application/pdf",
+        Some("json") => "application/json",
+        Some("xml" | "rss" | "atom") => "application/xml",
+        Some("png" | "jpg" | "jpeg" | "gif" | "webp" | "svg") => "image",
+        Some("mp3" | "wav" | "ogg" | "mp4" | "webm" | "mov") => "media",
+        Some("zip" | "gz" | "tar" | "csv" | "txt" | "doc" | "docx" | "xls" | "xlsx") => "application/octet-stream",
+        _ => "text/html",
+    }
+}
+
+fn is_html_url(input: &CaptureInput) -> bool {
+    input.url.as_deref().is_some_and(|url| guess_url_content_type(url) == "text/html")
+}
+
+/// Refuse URLs that robots.txt disallows when the config opts in.
+fn enforce_robots(input: &CaptureInput, config: &CaptureConfig) -> Result<(), CaptureError> {
+    match input.url.as_deref() {
+        Some(url) if config.respect_robots => super::robots::RobotsCache::global()
+            .check(url)
+            .map_err(|blocked| CaptureError::Disallowed { url: blocked.url, user_agent: blocked.user_agent }),
+        _ => Ok(()),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Built-in providers
+// ---------------------------------------------------------------------------
+
+/// Wrap a built-in provider, converting between its module-local capture
+/// types and the registry's. `$config` builds the provider's config from
+/// `(mode, options)`, `$extra` reads metadata extras, `$raw` converts raw data
+/// and `$specificity` ranks the provider for an input. Providers with `web: true`
+/// fetch URLs and so are subject to robots.txt.
+macro_rules! builtin_provider {
+    (
+        $adapter:ident, $module:ident::$provider:ident, $id:literal, $name:literal, web: $web:literal,
+        config: |$mode:ident, $options:ident| $config:expr,
+        extra: |$meta:ident| $extra:expr,
+        raw: |$data:ident| $raw:expr,
+        specificity: |$input:ident| $specificity:expr $(,)?
+    ) => {
+        struct $adapter(super::$module::$provider);
+
+        impl $adapter {
+            fn input(input: &CaptureInput) -> super::$module::CaptureInput {
+                super::$module::CaptureInput {
+                    url: input.url.clone(),
+                    file: input.file.clone(),
+                    email: input.email.clone(),
+                    share_data: input.share_data.clone(),
+                }
+            }
+        }
+
+        impl CaptureModePlugin for $adapter {
+            fn id(&self) -> &str { $id }
+            fn display_name(&self) -> &str { $name }
+
+            fn capture(&self, input: &CaptureInput, config: &CaptureConfig) -> Result<CaptureItem, CaptureError> {
+                if $web {
+                    enforce_robots(input, config)?;
+                }
+                let ($mode, $options) = (config.mode.clone(), config.options.clone());
+                let item = self.0.capture(&Self::input(input), &$config)
+                    .map_err(|e| CaptureError::ProviderFailed { provider: $id.to_string(), message: e.to_string() })?;
+                let $meta = &item.source_metadata;
+                let extra = $extra;
+                let $data = item.raw_data;
+                Ok(CaptureItem {
+                    content: item.content,
+                    source_metadata: SourceMetadata {
+                        title: $meta.title.clone(),
+                        url: $meta.url.clone(),
+                        captured_at: $meta.captured_at.clone(),
+                        content_type: $meta.content_type.clone(),
+                        author: $meta.author.clone(),
+                        tags: $meta.tags.clone(),
+                        source: $meta.source.clone(),
+                        extra,
+                    },
+                    raw_data: $raw,
+                })
+            }
+
+            fn supports(&self, input: &CaptureInput) -> bool {
+                self.0.supports(&Self::input(input))
+            }
+
+            fn specificity(&self, $input: &CaptureInput) -> u32 {
+                $specificity
+            }
+        }
+    };
+}
+
+builtin_provider!(
+    WebArticle, web_article::WebArticleCaptureProvider, "web_article", "Web Article", web: true,
+    config: |mode, options| super::web_article::CaptureConfig { mode, options },
+    extra: |meta| meta.extra.clone(),
+    raw: |data| data.map(String::into_bytes),
+    specificity: |input| if is_html_url(input) { 3 } else { 0 },
+);
+builtin_provider!(
+    WebFullPage, web_full_page::WebFullPageCaptureProvider, "web_full_page", "Web Full Page", web: true,
+    config: |mode, options| super::web_full_page::CaptureConfig { mode, options },
+    extra: |meta| meta.extra.clone(),
+    raw: |data| data.map(String::into_bytes),
+    specificity: |input| if is_html_url(input) { 2 } else { 0 },
+);
+builtin_provider!(
+    WebBookmark, web_bookmark::WebBookmarkCaptureProvider, "web_bookmark", "Web Bookmark", web: true,
+    config: |mode, options| super::web_bookmark::CaptureConfig { mode, options },
+    extra: |_meta| None,
+    raw: |data| data.map(String::into_bytes),
+    specificity: |_input| 1,
+);
+builtin_provider!(
+    WebScreenshot, web_screenshot::WebScreenshotCaptureProvider, "web_screenshot", "Web Screenshot", web: true,
+    config: |mode, options| super::web_screenshot::CaptureConfig { mode, options },
+    extra: |_meta| None,
+    raw: |data| data,
+    specificity: |_input| 0,
+);
+builtin_provider!(
+    WebMarkdown, web_markdown::WebMarkdownCaptureProvider, "web_markdown", "Web Markdown", web: true,
+    config: |mode, options| super::web_markdown::CaptureConfig { mode, options },
+    extra: |meta| meta.extra.clone(),
+    raw: |data| data.map(String::into_bytes),
+    specificity: |input| if is_html_url(input) { 2 } else { 0 },
+);
+builtin_provider!(
+    FileUpload, file_upload::FileUploadCaptureProvider, "file_upload", "File Upload", web: false,
+    config: |mode, options| super::file_upload::CaptureConfig { mode, options },
+    extra: |_meta| None,
+    raw: |data| data,
+    specificity: |_input| 0,
+);
+builtin_provider!(
+    EmailForward, email_forward::EmailForwardCaptureProvider, "email_forward", "Email Forward", web: false,
+    config: |mode, options| super::email_forward::CaptureConfig { mode, options },
+    extra: |_meta| None,
+    raw: |data| data.map(String::into_bytes),
+    specificity: |_input| 0,
+);
+builtin_provider!(
+    ShareIntent, share_intent::ShareIntentCaptureProvider, "share_intent", "Share Intent", web: false,
+    config: |mode, options| super::share_intent::CaptureConfig { mode, options },
+    extra: |_meta| None,
+    raw: |data| data.map(String::into_bytes),
+    specificity: |_input| 0,
+);
+builtin_provider!(
+    ApiPoll, api_poll::ApiPollCaptureProvider, "api_poll", "API Poll", web: false,
+    config: |mode, options| super::api_poll::CaptureConfig { mode, options, cursor_store: None },
+    extra: |_meta| None,
+    raw: |data| data.map(String::into_bytes),
+    specificity: |input| match input.url.as_deref() {
+        Some(url) if guess_url_content_type(url) == "application/json" || url.contains("/api/") => 4,
+        _ => 0,
+    },
+);
+
+/// A registry seeded with the built-in providers, web providers first.
+pub fn default_registry() -> CaptureRegistry {
+    let mut registry = CaptureRegistry::new();
+    registry.register("web_article", || Box::new(WebArticle(super::web_article::WebArticleCaptureProvider::new())));
+    registry.register("web_full_page", || Box::new(WebFullPage(super::web_full_page::WebFullPageCaptureProvider::new())));
+    registry.register("web_bookmark", || Box::new(WebBookmark(super::web_bookmark::WebBookmarkCaptureProvider::new())));
+    registry.register("web_screenshot", || Box::new(WebScreenshot(super::web_screenshot::WebScreenshotCaptureProvider::new())));
+    registry.register("web_markdown", || Box::new(WebMarkdown(super::web_markdown::WebMarkdownCaptureProvider::new())));
+    registry.register("file_upload", || Box::new(FileUpload(super::file_upload::FileUploadCaptureProvider::new())));
+    registry.register("email_forward", || Box::new(EmailForward(super::email_forward::EmailForwardCaptureProvider::new())));
+    registry.register("share_intent", || Box::new(ShareIntent(super::share_intent::ShareIntentCaptureProvider::new())));
+    registry.register("api_poll", || Box::new(ApiPoll(super::api_poll::ApiPollCaptureProvider::new())));
+    registry
+}
+
+/// Create a built-in capture provider by id.
+pub fn create_provider(id: &str) -> Option<Box<dyn CaptureModePlugin>> {
+    default_registry().create(id)
+}
+
+/// Ids of the built-in capture providers.
+pub fn available_providers() -> Vec<String> {
+    default_registry().ids().into_iter().map(String::from).collect()
+}
+
+/// The first built-in provider that supports `input`.
+pub fn resolve_provider(input: &CaptureInput) -> Option<Box<dyn CaptureModePlugin>> {
+    default_registry().resolve(input)
+}
+
+/// The built-in provider for `input`, honoring `preferences` first and then
+/// provider specificity (see `CaptureRegistry::resolve_ranked`).
+pub fn resolve_provider_ranked(input: &CaptureInput, preferences: &[&str]) -> Option<Box<dyn CaptureModePlugin>> {
+    default_registry().resolve_ranked(input, preferences)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct NotionCapture;
+
+    impl CaptureModePlugin for NotionCapture {
+        fn id(&self) -> &str { "notion" }
+        fn display_name(&self) -> &str { "Notion Page" }
+
+        fn capture(&self, input: &CaptureInput, _config: &CaptureConfig) -> Result<CaptureItem, CaptureError> {
+            Ok(CaptureItem {
+                content: "page body".to_string(),
+                source_metadata: SourceMetadata {
+                    title: "Notion page".to_string(),
+                    url: input.url.clone(),
+                    captured_at: "2024-01-01T00:00:00Z".to_string(),
+                    content_type: "text/markdown".to_string(),
+                    author: None,
+                    tags: None,
+                    source: Some("notion".to_string()),
+                    extra: None,
+                },
+                raw_data: None,
+            })
+        }
+
+        fn supports(&self, input: &CaptureInput) -> bool {
+            input.url.as_deref().is_some_and(|u| u.starts_with("notion://"))
+        }
+    }
+
+    #[test]
+    fn registers_and_resolves_custom_provider() {
+        let mut registry = default_registry();
+        assert_eq!(registry.ids().len(), 9);
+        registry.register("notion", || Box::new(NotionCapture));
+
+        assert_eq!(registry.ids().last(), Some(&"notion"));
+        assert_eq!(registry.create("notion").unwrap().display_name(), "Notion Page");
+
+        let input = CaptureInput { url: Some("notion://workspace/page".to_string()), ..Default::default() };
+        let provider = registry.resolve(&input).unwrap();
+        assert_eq!(provider.id(), "notion");
+        let config = CaptureConfig { mode: "notion".to_string(), options: None, respect_robots: false };
+        assert_eq!(provider.capture(&input, &config).unwrap().content, "page body");
+
+        let web = CaptureInput { url: Some("https://example.com".to_string()), ..Default::default() };
+        assert_eq!(registry.resolve(&web).unwrap().id(), "web_article");
+        assert!(create_provider("notion").is_none());
+    }
+
+    fn url(url: &str) -> CaptureInput {
+        CaptureInput { url: Some(url.to_string()), ..Default::default() }
+    }
+
+    #[test]
+    fn ranked_resolution_honors_preferences() {
+        let article = url("https://example.com/posts/hello");
+        assert_eq!(resolve_provider_ranked(&article, &[]).unwrap().id(), "web_article");
+        assert_eq!(resolve_provider_ranked(&article, &["web_bookmark"]).unwrap().id(), "web_bookmark");
+        assert_eq!(
+            resolve_provider_ranked(&article, &["email_forward", "web_markdown", "web_bookmark"]).unwrap().id(),
+            "web_markdown"
+        );
+        assert_eq!(resolve_provider_ranked(&article, &["unknown"]).unwrap().id(), "web_article");
+    }
+
+    #[test]
+    fn ranked_resolution_uses_specificity() {
+        assert_eq!(resolve_provider_ranked(&url("https://example.com/report.pdf"), &[]).unwrap().id(), "web_bookmark");
+        assert_eq!(resolve_provider_ranked(&url("https://example.com/api/items?page=2"), &[]).unwrap().id(), "api_poll");
+        assert_eq!(resolve_provider_ranked(&url("https://example.com/index.html#top"), &[]).unwrap().id(), "web_article");
+        assert!(resolve_provider_ranked(&CaptureInput::default(), &[]).is_none());
+    }
+
+    struct TestRobots;
+
+    impl super::super::robots::RobotsFetcher for TestRobots {
+        fn fetch(&self, robots_url: &str) -> Option<String> {
+            match robots_url {
+                "https://open.example/robots.txt" => Some("User-agent: *\nAllow: /\n".to_string()),
+                "https://closed.example/robots.txt" => Some("User-agent: Clef-Capture\nDisallow: /\n".to_string()),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn web_providers_respect_robots_when_enabled() {
+        super::super::robots::RobotsCache::install(super::super::robots::RobotsCache::new(TestRobots));
+        let provider = create_provider("web_article").unwrap();
+        let config = |respect_robots| CaptureConfig { mode: "web_article".to_string(), options: None, respect_robots };
+
+        let closed = url("https://closed.example/post");
+        match provider.capture(&closed, &config(true)) {
+            Err(CaptureError::Disallowed { url, user_agent }) => {
+                assert_eq!(url, "https://closed.example/post");
+                assert_eq!(user_agent, "Clef-Capture");
+            }
+            other => panic!("expected Disallowed, got {:?}", other.map(|item| item.content)),
+        }
+        // Opting out skips the check, and an allowing file lets the fetch proceed
+        assert!(matches!(provider.capture(&closed, &config(false)), Err(CaptureError::ProviderFailed { .. })));
+        assert!(matches!(
+            provider.capture(&url("https://open.example/post"), &config(true)),
+            Err(CaptureError::ProviderFailed { .. })
+        ));
+    }
+}
@@ -0,0 +1,318 @@
+// Data Integration Kit - robots.txt Compliance for Web Capture
+// Parses and caches robots.txt per origin, checks paths for the Clef-Capture
+// user-agent and spaces requests to the same host by its Crawl-delay.
+
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+pub const USER_AGENT: &str = "Clef-Capture";
+
+/// Fetches the body of a robots.txt URL. `None` means there is no usable file
+/// (missing, unreachable or no HTTP client), which allows everything.
+pub trait RobotsFetcher: Send + Sync {
+    fn fetch(&self, robots_url: &str) -> Option<String>;
+}
+
+struct UnconfiguredFetcher;
+
+impl RobotsFetcher for UnconfiguredFetcher {
+    fn fetch(&self, _robots_url: &str) -> Option<String> {
+        // Platform HTTP integration point - delegates to runtime HTTP client
+        None
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Rule {
+    allow: bool,
+    pattern: String,
+}
+
+/// The rules of one robots.txt group that apply to a user-agent.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RobotsRules {
+    rules: Vec<Rule>,
+    pub crawl_delay: Option<Duration>,
+}
+
+impl RobotsRules {
+    /// Parse robots.txt, keeping the group that names `user_agent` or, failing
+    /// that, the `*` group. Consecutive user-agent lines share one group.
+    pub fn parse(text: &str, user_agent: &str) -> Self {
+        let agent = user_agent.to_ascii_lowercase();
+        let mut specific: Option<RobotsRules> = None;
+        let mut wildcard: Option<RobotsRules> = None;
+
+        let mut group_agents: Vec<String> = Vec::new();
+        let mut group = RobotsRules::default();
+        let mut in_rules = false;
+
+        let mut finish = |agents: &[String], group: RobotsRules| {
+            if agents.iter().any(|a| !a.is_empty() && agent.contains(a.as_str())) {
+                let target = specific.get_or_insert_with(RobotsRules::default);
+                target.rules.extend(group.rules);
+                target.crawl_delay = target.crawl_delay.or(group.crawl_delay);
+            } else if agents.iter().any(|a| a == "*") {
+                let target = wildcard.get_or_insert_with(RobotsRules::default);
+                target.rules.extend(group.rules);
+                target.crawl_delay = target.crawl_delay.or(group.crawl_delay);
+            }
+        };
+
+        for line in text.lines() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            let Some((key, value)) = line.split_once(':') else { continue };
+            let key = key.trim().to_ascii_lowercase();
+            let value = value.trim();
+
+            match key.as_str() {
+                "user-agent" => {
+                    if in_rules {
+                        finish(&group_agents, std::mem::take(&mut group));
+                        group_agents.clear();
+                        in_rules = false;
+                    }
+                    group_agents.push(value.to_ascii_lowercase());
+                }
+                "allow" | "disallow" => {
+                    in_rules = true;
+                    // An empty Disallow matches nothing
+                    if !value.is_empty() {
+                        group.rules.push(Rule { allow: key == "allow", pattern: value.to_string() });
+                    }
+                }
+                "crawl-delay" => {
+                    in_rules = true;
+                    group.crawl_delay = value.parse::<f64>().ok()
+                        .filter(|secs| secs.is_finite() && *secs >= 0.0)
+                        .map(Duration::from_secs_f64);
+                }
+                _ => {}
+            }
+        }
+        finish(&group_agents, group);
+
+        specific.or(wildcard).unwrap_or_default()
+    }
+
+    /// Whether `path` (including any query) may be fetched. The longest
+    /// matching rule wins, with Allow winning ties.
+    pub fn is_allowed(&self, path: &str) -> bool {
+        self.rules.iter()
+            .filter(|rule| pattern_matches(&rule.pattern, path))
+            .max_by_key(|rule| (rule.pattern.len(), rule.allow))
+            .is_none_or(|rule| rule.allow)
+    }
+}
+
+/// Match a robots.txt path pattern: a prefix match where `*` matches any run
+/// of characters and a trailing `$` anchors the end.
+fn pattern_matches(pattern: &str, path: &str) -> bool {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(p) => (p, true),
+        None => (pattern, false),
+    };
+    let mut pieces = pattern.split('*');
+    let first = pieces.next().unwrap_or("");
+    let Some(mut rest) = path.strip_prefix(first) else { return false };
+
+    let pieces: Vec<&str> = pieces.collect();
+    for (i, piece) in pieces.iter().enumerate() {
+        let last = i + 1 == pieces.len();
+        if last && anchored {
+            return rest.ends_with(piece);
+        }
+        match rest.find(piece) {
+            Some(pos) => rest = &rest[pos + piece.len()..],
+            None => return false,
+        }
+    }
+    !anchored || rest.is_empty()
+}
+
+/// Split an http(s) URL into its origin and path-with-query.
+fn split_url(url: &str) -> Option<(&str, &str)> {
+    let scheme_end = url.find("://")? + 3;
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        return None;
+    }
+    let url = url.split('#').next().unwrap_or(url);
+    match url[scheme_end..].find(['/', '?']) {
+        Some(pos) => {
+            let (origin, path) = url.split_at(scheme_end + pos);
+            Some((origin, if path.starts_with('?') { "/" } else { path }))
+        }
+        None => Some((url, "/")),
+    }
+}
+
+/// Lowercased host of an origin, without scheme, credentials or port.
+fn host_of(origin: &str) -> String {
+    let authority = origin.split_once("://").map_or(origin, |(_, rest)| rest);
+    let host = authority.rsplit_once('@').map_or(authority, |(_, host)| host);
+    let host = match host.rsplit_once(':') {
+        // IPv6 literals are bracketed, so a colon after `]` or in a plain name starts the port
+        Some((name, port)) if port.bytes().all(|b| b.is_ascii_digit())
+            && (name.ends_with(']') || !name.contains(':')) => name,
+        _ => host,
+    };
+    host.to_ascii_lowercase()
+}
+
+/// Why a URL may not be fetched.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Disallowed {
+    pub url: String,
+    pub user_agent: String,
+}
+
+/// Per-origin robots.txt rules plus the next free request slot per host.
+pub struct RobotsCache {
+    fetcher: Box<dyn RobotsFetcher>,
+    rules: Mutex<HashMap<String, Arc<RobotsRules>>>,
+    next_slot: Mutex<HashMap<String, Instant>>,
+}
+
+impl RobotsCache {
+    pub fn new(fetcher: impl RobotsFetcher + 'static) -> Self {
+        Self {
+            fetcher: Box::new(fetcher),
+            rules: Mutex::new(HashMap::new()),
+            next_slot: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The process-wide cache used by the built-in web providers.
+    pub fn global() -> Arc<RobotsCache> {
+        GLOBAL_CACHE.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Replace the process-wide cache, e.g. to plug in the platform HTTP client.
+    pub fn install(cache: RobotsCache) {
+        *GLOBAL_CACHE.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(cache);
+    }
+
+    /// Rules for the origin of `url`, fetching robots.txt on first use.
+    pub fn rules_for(&self, url: &str) -> Arc<RobotsRules> {
+        let Some((origin, _)) = split_url(url) else { return Arc::default() };
+        if let Some(rules) = self.rules.lock().unwrap_or_else(|e| e.into_inner()).get(origin) {
+            return rules.clone();
+        }
+        // Fetch outside the lock; a concurrent fetch of the same file is harmless
+        let rules = Arc::new(
+            self.fetcher.fetch(&format!("{}/robots.txt", origin))
+                .map(|text| RobotsRules::parse(&text, USER_AGENT))
+                .unwrap_or_default(),
+        );
+        self.rules.lock().unwrap_or_else(|e| e.into_inner())
+            .entry(origin.to_string())
+            .or_insert(rules)
+            .clone()
+    }
+
+    /// Check `url` against robots.txt and, when allowed, wait for the host's
+    /// next Crawl-delay slot before returning.
+    pub fn check(&self, url: &str) -> Result<(), Disallowed> {
+        let Some((origin, path)) = split_url(url) else { return Ok(()) };
+        let rules = self.rules_for(url);
+        if !rules.is_allowed(path) {
+            return Err(Disallowed { url: url.to_string(), user_agent: USER_AGENT.to_string() });
+        }
+
+        let Some(delay) = rules.crawl_delay else { return Ok(()) };
+        let now = Instant::now();
+        let slot = self.reserve_slot(&host_of(origin), delay, now);
+        // Sleep without the lock so other hosts, and later callers for this
+        // one, can take their own slots meanwhile.
+        std::thread::sleep(slot.saturating_duration_since(now));
+        Ok(())
+    }
+
+    /// Claim the earliest slot for `host` at or after `now` and push the
+    /// host's next slot `delay` past it.
+    fn reserve_slot(&self, host: &str, delay: Duration, now: Instant) -> Instant {
+        let mut next_slot = self.next_slot.lock().unwrap_or_else(|e| e.into_inner());
+        let slot = next_slot.get(host).map_or(now, |next| (*next).max(now));
+        next_slot.insert(host.to_string(), slot + delay);
+        slot
+    }
+}
+
+static GLOBAL_CACHE: LazyLock<RwLock<Arc<RobotsCache>>> =
+    LazyLock::new(|| RwLock::new(Arc::new(RobotsCache::new(UnconfiguredFetcher))));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct StaticFetcher(&'static str);
+
+    impl RobotsFetcher for StaticFetcher {
+        fn fetch(&self, _robots_url: &str) -> Option<String> {
+            Some(self.0.to_string())
+        }
+    }
+
+    const DISALLOW: &str = "\
+User-agent: *
+Disallow: /
+
+User-agent: Clef-Capture
+Disallow: /private/
+Disallow: /*.pdf$
+Allow: /private/press/
+Crawl-delay: 0.05
+";
+
+    #[test]
+    fn allow_file_permits_everything() {
+        let cache = RobotsCache::new(StaticFetcher("User-agent: *\nDisallow:\n"));
+        assert!(cache.check("https://example.com/").is_ok());
+        assert!(cache.check("https://example.com/private/notes?page=2").is_ok());
+    }
+
+    #[test]
+    fn disallow_file_blocks_matching_paths_for_our_agent() {
+        let cache = RobotsCache::new(StaticFetcher(DISALLOW));
+        assert!(cache.check("https://example.com/articles/1").is_ok());
+        assert!(cache.check("https://example.com/private/press/launch").is_ok());
+        assert!(cache.check("https://example.com/report.pdf?download=1").is_ok());
+
+        let blocked = cache.check("https://example.com/private/notes").unwrap_err();
+        assert_eq!(blocked.user_agent, "Clef-Capture");
+        assert!(cache.check("https://example.com/files/report.pdf").is_err());
+
+        let rules = RobotsRules::parse(DISALLOW, "OtherBot");
+        assert!(!rules.is_allowed("/articles/1"));
+        assert_eq!(rules.crawl_delay, None);
+    }
+
+    #[test]
+    fn slots_are_spaced_per_host() {
+        let cache = RobotsCache::new(StaticFetcher(DISALLOW));
+        let delay = Duration::from_secs(2);
+        let now = Instant::now();
+
+        assert_eq!(cache.reserve_slot(&host_of("https://example.com"), delay, now), now);
+        assert_eq!(cache.reserve_slot(&host_of("http://Example.com:8080"), delay, now), now + delay);
+        assert_eq!(cache.reserve_slot(&host_of("https://example.com"), delay, now), now + delay * 2);
+        assert_eq!(cache.reserve_slot(&host_of("https://other.example"), delay, now), now);
+
+        // Once the queue has drained, the next request goes straight out
+        let later = now + Duration::from_secs(60);
+        assert_eq!(cache.reserve_slot(&host_of("https://example.com"), delay, later), later);
+
+        assert_eq!(host_of("https://user@[::1]:8443"), "[::1]");
+        assert_eq!(host_of("https://[::1]"), "[::1]");
+    }
+
+    #[test]
+    fn crawl_delay_spaces_requests_to_an_origin() {
+        let cache = RobotsCache::new(StaticFetcher(DISALLOW));
+        let start = Instant::now();
+        cache.check("https://example.com/a").unwrap();
+        cache.check("https://example.com/b").unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(50));
+    }
+}
@@ -3,6 +3,9 @@
 
 use std::collections::HashMap;
 
+use super::charset::decode_body;
+use super::content_hash::{compute_content_hash, content_hash_extra};
+
 pub const PROVIDER_ID: &str = "web_article";
 pub const PLUGIN_TYPE: &str = "capture_mode";
 
@@ -29,6 +32,7 @@ pub struct SourceMetadata {
     pub author: Option<String>,
     pub tags: Option<Vec<String>>,
     pub source: Option<String>,
+    pub extra: Option<HashMap<String, serde_json::Value>>,
 }
 
 #[derive(Debug, Clone)]
@@ -162,36 +166,42 @@ impl WebArticleCaptureProvider {
     pub fn capture(&self, input: &CaptureInput, config: &CaptureConfig) -> Result<CaptureItem, CaptureError> {
         let url = input.url.as_ref().ok_or(CaptureError::MissingUrl)?;
         let html = http_get(url).map_err(|e| CaptureError::FetchError(e.to_string()))?;
+        Ok(self.capture_html(url, &html, config))
+    }
 
-        let title = extract_meta(&html, &[
+    /// Extract the article from HTML that has already been fetched for `url`.
+    pub fn capture_html(&self, url: &str, html: &str, config: &CaptureConfig) -> CaptureItem {
+        let title = extract_meta(html, &[
             r#"(?i)og:title["']\s+content=["']([^"']+)"#,
             r"(?i)<title>([^<]+)</title>",
         ]).unwrap_or_else(|| "Untitled".to_string());
 
-        let author = extract_meta(&html, &[
+        let author = extract_meta(html, &[
             r#"(?i)name=["']author["']\s+content=["']([^"']+)"#,
         ]);
 
-        let main_html = find_main_content(&html);
+        let main_html = find_main_content(html);
         let content = extract_text(&main_html);
+        let extra = content_hash_extra(config.options.as_ref(), || compute_content_hash(&content));
 
-        Ok(CaptureItem {
+        CaptureItem {
             content,
             source_metadata: SourceMetadata {
                 title,
-                url: Some(url.clone()),
+                url: Some(url.to_string()),
                 captured_at: chrono::Utc::now().to_rfc3339(),
                 content_type: "text/html".to_string(),
                 author,
                 tags: Some(vec!["article".to_string()]),
                 source: Some("web_article".to_string()),
+                extra,
             },
             raw_data: if config.options.as_ref().and_then(|o| o.get("includeRaw")).is_some() {
-                Some(html)
+                Some(html.to_string())
             } else {
                 None
             },
-        })
+        }
     }
 
     pub fn supports(&self, input: &CaptureInput) -> bool {
@@ -201,7 +211,14 @@ impl WebArticleCaptureProvider {
     }
 }
 
+/// Fetch `url` and decode the body with its declared charset.
 fn http_get(url: &str) -> Result<String, CaptureError> {
+    let (content_type, body) = http_get_raw(url)?;
+    Ok(decode_body(&body, content_type.as_deref(), true))
+}
+
+/// The response's Content-Type header and raw body bytes.
+fn http_get_raw(url: &str) -> Result<(Option<String>, Vec<u8>), CaptureError> {
     // Platform HTTP integration point - delegates to runtime HTTP client
     Err(CaptureError::FetchError(format!("HTTP client not configured for: {}", url)))
 }
@@ -3,6 +3,8 @@
 
 use std::collections::HashMap;
 
+use super::charset::decode_body;
+
 pub const PROVIDER_ID: &str = "web_bookmark";
 pub const PLUGIN_TYPE: &str = "capture_mode";
 
@@ -214,6 +216,13 @@ impl WebBookmarkCaptureProvider {
     }
 }
 
-fn http_get_partial(url: &str, _max_bytes: usize) -> Result<String, CaptureError> {
+/// Fetch at most `max_bytes` of `url` and decode them with the declared charset.
+fn http_get_partial(url: &str, max_bytes: usize) -> Result<String, CaptureError> {
+    let (content_type, body) = http_get_raw_partial(url, max_bytes)?;
+    Ok(decode_body(&body, content_type.as_deref(), true))
+}
+
+/// The response's Content-Type header and up to `max_bytes` of raw body.
+fn http_get_raw_partial(url: &str, _max_bytes: usize) -> Result<(Option<String>, Vec<u8>), CaptureError> {
     Err(CaptureError::FetchError(format!("HTTP client not configured for: {}", url)))
 }
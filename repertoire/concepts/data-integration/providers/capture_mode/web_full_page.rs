@@ -3,9 +3,16 @@
 
 use std::collections::HashMap;
 
+use super::charset::decode_body;
+use super::content_hash::{content_hash_extra, normalize_whitespace, sha256_hex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
 pub const PROVIDER_ID: &str = "web_full_page";
 pub const PLUGIN_TYPE: &str = "capture_mode";
 
+const DEFAULT_STYLESHEET_CONCURRENCY: usize = 6;
+
 #[derive(Debug, Clone)]
 pub struct CaptureInput {
     pub url: Option<String>,
@@ -29,6 +36,7 @@ pub struct SourceMetadata {
     pub author: Option<String>,
     pub tags: Option<Vec<String>>,
     pub source: Option<String>,
+    pub extra: Option<HashMap<String, serde_json::Value>>,
 }
 
 #[derive(Debug, Clone)]
@@ -99,11 +107,43 @@ fn resolve_all_relative_urls(html: &str, base_url: &str) -> String {
     }).to_string()
 }
 
-fn inline_stylesheets(html: &str, base_url: &str, css_fetcher: &dyn Fn(&str) -> Option<String>) -> String {
+/// Run `fetcher` over every URL with at most `concurrency` requests in flight,
+/// returning results in the same order as `urls`.
+fn fetch_concurrently<T: Send>(
+    urls: &[String],
+    concurrency: usize,
+    fetcher: &(dyn Fn(&str) -> Option<T> + Sync),
+) -> Vec<Option<T>> {
+    let results: Mutex<Vec<Option<T>>> = Mutex::new((0..urls.len()).map(|_| None).collect());
+    let next = AtomicUsize::new(0);
+    let workers = concurrency.max(1).min(urls.len());
+
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let idx = next.fetch_add(1, Ordering::SeqCst);
+                if idx >= urls.len() { break; }
+                let fetched = fetcher(&urls[idx]);
+                results.lock().unwrap()[idx] = fetched;
+            });
+        }
+    });
+
+    results.into_inner().unwrap()
+}
+
+fn inline_stylesheets(
+    html: &str,
+    base_url: &str,
+    concurrency: usize,
+    css_fetcher: &(dyn Fn(&str) -> Option<String> + Sync),
+) -> String {
     let urls = extract_stylesheet_urls(html, base_url);
+    let fetched = fetch_concurrently(&urls, concurrency, css_fetcher);
     let mut result = html.to_string();
-    for css_url in &urls {
-        if let Some(css_text) = css_fetcher(css_url) {
+    // Replace in document order; sheets that failed to fetch keep their <link>
+    for (css_url, css_text) in urls.iter().zip(fetched) {
+        if let Some(css_text) = css_text {
             let resolved_css = regex::Regex::new(r#"url\(["']?([^"')]+)["']?\)"#).unwrap()
                 .replace_all(&css_text, |caps: &regex::Captures| {
                     format!("url(\"{}\")", resolve_url(css_url, &caps[1]))
@@ -133,6 +173,34 @@ fn inline_images(html: &str, base_url: &str, img_fetcher: &dyn Fn(&str) -> Optio
     result
 }
 
+/// Strip the injected capture timestamp, sort each tag's attributes, and collapse
+/// whitespace so re-captures of an unchanged page normalize identically.
+fn normalize_for_hash(html: &str) -> String {
+    let mut normalized = regex::Regex::new(r"<!-- Full page snapshot captured from [\s\S]*?-->").unwrap()
+        .replace_all(html, "").to_string();
+    normalized = regex::Regex::new(r#"(?i)<meta[^>]+name=["']captured[_-]?at["'][^>]*>"#).unwrap()
+        .replace_all(&normalized, "").to_string();
+
+    let tag_re = regex::Regex::new(r"<([A-Za-z][A-Za-z0-9-]*)(\s[^<>]*?)?(/?)>").unwrap();
+    let attr_re = regex::Regex::new(r#"[^\s=/]+(?:\s*=\s*(?:"[^"]*"|'[^']*'|[^\s"'>]+))?"#).unwrap();
+    normalized = tag_re.replace_all(&normalized, |caps: &regex::Captures| {
+        let mut attrs: Vec<&str> = caps.get(2)
+            .map(|m| attr_re.find_iter(m.as_str()).map(|a| a.as_str()).collect())
+            .unwrap_or_default();
+        attrs.sort_unstable();
+        let mut tag = format!("<{}", &caps[1]);
+        for attr in attrs {
+            tag.push(' ');
+            tag.push_str(attr);
+        }
+        format!("{}{}>", tag, &caps[3])
+    }).to_string();
+
+    normalized = regex::Regex::new(r">\s+|\s+<").unwrap()
+        .replace_all(&normalized, |caps: &regex::Captures| caps[0].trim().to_string()).to_string();
+    normalize_whitespace(&normalized)
+}
+
 pub struct WebFullPageCaptureProvider;
 
 impl WebFullPageCaptureProvider {
@@ -141,13 +209,17 @@ impl WebFullPageCaptureProvider {
     pub fn capture(&self, input: &CaptureInput, config: &CaptureConfig) -> Result<CaptureItem, CaptureError> {
         let url = input.url.as_ref().ok_or(CaptureError::MissingUrl)?;
         let html = http_get(url).map_err(|e| CaptureError::FetchError(e.to_string()))?;
+        Ok(self.capture_html(url, &html, config))
+    }
 
+    /// Build a snapshot from HTML that has already been fetched for `url`.
+    pub fn capture_html(&self, url: &str, html: &str, config: &CaptureConfig) -> CaptureItem {
         let title = regex::Regex::new(r"(?i)<title>([^<]*)</title>")
             .ok().and_then(|re| re.captures(&html))
             .and_then(|caps| caps.get(1).map(|m| m.as_str().trim().to_string()))
             .unwrap_or_else(|| "Untitled Page".to_string());
 
-        let mut result_html = resolve_all_relative_urls(&html, url);
+        let mut result_html = resolve_all_relative_urls(html, url);
 
         let inline_css = config.options.as_ref()
             .and_then(|o| o.get("inlineStyles"))
@@ -158,8 +230,14 @@ impl WebFullPageCaptureProvider {
             .and_then(|v| v.as_bool())
             .unwrap_or(true);
 
+        let stylesheet_concurrency = config.options.as_ref()
+            .and_then(|o| o.get("stylesheetConcurrency"))
+            .and_then(|v| v.as_u64())
+            .map(|n| n as usize)
+            .unwrap_or(DEFAULT_STYLESHEET_CONCURRENCY);
+
         if inline_css {
-            result_html = inline_stylesheets(&result_html, url, &|css_url| {
+            result_html = inline_stylesheets(&result_html, url, stylesheet_concurrency, &|css_url| {
                 http_get(css_url).ok()
             });
         }
@@ -172,19 +250,22 @@ impl WebFullPageCaptureProvider {
         let timestamp = chrono::Utc::now().to_rfc3339();
         let snapshot = format!("<!-- Full page snapshot captured from {} at {} -->\n{}", url, timestamp, result_html);
 
-        Ok(CaptureItem {
+        let extra = content_hash_extra(config.options.as_ref(), || sha256_hex(&normalize_for_hash(&snapshot)));
+
+        CaptureItem {
             content: snapshot,
             source_metadata: SourceMetadata {
                 title,
-                url: Some(url.clone()),
+                url: Some(url.to_string()),
                 captured_at: timestamp,
                 content_type: "text/html".to_string(),
                 author: None,
                 tags: Some(vec!["full-page".to_string(), "snapshot".to_string()]),
                 source: Some("web_full_page".to_string()),
+                extra,
             },
             raw_data: None,
-        })
+        }
     }
 
     pub fn supports(&self, input: &CaptureInput) -> bool {
@@ -194,10 +275,127 @@ impl WebFullPageCaptureProvider {
     }
 }
 
+/// Fetch `url` and decode the body with its declared charset.
 fn http_get(url: &str) -> Result<String, CaptureError> {
-    Err(CaptureError::FetchError(format!("HTTP client not configured for: {}", url)))
+    let (content_type, body) = http_get_bytes(url)?;
+    Ok(decode_body(&body, Some(&content_type), content_type.contains("html")))
 }
 
 fn http_get_bytes(url: &str) -> Result<(String, Vec<u8>), CaptureError> {
     Err(CaptureError::FetchError(format!("HTTP client not configured for: {}", url)))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hashing_config() -> CaptureConfig {
+        let mut options = HashMap::new();
+        options.insert("contentHash".to_string(), serde_json::json!(true));
+        options.insert("inlineStyles".to_string(), serde_json::json!(false));
+        options.insert("inlineImages".to_string(), serde_json::json!(false));
+        CaptureConfig { mode: "web_full_page".to_string(), options: Some(options) }
+    }
+
+    fn content_hash(item: &CaptureItem) -> String {
+        item.source_metadata.extra.as_ref().unwrap()["contentHash"].as_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn recapturing_unchanged_page_yields_stable_hash() {
+        let provider = WebFullPageCaptureProvider::new();
+        let html = "<html><head><title>Docs</title></head><body><p class=\"a\" id=\"b\">Hello</p></body></html>";
+
+        let first = provider.capture_html("https://example.com/docs", html, &hashing_config());
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        let second = provider.capture_html("https://example.com/docs", html, &hashing_config());
+
+        assert_ne!(first.source_metadata.captured_at, second.source_metadata.captured_at);
+        assert_ne!(first.content, second.content);
+        assert_eq!(content_hash(&first), content_hash(&second));
+    }
+
+    #[test]
+    fn hash_ignores_attribute_order_and_whitespace_but_not_content() {
+        let provider = WebFullPageCaptureProvider::new();
+        let base = provider.capture_html("https://example.com", "<p class=\"a\" id=\"b\">Hello</p>", &hashing_config());
+        let reordered = provider.capture_html("https://example.com", "<p  id=\"b\"   class=\"a\">\n  Hello\n</p>", &hashing_config());
+        let edited = provider.capture_html("https://example.com", "<p class=\"a\" id=\"b\">Goodbye</p>", &hashing_config());
+
+        assert_eq!(content_hash(&base), content_hash(&reordered));
+        assert_ne!(content_hash(&base), content_hash(&edited));
+    }
+
+    #[test]
+    fn hash_is_opt_in() {
+        let config = CaptureConfig { mode: "web_full_page".to_string(), options: None };
+        let item = WebFullPageCaptureProvider::new().capture_html("https://example.com", "<p>Hi</p>", &config);
+        assert!(item.source_metadata.extra.is_none());
+    }
+
+    /// Counts fetchers that are inside the fetch at the same time. Each one
+    /// waits until `expected` have arrived, so the peak only reaches
+    /// `expected` if that many really run at once; the timeout just keeps a
+    /// serial implementation from hanging the test.
+    struct Rendezvous {
+        expected: usize,
+        inside: Mutex<usize>,
+        peak: AtomicUsize,
+        arrived: std::sync::Condvar,
+    }
+
+    impl Rendezvous {
+        fn new(expected: usize) -> Self {
+            Self { expected, inside: Mutex::new(0), peak: AtomicUsize::new(0), arrived: std::sync::Condvar::new() }
+        }
+
+        fn enter(&self) {
+            let mut inside = self.inside.lock().unwrap();
+            *inside += 1;
+            self.peak.fetch_max(*inside, Ordering::SeqCst);
+            self.arrived.notify_all();
+            let (mut inside, _) = self.arrived
+                .wait_timeout_while(inside, std::time::Duration::from_secs(5), |n| {
+                    *n < self.expected && self.peak.load(Ordering::SeqCst) < self.expected
+                })
+                .unwrap();
+            *inside -= 1;
+        }
+    }
+
+    #[test]
+    fn stylesheets_are_fetched_concurrently_in_order() {
+        let html: String = (0..8)
+            .map(|i| format!("<link rel=\"stylesheet\" href=\"https://example.com/s{}.css\">", i))
+            .collect();
+        let rendezvous = Rendezvous::new(8);
+        let fetcher = |css_url: &str| -> Option<String> {
+            rendezvous.enter();
+            if css_url.ends_with("/s3.css") { return None; }
+            Some(format!("/* {} */", css_url))
+        };
+
+        let result = inline_stylesheets(&html, "https://example.com/page", 8, &fetcher);
+
+        assert_eq!(rendezvous.peak.load(Ordering::SeqCst), 8);
+        let positions: Vec<usize> = [0, 1, 2, 4, 5, 6, 7].iter()
+            .map(|i| result.find(&format!("/* https://example.com/s{}.css */", i)).unwrap())
+            .collect();
+        assert!(positions.windows(2).all(|w| w[0] < w[1]));
+        assert!(result.contains("<link rel=\"stylesheet\" href=\"https://example.com/s3.css\">"));
+    }
+
+    #[test]
+    fn stylesheet_concurrency_is_bounded() {
+        let urls: Vec<String> = (0..6).map(|i| format!("https://example.com/s{}.css", i)).collect();
+        let rendezvous = Rendezvous::new(2);
+        let fetcher = |_: &str| -> Option<String> {
+            rendezvous.enter();
+            Some(String::new())
+        };
+
+        let results = fetch_concurrently(&urls, 2, &fetcher);
+        assert_eq!(results.len(), 6);
+        assert_eq!(rendezvous.peak.load(Ordering::SeqCst), 2);
+    }
+}
@@ -3,6 +3,9 @@
 
 use std::collections::HashMap;
 
+use super::charset::decode_body;
+use super::content_hash::{compute_content_hash, content_hash_extra};
+
 pub const PROVIDER_ID: &str = "web_markdown";
 pub const PLUGIN_TYPE: &str = "capture_mode";
 
@@ -29,6 +32,7 @@ pub struct SourceMetadata {
     pub author: Option<String>,
     pub tags: Option<Vec<String>>,
     pub source: Option<String>,
+    pub extra: Option<HashMap<String, serde_json::Value>>,
 }
 
 #[derive(Debug, Clone)]
@@ -214,14 +218,18 @@ fn html_to_markdown(html: &str) -> String {
     regex::Regex::new(r"\n{3,}").unwrap().replace_all(&md, "\n\n").trim().to_string()
 }
 
+/// Convert table rows to a GFM pipe table. Rows come out in document order,
+/// so a `<thead>` row leads naturally; without one the first row is the header.
 fn convert_table(html: &str) -> String {
     let row_re = regex::Regex::new(r"(?is)<tr[^>]*>([\s\S]*?)</tr>").unwrap();
     let cell_re = regex::Regex::new(r"(?is)<(?:td|th)[^>]*>([\s\S]*?)</(?:td|th)>").unwrap();
+    let ws_re = regex::Regex::new(r"\s+").unwrap();
 
     let mut rows: Vec<Vec<String>> = Vec::new();
     for row_caps in row_re.captures_iter(html) {
+        // A cell must stay on one line, and a literal pipe would split it.
         let cells: Vec<String> = cell_re.captures_iter(&row_caps[1])
-            .map(|c| strip_tags(&c[1]).trim().to_string())
+            .map(|c| ws_re.replace_all(strip_tags(&c[1]).trim(), " ").replace('|', "\\|"))
             .collect();
         if !cells.is_empty() { rows.push(cells); }
     }
@@ -262,9 +270,13 @@ impl WebMarkdownCaptureProvider {
     pub fn capture(&self, input: &CaptureInput, config: &CaptureConfig) -> Result<CaptureItem, CaptureError> {
         let url = input.url.as_ref().ok_or(CaptureError::MissingUrl)?;
         let html = http_get(url).map_err(|e| CaptureError::FetchError(e.to_string()))?;
+        Ok(self.capture_html(url, &html, config))
+    }
 
-        let meta = extract_article_meta(&html);
-        let cleaned = strip_non_content(&html);
+    /// Convert HTML that has already been fetched for `url` into Markdown.
+    pub fn capture_html(&self, url: &str, html: &str, config: &CaptureConfig) -> CaptureItem {
+        let meta = extract_article_meta(html);
+        let cleaned = strip_non_content(html);
         let markdown = html_to_markdown(&cleaned);
 
         let include_frontmatter = config.options.as_ref()
@@ -279,24 +291,26 @@ impl WebMarkdownCaptureProvider {
 
         let mut tags = vec!["markdown".to_string()];
         tags.extend(meta.tags.clone());
+        let extra = content_hash_extra(config.options.as_ref(), || compute_content_hash(&content));
 
-        Ok(CaptureItem {
+        CaptureItem {
             content,
             source_metadata: SourceMetadata {
                 title: meta.title,
-                url: Some(url.clone()),
+                url: Some(url.to_string()),
                 captured_at: chrono::Utc::now().to_rfc3339(),
                 content_type: "text/markdown".to_string(),
                 author: meta.author,
                 tags: Some(tags),
                 source: Some("web_markdown".to_string()),
+                extra,
             },
             raw_data: if config.options.as_ref().and_then(|o| o.get("includeHtml")).is_some() {
-                Some(html)
+                Some(html.to_string())
             } else {
                 None
             },
-        })
+        }
     }
 
     pub fn supports(&self, input: &CaptureInput) -> bool {
@@ -306,6 +320,51 @@ impl WebMarkdownCaptureProvider {
     }
 }
 
+/// Fetch `url` and decode the body with its declared charset.
 fn http_get(url: &str) -> Result<String, CaptureError> {
+    let (content_type, body) = http_get_raw(url)?;
+    Ok(decode_body(&body, content_type.as_deref(), true))
+}
+
+/// The response's Content-Type header and raw body bytes.
+fn http_get_raw(url: &str) -> Result<(Option<String>, Vec<u8>), CaptureError> {
     Err(CaptureError::FetchError(format!("HTTP client not configured for: {}", url)))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_table_without_thead_to_pipe_table() {
+        let html = "<table>\
+            <tr><th>Name</th><th>Shell</th></tr>\
+            <tr><td>Alice</td><td>bash | zsh</td></tr>\
+            <tr><td><b>Bob</b></td><td>fish</td></tr>\
+            </table>";
+
+        assert_eq!(
+            html_to_markdown(html),
+            "| Name | Shell |\n| --- | --- |\n| Alice | bash \\| zsh |\n| **Bob** | fish |"
+        );
+    }
+
+    #[test]
+    fn recapturing_unchanged_page_yields_stable_hash() {
+        let mut options = HashMap::new();
+        options.insert("contentHash".to_string(), serde_json::json!(true));
+        let config = CaptureConfig { mode: "web_markdown".to_string(), options: Some(options) };
+        let provider = WebMarkdownCaptureProvider::new();
+        let html = "<html><head><title>Notes</title></head><body><h1>Notes</h1><p>Body text.</p></body></html>";
+
+        let first = provider.capture_html("https://example.com/notes", html, &config);
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        let second = provider.capture_html("https://example.com/notes", html, &config);
+
+        assert_ne!(first.source_metadata.captured_at, second.source_metadata.captured_at);
+        assert_eq!(
+            first.source_metadata.extra.as_ref().unwrap()["contentHash"],
+            second.source_metadata.extra.as_ref().unwrap()["contentHash"],
+        );
+    }
+}
@@ -44,6 +44,7 @@ pub enum CaptureError {
     BrowserError(String),
     TimeoutError(String),
     SelectorError(String),
+    ProviderUnavailable(String),
 }
 
 impl std::fmt::Display for CaptureError {
@@ -53,6 +54,7 @@ impl std::fmt::Display for CaptureError {
             CaptureError::BrowserError(e) => write!(f, "Browser error: {}", e),
             CaptureError::TimeoutError(e) => write!(f, "Timeout error: {}", e),
             CaptureError::SelectorError(e) => write!(f, "Selector error: {}", e),
+            CaptureError::ProviderUnavailable(e) => write!(f, "Screenshot provider unavailable: {}", e),
         }
     }
 }
@@ -135,7 +137,7 @@ fn build_data_uri(bytes: &[u8], format: &ImageFormat) -> String {
 
 /// Represents a headless browser abstraction for screenshot capture
 pub trait HeadlessBrowser {
-    fn navigate(&self, url: &str, timeout_ms: u64) -> Result<(), CaptureError>;
+    fn navigate(&self, url: &str, wait_until: &WaitCondition, timeout_ms: u64) -> Result<(), CaptureError>;
     fn set_viewport(&self, width: u32, height: u32, scale: f64) -> Result<(), CaptureError>;
     fn get_title(&self) -> Result<String, CaptureError>;
     fn capture_viewport(&self, format: &ImageFormat, quality: Option<u8>) -> Result<Vec<u8>, CaptureError>;
@@ -153,11 +155,10 @@ impl WebScreenshotCaptureProvider {
         let url = input.url.as_ref().ok_or(CaptureError::MissingUrl)?;
         let options = parse_options(config);
 
-        let browser = create_browser()
-            .map_err(|e| CaptureError::BrowserError(e.to_string()))?;
+        let browser = create_browser()?;
 
         browser.set_viewport(options.width, options.height, options.device_scale_factor)?;
-        browser.navigate(url, options.timeout_ms)?;
+        browser.navigate(url, &options.wait_until, options.timeout_ms)?;
 
         if options.delay_ms > 0 {
             browser.wait(options.delay_ms)?;
@@ -211,6 +212,191 @@ impl WebScreenshotCaptureProvider {
     }
 }
 
+#[cfg(not(feature = "screenshot"))]
 fn create_browser() -> Result<Box<dyn HeadlessBrowser>, CaptureError> {
-    Err(CaptureError::BrowserError("Headless browser runtime not configured".to_string()))
+    Err(CaptureError::ProviderUnavailable(
+        "web_screenshot was built without the `screenshot` feature".to_string(),
+    ))
+}
+
+#[cfg(feature = "screenshot")]
+fn create_browser() -> Result<Box<dyn HeadlessBrowser>, CaptureError> {
+    Ok(Box::new(chromium::ChromiumBrowser::launch()?))
+}
+
+/// Headless Chromium backend driven over the DevTools protocol.
+/// chromiumoxide is async, so the browser owns a private tokio runtime
+/// and each `HeadlessBrowser` call blocks on it.
+#[cfg(feature = "screenshot")]
+mod chromium {
+    use super::{CaptureError, HeadlessBrowser, ImageFormat, WaitCondition};
+    use chromiumoxide::browser::{Browser, BrowserConfig};
+    use chromiumoxide::cdp::browser_protocol::emulation::SetDeviceMetricsOverrideParams;
+    use chromiumoxide::cdp::browser_protocol::page::{
+        CaptureScreenshotFormat, EventLifecycleEvent, SetLifecycleEventsEnabledParams, Viewport,
+    };
+    use chromiumoxide::page::{Page, ScreenshotParams, ScreenshotParamsBuilder};
+    use futures::StreamExt;
+    use std::time::Duration;
+
+    pub struct ChromiumBrowser {
+        runtime: tokio::runtime::Runtime,
+        browser: Browser,
+        page: Page,
+    }
+
+    impl ChromiumBrowser {
+        pub fn launch() -> Result<Self, CaptureError> {
+            let runtime = tokio::runtime::Builder::new_multi_thread()
+                .enable_all()
+                .build()
+                .map_err(|e| CaptureError::ProviderUnavailable(e.to_string()))?;
+
+            let (browser, page) = runtime.block_on(async {
+                let config = BrowserConfig::builder()
+                    .build()
+                    .map_err(CaptureError::ProviderUnavailable)?;
+                let (browser, mut handler) = Browser::launch(config)
+                    .await
+                    .map_err(|e| CaptureError::ProviderUnavailable(format!("Chromium launch failed: {}", e)))?;
+                tokio::spawn(async move {
+                    while let Some(event) = handler.next().await {
+                        if event.is_err() { break; }
+                    }
+                });
+                let page = browser.new_page("about:blank")
+                    .await
+                    .map_err(|e| CaptureError::BrowserError(e.to_string()))?;
+                Ok::<_, CaptureError>((browser, page))
+            })?;
+
+            Ok(Self { runtime, browser, page })
+        }
+
+        fn screenshot_format(format: &ImageFormat) -> CaptureScreenshotFormat {
+            match format {
+                ImageFormat::Png => CaptureScreenshotFormat::Png,
+                ImageFormat::Jpeg => CaptureScreenshotFormat::Jpeg,
+            }
+        }
+
+        fn screenshot_params(format: &ImageFormat, quality: Option<u8>) -> ScreenshotParamsBuilder {
+            let params = ScreenshotParams::builder().format(Self::screenshot_format(format));
+            match quality {
+                Some(q) => params.quality(q as i64),
+                None => params,
+            }
+        }
+
+        fn capture(&self, format: &ImageFormat, quality: Option<u8>, full_page: bool) -> Result<Vec<u8>, CaptureError> {
+            let params = Self::screenshot_params(format, quality).full_page(full_page);
+            self.runtime
+                .block_on(self.page.screenshot(params.build()))
+                .map_err(|e| CaptureError::BrowserError(e.to_string()))
+        }
+    }
+
+    impl HeadlessBrowser for ChromiumBrowser {
+        fn navigate(&self, url: &str, wait_until: &WaitCondition, timeout_ms: u64) -> Result<(), CaptureError> {
+            let navigation = async {
+                // Subscribe before navigating so an early networkIdle event is not missed.
+                self.page.execute(SetLifecycleEventsEnabledParams::new(true)).await?;
+                let mut lifecycle = self.page.event_listener::<EventLifecycleEvent>().await?;
+                // goto resolves once the load event has fired.
+                self.page.goto(url).await?;
+                if let WaitCondition::NetworkIdle = wait_until {
+                    while let Some(event) = lifecycle.next().await {
+                        if event.name == "networkIdle" { break; }
+                    }
+                }
+                Ok::<_, chromiumoxide::error::CdpError>(())
+            };
+
+            self.runtime.block_on(async {
+                match tokio::time::timeout(Duration::from_millis(timeout_ms), navigation).await {
+                    Ok(result) => result.map_err(|e| CaptureError::BrowserError(e.to_string())),
+                    Err(_) => Err(CaptureError::TimeoutError(format!(
+                        "Navigation to {} exceeded {}ms", url, timeout_ms
+                    ))),
+                }
+            })
+        }
+
+        fn set_viewport(&self, width: u32, height: u32, scale: f64) -> Result<(), CaptureError> {
+            let params = SetDeviceMetricsOverrideParams::new(width as i64, height as i64, scale, false);
+            self.runtime
+                .block_on(self.page.execute(params))
+                .map(|_| ())
+                .map_err(|e| CaptureError::BrowserError(e.to_string()))
+        }
+
+        fn get_title(&self) -> Result<String, CaptureError> {
+            self.runtime
+                .block_on(self.page.get_title())
+                .map(|t| t.unwrap_or_else(|| "Screenshot".to_string()))
+                .map_err(|e| CaptureError::BrowserError(e.to_string()))
+        }
+
+        fn capture_viewport(&self, format: &ImageFormat, quality: Option<u8>) -> Result<Vec<u8>, CaptureError> {
+            self.capture(format, quality, false)
+        }
+
+        fn capture_full_page(&self, format: &ImageFormat, quality: Option<u8>) -> Result<Vec<u8>, CaptureError> {
+            self.capture(format, quality, true)
+        }
+
+        fn capture_element(&self, selector: &str, format: &ImageFormat, quality: Option<u8>) -> Result<Vec<u8>, CaptureError> {
+            self.runtime.block_on(async {
+                let element = self.page
+                    .find_element(selector)
+                    .await
+                    .map_err(|e| CaptureError::SelectorError(format!("{}: {}", selector, e)))?;
+                // Element::screenshot has no quality knob, so clip a page
+                // screenshot to the element's box instead.
+                let clipped = async {
+                    let bounds = element.scroll_into_view().await?.bounding_box().await?;
+                    let viewport = self.page.layout_metrics().await?.css_layout_viewport;
+                    let clip = Viewport {
+                        x: viewport.page_x as f64 + bounds.x,
+                        y: viewport.page_y as f64 + bounds.y,
+                        width: bounds.width,
+                        height: bounds.height,
+                        scale: 1.0,
+                    };
+                    self.page.screenshot(Self::screenshot_params(format, quality).clip(clip).build()).await
+                };
+                clipped.await.map_err(|e| CaptureError::BrowserError(e.to_string()))
+            })
+        }
+
+        fn wait(&self, ms: u64) -> Result<(), CaptureError> {
+            self.runtime.block_on(tokio::time::sleep(Duration::from_millis(ms)));
+            Ok(())
+        }
+    }
+
+    impl Drop for ChromiumBrowser {
+        fn drop(&mut self) {
+            let _ = self.runtime.block_on(self.browser.close());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(not(feature = "screenshot"))]
+    #[test]
+    fn capture_without_screenshot_feature_is_unavailable() {
+        let input = CaptureInput {
+            url: Some("https://example.com".to_string()),
+            file: None,
+            email: None,
+            share_data: None,
+        };
+        let config = CaptureConfig { mode: "screenshot".to_string(), options: None };
+        let result = WebScreenshotCaptureProvider::new().capture(&input, &config);
+        assert!(matches!(result, Err(CaptureError::ProviderUnavailable(_))));
+    }
 }
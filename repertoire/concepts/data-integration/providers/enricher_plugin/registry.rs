@@ -0,0 +1,338 @@
+// Clef Data Integration Kit - Enricher Plugin Registry
+// Record-level enricher interface with built-in language and geo enrichers,
+// mapped by id so applications can register their own alongside them.
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+
+use async_trait::async_trait;
+
+pub const PLUGIN_TYPE: &str = "enricher_plugin";
+
+pub type Record = HashMap<String, serde_json::Value>;
+
+#[derive(Debug, Clone, Default)]
+pub struct EnricherConfig {
+    pub model: Option<String>,
+    pub api_key: Option<String>,
+    pub threshold: Option<f64>,
+    pub options: Option<HashMap<String, serde_json::Value>>,
+}
+
+impl EnricherConfig {
+    fn option_str(&self, key: &str) -> Option<&str> {
+        self.options.as_ref().and_then(|o| o.get(key)).and_then(|v| v.as_str())
+    }
+}
+
+#[derive(Debug)]
+pub enum EnricherError {
+    UnknownProvider(String),
+    MissingField(String),
+    ConfigError(String),
+}
+
+impl std::fmt::Display for EnricherError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EnricherError::UnknownProvider(id) => write!(f, "Unknown enricher provider: {}", id),
+            EnricherError::MissingField(field) => write!(f, "Record has no usable '{}' field", field),
+            EnricherError::ConfigError(message) => write!(f, "Enricher config error: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for EnricherError {}
+
+/// Interface every enricher exposes through the registry. `enrich` adds its
+/// fields to the record in place.
+#[async_trait]
+pub trait EnricherPlugin: Send + Sync {
+    fn id(&self) -> &str;
+
+    fn display_name(&self) -> &str;
+
+    async fn enrich(&self, record: &mut Record, config: &EnricherConfig) -> Result<(), EnricherError>;
+}
+
+pub type EnricherFactory = Box<dyn Fn() -> Box<dyn EnricherPlugin> + Send + Sync>;
+
+/// Enrichers by id, kept in registration order.
+#[derive(Default)]
+pub struct EnricherRegistry {
+    factories: Vec<(String, EnricherFactory)>,
+}
+
+impl EnricherRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register an enricher factory. Re-registering an id replaces the earlier
+    /// factory but keeps its position.
+    pub fn register<F>(&mut self, id: impl Into<String>, factory: F)
+    where
+        F: Fn() -> Box<dyn EnricherPlugin> + Send + Sync + 'static,
+    {
+        let id = id.into();
+        match self.factories.iter_mut().find(|(existing, _)| *existing == id) {
+            Some(entry) => entry.1 = Box::new(factory),
+            None => self.factories.push((id, Box::new(factory))),
+        }
+    }
+
+    pub fn create(&self, id: &str) -> Option<Box<dyn EnricherPlugin>> {
+        self.factories.iter()
+            .find(|(existing, _)| existing == id)
+            .map(|(_, factory)| factory())
+    }
+
+    pub fn ids(&self) -> Vec<&str> {
+        self.factories.iter().map(|(id, _)| id.as_str()).collect()
+    }
+
+    /// Run the enricher registered as `id` against `record`.
+    pub async fn enrich(&self, id: &str, record: &mut Record, config: &EnricherConfig) -> Result<(), EnricherError> {
+        let enricher = self.create(id).ok_or_else(|| EnricherError::UnknownProvider(id.to_string()))?;
+        enricher.enrich(record, config).await
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Language detection
+// ---------------------------------------------------------------------------
+
+/// Sets `targetField` (default "language") to the ISO 639-3 code whatlang
+/// detects in `sourceField` (default "content"). Text that cannot be detected,
+/// or whose confidence is below `config.threshold`, is tagged "und".
+pub struct LanguageDetectEnricher;
+
+#[async_trait]
+impl EnricherPlugin for LanguageDetectEnricher {
+    fn id(&self) -> &str { "language_detect" }
+    fn display_name(&self) -> &str { "Language Detection" }
+
+    async fn enrich(&self, record: &mut Record, config: &EnricherConfig) -> Result<(), EnricherError> {
+        let source = config.option_str("sourceField").unwrap_or("content");
+        let target = config.option_str("targetField").unwrap_or("language");
+        let text = record.get(source)
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| EnricherError::MissingField(source.to_string()))?;
+
+        let threshold = config.threshold.unwrap_or(0.0);
+        let detected = whatlang::detect(text).filter(|info| info.confidence() >= threshold);
+
+        let (code, confidence) = match detected {
+            Some(info) => (info.lang().code(), info.confidence()),
+            None => ("und", 0.0),
+        };
+        record.insert(target.to_string(), serde_json::json!(code));
+        record.insert(format!("{}Confidence", target), serde_json::json!((confidence * 1000.0).round() / 1000.0));
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Geo lookup
+// ---------------------------------------------------------------------------
+
+/// Resolves an IP address (`ipField`, default "ip") or a coordinate pair
+/// (`latField`/`lonField`, default "lat"/"lon") into `country` and `region`
+/// fields using lookup tables from the config:
+///   - `ipRanges`: `[{ "cidr": "203.0.113.0/24", "country": "AU", "region": "NSW" }]`,
+///     the longest matching prefix wins;
+///   - `areas`: `[{ "bounds": [minLat, minLon, maxLat, maxLon], "country": .., "region": .. }]`,
+///     the first containing box wins.
+///
+/// A record that matches no entry is left unchanged.
+pub struct GeoEnricher;
+
+impl GeoEnricher {
+    fn lookup_ip(ip: IpAddr, ranges: &[serde_json::Value]) -> Result<Option<&serde_json::Value>, EnricherError> {
+        let mut best: Option<(u32, &serde_json::Value)> = None;
+        for entry in ranges {
+            let cidr = entry.get("cidr").and_then(|v| v.as_str())
+                .ok_or_else(|| EnricherError::ConfigError("ipRanges entries need a 'cidr'".to_string()))?;
+            let (network, prefix) = parse_cidr(cidr)
+                .ok_or_else(|| EnricherError::ConfigError(format!("invalid CIDR '{}'", cidr)))?;
+            if cidr_contains(network, prefix, ip) && best.is_none_or(|(len, _)| prefix > len) {
+                best = Some((prefix, entry));
+            }
+        }
+        Ok(best.map(|(_, entry)| entry))
+    }
+
+    fn lookup_point(lat: f64, lon: f64, areas: &[serde_json::Value]) -> Result<Option<&serde_json::Value>, EnricherError> {
+        for entry in areas {
+            let bounds: Vec<f64> = entry.get("bounds").and_then(|v| v.as_array())
+                .map(|b| b.iter().filter_map(|n| n.as_f64()).collect())
+                .unwrap_or_default();
+            let [min_lat, min_lon, max_lat, max_lon] = bounds[..] else {
+                return Err(EnricherError::ConfigError("areas entries need 'bounds': [minLat, minLon, maxLat, maxLon]".to_string()));
+            };
+            if (min_lat..=max_lat).contains(&lat) && (min_lon..=max_lon).contains(&lon) {
+                return Ok(Some(entry));
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[async_trait]
+impl EnricherPlugin for GeoEnricher {
+    fn id(&self) -> &str { "geo" }
+    fn display_name(&self) -> &str { "Geo Lookup" }
+
+    async fn enrich(&self, record: &mut Record, config: &EnricherConfig) -> Result<(), EnricherError> {
+        let opts = config.options.as_ref();
+        let table = |key: &str| -> &[serde_json::Value] {
+            opts.and_then(|o| o.get(key)).and_then(|v| v.as_array()).map_or(&[], |a| a.as_slice())
+        };
+        let ip_field = config.option_str("ipField").unwrap_or("ip");
+        let lat_field = config.option_str("latField").unwrap_or("lat");
+        let lon_field = config.option_str("lonField").unwrap_or("lon");
+
+        let ip = record.get(ip_field).and_then(|v| v.as_str()).and_then(|s| s.trim().parse::<IpAddr>().ok());
+        let point = record.get(lat_field).and_then(|v| v.as_f64())
+            .zip(record.get(lon_field).and_then(|v| v.as_f64()));
+
+        let matched = match (ip, point) {
+            (Some(ip), _) => Self::lookup_ip(ip, table("ipRanges"))?,
+            (None, Some((lat, lon))) => Self::lookup_point(lat, lon, table("areas"))?,
+            (None, None) => return Err(EnricherError::MissingField(format!("{} or {}/{}", ip_field, lat_field, lon_field))),
+        };
+
+        if let Some(entry) = matched {
+            for key in ["country", "region"] {
+                if let Some(value) = entry.get(key).filter(|v| !v.is_null()) {
+                    record.insert(key.to_string(), value.clone());
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_cidr(cidr: &str) -> Option<(IpAddr, u32)> {
+    let (addr, prefix) = match cidr.split_once('/') {
+        Some((addr, prefix)) => (addr.parse::<IpAddr>().ok()?, Some(prefix.parse::<u32>().ok()?)),
+        None => (cidr.parse::<IpAddr>().ok()?, None),
+    };
+    let max = if addr.is_ipv4() { 32 } else { 128 };
+    let prefix = prefix.unwrap_or(max);
+    (prefix <= max).then_some((addr, prefix))
+}
+
+fn cidr_contains(network: IpAddr, prefix: u32, ip: IpAddr) -> bool {
+    let (network, ip, bits) = match (network, ip) {
+        (IpAddr::V4(n), IpAddr::V4(i)) => (u128::from(u32::from(n)), u128::from(u32::from(i)), 32),
+        (IpAddr::V6(n), IpAddr::V6(i)) => (u128::from(n), u128::from(i), 128),
+        _ => return false,
+    };
+    if prefix == 0 {
+        return true;
+    }
+    let shift = bits - prefix;
+    network >> shift == ip >> shift
+}
+
+// ---------------------------------------------------------------------------
+// Registry
+// ---------------------------------------------------------------------------
+
+/// A registry seeded with the built-in enrichers.
+pub fn default_registry() -> EnricherRegistry {
+    let mut registry = EnricherRegistry::new();
+    registry.register("language_detect", || Box::new(LanguageDetectEnricher));
+    registry.register("geo", || Box::new(GeoEnricher));
+    registry
+}
+
+/// Create a built-in enricher by id.
+pub fn create_provider(id: &str) -> Option<Box<dyn EnricherPlugin>> {
+    default_registry().create(id)
+}
+
+/// Ids of the built-in enrichers.
+pub fn available_providers() -> Vec<String> {
+    default_registry().ids().into_iter().map(String::from).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use futures::executor::block_on;
+
+    fn record(value: serde_json::Value) -> Record {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn options(value: serde_json::Value) -> EnricherConfig {
+        EnricherConfig { options: serde_json::from_value(value).ok(), ..Default::default() }
+    }
+
+    #[test]
+    fn language_detect_sets_language_field() {
+        let registry = default_registry();
+        let mut english = record(json!({ "content": "The quick brown fox jumps over the lazy dog while the farmer watches from the porch." }));
+        block_on(registry.enrich("language_detect", &mut english, &EnricherConfig::default())).unwrap();
+        assert_eq!(english["language"], json!("eng"));
+
+        let mut french = record(json!({ "body": "Le renard brun rapide saute par-dessus le chien paresseux pendant que le fermier regarde." }));
+        let config = options(json!({ "sourceField": "body", "targetField": "lang" }));
+        block_on(registry.enrich("language_detect", &mut french, &config)).unwrap();
+        assert_eq!(french["lang"], json!("fra"));
+        assert!(french["langConfidence"].as_f64().unwrap() > 0.0);
+
+        let strict = EnricherConfig { threshold: Some(1.1), ..Default::default() };
+        block_on(registry.enrich("language_detect", &mut english, &strict)).unwrap();
+        assert_eq!(english["language"], json!("und"));
+
+        let mut empty = Record::new();
+        assert!(matches!(
+            block_on(registry.enrich("language_detect", &mut empty, &EnricherConfig::default())),
+            Err(EnricherError::MissingField(field)) if field == "content"
+        ));
+    }
+
+    #[test]
+    fn geo_resolves_ip_and_coordinates() {
+        let config = options(json!({
+            "ipRanges": [
+                { "cidr": "203.0.0.0/8", "country": "AU" },
+                { "cidr": "203.0.113.0/24", "country": "AU", "region": "NSW" },
+                { "cidr": "2001:db8::/32", "country": "NZ", "region": "Auckland" }
+            ],
+            "areas": [
+                { "bounds": [45.0, 5.0, 48.0, 11.0], "country": "CH", "region": "Alps" }
+            ]
+        }));
+        let geo = create_provider("geo").unwrap();
+
+        let mut by_ip = record(json!({ "ip": "203.0.113.7" }));
+        block_on(geo.enrich(&mut by_ip, &config)).unwrap();
+        assert_eq!((&by_ip["country"], &by_ip["region"]), (&json!("AU"), &json!("NSW")));
+
+        let mut v6 = record(json!({ "ip": "2001:db8::1" }));
+        block_on(geo.enrich(&mut v6, &config)).unwrap();
+        assert_eq!(v6["region"], json!("Auckland"));
+
+        let mut by_point = record(json!({ "lat": 46.5, "lon": 7.9 }));
+        block_on(geo.enrich(&mut by_point, &config)).unwrap();
+        assert_eq!(by_point["country"], json!("CH"));
+
+        let mut unmatched = record(json!({ "ip": "198.51.100.1" }));
+        block_on(geo.enrich(&mut unmatched, &config)).unwrap();
+        assert!(!unmatched.contains_key("country"));
+
+        assert!(matches!(block_on(geo.enrich(&mut Record::new(), &config)), Err(EnricherError::MissingField(_))));
+    }
+
+    #[test]
+    fn registry_lists_and_rejects_unknown_enrichers() {
+        assert_eq!(available_providers(), vec!["language_detect", "geo"]);
+        let result = block_on(default_registry().enrich("sentiment", &mut Record::new(), &EnricherConfig::default()));
+        assert!(matches!(result, Err(EnricherError::UnknownProvider(id)) if id == "sentiment"));
+    }
+}
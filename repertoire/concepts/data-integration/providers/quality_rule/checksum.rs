@@ -0,0 +1,213 @@
+// Quality Rule Provider: Checksum Validation
+// Verifies check digits of card numbers (Luhn), IBANs (ISO 7064 mod 97-10)
+// and ISBN-13s.
+// Dimension: validity
+
+use std::collections::HashMap;
+
+pub const PROVIDER_ID: &str = "checksum";
+pub const PLUGIN_TYPE: &str = "quality_rule";
+
+#[derive(Debug, Clone)]
+pub struct FieldDef {
+    pub name: String,
+    pub field_type: String,
+    pub required: Option<bool>,
+    pub constraints: Option<HashMap<String, serde_json::Value>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RuleConfig {
+    pub options: Option<HashMap<String, serde_json::Value>>,
+    pub threshold: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Severity { Error, Warning, Info }
+
+#[derive(Debug, Clone)]
+pub struct RuleResult {
+    pub valid: bool,
+    pub message: Option<String>,
+    pub severity: Severity,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum QualityDimension {
+    Completeness, Uniqueness, Validity, Consistency, Timeliness, Accuracy,
+}
+
+pub struct ChecksumQualityProvider;
+
+impl ChecksumQualityProvider {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn validate(
+        &self,
+        value: &serde_json::Value,
+        field: &FieldDef,
+        _record: &HashMap<String, serde_json::Value>,
+        config: &RuleConfig,
+    ) -> RuleResult {
+        if value.is_null() {
+            return RuleResult { valid: true, message: None, severity: Severity::Warning };
+        }
+
+        let algorithm = config.options.as_ref()
+            .and_then(|o| o.get("algorithm"))
+            .and_then(|v| v.as_str())
+            .unwrap_or("luhn")
+            .to_lowercase();
+
+        let raw = match value.as_str() {
+            Some(s) => s.to_string(),
+            None => value.to_string(),
+        };
+        // Spaces and hyphens are presentation only.
+        let compact: String = raw.chars()
+            .filter(|c| !c.is_whitespace() && *c != '-')
+            .collect::<String>()
+            .to_uppercase();
+
+        let check = match algorithm.as_str() {
+            "luhn" => Self::luhn(&compact),
+            "iban" => Self::iban(&compact),
+            "isbn13" => Self::isbn13(&compact),
+            other => return RuleResult {
+                valid: false,
+                message: Some(format!(
+                    "Checksum rule for field '{}' is misconfigured: unknown algorithm '{}'.",
+                    field.name, other
+                )),
+                severity: Severity::Warning,
+            },
+        };
+
+        match check {
+            None => RuleResult {
+                valid: false,
+                message: Some(format!(
+                    "Field '{}' value '{}' is not a well-formed {} number.",
+                    field.name, raw, algorithm
+                )),
+                severity: Severity::Error,
+            },
+            Some((found, expected)) if found != expected => RuleResult {
+                valid: false,
+                message: Some(format!(
+                    "Field '{}' value '{}' failed {} checksum: check digit is {}, expected {}.",
+                    field.name, raw, algorithm, found, expected
+                )),
+                severity: Severity::Error,
+            },
+            Some(_) => RuleResult { valid: true, message: None, severity: Severity::Error },
+        }
+    }
+
+    /// Returns `(found, expected)` check digit for a Luhn-protected number.
+    fn luhn(digits: &str) -> Option<(String, String)> {
+        if digits.len() < 2 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        let (payload, found) = digits.split_at(digits.len() - 1);
+        // Walking the payload from the right, every first, third, ... digit is
+        // doubled, since the check digit itself will occupy the rightmost slot.
+        let sum: u32 = payload.bytes().rev().enumerate()
+            .map(|(i, b)| {
+                let d = (b - b'0') as u32;
+                if i % 2 == 0 {
+                    let doubled = d * 2;
+                    if doubled > 9 { doubled - 9 } else { doubled }
+                } else {
+                    d
+                }
+            })
+            .sum();
+        Some((found.to_string(), ((10 - sum % 10) % 10).to_string()))
+    }
+
+    /// Returns `(found, expected)` two-digit check for an IBAN.
+    fn iban(iban: &str) -> Option<(String, String)> {
+        if !(15..=34).contains(&iban.len()) || !iban.bytes().all(|b| b.is_ascii_alphanumeric()) {
+            return None;
+        }
+        let (country, rest) = iban.split_at(2);
+        let (found, bban) = rest.split_at(2);
+        if !country.bytes().all(|b| b.is_ascii_uppercase()) || !found.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+
+        // ISO 7064 mod 97-10 over BBAN + country + "00", letters as 10..35.
+        let remainder = format!("{}{}00", bban, country).chars()
+            .fold(0u32, |acc, c| {
+                let n = c.to_digit(36).unwrap();
+                if n >= 10 { (acc * 100 + n) % 97 } else { (acc * 10 + n) % 97 }
+            });
+        Some((found.to_string(), format!("{:02}", 98 - remainder)))
+    }
+
+    /// Returns `(found, expected)` check digit for an ISBN-13.
+    fn isbn13(digits: &str) -> Option<(String, String)> {
+        if digits.len() != 13 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        let (payload, found) = digits.split_at(12);
+        let sum: u32 = payload.bytes().enumerate()
+            .map(|(i, b)| (b - b'0') as u32 * if i % 2 == 0 { 1 } else { 3 })
+            .sum();
+        Some((found.to_string(), ((10 - sum % 10) % 10).to_string()))
+    }
+
+    pub fn applies_to(&self, field: &FieldDef) -> bool {
+        field.field_type.to_lowercase() == "string"
+    }
+
+    pub fn dimension(&self) -> QualityDimension {
+        QualityDimension::Validity
+    }
+}
+
+impl Default for ChecksumQualityProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    super::super::test_support::rule_fixtures!("account", "string");
+
+    fn algorithm(name: &str) -> RuleConfig {
+        config(json!({ "algorithm": name }))
+    }
+
+    #[test]
+    fn luhn_card_numbers() {
+        let provider = ChecksumQualityProvider::new();
+        let record = HashMap::new();
+
+        assert!(provider.validate(&json!("4111 1111 1111 1111"), &field(), &record, &algorithm("luhn")).valid);
+
+        let invalid = provider.validate(&json!("4111-1111-1111-1112"), &field(), &record, &algorithm("luhn"));
+        assert!(!invalid.valid);
+        assert!(invalid.message.unwrap().contains("check digit is 2, expected 1"));
+    }
+
+    #[test]
+    fn iban_and_isbn13() {
+        let provider = ChecksumQualityProvider::new();
+        let record = HashMap::new();
+
+        assert!(provider.validate(&json!("GB82 WEST 1234 5698 7654 32"), &field(), &record, &algorithm("iban")).valid);
+        let invalid = provider.validate(&json!("GB81WEST12345698765432"), &field(), &record, &algorithm("iban"));
+        assert!(invalid.message.unwrap().contains("check digit is 81, expected 82"));
+
+        assert!(provider.validate(&json!("978-0-306-40615-7"), &field(), &record, &algorithm("isbn13")).valid);
+        assert!(!provider.validate(&json!("978-0-306-40615-8"), &field(), &record, &algorithm("isbn13")).valid);
+    }
+}
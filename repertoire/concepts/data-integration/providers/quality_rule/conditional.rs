@@ -0,0 +1,120 @@
+// Quality Rule Wrapper: Conditional Validation
+// Applies an inner rule only to records matching a `when` predicate, e.g.
+// validate `tax_id` only when `country == "US"`. The predicate uses the
+// computed field-mapper expression language.
+
+use crate::field_mapper::{ComputedMapper, FieldMapperPlugin, MapperConfig};
+
+use super::quality_report::{
+    FieldDef, QualityDimension, QualityRulePlugin, Record, RuleConfig, RuleResult, Severity,
+};
+
+pub struct ConditionalRule {
+    inner: Box<dyn QualityRulePlugin>,
+    when: String,
+}
+
+impl ConditionalRule {
+    pub fn new(inner: Box<dyn QualityRulePlugin>, when: impl Into<String>) -> Self {
+        Self { inner, when: when.into() }
+    }
+
+    /// Evaluate the predicate against the record. Null, `false`, zero and
+    /// the empty string count as false.
+    fn predicate_holds(&self, record: &Record) -> Result<bool, String> {
+        let record = serde_json::Value::Object(
+            record.iter().map(|(k, v)| (k.clone(), v.clone())).collect(),
+        );
+        let result = ComputedMapper
+            .try_resolve(&record, &self.when, &MapperConfig::default())
+            .map_err(|e| e.to_string())?;
+
+        Ok(match result {
+            serde_json::Value::Null => false,
+            serde_json::Value::Bool(b) => b,
+            serde_json::Value::Number(n) => n.as_f64().is_some_and(|n| n != 0.0),
+            serde_json::Value::String(s) => !s.is_empty(),
+            _ => true,
+        })
+    }
+}
+
+impl QualityRulePlugin for ConditionalRule {
+    fn validate(
+        &self,
+        value: &serde_json::Value,
+        field: &FieldDef,
+        record: &Record,
+        config: &RuleConfig,
+    ) -> RuleResult {
+        match self.predicate_holds(record) {
+            Ok(true) => self.inner.validate(value, field, record, config),
+            Ok(false) => RuleResult::ok(),
+            Err(err) => RuleResult {
+                valid: false,
+                message: Some(format!(
+                    "Conditional rule for field '{}' has an invalid predicate '{}': {}",
+                    field.name, self.when, err
+                )),
+                severity: Severity::Error,
+            },
+        }
+    }
+
+    fn applies_to(&self, field: &FieldDef) -> bool {
+        self.inner.applies_to(field)
+    }
+
+    fn dimension(&self) -> QualityDimension {
+        self.inner.dimension()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    struct AlwaysFails;
+
+    impl QualityRulePlugin for AlwaysFails {
+        fn validate(&self, _: &serde_json::Value, field: &FieldDef, _: &Record, _: &RuleConfig) -> RuleResult {
+            RuleResult {
+                valid: false,
+                message: Some(format!("Field '{}' failed.", field.name)),
+                severity: Severity::Error,
+            }
+        }
+        fn applies_to(&self, field: &FieldDef) -> bool { field.name == "tax_id" }
+        fn dimension(&self) -> QualityDimension { QualityDimension::Validity }
+    }
+
+    fn record(country: &str) -> Record {
+        [("country".to_string(), json!(country)), ("tax_id".to_string(), json!("x"))]
+            .into_iter()
+            .collect()
+    }
+
+    #[test]
+    fn delegates_only_when_predicate_holds() {
+        let rule = ConditionalRule::new(Box::new(AlwaysFails), r#"country == "US""#);
+        let field = FieldDef { name: "tax_id".into(), field_type: "string".into(), required: None, constraints: None };
+        let config = RuleConfig { options: None, threshold: None };
+
+        assert!(!rule.validate(&json!("x"), &field, &record("US"), &config).valid);
+        assert!(rule.validate(&json!("x"), &field, &record("FR"), &config).valid);
+
+        assert!(rule.applies_to(&field));
+        assert_eq!(rule.dimension(), QualityDimension::Validity);
+    }
+
+    #[test]
+    fn reports_invalid_predicate() {
+        let rule = ConditionalRule::new(Box::new(AlwaysFails), "country ==");
+        let field = FieldDef { name: "tax_id".into(), field_type: "string".into(), required: None, constraints: None };
+        let config = RuleConfig { options: None, threshold: None };
+
+        let result = rule.validate(&json!("x"), &field, &record("US"), &config);
+        assert!(result.message.unwrap().contains("invalid predicate"));
+    }
+}
@@ -45,11 +45,22 @@ impl CrossFieldQualityProvider {
 
     pub fn validate(
         &self,
-        _value: &serde_json::Value,
+        value: &serde_json::Value,
         field: &FieldDef,
         record: &HashMap<String, serde_json::Value>,
         config: &RuleConfig,
     ) -> RuleResult {
+        if let Some(other_field) = config.options.as_ref()
+            .and_then(|o| o.get("otherField"))
+            .and_then(|v| v.as_str())
+        {
+            let operator = config.options.as_ref()
+                .and_then(|o| o.get("operator"))
+                .and_then(|v| v.as_str())
+                .unwrap_or("==");
+            return self.validate_against_field(value, field, other_field, operator, record);
+        }
+
         let expression = match config.options.as_ref()
             .and_then(|o| o.get("expression"))
             .and_then(|v| v.as_str())
@@ -114,6 +125,74 @@ impl CrossFieldQualityProvider {
         }
     }
 
+    /// Compare the field's own value with `record[other_field]`, e.g.
+    /// `endDate >= startDate`. Numbers (and numeric strings) compare
+    /// numerically; other strings compare lexically, which orders ISO 8601
+    /// dates correctly. The rule passes when either side is null.
+    fn validate_against_field(
+        &self,
+        value: &serde_json::Value,
+        field: &FieldDef,
+        other_field: &str,
+        operator: &str,
+        record: &HashMap<String, serde_json::Value>,
+    ) -> RuleResult {
+        let other = record.get(other_field).unwrap_or(&serde_json::Value::Null);
+        if value.is_null() || other.is_null() {
+            return RuleResult { valid: true, message: None, severity: Severity::Error };
+        }
+
+        let ordering = match (self.resolve_value(Some(value)), self.resolve_value(Some(other))) {
+            (Some(l), Some(r)) => l.partial_cmp(&r),
+            _ => match (value.as_str(), other.as_str()) {
+                (Some(l), Some(r)) => Some(l.cmp(r)),
+                _ => None,
+            },
+        };
+
+        let ordering = match ordering {
+            Some(o) => o,
+            None => return RuleResult {
+                valid: false,
+                message: Some(format!(
+                    "Cross-field rule for '{}': cannot compare {} with '{}' value {}.",
+                    field.name, value, other_field, other
+                )),
+                severity: Severity::Error,
+            },
+        };
+
+        let holds = match operator {
+            "<" => ordering.is_lt(),
+            "<=" => ordering.is_le(),
+            ">" => ordering.is_gt(),
+            ">=" => ordering.is_ge(),
+            "==" => ordering.is_eq(),
+            "!=" => ordering.is_ne(),
+            _ => return RuleResult {
+                valid: false,
+                message: Some(format!(
+                    "Cross-field rule for '{}' is misconfigured: unknown operator '{}'.",
+                    field.name, operator
+                )),
+                severity: Severity::Error,
+            },
+        };
+
+        if holds {
+            RuleResult { valid: true, message: None, severity: Severity::Error }
+        } else {
+            RuleResult {
+                valid: false,
+                message: Some(format!(
+                    "Cross-field validation failed: {} {} {} does not hold ({} vs {}).",
+                    field.name, operator, other_field, value, other
+                )),
+                severity: Severity::Error,
+            }
+        }
+    }
+
     fn evaluate_expression(
         &self,
         expression: &str,
@@ -217,3 +296,29 @@ impl Default for CrossFieldQualityProvider {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    super::super::test_support::rule_fixtures!("endDate", "date");
+
+    #[test]
+    fn compares_against_other_field() {
+        let provider = CrossFieldQualityProvider::new();
+        let config = config(json!({ "otherField": "startDate", "operator": ">=" }));
+        let record: HashMap<String, serde_json::Value> =
+            [("startDate".to_string(), json!("2024-03-10"))].into_iter().collect();
+
+        let before = provider.validate(&json!("2024-03-01"), &field(), &record, &config);
+        assert!(!before.valid);
+        assert!(before.message.unwrap().contains("endDate >= startDate"));
+
+        assert!(provider.validate(&json!("2024-03-10"), &field(), &record, &config).valid);
+
+        let numbers: HashMap<String, serde_json::Value> =
+            [("startDate".to_string(), json!(10))].into_iter().collect();
+        assert!(!provider.validate(&json!(9), &field(), &numbers, &config).valid);
+    }
+}
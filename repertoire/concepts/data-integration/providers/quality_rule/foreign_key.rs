@@ -1,8 +1,10 @@
 // Quality Rule Provider: Foreign Key Validation
-// Ensures referenced entities exist in the target content type's storage.
+// Ensures referenced entities exist in the target content type's storage, either
+// from registered reference data or through a cached StorageAdapter lookup.
 // Dimension: consistency
 
 use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
 
 pub const PROVIDER_ID: &str = "foreign_key";
 pub const PLUGIN_TYPE: &str = "quality_rule";
@@ -36,15 +38,72 @@ pub enum QualityDimension {
     Completeness, Uniqueness, Validity, Consistency, Timeliness, Accuracy,
 }
 
+/// Entity lookups against the target content type's storage, used when no
+/// reference data has been registered for a target.
+pub trait StorageAdapter: Send + Sync {
+    fn entity_exists(&self, entity_type: &str, key: &str) -> bool;
+
+    /// Existence of each key, in the order given.
+    fn entities_batch_exist(&self, entity_type: &str, keys: &[&str]) -> Vec<bool>;
+}
+
+/// Upper bound on cached lookups; the cache is cleared when it fills up.
+const CACHE_CAPACITY: usize = 10_000;
+
 pub struct ForeignKeyQualityProvider {
     reference_store: HashMap<String, HashSet<String>>,
+    storage: Option<Box<dyn StorageAdapter>>,
+    cache: Mutex<HashMap<(String, String), bool>>,
 }
 
 impl ForeignKeyQualityProvider {
     pub fn new() -> Self {
         Self {
             reference_store: HashMap::new(),
+            storage: None,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Resolve references through `storage`, caching each answer.
+    pub fn with_storage(storage: Box<dyn StorageAdapter>) -> Self {
+        Self { storage: Some(storage), ..Self::new() }
+    }
+
+    /// Fill the cache for `keys` with a single `entities_batch_exist` call,
+    /// so a bulk validation run does not hit storage once per record.
+    pub fn prewarm(&self, entity_type: &str, keys: &[&str]) {
+        let Some(storage) = &self.storage else { return };
+        let exists = storage.entities_batch_exist(entity_type, keys);
+
+        let mut cache = self.cache.lock().unwrap();
+        for (key, found) in keys.iter().zip(exists) {
+            Self::remember(&mut cache, (entity_type.to_string(), key.to_string()), found);
+        }
+    }
+
+    pub fn clear_cache(&self) {
+        self.cache.lock().unwrap().clear();
+    }
+
+    /// Check the cache first, then storage, remembering the answer.
+    fn lookup(&self, storage: &dyn StorageAdapter, entity_type: &str, key: &str) -> bool {
+        let cache_key = (entity_type.to_string(), key.to_string());
+        if let Some(found) = self.cache.lock().unwrap().get(&cache_key) {
+            return *found;
         }
+
+        let found = storage.entity_exists(entity_type, key);
+        Self::remember(&mut self.cache.lock().unwrap(), cache_key, found);
+        found
+    }
+
+    /// Cache one answer, clearing the cache first if it is full.
+    fn remember(cache: &mut HashMap<(String, String), bool>, key: (String, String), found: bool) {
+        if cache.len() >= CACHE_CAPACITY && !cache.contains_key(&key) {
+            cache.clear();
+        }
+        cache.insert(key, found);
     }
 
     /// Register known reference values for a given target type and field.
@@ -86,10 +145,16 @@ impl ForeignKeyQualityProvider {
             },
         };
 
+        let ref_value = match value.as_str() {
+            Some(s) => s.to_string(),
+            None => value.to_string().trim_matches('"').to_string(),
+        };
+
         let store_key = format!("{}::{}", target_type, target_field);
-        let store = match self.reference_store.get(&store_key) {
-            Some(s) => s,
-            None => return RuleResult {
+        let exists = match (self.reference_store.get(&store_key), &self.storage) {
+            (Some(store), _) => store.contains(&ref_value),
+            (None, Some(storage)) => self.lookup(storage.as_ref(), target_type, &ref_value),
+            (None, None) => return RuleResult {
                 valid: false,
                 message: Some(format!(
                     "Foreign key rule for field '{}': no reference data loaded for {}.{}.",
@@ -99,12 +164,7 @@ impl ForeignKeyQualityProvider {
             },
         };
 
-        let ref_value = match value.as_str() {
-            Some(s) => s.to_string(),
-            None => value.to_string().trim_matches('"').to_string(),
-        };
-
-        if !store.contains(&ref_value) {
+        if !exists {
             return RuleResult {
                 valid: false,
                 message: Some(format!(
@@ -136,3 +196,77 @@ impl Default for ForeignKeyQualityProvider {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[derive(Default)]
+    struct MockStorage {
+        single_calls: AtomicUsize,
+        batch_calls: AtomicUsize,
+    }
+
+    impl StorageAdapter for Arc<MockStorage> {
+        fn entity_exists(&self, entity_type: &str, key: &str) -> bool {
+            self.single_calls.fetch_add(1, Ordering::SeqCst);
+            entity_type == "user" && key.starts_with('u')
+        }
+
+        fn entities_batch_exist(&self, entity_type: &str, keys: &[&str]) -> Vec<bool> {
+            self.batch_calls.fetch_add(1, Ordering::SeqCst);
+            keys.iter().map(|k| entity_type == "user" && k.starts_with('u')).collect()
+        }
+    }
+
+    super::super::test_support::rule_fixtures!("owner", "reference");
+
+    fn user_ids() -> RuleConfig {
+        config(json!({ "targetType": "user", "targetField": "id" }))
+    }
+
+    #[test]
+    fn storage_lookups_are_cached() {
+        let storage = Arc::new(MockStorage::default());
+        let provider = ForeignKeyQualityProvider::with_storage(Box::new(storage.clone()));
+        let record = HashMap::new();
+
+        assert!(provider.validate(&json!("u1"), &field(), &record, &user_ids()).valid);
+        assert!(provider.validate(&json!("u1"), &field(), &record, &user_ids()).valid);
+        assert_eq!(storage.single_calls.load(Ordering::SeqCst), 1);
+
+        let missing = provider.validate(&json!("x9"), &field(), &record, &user_ids());
+        assert!(!missing.valid);
+        assert!(missing.message.unwrap().contains("'x9' which does not exist in user.id"));
+    }
+
+    #[test]
+    fn prewarm_uses_one_batch_call() {
+        let storage = Arc::new(MockStorage::default());
+        let provider = ForeignKeyQualityProvider::with_storage(Box::new(storage.clone()));
+        let record = HashMap::new();
+
+        provider.prewarm("user", &["u1", "u2", "x3"]);
+        assert!(provider.validate(&json!("u2"), &field(), &record, &user_ids()).valid);
+        assert!(!provider.validate(&json!("x3"), &field(), &record, &user_ids()).valid);
+
+        assert_eq!(storage.batch_calls.load(Ordering::SeqCst), 1);
+        assert_eq!(storage.single_calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn prewarm_stays_within_cache_capacity() {
+        let storage = Arc::new(MockStorage::default());
+        let provider = ForeignKeyQualityProvider::with_storage(Box::new(storage.clone()));
+        let keys: Vec<String> = (0..CACHE_CAPACITY + 10).map(|i| format!("u{}", i)).collect();
+        let keys: Vec<&str> = keys.iter().map(String::as_str).collect();
+
+        provider.prewarm("user", &keys);
+        assert!(provider.cache.lock().unwrap().len() <= CACHE_CAPACITY);
+        assert!(provider.validate(&json!(keys[keys.len() - 1]), &field(), &HashMap::new(), &user_ids()).valid);
+        assert_eq!(storage.single_calls.load(Ordering::SeqCst), 0);
+    }
+}
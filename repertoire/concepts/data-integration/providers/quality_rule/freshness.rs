@@ -1,10 +1,13 @@
 // Quality Rule Provider: Freshness Validation
-// Ensures data timestamps are within an acceptable recency window.
+// Ensures data timestamps are within an acceptable recency window, and optionally
+// not dated in the future beyond an allowed clock skew.
 // Dimension: timeliness
 
 use std::collections::HashMap;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+
 pub const PROVIDER_ID: &str = "freshness";
 pub const PLUGIN_TYPE: &str = "quality_rule";
 
@@ -95,25 +98,42 @@ impl FreshnessQualityProvider {
             },
         };
 
+        let opts = config.options.as_ref();
         let max_age_ms = match self.parse_max_age(
-            config.options.as_ref().and_then(|o| o.get("maxAge"))
+            opts.and_then(|o| o.get("maxAgeSeconds").or_else(|| o.get("maxAge")))
         ) {
             Some(ms) => ms,
             None => return RuleResult {
                 valid: false,
                 message: Some(format!(
-                    "Freshness rule for '{}' is misconfigured: invalid or missing maxAge.",
+                    "Freshness rule for '{}' is misconfigured: invalid or missing maxAgeSeconds.",
                     field.name
                 )),
                 severity: Severity::Warning,
             },
         };
+        let max_skew_ms = opts.and_then(|o| o.get("maxSkewSeconds"))
+            .and_then(|v| self.parse_max_age(Some(v)));
 
         let now_ms = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .map(|d| d.as_millis() as u64)
             .unwrap_or(0);
 
+        if let Some(max_skew_ms) = max_skew_ms {
+            let ahead_ms = timestamp_ms.saturating_sub(now_ms);
+            if ahead_ms > max_skew_ms {
+                return RuleResult {
+                    valid: false,
+                    message: Some(format!(
+                        "Field '{}' timestamp is {}s in the future, maximum allowed skew is {}s.",
+                        field.name, ahead_ms / 1000, max_skew_ms / 1000
+                    )),
+                    severity: Severity::Error,
+                };
+            }
+        }
+
         let age_ms = now_ms.saturating_sub(timestamp_ms);
 
         if age_ms > max_age_ms {
@@ -122,8 +142,8 @@ impl FreshnessQualityProvider {
             return RuleResult {
                 valid: false,
                 message: Some(format!(
-                    "Field '{}' data is stale: age is {:.1}h, maximum allowed is {:.1}h.",
-                    field.name, age_hours, max_age_hours
+                    "Field '{}' data is stale: age is {}s ({:.1}h), maximum allowed is {:.1}h.",
+                    field.name, age_ms / 1000, age_hours, max_age_hours
                 )),
                 severity: Severity::Error,
             };
@@ -141,9 +161,24 @@ impl FreshnessQualityProvider {
             let ms = if n > 1_000_000_000_000.0 { n as u64 } else { (n * 1000.0) as u64 };
             return Some(ms);
         }
-        // ISO 8601 date string is not parsed here in pure Rust without chrono;
-        // callers should provide epoch timestamps for Rust usage.
-        None
+        value.as_str().and_then(|s| Self::parse_iso8601(s.trim()))
+    }
+
+    /// Parse the date forms the range rule compares — `YYYY-MM-DD` and
+    /// `YYYY-MM-DD[T ]HH:MM[:SS[.fff]][Z|±HH:MM]` — into epoch milliseconds.
+    /// Values without an offset are taken as UTC.
+    fn parse_iso8601(s: &str) -> Option<u64> {
+        let s = s.replacen(['t', ' '], "T", 1);
+        let with_offset = DateTime::parse_from_rfc3339(&s)
+            .or_else(|_| DateTime::parse_from_str(&s, "%Y-%m-%dT%H:%M%#z"));
+        if let Ok(dt) = with_offset {
+            return u64::try_from(dt.timestamp_millis()).ok();
+        }
+        let naive = ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%dT%H:%M"]
+            .iter()
+            .find_map(|format| NaiveDateTime::parse_from_str(&s, format).ok())
+            .or_else(|| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok()?.and_hms_opt(0, 0, 0))?;
+        u64::try_from(naive.and_utc().timestamp_millis()).ok()
     }
 
     fn parse_max_age(&self, max_age: Option<&serde_json::Value>) -> Option<u64> {
@@ -200,3 +235,72 @@ impl Default for FreshnessQualityProvider {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    super::super::test_support::rule_fixtures!("updatedAt", "datetime");
+
+    fn now_secs() -> u64 {
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs()
+    }
+
+    #[test]
+    fn parses_iso8601_dates() {
+        assert_eq!(FreshnessQualityProvider::parse_iso8601("1970-01-02"), Some(86_400_000));
+        assert_eq!(FreshnessQualityProvider::parse_iso8601("2024-02-29T12:00:00.5Z"), Some(1_709_208_000_500));
+        assert_eq!(FreshnessQualityProvider::parse_iso8601("2024-02-29T14:00:00+02:00"), Some(1_709_208_000_000));
+        assert_eq!(FreshnessQualityProvider::parse_iso8601("not a date"), None);
+    }
+
+    #[test]
+    fn rejects_impossible_and_multibyte_dates() {
+        assert_eq!(FreshnessQualityProvider::parse_iso8601("2024-02-31"), None);
+        assert_eq!(FreshnessQualityProvider::parse_iso8601("2023-02-29T00:00:00Z"), None);
+        assert_eq!(FreshnessQualityProvider::parse_iso8601("2024-01-01T10:00:00.\u{e9}5Z"), None);
+        assert_eq!(FreshnessQualityProvider::parse_iso8601("2024-01-01T10:00:00+0\u{e9}"), None);
+        assert_eq!(FreshnessQualityProvider::parse_iso8601("2024-01-01T10:00:00+\u{e9}"), None);
+        assert_eq!(FreshnessQualityProvider::parse_iso8601("2024-01-01T10:00:00.12345Z"), Some(1_704_103_200_123));
+    }
+
+    #[test]
+    fn flags_stale_timestamp_with_age() {
+        let provider = FreshnessQualityProvider::new();
+        let result = provider.validate(
+            &json!("2020-01-01T00:00:00Z"),
+            &field(),
+            &HashMap::new(),
+            &config(json!({ "maxAgeSeconds": 3600 })),
+        );
+        assert!(!result.valid);
+        assert!(result.message.unwrap().contains("stale"));
+
+        let fresh = provider.validate(
+            &json!(now_secs() - 60),
+            &field(),
+            &HashMap::new(),
+            &config(json!({ "maxAgeSeconds": 3600 })),
+        );
+        assert!(fresh.valid);
+    }
+
+    #[test]
+    fn flags_future_timestamp_beyond_skew() {
+        let provider = FreshnessQualityProvider::new();
+        let tomorrow = json!(now_secs() + 86_400);
+
+        let result = provider.validate(
+            &tomorrow,
+            &field(),
+            &HashMap::new(),
+            &config(json!({ "maxAgeSeconds": 3600, "maxSkewSeconds": 300 })),
+        );
+        assert!(!result.valid);
+        assert!(result.message.unwrap().contains("in the future"));
+
+        let unchecked = provider.validate(&tomorrow, &field(), &HashMap::new(), &config(json!({ "maxAgeSeconds": 3600 })));
+        assert!(unchecked.valid);
+    }
+}
@@ -132,7 +132,7 @@ impl NoDuplicatesQualityProvider {
                 let similarities: Vec<f64> = a.iter()
                     .map(|(k, v)| {
                         let bv = b.get(k).map(String::as_str).unwrap_or("");
-                        self.levenshtein_similarity(v, bv)
+                        levenshtein_similarity(v, bv)
                     })
                     .collect();
                 let avg = similarities.iter().sum::<f64>() / similarities.len() as f64;
@@ -150,31 +150,6 @@ impl NoDuplicatesQualityProvider {
         }
     }
 
-    fn levenshtein_similarity(&self, a: &str, b: &str) -> f64 {
-        if a == b { return 1.0; }
-        let max_len = a.len().max(b.len());
-        if max_len == 0 { return 1.0; }
-
-        let a_bytes = a.as_bytes();
-        let b_bytes = b.as_bytes();
-        let mut matrix = vec![vec![0usize; b.len() + 1]; a.len() + 1];
-
-        for i in 0..=a.len() { matrix[i][0] = i; }
-        for j in 0..=b.len() { matrix[0][j] = j; }
-
-        for i in 1..=a.len() {
-            for j in 1..=b.len() {
-                let cost = if a_bytes[i - 1] == b_bytes[j - 1] { 0 } else { 1 };
-                matrix[i][j] = (matrix[i - 1][j] + 1)
-                    .min(matrix[i][j - 1] + 1)
-                    .min(matrix[i - 1][j - 1] + cost);
-            }
-        }
-
-        let distance = matrix[a.len()][b.len()];
-        1.0 - (distance as f64 / max_len as f64)
-    }
-
     fn soundex(&self, s: &str) -> String {
         let upper: String = s.to_uppercase().chars().filter(|c| c.is_ascii_alphabetic()).collect();
         if upper.is_empty() { return "0000".to_string(); }
@@ -226,6 +201,35 @@ impl NoDuplicatesQualityProvider {
     }
 }
 
+/// Edit distance between two strings, counted in characters.
+pub fn levenshtein_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut matrix = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+
+    for (i, row) in matrix.iter_mut().enumerate() { row[0] = i; }
+    for (j, cell) in matrix[0].iter_mut().enumerate() { *cell = j; }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            matrix[i][j] = (matrix[i - 1][j] + 1)
+                .min(matrix[i][j - 1] + 1)
+                .min(matrix[i - 1][j - 1] + cost);
+        }
+    }
+
+    matrix[a.len()][b.len()]
+}
+
+/// Similarity in 0–1 as `1 - distance / length` of the longer string.
+pub fn levenshtein_similarity(a: &str, b: &str) -> f64 {
+    if a == b { return 1.0; }
+    let max_len = a.chars().count().max(b.chars().count());
+    if max_len == 0 { return 1.0; }
+    1.0 - (levenshtein_distance(a, b) as f64 / max_len as f64)
+}
+
 impl Default for NoDuplicatesQualityProvider {
     fn default() -> Self {
         Self::new()
@@ -0,0 +1,181 @@
+// Quality Rule Provider: Statistical Outlier Detection
+// Flags numeric values outside a z-score or IQR fence. Distribution parameters
+// come from config, computed by an upstream profiling pass.
+// Dimension: accuracy
+
+use std::collections::HashMap;
+
+pub const PROVIDER_ID: &str = "outlier";
+pub const PLUGIN_TYPE: &str = "quality_rule";
+
+#[derive(Debug, Clone)]
+pub struct FieldDef {
+    pub name: String,
+    pub field_type: String,
+    pub required: Option<bool>,
+    pub constraints: Option<HashMap<String, serde_json::Value>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RuleConfig {
+    pub options: Option<HashMap<String, serde_json::Value>>,
+    pub threshold: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Severity { Error, Warning, Info }
+
+#[derive(Debug, Clone)]
+pub struct RuleResult {
+    pub valid: bool,
+    pub message: Option<String>,
+    pub severity: Severity,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum QualityDimension {
+    Completeness, Uniqueness, Validity, Consistency, Timeliness, Accuracy,
+}
+
+pub struct OutlierQualityProvider;
+
+impl OutlierQualityProvider {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn validate(
+        &self,
+        value: &serde_json::Value,
+        field: &FieldDef,
+        _record: &HashMap<String, serde_json::Value>,
+        config: &RuleConfig,
+    ) -> RuleResult {
+        if value.is_null() {
+            return RuleResult { valid: true, message: None, severity: Severity::Warning };
+        }
+
+        let num = match Self::parse_as_f64(value) {
+            Some(n) => n,
+            None => return RuleResult {
+                valid: false,
+                message: Some(format!(
+                    "Field '{}' value cannot be parsed as a number for outlier check.", field.name
+                )),
+                severity: Severity::Error,
+            },
+        };
+
+        let option = |key: &str| config.options.as_ref()
+            .and_then(|o| o.get(key))
+            .and_then(Self::parse_as_f64);
+
+        if let (Some(mean), Some(stddev)) = (option("mean"), option("stddev")) {
+            let threshold = option("zThreshold").unwrap_or(3.0);
+            if stddev <= 0.0 {
+                return RuleResult { valid: true, message: None, severity: Severity::Warning };
+            }
+            let z = (num - mean) / stddev;
+            if z.abs() > threshold {
+                return RuleResult {
+                    valid: false,
+                    message: Some(format!(
+                        "Field '{}' value {} is an outlier: z-score {:.2} exceeds threshold {}.",
+                        field.name, num, z, threshold
+                    )),
+                    severity: Severity::Warning,
+                };
+            }
+            return RuleResult { valid: true, message: None, severity: Severity::Warning };
+        }
+
+        if let (Some(q1), Some(q3)) = (option("iqrLow"), option("iqrHigh")) {
+            // Tukey fences: [Q1 - k * IQR, Q3 + k * IQR].
+            let k = option("iqrMultiplier").unwrap_or(1.5);
+            let iqr = q3 - q1;
+            let (lower, upper) = (q1 - k * iqr, q3 + k * iqr);
+            if num < lower || num > upper {
+                let (side, fence) = if num < lower { ("below", lower) } else { ("above", upper) };
+                let position = if iqr > 0.0 {
+                    format!(" ({:.2} IQRs from Q1)", (num - q1) / iqr)
+                } else {
+                    String::new()
+                };
+                return RuleResult {
+                    valid: false,
+                    message: Some(format!(
+                        "Field '{}' value {} is an outlier: {} the fence {}{}.",
+                        field.name, num, side, fence, position
+                    )),
+                    severity: Severity::Warning,
+                };
+            }
+            return RuleResult { valid: true, message: None, severity: Severity::Warning };
+        }
+
+        RuleResult {
+            valid: false,
+            message: Some(format!(
+                "Outlier rule for field '{}' is misconfigured: provide mean/stddev or iqrLow/iqrHigh.",
+                field.name
+            )),
+            severity: Severity::Warning,
+        }
+    }
+
+    fn parse_as_f64(value: &serde_json::Value) -> Option<f64> {
+        if let Some(n) = value.as_f64() { return Some(n); }
+        if let Some(s) = value.as_str() { return s.trim().parse::<f64>().ok(); }
+        None
+    }
+
+    pub fn applies_to(&self, field: &FieldDef) -> bool {
+        let numeric_types = ["number", "integer", "float"];
+        numeric_types.contains(&field.field_type.to_lowercase().as_str())
+    }
+
+    pub fn dimension(&self) -> QualityDimension {
+        QualityDimension::Accuracy
+    }
+}
+
+impl Default for OutlierQualityProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    super::super::test_support::rule_fixtures!("amount", "number");
+
+    #[test]
+    fn z_score_bounds() {
+        let provider = OutlierQualityProvider::new();
+        let config = config(json!({ "mean": 100, "stddev": 10, "zThreshold": 3 }));
+        let record = HashMap::new();
+
+        let far = provider.validate(&json!(140), &field(), &record, &config);
+        assert!(!far.valid);
+        assert!(far.message.unwrap().contains("z-score 4.00"));
+
+        assert!(provider.validate(&json!(115), &field(), &record, &config).valid);
+    }
+
+    #[test]
+    fn iqr_fences() {
+        let provider = OutlierQualityProvider::new();
+        let config = config(json!({ "iqrLow": 10, "iqrHigh": 20 }));
+        let record = HashMap::new();
+
+        let high = provider.validate(&json!(40), &field(), &record, &config);
+        assert!(!high.valid);
+        assert!(high.message.unwrap().contains("above the fence 35"));
+
+        assert!(provider.validate(&json!(-4), &field(), &record, &config).valid);
+        assert!(!provider.validate(&json!(-6), &field(), &record, &config).valid);
+    }
+}
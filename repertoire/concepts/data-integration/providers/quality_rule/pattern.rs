@@ -1,5 +1,6 @@
 // Quality Rule Provider: Pattern (Regex) Validation
-// Validates that string values match a configured regular expression.
+// Validates that string values match a configured regular expression or a
+// named preset (email, url, date, datetime, uuid).
 // Dimension: validity
 
 use std::collections::HashMap;
@@ -61,6 +62,10 @@ impl PatternQualityProvider {
         let pattern_str = match config.options.as_ref()
             .and_then(|o| o.get("pattern"))
             .and_then(|v| v.as_str())
+            .or_else(|| config.options.as_ref()
+                .and_then(|o| o.get("preset"))
+                .and_then(|v| v.as_str())
+                .and_then(Self::preset))
         {
             Some(p) => p.to_string(),
             None => return RuleResult {
@@ -120,6 +125,18 @@ impl PatternQualityProvider {
         RuleResult { valid: true, message: None, severity: Severity::Error }
     }
 
+    /// Built-in patterns selectable with `options.preset` instead of `pattern`.
+    pub fn preset(name: &str) -> Option<&'static str> {
+        match name {
+            "email" => Some(r"^[^@\s]+@[^@\s]+\.[^@\s]+$"),
+            "url" => Some(r"^https?://[^\s/?#]+\.[^\s/?#]+(?:[/?#]\S*)?$"),
+            "date" => Some(r"^\d{4}-\d{2}-\d{2}$"),
+            "datetime" => Some(r"^\d{4}-\d{2}-\d{2}[T ]\d{2}:\d{2}(?::\d{2}(?:\.\d+)?)?(?:Z|[+-]\d{2}:?\d{2})?$"),
+            "uuid" => Some(r"^(?i)[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}$"),
+            _ => None,
+        }
+    }
+
     pub fn applies_to(&self, field: &FieldDef) -> bool {
         field.field_type.to_lowercase() == "string"
     }
@@ -0,0 +1,196 @@
+// Quality Rule Profiling: Column Profile
+// Summarizes the values of one column (nulls, distinct values, numeric
+// statistics, frequent values, inferred type and format) so range, enum and
+// outlier rules can be configured from the data itself.
+
+use std::collections::HashMap;
+
+use super::pattern::PatternQualityProvider;
+
+/// Number of most frequent values kept in a profile.
+pub const TOP_N: usize = 5;
+
+/// Column type inferred from the non-null values. The names match the
+/// `field_type` strings used by `FieldDef`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FieldType {
+    Boolean,
+    Integer,
+    Number,
+    Date,
+    DateTime,
+    String,
+    Array,
+    Object,
+    /// Values of incompatible types, e.g. numbers and free text.
+    Mixed,
+    /// No non-null values to infer from.
+    Unknown,
+}
+
+impl FieldType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Boolean => "boolean",
+            Self::Integer => "integer",
+            Self::Number => "number",
+            Self::Date => "date",
+            Self::DateTime => "datetime",
+            Self::String => "string",
+            Self::Array => "array",
+            Self::Object => "object",
+            Self::Mixed => "mixed",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NumericStats {
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    /// Population standard deviation.
+    pub stddev: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct ColumnProfile {
+    pub total_count: usize,
+    pub null_count: usize,
+    pub distinct_count: usize,
+    /// Statistics over numbers and numeric strings, if there are any.
+    pub numeric: Option<NumericStats>,
+    /// Up to `TOP_N` values with their counts, most frequent first.
+    pub top_values: Vec<(serde_json::Value, usize)>,
+    pub inferred_type: FieldType,
+    /// Pattern preset every non-null string value matches, e.g. "email".
+    pub format: Option<&'static str>,
+}
+
+/// Profile a column of values.
+pub fn profile_column(values: &[serde_json::Value]) -> ColumnProfile {
+    let non_null: Vec<&serde_json::Value> = values.iter().filter(|v| !v.is_null()).collect();
+
+    // Count by serialized value, remembering first appearance for stable ties.
+    let mut counts: HashMap<String, (usize, usize, &serde_json::Value)> = HashMap::new();
+    for (i, value) in non_null.iter().enumerate() {
+        counts.entry(value.to_string()).or_insert((0, i, value)).0 += 1;
+    }
+    let mut ranked: Vec<_> = counts.values().collect();
+    ranked.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    let top_values = ranked.iter()
+        .take(TOP_N)
+        .map(|(count, _, value)| ((*value).clone(), *count))
+        .collect();
+
+    let numbers: Vec<f64> = non_null.iter().filter_map(|v| as_number(v)).collect();
+    let numeric = (!numbers.is_empty()).then(|| {
+        let n = numbers.len() as f64;
+        let mean = numbers.iter().sum::<f64>() / n;
+        let variance = numbers.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+        NumericStats {
+            min: numbers.iter().copied().fold(f64::INFINITY, f64::min),
+            max: numbers.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            mean,
+            stddev: variance.sqrt(),
+        }
+    });
+
+    let strings: Vec<&str> = non_null.iter().filter_map(|v| v.as_str()).collect();
+    let format = detect_format(&strings);
+
+    ColumnProfile {
+        total_count: values.len(),
+        null_count: values.len() - non_null.len(),
+        distinct_count: counts.len(),
+        numeric,
+        top_values,
+        inferred_type: infer_type(&non_null, format),
+        format,
+    }
+}
+
+fn as_number(value: &serde_json::Value) -> Option<f64> {
+    match value {
+        serde_json::Value::Number(n) => n.as_f64(),
+        serde_json::Value::String(s) => s.trim().parse::<f64>().ok().filter(|n| n.is_finite()),
+        _ => None,
+    }
+}
+
+fn detect_format(strings: &[&str]) -> Option<&'static str> {
+    if strings.is_empty() {
+        return None;
+    }
+    ["email", "url", "uuid", "date", "datetime"].into_iter().find(|name| {
+        PatternQualityProvider::preset(name)
+            .and_then(|pattern| regex::Regex::new(pattern).ok())
+            .is_some_and(|re| strings.iter().all(|s| re.is_match(s.trim())))
+    })
+}
+
+fn infer_type(values: &[&serde_json::Value], format: Option<&str>) -> FieldType {
+    let kind = |value: &serde_json::Value| match value {
+        serde_json::Value::Bool(_) => FieldType::Boolean,
+        serde_json::Value::Array(_) => FieldType::Array,
+        serde_json::Value::Object(_) => FieldType::Object,
+        v => match as_number(v) {
+            Some(n) if n.fract() == 0.0 => FieldType::Integer,
+            Some(_) => FieldType::Number,
+            None => match format {
+                Some("date") => FieldType::Date,
+                Some("datetime") => FieldType::DateTime,
+                _ => FieldType::String,
+            },
+        },
+    };
+
+    values.iter().fold(FieldType::Unknown, |acc, value| {
+        match (acc, kind(value)) {
+            (FieldType::Unknown, t) => t,
+            (a, b) if a == b => a,
+            (FieldType::Integer, FieldType::Number) | (FieldType::Number, FieldType::Integer) => FieldType::Number,
+            _ => FieldType::Mixed,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn profiles_mixed_column() {
+        let values = [json!(1), json!("2"), json!(3.5), json!(null), json!("abc"), json!(1), json!(null)];
+        let profile = profile_column(&values);
+
+        assert_eq!(profile.inferred_type, FieldType::Mixed);
+        assert_eq!(profile.total_count, 7);
+        assert_eq!(profile.null_count, 2);
+        assert_eq!(profile.distinct_count, 4);
+        assert_eq!(profile.top_values[0], (json!(1), 2));
+        assert_eq!(profile.format, None);
+
+        let stats = profile.numeric.unwrap();
+        assert_eq!((stats.min, stats.max, stats.mean), (1.0, 3.5, 1.875));
+        assert!((stats.stddev - 1.0232).abs() < 1e-4);
+    }
+
+    #[test]
+    fn infers_types_and_formats() {
+        let numbers = profile_column(&[json!(1), json!(2.5), json!("3")]);
+        assert_eq!(numbers.inferred_type, FieldType::Number);
+
+        let emails = profile_column(&[json!("ada@example.com"), json!("bob@example.org"), json!(null)]);
+        assert_eq!(emails.inferred_type, FieldType::String);
+        assert_eq!(emails.format, Some("email"));
+
+        let dates = profile_column(&[json!("2024-01-31"), json!("2024-02-01")]);
+        assert_eq!(dates.inferred_type, FieldType::Date);
+        assert_eq!(dates.inferred_type.as_str(), "date");
+
+        assert_eq!(profile_column(&[json!(null)]).inferred_type, FieldType::Unknown);
+    }
+}
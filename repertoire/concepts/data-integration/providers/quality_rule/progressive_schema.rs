@@ -0,0 +1,147 @@
+// Quality Rule Profiling: Progressive Schema
+// Infers a schema from records as they stream in, widening field types on
+// conflict, so quality rules can be set up for sources without a declared schema.
+
+use std::collections::HashMap;
+
+use super::profile::FieldType;
+use super::quality_report::{FieldDef, Record};
+
+#[derive(Debug, Clone)]
+struct ObservedField {
+    name: String,
+    field_type: FieldType,
+    nullable: bool,
+    /// Records the field appeared in, null or not.
+    present: usize,
+}
+
+/// Builds a schema one record at a time. A field's type starts at the first
+/// non-null value seen and widens `integer` -> `number` -> `string` as
+/// conflicting values arrive; any other conflict also widens to `string`.
+#[derive(Debug, Clone, Default)]
+pub struct SchemaBuilder {
+    fields: Vec<ObservedField>,
+    index: HashMap<String, usize>,
+    records: usize,
+}
+
+impl SchemaBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn observe(&mut self, record: &Record) {
+        self.records += 1;
+        // A record does not keep its keys in order, so fields first seen
+        // together are added by name to keep the schema deterministic.
+        let mut entries: Vec<_> = record.iter().collect();
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+        for (name, value) in entries {
+            let position = *self.index.entry(name.clone()).or_insert_with(|| {
+                self.fields.push(ObservedField {
+                    name: name.clone(),
+                    field_type: FieldType::Unknown,
+                    nullable: false,
+                    present: 0,
+                });
+                self.fields.len() - 1
+            });
+            let field = &mut self.fields[position];
+            field.present += 1;
+            match value_type(value) {
+                None => field.nullable = true,
+                Some(observed) => field.field_type = widen(field.field_type, observed),
+            }
+        }
+    }
+
+    /// Number of records observed so far.
+    pub fn record_count(&self) -> usize {
+        self.records
+    }
+
+    /// Field definitions in first-seen order, by name within the record that
+    /// introduced them. A field is `required` when it
+    /// appeared in every record; `nullable` is recorded as a constraint.
+    pub fn build(&self) -> Vec<FieldDef> {
+        self.fields.iter()
+            .map(|field| FieldDef {
+                name: field.name.clone(),
+                field_type: field.field_type.as_str().to_string(),
+                required: Some(field.present == self.records),
+                constraints: Some(HashMap::from([
+                    ("nullable".to_string(), serde_json::Value::Bool(field.nullable)),
+                ])),
+            })
+            .collect()
+    }
+}
+
+fn value_type(value: &serde_json::Value) -> Option<FieldType> {
+    Some(match value {
+        serde_json::Value::Null => return None,
+        serde_json::Value::Bool(_) => FieldType::Boolean,
+        serde_json::Value::Number(n) if n.is_i64() || n.is_u64() => FieldType::Integer,
+        serde_json::Value::Number(_) => FieldType::Number,
+        serde_json::Value::String(_) => FieldType::String,
+        serde_json::Value::Array(_) => FieldType::Array,
+        serde_json::Value::Object(_) => FieldType::Object,
+    })
+}
+
+fn widen(current: FieldType, observed: FieldType) -> FieldType {
+    match (current, observed) {
+        (FieldType::Unknown, t) => t,
+        (a, b) if a == b => a,
+        (FieldType::Integer, FieldType::Number) | (FieldType::Number, FieldType::Integer) => FieldType::Number,
+        _ => FieldType::String,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn record(value: serde_json::Value) -> Record {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn field<'a>(schema: &'a [FieldDef], name: &str) -> &'a FieldDef {
+        schema.iter().find(|f| f.name == name).unwrap()
+    }
+
+    #[test]
+    fn widens_int_to_string_and_tracks_presence() {
+        let mut builder = SchemaBuilder::new();
+        builder.observe(&record(json!({ "id": 1, "code": 100, "score": 3 })));
+        assert_eq!(field(&builder.build(), "code").field_type, "integer");
+
+        builder.observe(&record(json!({ "id": 2, "code": "A-7", "score": 4.5, "note": null })));
+        builder.observe(&record(json!({ "id": 3, "code": 12, "score": null })));
+
+        let schema = builder.build();
+        assert_eq!(builder.record_count(), 3);
+        assert_eq!(field(&schema, "id").field_type, "integer");
+        assert_eq!(field(&schema, "code").field_type, "string");
+        assert_eq!(field(&schema, "score").field_type, "number");
+
+        assert_eq!(field(&schema, "id").required, Some(true));
+        assert_eq!(field(&schema, "score").constraints.as_ref().unwrap()["nullable"], json!(true));
+        assert_eq!(field(&schema, "code").constraints.as_ref().unwrap()["nullable"], json!(false));
+
+        let note = field(&schema, "note");
+        assert_eq!((note.field_type.as_str(), note.required), ("unknown", Some(false)));
+    }
+
+    #[test]
+    fn fields_keep_first_seen_order() {
+        let mut builder = SchemaBuilder::new();
+        builder.observe(&record(json!({ "title": "a", "id": 1 })));
+        builder.observe(&record(json!({ "body": "b", "id": 2, "author": "c" })));
+
+        let names: Vec<String> = builder.build().into_iter().map(|f| f.name).collect();
+        assert_eq!(names, ["id", "title", "author", "body"]);
+    }
+}
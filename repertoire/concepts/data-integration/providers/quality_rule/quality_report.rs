@@ -0,0 +1,305 @@
+// Quality Rule Batch Validation: Dataset Quality Report
+// Runs a set of quality rules over every record of a dataset and aggregates
+// pass/fail counts per dimension, per-field violations and an overall score.
+
+use std::collections::HashMap;
+
+/// A source record keyed by field name.
+pub type Record = HashMap<String, serde_json::Value>;
+
+#[derive(Debug, Clone)]
+pub struct FieldDef {
+    pub name: String,
+    pub field_type: String,
+    pub required: Option<bool>,
+    pub constraints: Option<HashMap<String, serde_json::Value>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RuleConfig {
+    pub options: Option<HashMap<String, serde_json::Value>>,
+    pub threshold: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Severity { Error, Warning, Info }
+
+#[derive(Debug, Clone)]
+pub struct RuleResult {
+    pub valid: bool,
+    pub message: Option<String>,
+    pub severity: Severity,
+}
+
+impl RuleResult {
+    /// A passing result.
+    pub fn ok() -> Self {
+        Self { valid: true, message: None, severity: Severity::Info }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QualityDimension {
+    Completeness, Uniqueness, Validity, Consistency, Timeliness, Accuracy,
+}
+
+/// Interface a quality rule provider exposes to the batch validator.
+pub trait QualityRulePlugin: Send + Sync {
+    fn validate(
+        &self,
+        value: &serde_json::Value,
+        field: &FieldDef,
+        record: &Record,
+        config: &RuleConfig,
+    ) -> RuleResult;
+
+    fn applies_to(&self, field: &FieldDef) -> bool;
+
+    fn dimension(&self) -> QualityDimension;
+}
+
+/// Implement `QualityRulePlugin` for a built-in provider. Each provider
+/// module declares its own copies of the rule types, so values are converted
+/// on the way in and out. Providers whose `validate` takes `&mut self` are
+/// adapted behind a `Mutex`.
+macro_rules! builtin_rule {
+    ($module:ident :: $provider:ident) => {
+        builtin_rule!(@impl $module, super::$module::$provider, |rule| rule);
+    };
+    (mut $module:ident :: $provider:ident) => {
+        builtin_rule!(@impl $module, std::sync::Mutex<super::$module::$provider>, |rule| &mut *rule.lock().unwrap());
+    };
+    (@impl $module:ident, $target:ty, |$rule:ident| $inner:expr) => {
+        impl QualityRulePlugin for $target {
+            fn validate(&self, value: &serde_json::Value, field: &FieldDef, record: &Record, config: &RuleConfig) -> RuleResult {
+                use super::$module as rule;
+                let config = rule::RuleConfig { options: config.options.clone(), threshold: config.threshold };
+                let $rule = self;
+                let result = $inner.validate(value, &builtin_rule!(@field $module, field), record, &config);
+                RuleResult {
+                    valid: result.valid,
+                    message: result.message,
+                    severity: match result.severity {
+                        rule::Severity::Error => Severity::Error,
+                        rule::Severity::Warning => Severity::Warning,
+                        rule::Severity::Info => Severity::Info,
+                    },
+                }
+            }
+
+            fn applies_to(&self, field: &FieldDef) -> bool {
+                let $rule = self;
+                $inner.applies_to(&builtin_rule!(@field $module, field))
+            }
+
+            fn dimension(&self) -> QualityDimension {
+                use super::$module::QualityDimension as D;
+                let $rule = self;
+                match $inner.dimension() {
+                    D::Completeness => QualityDimension::Completeness,
+                    D::Uniqueness => QualityDimension::Uniqueness,
+                    D::Validity => QualityDimension::Validity,
+                    D::Consistency => QualityDimension::Consistency,
+                    D::Timeliness => QualityDimension::Timeliness,
+                    D::Accuracy => QualityDimension::Accuracy,
+                }
+            }
+        }
+    };
+    (@field $module:ident, $field:expr) => {
+        super::$module::FieldDef {
+            name: $field.name.clone(),
+            field_type: $field.field_type.clone(),
+            required: $field.required,
+            constraints: $field.constraints.clone(),
+        }
+    };
+}
+
+builtin_rule!(checksum::ChecksumQualityProvider);
+builtin_rule!(cross_field::CrossFieldQualityProvider);
+builtin_rule!(enum_check::EnumCheckQualityProvider);
+builtin_rule!(foreign_key::ForeignKeyQualityProvider);
+builtin_rule!(freshness::FreshnessQualityProvider);
+builtin_rule!(mut no_duplicates::NoDuplicatesQualityProvider);
+builtin_rule!(outlier::OutlierQualityProvider);
+builtin_rule!(mut pattern::PatternQualityProvider);
+builtin_rule!(range::RangeQualityProvider);
+builtin_rule!(reconciliation::ReconciliationQualityProvider);
+builtin_rule!(required::RequiredQualityProvider);
+builtin_rule!(type_check::TypeCheckQualityProvider);
+builtin_rule!(mut unique::UniqueQualityProvider);
+
+/// Pass/fail counts of the rule checks for one quality dimension.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DimensionCounts {
+    pub passed: usize,
+    pub failed: usize,
+}
+
+/// A single failed rule check.
+#[derive(Debug, Clone)]
+pub struct Violation {
+    pub record_index: usize,
+    pub dimension: QualityDimension,
+    pub message: Option<String>,
+    pub severity: Severity,
+}
+
+#[derive(Debug, Clone)]
+pub struct QualityReport {
+    pub total_records: usize,
+    pub dimensions: HashMap<QualityDimension, DimensionCounts>,
+    /// Failed checks keyed by field name.
+    pub violations: HashMap<String, Vec<Violation>>,
+    /// Share of passed checks, 0–100. A dataset with no applicable checks scores 100.
+    pub score: f64,
+}
+
+/// Run every applicable rule against every record and aggregate the results.
+/// A rule applies to a field when its `applies_to` accepts the field definition;
+/// fields missing from a record are validated as null.
+pub async fn validate_dataset(
+    records: &[Record],
+    rules: &[(FieldDef, Box<dyn QualityRulePlugin>, RuleConfig)],
+) -> QualityReport {
+    let mut dimensions: HashMap<QualityDimension, DimensionCounts> = HashMap::new();
+    let mut violations: HashMap<String, Vec<Violation>> = HashMap::new();
+    let (mut passed, mut total) = (0usize, 0usize);
+
+    let applicable: Vec<_> = rules.iter()
+        .filter(|(field, rule, _)| rule.applies_to(field))
+        .collect();
+
+    for (index, record) in records.iter().enumerate() {
+        for (field, rule, config) in &applicable {
+            let value = record.get(&field.name).unwrap_or(&serde_json::Value::Null);
+            let result = rule.validate(value, field, record, config);
+            let counts = dimensions.entry(rule.dimension()).or_default();
+
+            total += 1;
+            if result.valid {
+                passed += 1;
+                counts.passed += 1;
+            } else {
+                counts.failed += 1;
+                violations.entry(field.name.clone()).or_default().push(Violation {
+                    record_index: index,
+                    dimension: rule.dimension(),
+                    message: result.message,
+                    severity: result.severity,
+                });
+            }
+        }
+    }
+
+    let score = if total == 0 { 100.0 } else { passed as f64 * 100.0 / total as f64 };
+
+    QualityReport {
+        total_records: records.len(),
+        dimensions,
+        violations,
+        score,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+    use serde_json::json;
+
+    struct NotEmpty;
+
+    impl QualityRulePlugin for NotEmpty {
+        fn validate(&self, value: &serde_json::Value, field: &FieldDef, _: &Record, _: &RuleConfig) -> RuleResult {
+            let valid = !value.is_null() && value.as_str() != Some("");
+            let message = (!valid).then(|| format!("Field '{}' is required.", field.name));
+            RuleResult { valid, message, severity: Severity::Error }
+        }
+        fn applies_to(&self, field: &FieldDef) -> bool { field.required.unwrap_or(false) }
+        fn dimension(&self) -> QualityDimension { QualityDimension::Completeness }
+    }
+
+    struct NonNegative;
+
+    impl QualityRulePlugin for NonNegative {
+        fn validate(&self, value: &serde_json::Value, field: &FieldDef, _: &Record, _: &RuleConfig) -> RuleResult {
+            let valid = value.as_f64().is_none_or(|n| n >= 0.0);
+            let message = (!valid).then(|| format!("Field '{}' must not be negative.", field.name));
+            RuleResult { valid, message, severity: Severity::Error }
+        }
+        fn applies_to(&self, field: &FieldDef) -> bool { field.field_type == "number" }
+        fn dimension(&self) -> QualityDimension { QualityDimension::Validity }
+    }
+
+    fn field(name: &str, field_type: &str, required: bool) -> FieldDef {
+        FieldDef { name: name.into(), field_type: field_type.into(), required: Some(required), constraints: None }
+    }
+
+    #[test]
+    fn aggregates_report_over_dataset() {
+        let config = RuleConfig { options: None, threshold: None };
+        let rules: Vec<(FieldDef, Box<dyn QualityRulePlugin>, RuleConfig)> = vec![
+            (field("email", "string", true), Box::new(NotEmpty), config.clone()),
+            (field("age", "number", false), Box::new(NonNegative), config.clone()),
+            // Not applicable: age is not a required field.
+            (field("age", "number", false), Box::new(NotEmpty), config),
+        ];
+
+        let records: Vec<Record> = [
+            json!({ "email": "a@example.com", "age": 31 }),
+            json!({ "email": "", "age": 22 }),
+            json!({ "email": "c@example.com", "age": -4 }),
+            json!({ "email": "d@example.com" }),
+            json!({ "age": -1 }),
+        ]
+        .into_iter()
+        .map(|r| r.as_object().unwrap().clone().into_iter().collect())
+        .collect();
+
+        let report = block_on(validate_dataset(&records, &rules));
+
+        assert_eq!(report.total_records, 5);
+        assert_eq!(report.dimensions[&QualityDimension::Completeness], DimensionCounts { passed: 3, failed: 2 });
+        assert_eq!(report.dimensions[&QualityDimension::Validity], DimensionCounts { passed: 3, failed: 2 });
+        assert_eq!(report.violations["email"].iter().map(|v| v.record_index).collect::<Vec<_>>(), vec![1, 4]);
+        assert_eq!(report.violations["age"].iter().map(|v| v.record_index).collect::<Vec<_>>(), vec![2, 4]);
+        assert_eq!(report.score, 60.0);
+    }
+
+    #[test]
+    fn validates_dataset_with_builtin_providers() {
+        let config = RuleConfig { options: None, threshold: None };
+        let range = RuleConfig {
+            options: Some([("min".to_string(), json!(0))].into_iter().collect()),
+            threshold: None,
+        };
+        let rules: Vec<(FieldDef, Box<dyn QualityRulePlugin>, RuleConfig)> = vec![
+            (field("email", "string", true), Box::new(super::super::required::RequiredQualityProvider::new()), config.clone()),
+            (field("age", "number", false), Box::new(super::super::range::RangeQualityProvider::new()), range),
+            (
+                FieldDef { constraints: Some([("unique".to_string(), json!(true))].into_iter().collect()), ..field("email", "string", true) },
+                Box::new(std::sync::Mutex::new(super::super::unique::UniqueQualityProvider::new())),
+                config,
+            ),
+        ];
+
+        let records: Vec<Record> = [
+            json!({ "email": "a@example.com", "age": 31 }),
+            json!({ "email": "a@example.com", "age": -4 }),
+            json!({ "age": 5 }),
+        ]
+        .into_iter()
+        .map(|r| r.as_object().unwrap().clone().into_iter().collect())
+        .collect();
+
+        let report = block_on(validate_dataset(&records, &rules));
+
+        assert_eq!(report.dimensions[&QualityDimension::Completeness], DimensionCounts { passed: 2, failed: 1 });
+        assert_eq!(report.dimensions[&QualityDimension::Validity], DimensionCounts { passed: 2, failed: 1 });
+        assert_eq!(report.dimensions[&QualityDimension::Uniqueness].failed, 1);
+        assert_eq!(report.violations["age"].iter().map(|v| v.record_index).collect::<Vec<_>>(), vec![1]);
+        assert_eq!(report.violations["email"][0].record_index, 1);
+    }
+}
@@ -0,0 +1,22 @@
+// Shared test fixtures for the quality rule providers.
+// Each provider declares its own FieldDef and RuleConfig, so the fixtures
+// are a macro expanded inside the provider's test module.
+
+#![cfg(test)]
+
+/// Define `field()`, a definition of the named field, and
+/// `config(options)`, a rule config built from a JSON options object.
+macro_rules! rule_fixtures {
+    ($name:expr, $field_type:expr) => {
+        fn field() -> FieldDef {
+            FieldDef { name: $name.into(), field_type: $field_type.into(), required: None, constraints: None }
+        }
+
+        fn config(options: serde_json::Value) -> RuleConfig {
+            let options = options.as_object().unwrap().clone().into_iter().collect();
+            RuleConfig { options: Some(options), threshold: None }
+        }
+    };
+}
+
+pub(crate) use rule_fixtures;
@@ -4,6 +4,8 @@
 
 use std::collections::{HashMap, HashSet};
 
+use super::no_duplicates::{levenshtein_distance, levenshtein_similarity};
+
 pub const PROVIDER_ID: &str = "unique";
 pub const PLUGIN_TYPE: &str = "quality_rule";
 
@@ -39,6 +41,8 @@ pub enum QualityDimension {
 pub struct UniqueQualityProvider {
     global_index: HashSet<String>,
     scoped_index: HashMap<String, HashSet<String>>,
+    /// Fuzzy mode: (normalized, original) values seen per field and scope.
+    fuzzy_index: HashMap<String, Vec<(String, String)>>,
 }
 
 impl UniqueQualityProvider {
@@ -46,6 +50,7 @@ impl UniqueQualityProvider {
         Self {
             global_index: HashSet::new(),
             scoped_index: HashMap::new(),
+            fuzzy_index: HashMap::new(),
         }
     }
 
@@ -56,7 +61,12 @@ impl UniqueQualityProvider {
         record: &HashMap<String, serde_json::Value>,
         config: &RuleConfig,
     ) -> RuleResult {
-        if value.is_null() {
+        let composite_fields: Option<Vec<&str>> = config.options.as_ref()
+            .and_then(|o| o.get("compositeFields"))
+            .and_then(|v| v.as_array())
+            .map(|fields| fields.iter().filter_map(|f| f.as_str()).collect());
+
+        if value.is_null() && composite_fields.is_none() {
             return RuleResult { valid: true, message: None, severity: Severity::Error };
         }
 
@@ -70,10 +80,41 @@ impl UniqueQualityProvider {
             .and_then(|v| v.as_str())
             .unwrap_or("global");
 
-        let raw_value = match value {
-            serde_json::Value::String(s) => s.clone(),
-            other => other.to_string(),
+        let (raw_value, subject) = match &composite_fields {
+            Some(fields) => (
+                Self::composite_key(fields, value, field, record),
+                format!("composite key ({})", fields.join(", ")),
+            ),
+            None => (
+                match value {
+                    serde_json::Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                },
+                "value".to_string(),
+            ),
         };
+
+        let record_type = record.get("_type")
+            .and_then(|v| v.as_str())
+            .unwrap_or("__default__")
+            .to_string();
+
+        let fuzzy = config.options.as_ref()
+            .and_then(|o| o.get("fuzzy"))
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        if fuzzy {
+            let scope_key = if scope == "per-type" {
+                format!("{}::{}", record_type, field.name)
+            } else {
+                field.name.clone()
+            };
+            let threshold = config.options.as_ref()
+                .and_then(|o| o.get("similarityThreshold"))
+                .and_then(|v| v.as_f64());
+            return self.validate_fuzzy(scope_key, raw_value, threshold, field);
+        }
+
         let normalized = if case_sensitive {
             raw_value
         } else {
@@ -81,11 +122,6 @@ impl UniqueQualityProvider {
         };
 
         if scope == "per-type" {
-            let record_type = record.get("_type")
-                .and_then(|v| v.as_str())
-                .unwrap_or("__default__")
-                .to_string();
-
             let type_index = self.scoped_index
                 .entry(record_type.clone())
                 .or_insert_with(HashSet::new);
@@ -95,8 +131,8 @@ impl UniqueQualityProvider {
                 return RuleResult {
                     valid: false,
                     message: Some(format!(
-                        "Field '{}' value is not unique within type '{}'.",
-                        field.name, record_type
+                        "Field '{}' {} is not unique within type '{}'.",
+                        field.name, subject, record_type
                     )),
                     severity: Severity::Error,
                 };
@@ -108,8 +144,8 @@ impl UniqueQualityProvider {
                 return RuleResult {
                     valid: false,
                     message: Some(format!(
-                        "Field '{}' value is not unique.",
-                        field.name
+                        "Field '{}' {} is not unique.",
+                        field.name, subject
                     )),
                     severity: Severity::Error,
                 };
@@ -120,6 +156,70 @@ impl UniqueQualityProvider {
         RuleResult { valid: true, message: None, severity: Severity::Error }
     }
 
+    /// Near-duplicate check: values are compared after lowercasing and
+    /// stripping punctuation and whitespace. With a `similarityThreshold`
+    /// (0–1, as `1 - distance / length`), values within edit distance of a
+    /// previously seen value are flagged as well.
+    fn validate_fuzzy(
+        &mut self,
+        scope_key: String,
+        raw_value: String,
+        threshold: Option<f64>,
+        field: &FieldDef,
+    ) -> RuleResult {
+        let normalized: String = raw_value.chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(char::to_lowercase)
+            .collect();
+
+        let seen = self.fuzzy_index.entry(scope_key).or_default();
+        for (prior_normalized, prior) in seen.iter() {
+            let distance = levenshtein_distance(&normalized, prior_normalized);
+            let similarity = levenshtein_similarity(&normalized, prior_normalized);
+
+            if distance == 0 || threshold.is_some_and(|t| similarity >= t) {
+                return RuleResult {
+                    valid: false,
+                    message: Some(format!(
+                        "Field '{}' value '{}' is a near-duplicate of '{}' (distance {}).",
+                        field.name, raw_value, prior, distance
+                    )),
+                    severity: Severity::Error,
+                };
+            }
+        }
+
+        seen.push((normalized, raw_value));
+        RuleResult { valid: true, message: None, severity: Severity::Error }
+    }
+
+    /// Serialize the composite fields of a record into one key, in the
+    /// configured order. Parts are joined with `|`, with `\` and `|` escaped
+    /// so distinct tuples never collide; null parts are written as `\N`.
+    fn composite_key(
+        fields: &[&str],
+        value: &serde_json::Value,
+        field: &FieldDef,
+        record: &HashMap<String, serde_json::Value>,
+    ) -> String {
+        fields.iter()
+            .map(|name| {
+                let part = match record.get(*name) {
+                    Some(v) => v,
+                    None if *name == field.name => value,
+                    None => &serde_json::Value::Null,
+                };
+                let text = match part {
+                    serde_json::Value::Null => return "\\N".to_string(),
+                    serde_json::Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                text.replace('\\', "\\\\").replace('|', "\\|")
+            })
+            .collect::<Vec<_>>()
+            .join("|")
+    }
+
     pub fn applies_to(&self, field: &FieldDef) -> bool {
         field.constraints.as_ref()
             .and_then(|c| c.get("unique"))
@@ -134,6 +234,7 @@ impl UniqueQualityProvider {
     pub fn reset(&mut self) {
         self.global_index.clear();
         self.scoped_index.clear();
+        self.fuzzy_index.clear();
     }
 }
 
@@ -142,3 +243,65 @@ impl Default for UniqueQualityProvider {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    super::super::test_support::rule_fixtures!("email", "string");
+
+    fn record(tenant: &str, email: &str) -> HashMap<String, serde_json::Value> {
+        [("tenant_id".to_string(), json!(tenant)), ("email".to_string(), json!(email))]
+            .into_iter()
+            .collect()
+    }
+
+    #[test]
+    fn composite_key_uniqueness() {
+        let mut provider = UniqueQualityProvider::new();
+        let config = config(json!({ "compositeFields": ["tenant_id", "email"], "caseSensitive": false }));
+
+        let a = record("acme", "ada@example.com");
+        let b = record("globex", "ada@example.com");
+        let dup = record("acme", "ADA@example.com");
+
+        assert!(provider.validate(&a["email"], &field(), &a, &config).valid);
+        assert!(provider.validate(&b["email"], &field(), &b, &config).valid);
+
+        let result = provider.validate(&dup["email"], &field(), &dup, &config);
+        assert!(!result.valid);
+        assert!(result.message.unwrap().contains("composite key (tenant_id, email)"));
+    }
+
+    #[test]
+    fn composite_key_escapes_separator() {
+        let mut provider = UniqueQualityProvider::new();
+        let config = config(json!({ "compositeFields": ["tenant_id", "email"] }));
+
+        let a = record("a|b", "c");
+        let b = record("a", "b|c");
+        assert!(provider.validate(&a["email"], &field(), &a, &config).valid);
+        assert!(provider.validate(&b["email"], &field(), &b, &config).valid);
+    }
+
+    #[test]
+    fn fuzzy_flags_near_duplicates() {
+        let mut provider = UniqueQualityProvider::new();
+        let name = FieldDef { name: "company".into(), field_type: "string".into(), required: None, constraints: None };
+        let config = config(json!({ "fuzzy": true, "similarityThreshold": 0.9 }));
+        let record = HashMap::new();
+
+        assert!(provider.validate(&json!("Acme, Inc."), &name, &record, &config).valid);
+        let punctuation = provider.validate(&json!("Acme Inc"), &name, &record, &config);
+        assert!(!punctuation.valid);
+        assert!(punctuation.message.unwrap().contains("near-duplicate of 'Acme, Inc.' (distance 0)"));
+
+        assert!(provider.validate(&json!("Jonathan Smith Ltd"), &name, &record, &config).valid);
+        let typo = provider.validate(&json!("Jonathon Smith Ltd"), &name, &record, &config);
+        assert!(!typo.valid);
+        assert!(typo.message.unwrap().contains("(distance 1)"));
+
+        assert!(provider.validate(&json!("Globex"), &name, &record, &config).valid);
+    }
+}